@@ -0,0 +1,52 @@
+//! Records the rustc version and git revision used to build this crate, and
+//! pre-generates man pages and shell completions so packagers can install
+//! them without executing the binary (the runtime `--generate-man-pages`
+//! and `completions` paths in `src/cli/mod.rs` stay around for on-demand
+//! regeneration and share the exact same generation code).
+
+fn main() {
+    let rustc_version = std::process::Command::new(std::env::var("RUSTC").unwrap_or_else(|_| "rustc".into()))
+        .arg("--version")
+        .output()
+        .ok()
+        .and_then(|out| String::from_utf8(out.stdout).ok())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    println!(
+        "cargo:rustc-env=FMM_RUSTC_VERSION={}",
+        rustc_version.trim()
+    );
+
+    let git_hash = std::process::Command::new("git")
+        .args(["rev-parse", "--short=10", "HEAD"])
+        .output()
+        .ok()
+        .filter(|out| out.status.success())
+        .and_then(|out| String::from_utf8(out.stdout).ok())
+        .map(|s| s.trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    println!("cargo:rustc-env=FMM_BUILD_GIT_HASH={}", git_hash);
+
+    let out_dir = std::env::var("OUT_DIR").expect("OUT_DIR set by cargo");
+    if let Err(e) = fmm::cli::generate_man_pages(std::path::Path::new(&out_dir)) {
+        println!("cargo:warning=failed to generate man pages: {}", e);
+    }
+
+    for (shell, suffix) in [
+        (clap_complete::Shell::Bash, "bash"),
+        (clap_complete::Shell::Zsh, "zsh"),
+        (clap_complete::Shell::Fish, "fish"),
+        (clap_complete::Shell::PowerShell, "ps1"),
+    ] {
+        let path = std::path::Path::new(&out_dir).join(format!("fmm.{suffix}"));
+        match std::fs::File::create(&path) {
+            Ok(mut file) => fmm::cli::generate_completions(shell, &mut file),
+            Err(e) => println!("cargo:warning=failed to write {}: {}", path.display(), e),
+        }
+    }
+
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src/cli/mod.rs");
+    println!("cargo:rerun-if-env-changed=FMM_BUILD_GIT_HASH");
+}
@@ -1,5 +1,5 @@
 use anyhow::{Context, Result};
-use clap::{Parser, Subcommand, ValueEnum};
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
 use clap_complete::Shell;
 use color_print::cstr;
 use colored::Colorize;
@@ -8,7 +8,13 @@ use rayon::prelude::*;
 use std::path::{Path, PathBuf};
 
 use crate::config::Config;
-use crate::extractor::{sidecar_path_for, FileProcessor};
+use crate::extractor::{
+    dependents_of, find_cycles, resolved_edges, reverse_edges, sidecar_path_for,
+    unresolved_imports, FileProcessor, FrontmatterCache, SourceManifest,
+};
+
+mod watch;
+pub use watch::watch;
 
 // -- Help text constants (keeps the derive attrs readable) --
 
@@ -54,15 +60,19 @@ const BEFORE_LONG_HELP: &str = cstr!(
   <bold>update</bold>        Regenerate all .fmm sidecars from source
   <bold>validate</bold>      Check sidecars are up to date (CI-friendly)
   <bold>clean</bold>         Remove all .fmm sidecar files
+  <bold>format</bold>        Reformat fenced code blocks embedded in sidecars
 
 <bold><underline>Setup</underline></bold>
   <bold>init</bold>          Initialize fmm in this project (config, skill, MCP)
   <bold>status</bold>        Show current fmm status and configuration
   <bold>completions</bold>   Generate shell completions (bash, zsh, fish, powershell)
+  <bold>tidy</bold>          Regenerate (or --check) CLI docs, man page, and completions
+  <bold>config</bold>        Show resolved fmm.toml defaults (or --schema for its JSON Schema)
 
 <bold><underline>Integration</underline></bold>
   <bold>mcp</bold>           Start MCP server for LLM tool integration
   <bold>gh</bold>            GitHub integrations (issue fixing, PR creation)
+  <bold>fetch-grammar</bold> Clone, build, and register a tree-sitter grammar at runtime
 
 <bold><underline>Analysis</underline></bold>
   <bold>search</bold>        Query sidecars by export, import, dependency, or LOC
@@ -70,6 +80,16 @@ const BEFORE_LONG_HELP: &str = cstr!(
 "#
 );
 
+/// Crate version plus the short git revision it was built from (captured by
+/// `build.rs` via `git rev-parse --short=10 HEAD`), so bug reports identify
+/// the exact build — see [`status`] for the same hash surfaced in `fmm status`.
+const VERSION: &str = concat!(
+    env!("CARGO_PKG_VERSION"),
+    " (",
+    env!("FMM_BUILD_GIT_HASH"),
+    ")"
+);
+
 #[derive(Parser)]
 #[command(
     name = "fmm",
@@ -77,7 +97,7 @@ const BEFORE_LONG_HELP: &str = cstr!(
     long_about = LONG_ABOUT,
     before_long_help = BEFORE_LONG_HELP,
     after_long_help = AFTER_LONG_HELP,
-    version,
+    version = VERSION,
 )]
 pub struct Cli {
     /// Print CLI reference as Markdown and exit
@@ -110,7 +130,13 @@ pub enum Commands {
     Generate sidecars for a specific directory
 
   <dim>$</dim> <bold>fmm generate -n</bold>
-    Dry run — show what would be created without writing files"#),
+    Dry run — show what would be created without writing files
+
+  <dim>$</dim> <bold>fmm generate --changed</bold>
+    Only process files changed since HEAD (working tree + untracked)
+
+  <dim>$</dim> <bold>fmm generate --changed main</bold>
+    Only process files changed since the 'main' branch"#),
     )]
     Generate {
         /// Path to file or directory
@@ -120,6 +146,28 @@ pub enum Commands {
         /// Show what would be created without writing files
         #[arg(short = 'n', long)]
         dry_run: bool,
+
+        /// Only process files changed since REF (default: HEAD). Combines
+        /// git's tracked-diff and untracked-file lists with the usual
+        /// .gitignore-aware walk.
+        #[arg(
+            short = 'c',
+            long,
+            num_args = 0..=1,
+            default_missing_value = "HEAD"
+        )]
+        changed: Option<String>,
+
+        /// Only process files matching this glob (relative to the resolved
+        /// root); repeatable. An empty set means "all supported files", as
+        /// today.
+        #[arg(long)]
+        include: Vec<String>,
+
+        /// Skip files matching this glob (relative to the resolved root);
+        /// repeatable. Wins over --include when a file matches both.
+        #[arg(long)]
+        exclude: Vec<String>,
     },
 
     /// Regenerate all .fmm sidecars from source
@@ -134,7 +182,10 @@ pub enum Commands {
     Refresh all sidecars in the current directory
 
   <dim>$</dim> <bold>fmm update src/ -n</bold>
-    Preview which sidecars would change"#),
+    Preview which sidecars would change
+
+  <dim>$</dim> <bold>fmm update --changed</bold>
+    Only refresh sidecars for files changed since HEAD"#),
     )]
     Update {
         /// Path to file or directory
@@ -144,6 +195,33 @@ pub enum Commands {
         /// Show what would be changed without writing files
         #[arg(short = 'n', long)]
         dry_run: bool,
+
+        /// Only process files changed since REF (default: HEAD). Combines
+        /// git's tracked-diff and untracked-file lists with the usual
+        /// .gitignore-aware walk.
+        #[arg(
+            short = 'c',
+            long,
+            num_args = 0..=1,
+            default_missing_value = "HEAD"
+        )]
+        changed: Option<String>,
+
+        /// Only process files matching this glob (relative to the resolved
+        /// root); repeatable. An empty set means "all supported files", as
+        /// today.
+        #[arg(long)]
+        include: Vec<String>,
+
+        /// Skip files matching this glob (relative to the resolved root);
+        /// repeatable. Wins over --include when a file matches both.
+        #[arg(long)]
+        exclude: Vec<String>,
+
+        /// Ignore the persisted source manifest and re-parse every file,
+        /// even ones whose content hasn't changed since the last run
+        #[arg(long)]
+        no_cache: bool,
     },
 
     /// Check sidecars are up to date (CI-friendly)
@@ -158,12 +236,52 @@ pub enum Commands {
     Check all sidecars in the current directory
 
   <dim>$</dim> <bold>fmm validate src/</bold>
-    Check a specific directory"#),
+    Check a specific directory
+
+  <dim>$</dim> <bold>fmm validate --changed main</bold>
+    Only check files changed since the 'main' branch
+
+  <dim>$</dim> <bold>fmm validate --dependents src/db.ts</bold>
+    List every file that transitively depends on src/db.ts"#),
     )]
     Validate {
         /// Path to file or directory
         #[arg(default_value = ".")]
         path: String,
+
+        /// Only check files changed since REF (default: HEAD). Combines
+        /// git's tracked-diff and untracked-file lists with the usual
+        /// .gitignore-aware walk.
+        #[arg(
+            short = 'c',
+            long,
+            num_args = 0..=1,
+            default_missing_value = "HEAD"
+        )]
+        changed: Option<String>,
+
+        /// Only check files matching this glob (relative to the resolved
+        /// root); repeatable. An empty set means "all supported files", as
+        /// today.
+        #[arg(long)]
+        include: Vec<String>,
+
+        /// Skip files matching this glob (relative to the resolved root);
+        /// repeatable. Wins over --include when a file matches both.
+        #[arg(long)]
+        exclude: Vec<String>,
+
+        /// Ignore the persisted source manifest and re-parse every file,
+        /// even ones whose content hasn't changed since the last run
+        #[arg(long)]
+        no_cache: bool,
+
+        /// After validating, also report every file (transitively) that
+        /// depends on this path, by walking the resolved dependency graph's
+        /// reverse edges — e.g. to see what else to double-check after
+        /// editing a widely-imported module.
+        #[arg(long)]
+        dependents: Option<String>,
     },
 
     /// Remove all .fmm sidecar files
@@ -187,6 +305,78 @@ pub enum Commands {
         /// Show what would be removed without deleting files
         #[arg(short = 'n', long)]
         dry_run: bool,
+
+        /// Only consider files matching this glob (relative to the resolved
+        /// root); repeatable. An empty set means "all supported files", as
+        /// today.
+        #[arg(long)]
+        include: Vec<String>,
+
+        /// Skip files matching this glob (relative to the resolved root);
+        /// repeatable. Wins over --include when a file matches both.
+        #[arg(long)]
+        exclude: Vec<String>,
+    },
+
+    /// Watch for file changes and incrementally regenerate sidecars
+    #[command(
+        long_about = "Watch the source tree and incrementally regenerate .fmm sidecars as files \
+            change.\n\nDebounces rapid edits, skips files whose content didn't actually change, \
+            cascades to every file that depends on the one that changed, and honors \
+            .gitignore/.fmmignore the same way 'generate' does. Runs until interrupted — useful \
+            as an editor-side daemon so sidecars never drift while you work.",
+        after_long_help = cstr!(
+            r#"<bold><underline>Examples</underline></bold>
+
+  <dim>$</dim> <bold>fmm watch</bold>
+    Watch the current directory and keep sidecars in sync
+
+  <dim>$</dim> <bold>fmm watch src/</bold>
+    Watch a specific directory
+
+  <dim>$</dim> <bold>fmm watch --debounce 500</bold>
+    Wait 500ms after the last event in a burst before regenerating"#),
+    )]
+    Watch {
+        /// Path to file or directory
+        #[arg(default_value = ".")]
+        path: String,
+
+        /// Milliseconds to wait after the last event in a burst before
+        /// regenerating, so a save-triggered flurry of events collapses into
+        /// one pass
+        #[arg(long, default_value_t = 300)]
+        debounce: u64,
+    },
+
+    /// Reformat fenced code blocks embedded in sidecars
+    #[command(
+        long_about = "Scan sidecar files for fenced code blocks and reformat them in place \
+            with an external formatter, configured per language in `.fmmrc.json` \
+            (e.g. `\"formatters\": {\"rust\": \"rustfmt\", \"json\": \"prettier --parser json\"}`). \
+            A language with no configured formatter is left untouched, as is any block \
+            whose formatter command exits non-zero (its stderr is reported).",
+        after_long_help = cstr!(
+            r#"<bold><underline>Examples</underline></bold>
+
+  <dim>$</dim> <bold>fmm format</bold>
+    Reformat embedded code blocks in all sidecars
+
+  <dim>$</dim> <bold>fmm format --check</bold>
+    Exit non-zero and list sidecars with unformatted blocks, without writing"#),
+    )]
+    Format {
+        /// Path to file or directory
+        #[arg(default_value = ".")]
+        path: String,
+
+        /// Report drift without writing; exits non-zero if anything is stale
+        #[arg(long)]
+        check: bool,
+
+        /// Show what would change without writing
+        #[arg(short = 'n', long)]
+        dry_run: bool,
     },
 
     /// Initialize fmm in this project (config, skill, MCP)
@@ -205,7 +395,10 @@ pub enum Commands {
     Install only the Claude Code navigation skill
 
   <dim>$</dim> <bold>fmm init --mcp</bold>
-    Install only the MCP server configuration"#),
+    Install only the MCP server configuration
+
+  <dim>$</dim> <bold>fmm init --hooks</bold>
+    Install a git pre-commit hook that keeps staged sidecars in sync"#),
     )]
     Init {
         /// Install Claude Code skill only (.claude/skills/fmm-navigate.md)
@@ -216,6 +409,10 @@ pub enum Commands {
         #[arg(long)]
         mcp: bool,
 
+        /// Install a git pre-commit hook only (regenerates sidecars for staged files)
+        #[arg(long)]
+        hooks: bool,
+
         /// Install all integrations (non-interactive)
         #[arg(long)]
         all: bool,
@@ -223,6 +420,10 @@ pub enum Commands {
         /// Skip auto-generating sidecars (config files only)
         #[arg(long)]
         no_generate: bool,
+
+        /// Show what the pre-commit hook would write without installing it
+        #[arg(short = 'n', long)]
+        dry_run: bool,
     },
 
     /// Show current fmm status and configuration
@@ -251,9 +452,20 @@ pub enum Commands {
     Find files over 500 lines
 
   <dim>$</dim> <bold>fmm search --depends-on src/utils.ts --json</bold>
-    Find dependents of a file, output as JSON"#),
+    Find dependents of a file, output as JSON
+
+  <dim>$</dim> <bold>fmm search big</bold>
+    Run the "big" saved query from .fmmrc.json's `search_aliases`
+
+  <dim>$</dim> <bold>fmm search --imports react --pick</bold>
+    Narrow multiple matches down to one file interactively"#),
     )]
     Search {
+        /// Name of a saved query from `.fmmrc.json`'s `search_aliases`
+        /// (e.g. `fmm search big` for `{"search_aliases": {"big": "--loc >500"}}`).
+        /// Any flag passed explicitly alongside it wins over the alias.
+        query: Option<String>,
+
         /// Find file by export name (O(1) reverse-index lookup)
         #[arg(short = 'e', long = "export")]
         export: Option<String>,
@@ -279,6 +491,51 @@ pub enum Commands {
         /// Output as JSON
         #[arg(short = 'j', long = "json")]
         json: bool,
+
+        /// Interactively narrow multiple matches down to one
+        #[arg(short = 'p', long)]
+        pick: bool,
+    },
+
+    /// Report unresolved relative imports and dependency cycles
+    #[command(
+        long_about = "Resolve every sidecar's relative `dependencies` entries against the \
+            manifest and report two things a raw import string can't tell you on its own: \
+            specs that don't point at any file fmm knows about, and cycles in the resolved \
+            dependency graph.",
+        after_long_help = cstr!(
+            r#"<bold><underline>Examples</underline></bold>
+
+  <dim>$</dim> <bold>fmm check</bold>
+    Report unresolved imports and cycles across the whole project
+
+  <dim>$</dim> <bold>fmm check --json</bold>
+    Same, machine-readable"#),
+    )]
+    Check {
+        /// Output as JSON
+        #[arg(short = 'j', long = "json")]
+        json: bool,
+    },
+
+    /// Print the resolved cross-file dependency graph
+    #[command(
+        long_about = "Print the project's resolved import dependency graph: for each file, \
+            the other files it depends on. Bare package specs and specs that don't resolve \
+            to a project file are omitted — see 'fmm check' for those.",
+        after_long_help = cstr!(
+            r#"<bold><underline>Examples</underline></bold>
+
+  <dim>$</dim> <bold>fmm graph</bold>
+    Print every file's resolved dependencies
+
+  <dim>$</dim> <bold>fmm graph --json</bold>
+    Same, machine-readable"#),
+    )]
+    Graph {
+        /// Output as JSON
+        #[arg(short = 'j', long = "json")]
+        json: bool,
     },
 
     /// Start MCP server for LLM tool integration
@@ -294,6 +551,18 @@ pub enum Commands {
     #[command(hide = true)]
     Serve,
 
+    /// Start an LSP server over the same sidecar index as the MCP server
+    #[command(
+        long_about = "Start a Language Server Protocol server over stdio, backed by the same \
+            sidecar index as 'fmm mcp'.\n\n\
+            Maps workspace/symbol onto the export index, textDocument/documentSymbol onto a \
+            file's outline, textDocument/definition onto an export lookup resolved through \
+            dependency edges, and textDocument/references onto downstream dependents. Point an \
+            editor's LSP client at 'fmm lsp' to browse a codebase's structure without a \
+            language-specific server."
+    )]
+    Lsp,
+
     /// Generate shell completions for bash, zsh, fish, or powershell
     #[command(
         long_about = "Generate shell completion scripts for fmm.\n\n\
@@ -309,7 +578,42 @@ pub enum Commands {
     )]
     Completions {
         /// Target shell
-        shell: Shell,
+        #[arg(required_unless_present = "complete_exports")]
+        shell: Option<Shell>,
+
+        /// Print every indexed export name, one per line. Hidden helper
+        /// the generated zsh/fish scripts shell out to so `--export`
+        /// completes against the actual codebase instead of nothing.
+        #[arg(long, hide = true)]
+        complete_exports: bool,
+    },
+
+    /// Regenerate the CLI reference, man page, and shell completions
+    #[command(
+        long_about = "Regenerate docs/cli.md, man/fmm.1, and completions/fmm.* from the \
+            current Cli definition.\n\n\
+            With --check, nothing is written: any file that would change is reported with \
+            a diff and the command exits non-zero, so CI can catch docs/completions that \
+            drifted from the source of truth."
+    )]
+    Tidy {
+        /// Report drift without writing; exits non-zero if anything is stale
+        #[arg(long)]
+        check: bool,
+    },
+
+    /// Inspect or scaffold the `fmm.toml` project configuration
+    #[command(
+        long_about = "Work with the layered `fmm.toml` config that supplies defaults for \
+            `fmm gh issue` and `fmm compare` (model, max budget, branch prefix, output \
+            format) — CLI flags always override it.\n\n\
+            With --schema, prints the config's JSON Schema instead, for editor \
+            validation/autocompletion."
+    )]
+    Config {
+        /// Print the fmm.toml JSON Schema instead of the resolved config
+        #[arg(long)]
+        schema: bool,
     },
 
     /// GitHub integrations (issue fixing, PR creation)
@@ -340,7 +644,10 @@ pub enum Commands {
     Quick mode with fewer tasks
 
   <dim>$</dim> <bold>fmm compare https://github.com/owner/repo --format json -o results/</bold>
-    JSON output to a specific directory"#),
+    JSON output to a specific directory
+
+  <dim>$</dim> <bold>fmm compare https://github.com/owner/repo --container</bold>
+    Clone and generate inside a throwaway container (requires docker/podman)"#),
     )]
     Compare {
         /// GitHub repository URL (e.g., https://github.com/owner/repo)
@@ -362,29 +669,138 @@ pub enum Commands {
         #[arg(long, default_value = "1")]
         runs: u32,
 
+        /// Maximum number of control/FMM runs to execute concurrently
+        #[arg(long, default_value = "4")]
+        concurrency: usize,
+
+        /// Token budget for the FMM manifest context injected into the FMM
+        /// variant's prompt. A manifest estimated over this is replaced
+        /// with a reduced view (see --context-ranking) rather than inlined
+        /// whole.
+        #[arg(long, default_value = "8000")]
+        context_token_budget: usize,
+
+        /// How a reduced context view picks which files to drop once the
+        /// manifest exceeds --context-token-budget
+        #[arg(long, value_enum, default_value = "in-degree")]
+        context_ranking: ContextRanking,
+
         /// Output directory for results
         #[arg(short, long)]
         output: Option<PathBuf>,
 
-        /// Output format
-        #[arg(long, value_enum, default_value = "both")]
-        format: OutputFormat,
+        /// Output format (default: "both", or fmm.toml's compare.format).
+        /// "junit" and "jsonl" are CI-consumable machine reports rather
+        /// than the human-facing json/markdown/both outputs, and are only
+        /// written when explicitly requested.
+        #[arg(long, value_enum)]
+        format: Option<OutputFormat>,
+
+        /// Minimum tool-call reduction percentage a task must show to pass
+        /// the --format junit/jsonl CI gate (e.g. 10 for 10%). Tasks below
+        /// this, or whose FMM variant crashed, are reported as failed.
+        #[arg(long, default_value = "0.0")]
+        min_reduction: f64,
 
-        /// Maximum budget in USD
-        #[arg(long, default_value = "10.0")]
-        max_budget: f64,
+        /// Maximum budget in USD (default: 10.0, or fmm.toml's compare.max_budget)
+        #[arg(long)]
+        max_budget: Option<f64>,
 
         /// Skip cache (always re-run tasks)
         #[arg(long)]
         no_cache: bool,
 
+        /// Bypass cache reads and always rerun tasks, but still refresh the
+        /// cache with the new result (unlike --no-cache, which disables
+        /// caching entirely)
+        #[arg(long)]
+        force_refresh: bool,
+
+        /// Diff the cloned commit against the last commit this repo was
+        /// benchmarked at, and reuse a cached result for any task whose
+        /// recorded files touched didn't change, instead of recomputing
+        /// every task just because the commit moved
+        #[arg(long)]
+        incremental: bool,
+
         /// Quick mode (fewer tasks, faster results)
         #[arg(long)]
         quick: bool,
 
+        /// Also run a "rag" baseline variant: a naive gitignore-aware crawl
+        /// of the repo's source files, concatenated up to
+        /// --context-token-budget, injected in place of the FMM manifest.
+        /// Off by default since it roughly adds a third of the cost/time to
+        /// every run.
+        #[arg(long)]
+        rag: bool,
+
         /// Model to use
         #[arg(long, default_value = "sonnet")]
         model: String,
+
+        /// Agent backend to benchmark against (claude, aichat)
+        #[arg(long, default_value = "claude")]
+        backend: String,
+
+        /// Depth of the sandbox's initial clone (--single-branch). Falls
+        /// back to a full clone automatically if the server rejects the
+        /// shallow fetch.
+        #[arg(long, default_value = "1")]
+        depth: u32,
+
+        /// Clone full history instead of the shallow --depth clone
+        #[arg(long)]
+        full_clone: bool,
+
+        /// Run the clone + `fmm generate` step inside a throwaway
+        /// Docker/Podman container instead of on the host, so an untrusted
+        /// repo's git fetch and parse never touch the host's filesystem or
+        /// network directly. Requires docker or podman on PATH.
+        #[arg(long)]
+        container: bool,
+    },
+
+    /// Clone, build, and register a tree-sitter grammar at runtime
+    #[command(
+        long_about = "Clone a tree-sitter grammar's repo, generate its parser sources, \
+            compile the scanner into a shared library under ~/.fmm/grammars/, and \
+            register it in languages.toml — so fmm can parse a language with no \
+            builtin parser without a new fmm release.\n\n\
+            Without --url, the grammar name is looked up in a small table of \
+            known grammars (kotlin, swift, scala, elixir, zig, lua); anything else \
+            needs an explicit --url.",
+        after_long_help = cstr!(
+            r#"<bold><underline>Examples</underline></bold>
+
+  <dim>$</dim> <bold>fmm fetch-grammar kotlin</bold>
+    Fetch and register the known Kotlin grammar
+
+  <dim>$</dim> <bold>fmm fetch-grammar ocaml --url https://github.com/tree-sitter/tree-sitter-ocaml --ref v0.23.0</bold>
+    Fetch an arbitrary grammar repo at a specific tag
+
+  <dim>$</dim> <bold>fmm fetch-grammar kotlin --extensions kt --extensions kts</bold>
+    Register the grammar for specific file extensions"#),
+    )]
+    FetchGrammar {
+        /// Grammar name (also the registered language_id)
+        name: String,
+
+        /// Git URL to clone, overriding the built-in name lookup
+        #[arg(long)]
+        url: Option<String>,
+
+        /// Git ref (tag, branch, or commit) to check out after cloning
+        #[arg(long = "ref")]
+        git_ref: Option<String>,
+
+        /// File extensions to register this grammar for (default: the grammar name)
+        #[arg(long)]
+        extensions: Vec<String>,
+
+        /// Path to the languages.toml manifest to update
+        #[arg(long, default_value = "languages.toml")]
+        manifest: PathBuf,
     },
 }
 
@@ -394,6 +810,22 @@ pub enum OutputFormat {
     Json,
     Markdown,
     Both,
+    /// JUnit XML, one testcase per task — for CI test-report steps.
+    Junit,
+    /// One JSON object per task, newline-delimited.
+    Jsonl,
+}
+
+/// Which files a reduced FMM context view drops first when the manifest
+/// exceeds `--context-token-budget`.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ContextRanking {
+    /// Drop the least-depended-on files first (fewest other files import
+    /// them).
+    InDegree,
+    /// Drop files in manifest order, ignoring the dependency graph — a
+    /// baseline for measuring how much in-degree ranking actually helps.
+    Arbitrary,
 }
 
 /// GitHub subcommands
@@ -421,25 +853,25 @@ pub enum GhSubcommand {
         /// GitHub issue URL (e.g., https://github.com/owner/repo/issues/123)
         url: String,
 
-        /// Claude model to use
-        #[arg(long, default_value = "sonnet")]
-        model: String,
+        /// Claude model to use (default: "sonnet", or fmm.toml's gh.model)
+        #[arg(long)]
+        model: Option<String>,
 
-        /// Maximum turns for Claude
-        #[arg(long, default_value = "30")]
-        max_turns: u32,
+        /// Maximum turns for Claude (default: 30, or fmm.toml's gh.max_turns)
+        #[arg(long)]
+        max_turns: Option<u32>,
 
-        /// Maximum budget in USD
-        #[arg(long, default_value = "5.0")]
-        max_budget: f64,
+        /// Maximum budget in USD (default: 5.0, or fmm.toml's gh.max_budget)
+        #[arg(long)]
+        max_budget: Option<f64>,
 
         /// Show plan without executing (extract refs + assembled prompt)
         #[arg(short = 'n', long)]
         dry_run: bool,
 
-        /// Git branch prefix
-        #[arg(long, default_value = "fmm")]
-        branch_prefix: String,
+        /// Git branch prefix (default: "fmm", or fmm.toml's gh.branch_prefix)
+        #[arg(long)]
+        branch_prefix: Option<String>,
 
         /// Commit and push only, skip PR creation
         #[arg(long)]
@@ -448,9 +880,108 @@ pub enum GhSubcommand {
         /// Override workspace directory
         #[arg(long)]
         workspace: Option<String>,
+
+        /// Run a compare benchmark on the fix before opening the PR
+        #[arg(long)]
+        compare: bool,
+
+        /// Number of control/fmm trials to run when --compare is set, so
+        /// the savings verdict carries mean ± stddev and a significance
+        /// test instead of a single noisy run. 1 (the default) skips
+        /// significance testing entirely.
+        #[arg(long, default_value = "1")]
+        runs: u32,
+
+        /// With --compare, diff this run's savings against a baseline saved
+        /// earlier via --save-baseline (requires --output)
+        #[arg(long)]
+        baseline: Option<String>,
+
+        /// With --compare, save this run's report as a named baseline under
+        /// --output for a future run to diff against via --baseline
+        #[arg(long)]
+        save_baseline: Option<String>,
+
+        /// Percentage points the cost reduction may drop against --baseline
+        /// before the run fails (e.g. 5 tolerates a 5pp regression). Only
+        /// consulted when --baseline is set.
+        #[arg(long, default_value = "0.0")]
+        max_regression: f64,
+
+        /// Write the assembled prompt/plan to this path instead of stdout
+        #[arg(long)]
+        output: Option<String>,
+
+        /// Open the assembled prompt in $EDITOR before invoking Claude
+        #[arg(long)]
+        edit: bool,
+
+        /// Named preset from the global config's `[profiles.<name>]` to fill
+        /// in model/max-turns/max-budget/branch-prefix/allowed-tools that
+        /// weren't passed explicitly (see `GlobalConfig::profiles`)
+        #[arg(long)]
+        profile: Option<String>,
+
+        /// Depth of the workspace's initial clone (--single-branch). Falls
+        /// back to a full clone automatically if the server rejects the
+        /// shallow fetch.
+        #[arg(long, default_value = "1")]
+        depth: u32,
+
+        /// Clone full history instead of the shallow --depth clone
+        #[arg(long)]
+        full_clone: bool,
     },
 }
 
+/// Expand a user-defined alias (`Config::aliases`) appearing as `argv[1]`
+/// into its configured tokens, splicing them into `args` in place — e.g.
+/// `fmm gen` with `{"gen": "generate src/ -n"}` becomes `fmm generate src/
+/// -n`. Only the first positional token is considered, matching how clap
+/// picks the subcommand; a token that's already a known subcommand (or
+/// doesn't match any alias) passes through untouched. Any flags already on
+/// the command line are kept after the spliced tokens, so they win over
+/// whatever the alias supplies (clap takes the last occurrence of a
+/// non-repeatable flag). Chases an alias that expands to another alias, and
+/// errors out if that chase revisits an alias it's already expanded
+/// (self-referential or mutually recursive), rather than looping forever.
+pub fn expand_aliases(mut args: Vec<String>, config: &Config) -> Result<Vec<String>> {
+    if config.aliases.is_empty() || args.len() < 2 {
+        return Ok(args);
+    }
+
+    let known_subcommands: std::collections::HashSet<String> = Cli::command()
+        .get_subcommands()
+        .map(|s| s.get_name().to_string())
+        .collect();
+
+    let mut expanded = std::collections::HashSet::new();
+    loop {
+        let candidate = args[1].clone();
+        if known_subcommands.contains(&candidate) {
+            return Ok(args);
+        }
+        let Some(value) = config.aliases.get(&candidate) else {
+            return Ok(args);
+        };
+        if !expanded.insert(candidate.clone()) {
+            anyhow::bail!(
+                "alias `{candidate}` is recursive — aliases cannot reference themselves, \
+                 directly or through another alias"
+            );
+        }
+
+        let tokens = value.tokens();
+        if tokens.is_empty() {
+            anyhow::bail!("alias `{candidate}` expands to no tokens");
+        }
+        let rest = args.split_off(2);
+        args.truncate(1);
+        args.extend(tokens);
+        args.extend(rest);
+    }
+}
+
 /// Resolve the root directory from the target path.
 /// If a directory, use it directly. If a file, use its parent.
 /// Falls back to CWD if the path doesn't exist.
@@ -470,10 +1001,27 @@ fn resolve_root(path: &str) -> Result<PathBuf> {
     }
 }
 
-pub fn generate(path: &str, dry_run: bool) -> Result<()> {
+/// Persisted `fmm generate` extraction cache, kept at the root passed in
+/// (next to `.fmm-cache.json`, the manifest's own cache), so a repeated
+/// `--dry-run` sweep over a tree that still lacks frontmatter reuses
+/// parses instead of redoing them from scratch.
+const FRONTMATTER_CACHE_FILE: &str = ".fmm-frontmatter-cache";
+
+/// Persisted [`SourceManifest`], kept at the root passed in, so `fmm update`
+/// and `fmm validate` can skip a file whose source hasn't changed since the
+/// last pass instead of re-parsing it.
+const SOURCE_MANIFEST_FILE: &str = ".fmm-source-manifest";
+
+pub fn generate(
+    path: &str,
+    dry_run: bool,
+    changed: Option<&str>,
+    include: &[String],
+    exclude: &[String],
+) -> Result<()> {
     let config = Config::load().unwrap_or_default();
-    let files = collect_files(path, &config)?;
     let root = resolve_root(path)?;
+    let files = collect_files_filtered(path, &config, &root, changed, include, exclude)?;
 
     if files.is_empty() {
         println!("{} No supported source files found", "!".yellow());
@@ -496,11 +1044,14 @@ pub fn generate(path: &str, dry_run: bool) -> Result<()> {
 
     println!("Found {} files to process", files.len());
 
+    let cache_path = root.join(FRONTMATTER_CACHE_FILE);
+    let cache = std::sync::RwLock::new(FrontmatterCache::load(&cache_path));
+
     let results: Vec<_> = files
         .par_iter()
         .filter_map(|file| {
             let processor = FileProcessor::new(&config, &root);
-            match processor.generate(file, dry_run) {
+            match processor.generate_cached(file, dry_run, &cache) {
                 Ok(Some(msg)) => Some((file.to_path_buf(), msg)),
                 Ok(None) => None,
                 Err(e) => {
@@ -517,6 +1068,12 @@ pub fn generate(path: &str, dry_run: bool) -> Result<()> {
         })
         .collect();
 
+    cache
+        .into_inner()
+        .unwrap()
+        .save(&cache_path)
+        .context("Failed to persist frontmatter extraction cache")?;
+
     for (file, msg) in &results {
         let sidecar = sidecar_path_for(file);
         let display = sidecar.strip_prefix(&root).unwrap_or(&sidecar).display();
@@ -550,10 +1107,17 @@ pub fn generate(path: &str, dry_run: bool) -> Result<()> {
     Ok(())
 }
 
-pub fn update(path: &str, dry_run: bool) -> Result<()> {
+pub fn update(
+    path: &str,
+    dry_run: bool,
+    changed: Option<&str>,
+    include: &[String],
+    exclude: &[String],
+    no_cache: bool,
+) -> Result<()> {
     let config = Config::load().unwrap_or_default();
-    let files = collect_files(path, &config)?;
     let root = resolve_root(path)?;
+    let files = collect_files_filtered(path, &config, &root, changed, include, exclude)?;
 
     if files.is_empty() {
         println!("{} No supported source files found", "!".yellow());
@@ -566,11 +1130,18 @@ pub fn update(path: &str, dry_run: bool) -> Result<()> {
 
     println!("Found {} files to process", files.len());
 
+    let manifest_path = root.join(SOURCE_MANIFEST_FILE);
+    let manifest = std::sync::RwLock::new(if no_cache {
+        SourceManifest::default()
+    } else {
+        SourceManifest::load(&manifest_path)
+    });
+
     let results: Vec<_> = files
         .par_iter()
         .filter_map(|file| {
             let processor = FileProcessor::new(&config, &root);
-            match processor.update(file, dry_run) {
+            match processor.update_cached(file, dry_run, &manifest) {
                 Ok(Some(msg)) => Some((file.to_path_buf(), msg)),
                 Ok(None) => None,
                 Err(e) => {
@@ -587,6 +1158,14 @@ pub fn update(path: &str, dry_run: bool) -> Result<()> {
         })
         .collect();
 
+    if !dry_run {
+        manifest
+            .into_inner()
+            .unwrap()
+            .save(&manifest_path)
+            .context("Failed to persist source manifest")?;
+    }
+
     for (file, msg) in &results {
         let sidecar = sidecar_path_for(file);
         let display = sidecar.strip_prefix(&root).unwrap_or(&sidecar).display();
@@ -620,10 +1199,17 @@ pub fn update(path: &str, dry_run: bool) -> Result<()> {
     Ok(())
 }
 
-pub fn validate(path: &str) -> Result<()> {
+pub fn validate(
+    path: &str,
+    changed: Option<&str>,
+    include: &[String],
+    exclude: &[String],
+    no_cache: bool,
+    dependents: Option<&str>,
+) -> Result<()> {
     let config = Config::load().unwrap_or_default();
-    let files = collect_files(path, &config)?;
     let root = resolve_root(path)?;
+    let files = collect_files_filtered(path, &config, &root, changed, include, exclude)?;
 
     if files.is_empty() {
         println!("{} No supported source files found", "!".yellow());
@@ -636,11 +1222,18 @@ pub fn validate(path: &str) -> Result<()> {
 
     println!("Validating {} files...", files.len());
 
+    let manifest_path = root.join(SOURCE_MANIFEST_FILE);
+    let manifest = std::sync::RwLock::new(if no_cache {
+        SourceManifest::default()
+    } else {
+        SourceManifest::load(&manifest_path)
+    });
+
     let invalid: Vec<_> = files
         .par_iter()
         .filter_map(|file| {
             let processor = FileProcessor::new(&config, &root);
-            match processor.validate(file) {
+            match processor.validate_cached(file, &manifest) {
                 Ok(true) => None,
                 Ok(false) => {
                     let sidecar = sidecar_path_for(file);
@@ -656,6 +1249,16 @@ pub fn validate(path: &str) -> Result<()> {
         })
         .collect();
 
+    manifest
+        .into_inner()
+        .unwrap()
+        .save(&manifest_path)
+        .context("Failed to persist source manifest")?;
+
+    if let Some(changed_path) = dependents {
+        report_dependents(&root, changed_path)?;
+    }
+
     if invalid.is_empty() {
         println!("{} All sidecars are up to date!", "✓".green().bold());
         Ok(())
@@ -677,10 +1280,15 @@ pub fn validate(path: &str) -> Result<()> {
     }
 }
 
-pub fn clean(path: &str, dry_run: bool) -> Result<()> {
+pub fn clean(
+    path: &str,
+    dry_run: bool,
+    include: &[String],
+    exclude: &[String],
+) -> Result<()> {
     let config = Config::load().unwrap_or_default();
-    let files = collect_files(path, &config)?;
     let root = resolve_root(path)?;
+    let files = filter_by_glob(collect_files(path, &config)?, &root, include, exclude)?;
 
     let mut removed = 0u32;
 
@@ -729,6 +1337,18 @@ pub fn clean(path: &str, dry_run: bool) -> Result<()> {
         }
     }
 
+    // The manifest cache is derived entirely from sidecars, so it's stale
+    // the moment they're gone.
+    let cache_file = root.join(".fmm-cache.json");
+    if cache_file.exists() {
+        if dry_run {
+            println!("  Would remove: .fmm-cache.json");
+        } else {
+            std::fs::remove_file(&cache_file)?;
+            println!("{} Removed .fmm-cache.json", "✓".green());
+        }
+    }
+
     println!(
         "\n{} {} sidecar(s) {}",
         "✓".green().bold(),
@@ -743,55 +1363,346 @@ pub fn clean(path: &str, dry_run: bool) -> Result<()> {
     Ok(())
 }
 
-pub fn init(skill: bool, mcp: bool, all: bool, no_generate: bool) -> Result<()> {
-    println!(
-        "\n{}",
-        "Frontmatter Matters — metadata sidecars for LLM code navigation"
-            .cyan()
-            .bold()
-    );
-    println!();
-
-    let specific = skill || mcp;
-    let full_setup = !specific || all;
+/// Reformats fenced code blocks embedded in sidecar files, piping each
+/// block's body through the formatter configured for its language in
+/// `config.formatters` and splicing the result back in. CRLF files are
+/// normalized to LF for scanning and restored on write; blocks whose
+/// language has no configured formatter, or whose formatter exits non-zero,
+/// are left as-is (the latter reported via stderr).
+pub fn format(path: &str, check: bool, dry_run: bool) -> Result<()> {
+    let config = Config::load().unwrap_or_default();
+    let root = resolve_root(path)?;
+    let files = collect_files(path, &config)?;
 
-    let install_config = full_setup;
-    let install_skill = skill || full_setup;
-    let install_mcp = mcp || full_setup;
+    let sidecars: Vec<PathBuf> = files
+        .iter()
+        .map(|f| sidecar_path_for(f))
+        .filter(|s| s.exists())
+        .collect();
 
-    if install_config {
-        init_config()?;
-    }
-    if install_skill {
-        init_skill()?;
-    }
-    if install_mcp {
-        init_mcp_config()?;
+    if sidecars.is_empty() {
+        println!("{} No sidecars found", "!".yellow());
+        return Ok(());
     }
 
-    // Auto-generate sidecars unless --no-generate or partial install
-    if full_setup && !no_generate {
-        println!();
-        let config = Config::load().unwrap_or_default();
-        let files = collect_files(".", &config)?;
+    let mut changed: Vec<String> = Vec::new();
+    let mut had_errors = false;
 
-        if !files.is_empty() {
-            // Detect languages present
-            let mut lang_set = std::collections::BTreeSet::new();
-            for file in &files {
-                if let Some(ext) = file.extension().and_then(|e| e.to_str()) {
-                    lang_set.insert(ext.to_string());
-                }
-            }
-            println!(
-                "{} {} source files detected ({})",
+    for sidecar in &sidecars {
+        let rel = sidecar
+            .strip_prefix(&root)
+            .unwrap_or(sidecar)
+            .display()
+            .to_string();
+
+        let original = std::fs::read_to_string(sidecar)
+            .with_context(|| format!("Failed to read {}", sidecar.display()))?;
+        let has_crlf = original.contains("\r\n");
+        let normalized = original.replace("\r\n", "\n");
+
+        let outcome = format_fenced_blocks(&normalized, &config.formatters);
+        for (lang, stderr) in &outcome.errors {
+            eprintln!("  {} {} ({}): {}", "✗".red(), rel, lang, stderr.trim());
+            had_errors = true;
+        }
+
+        let rewritten = if has_crlf {
+            outcome.content.replace('\n', "\r\n")
+        } else {
+            outcome.content
+        };
+
+        if rewritten == original {
+            continue;
+        }
+
+        changed.push(rel.clone());
+        if check {
+            println!("  {} {}", "✗".red(), rel);
+        } else if dry_run {
+            println!("  {} Would reformat {}", "!".yellow(), rel);
+        } else {
+            std::fs::write(sidecar, &rewritten)
+                .with_context(|| format!("Failed to write {}", sidecar.display()))?;
+            println!("{} Reformatted {}", "✓".green(), rel);
+        }
+    }
+
+    if changed.is_empty() {
+        println!(
+            "{} All sidecar code blocks are formatted",
+            "✓".green().bold()
+        );
+    } else if check {
+        anyhow::bail!("{} sidecar(s) have unformatted code blocks", changed.len());
+    } else {
+        println!(
+            "\n{} {} sidecar(s) {}",
+            "✓".green().bold(),
+            changed.len(),
+            if dry_run {
+                "would be reformatted"
+            } else {
+                "reformatted"
+            }
+        );
+    }
+
+    if had_errors && !check {
+        anyhow::bail!("One or more formatter commands failed — see stderr above");
+    }
+
+    Ok(())
+}
+
+/// Result of [`format_fenced_blocks`]: the (possibly) rewritten content, and
+/// one `(language, stderr)` entry per block whose formatter exited non-zero.
+struct FormatOutcome {
+    content: String,
+    errors: Vec<(String, String)>,
+}
+
+/// A fenced code block's opening line: the fence character (`` ` `` or `~`),
+/// how many times it repeats, its leading indentation, and the first token
+/// of its info string (the language), if any.
+struct FenceOpen {
+    indent: usize,
+    ch: char,
+    len: usize,
+    lang: Option<String>,
+}
+
+/// Line-scans `content` tracking fence state, piping each recognized block's
+/// body through `formatters[lang]` (if configured) and splicing the result
+/// back in with the original indentation and fence characters preserved.
+/// Unterminated fences are left untouched.
+fn format_fenced_blocks(
+    content: &str,
+    formatters: &std::collections::HashMap<String, String>,
+) -> FormatOutcome {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut out = String::new();
+    let mut errors = Vec::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i];
+        let Some(fence) = parse_fence_open(line) else {
+            out.push_str(line);
+            out.push('\n');
+            i += 1;
+            continue;
+        };
+
+        out.push_str(line);
+        out.push('\n');
+
+        let mut body = Vec::new();
+        let mut close = None;
+        let mut j = i + 1;
+        while j < lines.len() {
+            if is_fence_close(lines[j], &fence) {
+                close = Some(j);
+                break;
+            }
+            // Strip the fence's own indentation so the formatter sees the
+            // body as written; it's re-added below when splicing back in.
+            let bline = lines[j];
+            let leading = bline.len() - bline.trim_start_matches(' ').len();
+            body.push(&bline[leading.min(fence.indent)..]);
+            j += 1;
+        }
+
+        let Some(close) = close else {
+            for bline in &lines[i + 1..j] {
+                out.push_str(bline);
+                out.push('\n');
+            }
+            i = j;
+            continue;
+        };
+
+        let formatter = fence.lang.as_deref().and_then(|l| formatters.get(l));
+        let body_text = body.join("\n");
+        let formatted = match formatter {
+            Some(cmd) => match run_formatter(cmd, &body_text) {
+                Ok(formatted) => formatted,
+                Err(stderr) => {
+                    errors.push((fence.lang.clone().unwrap_or_default(), stderr));
+                    body_text
+                }
+            },
+            None => body_text,
+        };
+
+        let indent = " ".repeat(fence.indent);
+        for bline in formatted.trim_end_matches('\n').lines() {
+            if !bline.is_empty() {
+                out.push_str(&indent);
+            }
+            out.push_str(bline);
+            out.push('\n');
+        }
+
+        out.push_str(lines[close]);
+        out.push('\n');
+        i = close + 1;
+    }
+
+    if !content.ends_with('\n') && out.ends_with('\n') {
+        out.pop();
+    }
+
+    FormatOutcome {
+        content: out,
+        errors,
+    }
+}
+
+/// Recognizes an opening fence: a line (after its leading whitespace) of
+/// three-or-more backticks or tildes, optionally followed by an info string
+/// whose first whitespace-separated token is the language.
+fn parse_fence_open(line: &str) -> Option<FenceOpen> {
+    let indent = line.len() - line.trim_start().len();
+    let trimmed = &line[indent..];
+    let ch = trimmed.chars().next()?;
+    if ch != '`' && ch != '~' {
+        return None;
+    }
+    let len = trimmed.chars().take_while(|&c| c == ch).count();
+    if len < 3 {
+        return None;
+    }
+    let info = trimmed[len..].trim();
+    let lang = info
+        .split_whitespace()
+        .next()
+        .map(|s| s.to_lowercase())
+        .filter(|s| !s.is_empty());
+
+    Some(FenceOpen {
+        indent,
+        ch,
+        len,
+        lang,
+    })
+}
+
+/// A closing fence is the same character repeated at least as many times as
+/// the opening fence, at the same indentation, with nothing but whitespace
+/// after it.
+fn is_fence_close(line: &str, open: &FenceOpen) -> bool {
+    let indent = line.len() - line.trim_start().len();
+    if indent != open.indent {
+        return false;
+    }
+    let trimmed = &line[indent..];
+    let len = trimmed.chars().take_while(|&c| c == open.ch).count();
+    len >= open.len && trimmed[len..].trim().is_empty()
+}
+
+/// Pipes `body` to `cmd`'s stdin and returns its stdout, trimmed of a
+/// trailing newline. `cmd` is split on whitespace into a program and its
+/// arguments (e.g. `"prettier --parser json"`) — no shell is involved.
+fn run_formatter(cmd: &str, body: &str) -> std::result::Result<String, String> {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let mut parts = cmd.split_whitespace();
+    let program = parts
+        .next()
+        .ok_or_else(|| "empty formatter command".to_string())?;
+
+    let mut child = std::process::Command::new(program)
+        .args(parts)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("failed to run `{}`: {}", cmd, e))?;
+
+    child
+        .stdin
+        .take()
+        .expect("child stdin was piped")
+        .write_all(body.as_bytes())
+        .map_err(|e| format!("failed to write to `{}` stdin: {}", cmd, e))?;
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("failed to wait on `{}`: {}", cmd, e))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .trim_end_matches('\n')
+        .to_string())
+}
+
+pub fn init(
+    skill: bool,
+    mcp: bool,
+    hooks: bool,
+    all: bool,
+    no_generate: bool,
+    dry_run: bool,
+) -> Result<()> {
+    println!(
+        "\n{}",
+        "Frontmatter Matters — metadata sidecars for LLM code navigation"
+            .cyan()
+            .bold()
+    );
+    println!();
+
+    let specific = skill || mcp || hooks;
+    let full_setup = !specific || all;
+
+    let install_config = full_setup;
+    let install_skill = skill || full_setup;
+    let install_mcp = mcp || full_setup;
+    let install_hooks = hooks || all;
+
+    if install_config {
+        init_config()?;
+    }
+    if install_skill {
+        init_skill()?;
+    }
+    if install_mcp {
+        init_mcp_config()?;
+    }
+    if install_hooks {
+        init_pre_commit_hook(dry_run)?;
+    }
+
+    // Auto-generate sidecars unless --no-generate or partial install
+    if full_setup && !no_generate {
+        println!();
+        let config = Config::load().unwrap_or_default();
+        let files = collect_files(".", &config)?;
+
+        if !files.is_empty() {
+            // Detect languages present
+            let mut lang_set = std::collections::BTreeSet::new();
+            for file in &files {
+                if let Some(ext) = file.extension().and_then(|e| e.to_str()) {
+                    lang_set.insert(ext.to_string());
+                }
+            }
+            println!(
+                "{} {} source files detected ({})",
                 "✓".green(),
                 files.len(),
-                lang_set.into_iter().collect::<Vec<_>>().join(", ")
+                lang_set.iter().cloned().collect::<Vec<_>>().join(", ")
             );
 
+            self_provision_grammars(&lang_set, all)?;
+
             println!("{}", "Generating sidecars...".green().bold());
-            generate(".", false)?;
+            generate(".", false, None, &[], &[])?;
 
             // Show one sample sidecar
             let root = resolve_root(".")?;
@@ -844,6 +1755,9 @@ pub fn init(skill: bool, mcp: bool, all: bool, no_generate: bool) -> Result<()>
     if install_mcp {
         println!("  MCP:      .mcp.json");
     }
+    if install_hooks && !dry_run {
+        println!("  Hooks:    .git/hooks/pre-commit");
+    }
 
     if no_generate || specific {
         println!(
@@ -860,6 +1774,51 @@ pub fn init(skill: bool, mcp: bool, all: bool, no_generate: bool) -> Result<()>
     Ok(())
 }
 
+/// Check the extensions `init` just detected against [`KNOWN_GRAMMARS`] for
+/// any with no registered parser. With `--all` (non-interactive setup),
+/// fetch and register the missing grammar immediately; otherwise just print
+/// the `fmm fetch-grammar` hint, since fetching reaches out to the network.
+fn self_provision_grammars(lang_set: &std::collections::BTreeSet<String>, all: bool) -> Result<()> {
+    let registry = crate::parser::ParserRegistry::with_builtins();
+
+    for (name, _repo, extensions) in KNOWN_GRAMMARS {
+        let present = extensions.iter().any(|ext| lang_set.contains(*ext));
+        let missing_parser = extensions
+            .iter()
+            .any(|ext| registry.get_parser(ext).is_err());
+        if !present || !missing_parser {
+            continue;
+        }
+
+        if all {
+            println!(
+                "{} {} source files found with no builtin parser — fetching the '{}' grammar...",
+                "!".yellow(),
+                name,
+                name
+            );
+            let exts: Vec<String> = extensions.iter().map(|s| s.to_string()).collect();
+            if let Err(err) = fetch_grammar(name, None, None, &exts, Path::new("languages.toml")) {
+                println!(
+                    "{} Could not fetch grammar '{}': {:#}",
+                    "!".yellow(),
+                    name,
+                    err
+                );
+            }
+        } else {
+            println!(
+                "{} {} source files found with no builtin parser — run `fmm fetch-grammar {}` to self-provision it",
+                "!".yellow(),
+                name,
+                name
+            );
+        }
+    }
+
+    Ok(())
+}
+
 fn init_config() -> Result<()> {
     let config_path = Path::new(".fmmrc.json");
     if config_path.exists() {
@@ -963,6 +1922,99 @@ fn init_mcp_config() -> Result<()> {
     Ok(())
 }
 
+/// Marker comment written at the top of the installed hook, used both to
+/// recognize a hook `init` previously installed (so re-running `init` is
+/// idempotent, matching how [`init_mcp_config`] recognizes its own entry in
+/// an existing `.mcp.json`) and to avoid clobbering an unrelated hook a
+/// project already had in place.
+const PRE_COMMIT_HOOK_MARKER: &str = "# fmm: managed pre-commit hook — regenerates sidecars for staged files";
+
+fn pre_commit_hook_script() -> String {
+    format!(
+        "#!/bin/sh\n\
+         {marker}\n\
+         # Keep sidecar metadata from drifting out of sync with the code it\n\
+         # describes: regenerate sidecars for everything staged, then stage\n\
+         # whatever changed so the commit carries both together.\n\
+         set -e\n\
+         \n\
+         staged=$(git diff --cached --name-only --diff-filter=ACMR)\n\
+         [ -z \"$staged\" ] && exit 0\n\
+         \n\
+         fmm update --changed HEAD .\n\
+         \n\
+         for f in $staged; do\n\
+         \tsidecar=\"$f.fmm\"\n\
+         \t[ -f \"$sidecar\" ] && git add \"$sidecar\"\n\
+         done\n",
+        marker = PRE_COMMIT_HOOK_MARKER,
+    )
+}
+
+/// Install a git `pre-commit` hook at `.git/hooks/pre-commit` that re-runs
+/// `fmm update` over staged files and stages the sidecars it touches, so a
+/// commit never ships code without matching metadata. Idempotent like
+/// [`init_mcp_config`]: a hook already carrying [`PRE_COMMIT_HOOK_MARKER`] is
+/// left alone, and a hook that doesn't isn't overwritten out from under the
+/// project — we report it and leave it for the user to merge by hand.
+fn init_pre_commit_hook(dry_run: bool) -> Result<()> {
+    let hooks_dir = Path::new(".git").join("hooks");
+    if !hooks_dir.is_dir() {
+        println!(
+            "{} No .git/hooks directory found (not a git repository?) — skipping hook install",
+            "!".yellow()
+        );
+        return Ok(());
+    }
+
+    let hook_path = hooks_dir.join("pre-commit");
+    let script = pre_commit_hook_script();
+
+    if hook_path.exists() {
+        let existing =
+            std::fs::read_to_string(&hook_path).context("Failed to read existing pre-commit hook")?;
+        if existing.contains(PRE_COMMIT_HOOK_MARKER) {
+            println!(
+                "{} .git/hooks/pre-commit already installed (skipping)",
+                "!".yellow()
+            );
+            return Ok(());
+        }
+        println!(
+            "{} .git/hooks/pre-commit already exists and isn't fmm-managed — leaving it alone",
+            "!".yellow()
+        );
+        println!("  Add this to it by hand if you'd like sidecars kept in sync:");
+        for line in script.lines() {
+            println!("    {}", line.dimmed());
+        }
+        return Ok(());
+    }
+
+    if dry_run {
+        println!("{} Would write .git/hooks/pre-commit:", "!".yellow());
+        for line in script.lines() {
+            println!("    {}", line.dimmed());
+        }
+        return Ok(());
+    }
+
+    std::fs::write(&hook_path, script).context("Failed to write .git/hooks/pre-commit")?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = std::fs::metadata(&hook_path)?.permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(&hook_path, perms)?;
+    }
+
+    println!(
+        "{} Installed .git/hooks/pre-commit (regenerates sidecars for staged files)",
+        "✓".green()
+    );
+    Ok(())
+}
+
 pub fn status() -> Result<()> {
     let config_path = Path::new(".fmmrc.json");
     let config_exists = config_path.exists();
@@ -971,6 +2023,11 @@ pub fn status() -> Result<()> {
 
     println!("{}", "fmm Status".cyan().bold());
     println!("{}", "=".repeat(40).dimmed());
+    println!(
+        "{} {}",
+        "Build:".dimmed(),
+        env!("FMM_BUILD_GIT_HASH").dimmed()
+    );
 
     println!("\n{}", "Configuration:".yellow().bold());
     if config_exists {
@@ -1023,6 +2080,10 @@ pub fn status() -> Result<()> {
                 files.len().to_string().white().bold(),
                 sidecar_count.to_string().white().bold()
             );
+
+            if let Some(health) = sidecar_health(&cwd, &files) {
+                println!("  {}", health.render());
+            }
         }
         Err(e) => {
             println!("  {} Error scanning: {}", "✗".red(), e);
@@ -1033,65 +2094,894 @@ pub fn status() -> Result<()> {
     Ok(())
 }
 
-fn collect_files(path: &str, config: &Config) -> Result<Vec<PathBuf>> {
-    let path = Path::new(path);
+/// Sidecar-health bucket counts for the git-status-style summary line in
+/// [`status`]. `staged`/`modified`/`untracked`/`conflicted` mirror git's own
+/// categories (restricted to paths that have a sidecar); `orphaned` and
+/// `stale` are fmm-specific: a sidecar whose source file is gone, or whose
+/// source file was modified more recently than the sidecar was written.
+#[derive(Default, PartialEq, Eq, Debug)]
+struct SidecarHealth {
+    staged: usize,
+    modified: usize,
+    untracked: usize,
+    conflicted: usize,
+    orphaned: usize,
+    stale: usize,
+}
 
-    if path.is_file() {
-        let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
-        return Ok(vec![canonical]);
+impl SidecarHealth {
+    fn render(&self) -> String {
+        let mut parts = Vec::new();
+        if self.staged > 0 {
+            parts.push(format!("+{}", self.staged).green().to_string());
+        }
+        if self.modified > 0 {
+            parts.push(format!("!{}", self.modified).yellow().to_string());
+        }
+        if self.untracked > 0 {
+            parts.push(format!("?{}", self.untracked).dimmed().to_string());
+        }
+        if self.conflicted > 0 {
+            parts.push(format!("✗{}", self.conflicted).red().to_string());
+        }
+        if self.orphaned > 0 {
+            parts.push(format!("⊘{}", self.orphaned).red().to_string());
+        }
+        if self.stale > 0 {
+            parts.push(format!("≈{}", self.stale).yellow().to_string());
+        }
+        if parts.is_empty() {
+            format!("{} sidecars in sync", "✓".green())
+        } else {
+            parts.join(" ")
+        }
     }
+}
 
-    let walker = WalkBuilder::new(path)
-        .standard_filters(true)
-        .add_custom_ignore_filename(".fmmignore")
-        .build();
+/// Cross-references discovered sidecars against `git status --porcelain=v2 -z`
+/// and the filesystem to build a [`SidecarHealth`] summary. Returns `None`
+/// when `root` isn't a git repository (or git isn't available) — the rest of
+/// `status()` still prints without the git-derived summary line.
+fn sidecar_health(root: &Path, source_files: &[PathBuf]) -> Option<SidecarHealth> {
+    let output = std::process::Command::new("git")
+        .args(["status", "--porcelain=v2", "-z"])
+        .current_dir(root)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
 
-    let files: Vec<PathBuf> = walker
-        .filter_map(|entry| entry.ok())
-        .filter(|entry| entry.file_type().is_some_and(|ft| ft.is_file()))
-        .filter(|entry| {
-            if let Some(ext) = entry.path().extension() {
-                config.is_supported_language(ext.to_str().unwrap_or(""))
-            } else {
-                false
+    let mut by_path: std::collections::HashMap<PathBuf, GitEntryKind> =
+        std::collections::HashMap::new();
+    let fields: Vec<&[u8]> = output
+        .stdout
+        .split(|&b| b == 0)
+        .filter(|f| !f.is_empty())
+        .collect();
+
+    let mut i = 0;
+    while i < fields.len() {
+        let entry = String::from_utf8_lossy(fields[i]);
+        // Per `git help status` --porcelain=v2, each line is a fixed number
+        // of space-separated fields followed by `path` (which may itself
+        // contain spaces, so it must be taken as everything left over after
+        // the fixed fields rather than split further): ordinary "1 XY sub mH
+        // mI mW hH hI path" (9 fields), rename/copy "2 ... X<score> path"
+        // (10 fields, plus a NUL-separated origPath we skip below), unmerged
+        // "u XY sub m1 m2 m3 mW h1 h2 h3 path" (11 fields), untracked
+        // "? path" (2 fields).
+        match entry.splitn(2, ' ').next() {
+            Some("1") => {
+                if let Some(xy) = entry.split(' ').nth(1) {
+                    let path = path_field(&entry, 9);
+                    by_path.insert(root.join(path), classify_xy(xy));
+                }
             }
-        })
-        .map(|entry| {
-            entry
-                .path()
-                .canonicalize()
-                .unwrap_or_else(|_| entry.path().to_path_buf())
-        })
+            Some("2") => {
+                if let Some(xy) = entry.split(' ').nth(1) {
+                    let path = path_field(&entry, 10);
+                    by_path.insert(root.join(path), classify_xy(xy));
+                }
+                // Rename/copy entries carry an extra NUL-separated origPath
+                // field we don't need — skip over it.
+                i += 1;
+            }
+            Some("u") => {
+                let path = path_field(&entry, 11);
+                by_path.insert(root.join(path), GitEntryKind::Conflicted);
+            }
+            Some("?") => {
+                let path = path_field(&entry, 2);
+                by_path.insert(root.join(path), GitEntryKind::Untracked);
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    let by_path: std::collections::HashMap<PathBuf, GitEntryKind> = by_path
+        .into_iter()
+        .map(|(path, kind)| (path.canonicalize().unwrap_or(path), kind))
         .collect();
 
-    Ok(files)
+    let mut health = SidecarHealth {
+        orphaned: count_orphaned_sidecars(root),
+        ..Default::default()
+    };
+    for source in source_files {
+        let sidecar = sidecar_path_for(source);
+        if !sidecar.exists() {
+            continue;
+        }
+        match by_path.get(source.as_path()) {
+            Some(GitEntryKind::Conflicted) => health.conflicted += 1,
+            Some(GitEntryKind::Staged) => health.staged += 1,
+            Some(GitEntryKind::Modified) => health.modified += 1,
+            Some(GitEntryKind::Untracked) => health.untracked += 1,
+            None => {
+                let source_mtime = std::fs::metadata(source).and_then(|m| m.modified()).ok();
+                let sidecar_mtime = std::fs::metadata(&sidecar).and_then(|m| m.modified()).ok();
+                if let (Some(src), Some(side)) = (source_mtime, sidecar_mtime) {
+                    if src > side {
+                        health.stale += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    Some(health)
 }
 
-/// Search result for JSON output
-#[derive(serde::Serialize)]
-struct SearchResult {
-    file: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    exports: Option<Vec<String>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    imports: Option<Vec<String>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    dependencies: Option<Vec<String>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    loc: Option<usize>,
+/// Splits a porcelain v2 entry into at most `n` space-separated pieces and
+/// returns the last one — the path, which may contain spaces of its own and
+/// so can't be recovered by splitting further.
+fn path_field<'a>(entry: &'a str, n: usize) -> &'a str {
+    entry.splitn(n, ' ').last().unwrap_or("")
 }
 
-pub fn search(
-    export: Option<String>,
-    imports: Option<String>,
-    loc: Option<String>,
-    depends_on: Option<String>,
-    json_output: bool,
-) -> Result<()> {
+/// Walks `root` for `.fmm` sidecar files whose source no longer exists on
+/// disk (`source_files` only ever contains files the walk just found, so
+/// this can't be detected by checking those — it needs its own walk over
+/// the sidecars themselves).
+fn count_orphaned_sidecars(root: &Path) -> usize {
+    WalkBuilder::new(root)
+        .standard_filters(true)
+        .build()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_some_and(|ft| ft.is_file()))
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "fmm"))
+        .filter(|entry| !entry.path().with_extension("").exists())
+        .count()
+}
+
+enum GitEntryKind {
+    Staged,
+    Modified,
+    Untracked,
+    Conflicted,
+}
+
+/// Classifies a porcelain v2 `XY` code: `U`/`AA`/`DD` are conflicts, a
+/// non-blank index column (X) means staged, a non-blank worktree column (Y)
+/// means modified (staged wins when both are set, matching git's own
+/// short-status precedence).
+fn classify_xy(xy: &str) -> GitEntryKind {
+    let mut chars = xy.chars();
+    let x = chars.next().unwrap_or('.');
+    let y = chars.next().unwrap_or('.');
+    if xy == "AA" || xy == "DD" || x == 'U' || y == 'U' {
+        GitEntryKind::Conflicted
+    } else if x != '.' {
+        GitEntryKind::Staged
+    } else {
+        GitEntryKind::Modified
+    }
+}
+
+/// Write `fmm.1` into `out_dir`, returning the number of man pages written.
+/// Shared by the `--generate-man-pages` runtime flag and by `build.rs`,
+/// which runs the same generation at compile time so packagers get man
+/// pages without ever executing the binary.
+pub fn generate_man_pages(out_dir: &Path) -> Result<usize> {
+    std::fs::create_dir_all(out_dir)?;
+    clap_mangen::generate_to(Cli::command(), out_dir)?;
+    let count = std::fs::read_dir(out_dir)?
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            e.path()
+                .extension()
+                .is_some_and(|ext| ext.to_str() == Some("1"))
+        })
+        .count();
+    Ok(count)
+}
+
+/// Write a shell-completion script for `shell` to `writer`. Shared by the
+/// `completions` subcommand and by `build.rs`, which generates bash/zsh/
+/// fish/powershell completions into `OUT_DIR` at compile time.
+pub fn generate_completions(shell: Shell, writer: &mut dyn std::io::Write) {
+    clap_complete::generate(shell, &mut Cli::command(), "fmm", writer);
+}
+
+/// Print every indexed export name, one per line, so a generated zsh/fish
+/// completion script can shell out to `fmm completions --complete-exports`
+/// and complete `--export <TAB>` against the actual codebase rather than
+/// leaving it uncompletable.
+pub fn complete_exports(writer: &mut dyn std::io::Write) -> Result<()> {
     let root = std::env::current_dir()?;
     let manifest = crate::manifest::Manifest::load_from_sidecars(&root)?;
 
-    if manifest.files.is_empty() {
+    let mut names: Vec<&String> = manifest.export_index.keys().collect();
+    names.sort();
+    for name in names {
+        writeln!(writer, "{}", name)?;
+    }
+    Ok(())
+}
+
+/// Print the resolved `fmm.toml` defaults (`--schema` prints its JSON
+/// Schema instead) — see [`crate::config::ProjectDefaults`].
+pub fn config(schema: bool) -> Result<()> {
+    if schema {
+        println!("{}", crate::config::ProjectDefaults::schema_json()?);
+        return Ok(());
+    }
+
+    let root = std::env::current_dir().context("Failed to get current directory")?;
+    let defaults = crate::config::ProjectDefaults::load_layered(&root);
+    println!("{}", toml::to_string_pretty(&defaults)?);
+    Ok(())
+}
+
+/// Grammars fmm knows how to fetch out of the box: name, git repo, and the
+/// file extensions it's registered for. Anything else needs an explicit
+/// `--url` (and, usually, `--extensions`) to `fmm fetch-grammar`.
+const KNOWN_GRAMMARS: &[(&str, &str, &[&str])] = &[
+    (
+        "kotlin",
+        "https://github.com/fwcd/tree-sitter-kotlin",
+        &["kt", "kts"],
+    ),
+    (
+        "swift",
+        "https://github.com/alex-pinkus/tree-sitter-swift",
+        &["swift"],
+    ),
+    (
+        "scala",
+        "https://github.com/tree-sitter/tree-sitter-scala",
+        &["scala"],
+    ),
+    (
+        "elixir",
+        "https://github.com/elixir-lang/tree-sitter-elixir",
+        &["ex", "exs"],
+    ),
+    (
+        "zig",
+        "https://github.com/maxxnino/tree-sitter-zig",
+        &["zig"],
+    ),
+    (
+        "lua",
+        "https://github.com/tree-sitter-grammars/tree-sitter-lua",
+        &["lua"],
+    ),
+];
+
+/// Clone, generate, and compile a tree-sitter grammar, then register it in
+/// `manifest_path` — the same clone/build/link pipeline an external grammar
+/// crate's own `build.rs` runs against its vendored `cc` crate, but driven
+/// at runtime instead of at `cargo build` time. See
+/// [`crate::parser::grammar`] for the loader this feeds.
+///
+/// `name` is looked up in [`KNOWN_GRAMMARS`] unless `url` is given
+/// explicitly. `extensions`, if empty, defaults to `[name]`.
+pub fn fetch_grammar(
+    name: &str,
+    url: Option<&str>,
+    git_ref: Option<&str>,
+    extensions: &[String],
+    manifest_path: &Path,
+) -> Result<()> {
+    let repo_url = match url {
+        Some(url) => url.to_string(),
+        None => KNOWN_GRAMMARS
+            .iter()
+            .find(|(known, _, _)| *known == name)
+            .map(|(_, repo, _)| repo.to_string())
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "no known grammar repo for '{name}' — pass --url <git-url> to fetch it explicitly"
+                )
+            })?,
+    };
+
+    let cache_dir = crate::parser::grammar::grammar_cache_dir()
+        .ok_or_else(|| anyhow::anyhow!("could not determine home directory for grammar cache"))?;
+    std::fs::create_dir_all(&cache_dir)
+        .with_context(|| format!("creating grammar cache dir {}", cache_dir.display()))?;
+
+    println!(
+        "{} Fetching grammar '{}' from {}",
+        "→".cyan(),
+        name,
+        repo_url
+    );
+    let checkout_dir = cache_dir.join("src").join(name);
+    fetch_grammar_checkout(&repo_url, git_ref, &checkout_dir)?;
+
+    println!("{} Generating parser sources...", "→".cyan());
+    fetch_grammar_generate(&checkout_dir)?;
+
+    println!("{} Compiling grammar library...", "→".cyan());
+    let library_path = fetch_grammar_compile(&checkout_dir, name, &cache_dir)?;
+
+    let mut manifest = crate::parser::grammar::GrammarManifest::load(manifest_path);
+    let extensions = if extensions.is_empty() {
+        vec![name.to_string()]
+    } else {
+        extensions.to_vec()
+    };
+    manifest.upsert(crate::parser::grammar::GrammarEntry {
+        name: name.to_string(),
+        extensions,
+        library: library_path.display().to_string(),
+        symbol: None,
+        blacklist: Vec::new(),
+    });
+    manifest.save(manifest_path)?;
+
+    println!(
+        "{} Registered '{}' in {}",
+        "✓".green(),
+        name,
+        manifest_path.display()
+    );
+    Ok(())
+}
+
+fn fetch_grammar_checkout(
+    repo_url: &str,
+    git_ref: Option<&str>,
+    checkout_dir: &Path,
+) -> Result<()> {
+    if checkout_dir.join(".git").exists() {
+        let output = std::process::Command::new("git")
+            .args(["fetch", "origin"])
+            .current_dir(checkout_dir)
+            .output()
+            .context("Failed to git fetch grammar repo")?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "git fetch failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+    } else {
+        if let Some(parent) = checkout_dir.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("creating {}", parent.display()))?;
+        }
+        let output = std::process::Command::new("git")
+            .args(["clone", repo_url, &checkout_dir.to_string_lossy()])
+            .output()
+            .context("Failed to git clone grammar repo")?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "git clone failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+    }
+
+    let checkout_ref = git_ref.unwrap_or("origin/HEAD");
+    let output = std::process::Command::new("git")
+        .args(["checkout", checkout_ref])
+        .current_dir(checkout_dir)
+        .output()
+        .context("Failed to git checkout grammar ref")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git checkout {} failed: {}",
+            checkout_ref,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    Ok(())
+}
+
+/// Run `tree-sitter generate` in `checkout_dir` to (re)produce
+/// `src/parser.c` (and `src/scanner.c` for grammars with an external
+/// scanner) from `grammar.js`. Requires the `tree-sitter` CLI on `PATH`.
+fn fetch_grammar_generate(checkout_dir: &Path) -> Result<()> {
+    let output = std::process::Command::new("tree-sitter")
+        .args(["generate"])
+        .current_dir(checkout_dir)
+        .output()
+        .context("Failed to run `tree-sitter generate` (is the tree-sitter CLI installed?)")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "tree-sitter generate failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    Ok(())
+}
+
+/// Compile `src/parser.c` (and `src/scanner.c`/`src/scanner.cc`, if
+/// present) into a shared library under `cache_dir`, mirroring the
+/// clone/build/link pipeline a `tree-sitter-<lang>` crate's own `build.rs`
+/// performs against its vendored `cc` crate.
+fn fetch_grammar_compile(checkout_dir: &Path, name: &str, cache_dir: &Path) -> Result<PathBuf> {
+    let src_dir = checkout_dir.join("src");
+    let mut sources = vec![src_dir.join("parser.c")];
+    let mut compiler = "cc";
+    if src_dir.join("scanner.cc").exists() {
+        sources.push(src_dir.join("scanner.cc"));
+        compiler = "c++";
+    } else if src_dir.join("scanner.c").exists() {
+        sources.push(src_dir.join("scanner.c"));
+    }
+
+    let library_path = cache_dir.join(format!(
+        "{}.{}",
+        name,
+        crate::parser::grammar::library_extension()
+    ));
+    let output = std::process::Command::new(compiler)
+        .arg("-shared")
+        .arg("-fPIC")
+        .arg("-O2")
+        .arg("-I")
+        .arg(&src_dir)
+        .args(sources.iter().map(|p| p.as_os_str()))
+        .arg("-o")
+        .arg(&library_path)
+        .output()
+        .with_context(|| format!("Failed to run `{compiler}` to compile grammar '{name}'"))?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "compiling grammar '{}' failed: {}",
+            name,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    Ok(library_path)
+}
+
+/// Relative paths of the generated docs/completions `fmm tidy` keeps in sync
+/// with the current [`Cli`] definition — maintainers commit these alongside
+/// the source so a `git clone` has working completions without a build.
+const TIDY_MARKDOWN_HELP_PATH: &str = "docs/cli.md";
+const TIDY_MAN_PAGE_PATH: &str = "man/fmm.1";
+const TIDY_COMPLETION_DIR: &str = "completions";
+
+struct TidyArtifact {
+    path: PathBuf,
+    generated: Vec<u8>,
+}
+
+/// Following the sourcegen-with-verification pattern: regenerate the CLI
+/// reference markdown, man page, and shell completions in memory and either
+/// write them over the committed copies, or (with `check`) diff against them
+/// and fail without touching disk — so CI can catch docs/completions that
+/// have drifted from the current [`Cli`] definition.
+pub fn tidy(check: bool) -> Result<()> {
+    let artifacts = tidy_artifacts()?;
+    let stale: Vec<&TidyArtifact> = artifacts
+        .iter()
+        .filter(|a| std::fs::read(&a.path).unwrap_or_default() != a.generated)
+        .collect();
+
+    if check {
+        if stale.is_empty() {
+            println!(
+                "{} generated docs/completions are up to date.",
+                "✓".green().bold()
+            );
+            return Ok(());
+        }
+
+        for artifact in &stale {
+            println!("\n{} {}", "✗ stale:".red().bold(), artifact.path.display());
+            let on_disk = std::fs::read_to_string(&artifact.path).unwrap_or_default();
+            let generated = String::from_utf8_lossy(&artifact.generated);
+            print_line_diff(&on_disk, &generated);
+        }
+
+        anyhow::bail!(
+            "{} generated file(s) out of date — run `fmm tidy` to update",
+            stale.len()
+        );
+    }
+
+    for artifact in &artifacts {
+        if let Some(parent) = artifact.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&artifact.path, &artifact.generated)?;
+    }
+
+    println!(
+        "{} Updated {} generated file(s).",
+        "✓".green().bold(),
+        artifacts.len()
+    );
+
+    Ok(())
+}
+
+/// Render every tidy-managed artifact in memory, without touching disk.
+fn tidy_artifacts() -> Result<Vec<TidyArtifact>> {
+    let mut artifacts = vec![
+        TidyArtifact {
+            path: PathBuf::from(TIDY_MARKDOWN_HELP_PATH),
+            generated: clap_markdown::help_markdown::<Cli>().into_bytes(),
+        },
+        TidyArtifact {
+            path: PathBuf::from(TIDY_MAN_PAGE_PATH),
+            generated: render_man_page()?,
+        },
+    ];
+
+    for (shell, suffix) in [
+        (Shell::Bash, "bash"),
+        (Shell::Zsh, "zsh"),
+        (Shell::Fish, "fish"),
+        (Shell::PowerShell, "ps1"),
+    ] {
+        let mut buf = Vec::new();
+        generate_completions(shell, &mut buf);
+        artifacts.push(TidyArtifact {
+            path: Path::new(TIDY_COMPLETION_DIR).join(format!("fmm.{suffix}")),
+            generated: buf,
+        });
+    }
+
+    Ok(artifacts)
+}
+
+/// Render `fmm.1` straight into memory, for comparison against the
+/// committed copy — unlike [`generate_man_pages`], this never touches disk.
+fn render_man_page() -> Result<Vec<u8>> {
+    let mut buf = Vec::new();
+    clap_mangen::Man::new(Cli::command()).render(&mut buf)?;
+    Ok(buf)
+}
+
+/// Minimal line-level diff for `fmm tidy --check`'s output. Not a proper
+/// LCS diff — just enough to show which lines differ without pulling in a
+/// new dependency for a CI-convenience feature.
+fn print_line_diff(old: &str, new: &str) {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let max = old_lines.len().max(new_lines.len());
+
+    for i in 0..max {
+        match (old_lines.get(i), new_lines.get(i)) {
+            (Some(a), Some(b)) if a == b => {}
+            (Some(a), Some(b)) => {
+                println!("  {} {}", "-".red(), a);
+                println!("  {} {}", "+".green(), b);
+            }
+            (Some(a), None) => println!("  {} {}", "-".red(), a),
+            (None, Some(b)) => println!("  {} {}", "+".green(), b),
+            (None, None) => {}
+        }
+    }
+}
+
+/// Build glob overrides from `config.include`/`config.exclude`, layered on
+/// top of the standard `.gitignore`/`.fmmignore`-aware walk so the initial
+/// [`collect_files`] pass and the live watcher apply identical rules.
+/// `exclude` patterns are blacklisted; `include` patterns are whitelisted —
+/// see [`ignore::overrides::OverrideBuilder`] for the exact precedence.
+fn build_overrides(root: &Path, config: &Config) -> Result<ignore::overrides::Override> {
+    let mut builder = ignore::overrides::OverrideBuilder::new(root);
+    for pattern in &config.exclude {
+        builder.add(&format!("!{}", pattern))?;
+    }
+    for pattern in &config.include {
+        builder.add(pattern)?;
+    }
+    Ok(builder.build()?)
+}
+
+fn collect_files(path: &str, config: &Config) -> Result<Vec<PathBuf>> {
+    let path = Path::new(path);
+
+    if path.is_file() {
+        let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        return Ok(vec![canonical]);
+    }
+
+    let overrides = build_overrides(path, config)?;
+
+    let walker = WalkBuilder::new(path)
+        .standard_filters(true)
+        .add_custom_ignore_filename(".fmmignore")
+        .overrides(overrides)
+        .build();
+
+    let files: Vec<PathBuf> = walker
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_some_and(|ft| ft.is_file()))
+        .filter(|entry| {
+            if let Some(ext) = entry.path().extension() {
+                config.is_supported_language(ext.to_str().unwrap_or(""))
+            } else {
+                false
+            }
+        })
+        .map(|entry| {
+            entry
+                .path()
+                .canonicalize()
+                .unwrap_or_else(|_| entry.path().to_path_buf())
+        })
+        .collect();
+
+    Ok(files)
+}
+
+/// Like [`collect_files`], but when `changed` is set, narrows the result to
+/// files modified since that git ref (tracked diff + untracked files),
+/// intersected with the usual `.gitignore`-aware walk; and when `include`/
+/// `exclude` globs are given, narrows it further (see [`PathFilter`]).
+fn collect_files_filtered(
+    path: &str,
+    config: &Config,
+    root: &Path,
+    changed: Option<&str>,
+    include: &[String],
+    exclude: &[String],
+) -> Result<Vec<PathBuf>> {
+    let files = collect_files(path, config)?;
+    let files = filter_by_glob(files, root, include, exclude)?;
+
+    let Some(base) = changed else {
+        return Ok(files);
+    };
+
+    let Some(changed_set) = changed_files(root, base)? else {
+        println!(
+            "{} Not a git repository — ignoring --changed and processing the full tree",
+            "!".yellow()
+        );
+        return Ok(files);
+    };
+    Ok(files
+        .into_iter()
+        .filter(|f| changed_set.contains(f))
+        .collect())
+}
+
+/// Narrow `files` to those whose path relative to `root` passes a
+/// [`PathFilter`] built from `include`/`exclude`. A no-op (no allocation, no
+/// `GlobSet` compiled) when both are empty.
+fn filter_by_glob(
+    files: Vec<PathBuf>,
+    root: &Path,
+    include: &[String],
+    exclude: &[String],
+) -> Result<Vec<PathBuf>> {
+    if include.is_empty() && exclude.is_empty() {
+        return Ok(files);
+    }
+    let filter = PathFilter::new(include, exclude)?;
+    Ok(files
+        .into_iter()
+        .filter(|f| filter.matches(f.strip_prefix(root).unwrap_or(f)))
+        .collect())
+}
+
+/// Compiled `--include`/`--exclude` globs, matched against paths relative to
+/// `resolve_root` — a per-invocation filter layered on top of
+/// [`collect_files`]'s walk, distinct from [`Config::include`]/[`Config::exclude`]'s
+/// persisted `.fmmrc.json` equivalents. An exclude match always wins over an
+/// include match; an empty include set means "no restriction", matching
+/// today's behavior.
+struct PathFilter {
+    include: Option<globset::GlobSet>,
+    exclude: Option<globset::GlobSet>,
+}
+
+impl PathFilter {
+    fn new(include: &[String], exclude: &[String]) -> Result<Self> {
+        Ok(Self {
+            include: Self::build(include)?,
+            exclude: Self::build(exclude)?,
+        })
+    }
+
+    fn build(patterns: &[String]) -> Result<Option<globset::GlobSet>> {
+        if patterns.is_empty() {
+            return Ok(None);
+        }
+        let mut builder = globset::GlobSetBuilder::new();
+        for pattern in patterns {
+            builder.add(globset::Glob::new(pattern)?);
+        }
+        Ok(Some(builder.build()?))
+    }
+
+    fn matches(&self, rel_path: &Path) -> bool {
+        if let Some(exclude) = &self.exclude {
+            if exclude.is_match(rel_path) {
+                return false;
+            }
+        }
+        match &self.include {
+            Some(include) => include.is_match(rel_path),
+            None => true,
+        }
+    }
+}
+
+/// Files changed relative to `base` inside the git repo rooted at `root`:
+/// the union of `git diff --name-only` (tracked, modified) and
+/// `git ls-files --others --exclude-standard` (untracked), resolved to
+/// canonical absolute paths for comparison against [`collect_files`]'s output.
+///
+/// Returns `Ok(None)` when `root` isn't inside a git work tree at all, so a
+/// caller can fall back to the usual full-tree walk instead of failing
+/// outright — `--changed` is a speed-up, not a requirement.
+fn changed_files(root: &Path, base: &str) -> Result<Option<std::collections::HashSet<PathBuf>>> {
+    let inside_work_tree = std::process::Command::new("git")
+        .args(["rev-parse", "--is-inside-work-tree"])
+        .current_dir(root)
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false);
+    if !inside_work_tree {
+        return Ok(None);
+    }
+
+    let mut changed = std::collections::HashSet::new();
+
+    let diff = std::process::Command::new("git")
+        .args(["diff", "--name-only", base])
+        .current_dir(root)
+        .output()
+        .context("Failed to run git diff — is this a git repository?")?;
+    if !diff.status.success() {
+        anyhow::bail!(
+            "git diff --name-only {} failed: {}",
+            base,
+            String::from_utf8_lossy(&diff.stderr)
+        );
+    }
+    for line in String::from_utf8_lossy(&diff.stdout).lines() {
+        changed.insert(root.join(line));
+    }
+
+    let untracked = std::process::Command::new("git")
+        .args(["ls-files", "--others", "--exclude-standard"])
+        .current_dir(root)
+        .output()
+        .context("Failed to list untracked files")?;
+    if untracked.status.success() {
+        for line in String::from_utf8_lossy(&untracked.stdout).lines() {
+            changed.insert(root.join(line));
+        }
+    }
+
+    Ok(Some(
+        changed
+            .into_iter()
+            .filter_map(|p| p.canonicalize().ok())
+            .collect(),
+    ))
+}
+
+/// Search result for JSON output
+#[derive(serde::Serialize)]
+struct SearchResult {
+    file: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exports: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    imports: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dependencies: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    loc: Option<usize>,
+}
+
+/// Up to 3 entries of `candidates` closest to `name` by
+/// [`crate::levenshtein::levenshtein_distance`], within a typo-tolerance of
+/// `max(2, name.len() / 3)`. Ties broken alphabetically so results are
+/// stable across runs.
+fn suggest_similar<'a>(name: &str, candidates: impl Iterator<Item = &'a String>) -> Vec<&'a str> {
+    let max_distance = (name.len() / 3).max(2);
+
+    let mut scored: Vec<(usize, &str)> = candidates
+        .map(|c| (crate::levenshtein::levenshtein_distance(name, c), c.as_str()))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .collect();
+
+    scored.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(b.1)));
+    scored.truncate(3);
+    scored.into_iter().map(|(_, name)| name).collect()
+}
+
+/// The known subcommand name closest to `attempted`, if any is within
+/// [`suggest_similar`]'s typo-tolerance — cargo-style "did you mean" for a
+/// mistyped subcommand (`fmm genrate`), used by `main` to turn clap's bare
+/// "unrecognized subcommand" error into an actionable hint.
+pub fn suggest_subcommand(attempted: &str) -> Option<String> {
+    let known: Vec<String> = Cli::command()
+        .get_subcommands()
+        .map(|s| s.get_name().to_string())
+        .collect();
+    suggest_similar(attempted, known.iter())
+        .into_iter()
+        .next()
+        .map(|s| s.to_string())
+}
+
+/// Parsed result of expanding a [`crate::config::Config::search_aliases`]
+/// entry: the same four filters `search`'s flags accept, always returned as
+/// `(export, imports, loc, depends_on)` wherever each flag appears in the
+/// expansion string. An alias may set any subset of them; an unset filter is
+/// `None`.
+fn parse_search_alias(expansion: &str) -> (Option<String>, Option<String>, Option<String>, Option<String>) {
+    let mut export = None;
+    let mut imports = None;
+    let mut loc = None;
+    let mut depends_on = None;
+
+    let tokens: Vec<&str> = expansion.split_whitespace().collect();
+    let mut i = 0;
+    while i < tokens.len() {
+        let value = tokens.get(i + 1).map(|s| s.to_string());
+        match tokens[i] {
+            "--export" | "-e" => export = value,
+            "--imports" | "-i" => imports = value,
+            "--loc" | "-l" => loc = value,
+            "--depends-on" | "-d" => depends_on = value,
+            _ => {
+                i += 1;
+                continue;
+            }
+        }
+        i += 2;
+    }
+
+    (export, imports, loc, depends_on)
+}
+
+pub fn search(
+    query: Option<String>,
+    export: Option<String>,
+    imports: Option<String>,
+    loc: Option<String>,
+    depends_on: Option<String>,
+    json_output: bool,
+    pick: bool,
+) -> Result<()> {
+    let root = std::env::current_dir()?;
+    let config = crate::config::Config::load_from_dir(&root).unwrap_or_default();
+
+    let (alias_export, alias_imports, alias_loc, alias_depends_on) = query
+        .as_deref()
+        .and_then(|name| config.search_aliases.get(name))
+        .map(|expansion| parse_search_alias(expansion))
+        .unwrap_or_default();
+
+    let export = export.or(alias_export);
+    let imports = imports.or(alias_imports);
+    let loc = loc.or(alias_loc);
+    let depends_on = depends_on.or(alias_depends_on);
+
+    let manifest = crate::manifest::Manifest::load_from_sidecars(&root)?;
+
+    if manifest.files.is_empty() {
         println!(
             "{} No .fmm sidecars found in the current directory",
             "!".yellow()
@@ -1202,65 +3092,313 @@ pub fn search(
 
     if json_output {
         println!("{}", serde_json::to_string_pretty(&results)?);
+    } else if pick && results.len() > 1 {
+        match pick_result(&results)? {
+            Some(result) => print_search_result(result),
+            None => println!("{} No file selected", "!".yellow()),
+        }
     } else if results.is_empty() {
         println!("{} No matches found", "!".yellow());
-        if export.is_some() {
-            println!(
-                "\n  {} Export names are case-sensitive. Try 'fmm search' with no filters to list all indexed files",
-                "hint:".cyan()
-            );
+
+        if let Some(ref export_name) = export {
+            let suggestions = manifest.suggest_exports(export_name, 3);
+            if !suggestions.is_empty() {
+                let suggestions: Vec<&str> = suggestions.iter().map(|(name, _)| name.as_str()).collect();
+                println!("\n  {} did you mean: {}?", "hint:".cyan(), suggestions.join(", "));
+            } else {
+                println!(
+                    "\n  {} Export names are case-sensitive. Try 'fmm search' with no filters to list all indexed files",
+                    "hint:".cyan()
+                );
+            }
+        }
+
+        if let Some(ref import_name) = imports {
+            let all_imports: Vec<String> = manifest
+                .files
+                .values()
+                .flat_map(|entry| entry.imports.iter().cloned())
+                .collect();
+            let suggestions = suggest_similar(import_name, all_imports.iter());
+            if !suggestions.is_empty() {
+                println!("\n  {} did you mean: {}?", "hint:".cyan(), suggestions.join(", "));
+            }
+        }
+
+        if let Some(ref dep_path) = depends_on {
+            let all_deps: Vec<String> = manifest
+                .files
+                .values()
+                .flat_map(|entry| entry.dependencies.iter().cloned())
+                .collect();
+            let suggestions = suggest_similar(dep_path, all_deps.iter());
+            if !suggestions.is_empty() {
+                println!("\n  {} did you mean: {}?", "hint:".cyan(), suggestions.join(", "));
+            }
         }
     } else {
         println!("{} {} file(s) found:\n", "✓".green(), results.len());
         for result in &results {
-            println!("{}", result.file.white().bold());
-            if let Some(ref exports) = result.exports {
-                if !exports.is_empty() {
-                    println!("  {} {}", "exports:".dimmed(), exports.join(", "));
-                }
-            }
-            if let Some(ref imports) = result.imports {
-                if !imports.is_empty() {
-                    println!("  {} {}", "imports:".dimmed(), imports.join(", "));
-                }
-            }
-            if let Some(loc_val) = result.loc {
-                println!("  {} {}", "loc:".dimmed(), loc_val);
-            }
-            println!();
+            print_search_result(result);
         }
     }
 
     Ok(())
 }
 
-fn parse_loc_expr(expr: &str) -> Result<(String, usize)> {
-    let expr = expr.trim();
-
-    if let Some(rest) = expr.strip_prefix(">=") {
-        let value: usize = rest.trim().parse().context("Invalid LOC value")?;
-        Ok((">=".to_string(), value))
-    } else if let Some(rest) = expr.strip_prefix("<=") {
-        let value: usize = rest.trim().parse().context("Invalid LOC value")?;
-        Ok(("<=".to_string(), value))
-    } else if let Some(rest) = expr.strip_prefix('>') {
-        let value: usize = rest.trim().parse().context("Invalid LOC value")?;
-        Ok((">".to_string(), value))
-    } else if let Some(rest) = expr.strip_prefix('<') {
-        let value: usize = rest.trim().parse().context("Invalid LOC value")?;
-        Ok(("<".to_string(), value))
-    } else if let Some(rest) = expr.strip_prefix('=') {
-        let value: usize = rest.trim().parse().context("Invalid LOC value")?;
-        Ok(("=".to_string(), value))
-    } else {
-        let value: usize = expr
-            .parse()
-            .context("Invalid LOC expression. Use: >500, <100, =200, >=50, <=1000")?;
-        Ok(("=".to_string(), value))
+/// Print one [`SearchResult`] the way the non-`--json` branch of [`search`]
+/// lists results, factored out so `--pick`'s single selected result renders
+/// identically to a row in the full list.
+fn print_search_result(result: &SearchResult) {
+    println!("{}", result.file.white().bold());
+    if let Some(ref exports) = result.exports {
+        if !exports.is_empty() {
+            println!("  {} {}", "exports:".dimmed(), exports.join(", "));
+        }
+    }
+    if let Some(ref imports) = result.imports {
+        if !imports.is_empty() {
+            println!("  {} {}", "imports:".dimmed(), imports.join(", "));
+        }
+    }
+    if let Some(loc_val) = result.loc {
+        println!("  {} {}", "loc:".dimmed(), loc_val);
     }
+    println!();
 }
 
-fn matches_loc_filter(loc: usize, op: &str, value: usize) -> bool {
+/// Interactively narrow `results` down to one for `--pick`: each prompt
+/// either selects a numbered row directly, or filters the candidate list to
+/// rows whose path contains the typed text, looping until exactly one
+/// remains. Returns `None` if stdin closes before that happens (e.g. piped,
+/// non-interactive use), so callers never hang waiting on a TTY that isn't
+/// there.
+fn pick_result(results: &[SearchResult]) -> Result<Option<&SearchResult>> {
+    use std::io::{BufRead, Write};
+
+    let mut candidates: Vec<&SearchResult> = results.iter().collect();
+    let stdin = std::io::stdin();
+
+    loop {
+        if candidates.len() == 1 {
+            return Ok(Some(candidates[0]));
+        }
+
+        println!(
+            "{} {} matches — narrow it down:\n",
+            "?".cyan(),
+            candidates.len()
+        );
+        for (i, result) in candidates.iter().enumerate() {
+            println!("  {} {}", format!("[{}]", i + 1).dimmed(), result.file);
+        }
+        print!("\n{} ", "pick (number or text to filter):".cyan());
+        std::io::stdout().flush()?;
+
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let input = line.trim();
+        if input.is_empty() {
+            continue;
+        }
+
+        if let Ok(index) = input.parse::<usize>() {
+            if index >= 1 && index <= candidates.len() {
+                return Ok(Some(candidates[index - 1]));
+            }
+            println!("{} {} is out of range\n", "!".yellow(), index);
+            continue;
+        }
+
+        let narrowed: Vec<&SearchResult> = candidates
+            .iter()
+            .copied()
+            .filter(|r| r.file.contains(input))
+            .collect();
+        if narrowed.is_empty() {
+            println!("{} no matches contain \"{}\"\n", "!".yellow(), input);
+            continue;
+        }
+        candidates = narrowed;
+    }
+}
+
+pub fn check(json_output: bool) -> Result<()> {
+    let root = std::env::current_dir()?;
+    let manifest = crate::manifest::Manifest::load_from_sidecars(&root)?;
+
+    if manifest.files.is_empty() {
+        println!(
+            "{} No .fmm sidecars found in the current directory",
+            "!".yellow()
+        );
+        println!(
+            "\n  {} fmm check resolves sidecar metadata. Run 'fmm generate' first to create it",
+            "hint:".cyan()
+        );
+        return Ok(());
+    }
+
+    let unresolved = unresolved_imports(&manifest);
+    let edges = resolved_edges(&manifest);
+    let cycles = find_cycles(&edges);
+
+    if json_output {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "unresolved": unresolved.iter().map(|u| serde_json::json!({
+                    "importer": u.importer,
+                    "spec": u.spec,
+                })).collect::<Vec<_>>(),
+                "cycles": cycles,
+            }))?
+        );
+        return Ok(());
+    }
+
+    if unresolved.is_empty() && cycles.is_empty() {
+        println!("{} No unresolved imports or dependency cycles", "✓".green());
+        return Ok(());
+    }
+
+    if !unresolved.is_empty() {
+        println!(
+            "{} {} unresolved import(s):\n",
+            "✗".red().bold(),
+            unresolved.len()
+        );
+        for u in &unresolved {
+            println!("  {} -> {}", u.importer.white().bold(), u.spec.dimmed());
+        }
+        println!();
+    }
+
+    if !cycles.is_empty() {
+        println!("{} {} dependency cycle(s):\n", "✗".red().bold(), cycles.len());
+        for cycle in &cycles {
+            println!("  {}", cycle.join(" -> ").dimmed());
+        }
+        println!();
+    }
+
+    anyhow::bail!("Dependency check failed");
+}
+
+pub fn graph(json_output: bool) -> Result<()> {
+    let root = std::env::current_dir()?;
+    let manifest = crate::manifest::Manifest::load_from_sidecars(&root)?;
+
+    if manifest.files.is_empty() {
+        println!(
+            "{} No .fmm sidecars found in the current directory",
+            "!".yellow()
+        );
+        println!(
+            "\n  {} fmm graph resolves sidecar metadata. Run 'fmm generate' first to create it",
+            "hint:".cyan()
+        );
+        return Ok(());
+    }
+
+    let edges = resolved_edges(&manifest);
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&edges)?);
+        return Ok(());
+    }
+
+    let mut paths: Vec<&String> = edges.keys().collect();
+    paths.sort();
+
+    for path in paths {
+        let targets = &edges[path];
+        println!("{}", path.white().bold());
+        if targets.is_empty() {
+            println!("  {}", "(no resolved dependencies)".dimmed());
+        } else {
+            for target in targets {
+                println!("  {} {}", "->".dimmed(), target);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Part of `fmm validate --dependents`: prints every file that transitively
+/// depends on `changed_path`, by walking [`reverse_edges`]'s graph — so a
+/// developer can see what else might need a second look after editing a
+/// widely-imported module. Unlike [`search`]'s `--depends-on` (a substring
+/// match over raw, unresolved `dependencies` specs), this matches exactly
+/// against resolved file paths, since it walks the same resolved graph
+/// [`check`] and [`graph`] do.
+fn report_dependents(root: &Path, changed_path: &str) -> Result<()> {
+    let manifest = crate::manifest::Manifest::load_from_sidecars(root)?;
+
+    if !manifest.files.contains_key(changed_path) {
+        println!(
+            "\n{} {} is not a known file in the manifest",
+            "!".yellow(),
+            changed_path
+        );
+        let suggestions = suggest_similar(changed_path, manifest.files.keys());
+        if !suggestions.is_empty() {
+            println!("  {} did you mean: {}?", "hint:".cyan(), suggestions.join(", "));
+        }
+        return Ok(());
+    }
+
+    let edges = resolved_edges(&manifest);
+    let reverse = reverse_edges(&edges);
+    let dependents = dependents_of(&reverse, changed_path);
+
+    if dependents.is_empty() {
+        println!("\n{} No files depend on {}", "✓".green(), changed_path);
+    } else {
+        println!(
+            "\n{} {} file(s) depend on {}:",
+            "!".yellow(),
+            dependents.len(),
+            changed_path
+        );
+        for dependent in &dependents {
+            println!("  {} {}", "<-".dimmed(), dependent);
+        }
+    }
+
+    Ok(())
+}
+
+fn parse_loc_expr(expr: &str) -> Result<(String, usize)> {
+    let expr = expr.trim();
+
+    if let Some(rest) = expr.strip_prefix(">=") {
+        let value: usize = rest.trim().parse().context("Invalid LOC value")?;
+        Ok((">=".to_string(), value))
+    } else if let Some(rest) = expr.strip_prefix("<=") {
+        let value: usize = rest.trim().parse().context("Invalid LOC value")?;
+        Ok(("<=".to_string(), value))
+    } else if let Some(rest) = expr.strip_prefix('>') {
+        let value: usize = rest.trim().parse().context("Invalid LOC value")?;
+        Ok((">".to_string(), value))
+    } else if let Some(rest) = expr.strip_prefix('<') {
+        let value: usize = rest.trim().parse().context("Invalid LOC value")?;
+        Ok(("<".to_string(), value))
+    } else if let Some(rest) = expr.strip_prefix('=') {
+        let value: usize = rest.trim().parse().context("Invalid LOC value")?;
+        Ok(("=".to_string(), value))
+    } else {
+        let value: usize = expr
+            .parse()
+            .context("Invalid LOC expression. Use: >500, <100, =200, >=50, <=1000")?;
+        Ok(("=".to_string(), value))
+    }
+}
+
+fn matches_loc_filter(loc: usize, op: &str, value: usize) -> bool {
     match op {
         ">" => loc > value,
         "<" => loc < value,
@@ -1284,6 +3422,42 @@ mod tests {
         assert!(result.is_absolute());
     }
 
+    #[test]
+    fn generate_man_pages_writes_fmm_1_to_out_dir() {
+        let tmp = TempDir::new().unwrap();
+        let count = generate_man_pages(tmp.path()).unwrap();
+        assert!(count >= 1);
+        assert!(tmp.path().join("fmm.1").exists());
+    }
+
+    #[test]
+    fn tidy_artifacts_cover_docs_man_and_all_completion_shells() {
+        let artifacts = tidy_artifacts().unwrap();
+        let paths: Vec<_> = artifacts.iter().map(|a| a.path.clone()).collect();
+
+        assert!(paths.contains(&PathBuf::from(TIDY_MARKDOWN_HELP_PATH)));
+        assert!(paths.contains(&PathBuf::from(TIDY_MAN_PAGE_PATH)));
+        for suffix in ["bash", "zsh", "fish", "ps1"] {
+            assert!(paths.contains(&Path::new(TIDY_COMPLETION_DIR).join(format!("fmm.{suffix}"))));
+        }
+        assert!(artifacts.iter().all(|a| !a.generated.is_empty()));
+    }
+
+    #[test]
+    fn print_line_diff_reports_no_output_for_identical_text() {
+        // Nothing to assert on stdout directly — this just documents that
+        // identical input is a no-op and doesn't panic.
+        print_line_diff("same\ntext\n", "same\ntext\n");
+    }
+
+    #[test]
+    fn generate_completions_writes_nonempty_script() {
+        let mut buf: Vec<u8> = Vec::new();
+        generate_completions(Shell::Bash, &mut buf);
+        assert!(!buf.is_empty());
+        assert!(String::from_utf8(buf).unwrap().contains("fmm"));
+    }
+
     #[test]
     fn resolve_root_with_relative_directory() {
         let result = resolve_root(".").unwrap();
@@ -1340,4 +3514,508 @@ mod tests {
         assert!(files[0].is_absolute());
         assert_eq!(files[0], file_path.canonicalize().unwrap());
     }
+
+    #[test]
+    fn collect_files_respects_config_exclude_glob() {
+        let tmp = TempDir::new().unwrap();
+        let vendor = tmp.path().join("vendor");
+        std::fs::create_dir_all(&vendor).unwrap();
+        std::fs::write(vendor.join("lib.ts"), "export const a = 1;").unwrap();
+        std::fs::write(tmp.path().join("app.ts"), "export const b = 2;").unwrap();
+
+        let mut config = Config::default();
+        config.exclude = vec!["vendor/**".to_string()];
+        let files = collect_files(tmp.path().to_str().unwrap(), &config).unwrap();
+
+        assert!(files.iter().any(|f| f.ends_with("app.ts")));
+        assert!(!files.iter().any(|f| f.ends_with("vendor/lib.ts")));
+    }
+
+    #[test]
+    fn collect_files_include_glob_whitelists_matching_paths() {
+        let tmp = TempDir::new().unwrap();
+        let vendor = tmp.path().join("vendor");
+        std::fs::create_dir_all(&vendor).unwrap();
+        std::fs::write(vendor.join("keep.ts"), "export const a = 1;").unwrap();
+        std::fs::write(vendor.join("skip.ts"), "export const b = 2;").unwrap();
+
+        let mut config = Config::default();
+        config.include = vec!["vendor/keep.ts".to_string()];
+        let files = collect_files(tmp.path().to_str().unwrap(), &config).unwrap();
+
+        assert!(files.iter().any(|f| f.ends_with("vendor/keep.ts")));
+        assert!(!files.iter().any(|f| f.ends_with("vendor/skip.ts")));
+    }
+
+    fn run_git(root: &Path, args: &[&str]) {
+        let status = std::process::Command::new("git")
+            .args(args)
+            .current_dir(root)
+            .status()
+            .expect("failed to run git");
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    fn init_repo(root: &Path) {
+        run_git(root, &["init", "-q"]);
+        run_git(root, &["config", "user.email", "test@example.com"]);
+        run_git(root, &["config", "user.name", "Test"]);
+    }
+
+    #[test]
+    fn changed_files_includes_modified_and_untracked() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path().canonicalize().unwrap();
+        init_repo(&root);
+
+        std::fs::write(root.join("tracked.ts"), "export const a = 1;").unwrap();
+        std::fs::write(root.join("untouched.ts"), "export const b = 2;").unwrap();
+        run_git(&root, &["add", "-A"]);
+        run_git(&root, &["commit", "-q", "-m", "initial"]);
+
+        std::fs::write(root.join("tracked.ts"), "export const a = 2;").unwrap();
+        std::fs::write(root.join("new.ts"), "export const c = 3;").unwrap();
+
+        let changed = changed_files(&root, "HEAD").unwrap().unwrap();
+
+        assert!(changed.contains(&root.join("tracked.ts")));
+        assert!(changed.contains(&root.join("new.ts")));
+        assert!(!changed.contains(&root.join("untouched.ts")));
+    }
+
+    #[test]
+    fn changed_files_returns_none_outside_a_git_repo() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path().canonicalize().unwrap();
+        assert!(changed_files(&root, "HEAD").unwrap().is_none());
+    }
+
+    #[test]
+    fn collect_files_filtered_falls_back_to_full_tree_outside_a_git_repo() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path().canonicalize().unwrap();
+        std::fs::write(root.join("a.ts"), "export const a = 1;").unwrap();
+
+        let config = Config::default();
+        let files = collect_files_filtered(
+            root.to_str().unwrap(),
+            &config,
+            &root,
+            Some("HEAD"),
+            &[],
+            &[],
+        )
+        .unwrap();
+
+        assert!(files.iter().any(|f| f.ends_with("a.ts")));
+    }
+
+    #[test]
+    fn collect_files_filtered_without_changed_returns_everything() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path().canonicalize().unwrap();
+        std::fs::write(root.join("a.ts"), "export const a = 1;").unwrap();
+        std::fs::write(root.join("b.ts"), "export const b = 2;").unwrap();
+
+        let config = Config::default();
+        let files =
+            collect_files_filtered(root.to_str().unwrap(), &config, &root, None, &[], &[])
+                .unwrap();
+
+        assert_eq!(files.len(), 2);
+    }
+
+    #[test]
+    fn collect_files_filtered_with_changed_narrows_to_diff() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path().canonicalize().unwrap();
+        init_repo(&root);
+
+        std::fs::write(root.join("a.ts"), "export const a = 1;").unwrap();
+        std::fs::write(root.join("b.ts"), "export const b = 2;").unwrap();
+        run_git(&root, &["add", "-A"]);
+        run_git(&root, &["commit", "-q", "-m", "initial"]);
+
+        std::fs::write(root.join("a.ts"), "export const a = 2;").unwrap();
+
+        let config = Config::default();
+        let files = collect_files_filtered(
+            root.to_str().unwrap(),
+            &config,
+            &root,
+            Some("HEAD"),
+            &[],
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(files, vec![root.join("a.ts").canonicalize().unwrap()]);
+    }
+
+    #[test]
+    fn collect_files_filtered_cli_include_glob_whitelists_matching_paths() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path().canonicalize().unwrap();
+        std::fs::write(root.join("a.ts"), "export const a = 1;").unwrap();
+        std::fs::write(root.join("b.ts"), "export const b = 2;").unwrap();
+
+        let config = Config::default();
+        let files = collect_files_filtered(
+            root.to_str().unwrap(),
+            &config,
+            &root,
+            None,
+            &["a.ts".to_string()],
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(files, vec![root.join("a.ts").canonicalize().unwrap()]);
+    }
+
+    #[test]
+    fn collect_files_filtered_cli_exclude_wins_over_include() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path().canonicalize().unwrap();
+        std::fs::write(root.join("a.ts"), "export const a = 1;").unwrap();
+
+        let config = Config::default();
+        let files = collect_files_filtered(
+            root.to_str().unwrap(),
+            &config,
+            &root,
+            None,
+            &["*.ts".to_string()],
+            &["a.ts".to_string()],
+        )
+        .unwrap();
+
+        assert!(files.is_empty());
+    }
+
+    #[test]
+    fn classify_xy_detects_conflicts_staged_and_modified() {
+        assert!(matches!(classify_xy("AA"), GitEntryKind::Conflicted));
+        assert!(matches!(classify_xy("DD"), GitEntryKind::Conflicted));
+        assert!(matches!(classify_xy("UU"), GitEntryKind::Conflicted));
+        assert!(matches!(classify_xy("M."), GitEntryKind::Staged));
+        assert!(matches!(classify_xy(".M"), GitEntryKind::Modified));
+    }
+
+    #[test]
+    fn sidecar_health_buckets_staged_modified_and_untracked() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path().canonicalize().unwrap();
+        init_repo(&root);
+
+        std::fs::write(root.join("staged.ts"), "export const a = 1;").unwrap();
+        std::fs::write(root.join("modified.ts"), "export const b = 1;").unwrap();
+        run_git(&root, &["add", "-A"]);
+        run_git(&root, &["commit", "-q", "-m", "initial"]);
+
+        std::fs::write(root.join("modified.ts"), "export const b = 2;").unwrap();
+        std::fs::write(root.join("staged.ts"), "export const a = 2;").unwrap();
+        run_git(&root, &["add", "staged.ts"]);
+        std::fs::write(root.join("untracked.ts"), "export const c = 1;").unwrap();
+
+        let sources = vec![
+            root.join("staged.ts"),
+            root.join("modified.ts"),
+            root.join("untracked.ts"),
+        ];
+        for source in &sources {
+            std::fs::write(sidecar_path_for(source), "exports: []\n").unwrap();
+        }
+
+        let health = sidecar_health(&root, &sources).expect("git status should succeed");
+
+        assert_eq!(health.staged, 1);
+        assert_eq!(health.modified, 1);
+        assert_eq!(health.untracked, 1);
+        assert_eq!(health.conflicted, 0);
+    }
+
+    #[test]
+    fn sidecar_health_flags_stale_sidecar_via_mtime() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path().canonicalize().unwrap();
+        init_repo(&root);
+
+        let source = root.join("a.ts");
+        std::fs::write(&source, "export const a = 1;").unwrap();
+        std::fs::write(sidecar_path_for(&source), "exports: []\n").unwrap();
+        run_git(&root, &["add", "-A"]);
+        run_git(&root, &["commit", "-q", "-m", "initial"]);
+
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        std::fs::write(&source, "export const a = 1;").unwrap();
+        run_git(&root, &["add", "-A"]);
+        run_git(&root, &["commit", "-q", "--allow-empty", "-m", "touch"]);
+
+        let health = sidecar_health(&root, &[source]).expect("git status should succeed");
+        assert_eq!(health.stale, 1);
+    }
+
+    #[test]
+    fn count_orphaned_sidecars_finds_dot_fmm_files_without_a_source() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path().canonicalize().unwrap();
+
+        std::fs::write(root.join("gone.ts.fmm"), "exports: []\n").unwrap();
+        std::fs::write(root.join("present.ts"), "export const a = 1;").unwrap();
+        std::fs::write(root.join("present.ts.fmm"), "exports: []\n").unwrap();
+
+        assert_eq!(count_orphaned_sidecars(&root), 1);
+    }
+
+    #[test]
+    fn parse_fence_open_recognizes_backtick_fence_with_language() {
+        let fence = parse_fence_open("  ```rust").unwrap();
+        assert_eq!(fence.indent, 2);
+        assert_eq!(fence.ch, '`');
+        assert_eq!(fence.len, 3);
+        assert_eq!(fence.lang.as_deref(), Some("rust"));
+    }
+
+    #[test]
+    fn parse_fence_open_rejects_short_fence_and_non_fence_lines() {
+        assert!(parse_fence_open("``not a fence").is_none());
+        assert!(parse_fence_open("plain text").is_none());
+    }
+
+    #[test]
+    fn is_fence_close_requires_same_indent_and_fence_char() {
+        let open = parse_fence_open("  ```rust").unwrap();
+        assert!(is_fence_close("  ```", &open));
+        assert!(!is_fence_close("```", &open), "different indentation");
+        assert!(!is_fence_close("  ~~~", &open), "different fence char");
+        assert!(!is_fence_close("  ``", &open), "too few fence chars");
+    }
+
+    #[test]
+    fn format_fenced_blocks_leaves_block_untouched_without_configured_formatter() {
+        let content = "# doc\n\n```rust\nfn  f( ) {}\n```\n";
+        let outcome = format_fenced_blocks(content, &std::collections::HashMap::new());
+        assert_eq!(outcome.content, content);
+        assert!(outcome.errors.is_empty());
+    }
+
+    #[test]
+    fn format_fenced_blocks_pipes_body_through_configured_formatter() {
+        let content = "```demo\nhello\n```\n";
+        let mut formatters = std::collections::HashMap::new();
+        formatters.insert("demo".to_string(), "tr a-z A-Z".to_string());
+
+        let outcome = format_fenced_blocks(content, &formatters);
+
+        assert_eq!(outcome.content, "```demo\nHELLO\n```\n");
+        assert!(outcome.errors.is_empty());
+    }
+
+    #[test]
+    fn format_fenced_blocks_preserves_indentation_of_nested_blocks() {
+        let content = "- item\n  ```demo\n  hello\n  ```\n";
+        let mut formatters = std::collections::HashMap::new();
+        formatters.insert("demo".to_string(), "tr a-z A-Z".to_string());
+
+        let outcome = format_fenced_blocks(content, &formatters);
+
+        assert_eq!(outcome.content, "- item\n  ```demo\n  HELLO\n  ```\n");
+    }
+
+    fn write_failing_formatter_script(tmp: &TempDir) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+
+        let script = tmp.path().join("fail.sh");
+        std::fs::write(&script, "#!/bin/sh\necho broken >&2\nexit 1\n").unwrap();
+        let mut perms = std::fs::metadata(&script).unwrap().permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(&script, perms).unwrap();
+        script
+    }
+
+    #[test]
+    fn format_fenced_blocks_reports_stderr_and_leaves_body_when_formatter_fails() {
+        let tmp = TempDir::new().unwrap();
+        let script = write_failing_formatter_script(&tmp);
+
+        let content = "```demo\nhello\n```\n";
+        let mut formatters = std::collections::HashMap::new();
+        formatters.insert("demo".to_string(), script.to_str().unwrap().to_string());
+
+        let outcome = format_fenced_blocks(content, &formatters);
+
+        assert_eq!(outcome.content, content);
+        assert_eq!(outcome.errors.len(), 1);
+        assert_eq!(outcome.errors[0].0, "demo");
+        assert!(outcome.errors[0].1.contains("broken"));
+    }
+
+    #[test]
+    fn format_fenced_blocks_leaves_unterminated_fence_untouched() {
+        let content = "```rust\nfn f() {}\n";
+        let outcome = format_fenced_blocks(content, &std::collections::HashMap::new());
+        assert_eq!(outcome.content, content);
+    }
+
+    #[test]
+    fn run_formatter_returns_trimmed_stdout() {
+        let result = run_formatter("tr a-z A-Z", "hello\n").unwrap();
+        assert_eq!(result, "HELLO");
+    }
+
+    #[test]
+    fn run_formatter_returns_stderr_on_nonzero_exit() {
+        let tmp = TempDir::new().unwrap();
+        let script = write_failing_formatter_script(&tmp);
+
+        let err = run_formatter(script.to_str().unwrap(), "hello").unwrap_err();
+        assert!(err.contains("broken"));
+    }
+
+    fn args(s: &str) -> Vec<String> {
+        std::iter::once("fmm".to_string())
+            .chain(s.split_whitespace().map(str::to_string))
+            .collect()
+    }
+
+    #[test]
+    fn expand_aliases_splices_a_string_alias() {
+        let mut config = Config::default();
+        config
+            .aliases
+            .insert("gen".to_string(), crate::config::AliasValue::Single("generate src/ -n".to_string()));
+
+        let expanded = expand_aliases(args("gen"), &config).unwrap();
+        assert_eq!(expanded, args("generate src/ -n"));
+    }
+
+    #[test]
+    fn expand_aliases_keeps_explicit_flags_after_alias_tokens() {
+        let mut config = Config::default();
+        config
+            .aliases
+            .insert("ci".to_string(), crate::config::AliasValue::Multiple(vec!["validate".to_string()]));
+
+        let expanded = expand_aliases(args("ci --strict"), &config).unwrap();
+        assert_eq!(expanded, args("validate --strict"));
+    }
+
+    #[test]
+    fn expand_aliases_leaves_known_subcommands_untouched() {
+        let mut config = Config::default();
+        config
+            .aliases
+            .insert("generate".to_string(), crate::config::AliasValue::Single("update".to_string()));
+
+        let expanded = expand_aliases(args("generate src/"), &config).unwrap();
+        assert_eq!(expanded, args("generate src/"));
+    }
+
+    #[test]
+    fn expand_aliases_rejects_self_referential_alias() {
+        let mut config = Config::default();
+        config
+            .aliases
+            .insert("gen".to_string(), crate::config::AliasValue::Single("gen".to_string()));
+
+        let err = expand_aliases(args("gen"), &config).unwrap_err();
+        assert!(err.to_string().contains("recursive"));
+    }
+
+    #[test]
+    fn expand_aliases_rejects_mutually_recursive_aliases() {
+        let mut config = Config::default();
+        config
+            .aliases
+            .insert("a".to_string(), crate::config::AliasValue::Single("b".to_string()));
+        config
+            .aliases
+            .insert("b".to_string(), crate::config::AliasValue::Single("a".to_string()));
+
+        let err = expand_aliases(args("a"), &config).unwrap_err();
+        assert!(err.to_string().contains("recursive"));
+    }
+
+    #[test]
+    fn suggest_similar_finds_close_typo_within_tolerance() {
+        let candidates = vec![
+            "createStore".to_string(),
+            "combineReducers".to_string(),
+            "applyMiddleware".to_string(),
+        ];
+        let suggestions = suggest_similar("createStoer", candidates.iter());
+        assert_eq!(suggestions, vec!["createStore"]);
+    }
+
+    #[test]
+    fn suggest_similar_excludes_candidates_beyond_tolerance() {
+        let candidates = vec!["combineReducers".to_string()];
+        let suggestions = suggest_similar("createStore", candidates.iter());
+        assert!(suggestions.is_empty());
+    }
+
+    #[test]
+    fn suggest_similar_caps_at_three_sorted_by_distance_then_name() {
+        let candidates = vec![
+            "foox".to_string(),
+            "fooz".to_string(),
+            "fooy".to_string(),
+            "fooa".to_string(),
+        ];
+        let suggestions = suggest_similar("foo", candidates.iter());
+        assert_eq!(suggestions, vec!["fooa", "foox", "fooy"]);
+    }
+
+    #[test]
+    fn suggest_subcommand_finds_a_close_typo() {
+        assert_eq!(suggest_subcommand("genrate"), Some("generate".to_string()));
+    }
+
+    #[test]
+    fn suggest_subcommand_returns_none_for_unrelated_input() {
+        assert_eq!(suggest_subcommand("xyzzy123"), None);
+    }
+
+    #[test]
+    fn parse_search_alias_reads_long_flags() {
+        let (export, imports, loc, depends_on) = parse_search_alias("--loc >500");
+        assert_eq!(export, None);
+        assert_eq!(imports, None);
+        assert_eq!(loc, Some(">500".to_string()));
+        assert_eq!(depends_on, None);
+    }
+
+    #[test]
+    fn parse_search_alias_reads_short_flags() {
+        let (export, imports, loc, depends_on) = parse_search_alias("-i react -e useState");
+        assert_eq!(export, Some("useState".to_string()));
+        assert_eq!(imports, Some("react".to_string()));
+        assert_eq!(loc, None);
+        assert_eq!(depends_on, None);
+    }
+
+    #[test]
+    fn parse_search_alias_reads_multiple_flags() {
+        let (export, imports, loc, depends_on) =
+            parse_search_alias("--imports react --depends-on src/utils.ts");
+        assert_eq!(export, None);
+        assert_eq!(imports, Some("react".to_string()));
+        assert_eq!(loc, None);
+        assert_eq!(depends_on, Some("src/utils.ts".to_string()));
+    }
+
+    #[test]
+    fn parse_search_alias_ignores_unknown_tokens() {
+        let (export, imports, loc, depends_on) = parse_search_alias("--bogus value --loc >10");
+        assert_eq!(export, None);
+        assert_eq!(imports, None);
+        assert_eq!(loc, Some(">10".to_string()));
+        assert_eq!(depends_on, None);
+    }
+
+    #[test]
+    fn parse_search_alias_on_empty_string_sets_nothing() {
+        assert_eq!(parse_search_alias(""), (None, None, None, None));
+    }
 }
@@ -1,8 +1,221 @@
 use anyhow::{Context, Result};
 use colored::Colorize;
+use ignore::overrides::{Override, OverrideBuilder};
+use rayon::prelude::*;
+use regex::Regex;
 
 use crate::manifest::{ExportLocation, Manifest};
 
+/// Below this file count, looping over `manifest.files` serially out-performs
+/// rayon's thread-pool dispatch overhead; above it (a monorepo-sized sidecar
+/// set), spreading the scan across cores wins.
+const PARALLEL_SCAN_THRESHOLD: usize = 2000;
+
+/// Collect the paths of files passing `predicate`, scanning in parallel via
+/// rayon once `manifest.files` is large enough that dispatch overhead pays
+/// for itself. Callers that need a deterministic order should sort the
+/// result — `par_iter` makes no ordering guarantee.
+fn scan_files<'a, F>(manifest: &'a Manifest, predicate: F) -> Vec<&'a String>
+where
+    F: Fn(&str, &crate::manifest::FileEntry) -> bool + Sync,
+{
+    if manifest.files.len() > PARALLEL_SCAN_THRESHOLD {
+        manifest
+            .files
+            .par_iter()
+            .filter(|(path, entry)| predicate(path, entry))
+            .map(|(path, _)| path)
+            .collect()
+    } else {
+        manifest
+            .files
+            .iter()
+            .filter(|(path, entry)| predicate(path, entry))
+            .map(|(path, _)| path)
+            .collect()
+    }
+}
+
+/// Built-in `--type` groups, mirroring ripgrep's `--type-list` table:
+/// lexicographically sorted by name, each mapping to the glob patterns that
+/// make up that language/file-type.
+const TYPE_GROUPS: &[(&str, &[&str])] = &[
+    ("go", &["*.go"]),
+    ("js", &["*.js", "*.jsx", "*.mjs"]),
+    ("py", &["*.py"]),
+    ("rust", &["*.rs"]),
+    ("ts", &["*.ts", "*.tsx"]),
+];
+
+/// A resolved `--type`/`--type-not` group surfaced in JSON output so callers
+/// can see exactly which globs a type name expanded to (built-in or
+/// `--type-add`).
+#[derive(serde::Serialize, Clone)]
+struct ResolvedType {
+    name: String,
+    globs: Vec<String>,
+}
+
+/// Wraps a `flag_search` JSON result with the `--type`/`--type-not` groups
+/// that were resolved for this search, so a caller scripting against the
+/// output can see what filter was actually applied. Only used when a type
+/// filter is active — without one, the JSON shape is unchanged.
+#[derive(serde::Serialize)]
+struct TypedResults<T: serde::Serialize> {
+    types: Vec<ResolvedType>,
+    results: T,
+}
+
+/// Compiled `--type`/`--type-not` filters, built once per search and
+/// consulted before any export/import/dependency/LOC matching runs.
+struct TypeFilter {
+    include: Option<Override>,
+    exclude: Option<Override>,
+    resolved: Vec<ResolvedType>,
+}
+
+impl TypeFilter {
+    fn matches(&self, path: &str) -> bool {
+        if let Some(include) = &self.include {
+            if !include.matched(path, false).is_whitelist() {
+                return false;
+            }
+        }
+        if let Some(exclude) = &self.exclude {
+            if exclude.matched(path, false).is_whitelist() {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Resolve a `--type`/`--type-not` name to its glob patterns: custom
+/// `--type-add` groups take precedence over the built-in table, the way
+/// ripgrep lets `--type-add` redefine a type name.
+fn resolve_type_globs<'a>(name: &str, custom: &'a [(String, Vec<String>)]) -> Option<Vec<String>> {
+    if let Some((_, globs)) = custom.iter().find(|(n, _)| n == name) {
+        return Some(globs.clone());
+    }
+    TYPE_GROUPS
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, globs)| globs.iter().map(|g| g.to_string()).collect())
+}
+
+/// Parse `--type-add name:glob,glob` definitions into `(name, globs)` pairs.
+fn parse_type_add(defs: &[String]) -> Result<Vec<(String, Vec<String>)>> {
+    defs.iter()
+        .map(|def| {
+            let (name, globs) = def
+                .split_once(':')
+                .with_context(|| format!("Invalid --type-add '{def}', expected name:glob,glob"))?;
+            Ok((
+                name.to_string(),
+                globs.split(',').map(|g| g.trim().to_string()).collect(),
+            ))
+        })
+        .collect()
+}
+
+fn build_override(name: &str, globs: &[String]) -> Result<Override> {
+    let mut builder = OverrideBuilder::new(".");
+    for glob in globs {
+        builder
+            .add(glob)
+            .with_context(|| format!("Invalid glob '{glob}' for type '{name}'"))?;
+    }
+    Ok(builder.build()?)
+}
+
+/// Build the combined `--type`/`--type-not` filter, resolving both against
+/// the built-in table and any `--type-add` groups. Returns `None` when
+/// neither flag was passed, meaning every file is allowed.
+fn build_type_filter(
+    type_name: Option<&str>,
+    type_not: Option<&str>,
+    type_add: &[String],
+) -> Result<Option<TypeFilter>> {
+    if type_name.is_none() && type_not.is_none() {
+        return Ok(None);
+    }
+
+    let custom = parse_type_add(type_add)?;
+    let mut resolved = Vec::new();
+
+    let include = type_name
+        .map(|name| {
+            let globs = resolve_type_globs(name, &custom)
+                .with_context(|| format!("Unknown --type '{name}'"))?;
+            resolved.push(ResolvedType {
+                name: name.to_string(),
+                globs: globs.clone(),
+            });
+            build_override(name, &globs)
+        })
+        .transpose()?;
+
+    let exclude = type_not
+        .map(|name| {
+            let globs = resolve_type_globs(name, &custom)
+                .with_context(|| format!("Unknown --type-not '{name}'"))?;
+            resolved.push(ResolvedType {
+                name: format!("not:{name}"),
+                globs: globs.clone(),
+            });
+            build_override(name, &globs)
+        })
+        .transpose()?;
+
+    Ok(Some(TypeFilter {
+        include,
+        exclude,
+        resolved,
+    }))
+}
+
+/// How `term`/`--export`/`--imports`/`--depends-on` values are matched
+/// against a candidate string: the existing substring check (case-sensitive
+/// or not, matching whatever the call site already did), or (with
+/// `--regex`) a compiled pattern, mirroring ripgrep's default-is-regex
+/// behavior. Plain patterns are case-sensitive unless the pattern itself
+/// carries an inline `(?i)` flag — `Regex::new` honors that already.
+enum TermMatcher {
+    Substring { term: String, case_insensitive: bool },
+    Regex(Regex),
+}
+
+impl TermMatcher {
+    fn new(term: &str, regex_mode: bool, case_insensitive: bool) -> Result<Self> {
+        if regex_mode {
+            let pattern =
+                Regex::new(term).with_context(|| format!("Invalid --regex pattern '{term}'"))?;
+            Ok(TermMatcher::Regex(pattern))
+        } else {
+            let term = if case_insensitive {
+                term.to_lowercase()
+            } else {
+                term.to_string()
+            };
+            Ok(TermMatcher::Substring {
+                term,
+                case_insensitive,
+            })
+        }
+    }
+
+    fn is_match(&self, candidate: &str) -> bool {
+        match self {
+            TermMatcher::Substring {
+                term,
+                case_insensitive: true,
+            } => candidate.to_lowercase().contains(term.as_str()),
+            TermMatcher::Substring { term, .. } => candidate.contains(term.as_str()),
+            TermMatcher::Regex(re) => re.is_match(candidate),
+        }
+    }
+}
+
 // -- JSON output types --
 
 #[derive(serde::Serialize)]
@@ -16,6 +229,8 @@ struct ExportMatch {
 /// JSON output for bare search (grouped by type)
 #[derive(serde::Serialize)]
 struct BareSearchResult {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    types: Vec<ResolvedType>,
     exports: Vec<ExportMatch>,
     files: Vec<String>,
     imports: Vec<ImportMatch>,
@@ -48,12 +263,17 @@ struct ExportWithLines {
     lines: Option<[usize; 2]>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn search(
     term: Option<String>,
     export: Option<String>,
     imports: Option<String>,
     loc: Option<String>,
     depends_on: Option<String>,
+    type_name: Option<String>,
+    type_not: Option<String>,
+    type_add: Vec<String>,
+    regex_mode: bool,
     json_output: bool,
 ) -> Result<()> {
     let root = std::env::current_dir()?;
@@ -71,14 +291,40 @@ pub fn search(
         return Ok(());
     }
 
+    let type_filter = build_type_filter(type_name.as_deref(), type_not.as_deref(), &type_add)?;
+
     let has_flags = export.is_some() || imports.is_some() || depends_on.is_some() || loc.is_some();
 
     if let Some(ref search_term) = term {
-        bare_search(&manifest, search_term, json_output)?;
+        bare_search(
+            &manifest,
+            search_term,
+            type_filter.as_ref(),
+            regex_mode,
+            json_output,
+        )?;
     } else if has_flags {
-        flag_search(&manifest, export, imports, loc, depends_on, json_output)?;
+        flag_search(
+            &manifest,
+            export,
+            imports,
+            loc,
+            depends_on,
+            type_filter.as_ref(),
+            regex_mode,
+            json_output,
+        )?;
     } else {
-        flag_search(&manifest, None, None, None, None, json_output)?;
+        flag_search(
+            &manifest,
+            None,
+            None,
+            None,
+            None,
+            type_filter.as_ref(),
+            regex_mode,
+            json_output,
+        )?;
     }
 
     Ok(())
@@ -86,36 +332,64 @@ pub fn search(
 
 // -- Bare search: searches everything, groups by type --
 
-fn bare_search(manifest: &Manifest, term: &str, json_output: bool) -> Result<()> {
-    let term_lower = term.to_lowercase();
+fn bare_search(
+    manifest: &Manifest,
+    term: &str,
+    type_filter: Option<&TypeFilter>,
+    regex_mode: bool,
+    json_output: bool,
+) -> Result<()> {
+    let matcher = TermMatcher::new(term, regex_mode, true)?;
+    let file_allowed = |path: &str| type_filter.is_none_or(|f| f.matches(path));
 
-    // 1. Exact export match (O(1))
+    // 1. Exact export match (O(1)) — only a valid fast path for a plain
+    // literal term, since a regex pattern isn't itself an export name.
     let mut export_matches: Vec<(&str, &ExportLocation)> = Vec::new();
     let mut seen_exports = std::collections::HashSet::new();
 
-    if let Some(loc) = manifest.export_locations.get(term) {
-        export_matches.push((term, loc));
-        seen_exports.insert(term.to_string());
+    if !regex_mode {
+        if let Some(loc) = manifest.export_locations.get(term) {
+            if file_allowed(&loc.file) {
+                export_matches.push((term, loc));
+                seen_exports.insert(term.to_string());
+            }
+        }
     }
 
-    // 2. Fuzzy export match (case-insensitive substring)
-    let mut fuzzy_exports: Vec<(&str, &ExportLocation)> = Vec::new();
-    for (name, loc) in &manifest.export_locations {
-        if seen_exports.contains(name.as_str()) {
-            continue;
-        }
-        if name.to_lowercase().contains(&term_lower) {
-            fuzzy_exports.push((name.as_str(), loc));
+    // 2. Remaining export match: fuzzy subsequence ranking in substring
+    // mode (best match first), or a direct regex match with no ranking.
+    if regex_mode {
+        let mut regex_exports: Vec<(&str, &ExportLocation)> = manifest
+            .export_locations
+            .iter()
+            .filter(|(name, loc)| {
+                !seen_exports.contains(name.as_str())
+                    && file_allowed(&loc.file)
+                    && matcher.is_match(name)
+            })
+            .map(|(name, loc)| (name.as_str(), loc))
+            .collect();
+        regex_exports.sort_by(|(a, _), (b, _)| a.cmp(b));
+        export_matches.extend(regex_exports);
+    } else {
+        let mut fuzzy_exports: Vec<(&str, &ExportLocation, i32)> = Vec::new();
+        for (name, loc) in &manifest.export_locations {
+            if seen_exports.contains(name.as_str()) || !file_allowed(&loc.file) {
+                continue;
+            }
+            if let Some(score) = fuzzy_score(term, name) {
+                fuzzy_exports.push((name.as_str(), loc, score));
+            }
         }
+        sort_fuzzy_matches(&mut fuzzy_exports);
+        export_matches.extend(fuzzy_exports.into_iter().map(|(name, loc, _)| (name, loc)));
     }
-    fuzzy_exports.sort_by_key(|(name, _)| name.to_lowercase());
-    export_matches.extend(fuzzy_exports);
 
     // 3. File path match
     let mut file_matches: Vec<&str> = manifest
         .files
         .keys()
-        .filter(|path| path.to_lowercase().contains(&term_lower))
+        .filter(|path| matcher.is_match(path) && file_allowed(path))
         .map(|s| s.as_str())
         .collect();
     file_matches.sort();
@@ -123,15 +397,35 @@ fn bare_search(manifest: &Manifest, term: &str, json_output: bool) -> Result<()>
     // 4. Import match — find unique packages matching the term, with their files
     let mut import_map: std::collections::BTreeMap<String, Vec<String>> =
         std::collections::BTreeMap::new();
-    for (file_path, entry) in &manifest.files {
-        for imp in &entry.imports {
-            if imp.to_lowercase().contains(&term_lower) {
-                import_map
-                    .entry(imp.clone())
-                    .or_default()
-                    .push(file_path.clone());
-            }
-        }
+    let import_hits: Vec<(String, String)> = if manifest.files.len() > PARALLEL_SCAN_THRESHOLD {
+        manifest
+            .files
+            .par_iter()
+            .filter(|(file_path, _)| file_allowed(file_path))
+            .flat_map_iter(|(file_path, entry)| {
+                entry
+                    .imports
+                    .iter()
+                    .filter(|imp| matcher.is_match(imp))
+                    .map(move |imp| (imp.clone(), file_path.clone()))
+            })
+            .collect()
+    } else {
+        manifest
+            .files
+            .iter()
+            .filter(|(file_path, _)| file_allowed(file_path))
+            .flat_map(|(file_path, entry)| {
+                entry
+                    .imports
+                    .iter()
+                    .filter(|imp| matcher.is_match(imp))
+                    .map(move |imp| (imp.clone(), file_path.clone()))
+            })
+            .collect()
+    };
+    for (imp, file_path) in import_hits {
+        import_map.entry(imp).or_default().push(file_path);
     }
     for files in import_map.values_mut() {
         files.sort();
@@ -141,6 +435,7 @@ fn bare_search(manifest: &Manifest, term: &str, json_output: bool) -> Result<()>
 
     if json_output {
         let result = BareSearchResult {
+            types: type_filter.map(|f| f.resolved.clone()).unwrap_or_default(),
             exports: export_matches
                 .iter()
                 .map(|(name, loc)| ExportMatch {
@@ -231,19 +526,25 @@ fn bare_search(manifest: &Manifest, term: &str, json_output: bool) -> Result<()>
 
 // -- Flag-based search: existing behavior, enhanced with fuzzy exports + rich output --
 
+#[allow(clippy::too_many_arguments)]
 fn flag_search(
     manifest: &Manifest,
     export: Option<String>,
     imports: Option<String>,
     loc: Option<String>,
     depends_on: Option<String>,
+    type_filter: Option<&TypeFilter>,
+    regex_mode: bool,
     json_output: bool,
 ) -> Result<()> {
+    let file_allowed = |path: &str| type_filter.is_none_or(|f| f.matches(path));
     let mut results: Vec<FlagSearchResult> = Vec::new();
 
-    // Search by export name — exact first, then fuzzy
+    // Search by export name — exact first, then fuzzy (or, in regex mode, a
+    // direct pattern match with no ranking)
     if let Some(ref export_name) = export {
-        let matches = find_export_matches(manifest, export_name);
+        let mut matches = find_export_matches(manifest, export_name, regex_mode)?;
+        matches.retain(|(_, loc)| file_allowed(&loc.file));
 
         if matches.is_empty() && !json_output {
             print_export_matches(&matches, json_output);
@@ -282,15 +583,15 @@ fn flag_search(
 
     // Search by imports
     if let Some(ref import_name) = imports {
-        for (file_path, entry) in &manifest.files {
-            if entry
-                .imports
-                .iter()
-                .any(|i| i.contains(import_name.as_str()))
-            {
-                if results.iter().any(|r| r.file == *file_path) {
-                    continue;
-                }
+        let matcher = TermMatcher::new(import_name, regex_mode, false)?;
+        let hits = scan_files(manifest, |file_path, entry| {
+            file_allowed(file_path) && entry.imports.iter().any(|i| matcher.is_match(i))
+        });
+        for file_path in hits {
+            if results.iter().any(|r| r.file == *file_path) {
+                continue;
+            }
+            if let Some(entry) = manifest.files.get(file_path) {
                 results.push(file_to_flag_result(file_path, entry));
             }
         }
@@ -298,15 +599,15 @@ fn flag_search(
 
     // Search by dependencies
     if let Some(ref dep_path) = depends_on {
-        for (file_path, entry) in &manifest.files {
-            if entry
-                .dependencies
-                .iter()
-                .any(|d| d.contains(dep_path.as_str()))
-            {
-                if results.iter().any(|r| r.file == *file_path) {
-                    continue;
-                }
+        let matcher = TermMatcher::new(dep_path, regex_mode, false)?;
+        let hits = scan_files(manifest, |file_path, entry| {
+            file_allowed(file_path) && entry.dependencies.iter().any(|d| matcher.is_match(d))
+        });
+        for file_path in hits {
+            if results.iter().any(|r| r.file == *file_path) {
+                continue;
+            }
+            if let Some(entry) = manifest.files.get(file_path) {
                 results.push(file_to_flag_result(file_path, entry));
             }
         }
@@ -317,8 +618,11 @@ fn flag_search(
         let (op, value) = parse_loc_expr(loc_expr)?;
 
         if export.is_none() && imports.is_none() && depends_on.is_none() {
-            for (file_path, entry) in &manifest.files {
-                if matches_loc_filter(entry.loc, &op, value) {
+            let hits = scan_files(manifest, |file_path, entry| {
+                file_allowed(file_path) && matches_loc_filter(entry.loc, &op, value)
+            });
+            for file_path in hits {
+                if let Some(entry) = manifest.files.get(file_path) {
                     results.push(file_to_flag_result(file_path, entry));
                 }
             }
@@ -329,18 +633,24 @@ fn flag_search(
 
     // If no filters provided, list all files
     if export.is_none() && imports.is_none() && depends_on.is_none() && loc.is_none() {
-        for (file_path, entry) in &manifest.files {
-            results.push(file_to_flag_result(file_path, entry));
+        let hits = scan_files(manifest, |file_path, _| file_allowed(file_path));
+        for file_path in hits {
+            if let Some(entry) = manifest.files.get(file_path) {
+                results.push(file_to_flag_result(file_path, entry));
+            }
         }
     }
 
     results.sort_by(|a, b| a.file.cmp(&b.file));
 
+    let resolved_types = type_filter.map(|f| f.resolved.clone()).unwrap_or_default();
+
     if json_output {
         // For export-only searches, use the rich export JSON format
         if let Some(ref export_name) = export {
             if imports.is_none() && depends_on.is_none() && loc.is_none() {
-                let matches = find_export_matches(manifest, export_name);
+                let mut matches = find_export_matches(manifest, export_name, regex_mode)?;
+                matches.retain(|(_, eloc)| file_allowed(&eloc.file));
                 let export_results: Vec<ExportMatch> = matches
                     .iter()
                     .map(|(name, eloc)| ExportMatch {
@@ -349,11 +659,31 @@ fn flag_search(
                         lines: eloc.lines.as_ref().map(|l| [l.start, l.end]),
                     })
                     .collect();
-                println!("{}", serde_json::to_string_pretty(&export_results)?);
+                if resolved_types.is_empty() {
+                    println!("{}", serde_json::to_string_pretty(&export_results)?);
+                } else {
+                    println!(
+                        "{}",
+                        serde_json::to_string_pretty(&TypedResults {
+                            types: resolved_types,
+                            results: export_results,
+                        })?
+                    );
+                }
                 return Ok(());
             }
         }
-        println!("{}", serde_json::to_string_pretty(&results)?);
+        if resolved_types.is_empty() {
+            println!("{}", serde_json::to_string_pretty(&results)?);
+        } else {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&TypedResults {
+                    types: resolved_types,
+                    results,
+                })?
+            );
+        }
     } else if results.is_empty() && export.is_none() {
         println!("{} No matches found", "!".yellow());
     } else if !results.is_empty() {
@@ -387,34 +717,135 @@ fn flag_search(
     Ok(())
 }
 
-/// Find exports matching a name — exact O(1) first, then case-insensitive substring.
+/// Find exports matching a name — exact O(1) first, then (in `--regex` mode)
+/// a direct pattern match, otherwise case-insensitive fuzzy subsequence
+/// ranking.
 fn find_export_matches<'a>(
     manifest: &'a Manifest,
     name: &str,
-) -> Vec<(String, &'a ExportLocation)> {
+    regex_mode: bool,
+) -> Result<Vec<(String, &'a ExportLocation)>> {
     let mut matches: Vec<(String, &ExportLocation)> = Vec::new();
     let mut seen = std::collections::HashSet::new();
 
-    // Exact match (O(1))
-    if let Some(loc) = manifest.export_locations.get(name) {
-        matches.push((name.to_string(), loc));
-        seen.insert(name.to_string());
+    // Exact match (O(1)) — only a valid fast path for a plain literal name.
+    if !regex_mode {
+        if let Some(loc) = manifest.export_locations.get(name) {
+            matches.push((name.to_string(), loc));
+            seen.insert(name.to_string());
+        }
     }
 
-    // Fuzzy: case-insensitive substring
-    let name_lower = name.to_lowercase();
-    let mut fuzzy: Vec<(String, &ExportLocation)> = manifest
-        .export_locations
-        .iter()
-        .filter(|(export_name, _)| {
-            !seen.contains(export_name.as_str()) && export_name.to_lowercase().contains(&name_lower)
-        })
-        .map(|(export_name, loc)| (export_name.clone(), loc))
-        .collect();
-    fuzzy.sort_by(|(a, _), (b, _)| a.to_lowercase().cmp(&b.to_lowercase()));
-    matches.extend(fuzzy);
+    if regex_mode {
+        let pattern =
+            Regex::new(name).with_context(|| format!("Invalid --regex pattern '{name}'"))?;
+        let mut regex_matches: Vec<(&str, &ExportLocation)> = manifest
+            .export_locations
+            .iter()
+            .filter(|(export_name, _)| !seen.contains(export_name.as_str()))
+            .filter(|(export_name, _)| pattern.is_match(export_name))
+            .map(|(export_name, loc)| (export_name.as_str(), loc))
+            .collect();
+        regex_matches.sort_by(|(a, _), (b, _)| a.cmp(b));
+        matches.extend(
+            regex_matches
+                .into_iter()
+                .map(|(export_name, loc)| (export_name.to_string(), loc)),
+        );
+    } else {
+        // Fuzzy: case-insensitive subsequence, ranked by fuzzy_score
+        let mut fuzzy: Vec<(&str, &ExportLocation, i32)> = manifest
+            .export_locations
+            .iter()
+            .filter(|(export_name, _)| !seen.contains(export_name.as_str()))
+            .filter_map(|(export_name, loc)| {
+                fuzzy_score(name, export_name).map(|score| (export_name.as_str(), loc, score))
+            })
+            .collect();
+        sort_fuzzy_matches(&mut fuzzy);
+        matches.extend(
+            fuzzy
+                .into_iter()
+                .map(|(export_name, loc, _)| (export_name.to_string(), loc)),
+        );
+    }
+
+    Ok(matches)
+}
+
+/// Sort fuzzy export matches by descending score, breaking ties by shorter
+/// candidate length then alphabetically (matching ripgrep/rust-analyzer's
+/// "shortest/most-specific wins" fuzzy ranking convention).
+fn sort_fuzzy_matches<T: AsRef<str> + Copy>(matches: &mut [(T, &ExportLocation, i32)]) {
+    matches.sort_by(|(name_a, _, score_a), (name_b, _, score_b)| {
+        score_b
+            .cmp(score_a)
+            .then_with(|| name_a.as_ref().len().cmp(&name_b.as_ref().len()))
+            .then_with(|| name_a.as_ref().to_lowercase().cmp(&name_b.as_ref().to_lowercase()))
+    });
+}
+
+/// Score how well `candidate` matches `query` as a case-insensitive
+/// subsequence, the way rust-analyzer ranks symbol/import search results.
+/// Returns `None` if `query`'s characters don't all appear, in order, in
+/// `candidate`.
+///
+/// Bonuses: `+16` if the match starts at index 0 (prefix), `+8` when a
+/// matched character falls on a word boundary (start of string, first char
+/// after a non-alphanumeric separator, or a camelCase lower→upper "hump"),
+/// `+4` for each character matched consecutively right after the previous
+/// match. Penalty: `-1` per candidate character skipped between two
+/// matches.
+fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let query_chars: Vec<char> = query.to_lowercase().chars().collect();
+    let candidate_chars: Vec<char> = candidate.chars().collect();
+    let candidate_lower: Vec<char> = candidate.to_lowercase().chars().collect();
+
+    let mut score = 0i32;
+    let mut query_index = 0;
+    let mut last_match: Option<usize> = None;
+    let mut first_match: Option<usize> = None;
+
+    for (i, &ch) in candidate_lower.iter().enumerate() {
+        if query_index >= query_chars.len() {
+            break;
+        }
+        if ch != query_chars[query_index] {
+            continue;
+        }
+
+        first_match.get_or_insert(i);
+
+        let is_boundary = i == 0
+            || !candidate_chars[i - 1].is_alphanumeric()
+            || (candidate_chars[i - 1].is_lowercase() && candidate_chars[i].is_uppercase());
+        if is_boundary {
+            score += 8;
+        }
+
+        match last_match {
+            Some(prev) if i == prev + 1 => score += 4,
+            Some(prev) => score -= (i - prev - 1) as i32,
+            None => {}
+        }
+
+        last_match = Some(i);
+        query_index += 1;
+    }
+
+    if query_index < query_chars.len() {
+        return None;
+    }
+
+    if first_match == Some(0) {
+        score += 16;
+    }
 
-    matches
+    Some(score)
 }
 
 /// Print export matches in the rich aligned format.
@@ -610,10 +1041,44 @@ mod tests {
         m
     }
 
+    #[test]
+    fn type_filter_matches_built_in_group() {
+        let filter = build_type_filter(Some("ts"), None, &[]).unwrap().unwrap();
+        assert!(filter.matches("src/store/index.ts"));
+        assert!(filter.matches("src/store/provider.tsx"));
+        assert!(!filter.matches("src/auth/login.py"));
+    }
+
+    #[test]
+    fn type_filter_type_not_excludes_group() {
+        let filter = build_type_filter(None, Some("py"), &[]).unwrap().unwrap();
+        assert!(filter.matches("src/main.rs"));
+        assert!(!filter.matches("src/main.py"));
+    }
+
+    #[test]
+    fn type_filter_type_add_defines_custom_group() {
+        let filter = build_type_filter(Some("proto"), None, &["proto:*.proto".to_string()])
+            .unwrap()
+            .unwrap();
+        assert!(filter.matches("api/service.proto"));
+        assert!(!filter.matches("src/main.rs"));
+    }
+
+    #[test]
+    fn type_filter_unknown_type_errors() {
+        assert!(build_type_filter(Some("not-a-real-type"), None, &[]).is_err());
+    }
+
+    #[test]
+    fn no_type_filter_allows_everything() {
+        assert!(build_type_filter(None, None, &[]).unwrap().is_none());
+    }
+
     #[test]
     fn exact_export_match() {
         let m = test_manifest();
-        let matches = find_export_matches(&m, "createStore");
+        let matches = find_export_matches(&m, "createStore", false).unwrap();
         assert_eq!(matches.len(), 1);
         assert_eq!(matches[0].0, "createStore");
         assert_eq!(matches[0].1.file, "src/store/index.ts");
@@ -622,7 +1087,7 @@ mod tests {
     #[test]
     fn fuzzy_export_match_substring() {
         let m = test_manifest();
-        let matches = find_export_matches(&m, "store");
+        let matches = find_export_matches(&m, "store", false).unwrap();
         // Should match: createStore, destroyStore, StoreProvider, useStore
         assert!(matches.len() >= 3);
         let names: Vec<&str> = matches.iter().map(|(n, _)| n.as_str()).collect();
@@ -634,7 +1099,7 @@ mod tests {
     #[test]
     fn fuzzy_export_match_case_insensitive() {
         let m = test_manifest();
-        let matches = find_export_matches(&m, "STORE");
+        let matches = find_export_matches(&m, "STORE", false).unwrap();
         assert!(matches.len() >= 3);
         let names: Vec<&str> = matches.iter().map(|(n, _)| n.as_str()).collect();
         assert!(names.contains(&"createStore"));
@@ -644,20 +1109,131 @@ mod tests {
     #[test]
     fn export_no_match() {
         let m = test_manifest();
-        let matches = find_export_matches(&m, "xyznothing");
+        let matches = find_export_matches(&m, "xyznothing", false).unwrap();
         assert!(matches.is_empty());
     }
 
+    #[test]
+    fn fuzzy_export_match_ranks_subsequence_hits() {
+        let m = test_manifest();
+        // "usestr" is a subsequence of "useStore" but not a substring of it.
+        let matches = find_export_matches(&m, "usestr", false).unwrap();
+        assert_eq!(matches[0].0, "useStore");
+    }
+
+    #[test]
+    fn fuzzy_score_rejects_non_subsequence() {
+        assert_eq!(fuzzy_score("xyz", "useStore"), None);
+    }
+
+    #[test]
+    fn fuzzy_score_prefers_prefix_and_consecutive_matches() {
+        let prefix_score = fuzzy_score("use", "useStore").unwrap();
+        let scattered_score = fuzzy_score("use", "unstableEntry").unwrap();
+        assert!(prefix_score > scattered_score);
+    }
+
+    #[test]
+    fn fuzzy_score_rewards_camel_case_humps() {
+        // "fb" lands on the B hump in "fooBar" but on a plain mid-word "b"
+        // in "foobar", so only the first earns the boundary bonus.
+        let hump_score = fuzzy_score("fb", "fooBar").unwrap();
+        let mid_word_score = fuzzy_score("fb", "foobar").unwrap();
+        assert!(hump_score > mid_word_score);
+    }
+
     #[test]
     fn exact_match_ranked_first() {
         let m = test_manifest();
-        let matches = find_export_matches(&m, "createStore");
+        let matches = find_export_matches(&m, "createStore", false).unwrap();
         assert_eq!(matches[0].0, "createStore");
         // Only exact match, no fuzzy (since "createStore" is a full name)
         // But destroyStore also contains "store" — exact should be first
         assert_eq!(matches.len(), 1);
     }
 
+    #[test]
+    fn regex_export_match_uses_pattern_not_substring() {
+        let m = test_manifest();
+        let matches = find_export_matches(&m, "^(create|destroy)Store$", true).unwrap();
+        let names: Vec<&str> = matches.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(names, vec!["createStore", "destroyStore"]);
+    }
+
+    #[test]
+    fn regex_export_match_respects_inline_case_insensitive_flag() {
+        let m = test_manifest();
+        let matches = find_export_matches(&m, "(?i)usestore", true).unwrap();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].0, "useStore");
+    }
+
+    #[test]
+    fn regex_export_match_is_case_sensitive_by_default() {
+        let m = test_manifest();
+        let matches = find_export_matches(&m, "usestore", true).unwrap();
+        assert!(matches.is_empty());
+    }
+
+    #[test]
+    fn invalid_regex_pattern_errors() {
+        let m = test_manifest();
+        assert!(find_export_matches(&m, "useStore(", true).is_err());
+    }
+
+    #[test]
+    fn term_matcher_regex_mode_matches_pattern() {
+        let matcher = TermMatcher::new(r"^src/store/.*\.tsx?$", true, false).unwrap();
+        assert!(matcher.is_match("src/store/index.ts"));
+        assert!(matcher.is_match("src/store/provider.tsx"));
+        assert!(!matcher.is_match("src/hooks/useStore.ts"));
+    }
+
+    #[test]
+    fn term_matcher_substring_mode_preserves_case_sensitivity_setting() {
+        let insensitive = TermMatcher::new("REDUX", false, true).unwrap();
+        assert!(insensitive.is_match("uses redux internally"));
+
+        let sensitive = TermMatcher::new("REDUX", false, false).unwrap();
+        assert!(!sensitive.is_match("uses redux internally"));
+        assert!(sensitive.is_match("uses REDUX internally"));
+    }
+
+    #[test]
+    fn term_matcher_rejects_invalid_pattern() {
+        assert!(TermMatcher::new("(unterminated", true, false).is_err());
+    }
+
+    #[test]
+    fn scan_files_matches_serial_filter_below_threshold() {
+        let m = test_manifest();
+        let mut hits = scan_files(&m, |path, _| path.ends_with(".ts"));
+        hits.sort();
+        assert_eq!(
+            hits,
+            vec!["src/hooks/useStore.ts", "src/store/index.ts"]
+        );
+    }
+
+    #[test]
+    fn scan_files_matches_serial_filter_above_threshold() {
+        let mut m = Manifest::new();
+        for i in 0..(PARALLEL_SCAN_THRESHOLD + 10) {
+            m.files.insert(
+                format!("src/generated/file_{i}.ts"),
+                FileEntry {
+                    exports: vec![],
+                    export_lines: None,
+                    imports: vec![],
+                    dependencies: vec![],
+                    loc: i,
+                },
+            );
+        }
+        let hits = scan_files(&m, |_, entry| entry.loc == 5);
+        assert_eq!(hits, vec!["src/generated/file_5.ts"]);
+    }
+
     #[test]
     fn parse_loc_operators() {
         assert_eq!(parse_loc_expr(">500").unwrap(), (">".to_string(), 500));
@@ -1,31 +1,124 @@
-use std::path::Path;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, RwLock};
 use std::time::Duration;
 
 use anyhow::Result;
 use colored::Colorize;
 use notify::RecursiveMode;
 use notify_debouncer_full::{new_debouncer, DebounceEventResult};
+use rayon::prelude::*;
 
 use crate::config::Config;
 use crate::extractor::{sidecar_path_for, FileProcessor};
 
 use super::{collect_files, resolve_root};
 
+/// Name of the fmm config file, watched alongside the project source so
+/// editing it (e.g. adding a supported language) takes effect without a
+/// restart — see [`Config::load_from_dir`].
+const CONFIG_FILE_NAME: &str = ".fmmrc.json";
+
+/// Maps each file to the set of files that depend on it (via import/include),
+/// so a change to one file can cascade to every file that depends on it —
+/// much like Deno's watcher re-running modules whose local dependents changed.
+type DependencyGraph = HashMap<PathBuf, HashSet<PathBuf>>;
+
+/// A watched file's version at the moment its sidecar was last synced: a
+/// cargo-style `stat()` fingerprint (mtime + length) plus a content hash.
+/// The fingerprint lets [`is_unchanged`](FileVersion::is_unchanged) answer
+/// "definitely unchanged" for a metadata-only event (a `chmod`) without
+/// reading the file at all; the content hash is what actually
+/// catches a noisy burst of events for bytes that round-tripped back to the
+/// same content, e.g. `git checkout` touching a file's mtime without
+/// changing what's in it. The mtime has whole-second resolution, so a
+/// fingerprint whose mtime falls in the second the version was recorded
+/// is not trusted and the content hash decides.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileVersion {
+    fingerprint: Option<(i64, u64)>,
+    hash: u64,
+    recorded_secs: i64,
+}
+
+impl FileVersion {
+    fn of(path: &Path) -> Option<Self> {
+        let recorded_secs = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs() as i64);
+        Some(Self {
+            fingerprint: stat_fingerprint(path),
+            hash: content_hash(path)?,
+            recorded_secs,
+        })
+    }
+
+    fn is_unchanged(&self, path: &Path) -> bool {
+        if let Some((mtime, _)) = self.fingerprint {
+            if mtime < self.recorded_secs && self.fingerprint == stat_fingerprint(path) {
+                return true;
+            }
+        }
+        content_hash(path) == Some(self.hash)
+    }
+}
+
+/// Last-seen version per watched file, used to skip re-parsing files whose
+/// bytes didn't actually change across a noisy burst of events.
+type HashCache = HashMap<PathBuf, FileVersion>;
+
+fn stat_fingerprint(path: &Path) -> Option<(i64, u64)> {
+    let meta = std::fs::metadata(path).ok()?;
+    let mtime = meta
+        .modified()
+        .ok()?
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()?;
+    Some((mtime.as_secs() as i64, meta.len()))
+}
+
+fn content_hash(path: &Path) -> Option<u64> {
+    use std::hash::{Hash, Hasher};
+    let bytes = std::fs::read(path).ok()?;
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    Some(hasher.finish())
+}
+
+fn build_hash_cache(files: &[PathBuf]) -> HashCache {
+    files
+        .iter()
+        .filter_map(|file| FileVersion::of(file).map(|version| (file.clone(), version)))
+        .collect()
+}
+
 pub fn watch(path: &str, debounce_ms: u64) -> Result<()> {
-    let config = Config::load().unwrap_or_default();
+    let config = Arc::new(RwLock::new(Config::load().unwrap_or_default()));
     let root = resolve_root(path)?;
     let target = Path::new(path)
         .canonicalize()
         .unwrap_or_else(|_| root.clone());
+    let config_path = root.join(CONFIG_FILE_NAME);
 
     // Initial generate pass
     println!("{}", "Running initial generate pass...".green().bold());
-    super::generate(path, false)?;
+    super::generate(path, false, None, &[], &[])?;
+
+    let files = collect_files(path, &config.read().unwrap())?;
+    println!("\nWatching {} files in {} ...\n", files.len(), path);
 
-    let file_count = collect_files(path, &config)?.len();
-    println!("\nWatching {} files in {} ...\n", file_count, path);
+    // Shared across every debounce callback invocation for the life of the
+    // watch — rebuilding the parser registry (tree-sitter grammars, query
+    // packs) on each event would make every keystroke-triggered save pay
+    // startup cost that only needs to happen once.
+    let processor = Arc::new(FileProcessor::new(&root));
+    let graph = Arc::new(Mutex::new(build_dependency_graph(
+        &files,
+        &root,
+        processor.as_ref(),
+    )));
+    let hashes = Arc::new(Mutex::new(build_hash_cache(&files)));
 
     let updates = Arc::new(AtomicUsize::new(0));
 
@@ -44,6 +137,15 @@ pub fn watch(path: &str, debounce_ms: u64) -> Result<()> {
     let updates_for_handler = updates.clone();
     let config_for_handler = config.clone();
     let root_for_handler = root.clone();
+    let graph_for_handler = graph.clone();
+    let hashes_for_handler = hashes.clone();
+    let config_path_for_handler = config_path.clone();
+    let path_for_handler = path.to_string();
+    let processor_for_handler = processor.clone();
+    // Correlates a split `From`/`To` rename pair across the debounce window —
+    // `notify` delivers them as two separate events on some platforms.
+    let pending_rename: Arc<Mutex<Option<PathBuf>>> = Arc::new(Mutex::new(None));
+    let pending_rename_for_handler = pending_rename.clone();
 
     // The debouncer callback runs on its own thread — we use a parking channel
     // to keep the main thread alive until Ctrl+C fires.
@@ -54,24 +156,107 @@ pub fn watch(path: &str, debounce_ms: u64) -> Result<()> {
         None,
         move |result: DebounceEventResult| {
             if let Ok(events) = result {
-                let processor = FileProcessor::new(&root_for_handler);
+                let processor = processor_for_handler.clone();
+                // Create/Modify paths are collected here instead of handled
+                // inline — a single bulk change (branch switch, formatter
+                // run) can queue hundreds of them, and parsing each on this
+                // thread one at a time would stall the next debounce window.
+                let mut batch_paths: Vec<PathBuf> = Vec::new();
                 for event in events {
-                    for event_path in &event.paths {
-                        handle_event(
-                            event_path,
-                            &event.kind,
-                            &config_for_handler,
-                            &processor,
+                    if let notify::EventKind::Modify(notify::event::ModifyKind::Name(mode)) =
+                        &event.kind
+                    {
+                        let snapshot = config_for_handler.read().unwrap().clone();
+                        handle_rename_event(
+                            mode,
+                            &event.paths,
+                            &snapshot,
+                            processor.as_ref(),
                             &root_for_handler,
                             &updates_for_handler,
+                            &graph_for_handler,
+                            &hashes_for_handler,
+                            &pending_rename_for_handler,
                         );
+                        continue;
+                    }
+                    for event_path in &event.paths {
+                        if paths_match(event_path, &config_path_for_handler) {
+                            reload_config(
+                                &config_for_handler,
+                                &config_path_for_handler,
+                                &path_for_handler,
+                                processor.as_ref(),
+                                &root_for_handler,
+                                &updates_for_handler,
+                            );
+                            continue;
+                        }
+                        if matches!(event.kind, notify::EventKind::Remove(_)) {
+                            // An atomic-save rename (write a temp file, then
+                            // rename over the target) can surface as a
+                            // Remove/Create pair instead of a single Rename
+                            // event on some platforms/filesystems. If the
+                            // path already exists again by the time this
+                            // debounce window fires, treat it as an update
+                            // rather than clean its sidecar and immediately
+                            // recreate it — avoids a spurious "Removed"
+                            // followed by "Created" for one real edit.
+                            if event_path.exists() {
+                                batch_paths.push(event_path.clone());
+                                continue;
+                            }
+                            let snapshot = config_for_handler.read().unwrap().clone();
+                            handle_event(
+                                event_path,
+                                &event.kind,
+                                &snapshot,
+                                processor.as_ref(),
+                                &root_for_handler,
+                                &updates_for_handler,
+                                &graph_for_handler,
+                                &hashes_for_handler,
+                            );
+                        } else {
+                            batch_paths.push(event_path.clone());
+                        }
                     }
                 }
+
+                if !batch_paths.is_empty() {
+                    let snapshot = config_for_handler.read().unwrap().clone();
+                    process_batch(
+                        &batch_paths,
+                        &snapshot,
+                        &processor,
+                        &root_for_handler,
+                        &updates_for_handler,
+                        &graph_for_handler,
+                        &hashes_for_handler,
+                    );
+                }
+
+                // A `From` with no matching `To` by the end of the debounce
+                // window is an orphaned rename — drop its stale sidecar.
+                if let Some(old) = pending_rename_for_handler.lock().ok().and_then(|mut p| p.take())
+                {
+                    cleanup_orphaned_rename(
+                        &old,
+                        &root_for_handler,
+                        &updates_for_handler,
+                        &graph_for_handler,
+                        &hashes_for_handler,
+                    );
+                }
             }
         },
     )?;
 
     debouncer.watch(&target, RecursiveMode::Recursive)?;
+    // The config file may live outside `target` (e.g. `fmm watch src` watching
+    // a config at the repo root) — watch it explicitly too. Ignore errors from
+    // an already-covered path.
+    let _ = debouncer.watch(&config_path, RecursiveMode::NonRecursive);
 
     // Block forever — Ctrl+C handler exits the process
     let _ = rx.recv();
@@ -79,14 +264,163 @@ pub fn watch(path: &str, debounce_ms: u64) -> Result<()> {
     Ok(())
 }
 
+fn paths_match(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+/// Reload the fmm config from disk, validating it before it takes effect: a
+/// parse/IO failure leaves the currently running config in place (with a
+/// warning) instead of crashing the watcher or silently swapping in
+/// `Config::default()`. On success, diffs the old and new `collect_files`
+/// sets so files the new config newly excludes get their sidecar pruned and
+/// files it newly includes get one generated — the same hot-reload shape a
+/// config change should have, without a restart.
+fn reload_config(
+    config: &RwLock<Config>,
+    config_path: &Path,
+    path: &str,
+    processor: &FileProcessor,
+    root: &Path,
+    updates: &AtomicUsize,
+) {
+    let dir = config_path.parent().unwrap_or(Path::new("."));
+    let reloaded = match Config::load_from_dir(dir) {
+        Ok(cfg) => cfg,
+        Err(e) => {
+            eprintln!(
+                "\n{} Failed to reload {}: {} — keeping the running config.",
+                "!".yellow().bold(),
+                CONFIG_FILE_NAME,
+                e
+            );
+            return;
+        }
+    };
+
+    let previous_files: HashSet<PathBuf> = config
+        .read()
+        .ok()
+        .and_then(|cfg| collect_files(path, &cfg).ok())
+        .map(|files| files.into_iter().collect())
+        .unwrap_or_default();
+
+    {
+        let mut guard = config.write().unwrap();
+        *guard = reloaded;
+    }
+
+    let current_files: HashSet<PathBuf> = config
+        .read()
+        .ok()
+        .and_then(|cfg| collect_files(path, &cfg).ok())
+        .map(|files| files.into_iter().collect())
+        .unwrap_or_default();
+
+    for removed in previous_files.difference(&current_files) {
+        let sidecar = sidecar_path_for(removed);
+        if sidecar.exists() && std::fs::remove_file(&sidecar).is_ok() {
+            let display = sidecar.strip_prefix(root).unwrap_or(&sidecar).display();
+            println!(
+                "  {} Removed {} (no longer included)",
+                "✓".green(),
+                display
+            );
+            updates.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    for added in current_files.difference(&previous_files) {
+        let display_path = added.strip_prefix(root).unwrap_or(added);
+        match processor.process(added, false) {
+            Ok(Some(_)) => {
+                println!(
+                    "  {} Created {} (newly included)",
+                    "✓".green(),
+                    display_path.display()
+                );
+                updates.fetch_add(1, Ordering::Relaxed);
+            }
+            Ok(None) => {}
+            Err(e) => eprintln!("  {} {}: {}", "✗".red(), display_path.display(), e),
+        }
+    }
+
+    println!(
+        "\n{} Config reloaded — now watching {} files.\n",
+        "↻".cyan().bold(),
+        current_files.len()
+    );
+}
+
+/// Build the initial reverse-dependency index: for every file, resolve its
+/// parsed `dependencies`/`imports` to on-disk paths and invert the result
+/// so each dependency maps to the set of files that depend on it.
+fn build_dependency_graph(
+    files: &[PathBuf],
+    root: &Path,
+    processor: &FileProcessor,
+) -> DependencyGraph {
+    let mut graph: DependencyGraph = HashMap::new();
+    for file in files {
+        for dep in file_dependencies(file, root, processor) {
+            graph.entry(dep).or_default().insert(file.clone());
+        }
+    }
+    graph
+}
+
+/// Resolve `file`'s parsed imports/dependencies to on-disk paths: relative
+/// to `file`'s own directory first, falling back to resolving relative to
+/// `root`. Entries that don't resolve to an existing file (external
+/// packages, std headers) are silently dropped.
+fn file_dependencies(file: &Path, root: &Path, processor: &FileProcessor) -> Vec<PathBuf> {
+    let Ok(Some(metadata)) = processor.extract_metadata(file) else {
+        return vec![];
+    };
+    let dir = file.parent().unwrap_or(root);
+
+    metadata
+        .dependencies
+        .iter()
+        .chain(metadata.imports.iter())
+        .filter_map(|raw| resolve_dependency_path(raw, dir, root))
+        .collect()
+}
+
+/// Extensions tried, in order, when a relative import omits one (e.g. `./util`).
+const DEPENDENCY_EXTENSIONS: &[&str] = &["ts", "tsx", "js", "jsx", "py", "go", "rs"];
+
+fn resolve_dependency_path(raw: &str, including_dir: &Path, root: &Path) -> Option<PathBuf> {
+    for base in [including_dir.join(raw), root.join(raw)] {
+        if base.exists() && !base.is_dir() {
+            return base.canonicalize().ok();
+        }
+        if base.extension().is_none() {
+            for ext in DEPENDENCY_EXTENSIONS {
+                let candidate = base.with_extension(ext);
+                if candidate.exists() {
+                    return candidate.canonicalize().ok();
+                }
+            }
+        }
+    }
+    None
+}
+
 /// Returns true if a path should be processed by the watcher.
-fn is_watchable(path: &Path, config: &Config) -> bool {
+fn is_watchable(path: &Path, config: &Config, root: &Path) -> bool {
     if path
         .extension()
         .is_some_and(|ext| ext.to_str() == Some("fmm"))
     {
         return false;
     }
+    if is_ignored(path, root, config) {
+        return false;
+    }
     let is_supported = path
         .extension()
         .and_then(|e| e.to_str())
@@ -97,6 +431,27 @@ fn is_watchable(path: &Path, config: &Config) -> bool {
     !path.is_dir()
 }
 
+/// Consults the same `.gitignore`/glob rules as [`super::collect_files`]'s
+/// initial walk — a root-level `.gitignore` plus `config.exclude`/`include`
+/// overrides built via [`super::build_overrides`] — so the watcher never
+/// churns on vendored or generated trees a user asked to ignore.
+fn is_ignored(path: &Path, root: &Path, config: &Config) -> bool {
+    let (gitignore, _) = ignore::gitignore::Gitignore::new(root.join(".gitignore"));
+    // A dir-only pattern like `build/` matches the directory, not the files
+    // inside it, so parent directories have to be checked too.
+    if path.starts_with(root)
+        && gitignore
+            .matched_path_or_any_parents(path, path.is_dir())
+            .is_ignore()
+    {
+        return true;
+    }
+    match super::build_overrides(root, config) {
+        Ok(overrides) => overrides.matched(path, path.is_dir()).is_ignore(),
+        Err(_) => false,
+    }
+}
+
 fn handle_event(
     path: &Path,
     kind: &notify::EventKind,
@@ -104,8 +459,10 @@ fn handle_event(
     processor: &FileProcessor,
     root: &Path,
     updates: &AtomicUsize,
+    graph: &Mutex<DependencyGraph>,
+    hashes: &Mutex<HashCache>,
 ) {
-    if !is_watchable(path, config) {
+    if !is_watchable(path, config, root) {
         return;
     }
 
@@ -118,6 +475,23 @@ fn handle_event(
             if !path.exists() {
                 return;
             }
+
+            let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+
+            // Event storms (`git checkout`, editor save-storms) hand us many
+            // Modify events for bytes that never actually changed. Check the
+            // last-seen version's `stat()` fingerprint first — a metadata-only
+            // event skips straight past without reading the file at all — and
+            // fall back to a content hash before invoking the parser.
+            let unchanged = hashes
+                .lock()
+                .ok()
+                .and_then(|cache| cache.get(&canonical).copied())
+                .is_some_and(|version| version.is_unchanged(&canonical));
+            if unchanged {
+                return;
+            }
+
             match processor.process(path, false) {
                 Ok(Some(msg)) => {
                     let verb = if msg.contains("Updated") {
@@ -133,6 +507,25 @@ fn handle_event(
                     eprintln!("  {} {}: {}", "✗".red(), display, e);
                 }
             }
+
+            if let Some(version) = FileVersion::of(&canonical) {
+                if let Ok(mut cache) = hashes.lock() {
+                    cache.insert(canonical.clone(), version);
+                }
+            }
+
+            // Refresh this file's outgoing edges, then cascade regeneration
+            // to every file that depends on it.
+            let new_deps = file_dependencies(&canonical, root, processor);
+            if let Ok(mut g) = graph.lock() {
+                for dependents in g.values_mut() {
+                    dependents.remove(&canonical);
+                }
+                for dep in new_deps {
+                    g.entry(dep).or_default().insert(canonical.clone());
+                }
+            }
+            cascade_to_dependents(&canonical, graph, processor, root, updates);
         }
         Remove(_) => {
             if sidecar.exists() {
@@ -146,15 +539,353 @@ fn handle_event(
                     }
                 }
             }
+            if let Ok(mut cache) = hashes.lock() {
+                cache.remove(path);
+            }
+            if let Ok(mut g) = graph.lock() {
+                g.remove(path);
+                for dependents in g.values_mut() {
+                    dependents.remove(path);
+                }
+            }
         }
         _ => {}
     }
 }
 
+/// Outcome of running a single file through [`process_one`] — captured so
+/// [`process_batch`] can print results and fold the success count into the
+/// shared counter after the parallel pass, rather than from inside it.
+enum BatchOutcome {
+    Updated(&'static str),
+    Unchanged,
+    Error(String),
+}
+
+/// Dedupe a debounce batch's affected paths, drop anything [`is_watchable`]
+/// rejects, then hand the rest to rayon instead of parsing them one at a
+/// time on the notify thread. Each worker gets its own `Arc`-shared handle to
+/// `processor`; because `deduped` is sorted before the parallel `map` and
+/// `par_iter` preserves input order, the collected results — and therefore
+/// the printed `✓ Updated`/`✓ Created` lines — stay deterministic even
+/// though the underlying parses ran concurrently.
+fn process_batch(
+    paths: &[PathBuf],
+    config: &Config,
+    processor: &Arc<FileProcessor>,
+    root: &Path,
+    updates: &AtomicUsize,
+    graph: &Mutex<DependencyGraph>,
+    hashes: &Mutex<HashCache>,
+) {
+    let mut deduped: Vec<PathBuf> = paths
+        .iter()
+        .cloned()
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .filter(|p| is_watchable(p, config, root))
+        .collect();
+    deduped.sort();
+
+    if deduped.is_empty() {
+        return;
+    }
+
+    let results: Vec<(PathBuf, BatchOutcome)> = deduped
+        .par_iter()
+        .map(|path| {
+            let processor = Arc::clone(processor);
+            let outcome = process_one(path, &processor, root, graph, hashes);
+            (path.clone(), outcome)
+        })
+        .collect();
+
+    let mut success_count = 0usize;
+    let mut changed: Vec<PathBuf> = Vec::new();
+    for (path, outcome) in results {
+        let sidecar = sidecar_path_for(&path);
+        let display = sidecar.strip_prefix(root).unwrap_or(&sidecar).display();
+        match outcome {
+            BatchOutcome::Updated(verb) => {
+                println!("  {} {} {}", "✓".green(), verb, display);
+                success_count += 1;
+                changed.push(path.canonicalize().unwrap_or(path));
+            }
+            BatchOutcome::Unchanged => {}
+            BatchOutcome::Error(e) => {
+                eprintln!("  {} {}: {}", "✗".red(), display, e);
+            }
+        }
+    }
+
+    if success_count > 0 {
+        updates.fetch_add(success_count, Ordering::Relaxed);
+    }
+
+    // Cascades run sequentially, after the parallel pass — each one walks
+    // and mutates `graph` via BFS, which doesn't parallelize cleanly, and
+    // bulk changes rarely fan out to many independent dependency chains.
+    for path in changed {
+        cascade_to_dependents(&path, graph, processor, root, updates);
+    }
+}
+
+/// Process a single file for [`process_batch`]'s parallel pass: the
+/// content-hash skip, the actual parse/write, and the hash-cache and
+/// dependency-graph bookkeeping that [`handle_event`] performs inline for
+/// the sequential single-file path.
+fn process_one(
+    path: &Path,
+    processor: &FileProcessor,
+    root: &Path,
+    graph: &Mutex<DependencyGraph>,
+    hashes: &Mutex<HashCache>,
+) -> BatchOutcome {
+    if !path.exists() {
+        return BatchOutcome::Unchanged;
+    }
+
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+
+    let unchanged = hashes
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(&canonical).copied())
+        .is_some_and(|version| version.is_unchanged(&canonical));
+    if unchanged {
+        return BatchOutcome::Unchanged;
+    }
+
+    let outcome = match processor.process(path, false) {
+        Ok(Some(msg)) => {
+            let verb = if msg.contains("Updated") {
+                "Updated"
+            } else {
+                "Created"
+            };
+            BatchOutcome::Updated(verb)
+        }
+        Ok(None) => BatchOutcome::Unchanged,
+        Err(e) => BatchOutcome::Error(e.to_string()),
+    };
+
+    if let Some(version) = FileVersion::of(&canonical) {
+        if let Ok(mut cache) = hashes.lock() {
+            cache.insert(canonical.clone(), version);
+        }
+    }
+
+    let new_deps = file_dependencies(&canonical, root, processor);
+    if let Ok(mut g) = graph.lock() {
+        for dependents in g.values_mut() {
+            dependents.remove(&canonical);
+        }
+        for dep in new_deps {
+            g.entry(dep).or_default().insert(canonical.clone());
+        }
+    }
+
+    outcome
+}
+
+/// Dispatch a `Modify(ModifyKind::Name(..))` rename event. `Both`-mode
+/// events carry `[old, new]` in one shot; `From`/`To` arrive as separate
+/// events, so the `From` half is stashed in `pending_rename` until its `To`
+/// counterpart shows up (or the debounce window ends without one).
+fn handle_rename_event(
+    mode: &notify::event::RenameMode,
+    paths: &[PathBuf],
+    config: &Config,
+    processor: &FileProcessor,
+    root: &Path,
+    updates: &AtomicUsize,
+    graph: &Mutex<DependencyGraph>,
+    hashes: &Mutex<HashCache>,
+    pending_rename: &Mutex<Option<PathBuf>>,
+) {
+    use notify::event::RenameMode;
+
+    match mode {
+        RenameMode::Both => {
+            if let [old, new] = paths {
+                move_sidecar(old, new, config, processor, root, updates, graph, hashes);
+            }
+        }
+        RenameMode::From => {
+            if let Some(old) = paths.first() {
+                if let Ok(mut pending) = pending_rename.lock() {
+                    *pending = Some(old.clone());
+                }
+            }
+        }
+        RenameMode::To => {
+            if let Some(new) = paths.first() {
+                let old = pending_rename.lock().ok().and_then(|mut p| p.take());
+                match old {
+                    Some(old) => {
+                        move_sidecar(&old, new, config, processor, root, updates, graph, hashes)
+                    }
+                    None => handle_event(
+                        new,
+                        &notify::EventKind::Create(notify::event::CreateKind::File),
+                        config,
+                        processor,
+                        root,
+                        updates,
+                        graph,
+                        hashes,
+                    ),
+                }
+            }
+        }
+        RenameMode::Any | RenameMode::Other => {
+            for p in paths {
+                handle_event(
+                    p,
+                    &notify::EventKind::Modify(notify::event::ModifyKind::Any),
+                    config,
+                    processor,
+                    root,
+                    updates,
+                    graph,
+                    hashes,
+                );
+            }
+        }
+    }
+}
+
+/// Move `old`'s sidecar to `new`'s path and regenerate it in place so the
+/// embedded `file:` path reflects the rename, updating the dependency graph
+/// and hash cache to follow the file to its new identity.
+fn move_sidecar(
+    old: &Path,
+    new: &Path,
+    config: &Config,
+    processor: &FileProcessor,
+    root: &Path,
+    updates: &AtomicUsize,
+    graph: &Mutex<DependencyGraph>,
+    hashes: &Mutex<HashCache>,
+) {
+    if !is_watchable(new, config, root) {
+        return;
+    }
+
+    let old_sidecar = sidecar_path_for(old);
+    let new_sidecar = sidecar_path_for(new);
+    let display = new_sidecar
+        .strip_prefix(root)
+        .unwrap_or(&new_sidecar)
+        .display();
+
+    if old_sidecar.exists() {
+        if let Err(e) = std::fs::rename(&old_sidecar, &new_sidecar) {
+            eprintln!("  {} {}: {}", "✗".red(), display, e);
+        }
+    }
+
+    match processor.process(new, false) {
+        Ok(_) => println!("  {} Renamed {}", "✓".green(), display),
+        Err(e) => eprintln!("  {} {}: {}", "✗".red(), display, e),
+    }
+    updates.fetch_add(1, Ordering::Relaxed);
+
+    if let Ok(mut cache) = hashes.lock() {
+        cache.remove(old);
+        if let Some(version) = FileVersion::of(new) {
+            cache.insert(new.to_path_buf(), version);
+        }
+    }
+
+    if let Ok(mut g) = graph.lock() {
+        if let Some(dependents) = g.remove(old) {
+            g.insert(new.to_path_buf(), dependents);
+        }
+        for dependents in g.values_mut() {
+            if dependents.remove(old) {
+                dependents.insert(new.to_path_buf());
+            }
+        }
+    }
+}
+
+/// A `From` rename half with no `To` counterpart by the end of the debounce
+/// window — clean up the stale sidecar left at the old path.
+fn cleanup_orphaned_rename(
+    old: &Path,
+    root: &Path,
+    updates: &AtomicUsize,
+    graph: &Mutex<DependencyGraph>,
+    hashes: &Mutex<HashCache>,
+) {
+    let sidecar = sidecar_path_for(old);
+    if sidecar.exists() {
+        let display = sidecar.strip_prefix(root).unwrap_or(&sidecar).display();
+        match std::fs::remove_file(&sidecar) {
+            Ok(()) => {
+                println!("  {} Removed {}", "✓".green(), display);
+                updates.fetch_add(1, Ordering::Relaxed);
+            }
+            Err(e) => eprintln!("  {} {}: {}", "✗".red(), display, e),
+        }
+    }
+
+    if let Ok(mut cache) = hashes.lock() {
+        cache.remove(old);
+    }
+    if let Ok(mut g) = graph.lock() {
+        g.remove(old);
+        for dependents in g.values_mut() {
+            dependents.remove(old);
+        }
+    }
+}
+
+/// BFS over `graph` from `changed`, re-processing every transitive
+/// dependent. A `visited` set breaks `#include`-style cycles.
+fn cascade_to_dependents(
+    changed: &Path,
+    graph: &Mutex<DependencyGraph>,
+    processor: &FileProcessor,
+    root: &Path,
+    updates: &AtomicUsize,
+) {
+    let mut visited: HashSet<PathBuf> = HashSet::new();
+    visited.insert(changed.to_path_buf());
+    let mut queue: VecDeque<PathBuf> = VecDeque::new();
+    queue.push_back(changed.to_path_buf());
+
+    while let Some(current) = queue.pop_front() {
+        let dependents = graph
+            .lock()
+            .ok()
+            .and_then(|g| g.get(&current).cloned())
+            .unwrap_or_default();
+
+        for dependent in dependents {
+            if !visited.insert(dependent.clone()) {
+                continue;
+            }
+            let sidecar = sidecar_path_for(&dependent);
+            let display = sidecar.strip_prefix(root).unwrap_or(&sidecar).display();
+            match processor.process(&dependent, false) {
+                Ok(Some(_)) => {
+                    println!("  {} {} (dependent)", "✓".green(), display);
+                    updates.fetch_add(1, Ordering::Relaxed);
+                }
+                Ok(None) => {}
+                Err(e) => eprintln!("  {} {}: {}", "✗".red(), display, e),
+            }
+            queue.push_back(dependent);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use notify::event::{CreateKind, ModifyKind, RemoveKind};
+    use notify::event::{CreateKind, ModifyKind, RemoveKind, RenameMode};
     use std::fs;
     use tempfile::TempDir;
 
@@ -176,7 +907,7 @@ mod tests {
         let ts_file = tmp.path().join("foo.ts");
         fs::write(&ts_file, "").unwrap();
         let config = Config::default();
-        assert!(is_watchable(&ts_file, &config));
+        assert!(is_watchable(&ts_file, &config, tmp.path()));
     }
 
     #[test]
@@ -185,7 +916,7 @@ mod tests {
         let fmm_file = tmp.path().join("foo.ts.fmm");
         fs::write(&fmm_file, "").unwrap();
         let config = Config::default();
-        assert!(!is_watchable(&fmm_file, &config));
+        assert!(!is_watchable(&fmm_file, &config, tmp.path()));
     }
 
     #[test]
@@ -194,7 +925,7 @@ mod tests {
         let txt_file = tmp.path().join("readme.txt");
         fs::write(&txt_file, "").unwrap();
         let config = Config::default();
-        assert!(!is_watchable(&txt_file, &config));
+        assert!(!is_watchable(&txt_file, &config, tmp.path()));
     }
 
     #[test]
@@ -203,7 +934,7 @@ mod tests {
         let dir = tmp.path().join("src");
         fs::create_dir_all(&dir).unwrap();
         let config = Config::default();
-        assert!(!is_watchable(&dir, &config));
+        assert!(!is_watchable(&dir, &config, tmp.path()));
     }
 
     #[test]
@@ -212,7 +943,7 @@ mod tests {
         let no_ext = tmp.path().join("Makefile");
         fs::write(&no_ext, "").unwrap();
         let config = Config::default();
-        assert!(!is_watchable(&no_ext, &config));
+        assert!(!is_watchable(&no_ext, &config, tmp.path()));
     }
 
     #[test]
@@ -230,6 +961,8 @@ mod tests {
             &processor,
             &root,
             &updates,
+            &Mutex::new(HashMap::new()),
+            &Mutex::new(HashMap::new()),
         );
 
         assert!(sidecar_path_for(&source).exists());
@@ -261,6 +994,8 @@ mod tests {
             &processor,
             &root,
             &updates,
+            &Mutex::new(HashMap::new()),
+            &Mutex::new(HashMap::new()),
         );
 
         let sidecar_content = fs::read_to_string(sidecar_path_for(&source)).unwrap();
@@ -287,6 +1022,8 @@ mod tests {
             &processor,
             &root,
             &updates,
+            &Mutex::new(HashMap::new()),
+            &Mutex::new(HashMap::new()),
         );
 
         assert_eq!(updates.load(Ordering::Relaxed), 0);
@@ -315,6 +1052,8 @@ mod tests {
             &processor,
             &root,
             &updates,
+            &Mutex::new(HashMap::new()),
+            &Mutex::new(HashMap::new()),
         );
 
         assert!(!sidecar.exists());
@@ -339,6 +1078,8 @@ mod tests {
             &processor,
             &root,
             &updates,
+            &Mutex::new(HashMap::new()),
+            &Mutex::new(HashMap::new()),
         );
 
         assert_eq!(updates.load(Ordering::Relaxed), 0);
@@ -360,6 +1101,8 @@ mod tests {
             &processor,
             &root,
             &updates,
+            &Mutex::new(HashMap::new()),
+            &Mutex::new(HashMap::new()),
         );
 
         assert_eq!(updates.load(Ordering::Relaxed), 0);
@@ -381,6 +1124,8 @@ mod tests {
             &processor,
             &root,
             &updates,
+            &Mutex::new(HashMap::new()),
+            &Mutex::new(HashMap::new()),
         );
 
         assert_eq!(updates.load(Ordering::Relaxed), 0);
@@ -402,6 +1147,8 @@ mod tests {
             &processor,
             &root,
             &updates,
+            &Mutex::new(HashMap::new()),
+            &Mutex::new(HashMap::new()),
         );
 
         let sidecar = sidecar_path_for(&new_file);
@@ -410,4 +1157,478 @@ mod tests {
         assert!(content.contains("Widget"));
         assert_eq!(updates.load(Ordering::Relaxed), 1);
     }
+
+    #[test]
+    fn file_dependencies_resolves_relative_imports_to_disk_paths() {
+        let tmp = TempDir::new().unwrap();
+        let src = tmp.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("util.ts"), "export function helper() {}\n").unwrap();
+        fs::write(
+            src.join("app.ts"),
+            "import { helper } from './util';\nexport function main() {}\n",
+        )
+        .unwrap();
+        let root = tmp.path().canonicalize().unwrap();
+        let processor = FileProcessor::new(&root);
+
+        let deps = file_dependencies(&root.join("src/app.ts"), &root, &processor);
+
+        assert_eq!(deps, vec![root.join("src/util.ts")]);
+    }
+
+    #[test]
+    fn handle_modify_cascades_to_dependents() {
+        let tmp = TempDir::new().unwrap();
+        let src = tmp.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("util.ts"), "export function helper() {}\n").unwrap();
+        fs::write(
+            src.join("app.ts"),
+            "import { helper } from './util';\nexport function main() {}\n",
+        )
+        .unwrap();
+        let config = Config::default();
+        let root = tmp.path().canonicalize().unwrap();
+        let util = root.join("src/util.ts");
+        let app = root.join("src/app.ts");
+        let processor = FileProcessor::new(&root);
+
+        // Seed sidecars and the dependency graph as `watch()` would on startup.
+        processor.process(&util, false).unwrap();
+        processor.process(&app, false).unwrap();
+        let graph = Mutex::new(build_dependency_graph(
+            &[util.clone(), app.clone()],
+            &root,
+            &processor,
+        ));
+        let updates = AtomicUsize::new(0);
+
+        fs::write(
+            &util,
+            "export function helper() {}\nexport function helper2() {}\n",
+        )
+        .unwrap();
+        // Drop app.ts's sidecar so its cascade re-run is unambiguously observable
+        // (a content-unchanged dependent may otherwise be reported as a no-op by
+        // the underlying processor, which only diffs a file against its own sidecar).
+        fs::remove_file(sidecar_path_for(&app)).unwrap();
+
+        handle_event(
+            &util,
+            &notify::EventKind::Modify(ModifyKind::Data(notify::event::DataChange::Content)),
+            &config,
+            &processor,
+            &root,
+            &updates,
+            &graph,
+            &Mutex::new(HashMap::new()),
+        );
+
+        // util.ts itself plus its dependent app.ts both get reprocessed.
+        assert_eq!(updates.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn reload_config_picks_up_newly_added_language() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path().canonicalize().unwrap();
+        fs::write(root.join("app.rb"), "puts 'hi'\n").unwrap();
+
+        let config = RwLock::new(Config::default());
+        let config_path = root.join(CONFIG_FILE_NAME);
+        assert!(!is_watchable(&root.join("app.rb"), &config.read().unwrap(), &root));
+
+        fs::write(&config_path, r#"{"languages": ["rb"]}"#).unwrap();
+        let processor = FileProcessor::new(&root);
+        let updates = AtomicUsize::new(0);
+        reload_config(
+            &config,
+            &config_path,
+            root.to_str().unwrap(),
+            &processor,
+            &root,
+            &updates,
+        );
+
+        assert!(is_watchable(&root.join("app.rb"), &config.read().unwrap(), &root));
+    }
+
+    #[test]
+    fn reload_config_generates_sidecar_for_newly_included_file() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path().canonicalize().unwrap();
+        fs::write(root.join("app.rb"), "puts 'hi'\n").unwrap();
+        let sidecar = sidecar_path_for(&root.join("app.rb"));
+
+        let config = RwLock::new(Config::default());
+        let config_path = root.join(CONFIG_FILE_NAME);
+        assert!(!sidecar.exists());
+
+        fs::write(&config_path, r#"{"languages": ["rb"]}"#).unwrap();
+        let processor = FileProcessor::new(&root);
+        let updates = AtomicUsize::new(0);
+        reload_config(
+            &config,
+            &config_path,
+            root.to_str().unwrap(),
+            &processor,
+            &root,
+            &updates,
+        );
+
+        assert!(sidecar.exists());
+        assert_eq!(updates.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn reload_config_prunes_sidecar_for_newly_excluded_file() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path().canonicalize().unwrap();
+        fs::write(root.join("app.ts"), "export const a = 1;\n").unwrap();
+        let processor = FileProcessor::new(&root);
+        processor.process(&root.join("app.ts"), false).unwrap();
+        let sidecar = sidecar_path_for(&root.join("app.ts"));
+        assert!(sidecar.exists());
+
+        let config = RwLock::new(Config::default());
+        let config_path = root.join(CONFIG_FILE_NAME);
+        fs::write(&config_path, r#"{"exclude": ["app.ts"]}"#).unwrap();
+        let updates = AtomicUsize::new(0);
+        reload_config(
+            &config,
+            &config_path,
+            root.to_str().unwrap(),
+            &processor,
+            &root,
+            &updates,
+        );
+
+        assert!(!sidecar.exists());
+        assert_eq!(updates.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn reload_config_keeps_running_config_on_invalid_json() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path().canonicalize().unwrap();
+        fs::write(root.join("app.rb"), "puts 'hi'\n").unwrap();
+
+        let mut initial = Config::default();
+        initial.languages.insert("rb".to_string());
+        let config = RwLock::new(initial);
+        let config_path = root.join(CONFIG_FILE_NAME);
+        fs::write(&config_path, "{ not valid json").unwrap();
+
+        let processor = FileProcessor::new(&root);
+        let updates = AtomicUsize::new(0);
+        reload_config(
+            &config,
+            &config_path,
+            root.to_str().unwrap(),
+            &processor,
+            &root,
+            &updates,
+        );
+
+        // Invalid config is rejected — "rb" stays supported from the
+        // previously running config rather than falling back to the default.
+        assert!(is_watchable(&root.join("app.rb"), &config.read().unwrap(), &root));
+        assert_eq!(updates.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn paths_match_compares_canonicalized_paths() {
+        let tmp = TempDir::new().unwrap();
+        let file = tmp.path().join("a.txt");
+        fs::write(&file, "").unwrap();
+        let relative = tmp.path().join(".").join("a.txt");
+
+        assert!(paths_match(&file, &relative));
+    }
+
+    #[test]
+    fn handle_modify_skips_parsing_when_content_hash_is_unchanged() {
+        let (tmp, config) = setup_watch_project();
+        let root = tmp.path().canonicalize().unwrap();
+        let source = root.join("src/app.ts");
+        let processor = FileProcessor::new(&root);
+        let updates = AtomicUsize::new(0);
+        let graph = Mutex::new(HashMap::new());
+
+        // Seed the hash cache as `watch()`'s initial `collect_files` pass
+        // would, *without* ever generating a sidecar — the skip must happen
+        // before the parser runs at all, not as a post-parse diff.
+        let version = FileVersion::of(&source).unwrap();
+        let hashes = Mutex::new(HashMap::from([(source.clone(), version)]));
+
+        handle_event(
+            &source,
+            &notify::EventKind::Modify(ModifyKind::Data(notify::event::DataChange::Content)),
+            &config,
+            &processor,
+            &root,
+            &updates,
+            &graph,
+            &hashes,
+        );
+
+        assert!(!sidecar_path_for(&source).exists());
+        assert_eq!(updates.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn file_version_is_unchanged_falls_back_to_content_hash_when_fingerprint_moved() {
+        let tmp = TempDir::new().unwrap();
+        let file = tmp.path().join("a.ts");
+        fs::write(&file, "export const a = 1;\n").unwrap();
+        let version = FileVersion::of(&file).unwrap();
+
+        // Rewriting identical bytes moves the mtime (simulating `git
+        // checkout` touching a file without changing its content) but the
+        // content hash fallback should still report it as unchanged.
+        fs::write(&file, "export const a = 1;\n").unwrap();
+
+        assert!(version.is_unchanged(&file));
+    }
+
+    #[test]
+    fn file_version_is_unchanged_detects_real_content_changes() {
+        let tmp = TempDir::new().unwrap();
+        let file = tmp.path().join("a.ts");
+        fs::write(&file, "export const a = 1;\n").unwrap();
+        let version = FileVersion::of(&file).unwrap();
+
+        fs::write(&file, "export const a = 2;\n").unwrap();
+
+        assert!(!version.is_unchanged(&file));
+    }
+
+    #[test]
+    fn handle_rename_both_moves_sidecar_to_new_path() {
+        let (tmp, config) = setup_watch_project();
+        let root = tmp.path().canonicalize().unwrap();
+        let old = root.join("src/app.ts");
+        let new = root.join("src/main.ts");
+        let processor = FileProcessor::new(&root);
+        let updates = AtomicUsize::new(0);
+
+        processor.process(&old, false).unwrap();
+        assert!(sidecar_path_for(&old).exists());
+        fs::rename(&old, &new).unwrap();
+
+        handle_rename_event(
+            &RenameMode::Both,
+            &[old.clone(), new.clone()],
+            &config,
+            &processor,
+            &root,
+            &updates,
+            &Mutex::new(HashMap::new()),
+            &Mutex::new(HashMap::new()),
+            &Mutex::new(None),
+        );
+
+        assert!(!sidecar_path_for(&old).exists());
+        assert!(sidecar_path_for(&new).exists());
+        assert_eq!(updates.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn handle_rename_split_from_then_to_moves_sidecar() {
+        let (tmp, config) = setup_watch_project();
+        let root = tmp.path().canonicalize().unwrap();
+        let old = root.join("src/app.ts");
+        let new = root.join("src/main.ts");
+        let processor = FileProcessor::new(&root);
+        let updates = AtomicUsize::new(0);
+        let pending = Mutex::new(None);
+
+        processor.process(&old, false).unwrap();
+        fs::rename(&old, &new).unwrap();
+
+        handle_rename_event(
+            &RenameMode::From,
+            &[old.clone()],
+            &config,
+            &processor,
+            &root,
+            &updates,
+            &Mutex::new(HashMap::new()),
+            &Mutex::new(HashMap::new()),
+            &pending,
+        );
+        // The `From` half alone performs no work yet — it's stashed for
+        // correlation with the matching `To`.
+        assert_eq!(updates.load(Ordering::Relaxed), 0);
+
+        handle_rename_event(
+            &RenameMode::To,
+            &[new.clone()],
+            &config,
+            &processor,
+            &root,
+            &updates,
+            &Mutex::new(HashMap::new()),
+            &Mutex::new(HashMap::new()),
+            &pending,
+        );
+
+        assert!(!sidecar_path_for(&old).exists());
+        assert!(sidecar_path_for(&new).exists());
+        assert_eq!(updates.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn cleanup_orphaned_rename_removes_stale_sidecar() {
+        let (tmp, _config) = setup_watch_project();
+        let root = tmp.path().canonicalize().unwrap();
+        let old = root.join("src/app.ts");
+        let processor = FileProcessor::new(&root);
+        let updates = AtomicUsize::new(0);
+
+        processor.process(&old, false).unwrap();
+        assert!(sidecar_path_for(&old).exists());
+        fs::remove_file(&old).unwrap();
+
+        cleanup_orphaned_rename(
+            &old,
+            &root,
+            &updates,
+            &Mutex::new(HashMap::new()),
+            &Mutex::new(HashMap::new()),
+        );
+
+        assert!(!sidecar_path_for(&old).exists());
+        assert_eq!(updates.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn is_watchable_rejects_paths_matched_by_root_gitignore() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path().canonicalize().unwrap();
+        fs::write(root.join(".gitignore"), "build/\n").unwrap();
+        let build = root.join("build");
+        fs::create_dir_all(&build).unwrap();
+        let ignored = build.join("app.ts");
+        fs::write(&ignored, "").unwrap();
+        let config = Config::default();
+
+        assert!(!is_watchable(&ignored, &config, &root));
+    }
+
+    #[test]
+    fn process_batch_dedupes_and_processes_paths_in_sorted_order() {
+        let tmp = TempDir::new().unwrap();
+        let src = tmp.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("zeta.ts"), "export const z = 1;\n").unwrap();
+        fs::write(src.join("alpha.ts"), "export const a = 1;\n").unwrap();
+        let root = tmp.path().canonicalize().unwrap();
+        let zeta = root.join("src/zeta.ts");
+        let alpha = root.join("src/alpha.ts");
+        let config = Config::default();
+        let processor = Arc::new(FileProcessor::new(&root));
+        let updates = AtomicUsize::new(0);
+        let graph = Mutex::new(HashMap::new());
+        let hashes = Mutex::new(HashMap::new());
+
+        // Duplicate entries (as a noisy debounce batch would deliver) and
+        // out-of-order paths should still yield exactly one sidecar per file.
+        let batch = vec![zeta.clone(), alpha.clone(), zeta.clone()];
+        process_batch(
+            &batch, &config, &processor, &root, &updates, &graph, &hashes,
+        );
+
+        assert!(sidecar_path_for(&zeta).exists());
+        assert!(sidecar_path_for(&alpha).exists());
+        assert_eq!(updates.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn process_batch_skips_paths_rejected_by_is_watchable() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path().canonicalize().unwrap();
+        let txt = root.join("notes.txt");
+        fs::write(&txt, "not code").unwrap();
+        let config = Config::default();
+        let processor = Arc::new(FileProcessor::new(&root));
+        let updates = AtomicUsize::new(0);
+        let graph = Mutex::new(HashMap::new());
+        let hashes = Mutex::new(HashMap::new());
+
+        process_batch(
+            &[txt.clone()],
+            &config,
+            &processor,
+            &root,
+            &updates,
+            &graph,
+            &hashes,
+        );
+
+        assert!(!sidecar_path_for(&txt).exists());
+        assert_eq!(updates.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn process_batch_cascades_to_dependents() {
+        let tmp = TempDir::new().unwrap();
+        let src = tmp.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("util.ts"), "export function helper() {}\n").unwrap();
+        fs::write(
+            src.join("app.ts"),
+            "import { helper } from './util';\nexport function main() {}\n",
+        )
+        .unwrap();
+        let config = Config::default();
+        let root = tmp.path().canonicalize().unwrap();
+        let util = root.join("src/util.ts");
+        let app = root.join("src/app.ts");
+        let processor = Arc::new(FileProcessor::new(&root));
+
+        processor.process(&util, false).unwrap();
+        processor.process(&app, false).unwrap();
+        let graph = Mutex::new(build_dependency_graph(
+            &[util.clone(), app.clone()],
+            &root,
+            &processor,
+        ));
+        let hashes = Mutex::new(HashMap::new());
+        let updates = AtomicUsize::new(0);
+
+        fs::write(
+            &util,
+            "export function helper() {}\nexport function helper2() {}\n",
+        )
+        .unwrap();
+        fs::remove_file(sidecar_path_for(&app)).unwrap();
+
+        process_batch(
+            &[util.clone()],
+            &config,
+            &processor,
+            &root,
+            &updates,
+            &graph,
+            &hashes,
+        );
+
+        assert_eq!(updates.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn is_watchable_rejects_paths_matched_by_config_exclude_glob() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path().canonicalize().unwrap();
+        let vendor = root.join("vendor");
+        fs::create_dir_all(&vendor).unwrap();
+        let ignored = vendor.join("lib.ts");
+        fs::write(&ignored, "").unwrap();
+        let mut config = Config::default();
+        config.exclude = vec!["vendor/**".to_string()];
+
+        assert!(!is_watchable(&ignored, &config, &root));
+    }
 }
@@ -0,0 +1,238 @@
+//! Pluggable benchmark backends behind a shared `Runner` trait.
+//!
+//! `ClaudeRunner` only ever drove the `claude` CLI, so benchmarking FMM's
+//! injected context against a different assistant meant rewriting the
+//! harness. Each backend instead implements [`Runner`] and normalizes its
+//! own output into the shared [`RunResult`], the way aichat dispatches the
+//! same prompt across multiple LLM clients behind one interface.
+
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+use std::time::Instant;
+
+use super::cache::{config_digest, CacheKey};
+use super::runner::{ClaudeRunner, RunResult};
+use super::tasks::Task;
+
+/// A benchmark backend capable of running one [`Task`] and returning a
+/// normalized [`RunResult`], regardless of which underlying CLI or model
+/// it shells out to.
+pub trait Runner: Send + Sync {
+    /// Short, stable identifier used on the CLI (`--backend claude`).
+    /// Backends other than `claude` fold it into their cache-key digest so
+    /// two backends never share a result.
+    fn name(&self) -> &'static str;
+
+    fn run_task(
+        &self,
+        task: &Task,
+        working_dir: &Path,
+        variant: &str,
+        fmm_context: Option<&str>,
+    ) -> Result<RunResult>;
+
+    /// Build the cache key for a run of `task` under this backend's
+    /// configuration (see [`ClaudeRunner::cache_key`]), so a differently
+    /// configured or differently named backend never gets served a stale
+    /// [`RunResult`] from another one.
+    fn cache_key(
+        &self,
+        repo_url: &str,
+        commit_sha: &str,
+        task: &Task,
+        variant: &str,
+        fmm_context: Option<&str>,
+    ) -> CacheKey;
+}
+
+impl Runner for ClaudeRunner {
+    fn name(&self) -> &'static str {
+        "claude"
+    }
+
+    fn run_task(
+        &self,
+        task: &Task,
+        working_dir: &Path,
+        variant: &str,
+        fmm_context: Option<&str>,
+    ) -> Result<RunResult> {
+        ClaudeRunner::run_task(self, task, working_dir, variant, fmm_context)
+    }
+
+    fn cache_key(
+        &self,
+        repo_url: &str,
+        commit_sha: &str,
+        task: &Task,
+        variant: &str,
+        fmm_context: Option<&str>,
+    ) -> CacheKey {
+        ClaudeRunner::cache_key(self, repo_url, commit_sha, task, variant, fmm_context)
+    }
+}
+
+/// Adapter for an OpenAI-compatible, aichat-style CLI: a single-shot
+/// `aichat <prompt>` invocation rather than `claude`'s `stream-json`
+/// transcript, so there's no tool-call/token breakdown to parse — just an
+/// exit status and the response text on stdout.
+#[derive(Debug, Clone, Default)]
+pub struct AichatRunner {
+    /// `--model` passed through to `aichat` (provider-qualified, e.g.
+    /// `openai:gpt-4o`), if set.
+    model: Option<String>,
+}
+
+impl AichatRunner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the `--model` argument passed to `aichat`.
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+}
+
+impl Runner for AichatRunner {
+    fn name(&self) -> &'static str {
+        "aichat"
+    }
+
+    fn cache_key(
+        &self,
+        repo_url: &str,
+        commit_sha: &str,
+        task: &Task,
+        variant: &str,
+        fmm_context: Option<&str>,
+    ) -> CacheKey {
+        let model = format!(
+            "{}:{}",
+            self.name(),
+            self.model.as_deref().unwrap_or("default")
+        );
+        let digest = config_digest(
+            &model,
+            &[],
+            false,
+            fmm_context,
+            task.max_turns,
+            task.max_budget_usd,
+        );
+        CacheKey::with_config(repo_url, commit_sha, &task.id, variant, &digest)
+    }
+
+    fn run_task(
+        &self,
+        task: &Task,
+        working_dir: &Path,
+        variant: &str,
+        fmm_context: Option<&str>,
+    ) -> Result<RunResult> {
+        let start = Instant::now();
+
+        let prompt = match fmm_context {
+            Some(context) => format!("{context}\n\n{}", task.prompt),
+            None => task.prompt.clone(),
+        };
+
+        let mut cmd = Command::new("aichat");
+        cmd.current_dir(working_dir);
+        cmd.arg("--no-stream");
+        if let Some(model) = &self.model {
+            cmd.arg("--model").arg(model);
+        }
+        cmd.arg(&prompt);
+
+        let output = cmd.output().context("Failed to spawn aichat CLI")?;
+        let duration = start.elapsed();
+        let success = output.status.success();
+
+        Ok(RunResult {
+            task_id: task.id.clone(),
+            variant: variant.to_string(),
+            tool_calls: 0,
+            tools_by_name: HashMap::new(),
+            files_accessed: vec![],
+            read_calls: 0,
+            failed_tool_calls: 0,
+            redundant_reads: 0,
+            tool_failures_by_name: HashMap::new(),
+            input_tokens: 0,
+            output_tokens: 0,
+            cache_read_tokens: 0,
+            total_cost_usd: 0.0,
+            duration_ms: duration.as_millis() as u64,
+            num_turns: 1,
+            response: String::from_utf8_lossy(&output.stdout).trim().to_string(),
+            success,
+            error: if success {
+                None
+            } else {
+                Some(String::from_utf8_lossy(&output.stderr).trim().to_string())
+            },
+        })
+    }
+}
+
+/// Select a backend by name, so the benchmark harness can compare the
+/// same tasks across providers without the caller knowing how each one
+/// constructs its command line or parses its output.
+pub fn select_runner(name: &str) -> Result<Box<dyn Runner>> {
+    match name {
+        "claude" => Ok(Box::new(ClaudeRunner::new())),
+        "aichat" => Ok(Box::new(AichatRunner::new())),
+        other => anyhow::bail!(
+            "unknown runner backend '{other}' (expected one of: {})",
+            known_backends().join(", ")
+        ),
+    }
+}
+
+/// Names `select_runner` accepts, for `--help` text and validation errors.
+pub fn known_backends() -> &'static [&'static str] {
+    &["claude", "aichat"]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn select_runner_accepts_known_backends() {
+        assert!(select_runner("claude").is_ok());
+        assert!(select_runner("aichat").is_ok());
+    }
+
+    #[test]
+    fn select_runner_rejects_unknown_backend() {
+        assert!(select_runner("not-a-real-backend").is_err());
+    }
+
+    #[test]
+    fn select_runner_name_matches_requested_backend() {
+        for backend in known_backends() {
+            assert_eq!(select_runner(backend).unwrap().name(), *backend);
+        }
+    }
+
+    #[test]
+    fn aichat_runner_fails_gracefully_without_the_binary() {
+        let runner = AichatRunner::new();
+        let task = Task {
+            id: "task".to_string(),
+            name: "Task".to_string(),
+            prompt: "hello".to_string(),
+            max_turns: 1,
+            max_budget_usd: 0.1,
+        };
+        // No `aichat` binary in the test environment, so this should
+        // surface as an error rather than panic.
+        let result = runner.run_task(&task, Path::new("."), "aichat", None);
+        assert!(result.is_err());
+    }
+}
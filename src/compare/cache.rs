@@ -1,11 +1,19 @@
 //! Result caching layer for comparison runs
+//!
+//! Modeled on [bkt](https://github.com/dimo414/bkt)'s subprocess-caching
+//! semantics: a fresh hit is served straight from disk/memory, a stale hit
+//! (past its TTL but still in the grace window) is served immediately while
+//! a background thread revalidates it, and a miss acquires a cross-process
+//! file lock before recomputing so two concurrent `fmm compare` runs never
+//! invoke the same expensive Claude CLI call twice for the same key.
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::time::{Duration, SystemTime};
+use std::sync::Mutex;
+use std::time::Duration;
 
 use super::report::ComparisonReport;
 use super::runner::RunResult;
@@ -17,28 +25,85 @@ pub struct CacheKey {
     pub commit_sha: String,
     pub task_id: String,
     pub variant: String,
+    /// Digest of the invocation parameters that don't have their own field
+    /// here but still affect the resulting `RunResult` — see
+    /// [`config_digest`]. Empty for keys built without one (legacy `new`),
+    /// which never collides with a digested key since the filename/hash
+    /// differ in shape.
+    pub config_digest: String,
 }
 
 impl CacheKey {
     pub fn new(repo_url: &str, commit_sha: &str, task_id: &str, variant: &str) -> Self {
+        Self::with_config(repo_url, commit_sha, task_id, variant, "")
+    }
+
+    /// Build a cache key that also folds in a digest of the runner/task
+    /// configuration (model, allowed tools, injected FMM context, task
+    /// limits), so two runs with different setups never share a cache
+    /// entry. Prefer [`super::runner::ClaudeRunner::cache_key`] over
+    /// calling this directly — it assembles `config_digest` for you.
+    pub fn with_config(
+        repo_url: &str,
+        commit_sha: &str,
+        task_id: &str,
+        variant: &str,
+        config_digest: &str,
+    ) -> Self {
         Self {
             repo_url: repo_url.to_string(),
             commit_sha: commit_sha.to_string(),
             task_id: task_id.to_string(),
             variant: variant.to_string(),
+            config_digest: config_digest.to_string(),
         }
     }
 
     /// Generate a filesystem-safe cache filename
     pub fn to_filename(&self) -> String {
         let url_hash = simple_hash(&self.repo_url);
-        format!(
-            "{}_{}_{}_{}",
-            url_hash, self.commit_sha, self.task_id, self.variant
-        )
+        if self.config_digest.is_empty() {
+            format!(
+                "{}_{}_{}_{}",
+                url_hash, self.commit_sha, self.task_id, self.variant
+            )
+        } else {
+            format!(
+                "{}_{}_{}_{}_{}",
+                url_hash, self.commit_sha, self.task_id, self.variant, self.config_digest
+            )
+        }
     }
 }
 
+/// Build a stable digest over invocation parameters that affect a
+/// `RunResult` but aren't part of a [`CacheKey`]'s identity fields: the
+/// model, the sorted allowed-tools list, whether permissions are skipped,
+/// the injected FMM context, and the task's turn/budget limits. Changing
+/// any of these should never return a cached result computed under a
+/// different configuration.
+pub fn config_digest(
+    model: &str,
+    allowed_tools: &[String],
+    skip_permissions: bool,
+    fmm_context: Option<&str>,
+    max_turns: u32,
+    max_budget_usd: f64,
+) -> String {
+    let mut tools = allowed_tools.to_vec();
+    tools.sort();
+    let parts = format!(
+        "{}|{}|{}|{}|{}|{}",
+        model,
+        tools.join(","),
+        skip_permissions,
+        fmm_context.unwrap_or(""),
+        max_turns,
+        max_budget_usd
+    );
+    simple_hash(&parts)
+}
+
 /// Cached result entry
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CachedResult {
@@ -48,13 +113,128 @@ pub struct CachedResult {
     pub expires_at: String,
 }
 
+/// Outcome of a [`CacheManager::get_or_run`] lookup, so callers can report
+/// what happened without re-deriving it themselves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheOutcome {
+    /// Served from a fresh (within-TTL) cache entry.
+    Hit,
+    /// Served from a stale (past-TTL, within grace) entry; a background
+    /// thread is revalidating it.
+    Stale,
+    /// No usable cache entry; recomputed synchronously.
+    Miss,
+    /// Served from a result cached under a different commit, via
+    /// changed-files-aware incremental reuse (see
+    /// [`CacheManager::find_reusable_result`]) — none of the files that run
+    /// touched appear in the diff against that commit.
+    Reused,
+}
+
+/// Sort order for cache-admin listing and [`CacheDeleteScope::Group`]
+/// selection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(dead_code)]
+pub enum CacheSort {
+    /// By `cached_at`, oldest first.
+    Oldest,
+    /// By file size on disk, largest first.
+    Largest,
+    /// By cache filename, alphabetically.
+    Alpha,
+}
+
+/// What [`CacheManager::delete_entries`] should remove.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[allow(dead_code)]
+pub enum CacheDeleteScope {
+    /// Delete every cached result.
+    All,
+    /// Sort entries by `sort` (reversed if `invert`), then delete the
+    /// first `n` of them.
+    Group {
+        sort: CacheSort,
+        invert: bool,
+        n: usize,
+    },
+}
+
+/// One row of a cache-admin listing: enough to audit and selectively
+/// prune benchmark results without hand-deleting cache JSON files.
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct CacheEntry {
+    pub repo_url: String,
+    pub commit_sha: String,
+    pub task_id: String,
+    pub variant: String,
+    pub size_bytes: u64,
+    pub cached_at: String,
+    path: PathBuf,
+}
+
+/// How a cache entry compares to now, relative to `ttl`/`stale_ttl`.
+#[derive(Debug, PartialEq, Eq)]
+enum Freshness {
+    Fresh,
+    Stale,
+    Expired,
+}
+
+/// Cross-process advisory lock: a sentinel `.lock` file in the cache
+/// directory, acquired by exclusive creation and released by removal on
+/// drop. Best-effort — a wait that times out proceeds anyway rather than
+/// deadlock on a lock file orphaned by a crashed process.
+struct CacheLock {
+    path: Option<PathBuf>,
+}
+
+impl CacheLock {
+    fn acquire(cache_dir: &Path, filename: &str) -> Self {
+        let path = cache_dir.join(format!("{}.lock", filename));
+        let deadline = std::time::Instant::now() + Duration::from_secs(30);
+
+        loop {
+            match fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&path)
+            {
+                Ok(_) => return Self { path: Some(path) },
+                Err(_) if std::time::Instant::now() < deadline => {
+                    std::thread::sleep(Duration::from_millis(50));
+                }
+                Err(_) => return Self { path: None },
+            }
+        }
+    }
+}
+
+impl Drop for CacheLock {
+    fn drop(&mut self) {
+        if let Some(path) = &self.path {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
 /// Cache manager for comparison results
 pub struct CacheManager {
     cache_dir: PathBuf,
     ttl: Duration,
+    /// Extra window past `ttl` during which an expired entry is still
+    /// served as "stale" while a background refresh runs, rather than
+    /// forcing the caller to block on a recompute.
+    stale_ttl: Duration,
     max_size_mb: u64,
-    /// In-memory cache for current session
-    memory_cache: HashMap<CacheKey, CachedResult>,
+    /// Bypass cache reads entirely and always recompute (cache writes still
+    /// happen, so a later non-forced run benefits).
+    force_refresh: bool,
+    /// In-memory cache for current session. A `Mutex` rather than a plain
+    /// map so the comparison worker pool can share one `CacheManager`
+    /// across threads instead of serializing every lookup behind
+    /// `Orchestrator`'s own borrow.
+    memory_cache: Mutex<HashMap<CacheKey, CachedResult>>,
 }
 
 impl CacheManager {
@@ -72,8 +252,10 @@ impl CacheManager {
         Ok(Self {
             cache_dir,
             ttl: Duration::from_secs(7 * 24 * 3600), // 7 days
+            stale_ttl: Duration::from_secs(24 * 3600), // 1 day grace
             max_size_mb: 100,
-            memory_cache: HashMap::new(),
+            force_refresh: false,
+            memory_cache: Mutex::new(HashMap::new()),
         })
     }
 
@@ -84,6 +266,13 @@ impl CacheManager {
         self
     }
 
+    /// Set the stale-while-revalidate grace window.
+    #[allow(dead_code)]
+    pub fn with_stale_ttl(mut self, stale_ttl: Duration) -> Self {
+        self.stale_ttl = stale_ttl;
+        self
+    }
+
     /// Set max cache size
     #[allow(dead_code)]
     pub fn with_max_size(mut self, max_size_mb: u64) -> Self {
@@ -91,11 +280,18 @@ impl CacheManager {
         self
     }
 
+    /// Bypass cache reads and always recompute, as if nothing were cached
+    /// (the fresh result still overwrites the cache entry for next time).
+    pub fn with_force_refresh(mut self, force_refresh: bool) -> Self {
+        self.force_refresh = force_refresh;
+        self
+    }
+
     /// Get a cached result
-    pub fn get(&mut self, key: &CacheKey) -> Option<RunResult> {
+    pub fn get(&self, key: &CacheKey) -> Option<RunResult> {
         // Check memory cache first
-        if let Some(cached) = self.memory_cache.get(key) {
-            if !Self::is_expired(&cached.expires_at) {
+        if let Some(cached) = self.memory_cache.lock().unwrap().get(key) {
+            if cached.key == *key && !Self::is_expired(&cached.expires_at) {
                 return Some(cached.result.clone());
             }
         }
@@ -107,9 +303,20 @@ impl CacheManager {
         if cache_path.exists() {
             if let Ok(content) = fs::read_to_string(&cache_path) {
                 if let Ok(cached) = serde_json::from_str::<CachedResult>(&content) {
+                    if cached.key != *key {
+                        // A filename collision between two different keys:
+                        // the embedded key doesn't match what we asked
+                        // for. Treat it as a miss and drop the offending
+                        // file so it doesn't keep shadowing this key.
+                        let _ = fs::remove_file(&cache_path);
+                        return None;
+                    }
                     if !Self::is_expired(&cached.expires_at) {
                         // Update memory cache
-                        self.memory_cache.insert(key.clone(), cached.clone());
+                        self.memory_cache
+                            .lock()
+                            .unwrap()
+                            .insert(key.clone(), cached.clone());
                         return Some(cached.result);
                     } else {
                         // Clean up expired entry
@@ -123,46 +330,225 @@ impl CacheManager {
     }
 
     /// Store a result in cache
-    pub fn set(&mut self, key: CacheKey, result: RunResult) -> Result<()> {
+    pub fn set(&self, key: CacheKey, result: RunResult) -> Result<()> {
+        let cached = Self::write_entry(&self.cache_dir, self.ttl, key.clone(), result)?;
+        self.memory_cache.lock().unwrap().insert(key, cached);
+        self.evict_if_needed()?;
+        Ok(())
+    }
+
+    /// Run `compute` through the cache, bkt-style: a fresh hit returns
+    /// immediately, a stale hit (past `ttl` but within `stale_ttl`) returns
+    /// the old value while `compute` reruns on a background thread, and a
+    /// true miss acquires a cross-process lock before recomputing so two
+    /// concurrent `fmm compare` invocations don't both pay for the same
+    /// subprocess call. `with_force_refresh(true)` skips straight to the
+    /// miss path.
+    pub fn get_or_run<F>(&self, key: &CacheKey, compute: F) -> Result<(RunResult, CacheOutcome)>
+    where
+        F: FnOnce() -> Result<RunResult> + Send + 'static,
+    {
+        if !self.force_refresh {
+            if let Some(cached) = self.lookup_entry(key) {
+                match Self::freshness(&cached.expires_at, self.stale_ttl) {
+                    Freshness::Fresh => return Ok((cached.result, CacheOutcome::Hit)),
+                    Freshness::Stale => {
+                        let stale_value = cached.result.clone();
+                        self.spawn_revalidate(key.clone(), compute);
+                        return Ok((stale_value, CacheOutcome::Stale));
+                    }
+                    Freshness::Expired => {}
+                }
+            }
+        }
+
+        // Miss, force-refresh, or stale-past-grace: recompute, but only
+        // after taking the lock so a concurrent process computing the same
+        // key doesn't duplicate the work.
+        let _lock = CacheLock::acquire(&self.cache_dir, &key.to_filename());
+
+        // Another process may have finished while we waited for the lock.
+        if !self.force_refresh {
+            if let Some(cached) = self.lookup_entry(key) {
+                if Self::freshness(&cached.expires_at, self.stale_ttl) == Freshness::Fresh {
+                    return Ok((cached.result, CacheOutcome::Hit));
+                }
+            }
+        }
+
+        let result = compute()?;
+        if result.success {
+            self.set(key.clone(), result.clone())?;
+        }
+        Ok((result, CacheOutcome::Miss))
+    }
+
+    /// Disk-only scan for a cached result of `(repo_url, task_id, variant)`
+    /// computed under any other commit, for changed-files-aware incremental
+    /// reuse: [`CacheManager::get_or_run`] keys on `commit_sha`, so a
+    /// comparison run against a new commit always misses on a repo's first
+    /// run there even when nothing the task actually touched changed.
+    /// Returns the most recently cached entry whose `RunResult::files_accessed`
+    /// doesn't intersect `changed_files`, ignoring expiry (a prior commit's
+    /// result is evidence about the current tree regardless of its own
+    /// TTL), or `None` if every candidate touched something that changed.
+    pub fn find_reusable_result(
+        &self,
+        repo_url: &str,
+        task_id: &str,
+        variant: &str,
+        changed_files: &HashSet<String>,
+    ) -> Option<RunResult> {
+        let dir = fs::read_dir(&self.cache_dir).ok()?;
+        let mut best: Option<CachedResult> = None;
+
+        for entry in dir.flatten() {
+            let path = entry.path();
+            if path.extension().is_none_or(|e| e != "json") {
+                continue;
+            }
+            let Ok(content) = fs::read_to_string(&path) else {
+                continue;
+            };
+            let Ok(cached) = serde_json::from_str::<CachedResult>(&content) else {
+                continue;
+            };
+            if cached.key.repo_url != repo_url
+                || cached.key.task_id != task_id
+                || cached.key.variant != variant
+            {
+                continue;
+            }
+            if cached
+                .result
+                .files_accessed
+                .iter()
+                .any(|f| changed_files.contains(f))
+            {
+                continue;
+            }
+            if best.as_ref().is_none_or(|b| cached.cached_at > b.cached_at) {
+                best = Some(cached);
+            }
+        }
+
+        best.map(|c| c.result)
+    }
+
+    /// Most recently cached commit SHA benchmarked for `repo_url`, other
+    /// than `exclude_commit` — the diff base for incremental reuse. `None`
+    /// if this repo has never been benchmarked before, or only at
+    /// `exclude_commit` itself.
+    pub fn last_cached_commit(&self, repo_url: &str, exclude_commit: &str) -> Option<String> {
+        self.list_entries()
+            .ok()?
+            .into_iter()
+            .filter(|e| e.repo_url == repo_url && e.commit_sha != exclude_commit)
+            .max_by(|a, b| a.cached_at.cmp(&b.cached_at))
+            .map(|e| e.commit_sha)
+    }
+
+    /// Read a cache entry regardless of expiry, checking memory then disk.
+    /// Verifies the entry's embedded key matches `key` exactly, since a
+    /// hash collision in the filename would otherwise silently serve a
+    /// different invocation's result; a mismatch is treated as no entry
+    /// and the offending file is removed.
+    fn lookup_entry(&self, key: &CacheKey) -> Option<CachedResult> {
+        if let Some(cached) = self.memory_cache.lock().unwrap().get(key) {
+            if cached.key == *key {
+                return Some(cached.clone());
+            }
+        }
+
+        let cache_path = self.cache_dir.join(format!("{}.json", key.to_filename()));
+        let content = fs::read_to_string(&cache_path).ok()?;
+        let cached: CachedResult = serde_json::from_str(&content).ok()?;
+        if cached.key != *key {
+            let _ = fs::remove_file(&cache_path);
+            return None;
+        }
+        self.memory_cache
+            .lock()
+            .unwrap()
+            .insert(key.clone(), cached.clone());
+        Some(cached)
+    }
+
+    fn freshness(expires_at: &str, stale_ttl: Duration) -> Freshness {
+        let Ok(expires) = chrono::DateTime::parse_from_rfc3339(expires_at) else {
+            return Freshness::Expired;
+        };
         let now = chrono::Utc::now();
-        let expires = now + chrono::Duration::from_std(self.ttl).unwrap_or_default();
+        if now <= expires {
+            Freshness::Fresh
+        } else if now <= expires + chrono::Duration::from_std(stale_ttl).unwrap_or_default() {
+            Freshness::Stale
+        } else {
+            Freshness::Expired
+        }
+    }
+
+    /// Kick off a background revalidation of `key`, writing the refreshed
+    /// result straight to disk. Best-effort: a failed recompute just leaves
+    /// the stale entry in place for the next caller to retry.
+    fn spawn_revalidate<F>(&self, key: CacheKey, compute: F)
+    where
+        F: FnOnce() -> Result<RunResult> + Send + 'static,
+    {
+        let cache_dir = self.cache_dir.clone();
+        let ttl = self.ttl;
+        std::thread::spawn(move || {
+            let _lock = CacheLock::acquire(&cache_dir, &key.to_filename());
+            if let Ok(result) = compute() {
+                if result.success {
+                    let _ = Self::write_entry(&cache_dir, ttl, key, result);
+                }
+            }
+        });
+    }
+
+    /// Write a fresh cache entry to disk and return it, without touching
+    /// any in-memory cache (callers on the background-refresh path don't
+    /// have access to `self`).
+    fn write_entry(
+        cache_dir: &Path,
+        ttl: Duration,
+        key: CacheKey,
+        result: RunResult,
+    ) -> Result<CachedResult> {
+        let now = chrono::Utc::now();
+        let expires = now + chrono::Duration::from_std(ttl).unwrap_or_default();
 
         let cached = CachedResult {
-            key: key.clone(),
+            key,
             result,
             cached_at: now.to_rfc3339(),
             expires_at: expires.to_rfc3339(),
         };
 
-        // Store in memory
-        self.memory_cache.insert(key.clone(), cached.clone());
-
-        // Store on disk
-        let filename = key.to_filename();
-        let cache_path = self.cache_dir.join(format!("{}.json", filename));
+        let cache_path = cache_dir.join(format!("{}.json", cached.key.to_filename()));
         let json = serde_json::to_string_pretty(&cached)?;
         fs::write(&cache_path, json).context("Failed to write cache file")?;
 
-        // Evict if needed
-        self.evict_if_needed()?;
-
-        Ok(())
+        Ok(cached)
     }
 
     /// Check if a cache key exists and is valid
     #[allow(dead_code)]
-    pub fn has(&mut self, key: &CacheKey) -> bool {
+    pub fn has(&self, key: &CacheKey) -> bool {
         self.get(key).is_some()
     }
 
     /// Clear all cached results for a repository
     #[allow(dead_code)]
-    pub fn clear_repo(&mut self, repo_url: &str) -> Result<u32> {
+    pub fn clear_repo(&self, repo_url: &str) -> Result<u32> {
         let url_hash = simple_hash(repo_url);
         let mut cleared = 0u32;
 
         // Clear from memory
         self.memory_cache
+            .lock()
+            .unwrap()
             .retain(|k, _| !k.repo_url.contains(repo_url));
 
         // Clear from disk
@@ -180,8 +566,8 @@ impl CacheManager {
 
     /// Clear all cache
     #[allow(dead_code)]
-    pub fn clear_all(&mut self) -> Result<u32> {
-        self.memory_cache.clear();
+    pub fn clear_all(&self) -> Result<u32> {
+        self.memory_cache.lock().unwrap().clear();
 
         let mut cleared = 0u32;
         for entry in fs::read_dir(&self.cache_dir)? {
@@ -260,38 +646,110 @@ impl CacheManager {
             return Ok(());
         }
 
-        // Get all cache files with their modification times
-        let mut entries: Vec<(PathBuf, SystemTime)> = vec![];
+        // Delete the oldest group of entries until back under 80% of the
+        // limit, reusing the same selection machinery cache-admin uses.
+        let target_size = self.max_size_mb * 1_000_000 * 80 / 100;
+        let entries = Self::sort_entries(self.list_entries()?, CacheSort::Oldest, false);
+        let mut current_size = total_size;
+
+        for entry in entries {
+            if current_size <= target_size {
+                break;
+            }
+            if fs::remove_file(&entry.path).is_ok() {
+                current_size = current_size.saturating_sub(entry.size_bytes);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Enumerate every cached result on disk into a sortable table, so a
+    /// CLI can audit or prune benchmark results without hand-deleting
+    /// cache JSON files.
+    #[allow(dead_code)]
+    pub fn list_entries(&self) -> Result<Vec<CacheEntry>> {
+        let mut entries = vec![];
+
         for entry in fs::read_dir(&self.cache_dir)? {
             let entry = entry?;
-            if entry.path().extension().is_some_and(|e| e == "json") {
-                if let Ok(metadata) = entry.metadata() {
-                    if let Ok(modified) = metadata.modified() {
-                        entries.push((entry.path(), modified));
-                    }
-                }
+            let path = entry.path();
+            if path.extension().is_none_or(|e| e != "json") {
+                continue;
             }
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if !metadata.is_file() {
+                continue;
+            }
+            let Ok(content) = fs::read_to_string(&path) else {
+                continue;
+            };
+            let Ok(cached) = serde_json::from_str::<CachedResult>(&content) else {
+                continue;
+            };
+
+            entries.push(CacheEntry {
+                repo_url: cached.key.repo_url,
+                commit_sha: cached.key.commit_sha,
+                task_id: cached.key.task_id,
+                variant: cached.key.variant,
+                size_bytes: metadata.len(),
+                cached_at: cached.cached_at,
+                path,
+            });
         }
 
-        // Sort by modification time (oldest first)
-        entries.sort_by(|a, b| a.1.cmp(&b.1));
+        Ok(entries)
+    }
 
-        // Remove oldest entries until under limit
-        let target_size = self.max_size_mb * 1_000_000 * 80 / 100; // Target 80%
-        let mut current_size = total_size;
+    /// Sort `entries` by `sort`, reversing the result if `invert` is set.
+    fn sort_entries(
+        mut entries: Vec<CacheEntry>,
+        sort: CacheSort,
+        invert: bool,
+    ) -> Vec<CacheEntry> {
+        match sort {
+            CacheSort::Oldest => entries.sort_by(|a, b| a.cached_at.cmp(&b.cached_at)),
+            CacheSort::Largest => entries.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes)),
+            CacheSort::Alpha => entries.sort_by(|a, b| a.path.file_name().cmp(&b.path.file_name())),
+        }
+        if invert {
+            entries.reverse();
+        }
+        entries
+    }
 
-        for (path, _) in entries {
-            if current_size <= target_size {
-                break;
+    /// Delete cache entries matching `scope`, returning `(count, freed_bytes)`
+    /// so a CLI can report what was cleared.
+    #[allow(dead_code)]
+    pub fn delete_entries(&self, scope: &CacheDeleteScope) -> Result<(u32, u64)> {
+        let entries = self.list_entries()?;
+        let selected = match scope {
+            CacheDeleteScope::All => entries,
+            CacheDeleteScope::Group { sort, invert, n } => {
+                let sorted = Self::sort_entries(entries, *sort, *invert);
+                sorted.into_iter().take(*n).collect()
             }
+        };
 
-            if let Ok(metadata) = fs::metadata(&path) {
-                current_size -= metadata.len();
-                let _ = fs::remove_file(&path);
+        let mut count = 0u32;
+        let mut freed = 0u64;
+        for entry in selected {
+            if fs::remove_file(&entry.path).is_ok() {
+                count += 1;
+                freed += entry.size_bytes;
+                self.memory_cache.lock().unwrap().retain(|k, _| {
+                    !(k.repo_url == entry.repo_url
+                        && k.commit_sha == entry.commit_sha
+                        && k.task_id == entry.task_id
+                        && k.variant == entry.variant)
+                });
             }
         }
 
-        Ok(())
+        Ok((count, freed))
     }
 
     fn calculate_cache_size(&self) -> Result<u64> {
@@ -316,13 +774,17 @@ impl CacheManager {
     }
 }
 
-/// Simple string hash for cache filenames
+/// Hash a string into a filesystem-safe hex digest for cache filenames and
+/// config digests. Uses the same keyed SipHash (`DefaultHasher`) the
+/// repo's [`crate::parser::cache::ParseCache`] relies on for content
+/// hashing — collision-resistant enough that two different URLs or
+/// configs are never expected to share a digest, unlike the old
+/// multiplier-based rolling hash this replaced.
 fn simple_hash(s: &str) -> String {
-    let mut hash = 0u64;
-    for c in s.bytes() {
-        hash = hash.wrapping_mul(31).wrapping_add(c as u64);
-    }
-    format!("{:016x}", hash)
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    s.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
 }
 
 #[cfg(test)]
@@ -339,6 +801,9 @@ mod tests {
             tools_by_name: HashMap::new(),
             files_accessed: vec![],
             read_calls: 3,
+            failed_tool_calls: 0,
+            redundant_reads: 0,
+            tool_failures_by_name: HashMap::new(),
             input_tokens: 1000,
             output_tokens: 500,
             cache_read_tokens: 0,
@@ -354,7 +819,7 @@ mod tests {
     #[test]
     fn test_cache_set_and_get() {
         let temp = tempdir().unwrap();
-        let mut cache = CacheManager::new(Some(temp.path().to_path_buf())).unwrap();
+        let cache = CacheManager::new(Some(temp.path().to_path_buf())).unwrap();
 
         let key = CacheKey::new("https://github.com/test/repo", "abc123", "task1", "control");
         let result = create_test_result("task1", "control");
@@ -366,6 +831,102 @@ mod tests {
         assert_eq!(retrieved.tool_calls, result.tool_calls);
     }
 
+    #[test]
+    fn config_digest_ignores_allowed_tools_order_but_not_model_or_context() {
+        let tools_a = vec!["Read".to_string(), "Grep".to_string()];
+        let tools_b = vec!["Grep".to_string(), "Read".to_string()];
+
+        assert_eq!(
+            config_digest("sonnet", &tools_a, false, Some("ctx"), 10, 1.0),
+            config_digest("sonnet", &tools_b, false, Some("ctx"), 10, 1.0),
+            "tool order shouldn't affect the digest"
+        );
+
+        assert_ne!(
+            config_digest("sonnet", &tools_a, false, Some("ctx"), 10, 1.0),
+            config_digest("opus", &tools_a, false, Some("ctx"), 10, 1.0),
+            "changing the model should change the digest"
+        );
+
+        assert_ne!(
+            config_digest("sonnet", &tools_a, false, Some("ctx"), 10, 1.0),
+            config_digest("sonnet", &tools_a, false, Some("other ctx"), 10, 1.0),
+            "changing the FMM context should change the digest"
+        );
+    }
+
+    #[test]
+    fn list_entries_reports_size_and_metadata() {
+        let temp = tempdir().unwrap();
+        let cache = CacheManager::new(Some(temp.path().to_path_buf())).unwrap();
+
+        cache
+            .set(
+                CacheKey::new("https://github.com/test/repo", "abc123", "task1", "control"),
+                create_test_result("task1", "control"),
+            )
+            .unwrap();
+        cache
+            .set(
+                CacheKey::new("https://github.com/test/repo", "abc123", "task2", "fmm"),
+                create_test_result("task2", "fmm"),
+            )
+            .unwrap();
+
+        let entries = cache.list_entries().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(entries.iter().all(|e| e.size_bytes > 0));
+        assert!(entries.iter().any(|e| e.task_id == "task1"));
+        assert!(entries.iter().any(|e| e.task_id == "task2"));
+    }
+
+    #[test]
+    fn delete_entries_group_respects_sort_and_count() {
+        let temp = tempdir().unwrap();
+        let cache = CacheManager::new(Some(temp.path().to_path_buf())).unwrap();
+
+        for i in 0..3 {
+            let key = CacheKey::new(
+                "https://github.com/test/repo",
+                "abc123",
+                &format!("task{i}"),
+                "control",
+            );
+            cache
+                .set(key, create_test_result(&format!("task{i}"), "control"))
+                .unwrap();
+            // Ensure distinct `cached_at` ordering between entries.
+            std::thread::sleep(Duration::from_millis(5));
+        }
+
+        let (count, freed) = cache
+            .delete_entries(&CacheDeleteScope::Group {
+                sort: CacheSort::Oldest,
+                invert: false,
+                n: 1,
+            })
+            .unwrap();
+        assert_eq!(count, 1);
+        assert!(freed > 0);
+        assert_eq!(cache.list_entries().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn delete_entries_all_clears_everything() {
+        let temp = tempdir().unwrap();
+        let cache = CacheManager::new(Some(temp.path().to_path_buf())).unwrap();
+        cache
+            .set(
+                CacheKey::new("https://github.com/test/repo", "abc123", "task1", "control"),
+                create_test_result("task1", "control"),
+            )
+            .unwrap();
+
+        let (count, _) = cache.delete_entries(&CacheDeleteScope::All).unwrap();
+        assert_eq!(count, 1);
+        assert!(cache.list_entries().unwrap().is_empty());
+    }
+
     #[test]
     fn test_cache_key_filename() {
         let key = CacheKey::new("https://github.com/test/repo", "abc123", "task1", "fmm");
@@ -374,4 +935,115 @@ mod tests {
         assert!(filename.contains("task1"));
         assert!(filename.contains("fmm"));
     }
+
+    #[test]
+    fn get_rejects_an_entry_whose_embedded_key_does_not_match() {
+        let temp = tempdir().unwrap();
+        let cache = CacheManager::new(Some(temp.path().to_path_buf())).unwrap();
+
+        let requested_key =
+            CacheKey::new("https://github.com/test/repo", "abc123", "task1", "control");
+        let other_key = CacheKey::new("https://github.com/other/repo", "def456", "task9", "fmm");
+
+        // Simulate a filename collision: write an entry under the
+        // requested key's filename, but with a different embedded key.
+        let mismatched = CachedResult {
+            key: other_key,
+            result: create_test_result("task9", "fmm"),
+            cached_at: chrono::Utc::now().to_rfc3339(),
+            expires_at: (chrono::Utc::now() + chrono::Duration::hours(1)).to_rfc3339(),
+        };
+        let cache_path = temp
+            .path()
+            .join(format!("{}.json", requested_key.to_filename()));
+        fs::write(&cache_path, serde_json::to_string(&mismatched).unwrap()).unwrap();
+
+        assert!(cache.get(&requested_key).is_none());
+        assert!(
+            !cache_path.exists(),
+            "the colliding file should be removed once detected"
+        );
+    }
+
+    #[test]
+    fn get_or_run_is_a_miss_then_a_hit() {
+        let temp = tempdir().unwrap();
+        let cache = CacheManager::new(Some(temp.path().to_path_buf())).unwrap();
+        let key = CacheKey::new("https://github.com/test/repo", "abc123", "task1", "control");
+
+        let (result, outcome) = cache
+            .get_or_run(&key, || Ok(create_test_result("task1", "control")))
+            .unwrap();
+        assert_eq!(outcome, CacheOutcome::Miss);
+        assert_eq!(result.task_id, "task1");
+
+        let (result, outcome) = cache
+            .get_or_run(&key, || panic!("should not recompute on a fresh hit"))
+            .unwrap();
+        assert_eq!(outcome, CacheOutcome::Hit);
+        assert_eq!(result.task_id, "task1");
+    }
+
+    #[test]
+    fn get_or_run_force_refresh_bypasses_cache() {
+        let temp = tempdir().unwrap();
+        let cache = CacheManager::new(Some(temp.path().to_path_buf()))
+            .unwrap()
+            .with_force_refresh(true);
+        let key = CacheKey::new("https://github.com/test/repo", "abc123", "task1", "control");
+
+        cache
+            .get_or_run(&key, || Ok(create_test_result("task1", "control")))
+            .unwrap();
+
+        let recomputed = std::sync::atomic::AtomicBool::new(false);
+        let (_, outcome) = cache
+            .get_or_run(&key, move || {
+                recomputed.store(true, std::sync::atomic::Ordering::SeqCst);
+                Ok(create_test_result("task1", "control"))
+            })
+            .unwrap();
+        assert_eq!(outcome, CacheOutcome::Miss);
+    }
+
+    #[test]
+    fn get_or_run_serves_stale_entry_while_revalidating() {
+        let temp = tempdir().unwrap();
+        let cache = CacheManager::new(Some(temp.path().to_path_buf()))
+            .unwrap()
+            .with_ttl(Duration::from_millis(0))
+            .with_stale_ttl(Duration::from_secs(60));
+        let key = CacheKey::new("https://github.com/test/repo", "abc123", "task1", "control");
+
+        cache
+            .get_or_run(&key, || Ok(create_test_result("task1", "control")))
+            .unwrap();
+        std::thread::sleep(Duration::from_millis(10));
+
+        let (result, outcome) = cache
+            .get_or_run(&key, || Ok(create_test_result("task1", "control")))
+            .unwrap();
+        assert_eq!(outcome, CacheOutcome::Stale);
+        assert_eq!(result.task_id, "task1");
+    }
+
+    #[test]
+    fn cache_lock_blocks_a_second_acquire_until_released() {
+        let temp = tempdir().unwrap();
+        let guard = CacheLock::acquire(temp.path(), "task1");
+        assert!(guard.path.is_some());
+
+        let cache_dir = temp.path().to_path_buf();
+        let handle = std::thread::spawn(move || {
+            let start = std::time::Instant::now();
+            let _second = CacheLock::acquire(&cache_dir, "task1");
+            start.elapsed()
+        });
+
+        std::thread::sleep(Duration::from_millis(100));
+        drop(guard);
+
+        let waited = handle.join().unwrap();
+        assert!(waited >= Duration::from_millis(50));
+    }
 }
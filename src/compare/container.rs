@@ -0,0 +1,290 @@
+//! Optional container-based isolation for `Sandbox` runs.
+//!
+//! `Sandbox` confines a clone to a temp directory and validates the job
+//! id/URL that name it, but the clone itself and the `fmm generate` step
+//! that follows still run directly on the host, via `std::env::current_exe`.
+//! For comparisons against a repo the caller doesn't trust, [`ContainerSandbox`]
+//! runs both steps inside a throwaway Docker/Podman container instead, with
+//! only the sandbox root bind-mounted, [`ResourceLimits::max_memory_mb`]
+//! passed as `--memory`, fixed `--pids-limit`, `--network`, and
+//! `--stop-timeout` flags, and the clone plus parse timeouts as the
+//! wall-clock limit. It's
+//! opt-in: callers who just want `Sandbox::with_limits`'s host-side isolation
+//! are unaffected.
+//!
+//! The container is named after the job id so [`Drop`] can always find and
+//! remove it, even if the run itself failed or was killed partway through —
+//! the same "clean up no matter how we got here" intent as `Sandbox::cleanup`.
+
+use anyhow::{Context, Result};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use super::isolation::has_binary;
+use super::sandbox::{validate_repo_url, ResourceLimits, Sandbox};
+
+/// Validate a branch name is safe to pass to the container as an
+/// environment variable: alphanumerics plus `-`, `_`, `.`, `/`, since
+/// branch names routinely look like `release/1.2` or `feature/foo`.
+fn validate_branch(branch: &str) -> Result<()> {
+    if branch.is_empty() {
+        anyhow::bail!("Branch name must not be empty");
+    }
+    if !branch
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '/'))
+    {
+        anyhow::bail!(
+            "Invalid branch name '{}': only alphanumeric, '-', '_', '.', and '/' allowed",
+            branch
+        );
+    }
+    Ok(())
+}
+
+/// Image used to run the clone + `fmm generate` steps. Needs `git` and an
+/// `fmm` binary on `PATH`; overridable for installs that build their own.
+const DEFAULT_IMAGE: &str = "fmm-compare-runner:latest";
+
+/// Fixed script run inside the container by [`ContainerSandbox::clone_and_generate`].
+/// `GIT_URL`/`GIT_BRANCH` are read from the environment (set via `-e` on the
+/// `docker`/`podman run` invocation) rather than interpolated here, so the
+/// script text itself never contains untrusted input.
+const CLONE_AND_GENERATE_SCRIPT: &str = "set -e\n\
+     depth_args=\"\"\n\
+     if [ -n \"$GIT_DEPTH\" ]; then\n\
+       depth_args=\"--depth $GIT_DEPTH --single-branch\"\n\
+     fi\n\
+     if [ -n \"$GIT_BRANCH\" ]; then\n\
+       git clone $depth_args --branch \"$GIT_BRANCH\" \"$GIT_URL\" control\n\
+     else\n\
+       git clone $depth_args \"$GIT_URL\" control\n\
+     fi\n\
+     cp -a control fmm\n\
+     (cd fmm && fmm generate --manifest-only) || echo 'fmm generate had issues' >&2\n";
+
+/// Runs `Sandbox::clone_repo` + `Sandbox::generate_fmm_manifest`'s work
+/// inside a throwaway container instead of on the host. Reuses `Sandbox`
+/// for directory layout and host-side cleanup; the container only ever
+/// sees `sandbox.root` bind-mounted in, nothing else.
+pub struct ContainerSandbox {
+    sandbox: Sandbox,
+    runtime: &'static str,
+    image: String,
+    container_name: String,
+    limits: ResourceLimits,
+}
+
+impl ContainerSandbox {
+    /// Create a container-isolated sandbox for `job_id`, bound by `limits`.
+    /// Fails immediately if neither `docker` nor `podman` is on `PATH`,
+    /// rather than silently falling back to running on the host.
+    pub fn new(job_id: &str, limits: ResourceLimits) -> Result<Self> {
+        let runtime = detect_runtime()?;
+        let sandbox = Sandbox::with_limits(job_id, limits.clone())?;
+        Ok(Self {
+            sandbox,
+            runtime,
+            image: std::env::var("FMM_COMPARE_IMAGE").unwrap_or_else(|_| DEFAULT_IMAGE.to_string()),
+            container_name: format!("fmm-compare-{job_id}"),
+            limits,
+        })
+    }
+
+    /// Clone `url` for both variants and generate the FMM manifest, all in
+    /// one container run — the untrusted repo's git fetch and parse never
+    /// touch the host's filesystem or network stack directly.
+    ///
+    /// `url` and `branch` are passed into the container as `GIT_URL`/
+    /// `GIT_BRANCH` environment variables (each its own `docker`/`podman`
+    /// argv element) rather than interpolated into the `sh -c` script
+    /// text, so a crafted URL or branch (backticks, `$()`, `;`, ...) can't
+    /// break out of the quoted shell variable it's read from.
+    ///
+    /// `depth` mirrors `Sandbox::clone_repo`'s: `Some(n)` for a shallow
+    /// `--depth n --single-branch` clone, `None` for full history.
+    pub fn clone_and_generate(&self, url: &str, branch: Option<&str>, depth: Option<u32>) -> Result<()> {
+        validate_repo_url(url)?;
+        if let Some(b) = branch {
+            validate_branch(b)?;
+        }
+
+        let depth_str = depth.map(|n| n.to_string());
+        let mut env = vec![("GIT_URL", url)];
+        if let Some(b) = branch {
+            env.push(("GIT_BRANCH", b));
+        }
+        if let Some(d) = depth_str.as_deref() {
+            env.push(("GIT_DEPTH", d));
+        }
+
+        let timeout = Duration::from_secs(self.limits.clone_timeout_secs + self.limits.parse_timeout_secs);
+        self.run(CLONE_AND_GENERATE_SCRIPT, &env, timeout)
+            .with_context(|| format!("running containerized clone+generate for {url}"))
+    }
+
+    /// Build and run the `docker`/`podman run` invocation, streaming the
+    /// container's stdout/stderr straight through to ours (so a long clone
+    /// or generate isn't silent) and stopping the container if it's still
+    /// running after `timeout`. `env` is passed via `-e KEY=VALUE` argv
+    /// elements rather than folded into `script`, so values never need
+    /// shell-escaping.
+    fn run(&self, script: &str, env: &[(&str, &str)], timeout: Duration) -> Result<()> {
+        let memory_mb = self.limits.max_memory_mb.unwrap_or(2048);
+
+        let mut cmd = Command::new(self.runtime);
+        cmd.arg("run")
+            .arg("--rm")
+            .arg("--name")
+            .arg(&self.container_name)
+            .arg("--network")
+            .arg("bridge") // the clone step needs it; nothing else runs after
+            .arg("--memory")
+            .arg(format!("{memory_mb}m"))
+            .arg("--pids-limit")
+            .arg("512")
+            .arg("--stop-timeout")
+            .arg("10")
+            .arg("-v")
+            .arg(format!("{}:/workspace", self.sandbox.root.display()))
+            .arg("-w")
+            .arg("/workspace");
+        for (key, value) in env {
+            cmd.arg("-e").arg(format!("{key}={value}"));
+        }
+        cmd.arg(&self.image)
+            .arg("sh")
+            .arg("-c")
+            .arg(script)
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit());
+
+        let start = Instant::now();
+        let mut child = cmd
+            .spawn()
+            .with_context(|| format!("failed to start {} container", self.runtime))?;
+
+        loop {
+            if let Some(status) = child.try_wait().context("polling container run")? {
+                if !status.success() {
+                    anyhow::bail!("container run exited with {status}");
+                }
+                return Ok(());
+            }
+
+            if start.elapsed() > timeout {
+                self.stop_container();
+                let _ = child.kill();
+                let _ = child.wait();
+                anyhow::bail!(
+                    "container run timed out after {}s",
+                    timeout.as_secs()
+                );
+            }
+
+            std::thread::sleep(Duration::from_millis(200));
+        }
+    }
+
+    fn stop_container(&self) {
+        let _ = Command::new(self.runtime)
+            .arg("stop")
+            .arg("--time")
+            .arg("10")
+            .arg(&self.container_name)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+    }
+
+    /// Delegate to the underlying `Sandbox` for host-side directory cleanup.
+    pub fn cleanup(&self) {
+        self.sandbox.cleanup();
+    }
+
+    /// The underlying host-side `Sandbox` — its `root`/`control_dir`/
+    /// `fmm_dir` are populated by [`Self::clone_and_generate`] via the
+    /// bind-mounted `root`, so callers can use it exactly like a plain
+    /// `Sandbox` once that call returns.
+    pub(super) fn sandbox(&self) -> &Sandbox {
+        &self.sandbox
+    }
+}
+
+impl Drop for ContainerSandbox {
+    fn drop(&mut self) {
+        // `--rm` already asks the runtime to remove the container on exit,
+        // but that's a no-op if we killed our side without it finishing
+        // cleanly — `stop` plus an explicit `rm` covers that gap.
+        self.stop_container();
+        let _ = Command::new(self.runtime)
+            .arg("rm")
+            .arg("-f")
+            .arg(&self.container_name)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+    }
+}
+
+/// Picks `docker` over `podman` when both are present, purely because
+/// that's the more common default; either works identically here.
+fn detect_runtime() -> Result<&'static str> {
+    if has_binary("docker") {
+        Ok("docker")
+    } else if has_binary("podman") {
+        Ok("podman")
+    } else {
+        anyhow::bail!("ContainerSandbox requires docker or podman on PATH")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_branch_accepts_typical_names() {
+        assert!(validate_branch("main").is_ok());
+        assert!(validate_branch("release/1.2").is_ok());
+        assert!(validate_branch("feature/foo-bar_baz").is_ok());
+    }
+
+    #[test]
+    fn validate_branch_rejects_empty() {
+        assert!(validate_branch("").is_err());
+    }
+
+    #[test]
+    fn validate_branch_rejects_shell_metacharacters() {
+        assert!(validate_branch("$(touch pwned)").is_err());
+        assert!(validate_branch("`touch pwned`").is_err());
+        assert!(validate_branch("main; rm -rf /").is_err());
+        assert!(validate_branch("main && rm -rf /").is_err());
+        assert!(validate_branch("main|cat /etc/passwd").is_err());
+        assert!(validate_branch("main\0").is_err());
+    }
+
+    /// [`CLONE_AND_GENERATE_SCRIPT`] is a fixed literal — `url`/`branch`
+    /// never touch it. This pins that down so a future edit can't
+    /// reintroduce interpolation by accident.
+    #[test]
+    fn clone_and_generate_script_has_no_interpolation_points() {
+        assert!(!CLONE_AND_GENERATE_SCRIPT.contains("{url}"));
+        assert!(!CLONE_AND_GENERATE_SCRIPT.contains("{branch}"));
+        assert!(!CLONE_AND_GENERATE_SCRIPT.contains("{depth}"));
+        assert!(CLONE_AND_GENERATE_SCRIPT.contains("\"$GIT_URL\""));
+        assert!(CLONE_AND_GENERATE_SCRIPT.contains("\"$GIT_BRANCH\""));
+        assert!(CLONE_AND_GENERATE_SCRIPT.contains("$GIT_DEPTH"));
+    }
+
+    #[test]
+    fn clone_and_generate_rejects_command_injection_in_url() {
+        // Can't exercise the full `clone_and_generate` without docker/podman
+        // on `PATH`, but `validate_repo_url` is the first thing it does —
+        // a crafted URL never reaches the point where it would be passed
+        // into the container.
+        assert!(validate_repo_url("https://x.com/$(touch pwned)").is_err());
+        assert!(validate_repo_url("https://x.com/`touch pwned`").is_err());
+    }
+}
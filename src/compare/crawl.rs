@@ -0,0 +1,112 @@
+//! Gitignore-aware repo crawl for the `"rag"` baseline variant: a naive
+//! retrieval context built by concatenating source files, as a middle
+//! ground between the no-context `"control"` variant and an FMM manifest.
+//! Mirrors the crawl-then-retrieve technique lsp-ai uses for its RAG
+//! support.
+
+use anyhow::{Context, Result};
+use ignore::WalkBuilder;
+use std::path::Path;
+
+use crate::context_budget;
+
+/// Token budget for a crawled RAG context, matched to
+/// `CompareOptions::context_token_budget`'s default so the three variants
+/// spend comparably on injected context.
+pub const MAX_CONTEXT_SIZE: usize = 8000;
+
+/// Walk `working_dir` with `ignore`'s `WalkBuilder` (respecting
+/// `.gitignore` and skipping hidden files, same as every other sidecar
+/// walk in this crate), concatenating files whose extension is in
+/// `extensions` until the render would exceed `MAX_CONTEXT_SIZE` tokens.
+/// Files are visited in a deterministic (sorted) order so the same repo
+/// always produces the same context, and each file is walled off with a
+/// path header so the model can tell where one file ends and the next
+/// begins.
+pub fn build_rag_context(working_dir: &Path, extensions: &[&str]) -> Result<String> {
+    let mut paths: Vec<_> = WalkBuilder::new(working_dir)
+        .standard_filters(true)
+        .build()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_some_and(|ft| ft.is_file()))
+        .filter(|entry| {
+            entry
+                .path()
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| extensions.contains(&ext))
+        })
+        .map(|entry| entry.into_path())
+        .collect();
+    paths.sort();
+
+    let preamble = "IMPORTANT: The following is a naive retrieval of source files from this repository (no structural analysis, just concatenated file contents). Use it to locate relevant code before reading further.\n\n";
+    let mut out = preamble.to_string();
+    let mut tokens = context_budget::estimate_tokens(&out);
+
+    for path in paths {
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let relative = path.strip_prefix(working_dir).unwrap_or(&path);
+        let chunk = format!("### {}\n```\n{}\n```\n\n", relative.display(), content);
+        let chunk_tokens = context_budget::estimate_tokens(&chunk);
+
+        if tokens + chunk_tokens > MAX_CONTEXT_SIZE {
+            break;
+        }
+
+        out.push_str(&chunk);
+        tokens += chunk_tokens;
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn crawl_concatenates_matching_files_in_sorted_order() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("b.rs"), "fn b() {}").unwrap();
+        fs::write(dir.path().join("a.rs"), "fn a() {}").unwrap();
+        fs::write(dir.path().join("ignored.txt"), "not rust").unwrap();
+
+        let context = build_rag_context(dir.path(), &["rs"]).unwrap();
+
+        let a_pos = context.find("a.rs").unwrap();
+        let b_pos = context.find("b.rs").unwrap();
+        assert!(a_pos < b_pos);
+        assert!(!context.contains("ignored.txt"));
+    }
+
+    #[test]
+    fn crawl_respects_gitignore() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join(".gitignore"), "skip.rs\n").unwrap();
+        fs::write(dir.path().join("skip.rs"), "fn skip() {}").unwrap();
+        fs::write(dir.path().join("keep.rs"), "fn keep() {}").unwrap();
+
+        let context = build_rag_context(dir.path(), &["rs"]).unwrap();
+
+        assert!(context.contains("keep.rs"));
+        assert!(!context.contains("skip.rs"));
+    }
+
+    #[test]
+    fn crawl_stops_once_budget_is_exceeded() {
+        let dir = tempfile::tempdir().unwrap();
+        // Roughly 4 chars/token, so each file is about half the budget:
+        // the first fits, the second would push the render past it.
+        let big = "x".repeat(MAX_CONTEXT_SIZE * 2);
+        fs::write(dir.path().join("a.rs"), &big).unwrap();
+        fs::write(dir.path().join("b.rs"), &big).unwrap();
+
+        let context = build_rag_context(dir.path(), &["rs"]).unwrap();
+
+        assert!(context.contains("a.rs"));
+        assert!(!context.contains("b.rs"));
+    }
+}
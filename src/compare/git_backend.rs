@@ -0,0 +1,304 @@
+//! Abstraction over how [`Sandbox`](super::sandbox::Sandbox) talks to git.
+//!
+//! Mirrors `crate::gh::git_backend`: [`ShellGitBackend`] shells out to the
+//! `git` binary and scrapes stderr on failure; [`GixBackend`] does the same
+//! work through `gix` (gitoxide) directly against the repository's
+//! object/ref store, so a clone or `rev-parse HEAD` no longer depends on a
+//! `git` binary being on `PATH` and failures come back as typed errors
+//! instead of parsed text. [`backend_for`] selects between them from
+//! [`GlobalConfig`].
+//!
+//! `GixBackend` also implements [`GitBackend::duplicate`] by copying an
+//! already-cloned working tree and re-checking it out locally, rather than
+//! cloning the remote a second time — `Sandbox::clone_repo` uses this to
+//! produce its FMM variant from the control variant's clone.
+
+use anyhow::{Context, Result};
+use std::io::Read;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use crate::config::GlobalConfig;
+
+/// Everything `Sandbox` needs from git, independent of how it's performed.
+pub trait GitBackend {
+    /// Clone `url` (optionally at `branch`) into `dir`, aborting with an
+    /// error if it's still running after `timeout` (`ResourceLimits::clone_timeout_secs`).
+    /// `depth` of `Some(n)` requests a shallow clone of the last `n` commits
+    /// on a single branch (see [`ShellGitBackend::clone`]'s fallback to a
+    /// full clone if the remote rejects that); `None` always clones full
+    /// history.
+    fn clone(
+        &self,
+        url: &str,
+        branch: Option<&str>,
+        dir: &Path,
+        timeout: Duration,
+        depth: Option<u32>,
+    ) -> Result<()>;
+
+    /// `git rev-parse HEAD`'s equivalent for `dir`.
+    fn current_sha(&self, dir: &Path) -> Result<String>;
+
+    /// Produce a second working tree at `dst`, equivalent to cloning `url`
+    /// into it fresh, but allowed to reuse `src`'s already-fetched objects
+    /// instead of hitting the network again. The default implementation
+    /// (used by [`ShellGitBackend`]) just clones again, bound by the same
+    /// `timeout`.
+    fn duplicate(
+        &self,
+        src: &Path,
+        url: &str,
+        branch: Option<&str>,
+        dst: &Path,
+        timeout: Duration,
+        depth: Option<u32>,
+    ) -> Result<()> {
+        let _ = src;
+        self.clone(url, branch, dst, timeout, depth)
+    }
+}
+
+/// Picks the backend `Sandbox` uses for clone/SHA operations. Shell is the
+/// default until the gitoxide path has seen enough use to trust as the
+/// default; `GlobalConfig` lets it be opted into per-install (the same
+/// setting `gh::git_backend::backend_for` reads).
+pub fn backend_for(global_config: &GlobalConfig) -> Box<dyn GitBackend> {
+    if global_config.git_backend.eq_ignore_ascii_case("gitoxide") {
+        Box::new(GixBackend)
+    } else {
+        Box::new(ShellGitBackend)
+    }
+}
+
+/// Build a `git clone` command for `url` into `dir`: `--branch <b>` when
+/// `branch` is given, plus `--depth <n> --single-branch` when `depth` is
+/// `Some`.
+fn clone_command(url: &str, branch: Option<&str>, dir: &Path, depth: Option<u32>) -> Command {
+    let mut cmd = Command::new("git");
+    cmd.arg("clone");
+
+    if let Some(n) = depth {
+        cmd.arg("--depth").arg(n.to_string()).arg("--single-branch");
+    }
+    if let Some(b) = branch {
+        cmd.arg("--branch").arg(b);
+    }
+
+    cmd.arg(url).arg(dir);
+    cmd
+}
+
+/// Shells out to the `git` CLI. Requires `git` on `PATH`.
+pub struct ShellGitBackend;
+
+impl GitBackend for ShellGitBackend {
+    fn clone(
+        &self,
+        url: &str,
+        branch: Option<&str>,
+        dir: &Path,
+        timeout: Duration,
+        depth: Option<u32>,
+    ) -> Result<()> {
+        let output = run_with_timeout(clone_command(url, branch, dir, depth), timeout, "git clone")?;
+        if output.status.success() {
+            return Ok(());
+        }
+
+        // Some hosts (bare/dumb HTTP servers, certain self-hosted setups)
+        // reject a shallow fetch outright — retry once as a full clone
+        // rather than failing a benchmark run over a server quirk.
+        if depth.is_some() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            log::warn!(
+                "shallow clone of {url} failed ({}), retrying as a full clone",
+                stderr.trim()
+            );
+            let _ = std::fs::remove_dir_all(dir);
+            let output = run_with_timeout(clone_command(url, branch, dir, None), timeout, "git clone")?;
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                anyhow::bail!("Git clone failed: {}", stderr);
+            }
+            return Ok(());
+        }
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("Git clone failed: {}", stderr);
+    }
+
+    fn current_sha(&self, dir: &Path) -> Result<String> {
+        let output = Command::new("git")
+            .arg("rev-parse")
+            .arg("HEAD")
+            .current_dir(dir)
+            .output()
+            .context("Failed to get commit SHA")?;
+
+        if !output.status.success() {
+            anyhow::bail!("Git rev-parse failed");
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+}
+
+/// Talks to git directly through `gix`, without spawning a `git` binary.
+pub struct GixBackend;
+
+impl GitBackend for GixBackend {
+    fn clone(
+        &self,
+        url: &str,
+        branch: Option<&str>,
+        dir: &Path,
+        timeout: Duration,
+        depth: Option<u32>,
+    ) -> Result<()> {
+        // `depth` is accepted for interface parity with `ShellGitBackend`
+        // but not honored — gix's shallow-fetch plumbing isn't wired up
+        // here yet, so this always fetches full history. Slower on large
+        // repos, but never wrong.
+        let _ = depth;
+        // `gix`'s fetch runs in-process, so there's no child to kill on
+        // timeout the way `ShellGitBackend` does — run it on its own thread
+        // and stop *waiting* on it instead. The clone may keep running in
+        // the background after we bail; that's a known gap of cancelling
+        // in-process work, same honesty tradeoff as `GixBackend`'s
+        // unsupported-worktree errors in `gh::git_backend`.
+        let (tx, rx) = std::sync::mpsc::channel();
+        let url_owned = url.to_string();
+        let branch_owned = branch.map(|b| b.to_string());
+        let dir_owned = dir.to_path_buf();
+        std::thread::spawn(move || {
+            let result = clone_with_gix(&url_owned, branch_owned.as_deref(), &dir_owned);
+            let _ = tx.send(result);
+        });
+
+        match rx.recv_timeout(timeout) {
+            Ok(result) => result,
+            Err(_) => anyhow::bail!(
+                "gitoxide clone of {url} exceeded the {}s clone timeout",
+                timeout.as_secs()
+            ),
+        }
+    }
+
+    fn current_sha(&self, dir: &Path) -> Result<String> {
+        let repo = gix::open(dir).with_context(|| format!("opening repo at {}", dir.display()))?;
+        let head_id = repo.head_id().context("resolving HEAD")?;
+        Ok(head_id.to_string())
+    }
+
+    fn duplicate(
+        &self,
+        src: &Path,
+        _url: &str,
+        _branch: Option<&str>,
+        dst: &Path,
+        _timeout: Duration,
+        _depth: Option<u32>,
+    ) -> Result<()> {
+        // Purely local filesystem work, not bound by `clone_timeout_secs`.
+        copy_dir_all(src, dst)
+            .with_context(|| format!("copying {} to {}", src.display(), dst.display()))?;
+
+        // `copy_dir_all` duplicated `src`'s `.git` too, so `dst` already has
+        // every object `src` fetched — re-checking it out from the copied
+        // object database (rather than trusting the copied working-tree
+        // files byte-for-byte) is what actually saves the network round
+        // trip a second `clone` would cost.
+        let repo =
+            gix::open(dst).with_context(|| format!("opening duplicated repo at {}", dst.display()))?;
+        let tree = repo
+            .head_commit()
+            .context("duplicated repo has no HEAD commit")?
+            .tree()
+            .context("resolving HEAD tree")?;
+        gix::worktree::state::checkout(
+            &tree,
+            dst,
+            repo.objects.clone(),
+            &gix::progress::Discard,
+            &gix::progress::Discard,
+            &gix::interrupt::IS_INTERRUPTED,
+            gix::worktree::state::checkout::Options::default(),
+        )
+        .context("checking out duplicated working tree")?;
+        Ok(())
+    }
+}
+
+/// The actual `gix` clone, run on a background thread by [`GixBackend::clone`]
+/// so the caller can bound how long it waits without needing a child
+/// process to kill.
+fn clone_with_gix(url: &str, branch: Option<&str>, dir: &Path) -> Result<()> {
+    let mut prepare = gix::prepare_clone(url, dir)
+        .with_context(|| format!("preparing clone of {url} into {}", dir.display()))?;
+    if let Some(b) = branch {
+        prepare = prepare
+            .with_ref_name(Some(b))
+            .with_context(|| format!("invalid branch name {b}"))?;
+    }
+    prepare
+        .fetch_then_checkout(gix::progress::Discard, &gix::interrupt::IS_INTERRUPTED)
+        .with_context(|| format!("cloning {url} into {}", dir.display()))?;
+    Ok(())
+}
+
+/// Runs `cmd` to completion, killing it and returning an error if it hasn't
+/// exited within `timeout`. `Child` has no wait-with-a-deadline of its own,
+/// so this polls `try_wait` instead of blocking on `wait`.
+pub(super) fn run_with_timeout(
+    mut cmd: Command,
+    timeout: Duration,
+    what: &str,
+) -> Result<std::process::Output> {
+    let start = Instant::now();
+    let mut child = cmd
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("failed to start {what}"))?;
+
+    loop {
+        if let Some(status) = child
+            .try_wait()
+            .with_context(|| format!("polling {what}"))?
+        {
+            let mut stdout = Vec::new();
+            let mut stderr = Vec::new();
+            if let Some(mut out) = child.stdout.take() {
+                let _ = out.read_to_end(&mut stdout);
+            }
+            if let Some(mut err) = child.stderr.take() {
+                let _ = err.read_to_end(&mut stderr);
+            }
+            return Ok(std::process::Output { status, stdout, stderr });
+        }
+
+        if start.elapsed() > timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            anyhow::bail!("{what} timed out after {}s", timeout.as_secs());
+        }
+
+        std::thread::sleep(Duration::from_millis(100));
+    }
+}
+
+/// Recursively copies `src` onto `dst`, creating `dst` if needed.
+fn copy_dir_all(src: &Path, dst: &Path) -> Result<()> {
+    std::fs::create_dir_all(dst)?;
+    for entry in std::fs::read_dir(src)? {
+        let entry = entry?;
+        let dst_path = dst.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir_all(&entry.path(), &dst_path)?;
+        } else {
+            std::fs::copy(entry.path(), &dst_path)?;
+        }
+    }
+    Ok(())
+}
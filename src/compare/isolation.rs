@@ -0,0 +1,135 @@
+//! Linux namespace isolation for benchmark agent runs.
+//!
+//! `fmm compare` clones an arbitrary (untrusted) repository and then lets an
+//! LLM agent read and write inside it with real tool access. Wrapping the
+//! `claude` child process in `bwrap` (bubblewrap) confines it to a new
+//! mount/PID/network namespace with the rest of the filesystem read-only
+//! and only the task's working directory bind-mounted read/write; wrapping
+//! in `timeout` enforces `ResourceLimits::task_timeout_secs` even if the
+//! agent (or one of its tools) hangs; an optional `systemd-run` scope adds a
+//! cgroup memory cap. Each wrapper degrades to a no-op with a one-time
+//! warning when its binary isn't on `PATH` or the host isn't Linux, since
+//! `fmm compare` still has to run in dev environments and CI images that
+//! don't have any of this installed.
+
+use std::path::Path;
+use std::process::Command;
+use std::sync::Once;
+
+use super::sandbox::ResourceLimits;
+
+/// Rewrite `cmd` in place to run confined, in order: an optional
+/// `systemd-run --user --scope` memory cap, wrapping an optional `timeout
+/// --signal=KILL` wall-clock cap, wrapping an optional `bwrap` namespace
+/// sandbox, wrapping the original program and args.
+///
+/// Must be called before any `Stdio`/`current_dir` configuration on `cmd` —
+/// those aren't readable back off a `Command` to carry over to the rebuilt
+/// one, so the caller should apply them to the command this function
+/// returns control of, not before.
+pub fn isolate(cmd: &mut Command, working_dir: &Path, limits: &ResourceLimits) {
+    if !cfg!(target_os = "linux") {
+        return;
+    }
+
+    let program = cmd.get_program().to_owned();
+    let args: Vec<_> = cmd.get_args().map(|a| a.to_owned()).collect();
+
+    let mut wrapped = if has_binary("bwrap") {
+        let mut bwrap = Command::new("bwrap");
+        bwrap
+            .arg("--ro-bind")
+            .arg("/")
+            .arg("/")
+            .arg("--bind")
+            .arg(working_dir)
+            .arg(working_dir)
+            .arg("--dev")
+            .arg("/dev")
+            .arg("--proc")
+            .arg("/proc")
+            .arg("--unshare-pid")
+            .arg("--unshare-net")
+            .arg("--die-with-parent")
+            .arg("--new-session")
+            .arg(&program)
+            .args(&args);
+        bwrap
+    } else {
+        warn_once(&BWRAP_WARNING, "bwrap not found on PATH — agent runs are not namespace-isolated, only wall-clock/memory limited");
+        let mut bare = Command::new(&program);
+        bare.args(&args);
+        bare
+    };
+
+    if has_binary("timeout") {
+        let mut with_timeout = Command::new("timeout");
+        with_timeout
+            .arg("--signal=KILL")
+            .arg(limits.task_timeout_secs.to_string())
+            .arg(wrapped.get_program())
+            .args(wrapped.get_args());
+        wrapped = with_timeout;
+    } else {
+        warn_once(
+            &TIMEOUT_WARNING,
+            "timeout not found on PATH — agent runs have no wall-clock kill",
+        );
+    }
+
+    if let Some(limit_mb) = limits.max_memory_mb {
+        if has_binary("systemd-run") {
+            let mut with_cgroup = Command::new("systemd-run");
+            with_cgroup
+                .arg("--user")
+                .arg("--scope")
+                .arg("--quiet")
+                .arg("-p")
+                .arg(format!("MemoryMax={limit_mb}M"))
+                .arg(wrapped.get_program())
+                .args(wrapped.get_args());
+            wrapped = with_cgroup;
+        } else {
+            warn_once(
+                &SYSTEMD_RUN_WARNING,
+                "systemd-run not found on PATH — memory cap has no effect",
+            );
+        }
+    }
+
+    *cmd = wrapped;
+}
+
+static BWRAP_WARNING: Once = Once::new();
+static TIMEOUT_WARNING: Once = Once::new();
+static SYSTEMD_RUN_WARNING: Once = Once::new();
+
+fn warn_once(guard: &Once, message: &str) {
+    guard.call_once(|| eprintln!("Warning: {message}"));
+}
+
+/// Whether `name` resolves to an executable on `PATH`, without running it
+/// (some of these binaries don't support a harmless `--version`/`--help`
+/// probe, and we'd rather skip a wrapper than hang on one).
+pub(super) fn has_binary(name: &str) -> bool {
+    let Some(path_var) = std::env::var_os("PATH") else {
+        return false;
+    };
+    std::env::split_paths(&path_var).any(|dir| dir.join(name).is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn has_binary_finds_sh() {
+        // `sh` is present on essentially every CI/dev box this runs on.
+        assert!(has_binary("sh"));
+    }
+
+    #[test]
+    fn has_binary_rejects_nonexistent() {
+        assert!(!has_binary("definitely-not-a-real-binary-xyz"));
+    }
+}
@@ -2,14 +2,26 @@
 
 use anyhow::{Context, Result};
 use colored::Colorize;
+use std::collections::HashSet;
 use std::fs;
 use std::path::PathBuf;
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender};
+use std::sync::{Arc, Mutex};
 
-use super::cache::{CacheKey, CacheManager};
+use super::backend::{self, Runner};
+use super::cache::{CacheKey, CacheManager, CacheOutcome};
+use super::container::ContainerSandbox;
+use super::crawl;
 use super::report::{ComparisonReport, ReportFormat};
-use super::runner::{ClaudeRunner, RunResult};
-use super::sandbox::Sandbox;
+use super::runner::RunResult;
+use super::sandbox::{ResourceLimits, Sandbox};
 use super::tasks::{Task, TaskSet};
+use crate::context_budget::{self, RankingStrategy};
+use crate::manifest::Manifest;
+
+/// File extensions the `"rag"` variant's crawl treats as source, matching
+/// `Config`'s default `languages` set.
+const RAG_EXTENSIONS: &[&str] = &["ts", "tsx", "js", "jsx", "py", "rs", "go"];
 
 /// Options for comparison run
 #[derive(Debug, Clone)]
@@ -21,22 +33,69 @@ pub struct CompareOptions {
     pub src_path: Option<String>,
     /// Task set to use (standard, quick, or custom path)
     pub task_set: String,
-    /// Number of runs per task (for averaging)
-    #[allow(dead_code)]
+    /// Number of runs per task (for averaging and significance testing)
     pub runs: u32,
+    /// Maximum number of control/FMM invocations to run concurrently,
+    /// jobserver-style (see [`TokenPool`]).
+    pub concurrency: usize,
+    /// Token budget for the FMM manifest context injected into the FMM
+    /// variant's prompt (see [`Orchestrator::build_fmm_context`]). A
+    /// manifest estimated over this is replaced with a reduced view rather
+    /// than inlined whole.
+    pub context_token_budget: usize,
+    /// Which files a reduced context view drops first once the manifest
+    /// exceeds `context_token_budget`.
+    pub context_ranking: RankingStrategy,
     /// Output directory for results
     pub output: Option<PathBuf>,
     /// Output format
     pub format: ReportFormat,
+    /// Minimum tool-call reduction percentage a task must show to pass the
+    /// [`ReportFormat::Junit`]/[`ReportFormat::Jsonl`] CI gate (default 0:
+    /// a task fails if its FMM variant crashed or used more tool calls than
+    /// control).
+    pub min_reduction: f64,
     /// Maximum budget in USD
     pub max_budget: f64,
     /// Use cached results when available
     pub use_cache: bool,
+    /// Bypass cache reads and always rerun tasks (cache is still refreshed
+    /// for subsequent runs)
+    pub force_refresh: bool,
+    /// Diff against the last commit this repo was benchmarked at, and reuse
+    /// a cached result for any task whose recorded `files_accessed` didn't
+    /// change, instead of recomputing every task just because the commit
+    /// moved (see [`CacheManager::find_reusable_result`]).
+    pub incremental: bool,
     /// Quick mode (fewer tasks)
     pub quick: bool,
     /// Model to use
     #[allow(dead_code)]
     pub model: String,
+    /// Which agent backend drives the control/FMM invocations (see
+    /// [`backend::select_runner`] for the supported names).
+    pub backend: String,
+    /// Also run a `"rag"` baseline variant per task: a naive retrieval
+    /// context built by crawling `working_dir` (see
+    /// [`super::crawl::build_rag_context`]) and injecting it the same way
+    /// the FMM manifest is, so the report shows whether FMM's structural
+    /// manifest beats simple file concatenation, not just no context at
+    /// all. Off by default since it roughly adds a third of the cost/time
+    /// to every run.
+    pub rag: bool,
+    /// Depth passed to the sandbox's initial clone (see
+    /// [`GitBackend::clone`](super::git_backend::GitBackend::clone)):
+    /// `Some(n)` for a shallow `--depth n --single-branch` clone, `None`
+    /// for full history. Defaults to `Some(1)`; `fmm compare --full-clone`
+    /// sets it to `None`.
+    pub clone_depth: Option<u32>,
+    /// Run the clone + `fmm generate` step inside a throwaway Docker/Podman
+    /// container (see [`ContainerSandbox`]) instead of on the host, for
+    /// comparisons against a repo the caller doesn't otherwise trust.
+    /// Requires `docker` or `podman` on `PATH`. Off by default since most
+    /// runs are against repos the caller already trusts enough to clone
+    /// directly.
+    pub container: bool,
 }
 
 impl Default for CompareOptions {
@@ -46,12 +105,40 @@ impl Default for CompareOptions {
             src_path: None,
             task_set: "standard".to_string(),
             runs: 1,
+            concurrency: 4,
+            context_token_budget: 8000,
+            context_ranking: RankingStrategy::InDegree,
             output: None,
             format: ReportFormat::Both,
+            min_reduction: 0.0,
             max_budget: 10.0,
             use_cache: true,
+            force_refresh: false,
+            incremental: false,
             quick: false,
             model: "sonnet".to_string(),
+            backend: "claude".to_string(),
+            rag: false,
+            clone_depth: Some(1),
+            container: false,
+        }
+    }
+}
+
+/// Either a host-side [`Sandbox`] or a [`ContainerSandbox`] wrapping one,
+/// depending on [`CompareOptions::container`] — lets `Orchestrator::run`
+/// use the same `&Sandbox`-based downstream logic (commit SHA, diffing,
+/// `control_dir`/`fmm_dir`) regardless of which one cloned the repo.
+enum SandboxHandle {
+    Host(Sandbox),
+    Container(ContainerSandbox),
+}
+
+impl SandboxHandle {
+    fn sandbox(&self) -> &Sandbox {
+        match self {
+            SandboxHandle::Host(sandbox) => sandbox,
+            SandboxHandle::Container(container) => container.sandbox(),
         }
     }
 }
@@ -60,15 +147,15 @@ impl Default for CompareOptions {
 pub struct Orchestrator {
     options: CompareOptions,
     cache: CacheManager,
-    runner: ClaudeRunner,
+    runner: Arc<dyn Runner>,
     total_cost: f64,
 }
 
 impl Orchestrator {
     /// Create a new orchestrator
     pub fn new(options: CompareOptions) -> Result<Self> {
-        let cache = CacheManager::new(None)?;
-        let runner = ClaudeRunner::new();
+        let cache = CacheManager::new(None)?.with_force_refresh(options.force_refresh);
+        let runner: Arc<dyn Runner> = Arc::from(backend::select_runner(&options.backend)?);
 
         Ok(Self {
             options,
@@ -84,10 +171,24 @@ impl Orchestrator {
 
         println!("{} Job ID: {}", "📋".yellow(), job_id.cyan());
 
-        // Step 1: Create sandbox and clone repo
+        // Step 1: Create sandbox and clone repo. --container runs the
+        // clone and the generate step below both inside a throwaway
+        // container instead of on the host (see `ContainerSandbox`).
         println!("{} Setting up sandbox...", "🔧".yellow());
-        let sandbox = Sandbox::new(&job_id)?;
-        sandbox.clone_repo(url, self.options.branch.as_deref())?;
+        let sandbox_handle = if self.options.container {
+            println!(
+                "  {} Cloning + generating inside a container...",
+                "🔧".yellow()
+            );
+            let container = ContainerSandbox::new(&job_id, ResourceLimits::default())?;
+            container.clone_and_generate(url, self.options.branch.as_deref(), self.options.clone_depth)?;
+            SandboxHandle::Container(container)
+        } else {
+            let sandbox = Sandbox::new(&job_id)?;
+            sandbox.clone_repo(url, self.options.branch.as_deref(), self.options.clone_depth)?;
+            SandboxHandle::Host(sandbox)
+        };
+        let sandbox = sandbox_handle.sandbox();
 
         let commit_sha = sandbox.get_commit_sha(&sandbox.control_dir)?;
         println!(
@@ -96,9 +197,13 @@ impl Orchestrator {
             &commit_sha[..8].dimmed()
         );
 
-        // Step 2: Generate FMM manifest for FMM variant
-        println!("{} Generating FMM manifest...", "🔧".yellow());
-        sandbox.generate_fmm_manifest()?;
+        // Step 2: Generate FMM manifest for FMM variant. Already done
+        // above as part of the containerized clone+generate when
+        // --container is set.
+        if !self.options.container {
+            println!("{} Generating FMM manifest...", "🔧".yellow());
+            sandbox.generate_fmm_manifest()?;
+        }
 
         // Check if manifest was generated
         let manifest_path = sandbox.fmm_dir.join(".fmm").join("index.json");
@@ -116,6 +221,38 @@ impl Orchestrator {
             );
         }
 
+        // Step 2b: For --incremental, diff against the last commit this
+        // repo was benchmarked at so `run_task_with_cache`/
+        // `run_task_with_fmm` can reuse a cached result for any task whose
+        // touched files didn't change, instead of recomputing every task
+        // just because the commit moved.
+        let changed_files: Option<HashSet<String>> = if self.options.incremental {
+            self.cache
+                .last_cached_commit(url, &commit_sha)
+                .and_then(
+                    |old_sha| match sandbox.diff_against(&sandbox.control_dir, &old_sha) {
+                        Ok(files) => {
+                            println!(
+                                "  {} Incremental: {} file(s) changed since {}",
+                                "✓".green(),
+                                files.len(),
+                                &old_sha[..old_sha.len().min(8)]
+                            );
+                            Some(files.into_iter().collect())
+                        }
+                        Err(e) => {
+                            println!(
+                                "  {} Incremental diff unavailable, running all tasks: {e}",
+                                "!".yellow()
+                            );
+                            None
+                        }
+                    },
+                )
+        } else {
+            None
+        };
+
         // Step 3: Load tasks
         let task_set = if self.options.quick {
             TaskSet::quick()
@@ -133,62 +270,207 @@ impl Orchestrator {
             task_set.tasks.len()
         );
 
-        // Step 4: Run tasks
-        let mut results: Vec<(Task, RunResult, RunResult)> = vec![];
+        // Step 4: Run every task/run/variant combination through a bounded
+        // worker pool instead of strictly sequentially. `pool` is a
+        // jobserver-style token channel: each in-flight Claude invocation
+        // holds a token for its duration, so at most `options.concurrency`
+        // run at once regardless of how many are queued. `budget` gates
+        // dispatch from inside the pool (rather than between loop
+        // iterations) so once it's exhausted no new invocation starts, even
+        // while others already running keep draining.
+        let fmm_context = self.build_fmm_context(&sandbox.fmm_dir)?;
+        let rag_context = if self.options.rag {
+            crawl::build_rag_context(&sandbox.control_dir, RAG_EXTENSIONS)?
+        } else {
+            String::new()
+        };
+        let runs = self.options.runs.max(1);
+        let pool = TokenPool::new(self.options.concurrency.max(1));
+        let budget = Budget::new(self.total_cost, self.options.max_budget);
+
+        // Pre-sized so each job writes its `RunResult` into its own
+        // `(task_index, run_index)` slot, giving deterministic report
+        // ordering no matter which order the pool's jobs actually finish in.
+        // The rag slot stays all-`None` (and is dropped before reaching the
+        // report) when `options.rag` is off.
+        let slots: Mutex<
+            Vec<(
+                Vec<Option<RunResult>>,
+                Vec<Option<RunResult>>,
+                Vec<Option<RunResult>>,
+            )>,
+        > = Mutex::new(
+            task_set
+                .tasks
+                .iter()
+                .map(|_| {
+                    (
+                        vec![None; runs as usize],
+                        vec![None; runs as usize],
+                        vec![None; runs as usize],
+                    )
+                })
+                .collect(),
+        );
 
-        for (i, task) in task_set.tasks.iter().enumerate() {
+        let changed_files = changed_files.as_ref();
+        let mut variants = vec![JobVariant::Control, JobVariant::Fmm];
+        if self.options.rag {
+            variants.push(JobVariant::Rag);
+        }
+
+        std::thread::scope(|scope| {
+            for (task_index, task) in task_set.tasks.iter().enumerate() {
+                for run_index in 0..runs {
+                    for variant in variants.iter().copied() {
+                        if budget.exhausted() {
+                            continue;
+                        }
+
+                        let pool = &pool;
+                        let budget = &budget;
+                        let slots = &slots;
+                        let fmm_context = &fmm_context;
+                        let rag_context = &rag_context;
+                        let sandbox = &sandbox;
+                        let commit_sha = &commit_sha;
+                        let this = &*self;
+
+                        scope.spawn(move || {
+                            let _permit = pool.acquire();
+                            if budget.exhausted() {
+                                return;
+                            }
+
+                            let label = format!(
+                                "{} run {}/{} {}",
+                                task.name,
+                                run_index + 1,
+                                runs,
+                                variant.label()
+                            );
+                            let result = match variant {
+                                JobVariant::Control => this.run_task_with_cache(
+                                    task,
+                                    &sandbox.control_dir,
+                                    variant.label(),
+                                    url,
+                                    commit_sha,
+                                    run_index,
+                                    runs,
+                                    changed_files,
+                                ),
+                                JobVariant::Fmm => this.run_task_with_fmm(
+                                    task,
+                                    &sandbox.fmm_dir,
+                                    variant.label(),
+                                    url,
+                                    commit_sha,
+                                    fmm_context,
+                                    run_index,
+                                    runs,
+                                    changed_files,
+                                ),
+                                JobVariant::Rag => this.run_task_with_fmm(
+                                    task,
+                                    &sandbox.control_dir,
+                                    variant.label(),
+                                    url,
+                                    commit_sha,
+                                    rag_context,
+                                    run_index,
+                                    runs,
+                                    changed_files,
+                                ),
+                            };
+
+                            match result {
+                                Ok(result) => {
+                                    budget.add(result.total_cost_usd);
+                                    println!(
+                                        "  {} {} ({} tools, ${:.4})",
+                                        if result.success {
+                                            "✓".green()
+                                        } else {
+                                            "✗".red()
+                                        },
+                                        label,
+                                        result.tool_calls,
+                                        result.total_cost_usd
+                                    );
+                                    let mut slots = slots.lock().unwrap();
+                                    match variant {
+                                        JobVariant::Control => {
+                                            slots[task_index].0[run_index as usize] = Some(result)
+                                        }
+                                        JobVariant::Fmm => {
+                                            slots[task_index].1[run_index as usize] = Some(result)
+                                        }
+                                        JobVariant::Rag => {
+                                            slots[task_index].2[run_index as usize] = Some(result)
+                                        }
+                                    }
+                                }
+                                Err(e) => {
+                                    println!("  {} {} failed: {e}", "✗".red(), label);
+                                }
+                            }
+                        });
+                    }
+                }
+            }
+        });
+
+        self.total_cost = budget.total();
+        if budget.exhausted() {
             println!(
-                "\n{} Task {}/{}: {}",
-                "▶".cyan(),
-                i + 1,
-                task_set.tasks.len(),
-                task.name.white().bold()
+                "{} Budget limit reached (${:.2} / ${:.2})",
+                "⚠".yellow(),
+                self.total_cost,
+                self.options.max_budget
             );
+        }
 
-            // Check budget
-            if self.total_cost >= self.options.max_budget {
-                println!(
-                    "{} Budget limit reached (${:.2} / ${:.2})",
-                    "⚠".yellow(),
-                    self.total_cost,
-                    self.options.max_budget
-                );
-                break;
+        // Step 4b: Stitch the per-slot results back into one entry per task,
+        // dropping tasks that never got a single run recorded (budget
+        // exhausted before they were dispatched).
+        let mut results: Vec<(Task, Vec<RunResult>, Vec<RunResult>, Vec<RunResult>)> = vec![];
+        let slots = slots.into_inner().unwrap();
+        for (task, (control_slots, fmm_slots, rag_slots)) in task_set.tasks.into_iter().zip(slots)
+        {
+            let control_runs: Vec<RunResult> = control_slots.into_iter().flatten().collect();
+            let fmm_runs: Vec<RunResult> = fmm_slots.into_iter().flatten().collect();
+            let rag_runs: Vec<RunResult> = rag_slots.into_iter().flatten().collect();
+            if control_runs.is_empty() && fmm_runs.is_empty() && rag_runs.is_empty() {
+                continue;
             }
 
-            // Run control variant
-            let control_result =
-                self.run_task_with_cache(task, &sandbox.control_dir, "control", url, &commit_sha)?;
-
-            // Run FMM variant
-            let fmm_context = self.build_fmm_context(&sandbox.fmm_dir)?;
-            let fmm_result = self.run_task_with_fmm(
-                task,
-                &sandbox.fmm_dir,
-                "fmm",
-                url,
-                &commit_sha,
-                &fmm_context,
-            )?;
-
-            // Update cost tracking
-            self.total_cost += control_result.total_cost_usd + fmm_result.total_cost_usd;
-
-            // Report progress
-            let reduction = if control_result.tool_calls > 0 {
-                ((control_result.tool_calls as f64 - fmm_result.tool_calls as f64)
-                    / control_result.tool_calls as f64)
-                    * 100.0
+            let control_mean = mean_tool_calls(&control_runs);
+            let fmm_mean = mean_tool_calls(&fmm_runs);
+            let reduction = if control_mean > 0.0 {
+                ((control_mean - fmm_mean) / control_mean) * 100.0
             } else {
                 0.0
             };
-
             println!(
-                "  Control: {} tools | FMM: {} tools | Reduction: {:.1}%",
-                control_result.tool_calls, fmm_result.tool_calls, reduction
+                "\n{} {}: Control {:.1} tools | FMM {:.1} tools | Reduction: {:.1}% (n={})",
+                "▶".cyan(),
+                task.name.white().bold(),
+                control_mean,
+                fmm_mean,
+                reduction,
+                control_runs.len().max(fmm_runs.len())
             );
+            if !rag_runs.is_empty() {
+                println!(
+                    "  {} RAG {:.1} tools (n={})",
+                    "▶".cyan(),
+                    mean_tool_calls(&rag_runs),
+                    rag_runs.len()
+                );
+            }
 
-            results.push((task.clone(), control_result, fmm_result));
+            results.push((task, control_runs, fmm_runs, rag_runs));
         }
 
         // Step 5: Generate report
@@ -202,7 +484,7 @@ impl Orchestrator {
 
         // Save report
         if let Some(ref output_dir) = self.options.output {
-            let saved = report.save(output_dir, self.options.format)?;
+            let saved = report.save(output_dir, self.options.format, self.options.min_reduction)?;
             for path in saved {
                 println!("  {} Saved: {}", "✓".green(), path.dimmed());
             }
@@ -221,78 +503,140 @@ impl Orchestrator {
         Ok(report)
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn run_task_with_cache(
-        &mut self,
+        &self,
         task: &Task,
         working_dir: &std::path::Path,
         variant: &str,
         repo_url: &str,
         commit_sha: &str,
+        run_index: u32,
+        runs: u32,
+        changed_files: Option<&HashSet<String>>,
     ) -> Result<RunResult> {
-        // Check cache
-        if self.options.use_cache {
-            let cache_key = CacheKey::new(repo_url, commit_sha, &task.id, variant);
-            if let Some(cached) = self.cache.get(&cache_key) {
-                println!("  {} {} (cached)", "●".dimmed(), variant.dimmed());
-                return Ok(cached);
-            }
+        print!("  {} {}...", "●".cyan(), variant);
+        let variant = run_variant_key(variant, run_index, runs);
+
+        if !self.options.use_cache {
+            let result = self.runner.run_task(task, working_dir, &variant, None)?;
+            Self::print_run_outcome(None, &result);
+            return Ok(result);
         }
 
-        // Run task
-        print!("  {} {}...", "●".cyan(), variant);
-        let result = self.runner.run_task(task, working_dir, variant, None)?;
+        let cache_key = self
+            .runner
+            .cache_key(repo_url, commit_sha, task, &variant, None);
 
-        // Cache result
-        if self.options.use_cache && result.success {
-            let cache_key = CacheKey::new(repo_url, commit_sha, &task.id, variant);
-            self.cache.set(cache_key, result.clone())?;
+        if let Some(reused) =
+            self.reuse_from_prior_commit(repo_url, task, &variant, &cache_key, changed_files)
+        {
+            Self::print_run_outcome(Some(CacheOutcome::Reused), &reused);
+            return Ok(reused);
         }
 
-        println!(
-            " {} ({} tools, ${:.4})",
-            if result.success {
-                "✓".green()
-            } else {
-                "✗".red()
-            },
-            result.tool_calls,
-            result.total_cost_usd
-        );
+        let runner = Arc::clone(&self.runner);
+        let task = task.clone();
+        let working_dir = working_dir.to_path_buf();
 
+        let (result, outcome) = self.cache.get_or_run(&cache_key, move || {
+            runner.run_task(&task, &working_dir, &variant, None)
+        })?;
+
+        Self::print_run_outcome(Some(outcome), &result);
         Ok(result)
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn run_task_with_fmm(
-        &mut self,
+        &self,
         task: &Task,
         working_dir: &std::path::Path,
         variant: &str,
         repo_url: &str,
         commit_sha: &str,
         fmm_context: &str,
+        run_index: u32,
+        runs: u32,
+        changed_files: Option<&HashSet<String>>,
     ) -> Result<RunResult> {
-        // Check cache
-        if self.options.use_cache {
-            let cache_key = CacheKey::new(repo_url, commit_sha, &task.id, variant);
-            if let Some(cached) = self.cache.get(&cache_key) {
-                println!("  {} {} (cached)", "●".dimmed(), variant.dimmed());
-                return Ok(cached);
-            }
-        }
-
-        // Run task with FMM context
         print!("  {} {}...", "●".cyan(), variant);
+        let variant = run_variant_key(variant, run_index, runs);
         let context = if fmm_context.is_empty() {
             None
         } else {
-            Some(fmm_context)
+            Some(fmm_context.to_string())
         };
-        let result = self.runner.run_task(task, working_dir, variant, context)?;
 
-        // Cache result
-        if self.options.use_cache && result.success {
-            let cache_key = CacheKey::new(repo_url, commit_sha, &task.id, variant);
-            self.cache.set(cache_key, result.clone())?;
+        if !self.options.use_cache {
+            let result = self
+                .runner
+                .run_task(task, working_dir, &variant, context.as_deref())?;
+            Self::print_run_outcome(None, &result);
+            return Ok(result);
+        }
+
+        let cache_key =
+            self.runner
+                .cache_key(repo_url, commit_sha, task, &variant, context.as_deref());
+
+        if let Some(reused) =
+            self.reuse_from_prior_commit(repo_url, task, &variant, &cache_key, changed_files)
+        {
+            Self::print_run_outcome(Some(CacheOutcome::Reused), &reused);
+            return Ok(reused);
+        }
+
+        let runner = Arc::clone(&self.runner);
+        let task = task.clone();
+        let working_dir = working_dir.to_path_buf();
+
+        let (result, outcome) = self.cache.get_or_run(&cache_key, move || {
+            runner.run_task(&task, &working_dir, &variant, context.as_deref())
+        })?;
+
+        Self::print_run_outcome(Some(outcome), &result);
+        Ok(result)
+    }
+
+    /// Incremental-mode fast path: if `changed_files` is set and a prior
+    /// commit's cached result for this `(repo_url, task.id, variant)`
+    /// touched none of them, adopt it for `cache_key`'s commit too (so the
+    /// next run against this same commit is a plain cache hit) rather than
+    /// re-invoking the runner.
+    fn reuse_from_prior_commit(
+        &self,
+        repo_url: &str,
+        task: &Task,
+        variant: &str,
+        cache_key: &CacheKey,
+        changed_files: Option<&HashSet<String>>,
+    ) -> Option<RunResult> {
+        let changed_files = changed_files?;
+        let reused = self
+            .cache
+            .find_reusable_result(repo_url, &task.id, variant, changed_files)?;
+        let _ = self.cache.set(cache_key.clone(), reused.clone());
+        Some(reused)
+    }
+
+    /// Print the trailing `... ✓ (N tools, $cost)` / `(cached)` /
+    /// `(stale, refreshing)` summary line for a completed task run.
+    fn print_run_outcome(outcome: Option<CacheOutcome>, result: &RunResult) {
+        match outcome {
+            Some(CacheOutcome::Hit) => {
+                println!(" {} (cached)", "●".dimmed());
+                return;
+            }
+            Some(CacheOutcome::Stale) => {
+                println!(" {} (stale, refreshing)", "●".yellow());
+                return;
+            }
+            Some(CacheOutcome::Reused) => {
+                println!(" {} (reused, unaffected by diff)", "●".cyan());
+                return;
+            }
+            Some(CacheOutcome::Miss) | None => {}
         }
 
         println!(
@@ -305,10 +649,14 @@ impl Orchestrator {
             result.tool_calls,
             result.total_cost_usd
         );
-
-        Ok(result)
     }
 
+    /// Build the system-prompt context describing the FMM manifest for the
+    /// FMM variant. Inlines the manifest verbatim when it fits within
+    /// `options.context_token_budget`; otherwise falls back to a reduced
+    /// view (see [`Self::build_reduced_fmm_context`]) so a large repo
+    /// doesn't blow past the model's context window or inflate cost —
+    /// defeating the comparison's whole point.
     fn build_fmm_context(&self, fmm_dir: &std::path::Path) -> Result<String> {
         let manifest_path = fmm_dir.join(".fmm").join("index.json");
 
@@ -319,9 +667,201 @@ impl Orchestrator {
         let manifest_content =
             fs::read_to_string(&manifest_path).context("Failed to read FMM manifest")?;
 
-        // Build context prompt
-        let context = format!(
-            r#"IMPORTANT: This repository has an FMM (Frontmatter Matters) manifest available.
+        if context_budget::estimate_tokens(&manifest_content) <= self.options.context_token_budget {
+            return Ok(format_full_fmm_context(&manifest_content));
+        }
+
+        let manifest: Manifest = serde_json::from_str(&manifest_content)
+            .context("Failed to parse FMM manifest for budgeting")?;
+        Ok(self.build_reduced_fmm_context(&manifest))
+    }
+
+    /// Reduced FMM context for manifests too large to inline whole: keeps
+    /// the export index and every kept file's exports/imports edges, but
+    /// drops line counts outright and evicts files per
+    /// `options.context_ranking` (lowest in-degree first, by default)
+    /// until the render fits `options.context_token_budget`. Elided files
+    /// are named in a trailing note so the model can ask for them by path.
+    fn build_reduced_fmm_context(&self, manifest: &Manifest) -> String {
+        let export_index =
+            serde_json::to_string(&manifest.export_index).unwrap_or_else(|_| "{}".to_string());
+        let preamble = format!(
+            r#"IMPORTANT: This repository has an FMM (Frontmatter Matters) manifest available, reduced to fit a {}-token context budget (the full manifest was larger).
+
+Before exploring the codebase with file reads, FIRST consult this manifest to understand the codebase structure. Line counts and some low-degree files (few or no other files import them) were dropped to fit the budget; ask for a dropped file's metadata by path if you need it.
+
+EXPORT INDEX (export name -> file path):
+```json
+{}
+```
+
+FILES (exports/imports edges):
+"#,
+            self.options.context_token_budget, export_index
+        );
+
+        // Sorted for deterministic output regardless of HashMap iteration
+        // order; `evict_to_budget`'s stable sort then ties on this order.
+        let mut paths: Vec<&String> = manifest.files.keys().collect();
+        paths.sort();
+
+        let candidates: Vec<context_budget::Candidate> = paths
+            .iter()
+            .map(|path| {
+                let entry = &manifest.files[*path];
+                let mut rendered = format!("### {path}\n");
+                if !entry.exports.is_empty() {
+                    rendered.push_str(&format!("  exports: [{}]\n", entry.exports.join(", ")));
+                }
+                if !entry.imports.is_empty() {
+                    rendered.push_str(&format!("  imports: [{}]\n", entry.imports.join(", ")));
+                }
+                let in_degree = manifest.importers.get(*path).map(Vec::len).unwrap_or(0);
+                context_budget::Candidate {
+                    key: (*path).clone(),
+                    in_degree,
+                    rendered,
+                }
+            })
+            .collect();
+
+        let preamble_tokens = context_budget::estimate_tokens(&preamble);
+        let elided = context_budget::evict_to_budget(
+            &candidates,
+            preamble_tokens,
+            self.options.context_token_budget,
+            self.options.context_ranking,
+        );
+        let elided: std::collections::HashSet<&str> = elided.iter().map(String::as_str).collect();
+
+        let mut out = preamble;
+        for candidate in &candidates {
+            if !elided.contains(candidate.key.as_str()) {
+                out.push_str(&candidate.rendered);
+            }
+        }
+
+        if !elided.is_empty() {
+            out.push_str(&format!(
+                "\n{} file(s) elided to fit the context budget: {}. Ask for any of these by path if you need their exports/imports.\n",
+                elided.len(),
+                paths
+                    .iter()
+                    .filter(|p| elided.contains(p.as_str()))
+                    .map(|p| p.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+
+        out
+    }
+
+    fn load_custom_tasks(&self, path: &str) -> Result<TaskSet> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to load custom tasks from {}", path))?;
+
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse custom tasks from {}", path))
+    }
+}
+
+/// Which variant (control, FMM or RAG) a scheduled job runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum JobVariant {
+    Control,
+    Fmm,
+    /// Naive retrieval baseline: crawled source files injected as context
+    /// instead of an FMM manifest (see [`super::crawl::build_rag_context`]).
+    Rag,
+}
+
+impl JobVariant {
+    fn label(self) -> &'static str {
+        match self {
+            JobVariant::Control => "control",
+            JobVariant::Fmm => "fmm",
+            JobVariant::Rag => "rag",
+        }
+    }
+}
+
+/// Jobserver-style concurrency limiter: `capacity` tokens are pushed into a
+/// bounded channel up front, and each in-flight job holds one for its
+/// duration via [`TokenPool::acquire`]. The returned guard hands its token
+/// back to the channel on drop, so the pool needs no locking of its own.
+struct TokenPool {
+    tokens: Receiver<()>,
+    release: SyncSender<()>,
+}
+
+impl TokenPool {
+    fn new(capacity: usize) -> Self {
+        let (release, tokens) = sync_channel(capacity);
+        for _ in 0..capacity {
+            release
+                .send(())
+                .expect("channel just created with this capacity, so it can't be full");
+        }
+        Self { tokens, release }
+    }
+
+    /// Block until a token is available, returning a guard that releases it
+    /// back to the pool when dropped.
+    fn acquire(&self) -> TokenPermit<'_> {
+        self.tokens
+            .recv()
+            .expect("release sender is held by self for the pool's whole lifetime");
+        TokenPermit {
+            release: &self.release,
+        }
+    }
+}
+
+struct TokenPermit<'a> {
+    release: &'a SyncSender<()>,
+}
+
+impl Drop for TokenPermit<'_> {
+    fn drop(&mut self) {
+        let _ = self.release.send(());
+    }
+}
+
+/// Thread-safe running total against `CompareOptions.max_budget`, shared by
+/// every job in the worker pool so dispatch can stop as soon as the limit is
+/// hit instead of only checking between sequential loop iterations.
+struct Budget {
+    spent: Mutex<f64>,
+    max: f64,
+}
+
+impl Budget {
+    fn new(starting: f64, max: f64) -> Self {
+        Self {
+            spent: Mutex::new(starting),
+            max,
+        }
+    }
+
+    fn add(&self, cost: f64) {
+        *self.spent.lock().unwrap() += cost;
+    }
+
+    fn exhausted(&self) -> bool {
+        *self.spent.lock().unwrap() >= self.max
+    }
+
+    fn total(&self) -> f64 {
+        *self.spent.lock().unwrap()
+    }
+}
+
+/// Full-manifest FMM context: the original `.fmm/index.json` inlined
+/// verbatim, used when it fits within `options.context_token_budget`.
+fn format_full_fmm_context(manifest_content: &str) -> String {
+    format!(
+        r#"IMPORTANT: This repository has an FMM (Frontmatter Matters) manifest available.
 
 Before exploring the codebase with file reads, FIRST consult this manifest to understand the codebase structure.
 The manifest contains:
@@ -342,19 +882,31 @@ Use this manifest to:
 4. Reduce unnecessary file reads
 
 Only read files when you need the actual implementation details."#,
-            manifest_content
-        );
+        manifest_content
+    )
+}
 
-        Ok(context)
+/// Cache key / `RunResult.variant` label for one repetition of a variant.
+/// Keeps the original bare `"control"`/`"fmm"` label (and cache entry) for
+/// `runs == 1` so single-run comparisons stay cache-compatible with older
+/// cached results; otherwise suffixes the run index so each repetition gets
+/// its own cache entry rather than replaying the same cached run.
+fn run_variant_key(variant: &str, run_index: u32, runs: u32) -> String {
+    if runs <= 1 {
+        variant.to_string()
+    } else {
+        format!("{variant}#{run_index}")
     }
+}
 
-    fn load_custom_tasks(&self, path: &str) -> Result<TaskSet> {
-        let content = fs::read_to_string(path)
-            .with_context(|| format!("Failed to load custom tasks from {}", path))?;
-
-        serde_json::from_str(&content)
-            .with_context(|| format!("Failed to parse custom tasks from {}", path))
+/// Mean tool-call count across a sample of runs, for the per-task progress
+/// line. The full per-run samples (and their significance verdict) live in
+/// [`super::report::ComparisonReport`].
+fn mean_tool_calls(runs: &[RunResult]) -> f64 {
+    if runs.is_empty() {
+        return 0.0;
     }
+    runs.iter().map(|r| r.tool_calls as f64).sum::<f64>() / runs.len() as f64
 }
 
 fn generate_job_id() -> String {
@@ -391,5 +943,54 @@ mod tests {
         assert_eq!(opts.runs, 1);
         assert_eq!(opts.max_budget, 10.0);
         assert!(opts.use_cache);
+        assert_eq!(opts.context_token_budget, 8000);
+        assert_eq!(opts.context_ranking, RankingStrategy::InDegree);
+        assert_eq!(opts.min_reduction, 0.0);
+        assert!(!opts.incremental);
+        assert!(!opts.rag);
+    }
+
+    #[test]
+    fn run_variant_key_is_bare_label_for_single_run() {
+        assert_eq!(run_variant_key("control", 0, 1), "control");
+    }
+
+    #[test]
+    fn run_variant_key_suffixes_index_for_multiple_runs() {
+        assert_eq!(run_variant_key("fmm", 2, 5), "fmm#2");
+    }
+
+    fn test_result(tool_calls: u32) -> RunResult {
+        RunResult {
+            task_id: "task".to_string(),
+            variant: "control".to_string(),
+            tool_calls,
+            tools_by_name: std::collections::HashMap::new(),
+            files_accessed: vec![],
+            read_calls: 0,
+            failed_tool_calls: 0,
+            redundant_reads: 0,
+            tool_failures_by_name: std::collections::HashMap::new(),
+            input_tokens: 0,
+            output_tokens: 0,
+            cache_read_tokens: 0,
+            total_cost_usd: 0.0,
+            duration_ms: 0,
+            num_turns: 0,
+            response: String::new(),
+            success: true,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn mean_tool_calls_averages_across_runs() {
+        let runs = vec![test_result(4), test_result(6)];
+        assert_eq!(mean_tool_calls(&runs), 5.0);
+    }
+
+    #[test]
+    fn mean_tool_calls_empty_is_zero() {
+        assert_eq!(mean_tool_calls(&[]), 0.0);
     }
 }
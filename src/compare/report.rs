@@ -0,0 +1,336 @@
+//! Comparison report generation - JSON and Markdown output
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::runner::RunResult;
+use super::stats::{MetricComparison, Significance};
+use super::tasks::Task;
+
+/// Which format(s) [`ComparisonReport::save`] writes to disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Json,
+    Markdown,
+    Both,
+    /// JUnit XML: one `testcase` per task, so `fmm compare` can be wired
+    /// into a CI workflow's test-report step (GitHub Actions, GitLab, or
+    /// `cargo-nextest`-style JUnit consumers) and fail the build the same
+    /// way a failing test would.
+    Junit,
+    /// One compact JSON object per task (see [`ComparisonReport::to_jsonl`]),
+    /// for CI steps that want to grep/jq a summary rather than parse XML.
+    Jsonl,
+}
+
+/// Per-task control-vs-FMM comparison across `runs` repetitions of each
+/// variant, with tool-call and cost reduction backed by a significance
+/// verdict rather than a single noisy point estimate. `rag_runs` and its
+/// comparisons are empty/degenerate unless `CompareOptions::rag` was set —
+/// the "rag" variant is opt-in, not run by default.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskComparison {
+    pub task: Task,
+    pub control_runs: Vec<RunResult>,
+    pub fmm_runs: Vec<RunResult>,
+    pub rag_runs: Vec<RunResult>,
+    pub tool_calls: MetricComparison,
+    pub cost_usd: MetricComparison,
+    pub rag_tool_calls: MetricComparison,
+    pub rag_cost_usd: MetricComparison,
+}
+
+impl TaskComparison {
+    fn new(
+        task: Task,
+        control_runs: Vec<RunResult>,
+        fmm_runs: Vec<RunResult>,
+        rag_runs: Vec<RunResult>,
+    ) -> Self {
+        let control_tool_calls: Vec<f64> =
+            control_runs.iter().map(|r| r.tool_calls as f64).collect();
+        let fmm_tool_calls: Vec<f64> = fmm_runs.iter().map(|r| r.tool_calls as f64).collect();
+        let control_cost: Vec<f64> = control_runs.iter().map(|r| r.total_cost_usd).collect();
+        let fmm_cost: Vec<f64> = fmm_runs.iter().map(|r| r.total_cost_usd).collect();
+        let rag_tool_calls: Vec<f64> = rag_runs.iter().map(|r| r.tool_calls as f64).collect();
+        let rag_cost: Vec<f64> = rag_runs.iter().map(|r| r.total_cost_usd).collect();
+
+        Self {
+            tool_calls: MetricComparison::new(&control_tool_calls, &fmm_tool_calls),
+            cost_usd: MetricComparison::new(&control_cost, &fmm_cost),
+            rag_tool_calls: MetricComparison::new(&control_tool_calls, &rag_tool_calls),
+            rag_cost_usd: MetricComparison::new(&control_cost, &rag_cost),
+            task,
+            control_runs,
+            fmm_runs,
+            rag_runs,
+        }
+    }
+}
+
+/// Complete comparison report for an `fmm compare` job: one [`TaskComparison`]
+/// per task in the task set that was run before the budget ran out.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComparisonReport {
+    pub job_id: String,
+    pub repo_url: String,
+    pub commit_sha: String,
+    pub branch: String,
+    pub tasks: Vec<TaskComparison>,
+}
+
+impl ComparisonReport {
+    /// Build a report from the raw per-run results `Orchestrator::run`
+    /// collected: `runs` repetitions of each variant per task.
+    pub fn new(
+        job_id: String,
+        repo_url: String,
+        commit_sha: String,
+        branch: String,
+        results: Vec<(Task, Vec<RunResult>, Vec<RunResult>, Vec<RunResult>)>,
+    ) -> Self {
+        let tasks = results
+            .into_iter()
+            .map(|(task, control_runs, fmm_runs, rag_runs)| {
+                TaskComparison::new(task, control_runs, fmm_runs, rag_runs)
+            })
+            .collect();
+
+        Self {
+            job_id,
+            repo_url,
+            commit_sha,
+            branch,
+            tasks,
+        }
+    }
+
+    /// Write the report to `output_dir` in the requested format(s),
+    /// returning the paths written. `min_reduction` only affects
+    /// [`ReportFormat::Junit`] and [`ReportFormat::Jsonl`]: a task whose
+    /// tool-call reduction falls below it (or whose FMM variant crashed) is
+    /// reported as failed.
+    pub fn save(
+        &self,
+        output_dir: &Path,
+        format: ReportFormat,
+        min_reduction: f64,
+    ) -> Result<Vec<PathBuf>> {
+        fs::create_dir_all(output_dir)?;
+        let mut saved = Vec::new();
+
+        if matches!(format, ReportFormat::Json | ReportFormat::Both) {
+            let path = output_dir.join(format!("{}.json", self.job_id));
+            fs::write(&path, serde_json::to_string_pretty(self)?)?;
+            saved.push(path);
+        }
+
+        if matches!(format, ReportFormat::Markdown | ReportFormat::Both) {
+            let path = output_dir.join(format!("{}.md", self.job_id));
+            fs::write(&path, self.to_markdown())?;
+            saved.push(path);
+        }
+
+        if matches!(format, ReportFormat::Junit) {
+            let path = output_dir.join(format!("{}.junit.xml", self.job_id));
+            fs::write(&path, self.to_junit(min_reduction))?;
+            saved.push(path);
+        }
+
+        if matches!(format, ReportFormat::Jsonl) {
+            let path = output_dir.join(format!("{}.jsonl", self.job_id));
+            fs::write(&path, self.to_jsonl(min_reduction))?;
+            saved.push(path);
+        }
+
+        Ok(saved)
+    }
+
+    /// Print a one-line-per-task summary to stdout, the same shape as
+    /// `Orchestrator::run`'s per-task progress line but with the full
+    /// multi-run stats and significance verdict.
+    pub fn print_summary(&self) {
+        for comparison in &self.tasks {
+            println!(
+                "{}: tool calls {:.1} ± {:.1} -> {:.1} ± {:.1} ({}, n={})",
+                comparison.task.name,
+                comparison.tool_calls.control.mean,
+                comparison.tool_calls.control.stddev,
+                comparison.tool_calls.fmm.mean,
+                comparison.tool_calls.fmm.stddev,
+                comparison.tool_calls.verdict(),
+                comparison.tool_calls.control.n,
+            );
+        }
+    }
+
+    fn to_markdown(&self) -> String {
+        let mut out = format!(
+            "# Comparison report: {}\n\nRepo: {}\nCommit: {}\nBranch: {}\n\n",
+            self.job_id, self.repo_url, self.commit_sha, self.branch
+        );
+
+        out.push_str("| Task | Runs | Control tool calls | FMM tool calls | Reduction | Significance |\n");
+        out.push_str("|------|------|---------------------|-----------------|-----------|--------------|\n");
+
+        for comparison in &self.tasks {
+            out.push_str(&format!(
+                "| {} | {} | {:.1} ± {:.1} | {:.1} ± {:.1} | {:.1}% | {} |\n",
+                comparison.task.name,
+                comparison.tool_calls.control.n,
+                comparison.tool_calls.control.mean,
+                comparison.tool_calls.control.stddev,
+                comparison.tool_calls.fmm.mean,
+                comparison.tool_calls.fmm.stddev,
+                comparison.tool_calls.reduction_pct,
+                significance_label(comparison.tool_calls.significance),
+            ));
+        }
+
+        if self.tasks.iter().any(|t| !t.rag_runs.is_empty()) {
+            out.push_str("\n| Task | Runs | RAG tool calls | Reduction vs RAG | Significance |\n");
+            out.push_str("|------|------|-----------------|-------------------|--------------|\n");
+            for comparison in &self.tasks {
+                if comparison.rag_runs.is_empty() {
+                    continue;
+                }
+                out.push_str(&format!(
+                    "| {} | {} | {:.1} ± {:.1} | {:.1}% | {} |\n",
+                    comparison.task.name,
+                    comparison.rag_tool_calls.fmm.n,
+                    comparison.rag_tool_calls.fmm.mean,
+                    comparison.rag_tool_calls.fmm.stddev,
+                    comparison.rag_tool_calls.reduction_pct,
+                    significance_label(comparison.rag_tool_calls.significance),
+                ));
+            }
+        }
+
+        out
+    }
+
+    /// JUnit XML with one `testsuite` (this job) containing one `testcase`
+    /// per task, named `task.id`. A testcase fails if the FMM variant
+    /// crashed on any run or the measured tool-call reduction is below
+    /// `min_reduction`, so a CI step that already understands JUnit (GitHub
+    /// Actions' test-report action, GitLab, `cargo-nextest` consumers) can
+    /// gate on `fmm compare` the same way it gates on a test suite.
+    fn to_junit(&self, min_reduction: f64) -> String {
+        let total_cost: f64 = self
+            .tasks
+            .iter()
+            .flat_map(|t| t.control_runs.iter().chain(&t.fmm_runs))
+            .map(|r| r.total_cost_usd)
+            .sum();
+        let failures = self
+            .tasks
+            .iter()
+            .filter(|t| task_gate(t, min_reduction).is_some())
+            .count();
+
+        let mut out = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuite name=\"fmm-compare\" tests=\"{}\" failures=\"{}\" id=\"{}\" commit=\"{}\" branch=\"{}\" cost_usd=\"{:.4}\">\n",
+            self.tasks.len(),
+            failures,
+            escape_xml(&self.job_id),
+            escape_xml(&self.commit_sha),
+            escape_xml(&self.branch),
+            total_cost,
+        );
+
+        for task in &self.tasks {
+            out.push_str(&format!(
+                "  <testcase name=\"{}\" classname=\"fmm-compare\">\n",
+                escape_xml(&task.task.id)
+            ));
+            out.push_str("    <properties>\n");
+            out.push_str(&format!(
+                "      <property name=\"tool_call_reduction_pct\" value=\"{:.2}\"/>\n",
+                task.tool_calls.reduction_pct
+            ));
+            out.push_str(&format!(
+                "      <property name=\"cost_reduction_pct\" value=\"{:.2}\"/>\n",
+                task.cost_usd.reduction_pct
+            ));
+            out.push_str("    </properties>\n");
+
+            if let Some(message) = task_gate(task, min_reduction) {
+                out.push_str(&format!(
+                    "    <failure message=\"{}\"/>\n",
+                    escape_xml(&message)
+                ));
+            }
+
+            out.push_str("  </testcase>\n");
+        }
+
+        out.push_str("</testsuite>\n");
+        out
+    }
+
+    /// One compact JSON object per task, newline-delimited, for CI steps
+    /// that would rather `jq` a summary than parse JUnit XML. Carries the
+    /// same pass/fail verdict as [`ComparisonReport::to_junit`].
+    fn to_jsonl(&self, min_reduction: f64) -> String {
+        let mut out = String::new();
+        for task in &self.tasks {
+            let gate = task_gate(task, min_reduction);
+            let line = serde_json::json!({
+                "job_id": self.job_id,
+                "commit_sha": self.commit_sha,
+                "branch": self.branch,
+                "task_id": task.task.id,
+                "tool_call_reduction_pct": task.tool_calls.reduction_pct,
+                "cost_reduction_pct": task.cost_usd.reduction_pct,
+                "cost_usd": task.fmm_runs.iter().map(|r| r.total_cost_usd).sum::<f64>(),
+                "passed": gate.is_none(),
+                "failure_reason": gate,
+            });
+            out.push_str(&line.to_string());
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// `None` if `task` passes the CI gate, otherwise `Some(reason)`: the FMM
+/// variant crashed on at least one run, or the tool-call reduction fell
+/// below `min_reduction`.
+fn task_gate(task: &TaskComparison, min_reduction: f64) -> Option<String> {
+    if let Some(crashed) = task.fmm_runs.iter().find(|r| !r.success) {
+        return Some(format!(
+            "fmm variant run failed: {}",
+            crashed
+                .error
+                .clone()
+                .unwrap_or_else(|| "no error message captured".to_string())
+        ));
+    }
+
+    if task.tool_calls.reduction_pct < min_reduction {
+        return Some(format!(
+            "tool-call reduction {:.1}% fell below --min-reduction {:.1}%",
+            task.tool_calls.reduction_pct, min_reduction
+        ));
+    }
+
+    None
+}
+
+/// Minimal XML text escaping for attribute/element content in [`ComparisonReport::to_junit`].
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn significance_label(significance: Significance) -> &'static str {
+    match significance {
+        Significance::Significant => "significant",
+        Significance::NotSignificant => "not significant",
+        Significance::InsufficientData => "no stats (single run)",
+    }
+}
@@ -3,10 +3,16 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read as _};
 use std::path::Path;
-use std::process::Command;
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
+use super::cache::{config_digest, CacheKey};
+use super::isolation;
+use super::sandbox::ResourceLimits;
 use super::tasks::Task;
 
 /// Result of a single benchmark run
@@ -24,6 +30,17 @@ pub struct RunResult {
     pub files_accessed: Vec<String>,
     /// Number of read calls
     pub read_calls: u32,
+    /// Tool calls whose matching `tool_result` came back with `is_error`
+    /// set, paired up by `tool_use_id` (see [`StreamState::process_line`]).
+    pub failed_tool_calls: u32,
+    /// Read/View calls whose normalized path had already been read earlier
+    /// in the same run — repeated exploration FMM's injected context is
+    /// meant to make unnecessary.
+    pub redundant_reads: u32,
+    /// Per-tool count of calls whose `tool_result` came back with
+    /// `is_error` set, keyed the same way as `tools_by_name` so a caller
+    /// can divide the two for a per-tool success rate.
+    pub tool_failures_by_name: HashMap<String, u32>,
     /// Input tokens used
     pub input_tokens: u64,
     /// Output tokens used
@@ -44,6 +61,31 @@ pub struct RunResult {
     pub error: Option<String>,
 }
 
+/// A live progress event emitted while streaming `stream-json` output from
+/// the `claude` CLI, as each line arrives rather than after the process
+/// exits. Lets a long-running benchmark show progress (or decide to bail
+/// early) instead of waiting in silence for the whole run to finish.
+#[derive(Debug, Clone)]
+pub enum RunEvent {
+    /// A tool call started; `index` is the 1-based count of tool calls
+    /// seen so far this run. `detail` is the tool's primary argument
+    /// (e.g. the path for `Read`), when present.
+    ToolCallStarted {
+        index: u32,
+        name: String,
+        detail: Option<String>,
+    },
+    /// A chunk of assistant response text arrived.
+    Text(String),
+    /// The terminal `result` event arrived, with running totals.
+    Usage {
+        input_tokens: u64,
+        output_tokens: u64,
+        total_cost_usd: f64,
+        num_turns: u32,
+    },
+}
+
 /// Metrics collected during a run
 #[derive(Debug, Default)]
 pub struct RunMetrics {
@@ -51,6 +93,9 @@ pub struct RunMetrics {
     pub tools_by_name: HashMap<String, u32>,
     pub files_accessed: Vec<String>,
     pub read_calls: u32,
+    pub failed_tool_calls: u32,
+    pub redundant_reads: u32,
+    pub tool_failures_by_name: HashMap<String, u32>,
     pub input_tokens: u64,
     pub output_tokens: u64,
     pub cache_read_tokens: u64,
@@ -60,6 +105,7 @@ pub struct RunMetrics {
 }
 
 /// Claude CLI runner with instrumentation
+#[derive(Clone)]
 pub struct ClaudeRunner {
     /// Allowed tools (empty = all)
     allowed_tools: Vec<String>,
@@ -67,6 +113,14 @@ pub struct ClaudeRunner {
     model: String,
     /// Whether to skip permissions (for sandboxed environments)
     skip_permissions: bool,
+    /// Wall-clock timeout and optional memory cap applied to every run via
+    /// `compare::isolation`.
+    limits: ResourceLimits,
+    /// In-process wall-clock timeout enforced by a watcher thread that
+    /// kills the child directly, independent of (and a fallback for) the
+    /// external `timeout` wrapper applied by `compare::isolation`. `None`
+    /// means no in-process timeout is enforced.
+    timeout: Option<Duration>,
 }
 
 impl Default for ClaudeRunner {
@@ -86,6 +140,8 @@ impl ClaudeRunner {
             ],
             model: "sonnet".to_string(),
             skip_permissions: false,
+            limits: ResourceLimits::default(),
+            timeout: None,
         }
     }
 
@@ -110,13 +166,73 @@ impl ClaudeRunner {
         self
     }
 
-    /// Run a task and collect metrics
+    /// Set the wall-clock timeout and optional memory cap enforced on every
+    /// run (see `compare::isolation`).
+    #[allow(dead_code)]
+    pub fn with_limits(mut self, limits: ResourceLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Enforce `timeout` as an in-process wall-clock cap: a watcher thread
+    /// kills the child directly once it elapses, rather than relying on
+    /// the external `timeout` binary `compare::isolation` shells out to.
+    /// Useful on hosts without `timeout` on `PATH`, or in tests.
+    #[allow(dead_code)]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Build the cache key for a run of `task`, folding this runner's
+    /// model/allowed-tools/skip-permissions configuration and the injected
+    /// FMM context into the key so a differently-configured runner never
+    /// gets served a stale `RunResult` from this one.
+    pub fn cache_key(
+        &self,
+        repo_url: &str,
+        commit_sha: &str,
+        task: &Task,
+        variant: &str,
+        fmm_context: Option<&str>,
+    ) -> CacheKey {
+        let digest = config_digest(
+            &self.model,
+            &self.allowed_tools,
+            self.skip_permissions,
+            fmm_context,
+            task.max_turns,
+            task.max_budget_usd,
+        );
+        CacheKey::with_config(repo_url, commit_sha, &task.id, variant, &digest)
+    }
+
+    /// Run a task and collect metrics, discarding live progress events.
+    /// Equivalent to [`ClaudeRunner::run_task_with_progress`] with a no-op
+    /// callback.
     pub fn run_task(
         &self,
         task: &Task,
         working_dir: &Path,
         variant: &str,
         fmm_context: Option<&str>,
+    ) -> Result<RunResult> {
+        self.run_task_with_progress(task, working_dir, variant, fmm_context, |_| {})
+    }
+
+    /// Run a task, streaming `stream-json` output from `claude` line by
+    /// line as it arrives instead of waiting for the process to exit.
+    /// Each tool call, text chunk, and the terminal usage/result event is
+    /// fed into `on_event` as it's parsed, so a long-running benchmark can
+    /// show live progress (or bail early) rather than seeing nothing until
+    /// the whole run completes.
+    pub fn run_task_with_progress(
+        &self,
+        task: &Task,
+        working_dir: &Path,
+        variant: &str,
+        fmm_context: Option<&str>,
+        mut on_event: impl FnMut(&RunEvent),
     ) -> Result<RunResult> {
         let start = Instant::now();
 
@@ -155,19 +271,72 @@ impl ClaudeRunner {
         // Session handling
         cmd.arg("--no-session-persistence");
 
+        // Confine the agent to a new mount/PID/network namespace with a
+        // wall-clock kill (and, if configured, a memory cap) so a
+        // misbehaving run against an untrusted clone can't touch the host
+        // or hang the whole comparison. No-op off Linux or without bwrap.
+        isolation::isolate(&mut cmd, working_dir, &self.limits);
+
         // Working directory
         cmd.current_dir(working_dir);
 
-        // Execute
-        let output = cmd.output().context("Failed to execute claude CLI")?;
+        cmd.stdout(Stdio::piped());
+        cmd.stderr(Stdio::piped());
+
+        let child = cmd.spawn().context("Failed to spawn claude CLI")?;
+        let child = Arc::new(Mutex::new(child));
+        let stdout = child
+            .lock()
+            .unwrap()
+            .stdout
+            .take()
+            .expect("stdout was piped");
+
+        let watcher = self
+            .timeout
+            .map(|timeout| TimeoutWatcher::spawn(Arc::clone(&child), timeout));
+
+        let mut state = StreamState::default();
+        for line in BufReader::new(stdout).lines() {
+            let Ok(line) = line else { break };
+            if line.trim().is_empty() {
+                continue;
+            }
+            state.process_line(&line, &mut on_event);
+        }
 
+        let mut stderr_text = String::new();
+        if let Some(mut stderr) = child.lock().unwrap().stderr.take() {
+            let _ = stderr.read_to_string(&mut stderr_text);
+        }
+        let status = wait_for_exit(&child).context("Failed waiting on claude CLI")?;
         let duration = start.elapsed();
+        let timed_out = watcher.is_some_and(|w| w.fired());
 
-        // Parse the output
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let stderr = String::from_utf8_lossy(&output.stderr);
+        if timed_out {
+            return Ok(RunResult {
+                task_id: task.id.clone(),
+                variant: variant.to_string(),
+                tool_calls: state.metrics.tool_calls,
+                tools_by_name: state.metrics.tools_by_name,
+                files_accessed: state.metrics.files_accessed,
+                read_calls: state.metrics.read_calls,
+                failed_tool_calls: state.metrics.failed_tool_calls,
+                redundant_reads: state.metrics.redundant_reads,
+                tool_failures_by_name: state.metrics.tool_failures_by_name,
+                input_tokens: state.metrics.input_tokens,
+                output_tokens: state.metrics.output_tokens,
+                cache_read_tokens: state.metrics.cache_read_tokens,
+                total_cost_usd: state.metrics.total_cost_usd,
+                duration_ms: duration.as_millis() as u64,
+                num_turns: state.metrics.num_turns,
+                response: state.response_text,
+                success: false,
+                error: Some("timeout".to_string()),
+            });
+        }
 
-        if !output.status.success() && stdout.is_empty() {
+        if !status.success() && state.final_result.is_none() {
             return Ok(RunResult {
                 task_id: task.id.clone(),
                 variant: variant.to_string(),
@@ -175,6 +344,9 @@ impl ClaudeRunner {
                 tools_by_name: HashMap::new(),
                 files_accessed: vec![],
                 read_calls: 0,
+                failed_tool_calls: 0,
+                redundant_reads: 0,
+                tool_failures_by_name: HashMap::new(),
                 input_tokens: 0,
                 output_tokens: 0,
                 cache_read_tokens: 0,
@@ -183,14 +355,90 @@ impl ClaudeRunner {
                 num_turns: 0,
                 response: String::new(),
                 success: false,
-                error: Some(stderr.to_string()),
+                error: Some(stderr_text),
             });
         }
 
-        // Parse stream-json output
-        self.parse_stream_json(&stdout, &task.id, variant, duration)
+        Ok(state.into_result(&task.id, variant, duration))
     }
 
+    /// Run every `task` × `variant` combination in `working_dir` across a
+    /// fixed-size worker pool instead of strictly sequentially, so a suite
+    /// of N tasks × M variants doesn't spawn `claude` processes one at a
+    /// time. The pool is sized to `jobs`, capped by the number of available
+    /// CPUs, and dispatch uses the same jobserver-style token channel as
+    /// `Orchestrator::run`'s `TokenPool`: each in-flight run holds a token
+    /// for its duration so at most that many run at once. Results are
+    /// collected into their `(task, variant)` slot so the returned order
+    /// matches `tasks`/`variants` regardless of completion order, and a
+    /// run that errors (rather than completing with `success: false`) is
+    /// turned into a failed `RunResult` carrying the error text.
+    pub fn run_suite(
+        &self,
+        tasks: &[Task],
+        working_dir: &Path,
+        variants: &[&str],
+        jobs: usize,
+    ) -> Vec<RunResult> {
+        let available = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+        let pool = SuitePool::new(jobs.max(1).min(available));
+
+        let slots: Mutex<Vec<Option<RunResult>>> =
+            Mutex::new(vec![None; tasks.len() * variants.len()]);
+
+        std::thread::scope(|scope| {
+            for (task_index, task) in tasks.iter().enumerate() {
+                for (variant_index, variant) in variants.iter().enumerate() {
+                    let pool = &pool;
+                    let slots = &slots;
+                    let this = &*self;
+                    scope.spawn(move || {
+                        let _permit = pool.acquire();
+                        let result = this
+                            .run_task(task, working_dir, variant, None)
+                            .unwrap_or_else(|err| RunResult {
+                                task_id: task.id.clone(),
+                                variant: variant.to_string(),
+                                tool_calls: 0,
+                                tools_by_name: HashMap::new(),
+                                files_accessed: vec![],
+                                read_calls: 0,
+                                failed_tool_calls: 0,
+                                redundant_reads: 0,
+                                tool_failures_by_name: HashMap::new(),
+                                input_tokens: 0,
+                                output_tokens: 0,
+                                cache_read_tokens: 0,
+                                total_cost_usd: 0.0,
+                                duration_ms: 0,
+                                num_turns: 0,
+                                response: String::new(),
+                                success: false,
+                                error: Some(err.to_string()),
+                            });
+                        let slot_index = task_index * variants.len() + variant_index;
+                        slots.lock().unwrap()[slot_index] = Some(result);
+                    });
+                }
+            }
+        });
+
+        slots
+            .into_inner()
+            .unwrap()
+            .into_iter()
+            .map(|slot| slot.expect("every (task, variant) slot was filled by the pool"))
+            .collect()
+    }
+
+    /// Parse a complete `stream-json` transcript in one pass, for callers
+    /// that already have the full buffered output (tests, or a cached
+    /// `claude` invocation replayed from a log). Runs through the same
+    /// per-line state machine [`ClaudeRunner::run_task_with_progress`]
+    /// streams through, just without a live callback.
+    #[allow(dead_code)]
     fn parse_stream_json(
         &self,
         output: &str,
@@ -198,100 +446,277 @@ impl ClaudeRunner {
         variant: &str,
         duration: Duration,
     ) -> Result<RunResult> {
-        let mut metrics = RunMetrics::default();
-        let mut response_text = String::new();
-        let mut final_result: Option<serde_json::Value> = None;
-
+        let mut state = StreamState::default();
         for line in output.lines() {
             if line.trim().is_empty() {
                 continue;
             }
+            state.process_line(line, &mut |_: &RunEvent| {});
+        }
+        Ok(state.into_result(task_id, variant, duration))
+    }
+}
+
+/// Jobserver-style concurrency limiter for [`ClaudeRunner::run_suite`]:
+/// `capacity` tokens are pushed into a bounded channel up front, and each
+/// in-flight run holds one for its duration via [`SuitePool::acquire`].
+/// Mirrors `Orchestrator`'s `TokenPool`, kept separate since the two pools
+/// dispatch onto different units of work (task/run/variant jobs vs. a flat
+/// task × variant suite).
+struct SuitePool {
+    tokens: Receiver<()>,
+    release: SyncSender<()>,
+}
+
+impl SuitePool {
+    fn new(capacity: usize) -> Self {
+        let (release, tokens) = sync_channel(capacity);
+        for _ in 0..capacity {
+            release
+                .send(())
+                .expect("channel just created with this capacity, so it can't be full");
+        }
+        Self { tokens, release }
+    }
+
+    /// Block until a token is available, returning a guard that releases it
+    /// back to the pool when dropped.
+    fn acquire(&self) -> SuitePermit<'_> {
+        self.tokens
+            .recv()
+            .expect("release sender is held by self for the pool's whole lifetime");
+        SuitePermit {
+            release: &self.release,
+        }
+    }
+}
 
-            let data: serde_json::Value = match serde_json::from_str(line) {
-                Ok(v) => v,
-                Err(_) => continue,
-            };
-
-            match data.get("type").and_then(|v| v.as_str()) {
-                Some("assistant") => {
-                    // Parse tool calls from assistant message
-                    if let Some(message) = data.get("message") {
-                        if let Some(content) = message.get("content").and_then(|c| c.as_array()) {
-                            for item in content {
-                                if item.get("type").and_then(|t| t.as_str()) == Some("tool_use") {
-                                    metrics.tool_calls += 1;
-
-                                    if let Some(name) = item.get("name").and_then(|n| n.as_str()) {
-                                        *metrics
-                                            .tools_by_name
-                                            .entry(name.to_string())
-                                            .or_insert(0) += 1;
-
-                                        // Track Read calls specifically
-                                        if name == "Read" || name == "View" {
-                                            metrics.read_calls += 1;
-                                            if let Some(input) = item.get("input") {
-                                                if let Some(path) = input
-                                                    .get("file_path")
-                                                    .or(input.get("path"))
-                                                    .and_then(|p| p.as_str())
-                                                {
-                                                    metrics.files_accessed.push(path.to_string());
-                                                }
+struct SuitePermit<'a> {
+    release: &'a SyncSender<()>,
+}
+
+impl Drop for SuitePermit<'_> {
+    fn drop(&mut self) {
+        let _ = self.release.send(());
+    }
+}
+
+/// Polls `child` for exit instead of calling the blocking `Child::wait`
+/// directly, so the lock is only ever held briefly: a blocking `wait()`
+/// would hold the mutex for the process's entire remaining lifetime,
+/// starving [`TimeoutWatcher`] of the lock it needs to kill a hung child.
+fn wait_for_exit(child: &Arc<Mutex<Child>>) -> std::io::Result<std::process::ExitStatus> {
+    loop {
+        if let Some(status) = child.lock().unwrap().try_wait()? {
+            return Ok(status);
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    }
+}
+
+/// Kills a shared child process if it's still running once `timeout`
+/// elapses, so a hung `claude` run can't stall `run_task_with_progress`
+/// indefinitely even when the external `timeout` wrapper from
+/// `compare::isolation` isn't available (e.g. off Linux, or in tests).
+/// Runs on its own thread since the main thread is blocked reading the
+/// child's stdout line by line.
+struct TimeoutWatcher {
+    fired: Arc<Mutex<bool>>,
+}
+
+impl TimeoutWatcher {
+    fn spawn(child: Arc<Mutex<Child>>, timeout: Duration) -> Self {
+        let fired = Arc::new(Mutex::new(false));
+        let fired_clone = Arc::clone(&fired);
+        std::thread::spawn(move || {
+            std::thread::sleep(timeout);
+            let mut child = child.lock().unwrap();
+            if child.try_wait().ok().flatten().is_none() {
+                let _ = child.kill();
+                *fired_clone.lock().unwrap() = true;
+            }
+        });
+        Self { fired }
+    }
+
+    /// Whether the watcher killed the child for exceeding the timeout.
+    /// Only meaningful to call after the caller's own `wait()` returns,
+    /// since the watcher thread may still be asleep until then.
+    fn fired(&self) -> bool {
+        *self.fired.lock().unwrap()
+    }
+}
+
+/// Per-line state accumulated while processing a `stream-json` transcript,
+/// shared by the streaming (`run_task_with_progress`) and batch
+/// (`parse_stream_json`) entry points.
+#[derive(Default)]
+struct StreamState {
+    metrics: RunMetrics,
+    response_text: String,
+    final_result: Option<serde_json::Value>,
+    /// `tool_use` calls awaiting their matching `tool_result`, keyed by
+    /// `tool_use_id`, so a later `user`/`tool_result` message can be
+    /// attributed back to the tool that made the call.
+    pending_tool_uses: HashMap<String, String>,
+    /// Normalized paths already seen via a `Read`/`View` call this run, to
+    /// detect the model re-reading a file it already read.
+    seen_read_paths: std::collections::HashSet<String>,
+}
+
+impl StreamState {
+    fn process_line(&mut self, line: &str, on_event: &mut dyn FnMut(&RunEvent)) {
+        let data: serde_json::Value = match serde_json::from_str(line) {
+            Ok(v) => v,
+            Err(_) => return,
+        };
+
+        match data.get("type").and_then(|v| v.as_str()) {
+            Some("assistant") => {
+                // Parse tool calls from assistant message
+                if let Some(message) = data.get("message") {
+                    if let Some(content) = message.get("content").and_then(|c| c.as_array()) {
+                        for item in content {
+                            if item.get("type").and_then(|t| t.as_str()) == Some("tool_use") {
+                                self.metrics.tool_calls += 1;
+
+                                if let Some(name) = item.get("name").and_then(|n| n.as_str()) {
+                                    *self
+                                        .metrics
+                                        .tools_by_name
+                                        .entry(name.to_string())
+                                        .or_insert(0) += 1;
+
+                                    if let Some(id) = item.get("id").and_then(|i| i.as_str()) {
+                                        self.pending_tool_uses
+                                            .insert(id.to_string(), name.to_string());
+                                    }
+
+                                    let detail = item.get("input").and_then(|input| {
+                                        input
+                                            .get("file_path")
+                                            .or(input.get("path"))
+                                            .and_then(|p| p.as_str())
+                                            .map(|p| p.to_string())
+                                    });
+
+                                    // Track Read calls specifically
+                                    if name == "Read" || name == "View" {
+                                        self.metrics.read_calls += 1;
+                                        if let Some(path) = &detail {
+                                            self.metrics.files_accessed.push(path.clone());
+                                            let normalized =
+                                                path.strip_prefix("./").unwrap_or(path).to_string();
+                                            if !self.seen_read_paths.insert(normalized) {
+                                                self.metrics.redundant_reads += 1;
                                             }
                                         }
                                     }
-                                } else if item.get("type").and_then(|t| t.as_str()) == Some("text")
-                                {
-                                    if let Some(text) = item.get("text").and_then(|t| t.as_str()) {
-                                        response_text = text.to_string();
-                                    }
+
+                                    on_event(&RunEvent::ToolCallStarted {
+                                        index: self.metrics.tool_calls,
+                                        name: name.to_string(),
+                                        detail,
+                                    });
+                                }
+                            } else if item.get("type").and_then(|t| t.as_str()) == Some("text") {
+                                if let Some(text) = item.get("text").and_then(|t| t.as_str()) {
+                                    self.response_text = text.to_string();
+                                    on_event(&RunEvent::Text(text.to_string()));
                                 }
                             }
                         }
                     }
                 }
-                Some("result") => {
-                    final_result = Some(data.clone());
-
-                    // Extract metrics from result
-                    if let Some(usage) = data.get("usage") {
-                        metrics.input_tokens = usage
-                            .get("input_tokens")
-                            .and_then(|v| v.as_u64())
-                            .unwrap_or(0);
-                        metrics.output_tokens = usage
-                            .get("output_tokens")
-                            .and_then(|v| v.as_u64())
-                            .unwrap_or(0);
-                        metrics.cache_read_tokens = usage
-                            .get("cache_read_input_tokens")
-                            .and_then(|v| v.as_u64())
-                            .unwrap_or(0);
+            }
+            Some("user") => {
+                // Pair each `tool_result` with the `tool_use` it answers so
+                // a failed call can be attributed back to its tool name.
+                if let Some(content) = data
+                    .get("message")
+                    .and_then(|m| m.get("content"))
+                    .and_then(|c| c.as_array())
+                {
+                    for item in content {
+                        if item.get("type").and_then(|t| t.as_str()) != Some("tool_result") {
+                            continue;
+                        }
+                        let Some(tool_use_id) =
+                            item.get("tool_use_id").and_then(|id| id.as_str())
+                        else {
+                            continue;
+                        };
+                        let Some(name) = self.pending_tool_uses.remove(tool_use_id) else {
+                            continue;
+                        };
+                        let is_error = item
+                            .get("is_error")
+                            .and_then(|e| e.as_bool())
+                            .unwrap_or(false);
+                        if is_error {
+                            self.metrics.failed_tool_calls += 1;
+                            *self
+                                .metrics
+                                .tool_failures_by_name
+                                .entry(name)
+                                .or_insert(0) += 1;
+                        }
                     }
+                }
+            }
+            Some("result") => {
+                self.final_result = Some(data.clone());
 
-                    metrics.total_cost_usd = data
-                        .get("total_cost_usd")
-                        .and_then(|v| v.as_f64())
-                        .unwrap_or(0.0);
-                    metrics.num_turns =
-                        data.get("num_turns").and_then(|v| v.as_u64()).unwrap_or(0) as u32;
-                    metrics.duration_ms = data
-                        .get("duration_ms")
+                // Extract metrics from result
+                if let Some(usage) = data.get("usage") {
+                    self.metrics.input_tokens = usage
+                        .get("input_tokens")
+                        .and_then(|v| v.as_u64())
+                        .unwrap_or(0);
+                    self.metrics.output_tokens = usage
+                        .get("output_tokens")
                         .and_then(|v| v.as_u64())
-                        .unwrap_or(duration.as_millis() as u64);
+                        .unwrap_or(0);
+                    self.metrics.cache_read_tokens = usage
+                        .get("cache_read_input_tokens")
+                        .and_then(|v| v.as_u64())
+                        .unwrap_or(0);
+                }
 
-                    if let Some(result_text) = data.get("result").and_then(|r| r.as_str()) {
-                        if response_text.is_empty() {
-                            response_text = result_text.to_string();
-                        }
+                self.metrics.total_cost_usd = data
+                    .get("total_cost_usd")
+                    .and_then(|v| v.as_f64())
+                    .unwrap_or(0.0);
+                self.metrics.num_turns =
+                    data.get("num_turns").and_then(|v| v.as_u64()).unwrap_or(0) as u32;
+
+                if let Some(result_text) = data.get("result").and_then(|r| r.as_str()) {
+                    if self.response_text.is_empty() {
+                        self.response_text = result_text.to_string();
                     }
                 }
-                _ => {}
+
+                on_event(&RunEvent::Usage {
+                    input_tokens: self.metrics.input_tokens,
+                    output_tokens: self.metrics.output_tokens,
+                    total_cost_usd: self.metrics.total_cost_usd,
+                    num_turns: self.metrics.num_turns,
+                });
             }
+            _ => {}
         }
+    }
 
-        let success = final_result
+    fn into_result(mut self, task_id: &str, variant: &str, duration: Duration) -> RunResult {
+        self.metrics.duration_ms = self
+            .final_result
+            .as_ref()
+            .and_then(|r| r.get("duration_ms"))
+            .and_then(|v| v.as_u64())
+            .unwrap_or(duration.as_millis() as u64);
+
+        let success = self
+            .final_result
             .as_ref()
             .and_then(|r| r.get("is_error"))
             .and_then(|e| e.as_bool())
@@ -299,7 +724,7 @@ impl ClaudeRunner {
             .unwrap_or(false);
 
         let error = if !success {
-            final_result
+            self.final_result
                 .as_ref()
                 .and_then(|r| r.get("subtype"))
                 .and_then(|s| s.as_str())
@@ -308,23 +733,26 @@ impl ClaudeRunner {
             None
         };
 
-        Ok(RunResult {
+        RunResult {
             task_id: task_id.to_string(),
             variant: variant.to_string(),
-            tool_calls: metrics.tool_calls,
-            tools_by_name: metrics.tools_by_name,
-            files_accessed: metrics.files_accessed,
-            read_calls: metrics.read_calls,
-            input_tokens: metrics.input_tokens,
-            output_tokens: metrics.output_tokens,
-            cache_read_tokens: metrics.cache_read_tokens,
-            total_cost_usd: metrics.total_cost_usd,
-            duration_ms: metrics.duration_ms,
-            num_turns: metrics.num_turns,
-            response: response_text,
+            tool_calls: self.metrics.tool_calls,
+            tools_by_name: self.metrics.tools_by_name,
+            files_accessed: self.metrics.files_accessed,
+            read_calls: self.metrics.read_calls,
+            failed_tool_calls: self.metrics.failed_tool_calls,
+            redundant_reads: self.metrics.redundant_reads,
+            tool_failures_by_name: self.metrics.tool_failures_by_name,
+            input_tokens: self.metrics.input_tokens,
+            output_tokens: self.metrics.output_tokens,
+            cache_read_tokens: self.metrics.cache_read_tokens,
+            total_cost_usd: self.metrics.total_cost_usd,
+            duration_ms: self.metrics.duration_ms,
+            num_turns: self.metrics.num_turns,
+            response: self.response_text,
             success,
             error,
-        })
+        }
     }
 }
 
@@ -337,4 +765,165 @@ mod tests {
         let runner = ClaudeRunner::new();
         assert!(!runner.allowed_tools.is_empty());
     }
+
+    #[test]
+    fn run_suite_returns_one_slot_per_task_variant_pair_in_order() {
+        let runner = ClaudeRunner::new();
+        let tasks = vec![
+            Task {
+                id: "task-a".to_string(),
+                name: "Task A".to_string(),
+                prompt: "a".to_string(),
+                max_turns: 1,
+                max_budget_usd: 0.1,
+            },
+            Task {
+                id: "task-b".to_string(),
+                name: "Task B".to_string(),
+                prompt: "b".to_string(),
+                max_turns: 1,
+                max_budget_usd: 0.1,
+            },
+        ];
+
+        // No `claude` binary in the test environment, so every run fails
+        // to spawn; run_suite should still return a deterministically
+        // ordered, fully-populated slot per (task, variant) rather than
+        // panicking or dropping results.
+        let results = runner.run_suite(&tasks, Path::new("."), &["control", "fmm"], 2);
+
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[0].task_id, "task-a");
+        assert_eq!(results[0].variant, "control");
+        assert_eq!(results[1].task_id, "task-a");
+        assert_eq!(results[1].variant, "fmm");
+        assert_eq!(results[2].task_id, "task-b");
+        assert_eq!(results[2].variant, "control");
+        assert_eq!(results[3].task_id, "task-b");
+        assert_eq!(results[3].variant, "fmm");
+        assert!(results.iter().all(|r| !r.success));
+    }
+
+    #[test]
+    fn parse_stream_json_success() {
+        let runner = ClaudeRunner::new();
+        let output = r#"{"type":"assistant","message":{"content":[{"type":"tool_use","name":"Read","input":{"file_path":"src/main.rs"}},{"type":"text","text":"Looked at main.rs"}]}}
+{"type":"result","is_error":false,"result":"Done","total_cost_usd":0.05,"num_turns":3,"usage":{"input_tokens":1000,"output_tokens":500},"duration_ms":5000}"#;
+
+        let result = runner
+            .parse_stream_json(output, "task1", "control", Duration::from_millis(0))
+            .unwrap();
+
+        assert!(result.success);
+        assert_eq!(result.tool_calls, 1);
+        assert_eq!(result.read_calls, 1);
+        assert_eq!(result.files_accessed, vec!["src/main.rs".to_string()]);
+        assert_eq!(result.response, "Looked at main.rs");
+        assert_eq!(result.duration_ms, 5000);
+        assert_eq!(result.failed_tool_calls, 0);
+        assert_eq!(result.redundant_reads, 0);
+    }
+
+    #[test]
+    fn parse_stream_json_pairs_tool_use_with_tool_result() {
+        let runner = ClaudeRunner::new();
+        let output = r#"{"type":"assistant","message":{"content":[{"type":"tool_use","id":"toolu_1","name":"Bash","input":{"command":"cargo build"}}]}}
+{"type":"user","message":{"content":[{"type":"tool_result","tool_use_id":"toolu_1","is_error":true,"content":"error: could not compile"}]}}
+{"type":"result","is_error":false,"total_cost_usd":0.02,"num_turns":2,"usage":{"input_tokens":10,"output_tokens":5},"duration_ms":100}"#;
+
+        let result = runner
+            .parse_stream_json(output, "task1", "control", Duration::from_millis(0))
+            .unwrap();
+
+        assert_eq!(result.failed_tool_calls, 1);
+        assert_eq!(result.tool_failures_by_name.get("Bash"), Some(&1));
+    }
+
+    #[test]
+    fn parse_stream_json_counts_redundant_reads() {
+        let runner = ClaudeRunner::new();
+        let output = r#"{"type":"assistant","message":{"content":[{"type":"tool_use","id":"toolu_1","name":"Read","input":{"file_path":"./src/main.rs"}}]}}
+{"type":"user","message":{"content":[{"type":"tool_result","tool_use_id":"toolu_1","content":"fn main() {}"}]}}
+{"type":"assistant","message":{"content":[{"type":"tool_use","id":"toolu_2","name":"Read","input":{"file_path":"src/main.rs"}}]}}
+{"type":"user","message":{"content":[{"type":"tool_result","tool_use_id":"toolu_2","content":"fn main() {}"}]}}
+{"type":"result","is_error":false,"total_cost_usd":0.02,"num_turns":2,"usage":{"input_tokens":10,"output_tokens":5},"duration_ms":100}"#;
+
+        let result = runner
+            .parse_stream_json(output, "task1", "control", Duration::from_millis(0))
+            .unwrap();
+
+        assert_eq!(result.read_calls, 2);
+        assert_eq!(result.redundant_reads, 1);
+        assert_eq!(result.failed_tool_calls, 0);
+    }
+
+    #[test]
+    fn parse_stream_json_emits_events_in_order() {
+        let runner = ClaudeRunner::new();
+        let output = r#"{"type":"assistant","message":{"content":[{"type":"tool_use","name":"Grep","input":{"pattern":"foo"}}]}}
+{"type":"result","is_error":false,"total_cost_usd":0.01,"num_turns":1,"usage":{"input_tokens":10,"output_tokens":5},"duration_ms":100}"#;
+
+        let mut events = vec![];
+        let mut state = StreamState::default();
+        for line in output.lines() {
+            state.process_line(line, &mut |event| events.push(event.clone()));
+        }
+
+        assert_eq!(events.len(), 2);
+        match &events[0] {
+            RunEvent::ToolCallStarted { index, name, .. } => {
+                assert_eq!(*index, 1);
+                assert_eq!(name, "Grep");
+            }
+            other => panic!("expected ToolCallStarted, got {other:?}"),
+        }
+        match &events[1] {
+            RunEvent::Usage { num_turns, .. } => assert_eq!(*num_turns, 1),
+            other => panic!("expected Usage, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn timeout_watcher_kills_long_running_child() {
+        let child = Command::new("sh")
+            .arg("-c")
+            .arg("sleep 5")
+            .spawn()
+            .expect("failed to spawn sleep");
+        let child = Arc::new(Mutex::new(child));
+        let watcher = TimeoutWatcher::spawn(Arc::clone(&child), Duration::from_millis(50));
+
+        let status = wait_for_exit(&child).expect("failed to wait");
+
+        assert!(!status.success());
+        assert!(watcher.fired());
+    }
+
+    #[test]
+    fn timeout_watcher_does_not_fire_for_fast_child() {
+        let child = Command::new("sh")
+            .arg("-c")
+            .arg("true")
+            .spawn()
+            .expect("failed to spawn true");
+        let child = Arc::new(Mutex::new(child));
+        let watcher = TimeoutWatcher::spawn(Arc::clone(&child), Duration::from_secs(5));
+
+        let status = wait_for_exit(&child).expect("failed to wait");
+
+        assert!(status.success());
+        std::thread::sleep(Duration::from_millis(50));
+        assert!(!watcher.fired());
+    }
+
+    #[test]
+    fn parse_stream_json_malformed_lines_skipped() {
+        let runner = ClaudeRunner::new();
+        let output = "not json\n{broken\n{\"type\":\"result\",\"is_error\":false,\"total_cost_usd\":0.01,\"num_turns\":1,\"usage\":{\"input_tokens\":10,\"output_tokens\":5},\"duration_ms\":100}";
+        let result = runner
+            .parse_stream_json(output, "task1", "control", Duration::from_millis(100))
+            .unwrap();
+        assert!(result.success);
+        assert_eq!(result.num_turns, 1);
+    }
 }
@@ -6,6 +6,9 @@ use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::time::{Duration, Instant};
 
+use super::git_backend::{self, GitBackend};
+use crate::config::GlobalConfig;
+
 /// Sandbox for isolated repo comparison
 pub struct Sandbox {
     /// Root directory for this sandbox
@@ -14,12 +17,9 @@ pub struct Sandbox {
     pub control_dir: PathBuf,
     /// FMM variant directory (with manifest)
     pub fmm_dir: PathBuf,
-    /// Maximum size in MB
-    #[allow(dead_code)]
-    max_size_mb: u64,
-    /// Maximum number of files
-    #[allow(dead_code)]
-    max_files: u64,
+    /// Bounds enforced during `clone_repo`/`generate_fmm_manifest`; see
+    /// [`Sandbox::with_limits`].
+    limits: ResourceLimits,
     /// Creation time
     #[allow(dead_code)]
     created_at: Instant,
@@ -28,11 +28,14 @@ pub struct Sandbox {
     ttl: Duration,
     /// Whether to cleanup on drop
     cleanup_on_drop: bool,
+    /// `git` implementation for `clone_repo`/`get_commit_sha`, selected from
+    /// [`GlobalConfig::git_backend`] (shell `git` by default, or `gix` when
+    /// configured) — see `compare::git_backend::backend_for`.
+    git_backend: Box<dyn GitBackend>,
 }
 
 /// Resource limits for sandbox operations
 #[derive(Debug, Clone)]
-#[allow(dead_code)]
 pub struct ResourceLimits {
     /// Max repo size in MB
     pub max_repo_size_mb: u64,
@@ -48,6 +51,10 @@ pub struct ResourceLimits {
     pub max_total_api_cost: f64,
     /// Job timeout in seconds
     pub job_timeout_secs: u64,
+    /// Optional memory cap (in MB) for an agent run, enforced via a
+    /// `systemd-run` cgroup scope on Linux (see `compare::isolation`). `None`
+    /// means no cap.
+    pub max_memory_mb: Option<u64>,
 }
 
 impl Default for ResourceLimits {
@@ -60,13 +67,21 @@ impl Default for ResourceLimits {
             task_timeout_secs: 180,
             max_total_api_cost: 5.0,
             job_timeout_secs: 1800,
+            max_memory_mb: None,
         }
     }
 }
 
 impl Sandbox {
-    /// Create a new sandbox for a job
+    /// Create a new sandbox for a job, with default `ResourceLimits`.
     pub fn new(job_id: &str) -> Result<Self> {
+        Self::with_limits(job_id, ResourceLimits::default())
+    }
+
+    /// Create a new sandbox for a job, bounded by `limits` — lets callers
+    /// tighten the defaults (smaller `max_repo_size_mb`, shorter
+    /// `clone_timeout_secs`, ...) for repos they don't otherwise trust.
+    pub fn with_limits(job_id: &str, limits: ResourceLimits) -> Result<Self> {
         validate_job_id(job_id)?;
         let root = std::env::temp_dir().join(format!("fmm-compare-{}", job_id));
         fs::create_dir_all(&root).context("Failed to create sandbox root")?;
@@ -78,76 +93,109 @@ impl Sandbox {
             root,
             control_dir,
             fmm_dir,
-            max_size_mb: 500,
-            max_files: 10_000,
+            limits,
             created_at: Instant::now(),
             ttl: Duration::from_secs(3600),
             cleanup_on_drop: true,
+            git_backend: git_backend::backend_for(&GlobalConfig::load()),
         })
     }
 
-    /// Clone a repository into the sandbox
-    pub fn clone_repo(&self, url: &str, branch: Option<&str>) -> Result<()> {
+    /// Clone a repository into the sandbox. The control variant is always a
+    /// fresh clone; the FMM variant is produced from it via
+    /// `GitBackend::duplicate`, which lets a library backend reuse the
+    /// control clone's already-fetched objects instead of cloning the
+    /// remote twice. The clone, and a duplicate that clones again, are bound
+    /// by `limits.clone_timeout_secs` (a local copy is not); once
+    /// cloned, the sandbox is torn down and rejected if it exceeds
+    /// `limits.max_repo_size_mb` or `limits.max_files_to_parse`.
+    ///
+    /// `depth` of `Some(n)` requests a shallow, single-branch clone of the
+    /// last `n` commits (see `ShellGitBackend::clone`'s fallback to a full
+    /// clone if the remote rejects that); pass `None` for full history.
+    pub fn clone_repo(&self, url: &str, branch: Option<&str>, depth: Option<u32>) -> Result<()> {
         validate_repo_url(url)?;
-        // Clone for control variant
-        self.clone_to_dir(url, branch, &self.control_dir)?;
-
-        // Clone for FMM variant (or copy)
-        self.clone_to_dir(url, branch, &self.fmm_dir)?;
-
+        let timeout = Duration::from_secs(self.limits.clone_timeout_secs);
+        self.git_backend
+            .clone(url, branch, &self.control_dir, timeout, depth)
+            .with_context(|| format!("cloning {url} for the control variant"))?;
+        self.git_backend
+            .duplicate(&self.control_dir, url, branch, &self.fmm_dir, timeout, depth)
+            .with_context(|| format!("producing the FMM variant of {url}"))?;
+
+        if let Err(e) = self.check_limits() {
+            self.cleanup();
+            return Err(e);
+        }
         Ok(())
     }
 
-    fn clone_to_dir(&self, url: &str, branch: Option<&str>, dir: &Path) -> Result<()> {
-        let mut cmd = Command::new("git");
-        cmd.arg("clone")
+    /// Get the current commit SHA from a directory
+    pub fn get_commit_sha(&self, dir: &Path) -> Result<String> {
+        self.git_backend.current_sha(dir)
+    }
+
+    /// List files changed between `old_commit` and the current `HEAD` of
+    /// `dir`, for changed-files-aware incremental cache reuse (see
+    /// `CacheManager::find_reusable_result`). `clone_repo`'s shallow clone
+    /// has no history past `HEAD`, so `old_commit` is fetched first; this
+    /// fails (the caller falls back to running every task) if the git host
+    /// doesn't allow fetching an arbitrary SHA.
+    pub fn diff_against(&self, dir: &Path, old_commit: &str) -> Result<Vec<String>> {
+        let fetch = Command::new("git")
+            .arg("fetch")
             .arg("--depth")
             .arg("1")
-            .arg("--single-branch");
-
-        if let Some(b) = branch {
-            cmd.arg("--branch").arg(b);
-        }
-
-        cmd.arg(url).arg(dir);
-
-        let output = cmd.output().context("Failed to execute git clone")?;
+            .arg("origin")
+            .arg(old_commit)
+            .current_dir(dir)
+            .output()
+            .context("Failed to run git fetch")?;
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            anyhow::bail!("Git clone failed: {}", stderr);
+        if !fetch.status.success() {
+            anyhow::bail!(
+                "Could not fetch prior commit {old_commit} for incremental diff: {}",
+                String::from_utf8_lossy(&fetch.stderr)
+            );
         }
 
-        Ok(())
-    }
-
-    /// Get the current commit SHA from a directory
-    pub fn get_commit_sha(&self, dir: &Path) -> Result<String> {
-        let output = Command::new("git")
-            .arg("rev-parse")
+        let diff = Command::new("git")
+            .arg("diff")
+            .arg("--name-only")
+            .arg(old_commit)
             .arg("HEAD")
             .current_dir(dir)
             .output()
-            .context("Failed to get commit SHA")?;
+            .context("Failed to run git diff")?;
 
-        if !output.status.success() {
-            anyhow::bail!("Git rev-parse failed");
+        if !diff.status.success() {
+            anyhow::bail!(
+                "git diff against {old_commit} failed: {}",
+                String::from_utf8_lossy(&diff.stderr)
+            );
         }
 
-        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+        Ok(String::from_utf8_lossy(&diff.stdout)
+            .lines()
+            .map(|l| l.to_string())
+            .collect())
     }
 
-    /// Generate FMM manifest for the FMM variant
+    /// Generate FMM manifest for the FMM variant, killing `fmm generate` if
+    /// it's still running after `limits.parse_timeout_secs`.
     pub fn generate_fmm_manifest(&self) -> Result<()> {
-        // Run fmm generate in the FMM directory
         let fmm_binary = std::env::current_exe().context("Failed to get current executable")?;
 
-        let output = Command::new(&fmm_binary)
-            .arg("generate")
+        let mut cmd = Command::new(&fmm_binary);
+        cmd.arg("generate")
             .arg("--manifest-only")
-            .current_dir(&self.fmm_dir)
-            .output()
-            .context("Failed to run fmm generate")?;
+            .current_dir(&self.fmm_dir);
+
+        let output = super::git_backend::run_with_timeout(
+            cmd,
+            Duration::from_secs(self.limits.parse_timeout_secs),
+            "fmm generate",
+        )?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -158,21 +206,30 @@ impl Sandbox {
         Ok(())
     }
 
-    /// Check if sandbox exceeds limits
-    #[allow(dead_code)]
+    /// Check if the sandbox exceeds its TTL, `limits.max_repo_size_mb`, or
+    /// `limits.max_files_to_parse`. Called by `clone_repo` right after
+    /// cloning, which tears the sandbox down on a failure here.
     pub fn check_limits(&self) -> Result<()> {
-        // Check TTL
         if self.created_at.elapsed() > self.ttl {
             anyhow::bail!("Sandbox expired (TTL exceeded)");
         }
 
-        // Check size (simplified - just check if dirs exist and have reasonable size)
         let size = dir_size(&self.root)?;
-        if size > self.max_size_mb * 1_000_000 {
+        let max_bytes = self.limits.max_repo_size_mb * 1_000_000;
+        if size > max_bytes {
             anyhow::bail!(
                 "Sandbox size exceeded: {} MB > {} MB",
                 size / 1_000_000,
-                self.max_size_mb
+                self.limits.max_repo_size_mb
+            );
+        }
+
+        let files = self.count_files()?;
+        if files > self.limits.max_files_to_parse {
+            anyhow::bail!(
+                "Sandbox file count exceeded: {} files > {} files",
+                files,
+                self.limits.max_files_to_parse
             );
         }
 
@@ -180,7 +237,6 @@ impl Sandbox {
     }
 
     /// Count files in the FMM variant
-    #[allow(dead_code)]
     pub fn count_files(&self) -> Result<u64> {
         count_files_in_dir(&self.fmm_dir)
     }
@@ -207,7 +263,6 @@ impl Drop for Sandbox {
     }
 }
 
-#[allow(dead_code)]
 fn dir_size(path: &Path) -> Result<u64> {
     let mut size = 0u64;
 
@@ -227,7 +282,6 @@ fn dir_size(path: &Path) -> Result<u64> {
     Ok(size)
 }
 
-#[allow(dead_code)]
 fn count_files_in_dir(path: &Path) -> Result<u64> {
     let mut count = 0u64;
 
@@ -265,7 +319,7 @@ fn validate_job_id(job_id: &str) -> Result<()> {
 }
 
 /// Validate repository URL is a safe HTTPS git URL
-fn validate_repo_url(url: &str) -> Result<()> {
+pub(super) fn validate_repo_url(url: &str) -> Result<()> {
     if !url.starts_with("https://") {
         anyhow::bail!("Repository URL must use HTTPS: {}", url);
     }
@@ -277,8 +331,14 @@ fn validate_repo_url(url: &str) -> Result<()> {
     if host.is_empty() || !host.contains('.') {
         anyhow::bail!("Invalid repository host in URL: {}", url);
     }
-    // Reject URLs with suspicious characters that could be used for injection
-    if url.contains("..") || url.contains('\0') || url.contains(';') || url.contains('|') {
+    // Reject URLs with suspicious characters that could be used for shell
+    // injection if a caller (e.g. `ContainerSandbox`) ever ends up quoting
+    // this into a shell command, in addition to the usual path-traversal
+    // and control-character cases.
+    const SHELL_METACHARACTERS: &[char] = &[
+        ';', '|', '&', '$', '`', '\'', '"', '(', ')', '<', '>', '\n', '\r', ' ', '\t',
+    ];
+    if url.contains("..") || url.contains('\0') || url.contains(SHELL_METACHARACTERS) {
         anyhow::bail!("Repository URL contains invalid characters: {}", url);
     }
     Ok(())
@@ -366,6 +426,33 @@ mod tests {
         assert!(!root_path.exists());
     }
 
+    #[test]
+    fn test_sandbox_with_limits_applies_custom_bounds() {
+        let limits = ResourceLimits {
+            max_repo_size_mb: 1,
+            ..ResourceLimits::default()
+        };
+        let sandbox = Sandbox::with_limits("limits-test-001", limits).unwrap();
+        assert!(sandbox.root.exists());
+        // An empty freshly-created sandbox is well under even a 1MB cap.
+        assert!(sandbox.check_limits().is_ok());
+        sandbox.cleanup();
+    }
+
+    #[test]
+    fn test_check_limits_rejects_too_many_files() {
+        let limits = ResourceLimits {
+            max_files_to_parse: 0,
+            ..ResourceLimits::default()
+        };
+        let sandbox = Sandbox::with_limits("limits-test-002", limits).unwrap();
+        fs::create_dir_all(&sandbox.fmm_dir).unwrap();
+        fs::write(sandbox.fmm_dir.join("a.txt"), "x").unwrap();
+
+        assert!(sandbox.check_limits().is_err());
+        sandbox.cleanup();
+    }
+
     #[test]
     fn test_sandbox_keep_on_drop() {
         let root_path;
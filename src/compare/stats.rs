@@ -0,0 +1,276 @@
+//! Descriptive statistics and Welch's t-test for multi-run `fmm compare`
+//! samples, so a reported reduction can be flagged as real signal rather
+//! than run-to-run LLM jitter.
+
+use serde::{Deserialize, Serialize};
+
+/// Mean/median/stddev summary of a sample of `f64` observations (one metric
+/// — tool calls, cost, etc. — across `runs` repetitions of a task/variant).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct SampleStats {
+    pub n: usize,
+    pub mean: f64,
+    pub median: f64,
+    pub stddev: f64,
+}
+
+impl SampleStats {
+    pub fn from_samples(samples: &[f64]) -> Self {
+        let n = samples.len();
+        if n == 0 {
+            return Self {
+                n: 0,
+                mean: 0.0,
+                median: 0.0,
+                stddev: 0.0,
+            };
+        }
+
+        let mean = samples.iter().sum::<f64>() / n as f64;
+        let variance = if n > 1 {
+            samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64
+        } else {
+            0.0
+        };
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let median = if n % 2 == 0 {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0
+        } else {
+            sorted[n / 2]
+        };
+
+        Self {
+            n,
+            mean,
+            median,
+            stddev: variance.sqrt(),
+        }
+    }
+
+    fn variance(&self) -> f64 {
+        self.stddev * self.stddev
+    }
+}
+
+/// Verdict on whether a control/FMM reduction is real signal or noise.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Significance {
+    /// `|t|` exceeded the critical value for the estimated degrees of freedom.
+    Significant,
+    NotSignificant,
+    /// `runs == 1` — no variance to test, point estimate only.
+    InsufficientData,
+}
+
+/// Control-vs-FMM comparison for one metric (tool calls, cost, ...) across
+/// `runs` repetitions, carrying the raw stats for each side plus a
+/// significance verdict instead of a single noisy point estimate.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct MetricComparison {
+    pub control: SampleStats,
+    pub fmm: SampleStats,
+    pub reduction_pct: f64,
+    pub significance: Significance,
+}
+
+impl MetricComparison {
+    pub fn new(control_samples: &[f64], fmm_samples: &[f64]) -> Self {
+        let control = SampleStats::from_samples(control_samples);
+        let fmm = SampleStats::from_samples(fmm_samples);
+
+        let reduction_pct = if control.mean > 0.0 {
+            ((control.mean - fmm.mean) / control.mean) * 100.0
+        } else {
+            0.0
+        };
+
+        Self {
+            significance: welch_significance(&control, &fmm),
+            control,
+            fmm,
+            reduction_pct,
+        }
+    }
+
+    /// One-line verdict pairing the reduction with its significance, e.g.
+    /// `"reduced by 62% (significant, p<0.05)"` or `"no significant
+    /// difference"` — so a caller never has to re-derive the direction or
+    /// re-state the significance label separately from the number.
+    pub fn verdict(&self) -> String {
+        match self.significance {
+            Significance::InsufficientData => format!(
+                "{:.1}% change (insufficient trials, n={})",
+                self.reduction_pct,
+                self.control.n.max(self.fmm.n)
+            ),
+            Significance::NotSignificant => "no significant difference".to_string(),
+            Significance::Significant => {
+                let direction = if self.reduction_pct >= 0.0 {
+                    "reduced"
+                } else {
+                    "increased"
+                };
+                format!(
+                    "{} by {:.0}% (significant, p<0.05)",
+                    direction,
+                    self.reduction_pct.abs()
+                )
+            }
+        }
+    }
+}
+
+/// Welch's t-test between two independent samples, summarized by their
+/// [`SampleStats`] (n/mean/variance) rather than the raw observations.
+/// Falls back to [`Significance::InsufficientData`] when either side only
+/// has one run, and handles the zero-variance case (identical runs) by
+/// comparing means directly rather than dividing by zero.
+fn welch_significance(control: &SampleStats, fmm: &SampleStats) -> Significance {
+    if control.n <= 1 || fmm.n <= 1 {
+        return Significance::InsufficientData;
+    }
+
+    let var_c = control.variance();
+    let var_f = fmm.variance();
+
+    if var_c == 0.0 && var_f == 0.0 {
+        return if control.mean != fmm.mean {
+            Significance::Significant
+        } else {
+            Significance::NotSignificant
+        };
+    }
+
+    let n_c = control.n as f64;
+    let n_f = fmm.n as f64;
+    let se_c = var_c / n_c;
+    let se_f = var_f / n_f;
+    let se_sum = se_c + se_f;
+
+    if se_sum == 0.0 {
+        return Significance::NotSignificant;
+    }
+
+    let t = (control.mean - fmm.mean) / se_sum.sqrt();
+    let df = se_sum.powi(2) / (se_c.powi(2) / (n_c - 1.0) + se_f.powi(2) / (n_f - 1.0));
+
+    if t.abs() > t_critical(df) {
+        Significance::Significant
+    } else {
+        Significance::NotSignificant
+    }
+}
+
+/// Two-sided critical t value at the 0.05 level: 1.96 for df >= 30 (normal
+/// approximation), otherwise a small lookup table of the standard t-table —
+/// plenty of precision for the handful of runs a `fmm compare` session
+/// collects.
+fn t_critical(df: f64) -> f64 {
+    const TABLE: &[(f64, f64)] = &[
+        (1.0, 12.706),
+        (2.0, 4.303),
+        (3.0, 3.182),
+        (4.0, 2.776),
+        (5.0, 2.571),
+        (6.0, 2.447),
+        (7.0, 2.365),
+        (8.0, 2.306),
+        (9.0, 2.262),
+        (10.0, 2.228),
+        (15.0, 2.131),
+        (20.0, 2.086),
+        (25.0, 2.060),
+        (30.0, 2.042),
+    ];
+
+    if df >= 30.0 {
+        return 1.96;
+    }
+
+    for &(threshold, critical) in TABLE {
+        if df <= threshold {
+            return critical;
+        }
+    }
+
+    1.96
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_stats_single_value() {
+        let stats = SampleStats::from_samples(&[5.0]);
+        assert_eq!(stats.n, 1);
+        assert_eq!(stats.mean, 5.0);
+        assert_eq!(stats.median, 5.0);
+        assert_eq!(stats.stddev, 0.0);
+    }
+
+    #[test]
+    fn sample_stats_even_count_median_averages_middle_pair() {
+        let stats = SampleStats::from_samples(&[1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(stats.median, 2.5);
+    }
+
+    #[test]
+    fn metric_comparison_single_run_is_insufficient_data() {
+        let cmp = MetricComparison::new(&[10.0], &[5.0]);
+        assert_eq!(cmp.significance, Significance::InsufficientData);
+        assert_eq!(cmp.reduction_pct, 50.0);
+    }
+
+    #[test]
+    fn metric_comparison_identical_samples_not_significant() {
+        let cmp = MetricComparison::new(&[10.0, 10.0, 10.0], &[10.0, 10.0, 10.0]);
+        assert_eq!(cmp.significance, Significance::NotSignificant);
+    }
+
+    #[test]
+    fn metric_comparison_zero_variance_different_means_significant() {
+        let cmp = MetricComparison::new(&[10.0, 10.0, 10.0], &[2.0, 2.0, 2.0]);
+        assert_eq!(cmp.significance, Significance::Significant);
+    }
+
+    #[test]
+    fn metric_comparison_clearly_separated_samples_significant() {
+        let control = vec![100.0, 102.0, 98.0, 101.0, 99.0];
+        let fmm = vec![40.0, 42.0, 38.0, 41.0, 39.0];
+        let cmp = MetricComparison::new(&control, &fmm);
+        assert_eq!(cmp.significance, Significance::Significant);
+    }
+
+    #[test]
+    fn metric_comparison_overlapping_samples_not_significant() {
+        let control = vec![10.0, 12.0, 8.0];
+        let fmm = vec![9.0, 11.0, 10.0];
+        let cmp = MetricComparison::new(&control, &fmm);
+        assert_eq!(cmp.significance, Significance::NotSignificant);
+    }
+
+    #[test]
+    fn verdict_reports_the_reduction_when_significant() {
+        let control = vec![100.0, 102.0, 98.0, 101.0, 99.0];
+        let fmm = vec![40.0, 42.0, 38.0, 41.0, 39.0];
+        let cmp = MetricComparison::new(&control, &fmm);
+        assert_eq!(cmp.verdict(), "reduced by 60% (significant, p<0.05)");
+    }
+
+    #[test]
+    fn verdict_reports_no_significant_difference() {
+        let control = vec![10.0, 12.0, 8.0];
+        let fmm = vec![9.0, 11.0, 10.0];
+        let cmp = MetricComparison::new(&control, &fmm);
+        assert_eq!(cmp.verdict(), "no significant difference");
+    }
+
+    #[test]
+    fn verdict_flags_insufficient_trials_for_a_single_run() {
+        let cmp = MetricComparison::new(&[10.0], &[5.0]);
+        assert_eq!(cmp.verdict(), "50.0% change (insufficient trials, n=1)");
+    }
+}
@@ -0,0 +1,87 @@
+//! Benchmark task definitions for `fmm compare`.
+
+use serde::{Deserialize, Serialize};
+
+/// A single benchmark task, run once per variant (control and FMM) against
+/// the cloned sandbox via the `claude` CLI.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Task {
+    /// Stable identifier used in cache keys and report output.
+    pub id: String,
+    /// Human-readable label shown in progress output.
+    pub name: String,
+    /// Prompt passed to `claude -p`.
+    pub prompt: String,
+    /// `--max-turns` cap for this task.
+    pub max_turns: u32,
+    /// `--max-budget` cap (in USD) for this task.
+    pub max_budget_usd: f64,
+}
+
+/// A named collection of [`Task`]s run together for one comparison.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskSet {
+    pub name: String,
+    pub tasks: Vec<Task>,
+}
+
+impl TaskSet {
+    /// The default task set: realistic codebase-navigation prompts covering
+    /// symbol lookup, dependency tracing, and a small edit.
+    pub fn standard() -> Self {
+        Self {
+            name: "standard".to_string(),
+            tasks: vec![
+                Task {
+                    id: "find-export".to_string(),
+                    name: "Find an exported symbol".to_string(),
+                    prompt: "Find where the main entry point is defined and summarize what it does."
+                        .to_string(),
+                    max_turns: 15,
+                    max_budget_usd: 1.0,
+                },
+                Task {
+                    id: "trace-dependency".to_string(),
+                    name: "Trace a dependency chain".to_string(),
+                    prompt: "Find the file that defines the primary configuration loader and list every file that depends on it."
+                        .to_string(),
+                    max_turns: 20,
+                    max_budget_usd: 1.5,
+                },
+                Task {
+                    id: "add-feature".to_string(),
+                    name: "Implement a small feature".to_string(),
+                    prompt: "Add a new exported helper function alongside an existing one of similar purpose, matching the surrounding style."
+                        .to_string(),
+                    max_turns: 25,
+                    max_budget_usd: 2.0,
+                },
+            ],
+        }
+    }
+
+    /// A single-task subset of [`TaskSet::standard`] for fast iteration.
+    pub fn quick() -> Self {
+        let mut set = Self::standard();
+        set.name = "quick".to_string();
+        set.tasks.truncate(1);
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn standard_has_multiple_tasks() {
+        assert!(TaskSet::standard().tasks.len() > 1);
+    }
+
+    #[test]
+    fn quick_is_a_single_task_subset() {
+        let quick = TaskSet::quick();
+        assert_eq!(quick.tasks.len(), 1);
+        assert_eq!(quick.tasks[0].id, TaskSet::standard().tasks[0].id);
+    }
+}
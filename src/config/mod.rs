@@ -1,7 +1,11 @@
 use anyhow::Result;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
-use std::path::Path;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+pub mod query_pack;
+pub mod rules;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -24,6 +28,105 @@ pub struct Config {
     /// Maximum file size to process (in KB)
     #[serde(default = "default_max_file_size")]
     pub max_file_size: usize,
+
+    /// Glob patterns (gitignore syntax) to additionally exclude, on top of
+    /// `.gitignore`/`.fmmignore` — e.g. `"vendor/**"`.
+    #[serde(default)]
+    pub exclude: Vec<String>,
+
+    /// Glob patterns (gitignore syntax) that must match for a file to be
+    /// processed at all, e.g. re-including a generated file inside an
+    /// otherwise-excluded directory.
+    #[serde(default)]
+    pub include: Vec<String>,
+
+    /// External formatter command per fenced-code-block language, used by
+    /// `fmm format` to normalize embedded snippets inside sidecars — e.g.
+    /// `{"rust": "rustfmt", "json": "prettier --parser json"}`. A language
+    /// with no entry here is left untouched.
+    #[serde(default)]
+    pub formatters: std::collections::HashMap<String, String>,
+
+    /// `if <expr> then <action>` rules (see [`rules`]), evaluated against
+    /// each file's metadata to compute extra `custom_fields` or override
+    /// inclusion — e.g. `"if contains(imports, \"jwt\") && count(exports) > 0
+    /// then security_sensitive = true"`. A rule that fails to parse is
+    /// reported once and skipped rather than aborting the run; see
+    /// [`Config::compiled_rules`].
+    #[serde(default)]
+    pub rules: Vec<String>,
+
+    /// Per-language overrides of `include_complexity`/`include_loc`/
+    /// `max_file_size`, keyed by file extension — e.g. `{"rs": {"include_complexity":
+    /// true}}` to turn on complexity metrics only for Rust. See
+    /// [`Config::for_extension`]. Doesn't affect `is_supported_language`,
+    /// which is still driven entirely by `languages`.
+    #[serde(default)]
+    pub overrides: std::collections::HashMap<String, LanguageOverride>,
+
+    /// How `fmm update` reconciles a freshly computed frontmatter block with
+    /// the one already in the file. See [`UpdateStrategy`].
+    #[serde(default = "default_update_strategy")]
+    pub update_strategy: UpdateStrategy,
+
+    /// Shorthand invocations expanded to a full `fmm` command line before
+    /// clap dispatch, cargo-alias style — e.g. `{"gen": "generate src/ -n",
+    /// "ci": ["validate", "--strict"]}` lets a team type `fmm gen` instead of
+    /// `fmm generate src/ -n`. See [`crate::cli::expand_aliases`].
+    #[serde(default)]
+    pub aliases: HashMap<String, AliasValue>,
+
+    /// Named saved `fmm search` queries, cargo-alias style but distinct from
+    /// [`Config::aliases`] (those expand a whole `fmm` command line; these
+    /// only expand a `search` flag set) — e.g. `{"big": "--loc >500", "react":
+    /// "--imports react"}` lets a team type `fmm search big` instead of
+    /// `fmm search --loc ">500"`. See [`crate::cli::parse_search_alias`].
+    #[serde(default)]
+    pub search_aliases: HashMap<String, String>,
+}
+
+/// A [`Config::aliases`] entry: either a single string, whitespace-split the
+/// way a shell would, or an already-split array of arguments (needed when an
+/// argument itself contains whitespace, e.g. a quoted rule expression).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AliasValue {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+impl AliasValue {
+    /// This alias's argument tokens, in the order they should be spliced
+    /// into argv.
+    pub fn tokens(&self) -> Vec<String> {
+        match self {
+            AliasValue::Single(s) => s.split_whitespace().map(str::to_string).collect(),
+            AliasValue::Multiple(v) => v.clone(),
+        }
+    }
+}
+
+/// A partial [`Config`] applied on top of the base config for files with a
+/// matching extension — see [`Config::overrides`] and [`Config::for_extension`].
+/// A field left `None` falls through to the base config's value.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LanguageOverride {
+    #[serde(default)]
+    pub include_loc: Option<bool>,
+    #[serde(default)]
+    pub include_complexity: Option<bool>,
+    #[serde(default)]
+    pub max_file_size: Option<usize>,
+}
+
+/// `include_loc`/`include_complexity`/`max_file_size` resolved for one
+/// extension, after applying any matching [`Config::overrides`] entry — see
+/// [`Config::for_extension`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResolvedConfig {
+    pub include_loc: bool,
+    pub include_complexity: bool,
+    pub max_file_size: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,6 +136,20 @@ pub enum FrontmatterFormat {
     Json,
 }
 
+/// How `fmm update` reconciles a file's existing frontmatter block with the
+/// one just computed from the source — see [`crate::extractor::FileProcessor::update`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateStrategy {
+    /// Overwrite only the machine-derived keys (`exports`, `imports`,
+    /// `dependencies`, `loc`, `file`, ...), preserving unknown/hand-added
+    /// keys and their ordering.
+    Merge,
+    /// Replace the whole frontmatter block with a freshly rendered one,
+    /// discarding anything hand-added.
+    Replace,
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
@@ -41,29 +158,335 @@ impl Default for Config {
             include_loc: true,
             include_complexity: false,
             max_file_size: 1024, // 1MB
+            exclude: Vec::new(),
+            include: Vec::new(),
+            formatters: std::collections::HashMap::new(),
+            rules: Vec::new(),
+            overrides: std::collections::HashMap::new(),
+            update_strategy: default_update_strategy(),
+            aliases: HashMap::new(),
+            search_aliases: HashMap::new(),
         }
     }
 }
 
+/// `.fmmrc` file names recognized at each directory level, tried in this
+/// order when more than one is present — the format is picked from
+/// whichever extension matches, same as [`FrontmatterFormat`] does for
+/// output.
+const CONFIG_FILE_NAMES: &[&str] = &[".fmmrc.json", ".fmmrc.yaml", ".fmmrc.yml", ".fmmrc.toml"];
+
 impl Config {
     pub fn load() -> Result<Self> {
         Self::load_from_dir(Path::new("."))
     }
 
+    /// Load `.fmmrc.{json,yaml,yml,toml}`, walking up from `dir` toward the
+    /// filesystem root the way cargo/git locate their config. A config file
+    /// found closer to `dir` overrides the fields it sets on any found
+    /// further up the chain; a field no file sets falls back to
+    /// [`Config::default`].
     pub fn load_from_dir(dir: &Path) -> Result<Self> {
-        let path = dir.join(".fmmrc.json");
-        if !path.exists() {
-            return Ok(Self::default());
+        let start = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+
+        let mut layers = Vec::new();
+        let mut current = Some(start.as_path());
+        while let Some(d) = current {
+            if let Some(raw) = Self::read_raw_config(d)? {
+                layers.push(raw);
+            }
+            current = d.parent();
         }
 
-        let content = std::fs::read_to_string(path)?;
-        let config: Config = serde_json::from_str(&content)?;
-        Ok(config)
+        // `layers` is nearest-first; fold from the farthest ancestor inward
+        // so a nearer file's fields win.
+        let merged = layers
+            .into_iter()
+            .rev()
+            .fold(RawConfig::default(), RawConfig::merge);
+        Ok(merged.into_config())
+    }
+
+    /// Read and parse whichever [`CONFIG_FILE_NAMES`] entry exists in `dir`
+    /// first, or `None` if this directory has no `.fmmrc` at all.
+    fn read_raw_config(dir: &Path) -> Result<Option<RawConfig>> {
+        for name in CONFIG_FILE_NAMES {
+            let path = dir.join(name);
+            if path.exists() {
+                let content = std::fs::read_to_string(&path)?;
+                return Ok(Some(parse_raw_config(&path, &content)?));
+            }
+        }
+        Ok(None)
     }
 
     pub fn is_supported_language(&self, extension: &str) -> bool {
         self.languages.contains(extension)
     }
+
+    /// Resolve `include_loc`/`include_complexity`/`max_file_size` for
+    /// `extension`, applying `overrides[extension]` (if any) on top of the
+    /// base config. Extensions with no override just get the base values
+    /// back.
+    pub fn for_extension(&self, extension: &str) -> ResolvedConfig {
+        let base = ResolvedConfig {
+            include_loc: self.include_loc,
+            include_complexity: self.include_complexity,
+            max_file_size: self.max_file_size,
+        };
+        let Some(over) = self.overrides.get(extension) else {
+            return base;
+        };
+        ResolvedConfig {
+            include_loc: over.include_loc.unwrap_or(base.include_loc),
+            include_complexity: over.include_complexity.unwrap_or(base.include_complexity),
+            max_file_size: over.max_file_size.unwrap_or(base.max_file_size),
+        }
+    }
+
+    /// Compile [`Config::rules`] into a ready-to-evaluate [`rules::RuleSet`].
+    /// Call once (e.g. alongside building a [`crate::parser::ParserRegistry`])
+    /// and reuse across every file in the run, rather than re-parsing the
+    /// rule text per file.
+    pub fn compiled_rules(&self) -> rules::RuleSet {
+        rules::RuleSet::compile(&self.rules)
+    }
+}
+
+/// Layered defaults for `fmm gh issue` / `fmm compare`, loaded from an
+/// `fmm.toml` so users don't have to repeat `--model`/`--max-budget`/
+/// `--branch-prefix`/`--format` on every invocation. Every field is
+/// optional — an unset field falls through to the next, less specific
+/// layer, and an explicit CLI flag always wins over all of them; see
+/// [`ProjectDefaults::load_layered`] and [`ProjectDefaults::schema_json`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ProjectDefaults {
+    #[serde(default)]
+    pub gh: GhDefaults,
+    #[serde(default)]
+    pub compare: CompareDefaults,
+}
+
+/// Defaults for `fmm gh issue`, mirroring its CLI flags.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct GhDefaults {
+    /// Claude model to use (overridden by `--model`)
+    pub model: Option<String>,
+    /// Maximum turns for Claude (overridden by `--max-turns`)
+    pub max_turns: Option<u32>,
+    /// Maximum budget in USD (overridden by `--max-budget`)
+    pub max_budget: Option<f64>,
+    /// Git branch prefix (overridden by `--branch-prefix`)
+    pub branch_prefix: Option<String>,
+}
+
+/// Defaults for `fmm compare`, mirroring its CLI flags.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct CompareDefaults {
+    /// Maximum budget in USD (overridden by `--max-budget`)
+    pub max_budget: Option<f64>,
+    /// Output format: "json", "markdown", or "both" (overridden by `--format`)
+    pub format: Option<String>,
+}
+
+impl ProjectDefaults {
+    /// Resolve the layered `fmm.toml`: the user config dir
+    /// (`$XDG_CONFIG_HOME/fmm/config.toml`, falling back to
+    /// `~/.config/fmm/config.toml`) applied first, then `root`'s own
+    /// `fmm.toml` overriding any field it sets. Missing files are silently
+    /// treated as empty layers — `fmm.toml` is opt-in, not required.
+    pub fn load_layered(root: &Path) -> Self {
+        let mut merged = Self::default();
+        if let Some(dir) = user_config_dir() {
+            merged.merge(Self::load_toml(&dir.join("config.toml")));
+        }
+        merged.merge(Self::load_toml(&root.join("fmm.toml")));
+        merged
+    }
+
+    fn load_toml(path: &Path) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|content| toml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn merge(&mut self, other: Self) {
+        self.gh.model = other.gh.model.or(self.gh.model.take());
+        self.gh.max_turns = other.gh.max_turns.or(self.gh.max_turns.take());
+        self.gh.max_budget = other.gh.max_budget.or(self.gh.max_budget.take());
+        self.gh.branch_prefix = other.gh.branch_prefix.or(self.gh.branch_prefix.take());
+        self.compare.max_budget = other.compare.max_budget.or(self.compare.max_budget.take());
+        self.compare.format = other.compare.format.or(self.compare.format.take());
+    }
+
+    /// Render the machine-readable JSON Schema for `fmm.toml`, for editors
+    /// that validate/autocomplete against it — backs `fmm config schema`.
+    pub fn schema_json() -> Result<String> {
+        let schema = schemars::schema_for!(ProjectDefaults);
+        Ok(serde_json::to_string_pretty(&schema)?)
+    }
+}
+
+/// Mirrors [`Config`] with every field optional, so a single `.fmmrc` layer
+/// can say "I don't set this" and [`Config::load_from_dir`] can tell that
+/// apart from an explicit value before falling back to an ancestor's layer
+/// or [`Config::default`].
+#[derive(Debug, Clone, Default, Deserialize)]
+struct RawConfig {
+    languages: Option<HashSet<String>>,
+    format: Option<FrontmatterFormat>,
+    include_loc: Option<bool>,
+    include_complexity: Option<bool>,
+    max_file_size: Option<usize>,
+    exclude: Option<Vec<String>>,
+    include: Option<Vec<String>>,
+    formatters: Option<std::collections::HashMap<String, String>>,
+    rules: Option<Vec<String>>,
+    overrides: Option<std::collections::HashMap<String, LanguageOverride>>,
+    update_strategy: Option<UpdateStrategy>,
+    aliases: Option<HashMap<String, AliasValue>>,
+    search_aliases: Option<HashMap<String, String>>,
+}
+
+impl RawConfig {
+    /// Layer `nearer` over `self`: a field `nearer` sets wins, a field it
+    /// leaves unset keeps whatever `self` (an ancestor directory's config)
+    /// already had.
+    fn merge(self, nearer: RawConfig) -> RawConfig {
+        RawConfig {
+            languages: nearer.languages.or(self.languages),
+            format: nearer.format.or(self.format),
+            include_loc: nearer.include_loc.or(self.include_loc),
+            include_complexity: nearer.include_complexity.or(self.include_complexity),
+            max_file_size: nearer.max_file_size.or(self.max_file_size),
+            exclude: nearer.exclude.or(self.exclude),
+            include: nearer.include.or(self.include),
+            formatters: nearer.formatters.or(self.formatters),
+            rules: nearer.rules.or(self.rules),
+            overrides: nearer.overrides.or(self.overrides),
+            update_strategy: nearer.update_strategy.or(self.update_strategy),
+            aliases: nearer.aliases.or(self.aliases),
+            search_aliases: nearer.search_aliases.or(self.search_aliases),
+        }
+    }
+
+    fn into_config(self) -> Config {
+        let defaults = Config::default();
+        Config {
+            languages: self.languages.unwrap_or(defaults.languages),
+            format: self.format.unwrap_or(defaults.format),
+            include_loc: self.include_loc.unwrap_or(defaults.include_loc),
+            include_complexity: self.include_complexity.unwrap_or(defaults.include_complexity),
+            max_file_size: self.max_file_size.unwrap_or(defaults.max_file_size),
+            exclude: self.exclude.unwrap_or(defaults.exclude),
+            include: self.include.unwrap_or(defaults.include),
+            formatters: self.formatters.unwrap_or(defaults.formatters),
+            rules: self.rules.unwrap_or(defaults.rules),
+            overrides: self.overrides.unwrap_or(defaults.overrides),
+            update_strategy: self.update_strategy.unwrap_or(defaults.update_strategy),
+            aliases: self.aliases.unwrap_or(defaults.aliases),
+            search_aliases: self.search_aliases.unwrap_or(defaults.search_aliases),
+        }
+    }
+}
+
+/// Parse a `.fmmrc` layer, picking the format from `path`'s extension —
+/// `.yaml`/`.yml` via `serde_yaml`, `.toml` via `toml`, anything else
+/// (notably `.json`) via `serde_json`.
+fn parse_raw_config(path: &Path, content: &str) -> Result<RawConfig> {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("yaml") | Some("yml") => Ok(serde_yaml::from_str(content)?),
+        Some("toml") => Ok(toml::from_str(content)?),
+        _ => Ok(serde_json::from_str(content)?),
+    }
+}
+
+/// Machine-level settings that apply across every project, read once at
+/// startup rather than threaded through `--flag`s: which [`GitBackend`]
+/// implementation to clone/fetch with (see `gh::git_backend::backend_for`
+/// and `compare::git_backend::backend_for`), and where issue workspaces
+/// get checked out by default.
+///
+/// [`GitBackend`]: crate::gh::git_backend::GitBackend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GlobalConfig {
+    /// `"shell"` (default, shells out to a `git` binary) or `"gitoxide"`
+    /// (pure-Rust, via `gix`, no `git` binary required).
+    #[serde(default = "default_git_backend")]
+    pub git_backend: String,
+
+    /// Root directory `fmm gh issue` clones repos into when `--workspace`
+    /// isn't passed.
+    #[serde(default = "default_workspace_dir")]
+    pub workspace_dir: PathBuf,
+
+    /// Named `fmm gh issue --profile <name>` presets, e.g. a `[profiles.cheap]`
+    /// pinning a cheaper model and a tight turn/budget cap. Selected by
+    /// `--profile`; a value a profile sets fills in any CLI flag the user
+    /// didn't pass explicitly, the same way a cargo alias fills in the args
+    /// its invocation didn't override. See [`GhProfile`].
+    #[serde(default)]
+    pub profiles: HashMap<String, GhProfile>,
+}
+
+impl Default for GlobalConfig {
+    fn default() -> Self {
+        Self {
+            git_backend: default_git_backend(),
+            workspace_dir: default_workspace_dir(),
+            profiles: HashMap::new(),
+        }
+    }
+}
+
+/// One named `fmm gh issue --profile <name>` preset — every field mirrors a
+/// [`crate::gh::GhIssueOptions`] flag and is optional, since a profile only
+/// needs to set the flags it wants to pin. Resolution order is explicit CLI
+/// flag > profile value > built-in default.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GhProfile {
+    pub model: Option<String>,
+    pub max_turns: Option<u32>,
+    pub max_budget: Option<f64>,
+    /// Comma-separated tool allowlist passed straight through to Claude
+    /// (e.g. `"Read,Write,Edit,Glob,Grep,Bash"`).
+    pub allowed_tools: Option<String>,
+    pub branch_prefix: Option<String>,
+}
+
+impl GlobalConfig {
+    /// Load `$XDG_CONFIG_HOME/fmm/config.toml` (falling back to
+    /// `~/.config/fmm/config.toml`) — the same file [`ProjectDefaults`]
+    /// reads its `[gh]`/`[compare]` layer from. A missing or unparseable
+    /// file falls back to [`GlobalConfig::default`] rather than erroring,
+    /// since this file is entirely optional.
+    pub fn load() -> Self {
+        user_config_dir()
+            .map(|dir| dir.join("config.toml"))
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|content| toml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+}
+
+fn default_git_backend() -> String {
+    "shell".to_string()
+}
+
+fn default_workspace_dir() -> PathBuf {
+    user_config_dir()
+        .map(|dir| dir.join("workspaces"))
+        .unwrap_or_else(|| std::env::temp_dir().join("fmm-workspaces"))
+}
+
+/// `$XDG_CONFIG_HOME/fmm`, falling back to `~/.config/fmm` on platforms
+/// (or shells) that don't set `XDG_CONFIG_HOME`.
+fn user_config_dir() -> Option<PathBuf> {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .map(|base| base.join("fmm"))
 }
 
 fn default_languages() -> HashSet<String> {
@@ -77,6 +500,10 @@ fn default_format() -> FrontmatterFormat {
     FrontmatterFormat::Yaml
 }
 
+fn default_update_strategy() -> UpdateStrategy {
+    UpdateStrategy::Merge
+}
+
 fn default_true() -> bool {
     true
 }
@@ -111,6 +538,20 @@ mod tests {
         assert!(!config.include_complexity);
         assert_eq!(config.max_file_size, 1024);
         assert!(matches!(config.format, FrontmatterFormat::Yaml));
+        assert!(matches!(config.update_strategy, UpdateStrategy::Merge));
+    }
+
+    #[test]
+    fn loads_replace_update_strategy() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(
+            tmp.path().join(".fmmrc.json"),
+            r#"{ "update_strategy": "replace" }"#,
+        )
+        .unwrap();
+
+        let config = Config::load_from_dir(tmp.path()).unwrap();
+        assert!(matches!(config.update_strategy, UpdateStrategy::Replace));
     }
 
     #[test]
@@ -158,6 +599,117 @@ mod tests {
         assert!(matches!(config.format, FrontmatterFormat::Yaml));
     }
 
+    #[test]
+    fn default_config_has_no_include_or_exclude_globs() {
+        let config = Config::default();
+        assert!(config.exclude.is_empty());
+        assert!(config.include.is_empty());
+    }
+
+    #[test]
+    fn default_config_has_no_rules() {
+        let config = Config::default();
+        assert!(config.rules.is_empty());
+        assert!(config.compiled_rules().admits(&crate::config::rules::RuleContext {
+            exports: &[],
+            imports: &[],
+            dependencies: &[],
+            loc: 0,
+            path: "a.rs",
+        }));
+    }
+
+    #[test]
+    fn loads_rules_array() {
+        let tmp = TempDir::new().unwrap();
+        let json = r#"{
+            "rules": ["if loc > 500 then big = true"]
+        }"#;
+        fs::write(tmp.path().join(".fmmrc.json"), json).unwrap();
+
+        let config = Config::load_from_dir(tmp.path()).unwrap();
+        assert_eq!(config.rules, vec!["if loc > 500 then big = true"]);
+    }
+
+    #[test]
+    fn loads_include_and_exclude_globs() {
+        let tmp = TempDir::new().unwrap();
+        let json = r#"{
+            "exclude": ["vendor/**", "*.generated.ts"],
+            "include": ["vendor/keep-me.ts"]
+        }"#;
+        fs::write(tmp.path().join(".fmmrc.json"), json).unwrap();
+
+        let config = Config::load_from_dir(tmp.path()).unwrap();
+        assert_eq!(config.exclude, vec!["vendor/**", "*.generated.ts"]);
+        assert_eq!(config.include, vec!["vendor/keep-me.ts"]);
+    }
+
+    #[test]
+    fn loads_yaml_config() {
+        let tmp = TempDir::new().unwrap();
+        let yaml = "languages:\n  - rs\n  - py\nmax_file_size: 256\n";
+        fs::write(tmp.path().join(".fmmrc.yaml"), yaml).unwrap();
+
+        let config = Config::load_from_dir(tmp.path()).unwrap();
+        assert_eq!(config.languages.len(), 2);
+        assert!(config.languages.contains("rs"));
+        assert_eq!(config.max_file_size, 256);
+    }
+
+    #[test]
+    fn loads_toml_config() {
+        let tmp = TempDir::new().unwrap();
+        let toml = "languages = [\"go\"]\ninclude_complexity = true\n";
+        fs::write(tmp.path().join(".fmmrc.toml"), toml).unwrap();
+
+        let config = Config::load_from_dir(tmp.path()).unwrap();
+        assert_eq!(config.languages.len(), 1);
+        assert!(config.languages.contains("go"));
+        assert!(config.include_complexity);
+    }
+
+    #[test]
+    fn prefers_json_over_other_formats_in_the_same_directory() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(tmp.path().join(".fmmrc.json"), r#"{ "languages": ["rs"] }"#).unwrap();
+        fs::write(tmp.path().join(".fmmrc.yaml"), "languages:\n  - py\n").unwrap();
+
+        let config = Config::load_from_dir(tmp.path()).unwrap();
+        assert_eq!(config.languages.len(), 1);
+        assert!(config.languages.contains("rs"));
+    }
+
+    #[test]
+    fn merges_an_ancestor_toml_with_a_nearer_json_override() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(
+            tmp.path().join(".fmmrc.toml"),
+            "languages = [\"rs\", \"go\"]\nmax_file_size = 2048\n",
+        )
+        .unwrap();
+        let nested = tmp.path().join("nested");
+        fs::create_dir(&nested).unwrap();
+        fs::write(nested.join(".fmmrc.json"), r#"{ "max_file_size": 64 }"#).unwrap();
+
+        let config = Config::load_from_dir(&nested).unwrap();
+        // `max_file_size` came from the nearer json file...
+        assert_eq!(config.max_file_size, 64);
+        // ...while `languages`, unset by the nearer layer, falls back to the
+        // ancestor toml file.
+        assert_eq!(config.languages.len(), 2);
+        assert!(config.languages.contains("rs"));
+        assert!(config.languages.contains("go"));
+    }
+
+    #[test]
+    fn handles_invalid_yaml_as_error() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(tmp.path().join(".fmmrc.yaml"), "languages: [rs, py\n").unwrap();
+        let result = Config::load_from_dir(tmp.path());
+        assert!(result.is_err());
+    }
+
     #[test]
     fn handles_invalid_json_as_error() {
         let tmp = TempDir::new().unwrap();
@@ -235,6 +787,55 @@ mod tests {
         assert_eq!(config.max_file_size, 1024);
     }
 
+    #[test]
+    fn for_extension_falls_back_to_base_when_no_override() {
+        let config = Config::default();
+        assert_eq!(
+            config.for_extension("rs"),
+            ResolvedConfig {
+                include_loc: config.include_loc,
+                include_complexity: config.include_complexity,
+                max_file_size: config.max_file_size,
+            }
+        );
+    }
+
+    #[test]
+    fn for_extension_applies_matching_override() {
+        let mut config = Config::default();
+        config.overrides.insert(
+            "rs".to_string(),
+            LanguageOverride {
+                include_complexity: Some(true),
+                max_file_size: Some(4096),
+                include_loc: None,
+            },
+        );
+
+        let resolved = config.for_extension("rs");
+        assert!(resolved.include_complexity);
+        assert_eq!(resolved.max_file_size, 4096);
+        assert_eq!(resolved.include_loc, config.include_loc);
+
+        // Unaffected extensions still get the base config.
+        let ts_resolved = config.for_extension("ts");
+        assert_eq!(ts_resolved.max_file_size, config.max_file_size);
+    }
+
+    #[test]
+    fn overrides_loaded_from_json_layer() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(
+            tmp.path().join(".fmmrc.json"),
+            r#"{"overrides": {"rs": {"include_complexity": true}}}"#,
+        )
+        .unwrap();
+
+        let config = Config::load_from_dir(tmp.path()).unwrap();
+        assert!(config.for_extension("rs").include_complexity);
+        assert!(!config.for_extension("py").include_complexity);
+    }
+
     #[test]
     fn config_serialization_roundtrip() {
         let config = Config::default();
@@ -245,4 +846,137 @@ mod tests {
         assert_eq!(config.include_complexity, deserialized.include_complexity);
         assert_eq!(config.max_file_size, deserialized.max_file_size);
     }
+
+    #[test]
+    fn project_defaults_load_toml_returns_default_for_missing_file() {
+        let tmp = TempDir::new().unwrap();
+        let defaults = ProjectDefaults::load_toml(&tmp.path().join("fmm.toml"));
+        assert!(defaults.gh.model.is_none());
+        assert!(defaults.compare.max_budget.is_none());
+    }
+
+    #[test]
+    fn project_defaults_load_toml_parses_fmm_toml() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("fmm.toml");
+        fs::write(
+            &path,
+            r#"
+            [gh]
+            model = "opus"
+            max_budget = 7.5
+
+            [compare]
+            format = "json"
+            "#,
+        )
+        .unwrap();
+
+        let defaults = ProjectDefaults::load_toml(&path);
+        assert_eq!(defaults.gh.model.as_deref(), Some("opus"));
+        assert_eq!(defaults.gh.max_budget, Some(7.5));
+        assert_eq!(defaults.compare.format.as_deref(), Some("json"));
+        assert!(defaults.gh.branch_prefix.is_none());
+    }
+
+    #[test]
+    fn project_defaults_merge_prefers_more_specific_layer() {
+        let mut broad = ProjectDefaults {
+            gh: GhDefaults {
+                model: Some("sonnet".to_string()),
+                branch_prefix: Some("fmm".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let specific = ProjectDefaults {
+            gh: GhDefaults {
+                model: Some("opus".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        broad.merge(specific);
+
+        // `model` was set by the more specific layer — it wins.
+        assert_eq!(broad.gh.model.as_deref(), Some("opus"));
+        // `branch_prefix` wasn't touched by the specific layer — the
+        // broader layer's value survives.
+        assert_eq!(broad.gh.branch_prefix.as_deref(), Some("fmm"));
+    }
+
+    #[test]
+    fn global_config_default_uses_the_shell_backend() {
+        let config = GlobalConfig::default();
+        assert_eq!(config.git_backend, "shell");
+        assert!(config.workspace_dir.ends_with("workspaces"));
+        assert!(config.profiles.is_empty());
+    }
+
+    #[test]
+    fn global_config_parses_named_profiles() {
+        let toml = r#"
+            [profiles.cheap]
+            model = "haiku"
+            max_turns = 10
+            max_budget = 1.0
+            allowed_tools = "Read,Grep"
+
+            [profiles.thorough]
+            model = "opus"
+            branch_prefix = "deep-fix"
+        "#;
+
+        let config: GlobalConfig = toml::from_str(toml).unwrap();
+        let cheap = config.profiles.get("cheap").unwrap();
+        assert_eq!(cheap.model.as_deref(), Some("haiku"));
+        assert_eq!(cheap.max_turns, Some(10));
+        assert_eq!(cheap.allowed_tools.as_deref(), Some("Read,Grep"));
+
+        let thorough = config.profiles.get("thorough").unwrap();
+        assert_eq!(thorough.branch_prefix.as_deref(), Some("deep-fix"));
+        assert_eq!(thorough.max_turns, None);
+    }
+
+    #[test]
+    fn global_config_load_returns_default_without_an_xdg_config_home() {
+        // `load()` always reads from the real `user_config_dir()`, so this
+        // only checks the no-file-found fallback behaves like `default()`,
+        // not that it's isolated from a config.toml a developer happens to
+        // have on this machine.
+        let config = GlobalConfig::load();
+        assert!(!config.git_backend.is_empty());
+    }
+
+    #[test]
+    fn project_defaults_schema_json_documents_expected_fields() {
+        let schema = ProjectDefaults::schema_json().unwrap();
+        assert!(schema.contains("\"gh\""));
+        assert!(schema.contains("\"compare\""));
+        assert!(schema.contains("max_budget"));
+        assert!(schema.contains("branch_prefix"));
+    }
+
+    #[test]
+    fn loads_string_and_array_aliases() {
+        let tmp = TempDir::new().unwrap();
+        let json = r#"{
+            "aliases": {
+                "gen": "generate src/ -n",
+                "ci": ["validate", "--strict"]
+            }
+        }"#;
+        fs::write(tmp.path().join(".fmmrc.json"), json).unwrap();
+
+        let config = Config::load_from_dir(tmp.path()).unwrap();
+        assert_eq!(
+            config.aliases.get("gen").unwrap().tokens(),
+            vec!["generate", "src/", "-n"]
+        );
+        assert_eq!(
+            config.aliases.get("ci").unwrap().tokens(),
+            vec!["validate", "--strict"]
+        );
+    }
 }
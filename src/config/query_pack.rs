@@ -0,0 +1,121 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// User-supplied tree-sitter queries, read from an `fmm.queries.toml` at
+/// the project root. Lets a user capture extra `custom_fields` per
+/// language — Rails `has_many`/`belongs_to` associations, TypeScript
+/// decorator names, and the like — without editing a parser's Rust
+/// source. Keyed by [`crate::parser::Parser::language_id`]; see
+/// [`QueryPackConfig::load`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct QueryPackConfig {
+    #[serde(default)]
+    pub languages: HashMap<String, LanguageQueryPack>,
+}
+
+/// Named tree-sitter queries for one language.
+///
+/// `exports`/`imports`/`dependencies` extend (never replace) whatever a
+/// builtin parser already finds for that language — a user fixing a gap
+/// in, say, TypeScript's export detection adds matches on top of the
+/// hardcoded queries rather than taking over extraction entirely. Each
+/// should capture the symbol as `@export`, `@import`, or `@dependency`
+/// respectively. A language with no builtin parser at all (one loaded via
+/// [`crate::parser::grammar`]) has nothing to extend, so these become its
+/// *only* source of exports/imports/dependencies.
+///
+/// Each key in `custom_fields` becomes a key in `ParseResult.custom_fields`;
+/// its value is every string captured by that query, the same way a
+/// builtin parser's own hard-coded queries are collected.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct LanguageQueryPack {
+    /// Query capturing exported symbols as `@export`.
+    #[serde(default)]
+    pub exports: Option<String>,
+    /// Query capturing imported module specifiers as `@import`.
+    #[serde(default)]
+    pub imports: Option<String>,
+    /// Query capturing dependency specifiers as `@dependency`.
+    #[serde(default)]
+    pub dependencies: Option<String>,
+    #[serde(default)]
+    pub custom_fields: HashMap<String, String>,
+}
+
+impl QueryPackConfig {
+    /// Load `root/fmm.queries.toml`. A missing or invalid file is treated
+    /// as an empty pack — user query packs are opt-in, not required.
+    pub fn load(root: &Path) -> Self {
+        std::fs::read_to_string(root.join("fmm.queries.toml"))
+            .ok()
+            .and_then(|content| toml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// The query pack configured for `language_id` (e.g. `"ruby"`,
+    /// `"typescript"`), if the user supplied one.
+    pub fn for_language(&self, language_id: &str) -> Option<&LanguageQueryPack> {
+        self.languages.get(language_id)
+    }
+
+    /// Render the machine-readable JSON Schema for `fmm.queries.toml`, for
+    /// editors that validate/autocomplete against it.
+    pub fn schema_json() -> anyhow::Result<String> {
+        let schema = schemars::schema_for!(QueryPackConfig);
+        Ok(serde_json::to_string_pretty(&schema)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn load_returns_default_for_missing_file() {
+        let tmp = TempDir::new().unwrap();
+        let pack = QueryPackConfig::load(tmp.path());
+        assert!(pack.languages.is_empty());
+        assert!(pack.for_language("ruby").is_none());
+    }
+
+    #[test]
+    fn load_parses_fmm_queries_toml() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join("fmm.queries.toml"),
+            r#"
+            [languages.ruby.custom_fields]
+            associations = "(call method: (identifier) @method (#match? @method \"^(has_many|belongs_to)$\"))"
+
+            [languages.typescript.custom_fields]
+            decorators = "(decorator (identifier) @name)"
+            "#,
+        )
+        .unwrap();
+
+        let pack = QueryPackConfig::load(tmp.path());
+        let ruby = pack.for_language("ruby").unwrap();
+        assert!(ruby.custom_fields.contains_key("associations"));
+        let ts = pack.for_language("typescript").unwrap();
+        assert!(ts.custom_fields.contains_key("decorators"));
+        assert!(pack.for_language("python").is_none());
+    }
+
+    #[test]
+    fn load_ignores_invalid_toml() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(tmp.path().join("fmm.queries.toml"), "not valid toml {{{").unwrap();
+        let pack = QueryPackConfig::load(tmp.path());
+        assert!(pack.languages.is_empty());
+    }
+
+    #[test]
+    fn schema_json_documents_expected_fields() {
+        let schema = QueryPackConfig::schema_json().unwrap();
+        assert!(schema.contains("\"languages\""));
+        assert!(schema.contains("custom_fields"));
+    }
+}
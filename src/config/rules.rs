@@ -0,0 +1,725 @@
+//! A small expression language for `.fmmrc.json`'s `rules` array, letting
+//! a user compute extra `custom_fields` or gate file inclusion from each
+//! file's already-extracted metadata instead of editing a parser.
+//!
+//! A rule is one string: `if <expr> then <action>`, where `<action>` is
+//! either a field assignment (`field_name = <expr>`, injected into
+//! [`crate::parser::ParseResult::custom_fields`]) or the literal
+//! `include` / `exclude` (folded into [`RuleSet::admits`]). `<expr>`
+//! supports identifiers resolved against a [`RuleContext`] (`exports`,
+//! `imports`, `dependencies`, `loc`, `path`), string/number literals, the
+//! operators `== != < > <= >= && || !`, and calls to a handful of helper
+//! functions (`count`, `contains`, `matches`, `ends_with`, `to_lower`).
+//!
+//! An unknown identifier evaluates to [`Value::Null`] rather than
+//! erroring, and `Null` on either side makes every comparison `false`
+//! except `!=`, which is `true` — so a rule testing a typo'd or
+//! not-yet-supported field with `==`/`<`/`>`/`<=`/`>=` just never fires
+//! instead of aborting the run. A rule whose text fails to parse is
+//! reported once (by [`RuleSet::compile`]) and dropped, so one bad rule
+//! in `.fmmrc.json` can't take down extraction for every file.
+
+use std::collections::HashMap;
+
+/// Runtime value produced by evaluating an [`Expr`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    Str(String),
+    Array(Vec<Value>),
+}
+
+impl Value {
+    /// Truthiness used by `&&`/`||`/the `if` condition: `Null`, `false`,
+    /// `0`, and `""` are falsy; everything else (including a non-empty
+    /// array) is truthy.
+    fn is_truthy(&self) -> bool {
+        match self {
+            Value::Null => false,
+            Value::Bool(b) => *b,
+            Value::Number(n) => *n != 0.0,
+            Value::Str(s) => !s.is_empty(),
+            Value::Array(items) => !items.is_empty(),
+        }
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        match self {
+            Value::Null => serde_json::Value::Null,
+            Value::Bool(b) => serde_json::Value::Bool(*b),
+            Value::Number(n) => serde_json::Number::from_f64(*n)
+                .map(serde_json::Value::Number)
+                .unwrap_or(serde_json::Value::Null),
+            Value::Str(s) => serde_json::Value::String(s.clone()),
+            Value::Array(items) => {
+                serde_json::Value::Array(items.iter().map(Value::to_json).collect())
+            }
+        }
+    }
+}
+
+/// The fields and helper functions a rule's expression can reference,
+/// built fresh per file from its [`crate::parser::Metadata`] and path.
+pub struct RuleContext<'a> {
+    pub exports: &'a [String],
+    pub imports: &'a [String],
+    pub dependencies: &'a [String],
+    pub loc: usize,
+    pub path: &'a str,
+}
+
+impl<'a> RuleContext<'a> {
+    fn resolve(&self, ident: &str) -> Value {
+        match ident {
+            "exports" => Value::Array(self.exports.iter().cloned().map(Value::Str).collect()),
+            "imports" => Value::Array(self.imports.iter().cloned().map(Value::Str).collect()),
+            "dependencies" => {
+                Value::Array(self.dependencies.iter().cloned().map(Value::Str).collect())
+            }
+            "loc" => Value::Number(self.loc as f64),
+            "path" => Value::Str(self.path.to_string()),
+            _ => Value::Null,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BinOp {
+    Eq,
+    Ne,
+    Lt,
+    Gt,
+    Le,
+    Ge,
+    And,
+    Or,
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Null,
+    Bool(bool),
+    Num(f64),
+    Str(String),
+    Ident(String),
+    Not(Box<Expr>),
+    BinOp(BinOp, Box<Expr>, Box<Expr>),
+    Call(String, Vec<Expr>),
+}
+
+impl Expr {
+    fn eval(&self, ctx: &RuleContext) -> Value {
+        match self {
+            Expr::Null => Value::Null,
+            Expr::Bool(b) => Value::Bool(*b),
+            Expr::Num(n) => Value::Number(*n),
+            Expr::Str(s) => Value::Str(s.clone()),
+            Expr::Ident(name) => ctx.resolve(name),
+            Expr::Not(inner) => Value::Bool(!inner.eval(ctx).is_truthy()),
+            Expr::BinOp(op, lhs, rhs) => eval_binop(*op, lhs, rhs, ctx),
+            Expr::Call(name, args) => {
+                let args: Vec<Value> = args.iter().map(|a| a.eval(ctx)).collect();
+                eval_call(name, &args)
+            }
+        }
+    }
+}
+
+fn eval_binop(op: BinOp, lhs: &Expr, rhs: &Expr, ctx: &RuleContext) -> Value {
+    // `&&`/`||` short-circuit without evaluating the right-hand side.
+    if op == BinOp::And {
+        let left = lhs.eval(ctx);
+        return if !left.is_truthy() {
+            Value::Bool(false)
+        } else {
+            Value::Bool(rhs.eval(ctx).is_truthy())
+        };
+    }
+    if op == BinOp::Or {
+        let left = lhs.eval(ctx);
+        return if left.is_truthy() {
+            Value::Bool(true)
+        } else {
+            Value::Bool(rhs.eval(ctx).is_truthy())
+        };
+    }
+
+    let left = lhs.eval(ctx);
+    let right = rhs.eval(ctx);
+
+    // `Null` on either side short-circuits every comparison to `false`
+    // (and `!=` to `true`) instead of erroring.
+    if left == Value::Null || right == Value::Null {
+        return Value::Bool(matches!(op, BinOp::Ne));
+    }
+
+    match op {
+        BinOp::Eq => Value::Bool(left == right),
+        BinOp::Ne => Value::Bool(left != right),
+        BinOp::Lt | BinOp::Gt | BinOp::Le | BinOp::Ge => {
+            let (Value::Number(l), Value::Number(r)) = (&left, &right) else {
+                return Value::Bool(false);
+            };
+            Value::Bool(match op {
+                BinOp::Lt => l < r,
+                BinOp::Gt => l > r,
+                BinOp::Le => l <= r,
+                BinOp::Ge => l >= r,
+                _ => unreachable!(),
+            })
+        }
+        BinOp::And | BinOp::Or => unreachable!("handled above with short-circuiting"),
+    }
+}
+
+fn eval_call(name: &str, args: &[Value]) -> Value {
+    match (name, args) {
+        ("count", [Value::Array(items)]) => Value::Number(items.len() as f64),
+        ("count", _) => Value::Null,
+        ("contains", [Value::Array(items), needle]) => {
+            Value::Bool(items.iter().any(|item| item == needle))
+        }
+        ("contains", [Value::Str(haystack), Value::Str(needle)]) => {
+            Value::Bool(haystack.contains(needle.as_str()))
+        }
+        ("contains", _) => Value::Bool(false),
+        ("matches", [Value::Str(s), Value::Str(pattern)]) => regex::Regex::new(pattern)
+            .map(|re| Value::Bool(re.is_match(s)))
+            .unwrap_or(Value::Bool(false)),
+        ("matches", _) => Value::Bool(false),
+        ("ends_with", [Value::Str(s), Value::Str(suffix)]) => {
+            Value::Bool(s.ends_with(suffix.as_str()))
+        }
+        ("ends_with", _) => Value::Bool(false),
+        ("to_lower", [Value::Str(s)]) => Value::Str(s.to_lowercase()),
+        ("to_lower", _) => Value::Null,
+        _ => Value::Null,
+    }
+}
+
+/// What a rule does once its `if` condition evaluates truthy.
+#[derive(Debug, Clone)]
+enum RuleAction {
+    /// `field = <expr>`: inject `<expr>`'s value into `custom_fields[field]`.
+    SetField { field: String, value: Expr },
+    /// The file should be kept even if something else would exclude it.
+    Include,
+    /// The file should be dropped from this extraction run.
+    Exclude,
+}
+
+/// One parsed, ready-to-evaluate rule from `.fmmrc.json`'s `rules` array.
+#[derive(Debug, Clone)]
+pub struct CompiledRule {
+    condition: Expr,
+    action: RuleAction,
+}
+
+/// The effect of evaluating one [`CompiledRule`] against a file.
+pub enum RuleOutcome {
+    /// The condition was false (or resolved to `Null`) — no effect.
+    NoMatch,
+    Field(String, serde_json::Value),
+    Include,
+    Exclude,
+}
+
+impl CompiledRule {
+    /// Parse a rule from its `.fmmrc.json` source text. Returns an error
+    /// (instead of panicking or silently defaulting) so [`RuleSet::compile`]
+    /// can report and skip it without taking the rest down.
+    pub fn parse(source: &str) -> anyhow::Result<Self> {
+        let tokens = tokenize(source)?;
+        let mut parser = RuleParser { tokens: &tokens, pos: 0 };
+        parser.parse_rule(source)
+    }
+
+    pub fn evaluate(&self, ctx: &RuleContext) -> RuleOutcome {
+        if !self.condition.eval(ctx).is_truthy() {
+            return RuleOutcome::NoMatch;
+        }
+        match &self.action {
+            RuleAction::SetField { field, value } => {
+                RuleOutcome::Field(field.clone(), value.eval(ctx).to_json())
+            }
+            RuleAction::Include => RuleOutcome::Include,
+            RuleAction::Exclude => RuleOutcome::Exclude,
+        }
+    }
+}
+
+/// A compiled `rules` array, ready to run against each file.
+#[derive(Debug, Clone, Default)]
+pub struct RuleSet {
+    rules: Vec<CompiledRule>,
+}
+
+impl RuleSet {
+    /// Compile every rule in `sources`, printing a warning for (and
+    /// dropping) any that fails to parse rather than propagating the
+    /// error — one malformed rule in `.fmmrc.json` shouldn't abort
+    /// extraction for every file in the project.
+    pub fn compile(sources: &[String]) -> Self {
+        let mut rules = Vec::with_capacity(sources.len());
+        for source in sources {
+            match CompiledRule::parse(source) {
+                Ok(rule) => rules.push(rule),
+                Err(e) => eprintln!("Skipping invalid rule '{source}': {e}"),
+            }
+        }
+        Self { rules }
+    }
+
+    /// Evaluate every rule against `ctx`, returning the `custom_fields`
+    /// entries computed by any matching field-assignment rules.
+    pub fn custom_fields(&self, ctx: &RuleContext) -> HashMap<String, serde_json::Value> {
+        let mut fields = HashMap::new();
+        for rule in &self.rules {
+            if let RuleOutcome::Field(key, value) = rule.evaluate(ctx) {
+                fields.insert(key, value);
+            }
+        }
+        fields
+    }
+
+    /// Whether `ctx`'s file should be kept: excluded if any `exclude`
+    /// rule matches, unless an `include` rule also matches (include wins,
+    /// the same precedence [`crate::config::Config::include`] has over
+    /// `exclude`). A `RuleSet` with no include/exclude rules always admits.
+    pub fn admits(&self, ctx: &RuleContext) -> bool {
+        let mut excluded = false;
+        let mut included = false;
+        for rule in &self.rules {
+            match rule.evaluate(ctx) {
+                RuleOutcome::Exclude => excluded = true,
+                RuleOutcome::Include => included = true,
+                RuleOutcome::Field(..) | RuleOutcome::NoMatch => {}
+            }
+        }
+        !excluded || included
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Num(f64),
+    If,
+    Then,
+    Include,
+    Exclude,
+    LParen,
+    RParen,
+    Comma,
+    Assign,
+    Eq,
+    Ne,
+    Lt,
+    Gt,
+    Le,
+    Ge,
+    And,
+    Or,
+    Not,
+}
+
+fn tokenize(source: &str) -> anyhow::Result<Vec<Token>> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '"' {
+            let mut s = String::new();
+            i += 1;
+            while i < chars.len() && chars[i] != '"' {
+                s.push(chars[i]);
+                i += 1;
+            }
+            anyhow::ensure!(i < chars.len(), "Unterminated string literal in rule: {source}");
+            i += 1;
+            tokens.push(Token::Str(s));
+        } else if c.is_ascii_digit() {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            let n = text
+                .parse::<f64>()
+                .with_context_parse(&text, source)?;
+            tokens.push(Token::Num(n));
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            tokens.push(match word.as_str() {
+                "if" => Token::If,
+                "then" => Token::Then,
+                "include" => Token::Include,
+                "exclude" => Token::Exclude,
+                _ => Token::Ident(word),
+            });
+        } else {
+            match c {
+                '(' => {
+                    tokens.push(Token::LParen);
+                    i += 1;
+                }
+                ')' => {
+                    tokens.push(Token::RParen);
+                    i += 1;
+                }
+                ',' => {
+                    tokens.push(Token::Comma);
+                    i += 1;
+                }
+                '=' if chars.get(i + 1) == Some(&'=') => {
+                    tokens.push(Token::Eq);
+                    i += 2;
+                }
+                '=' => {
+                    tokens.push(Token::Assign);
+                    i += 1;
+                }
+                '!' if chars.get(i + 1) == Some(&'=') => {
+                    tokens.push(Token::Ne);
+                    i += 2;
+                }
+                '!' => {
+                    tokens.push(Token::Not);
+                    i += 1;
+                }
+                '<' if chars.get(i + 1) == Some(&'=') => {
+                    tokens.push(Token::Le);
+                    i += 2;
+                }
+                '<' => {
+                    tokens.push(Token::Lt);
+                    i += 1;
+                }
+                '>' if chars.get(i + 1) == Some(&'=') => {
+                    tokens.push(Token::Ge);
+                    i += 2;
+                }
+                '>' => {
+                    tokens.push(Token::Gt);
+                    i += 1;
+                }
+                '&' if chars.get(i + 1) == Some(&'&') => {
+                    tokens.push(Token::And);
+                    i += 2;
+                }
+                '|' if chars.get(i + 1) == Some(&'|') => {
+                    tokens.push(Token::Or);
+                    i += 2;
+                }
+                _ => anyhow::bail!("Unexpected character '{c}' in rule: {source}"),
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+trait ParseFloatExt {
+    fn with_context_parse(self, text: &str, source: &str) -> anyhow::Result<f64>;
+}
+
+impl ParseFloatExt for Result<f64, std::num::ParseFloatError> {
+    fn with_context_parse(self, text: &str, source: &str) -> anyhow::Result<f64> {
+        self.map_err(|_| anyhow::anyhow!("Invalid number '{text}' in rule: {source}"))
+    }
+}
+
+/// Recursive-descent / Pratt parser over the condition expression;
+/// precedence (low to high): `||`, `&&`, comparisons, unary `!`, primary.
+struct RuleParser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+}
+
+impl<'a> RuleParser<'a> {
+    fn parse_rule(&mut self, source: &str) -> anyhow::Result<CompiledRule> {
+        self.expect(&Token::If, source)?;
+        let condition = self.parse_or(source)?;
+        self.expect(&Token::Then, source)?;
+        let action = self.parse_action(source)?;
+        anyhow::ensure!(
+            self.pos == self.tokens.len(),
+            "Unexpected trailing input in rule: {source}"
+        );
+        Ok(CompiledRule { condition, action })
+    }
+
+    fn parse_action(&mut self, source: &str) -> anyhow::Result<RuleAction> {
+        match self.peek() {
+            Some(Token::Include) => {
+                self.pos += 1;
+                Ok(RuleAction::Include)
+            }
+            Some(Token::Exclude) => {
+                self.pos += 1;
+                Ok(RuleAction::Exclude)
+            }
+            Some(Token::Ident(name)) => {
+                let field = name.clone();
+                self.pos += 1;
+                self.expect(&Token::Assign, source)?;
+                let value = self.parse_or(source)?;
+                Ok(RuleAction::SetField { field, value })
+            }
+            _ => anyhow::bail!(
+                "Expected 'include', 'exclude', or 'field = value' after 'then' in rule: {source}"
+            ),
+        }
+    }
+
+    fn parse_or(&mut self, source: &str) -> anyhow::Result<Expr> {
+        let mut lhs = self.parse_and(source)?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            let rhs = self.parse_and(source)?;
+            lhs = Expr::BinOp(BinOp::Or, Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_and(&mut self, source: &str) -> anyhow::Result<Expr> {
+        let mut lhs = self.parse_comparison(source)?;
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            let rhs = self.parse_comparison(source)?;
+            lhs = Expr::BinOp(BinOp::And, Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_comparison(&mut self, source: &str) -> anyhow::Result<Expr> {
+        let lhs = self.parse_unary(source)?;
+        let op = match self.peek() {
+            Some(Token::Eq) => BinOp::Eq,
+            Some(Token::Ne) => BinOp::Ne,
+            Some(Token::Lt) => BinOp::Lt,
+            Some(Token::Gt) => BinOp::Gt,
+            Some(Token::Le) => BinOp::Le,
+            Some(Token::Ge) => BinOp::Ge,
+            _ => return Ok(lhs),
+        };
+        self.pos += 1;
+        let rhs = self.parse_unary(source)?;
+        Ok(Expr::BinOp(op, Box::new(lhs), Box::new(rhs)))
+    }
+
+    fn parse_unary(&mut self, source: &str) -> anyhow::Result<Expr> {
+        if self.peek() == Some(&Token::Not) {
+            self.pos += 1;
+            let inner = self.parse_unary(source)?;
+            return Ok(Expr::Not(Box::new(inner)));
+        }
+        self.parse_primary(source)
+    }
+
+    fn parse_primary(&mut self, source: &str) -> anyhow::Result<Expr> {
+        match self.advance(source)?.clone() {
+            Token::Num(n) => Ok(Expr::Num(n)),
+            Token::Str(s) => Ok(Expr::Str(s)),
+            Token::LParen => {
+                let inner = self.parse_or(source)?;
+                self.expect(&Token::RParen, source)?;
+                Ok(inner)
+            }
+            Token::Ident(name) => {
+                if name == "null" {
+                    return Ok(Expr::Null);
+                }
+                if name == "true" {
+                    return Ok(Expr::Bool(true));
+                }
+                if name == "false" {
+                    return Ok(Expr::Bool(false));
+                }
+                if self.peek() == Some(&Token::LParen) {
+                    self.pos += 1;
+                    let mut args = Vec::new();
+                    if self.peek() != Some(&Token::RParen) {
+                        loop {
+                            args.push(self.parse_or(source)?);
+                            if self.peek() == Some(&Token::Comma) {
+                                self.pos += 1;
+                            } else {
+                                break;
+                            }
+                        }
+                    }
+                    self.expect(&Token::RParen, source)?;
+                    Ok(Expr::Call(name, args))
+                } else {
+                    Ok(Expr::Ident(name))
+                }
+            }
+            other => anyhow::bail!("Unexpected token {other:?} in rule: {source}"),
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn advance(&mut self, source: &str) -> anyhow::Result<&Token> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .ok_or_else(|| anyhow::anyhow!("Unexpected end of rule: {source}"))?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn expect(&mut self, expected: &Token, source: &str) -> anyhow::Result<()> {
+        if self.peek() == Some(expected) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            anyhow::bail!(
+                "Expected {expected:?} but found {:?} in rule: {source}",
+                self.peek()
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx<'a>(
+        exports: &'a [String],
+        imports: &'a [String],
+        dependencies: &'a [String],
+        loc: usize,
+        path: &'a str,
+    ) -> RuleContext<'a> {
+        RuleContext { exports, imports, dependencies, loc, path }
+    }
+
+    #[test]
+    fn field_assignment_injects_custom_field_when_condition_true() {
+        let rule =
+            CompiledRule::parse(r#"if loc > 10 then big = true"#).unwrap();
+        let c = ctx(&[], &[], &[], 20, "a.rs");
+        match rule.evaluate(&c) {
+            RuleOutcome::Field(key, value) => {
+                assert_eq!(key, "big");
+                assert_eq!(value, serde_json::Value::Bool(true));
+            }
+            _ => panic!("expected a Field outcome"),
+        }
+    }
+
+    #[test]
+    fn field_assignment_no_match_when_condition_false() {
+        let rule = CompiledRule::parse(r#"if loc > 100 then big = true"#).unwrap();
+        let c = ctx(&[], &[], &[], 20, "a.rs");
+        assert!(matches!(rule.evaluate(&c), RuleOutcome::NoMatch));
+    }
+
+    #[test]
+    fn contains_and_count_combine_with_and() {
+        let rule = CompiledRule::parse(
+            r#"if contains(imports, "jwt") && count(exports) > 0 then security_sensitive = true"#,
+        )
+        .unwrap();
+        let imports = vec!["jwt".to_string()];
+        let exports = vec!["decode".to_string()];
+        let c = ctx(&exports, &imports, &[], 5, "auth.rs");
+        assert!(matches!(rule.evaluate(&c), RuleOutcome::Field(..)));
+    }
+
+    #[test]
+    fn unknown_identifier_resolves_to_null_and_short_circuits_eq_to_false() {
+        let rule = CompiledRule::parse(r#"if not_a_real_field == "x" then tag = true"#).unwrap();
+        let c = ctx(&[], &[], &[], 0, "a.rs");
+        assert!(matches!(rule.evaluate(&c), RuleOutcome::NoMatch));
+    }
+
+    #[test]
+    fn unknown_identifier_ne_short_circuits_to_true() {
+        let rule = CompiledRule::parse(r#"if not_a_real_field != "x" then tag = true"#).unwrap();
+        let c = ctx(&[], &[], &[], 0, "a.rs");
+        assert!(matches!(rule.evaluate(&c), RuleOutcome::Field(..)));
+    }
+
+    #[test]
+    fn matches_evaluates_regex_against_path() {
+        let rule = CompiledRule::parse(r#"if matches(path, "_test\.rs$") then is_test = true"#)
+            .unwrap();
+        let c = ctx(&[], &[], &[], 0, "foo_test.rs");
+        assert!(matches!(rule.evaluate(&c), RuleOutcome::Field(..)));
+        let c = ctx(&[], &[], &[], 0, "foo.rs");
+        assert!(matches!(rule.evaluate(&c), RuleOutcome::NoMatch));
+    }
+
+    #[test]
+    fn ends_with_and_to_lower_helpers() {
+        let rule =
+            CompiledRule::parse(r#"if ends_with(to_lower(path), ".rs") then lang = "rust""#)
+                .unwrap();
+        let c = ctx(&[], &[], &[], 0, "Main.RS");
+        match rule.evaluate(&c) {
+            RuleOutcome::Field(key, value) => {
+                assert_eq!(key, "lang");
+                assert_eq!(value, serde_json::Value::String("rust".to_string()));
+            }
+            _ => panic!("expected a Field outcome"),
+        }
+    }
+
+    #[test]
+    fn negation_operator_inverts_condition() {
+        let rule = CompiledRule::parse(r#"if !(loc > 100) then small = true"#).unwrap();
+        let c = ctx(&[], &[], &[], 5, "a.rs");
+        assert!(matches!(rule.evaluate(&c), RuleOutcome::Field(..)));
+    }
+
+    #[test]
+    fn exclude_action_admits_returns_false() {
+        let set = RuleSet::compile(&["if ends_with(path, \"_generated.rs\") then exclude".to_string()]);
+        let c = ctx(&[], &[], &[], 0, "schema_generated.rs");
+        assert!(!set.admits(&c));
+        let c = ctx(&[], &[], &[], 0, "schema.rs");
+        assert!(set.admits(&c));
+    }
+
+    #[test]
+    fn include_rule_overrides_a_matching_exclude_rule() {
+        let set = RuleSet::compile(&[
+            "if ends_with(path, \"_generated.rs\") then exclude".to_string(),
+            "if ends_with(path, \"keep_generated.rs\") then include".to_string(),
+        ]);
+        let c = ctx(&[], &[], &[], 0, "keep_generated.rs");
+        assert!(set.admits(&c));
+    }
+
+    #[test]
+    fn invalid_rule_syntax_is_skipped_not_fatal() {
+        let set = RuleSet::compile(&[
+            "this is not a rule".to_string(),
+            "if loc > 0 then tag = true".to_string(),
+        ]);
+        let c = ctx(&[], &[], &[], 1, "a.rs");
+        assert_eq!(set.custom_fields(&c).get("tag"), Some(&serde_json::Value::Bool(true)));
+    }
+
+    #[test]
+    fn malformed_rule_reports_parse_error() {
+        assert!(CompiledRule::parse("if loc > then tag = true").is_err());
+    }
+}
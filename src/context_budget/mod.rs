@@ -0,0 +1,138 @@
+//! Token-budget-aware context assembly, shared by
+//! `compare::orchestrator::build_fmm_context` and `gh::build_prompt`. Both
+//! hand the model a manifest-derived summary of the codebase, and both need
+//! to stop doing that verbatim once the summary is big enough to blow past
+//! the model's context window (and inflate cost in the process). Trimming
+//! needs a consistent, deterministic policy: rank candidates by graph
+//! in-degree — how many other files import them, from `Manifest::importers`
+//! — and drop the least-depended-on ones first until the render fits.
+
+/// Which order a context-budgeting pass drops candidates in once the
+/// render exceeds its budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RankingStrategy {
+    /// Drop the lowest in-degree (least-depended-on) candidates first,
+    /// keeping hub files that more of the codebase relies on.
+    InDegree,
+    /// Drop candidates in the order they were given, ignoring the
+    /// dependency graph. A naive baseline for measuring how much the
+    /// in-degree ranking actually buys a comparison run.
+    Arbitrary,
+}
+
+/// Rough token estimate for a string: `chars / 4`, the heuristic commonly
+/// quoted for English prose and source code. Not a real tokenizer, but
+/// good enough to budget against without pulling one in just for this.
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// One renderable, droppable unit of context — a manifest file entry or a
+/// `ResolvedReference` — with `in_degree` precomputed by the caller from
+/// whatever "who imports this" index it has on hand.
+pub struct Candidate {
+    pub key: String,
+    pub in_degree: usize,
+    pub rendered: String,
+}
+
+/// Decide which of `candidates` to drop so that `preamble_tokens` plus
+/// every kept candidate's rendered size fits within `budget_tokens`.
+/// Always leaves at least one candidate, even if it alone is over budget,
+/// so a budgeted context is never emptied outright.
+///
+/// Returns the evicted keys, in the order they were dropped.
+pub fn evict_to_budget(
+    candidates: &[Candidate],
+    preamble_tokens: usize,
+    budget_tokens: usize,
+    strategy: RankingStrategy,
+) -> Vec<String> {
+    if candidates.len() <= 1 {
+        return Vec::new();
+    }
+
+    let mut total_tokens: usize = preamble_tokens
+        + candidates
+            .iter()
+            .map(|c| estimate_tokens(&c.rendered))
+            .sum::<usize>();
+
+    if total_tokens <= budget_tokens {
+        return Vec::new();
+    }
+
+    let mut drop_order: Vec<&Candidate> = candidates.iter().collect();
+    if strategy == RankingStrategy::InDegree {
+        // Stable sort: ties keep the caller's original order, so eviction
+        // is deterministic for a given manifest.
+        drop_order.sort_by_key(|c| c.in_degree);
+    }
+
+    let mut elided = Vec::new();
+    for candidate in drop_order {
+        if total_tokens <= budget_tokens || elided.len() + 1 >= candidates.len() {
+            break;
+        }
+        total_tokens -= estimate_tokens(&candidate.rendered);
+        elided.push(candidate.key.clone());
+    }
+
+    elided
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(key: &str, in_degree: usize, rendered: &str) -> Candidate {
+        Candidate {
+            key: key.to_string(),
+            in_degree,
+            rendered: rendered.to_string(),
+        }
+    }
+
+    #[test]
+    fn estimate_tokens_is_chars_over_four() {
+        assert_eq!(estimate_tokens("abcd"), 1);
+        assert_eq!(estimate_tokens("abcde"), 2);
+        assert_eq!(estimate_tokens(""), 0);
+    }
+
+    #[test]
+    fn no_eviction_when_within_budget() {
+        let candidates = vec![candidate("a", 0, "1234"), candidate("b", 5, "1234")];
+        let elided = evict_to_budget(&candidates, 0, 100, RankingStrategy::InDegree);
+        assert!(elided.is_empty());
+    }
+
+    #[test]
+    fn evicts_lowest_in_degree_first() {
+        let candidates = vec![
+            candidate("hub", 10, "12345678"),
+            candidate("leaf", 0, "12345678"),
+            candidate("mid", 3, "12345678"),
+        ];
+        // Each candidate is 2 tokens; budget for only one.
+        let elided = evict_to_budget(&candidates, 0, 2, RankingStrategy::InDegree);
+        assert_eq!(elided, vec!["leaf".to_string(), "mid".to_string()]);
+    }
+
+    #[test]
+    fn always_keeps_at_least_one_candidate() {
+        let candidates = vec![candidate("only-one-left", 0, "a".repeat(400).as_str())];
+        let elided = evict_to_budget(&candidates, 0, 1, RankingStrategy::InDegree);
+        assert!(elided.is_empty());
+    }
+
+    #[test]
+    fn arbitrary_strategy_evicts_in_given_order() {
+        let candidates = vec![
+            candidate("first", 10, "12345678"),
+            candidate("second", 0, "12345678"),
+        ];
+        let elided = evict_to_budget(&candidates, 0, 2, RankingStrategy::Arbitrary);
+        assert_eq!(elided, vec!["first".to_string()]);
+    }
+}
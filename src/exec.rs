@@ -0,0 +1,43 @@
+//! Hardened subprocess spawning for code that shells out while operating on
+//! untrusted, cloned repositories (`gh issue`, `gh batch`).
+//!
+//! [`std::process::Command::new`] resolves a bare program name the way the
+//! OS loader does — and on Windows that search checks the current working
+//! directory *before* `PATH`. Since fmm clones and runs against arbitrary
+//! third-party repos, a `git.exe`/`gh.exe` planted in a cloned repo's
+//! working tree would silently run instead of the real binary. [`command`]
+//! closes that hole the way starship's `create_command` helper does:
+//! resolve the absolute path via [`which`] up front and build the
+//! [`Command`] from that resolved path, never from the bare name.
+//!
+//! `clippy.toml`'s `disallowed-methods` lint flags any new
+//! `std::process::Command::new` call so this doesn't regress silently.
+
+use anyhow::{Context, Result};
+use std::process::Command;
+
+/// Resolve `name` to an absolute path on `PATH` and build a [`Command`]
+/// from it, rather than leaving the bare name for the OS loader to
+/// resolve. Errors clearly when `name` isn't found, instead of deferring
+/// to a less specific spawn failure.
+pub fn command(name: &str) -> Result<Command> {
+    let resolved = which::which(name).with_context(|| format!("`{name}` not found on PATH"))?;
+    Ok(Command::new(resolved))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_a_binary_that_exists_on_path() {
+        let program = if cfg!(windows) { "cmd" } else { "sh" };
+        assert!(command(program).is_ok());
+    }
+
+    #[test]
+    fn errors_clearly_for_a_missing_binary() {
+        let err = command("definitely-not-a-real-binary-xyz").unwrap_err();
+        assert!(err.to_string().contains("not found on PATH"));
+    }
+}
@@ -0,0 +1,456 @@
+//! Content-hash cache for rendered frontmatter, so a batch run over a
+//! mostly-unchanged tree can reuse what it already rendered instead of
+//! re-parsing every file. The key folds in the file's content hash, the
+//! parser it resolved to (`language_id`), and [`PARSER_VERSION`], so
+//! changing either invalidates every entry instead of serving a stale
+//! render from a different parser or grammar revision.
+//!
+//! Each entry also carries a cargo-style [`Fingerprint`] (mtime + length)
+//! recorded at render time. [`FrontmatterCache::get_by_fingerprint`] lets a
+//! caller skip reading and hashing a file's contents entirely when its
+//! `stat()` hasn't moved since — the content hash in [`get`](FrontmatterCache::get)
+//! is still the source of truth once a file's content is in hand.
+//!
+//! Persisted as a small fixed-layout binary archive (magic + format
+//! version, then one record per cached file) in the spirit of a zero-copy
+//! format like `rkyv` — validated wholesale on load rather than trusted
+//! record-by-record, so a bad magic, a version bump, or a truncated/
+//! corrupt archive just means "cache miss everything" and a full
+//! regeneration, never a crash.
+
+use std::collections::HashMap;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+const MAGIC: &[u8; 4] = b"FMMC";
+const FORMAT_VERSION: u32 = 2;
+
+/// `mtime` (seconds since the Unix epoch) and byte length of a file at the
+/// moment it was last cached, modeled on cargo's build fingerprinting: a
+/// cheap `stat()` call is enough to tell "definitely unchanged" from
+/// "maybe changed" without reading or hashing the file's contents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fingerprint {
+    pub(crate) mtime: i64,
+    pub(crate) len: u64,
+}
+
+impl Fingerprint {
+    /// `None` if the file's metadata can't be read (e.g. it vanished
+    /// between being listed and being stat'd) or its mtime predates the
+    /// Unix epoch — callers treat either as "no fingerprint available" and
+    /// fall back to reading the file.
+    pub fn of(path: &Path) -> Option<Self> {
+        let meta = fs::metadata(path).ok()?;
+        let mtime = meta.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+        Some(Self {
+            mtime: mtime.as_secs() as i64,
+            len: meta.len(),
+        })
+    }
+}
+
+/// One file's last-rendered frontmatter, keyed by its content hash and
+/// stat fingerprint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CacheEntry {
+    hash: u64,
+    fingerprint: Fingerprint,
+    rendered: String,
+    generated_at: String,
+}
+
+/// Content-hash cache for rendered frontmatter blocks, keyed by file path.
+/// `render()` callers consult [`get`](Self::get) first; on a miss they
+/// render normally and call [`insert`](Self::insert) so the next run over
+/// the same unchanged file is free.
+#[derive(Debug, Default)]
+pub struct FrontmatterCache {
+    entries: HashMap<PathBuf, CacheEntry>,
+    dirty: bool,
+}
+
+/// Bumped whenever a parser/grammar upgrade could change what the same
+/// source renders to, so a cache built by an older `fmm` binary never
+/// looks like a hit against the new one.
+pub const PARSER_VERSION: u32 = 1;
+
+/// Hashes `content` together with the parser that produced it
+/// (`language_id`) and [`PARSER_VERSION`], so a cache entry survives a
+/// process restart but still misses if the file starts resolving to a
+/// different parser or the parser itself changed, even though the
+/// content is byte-for-byte identical.
+pub fn content_hash(content: &str, language_id: &str, parser_version: u32) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    content.hash(&mut hasher);
+    language_id.hash(&mut hasher);
+    parser_version.hash(&mut hasher);
+    hasher.finish()
+}
+
+impl FrontmatterCache {
+    /// Load a previously persisted cache. A missing, truncated, corrupt, or
+    /// version-mismatched archive is treated as an empty cache rather than
+    /// an error — the next render just repopulates it.
+    pub fn load(path: &Path) -> Self {
+        fs::read(path)
+            .ok()
+            .and_then(|bytes| Self::decode(&bytes))
+            .unwrap_or_default()
+    }
+
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        let mut reader = ByteReader::new(bytes);
+        if reader.read_bytes(MAGIC.len())? != MAGIC {
+            return None;
+        }
+        if reader.read_u32()? != FORMAT_VERSION {
+            return None;
+        }
+
+        let count = reader.read_u32()?;
+        let mut entries = HashMap::with_capacity(count as usize);
+        for _ in 0..count {
+            let path = PathBuf::from(reader.read_string()?);
+            let hash = reader.read_u64()?;
+            let mtime = reader.read_u64()? as i64;
+            let len = reader.read_u64()?;
+            let rendered = reader.read_string()?;
+            let generated_at = reader.read_string()?;
+            entries.insert(
+                path,
+                CacheEntry {
+                    hash,
+                    fingerprint: Fingerprint { mtime, len },
+                    rendered,
+                    generated_at,
+                },
+            );
+        }
+        reader.expect_exhausted()?;
+
+        Some(Self {
+            entries,
+            dirty: false,
+        })
+    }
+
+    /// Persist the cache if anything changed since it was loaded or
+    /// created; a no-op otherwise.
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&(self.entries.len() as u32).to_le_bytes());
+        for (path, entry) in &self.entries {
+            write_string(&mut bytes, &path.to_string_lossy());
+            bytes.extend_from_slice(&entry.hash.to_le_bytes());
+            bytes.extend_from_slice(&(entry.fingerprint.mtime as u64).to_le_bytes());
+            bytes.extend_from_slice(&entry.fingerprint.len.to_le_bytes());
+            write_string(&mut bytes, &entry.rendered);
+            write_string(&mut bytes, &entry.generated_at);
+        }
+        fs::write(path, bytes)
+    }
+
+    /// The cached render for `file`, if its content hash still matches.
+    pub fn get(&self, file: &Path, hash: u64) -> Option<&str> {
+        self.entries
+            .get(file)
+            .filter(|entry| entry.hash == hash)
+            .map(|entry| entry.rendered.as_str())
+    }
+
+    /// The cached render for `file` if its `mtime`/length haven't moved
+    /// since it was cached — a pure `stat()` check that never reads or
+    /// hashes the file's contents. Skipped entirely (falls back to
+    /// [`get`](Self::get)) when `fingerprint` is `None`, e.g. because the
+    /// file's metadata couldn't be read.
+    pub fn get_by_fingerprint(&self, file: &Path, fingerprint: Option<Fingerprint>) -> Option<&str> {
+        let fingerprint = fingerprint?;
+        self.entries
+            .get(file)
+            .filter(|entry| entry.fingerprint == fingerprint)
+            .map(|entry| entry.rendered.as_str())
+    }
+
+    /// Records a freshly rendered frontmatter for `file` at `hash`,
+    /// replacing whatever was cached for it before. `fingerprint` is
+    /// `None` when the file's metadata couldn't be stat'd at render time;
+    /// such entries are only ever reachable via [`get`](Self::get), never
+    /// [`get_by_fingerprint`](Self::get_by_fingerprint).
+    pub fn insert(
+        &mut self,
+        file: PathBuf,
+        hash: u64,
+        fingerprint: Option<Fingerprint>,
+        rendered: String,
+        generated_at: String,
+    ) {
+        self.entries.insert(
+            file,
+            CacheEntry {
+                hash,
+                fingerprint: fingerprint.unwrap_or(Fingerprint { mtime: -1, len: u64::MAX }),
+                rendered,
+                generated_at,
+            },
+        );
+        self.dirty = true;
+    }
+
+    /// Number of files currently cached (exposed for diagnostics/tests).
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+pub(crate) fn write_string(bytes: &mut Vec<u8>, s: &str) {
+    bytes.extend_from_slice(&(s.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(s.as_bytes());
+}
+
+/// Bounds-checked sequential reader over an archive's bytes. Every method
+/// returns `None` instead of panicking on truncated or malformed input, so
+/// a corrupt cache file degrades to "treat as empty" rather than crashing
+/// the run that would have rebuilt it anyway. Shared with
+/// [`super::manifest::SourceManifest`], which persists in the same
+/// magic-then-fields archive shape.
+pub(crate) struct ByteReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    pub(crate) fn read_bytes(&mut self, n: usize) -> Option<&'a [u8]> {
+        let slice = self.bytes.get(self.pos..self.pos + n)?;
+        self.pos += n;
+        Some(slice)
+    }
+
+    pub(crate) fn read_u32(&mut self) -> Option<u32> {
+        Some(u32::from_le_bytes(self.read_bytes(4)?.try_into().ok()?))
+    }
+
+    pub(crate) fn read_u64(&mut self) -> Option<u64> {
+        Some(u64::from_le_bytes(self.read_bytes(8)?.try_into().ok()?))
+    }
+
+    pub(crate) fn read_string(&mut self) -> Option<String> {
+        let len = self.read_u32()? as usize;
+        let bytes = self.read_bytes(len)?;
+        String::from_utf8(bytes.to_vec()).ok()
+    }
+
+    pub(crate) fn expect_exhausted(&self) -> Option<()> {
+        (self.pos == self.bytes.len()).then_some(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn content_hash_differs_for_different_content() {
+        assert_ne!(
+            content_hash("a", "rust", PARSER_VERSION),
+            content_hash("b", "rust", PARSER_VERSION)
+        );
+        assert_eq!(
+            content_hash("same", "rust", PARSER_VERSION),
+            content_hash("same", "rust", PARSER_VERSION)
+        );
+    }
+
+    #[test]
+    fn content_hash_differs_for_different_language_id() {
+        assert_ne!(
+            content_hash("same", "rust", PARSER_VERSION),
+            content_hash("same", "typescript", PARSER_VERSION)
+        );
+    }
+
+    #[test]
+    fn content_hash_differs_for_different_parser_version() {
+        assert_ne!(
+            content_hash("same", "rust", 1),
+            content_hash("same", "rust", 2)
+        );
+    }
+
+    #[test]
+    fn get_misses_on_empty_cache() {
+        let cache = FrontmatterCache::default();
+        assert!(cache.get(Path::new("a.rs"), 1).is_none());
+    }
+
+    #[test]
+    fn insert_then_get_round_trips_within_one_run() {
+        let mut cache = FrontmatterCache::default();
+        cache.insert(
+            PathBuf::from("a.rs"),
+            42,
+            None,
+            "---\nfile: a.rs\n".to_string(),
+            "2026-01-01".to_string(),
+        );
+        assert_eq!(cache.get(Path::new("a.rs"), 42), Some("---\nfile: a.rs\n"));
+    }
+
+    #[test]
+    fn get_misses_when_hash_changed() {
+        let mut cache = FrontmatterCache::default();
+        cache.insert(
+            PathBuf::from("a.rs"),
+            42,
+            None,
+            "old".to_string(),
+            "2026-01-01".to_string(),
+        );
+        assert!(cache.get(Path::new("a.rs"), 43).is_none());
+    }
+
+    #[test]
+    fn get_by_fingerprint_hits_without_consulting_hash() {
+        let mut cache = FrontmatterCache::default();
+        let fp = Fingerprint { mtime: 100, len: 5 };
+        cache.insert(
+            PathBuf::from("a.rs"),
+            42,
+            Some(fp),
+            "---\nfile: a.rs\n".to_string(),
+            "2026-01-01".to_string(),
+        );
+        assert_eq!(
+            cache.get_by_fingerprint(Path::new("a.rs"), Some(fp)),
+            Some("---\nfile: a.rs\n")
+        );
+    }
+
+    #[test]
+    fn get_by_fingerprint_misses_when_mtime_or_len_changed() {
+        let mut cache = FrontmatterCache::default();
+        cache.insert(
+            PathBuf::from("a.rs"),
+            42,
+            Some(Fingerprint { mtime: 100, len: 5 }),
+            "---\nfile: a.rs\n".to_string(),
+            "2026-01-01".to_string(),
+        );
+        assert!(cache
+            .get_by_fingerprint(Path::new("a.rs"), Some(Fingerprint { mtime: 101, len: 5 }))
+            .is_none());
+    }
+
+    #[test]
+    fn get_by_fingerprint_misses_when_none_given() {
+        let mut cache = FrontmatterCache::default();
+        cache.insert(
+            PathBuf::from("a.rs"),
+            42,
+            Some(Fingerprint { mtime: 100, len: 5 }),
+            "---\nfile: a.rs\n".to_string(),
+            "2026-01-01".to_string(),
+        );
+        assert!(cache.get_by_fingerprint(Path::new("a.rs"), None).is_none());
+    }
+
+    #[test]
+    fn save_then_load_round_trips_to_disk() {
+        let tmp = TempDir::new().unwrap();
+        let cache_path = tmp.path().join("cache.bin");
+
+        let mut cache = FrontmatterCache::default();
+        let fp = Fingerprint { mtime: 1_700_000_000, len: 42 };
+        cache.insert(
+            PathBuf::from("src/lib.rs"),
+            7,
+            Some(fp),
+            "---\nfile: src/lib.rs\n".to_string(),
+            "2026-01-01".to_string(),
+        );
+        cache.save(&cache_path).unwrap();
+
+        let reloaded = FrontmatterCache::load(&cache_path);
+        assert_eq!(
+            reloaded.get(Path::new("src/lib.rs"), 7),
+            Some("---\nfile: src/lib.rs\n")
+        );
+        assert_eq!(
+            reloaded.get_by_fingerprint(Path::new("src/lib.rs"), Some(fp)),
+            Some("---\nfile: src/lib.rs\n")
+        );
+    }
+
+    #[test]
+    fn load_treats_missing_file_as_empty() {
+        let tmp = TempDir::new().unwrap();
+        let cache = FrontmatterCache::load(&tmp.path().join("does-not-exist.bin"));
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn load_discards_archive_with_wrong_magic() {
+        let tmp = TempDir::new().unwrap();
+        let cache_path = tmp.path().join("cache.bin");
+        fs::write(&cache_path, b"NOPE garbage bytes").unwrap();
+
+        let cache = FrontmatterCache::load(&cache_path);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn load_discards_archive_with_mismatched_version() {
+        let tmp = TempDir::new().unwrap();
+        let cache_path = tmp.path().join("cache.bin");
+
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&999u32.to_le_bytes());
+        bytes.extend_from_slice(&0u32.to_le_bytes());
+        fs::write(&cache_path, bytes).unwrap();
+
+        let cache = FrontmatterCache::load(&cache_path);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn load_discards_truncated_archive() {
+        let tmp = TempDir::new().unwrap();
+        let cache_path = tmp.path().join("cache.bin");
+
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&5u32.to_le_bytes()); // claims 5 entries, has none
+        fs::write(&cache_path, bytes).unwrap();
+
+        let cache = FrontmatterCache::load(&cache_path);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn save_is_a_no_op_when_nothing_changed() {
+        let tmp = TempDir::new().unwrap();
+        let cache_path = tmp.path().join("cache.bin");
+        let cache = FrontmatterCache::default();
+        cache.save(&cache_path).unwrap();
+        assert!(!cache_path.exists());
+    }
+}
@@ -0,0 +1,203 @@
+//! Build-graph context for Rust files, sourced from `cargo metadata`.
+//!
+//! `RustParser` only ever sees a file's own source text, so it can describe
+//! syntax (derives, unsafe blocks, ...) but nothing about the crate that
+//! file belongs to. This module shells out to `cargo metadata
+//! --format-version 1`, finds the crate owning a given file by the longest
+//! matching manifest directory, and resolves which of that crate's features
+//! are actually enabled, so sidecars can carry real build-graph context
+//! (crate name, version, edition, enabled features) instead of just
+//! per-file syntactic counts.
+//!
+//! Running `cargo metadata` is relatively slow (it shells out and may touch
+//! the network for registry info), so results are cached per workspace
+//! root for the lifetime of the process.
+
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{Mutex, OnceLock};
+
+/// A crate's build-graph context for one of its source files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrateContext {
+    pub name: String,
+    pub version: String,
+    pub edition: String,
+    pub features: Vec<String>,
+}
+
+struct CrateInfo {
+    manifest_dir: PathBuf,
+    name: String,
+    version: String,
+    edition: String,
+    features: Vec<String>,
+}
+
+/// Parsed `cargo metadata` output, reduced to what we need to look a file
+/// up by path.
+struct WorkspaceMetadata {
+    crates: Vec<CrateInfo>,
+}
+
+fn cache() -> &'static Mutex<HashMap<PathBuf, Option<WorkspaceMetadata>>> {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, Option<WorkspaceMetadata>>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Walks up from `start` looking for a directory containing `Cargo.toml`.
+fn find_workspace_root(start: &Path) -> Option<PathBuf> {
+    let mut dir = if start.is_dir() {
+        Some(start)
+    } else {
+        start.parent()
+    };
+    while let Some(candidate) = dir {
+        if candidate.join("Cargo.toml").is_file() {
+            return Some(candidate.to_path_buf());
+        }
+        dir = candidate.parent();
+    }
+    None
+}
+
+fn run_cargo_metadata(workspace_root: &Path) -> Option<Value> {
+    let output = Command::new("cargo")
+        .args(["metadata", "--format-version", "1"])
+        .current_dir(workspace_root)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    serde_json::from_slice(&output.stdout).ok()
+}
+
+/// Names of features enabled for `package_id` in the resolve graph, if one
+/// was produced. A missing or unresolvable entry just means "no features",
+/// not an error.
+fn resolved_features(metadata: &Value, package_id: &str) -> Vec<String> {
+    let nodes = metadata
+        .get("resolve")
+        .and_then(|r| r.get("nodes"))
+        .and_then(Value::as_array);
+    let Some(nodes) = nodes else {
+        return Vec::new();
+    };
+    nodes
+        .iter()
+        .find(|node| node.get("id").and_then(Value::as_str) == Some(package_id))
+        .and_then(|node| node.get("features"))
+        .and_then(Value::as_array)
+        .map(|features| {
+            features
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn parse_workspace_metadata(metadata: Value) -> WorkspaceMetadata {
+    let packages = metadata
+        .get("packages")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+
+    let crates = packages
+        .iter()
+        .filter_map(|package| {
+            let id = package.get("id").and_then(Value::as_str)?;
+            let name = package.get("name").and_then(Value::as_str)?.to_string();
+            let manifest_path = package.get("manifest_path").and_then(Value::as_str)?;
+            let manifest_dir = Path::new(manifest_path).parent()?.to_path_buf();
+            let version = package
+                .get("version")
+                .and_then(Value::as_str)
+                .unwrap_or("0.0.0")
+                .to_string();
+            let edition = package
+                .get("edition")
+                .and_then(Value::as_str)
+                .unwrap_or("2015")
+                .to_string();
+            let features = resolved_features(&metadata, id);
+
+            Some(CrateInfo {
+                manifest_dir,
+                name,
+                version,
+                edition,
+                features,
+            })
+        })
+        .collect();
+
+    WorkspaceMetadata { crates }
+}
+
+fn load_workspace_metadata(workspace_root: &Path) -> Option<WorkspaceMetadata> {
+    let mut guard = cache().lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(cached) = guard.get(workspace_root) {
+        return cached.as_ref().map(|metadata| WorkspaceMetadata {
+            crates: metadata
+                .crates
+                .iter()
+                .map(|c| CrateInfo {
+                    manifest_dir: c.manifest_dir.clone(),
+                    name: c.name.clone(),
+                    version: c.version.clone(),
+                    edition: c.edition.clone(),
+                    features: c.features.clone(),
+                })
+                .collect(),
+        });
+    }
+
+    let parsed = run_cargo_metadata(workspace_root).map(parse_workspace_metadata);
+    let result = parsed.as_ref().map(|metadata| WorkspaceMetadata {
+        crates: metadata
+            .crates
+            .iter()
+            .map(|c| CrateInfo {
+                manifest_dir: c.manifest_dir.clone(),
+                name: c.name.clone(),
+                version: c.version.clone(),
+                edition: c.edition.clone(),
+                features: c.features.clone(),
+            })
+            .collect(),
+    });
+    guard.insert(workspace_root.to_path_buf(), parsed);
+    result
+}
+
+/// Finds the crate that owns `file_path`, if the file sits under a
+/// `cargo metadata`-visible workspace. Nested crates are resolved by
+/// longest matching manifest directory, so a file in a workspace member
+/// is attributed to that member rather than the workspace root package.
+pub fn crate_context_for(file_path: &Path) -> Option<CrateContext> {
+    let absolute = if file_path.is_absolute() {
+        file_path.to_path_buf()
+    } else {
+        std::env::current_dir().ok()?.join(file_path)
+    };
+    let workspace_root = find_workspace_root(&absolute)?;
+    let metadata = load_workspace_metadata(&workspace_root)?;
+
+    metadata
+        .crates
+        .into_iter()
+        .filter(|c| absolute.starts_with(&c.manifest_dir))
+        .max_by_key(|c| c.manifest_dir.as_os_str().len())
+        .map(|c| CrateContext {
+            name: c.name,
+            version: c.version,
+            edition: c.edition,
+            features: c.features,
+        })
+}
@@ -0,0 +1,278 @@
+//! Project-level dependency resolution, run after every file's `Metadata`
+//! has been extracted. `Metadata.dependencies` are raw relative strings
+//! (`./db/pool`) handed out by each language's parser; this module resolves
+//! them against [`Manifest`]'s files (via [`Manifest::resolve_dependency`],
+//! the same lookup `reindex_importers` uses to build `importers`) into an
+//! in-memory forward graph, and reports the two things a raw string can't
+//! tell you on its own: which specs don't point at any file in the project,
+//! and which resolved edges loop back on themselves.
+
+use std::collections::HashMap;
+
+use crate::manifest::Manifest;
+
+/// A `dependencies` entry that didn't resolve to any file in the manifest.
+/// Bare package specs (`lodash`) are never reported here — they're expected
+/// to point outside the repo; only relative specs (`./foo`, `../bar`) that
+/// should resolve locally but don't are diagnostics-worthy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnresolvedImport {
+    pub importer: String,
+    pub spec: String,
+}
+
+/// A cycle in the resolved dependency graph: a sequence of file paths where
+/// each imports the next, and the last imports the first back.
+pub type Cycle = Vec<String>;
+
+/// Forward adjacency list of resolved dependency edges: `path -> [resolved
+/// targets path depends on]`, built by resolving every `dependencies` entry
+/// against `manifest`. This is the graph [`unresolved_imports`] and
+/// [`find_cycles`] both derive their reports from.
+pub fn resolved_edges(manifest: &Manifest) -> HashMap<String, Vec<String>> {
+    let mut edges: HashMap<String, Vec<String>> = HashMap::new();
+    for (path, entry) in &manifest.files {
+        let mut targets = Vec::new();
+        for spec in &entry.dependencies {
+            if let Some(target) = manifest.resolve_dependency(path, spec) {
+                if !targets.contains(&target) {
+                    targets.push(target);
+                }
+            }
+        }
+        edges.insert(path.clone(), targets);
+    }
+    edges
+}
+
+/// Every relative `dependencies` spec in `manifest` that doesn't resolve to
+/// a file the manifest knows about, sorted by (importer, spec) for stable
+/// output.
+pub fn unresolved_imports(manifest: &Manifest) -> Vec<UnresolvedImport> {
+    let mut unresolved = Vec::new();
+    for (path, entry) in &manifest.files {
+        for spec in &entry.dependencies {
+            let relative = spec.starts_with("./") || spec.starts_with("../");
+            if relative && manifest.resolve_dependency(path, spec).is_none() {
+                unresolved.push(UnresolvedImport {
+                    importer: path.clone(),
+                    spec: spec.clone(),
+                });
+            }
+        }
+    }
+    unresolved.sort_by(|a, b| (&a.importer, &a.spec).cmp(&(&b.importer, &b.spec)));
+    unresolved
+}
+
+/// Every cycle in `edges`, found via DFS with white/gray/black coloring: a
+/// gray node reached again (a back-edge into the current DFS stack) closes a
+/// cycle, reported as the stack slice from that node back to itself. Visits
+/// nodes in sorted order so the result is stable across runs.
+pub fn find_cycles(edges: &HashMap<String, Vec<String>>) -> Vec<Cycle> {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    enum Color {
+        White,
+        Gray,
+        Black,
+    }
+
+    fn visit(
+        node: &str,
+        edges: &HashMap<String, Vec<String>>,
+        color: &mut HashMap<String, Color>,
+        stack: &mut Vec<String>,
+        cycles: &mut Vec<Cycle>,
+    ) {
+        color.insert(node.to_string(), Color::Gray);
+        stack.push(node.to_string());
+
+        if let Some(targets) = edges.get(node) {
+            for target in targets {
+                match color.get(target.as_str()) {
+                    Some(Color::Gray) => {
+                        if let Some(pos) = stack.iter().position(|n| n == target) {
+                            let mut cycle = stack[pos..].to_vec();
+                            cycle.push(target.clone());
+                            cycles.push(cycle);
+                        }
+                    }
+                    Some(Color::Black) => {}
+                    Some(Color::White) | None => {
+                        visit(target, edges, color, stack, cycles);
+                    }
+                }
+            }
+        }
+
+        stack.pop();
+        color.insert(node.to_string(), Color::Black);
+    }
+
+    let mut color: HashMap<String, Color> =
+        edges.keys().map(|k| (k.clone(), Color::White)).collect();
+    let mut nodes: Vec<&String> = edges.keys().collect();
+    nodes.sort();
+
+    let mut stack = Vec::new();
+    let mut cycles = Vec::new();
+    for node in nodes {
+        if color.get(node.as_str()) == Some(&Color::White) {
+            visit(node, edges, &mut color, &mut stack, &mut cycles);
+        }
+    }
+    cycles
+}
+
+/// Invert [`resolved_edges`]'s forward adjacency list into `path -> [files
+/// that depend on path]`, so a caller can answer "what breaks if I change
+/// this file" instead of only "what does this file depend on". Targets are
+/// sorted for stable output; a node with no dependents still gets an empty
+/// entry so every file in `edges` is a key of the result.
+pub fn reverse_edges(edges: &HashMap<String, Vec<String>>) -> HashMap<String, Vec<String>> {
+    let mut reverse: HashMap<String, Vec<String>> =
+        edges.keys().map(|k| (k.clone(), Vec::new())).collect();
+    for (path, targets) in edges {
+        for target in targets {
+            reverse.entry(target.clone()).or_default().push(path.clone());
+        }
+    }
+    for dependents in reverse.values_mut() {
+        dependents.sort();
+    }
+    reverse
+}
+
+/// Every file that transitively depends on `start` (direct and indirect
+/// dependents), found by walking [`reverse_edges`]'s graph breadth-first.
+/// `start` itself is never included. Sorted for stable output.
+pub fn dependents_of(reverse: &HashMap<String, Vec<String>>, start: &str) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    let mut queue: std::collections::VecDeque<&str> = std::collections::VecDeque::new();
+    queue.push_back(start);
+
+    while let Some(node) = queue.pop_front() {
+        if let Some(dependents) = reverse.get(node) {
+            for dependent in dependents {
+                if dependent != start && seen.insert(dependent.clone()) {
+                    queue.push_back(dependent);
+                }
+            }
+        }
+    }
+
+    let mut result: Vec<String> = seen.into_iter().collect();
+    result.sort();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Metadata;
+
+    fn manifest_with(files: &[(&str, &[&str])]) -> Manifest {
+        let mut manifest = Manifest::new();
+        for (path, deps) in files {
+            let metadata = Metadata {
+                exports: vec![],
+                imports: vec![],
+                dependencies: deps.iter().map(|d| d.to_string()).collect(),
+                loc: 1,
+                re_exports: vec![],
+                test_loc: 0,
+                symbols: vec![],
+                diagnostics: crate::parser::Diagnostics::default(),
+            };
+            manifest.add_file(path, metadata);
+        }
+        manifest
+    }
+
+    #[test]
+    fn unresolved_imports_reports_specs_with_no_matching_file() {
+        let manifest = manifest_with(&[("src/a.ts", &["./missing"])]);
+        let unresolved = unresolved_imports(&manifest);
+        assert_eq!(unresolved.len(), 1);
+        assert_eq!(unresolved[0].importer, "src/a.ts");
+        assert_eq!(unresolved[0].spec, "./missing");
+    }
+
+    #[test]
+    fn unresolved_imports_ignores_bare_package_specs() {
+        let manifest = manifest_with(&[("src/a.ts", &["lodash"])]);
+        assert!(unresolved_imports(&manifest).is_empty());
+    }
+
+    #[test]
+    fn unresolved_imports_ignores_resolvable_specs() {
+        let manifest = manifest_with(&[("src/a.ts", &["./b"]), ("src/b.ts", &[])]);
+        assert!(unresolved_imports(&manifest).is_empty());
+    }
+
+    #[test]
+    fn resolved_edges_maps_specs_to_manifest_keys() {
+        let manifest = manifest_with(&[("src/a.ts", &["./b"]), ("src/b.ts", &[])]);
+        let edges = resolved_edges(&manifest);
+        assert_eq!(edges.get("src/a.ts"), Some(&vec!["src/b.ts".to_string()]));
+        assert_eq!(edges.get("src/b.ts"), Some(&vec![]));
+    }
+
+    #[test]
+    fn find_cycles_detects_a_two_node_cycle() {
+        let manifest = manifest_with(&[("src/a.ts", &["./b"]), ("src/b.ts", &["./a"])]);
+        let edges = resolved_edges(&manifest);
+        let cycles = find_cycles(&edges);
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].first(), cycles[0].last());
+        assert!(cycles[0].contains(&"src/a.ts".to_string()));
+        assert!(cycles[0].contains(&"src/b.ts".to_string()));
+    }
+
+    #[test]
+    fn find_cycles_returns_empty_for_acyclic_graph() {
+        let manifest = manifest_with(&[("src/a.ts", &["./b"]), ("src/b.ts", &[])]);
+        let edges = resolved_edges(&manifest);
+        assert!(find_cycles(&edges).is_empty());
+    }
+
+    #[test]
+    fn reverse_edges_inverts_the_forward_graph() {
+        let manifest = manifest_with(&[
+            ("src/a.ts", &["./c"]),
+            ("src/b.ts", &["./c"]),
+            ("src/c.ts", &[]),
+        ]);
+        let edges = resolved_edges(&manifest);
+        let reverse = reverse_edges(&edges);
+        assert_eq!(
+            reverse.get("src/c.ts"),
+            Some(&vec!["src/a.ts".to_string(), "src/b.ts".to_string()])
+        );
+        assert_eq!(reverse.get("src/a.ts"), Some(&vec![]));
+    }
+
+    #[test]
+    fn dependents_of_walks_transitive_reverse_edges() {
+        let manifest = manifest_with(&[
+            ("src/auth.ts", &["./db"]),
+            ("src/api.ts", &["./auth"]),
+            ("src/db.ts", &[]),
+        ]);
+        let edges = resolved_edges(&manifest);
+        let reverse = reverse_edges(&edges);
+        let dependents = dependents_of(&reverse, "src/db.ts");
+        assert_eq!(
+            dependents,
+            vec!["src/api.ts".to_string(), "src/auth.ts".to_string()]
+        );
+    }
+
+    #[test]
+    fn dependents_of_is_empty_for_a_leaf_with_no_dependents() {
+        let manifest = manifest_with(&[("src/a.ts", &["./b"]), ("src/b.ts", &[])]);
+        let edges = resolved_edges(&manifest);
+        let reverse = reverse_edges(&edges);
+        assert!(dependents_of(&reverse, "src/a.ts").is_empty());
+    }
+}
@@ -0,0 +1,259 @@
+//! Writes the inline `--- FMM ---` comment header that
+//! [`has_frontmatter`](super::has_frontmatter)/[`extract_frontmatter`](super::extract_frontmatter)
+//! recognize, the counterpart that was missing: something to produce that
+//! block in the first place from a computed [`Metadata`].
+//!
+//! Like `cargo fmt`, applying the header twice must be a no-op: the second
+//! run sees its own header, strips it via [`extract_frontmatter`](super::extract_frontmatter),
+//! and regenerates byte-identical content from the same `Metadata`.
+
+use serde_json::Value;
+
+use crate::parser::Metadata;
+
+/// How a language delimits comments, and therefore how the `--- FMM ---`
+/// header must be wrapped so it reads as a comment rather than code. A
+/// [`Line`](CommentStyle::Line) style repeats its prefix on every line (as a
+/// run of `//` lines would); a [`Block`](CommentStyle::Block) style opens
+/// once and closes once, since most block-comment syntaxes (`/* */`,
+/// `<!-- -->`) don't nest and repeating the delimiter mid-block would close
+/// the comment early.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentStyle {
+    /// e.g. `//` (Rust, TypeScript, Go) or `#` (Python) or `--` (SQL).
+    Line(&'static str),
+    /// e.g. `/* */` (CSS) or `<!-- -->` (HTML).
+    Block {
+        open: &'static str,
+        close: &'static str,
+    },
+}
+
+impl CommentStyle {
+    /// The comment style a parser's `language_id` (e.g. `"rust"`,
+    /// `"python"`) uses for single-line comments, or a block style for
+    /// languages with no line-comment syntax at all. Unrecognized ids fall
+    /// back to `//`, the most common style among fmm's builtin parsers.
+    pub fn for_language_id(language_id: &str) -> Self {
+        match language_id {
+            "python" | "ruby" => CommentStyle::Line("#"),
+            "sql" => CommentStyle::Line("--"),
+            "css" | "scss" => CommentStyle::Block {
+                open: "/*",
+                close: "*/",
+            },
+            "html" | "xml" | "markdown" => CommentStyle::Block {
+                open: "<!--",
+                close: "-->",
+            },
+            _ => CommentStyle::Line("//"),
+        }
+    }
+}
+
+/// Render a standalone `--- FMM ---` ... `---` block for `meta`, wrapped per
+/// `style`, with `custom` rendered as a trailing `custom: { ... }` line when
+/// present. Field order is fixed and `custom`'s object keys (if any) are
+/// sorted, so the same inputs always render the same bytes.
+pub fn render_header(meta: &Metadata, custom: Option<&Value>, style: CommentStyle) -> String {
+    let mut fields = Vec::new();
+
+    if !meta.exports.is_empty() {
+        let names: Vec<&str> = meta.exports.iter().map(|e| e.name.as_str()).collect();
+        fields.push(format!("exports: [{}]", names.join(", ")));
+    }
+    if !meta.imports.is_empty() {
+        fields.push(format!("imports: [{}]", meta.imports.join(", ")));
+    }
+    if !meta.dependencies.is_empty() {
+        fields.push(format!("dependencies: [{}]", meta.dependencies.join(", ")));
+    }
+    fields.push(format!("loc: {}", meta.loc));
+    if let Some(custom) = custom {
+        fields.push(format!("custom: {}", render_value_sorted(custom)));
+    }
+
+    match style {
+        CommentStyle::Line(prefix) => {
+            let mut lines = vec![format!("{prefix} --- FMM ---")];
+            lines.extend(fields.iter().map(|field| format!("{prefix} {field}")));
+            lines.push(format!("{prefix} ---"));
+            lines.join("\n")
+        }
+        CommentStyle::Block { open, close } => {
+            let mut lines = vec![format!("{open} --- FMM ---")];
+            lines.extend(fields.iter().cloned());
+            lines.push(format!("--- {close}"));
+            lines.join("\n")
+        }
+    }
+}
+
+/// Insert or replace the `--- FMM ---` block at the top of `source`, wrapped
+/// per `style`. An existing block (in any supported comment style) is
+/// stripped first via [`extract_frontmatter`](super::extract_frontmatter),
+/// so re-applying over a file this function already wrote reproduces the
+/// same output, and [`super::extract_frontmatter`] over that output
+/// reproduces the original body exactly.
+pub fn apply_header(source: &str, meta: &Metadata, style: CommentStyle) -> String {
+    let body = match super::extract_frontmatter(source) {
+        // Slice the body out of `source` itself: the `rest` that
+        // `extract_frontmatter` rejoins drops a trailing newline, which
+        // would make a second apply differ from the first.
+        Some((header, _)) if source.starts_with(&header) => {
+            source[header.len()..].trim_start().to_string()
+        }
+        Some((_, rest)) => rest,
+        None => source.to_string(),
+    };
+    format!("{}\n\n{}", render_header(meta, None, style), body)
+}
+
+/// `serde_json::Value::to_string`, but with object keys sorted so the same
+/// logical value always renders identically regardless of the map's
+/// iteration order.
+fn render_value_sorted(value: &Value) -> String {
+    match value {
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            let entries: Vec<String> = keys
+                .into_iter()
+                .map(|k| format!("{:?}:{}", k, render_value_sorted(&map[k])))
+                .collect();
+            format!("{{{}}}", entries.join(","))
+        }
+        Value::Array(items) => {
+            let entries: Vec<String> = items.iter().map(render_value_sorted).collect();
+            format!("[{}]", entries.join(","))
+        }
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{builtin::rust::RustParser, Parser};
+
+    #[test]
+    fn render_header_is_deterministic_regardless_of_custom_key_order() {
+        let meta = Metadata {
+            exports: Vec::new(),
+            imports: Vec::new(),
+            dependencies: Vec::new(),
+            loc: 1,
+            re_exports: Vec::new(),
+            test_loc: 0,
+            symbols: Vec::new(),
+            diagnostics: crate::parser::Diagnostics::default(),
+        };
+
+        let a = serde_json::json!({"b": 1, "a": 2});
+        let b = serde_json::json!({"a": 2, "b": 1});
+        assert_eq!(
+            render_header(&meta, Some(&a), CommentStyle::Line("//")),
+            render_header(&meta, Some(&b), CommentStyle::Line("//"))
+        );
+    }
+
+    #[test]
+    fn render_header_wraps_a_block_comment_style_once_rather_than_per_line() {
+        let meta = Metadata {
+            exports: Vec::new(),
+            imports: vec!["foo".to_string()],
+            dependencies: Vec::new(),
+            loc: 3,
+            re_exports: Vec::new(),
+            test_loc: 0,
+            symbols: Vec::new(),
+            diagnostics: crate::parser::Diagnostics::default(),
+        };
+
+        let rendered = render_header(
+            &meta,
+            None,
+            CommentStyle::Block {
+                open: "/*",
+                close: "*/",
+            },
+        );
+        let lines: Vec<&str> = rendered.lines().collect();
+
+        assert_eq!(lines[0], "/* --- FMM ---");
+        assert_eq!(lines.last().copied(), Some("--- */"));
+        assert!(lines[1..lines.len() - 1].iter().all(|line| !line.contains("/*")));
+    }
+
+    #[test]
+    fn apply_header_is_idempotent() {
+        let meta = Metadata {
+            exports: Vec::new(),
+            imports: vec!["anyhow".to_string()],
+            dependencies: Vec::new(),
+            loc: 10,
+            re_exports: Vec::new(),
+            test_loc: 0,
+            symbols: Vec::new(),
+            diagnostics: crate::parser::Diagnostics::default(),
+        };
+
+        let once = apply_header("fn main() {}", &meta, CommentStyle::Line("//"));
+        let twice = apply_header(&once, &meta, CommentStyle::Line("//"));
+        assert_eq!(once, twice);
+    }
+
+    #[test]
+    fn apply_header_is_idempotent_for_a_block_comment_style() {
+        let meta = Metadata {
+            exports: Vec::new(),
+            imports: Vec::new(),
+            dependencies: Vec::new(),
+            loc: 2,
+            re_exports: Vec::new(),
+            test_loc: 0,
+            symbols: Vec::new(),
+            diagnostics: crate::parser::Diagnostics::default(),
+        };
+        let style = CommentStyle::Block {
+            open: "/*",
+            close: "*/",
+        };
+
+        let once = apply_header("body { color: red; }", &meta, style);
+        let twice = apply_header(&once, &meta, style);
+        assert_eq!(once, twice);
+    }
+
+    #[test]
+    fn apply_then_strip_round_trips_to_the_original_body() {
+        let source = std::fs::read_to_string("fixtures/sample.rs").unwrap();
+        let mut parser = RustParser::new().unwrap();
+        let result = parser.parse(&source).unwrap();
+
+        let with_header = apply_header(&source, &result.metadata, CommentStyle::Line("//"));
+        let (_, stripped) = super::super::extract_frontmatter(&with_header).unwrap();
+        assert_eq!(stripped, source.trim_start());
+    }
+
+    #[test]
+    fn for_language_id_picks_a_block_style_for_languages_without_line_comments() {
+        assert_eq!(
+            CommentStyle::for_language_id("css"),
+            CommentStyle::Block {
+                open: "/*",
+                close: "*/"
+            }
+        );
+        assert_eq!(
+            CommentStyle::for_language_id("html"),
+            CommentStyle::Block {
+                open: "<!--",
+                close: "-->"
+            }
+        );
+        assert_eq!(CommentStyle::for_language_id("sql"), CommentStyle::Line("--"));
+        assert_eq!(CommentStyle::for_language_id("ruby"), CommentStyle::Line("#"));
+        assert_eq!(CommentStyle::for_language_id("unknown"), CommentStyle::Line("//"));
+    }
+}
@@ -0,0 +1,275 @@
+//! Persisted content-hash manifest recording each source file's fingerprint
+//! at the end of the last successful `validate`/`update` pass, so a repeat
+//! run over a mostly-unchanged tree can skip re-reading and re-parsing a
+//! file whose source hasn't moved instead of diffing every file again.
+//! Lives at `.fmm.manifest` at the project root, next to but independent of
+//! [`super::cache::FrontmatterCache`]'s own cache file — this tracks raw
+//! source state, not rendered frontmatter, so `validate` (which never
+//! renders anything on a hit) can use it too.
+//!
+//! Shares [`super::cache::Fingerprint`]'s cargo-style mtime+length check and
+//! the same binary archive shape as [`super::cache::FrontmatterCache`]
+//! (magic + format version, then one record per file), validated wholesale
+//! on load. On top of that, the header also records
+//! [`super::cache::PARSER_VERSION`] and the `fmm` binary's own version, so a
+//! parser/extractor schema upgrade — or just a new `fmm` release — treats
+//! every entry as stale rather than trusting a manifest a different build
+//! wrote.
+
+use std::collections::HashMap;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+use super::cache::{write_string, ByteReader, Fingerprint, PARSER_VERSION};
+
+const MAGIC: &[u8; 4] = b"FMMM";
+const FORMAT_VERSION: u32 = 1;
+
+/// A file's fingerprint at the moment it was last recorded as "validated
+/// and up to date" or "just updated".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ManifestEntry {
+    fingerprint: Fingerprint,
+    hash: u64,
+}
+
+/// Content-hash manifest of source files, keyed by path — see the module
+/// docs for what it's for and how it's invalidated.
+#[derive(Debug, Default)]
+pub struct SourceManifest {
+    entries: HashMap<PathBuf, ManifestEntry>,
+    dirty: bool,
+}
+
+/// Hashes raw file content, independent of language/parser — the manifest
+/// only needs to know "did the source change", not render anything.
+fn content_hash(content: &str) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    content.hash(&mut hasher);
+    hasher.finish()
+}
+
+impl SourceManifest {
+    /// Load a previously persisted manifest. A missing, truncated, corrupt,
+    /// or version-mismatched (format, parser, or `fmm` binary version)
+    /// archive is treated as an empty manifest rather than an error — the
+    /// next pass just repopulates it from scratch.
+    pub fn load(path: &Path) -> Self {
+        fs::read(path)
+            .ok()
+            .and_then(|bytes| Self::decode(&bytes))
+            .unwrap_or_default()
+    }
+
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        let mut reader = ByteReader::new(bytes);
+        if reader.read_bytes(MAGIC.len())? != MAGIC {
+            return None;
+        }
+        if reader.read_u32()? != FORMAT_VERSION {
+            return None;
+        }
+        if reader.read_u32()? != PARSER_VERSION {
+            return None;
+        }
+        if reader.read_string()? != env!("CARGO_PKG_VERSION") {
+            return None;
+        }
+
+        let count = reader.read_u32()?;
+        let mut entries = HashMap::with_capacity(count as usize);
+        for _ in 0..count {
+            let path = PathBuf::from(reader.read_string()?);
+            let mtime = reader.read_u64()? as i64;
+            let len = reader.read_u64()?;
+            let hash = reader.read_u64()?;
+            entries.insert(
+                path,
+                ManifestEntry {
+                    fingerprint: Fingerprint { mtime, len },
+                    hash,
+                },
+            );
+        }
+        reader.expect_exhausted()?;
+
+        Some(Self {
+            entries,
+            dirty: false,
+        })
+    }
+
+    /// Persist the manifest if anything changed since it was loaded or
+    /// created; a no-op otherwise.
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&PARSER_VERSION.to_le_bytes());
+        write_string(&mut bytes, env!("CARGO_PKG_VERSION"));
+        bytes.extend_from_slice(&(self.entries.len() as u32).to_le_bytes());
+        for (path, entry) in &self.entries {
+            write_string(&mut bytes, &path.to_string_lossy());
+            bytes.extend_from_slice(&(entry.fingerprint.mtime as u64).to_le_bytes());
+            bytes.extend_from_slice(&entry.fingerprint.len.to_le_bytes());
+            bytes.extend_from_slice(&entry.hash.to_le_bytes());
+        }
+        fs::write(path, bytes)
+    }
+
+    /// Whether `path`'s on-disk state still matches what was last recorded
+    /// for it: a cheap `stat()` fingerprint match short-circuits without
+    /// hashing `content`; otherwise falls back to comparing `content`'s
+    /// hash against what was recorded. `false` for a path with no entry.
+    pub fn is_unchanged(&self, path: &Path, content: &str) -> bool {
+        let Some(entry) = self.entries.get(path) else {
+            return false;
+        };
+        if Fingerprint::of(path) == Some(entry.fingerprint) {
+            return true;
+        }
+        content_hash(content) == entry.hash
+    }
+
+    /// A pure `stat()` check that never reads `path`'s contents: `true`
+    /// only when the file's current `mtime`/length exactly match what was
+    /// recorded for it. Lets a caller skip the read entirely for a file
+    /// that's almost certainly unchanged, at the cost of a false negative
+    /// (and one unnecessary read) whenever the timestamp moved but the
+    /// content didn't — [`is_unchanged`](Self::is_unchanged)'s content-hash
+    /// fallback is what catches that case.
+    pub fn is_unchanged_by_fingerprint(&self, path: &Path) -> bool {
+        let Some(entry) = self.entries.get(path) else {
+            return false;
+        };
+        Fingerprint::of(path) == Some(entry.fingerprint)
+    }
+
+    /// Record `path` as up to date as of `content`'s current state,
+    /// replacing whatever was recorded for it before.
+    pub fn record(&mut self, path: PathBuf, content: &str) {
+        let Some(fingerprint) = Fingerprint::of(&path) else {
+            return;
+        };
+        self.entries.insert(
+            path,
+            ManifestEntry {
+                fingerprint,
+                hash: content_hash(content),
+            },
+        );
+        self.dirty = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn is_unchanged_is_false_for_unknown_path() {
+        let manifest = SourceManifest::default();
+        assert!(!manifest.is_unchanged(Path::new("a.rs"), "fn main() {}"));
+    }
+
+    #[test]
+    fn record_then_is_unchanged_round_trips_within_one_run() {
+        let tmp = TempDir::new().unwrap();
+        let file = tmp.path().join("a.rs");
+        fs::write(&file, "fn main() {}").unwrap();
+
+        let mut manifest = SourceManifest::default();
+        manifest.record(file.clone(), "fn main() {}");
+
+        assert!(manifest.is_unchanged(&file, "fn main() {}"));
+    }
+
+    #[test]
+    fn is_unchanged_by_fingerprint_skips_the_content_hash_fallback() {
+        let tmp = TempDir::new().unwrap();
+        let file = tmp.path().join("a.rs");
+        fs::write(&file, "fn main() {}").unwrap();
+
+        let mut manifest = SourceManifest::default();
+        manifest.record(file.clone(), "fn main() {}");
+
+        assert!(manifest.is_unchanged_by_fingerprint(&file));
+    }
+
+    #[test]
+    fn is_unchanged_is_false_when_content_and_stat_both_changed() {
+        let tmp = TempDir::new().unwrap();
+        let file = tmp.path().join("a.rs");
+        fs::write(&file, "fn main() {}").unwrap();
+
+        let mut manifest = SourceManifest::default();
+        manifest.record(file.clone(), "fn main() {}");
+
+        fs::write(&file, "fn main() { changed(); }").unwrap();
+        assert!(!manifest.is_unchanged(&file, "fn main() { changed(); }"));
+    }
+
+    #[test]
+    fn save_then_load_round_trips_to_disk() {
+        let tmp = TempDir::new().unwrap();
+        let file = tmp.path().join("a.rs");
+        fs::write(&file, "fn main() {}").unwrap();
+        let manifest_path = tmp.path().join(".fmm.manifest");
+
+        let mut manifest = SourceManifest::default();
+        manifest.record(file.clone(), "fn main() {}");
+        manifest.save(&manifest_path).unwrap();
+
+        let reloaded = SourceManifest::load(&manifest_path);
+        assert!(reloaded.is_unchanged(&file, "fn main() {}"));
+    }
+
+    #[test]
+    fn load_treats_missing_file_as_empty() {
+        let tmp = TempDir::new().unwrap();
+        let manifest = SourceManifest::load(&tmp.path().join("does-not-exist"));
+        assert!(!manifest.is_unchanged(Path::new("a.rs"), "fn main() {}"));
+    }
+
+    #[test]
+    fn load_discards_archive_with_wrong_magic() {
+        let tmp = TempDir::new().unwrap();
+        let manifest_path = tmp.path().join(".fmm.manifest");
+        fs::write(&manifest_path, b"NOPE garbage bytes").unwrap();
+
+        let manifest = SourceManifest::load(&manifest_path);
+        assert!(!manifest.is_unchanged(Path::new("a.rs"), "anything"));
+    }
+
+    #[test]
+    fn load_discards_archive_with_mismatched_parser_version() {
+        let tmp = TempDir::new().unwrap();
+        let manifest_path = tmp.path().join(".fmm.manifest");
+
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&(PARSER_VERSION + 1).to_le_bytes());
+        write_string(&mut bytes, env!("CARGO_PKG_VERSION"));
+        bytes.extend_from_slice(&0u32.to_le_bytes());
+        fs::write(&manifest_path, bytes).unwrap();
+
+        let manifest = SourceManifest::load(&manifest_path);
+        assert!(!manifest.is_unchanged(Path::new("a.rs"), "anything"));
+    }
+
+    #[test]
+    fn save_is_a_no_op_when_nothing_changed() {
+        let tmp = TempDir::new().unwrap();
+        let manifest_path = tmp.path().join(".fmm.manifest");
+        let manifest = SourceManifest::default();
+        manifest.save(&manifest_path).unwrap();
+        assert!(!manifest_path.exists());
+    }
+}
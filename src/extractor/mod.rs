@@ -1,24 +1,62 @@
 use anyhow::{Context, Result};
+use chrono::Utc;
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
-
-use crate::config::Config;
+use std::sync::RwLock;
+
+mod cache;
+mod cargo_workspace;
+mod dependency_graph;
+mod header;
+mod manifest;
+
+pub use cache::{Fingerprint, FrontmatterCache};
+pub use manifest::SourceManifest;
+pub use dependency_graph::{
+    dependents_of, find_cycles, resolved_edges, reverse_edges, unresolved_imports, Cycle,
+    UnresolvedImport,
+};
+pub use header::{apply_header, render_header, CommentStyle};
+
+use crate::config::query_pack::QueryPackConfig;
+use crate::config::rules::{RuleContext, RuleSet};
+use crate::config::{Config, UpdateStrategy};
 use crate::formatter::Frontmatter;
-use crate::parser::{Metadata, ParserRegistry};
+use crate::parser::{Metadata, ParseResult, ParserRegistry};
 
 pub struct FileProcessor {
     config: Config,
     root: std::path::PathBuf,
     registry: ParserRegistry,
+    rules: RuleSet,
 }
 
 impl FileProcessor {
     pub fn new(config: &Config, root: &Path) -> Self {
+        let query_packs = QueryPackConfig::load(root);
+        let mut registry = ParserRegistry::with_builtins_and_query_packs(&query_packs);
+
+        // Runtime grammars registered via `fmm fetch-grammar` (see
+        // `languages.toml`) layer on top of the builtin/plugin parsers
+        // above so `generate`/`update`/`validate`/`watch` can actually
+        // parse a language fetched this way, not just `init`'s one-off
+        // check. Each loaded grammar library is leaked for the process
+        // lifetime, mirroring `ParserRegistry::load_external_plugins` —
+        // the `GrammarParser`s it registers keep referencing its code for
+        // as long as `registry` is alive.
+        let grammars = crate::parser::grammar::load_grammars(
+            &mut registry,
+            &root.join("languages.toml"),
+            &query_packs,
+        );
+        std::mem::forget(grammars);
+
         Self {
+            rules: config.compiled_rules(),
             config: config.clone(),
             root: root.to_path_buf(),
-            registry: ParserRegistry::with_builtins(),
+            registry,
         }
     }
 
@@ -30,9 +68,14 @@ impl FileProcessor {
         }
 
         let code = &content;
-        let metadata = self.extract_metadata_from_content(path, code)?;
-        let custom_fields = self.extract_custom_fields(path, code);
-        let frontmatter = self.format_frontmatter(path, &metadata, custom_fields.as_ref())?;
+        let parsed = self.parse_content(path, code)?;
+
+        if !self.admits(path, &parsed.metadata) {
+            return Ok(None);
+        }
+
+        let frontmatter =
+            self.format_frontmatter(path, &parsed.metadata, parsed.custom_fields.as_ref())?;
 
         if dry_run {
             return Ok(Some(format!("Would add:\n{}", frontmatter)));
@@ -44,6 +87,116 @@ impl FileProcessor {
         Ok(Some("Added frontmatter".to_string()))
     }
 
+    /// Whether `path` should be kept given `metadata` and the config's
+    /// `rules` (see [`crate::config::rules`]): dropped if an `exclude`
+    /// rule matches, unless an `include` rule also matches. Checked after
+    /// parsing (rules need `exports`/`imports`/`loc`, which only exist
+    /// post-parse) rather than during the filesystem walk in
+    /// `collect_files`, so a rule can't resurrect a file already dropped
+    /// by `.gitignore`/`.fmmignore`/glob `exclude` — only narrow a set
+    /// `collect_files` already admitted.
+    pub fn admits(&self, path: &Path, metadata: &Metadata) -> bool {
+        let exports = metadata.export_names();
+        let ctx = RuleContext {
+            exports: &exports,
+            imports: &metadata.imports,
+            dependencies: &metadata.dependencies,
+            loc: metadata.loc,
+            path: path.to_str().unwrap_or_default(),
+        };
+        self.rules.admits(&ctx)
+    }
+
+    /// Like [`generate`](Self::generate), but consults `cache` before
+    /// parsing: a file whose content hash, resolved parser, and
+    /// [`cache::PARSER_VERSION`] all still match what was last rendered
+    /// for it reuses that render instead of re-parsing, so a caller
+    /// sweeping a large, mostly-unchanged tree only pays for the files
+    /// that actually changed. On a `dry_run` sweep, a file whose `stat()`
+    /// fingerprint hasn't moved since it was cached skips the read and
+    /// hash entirely (see [`cache::Fingerprint`]), so a repeated dry-run
+    /// loop over an unchanged tree costs one `stat()` per file rather than
+    /// a full read. `cache` sits behind an `RwLock` rather than a plain
+    /// `&mut` so a parallel sweep (e.g. `rayon`'s `par_iter`) can call this
+    /// concurrently: the lock is only held for the cheap lookup/insert,
+    /// never across the parse itself.
+    pub fn generate_cached(
+        &self,
+        path: &Path,
+        dry_run: bool,
+        cache: &RwLock<FrontmatterCache>,
+    ) -> Result<Option<String>> {
+        let fingerprint = cache::Fingerprint::of(path);
+
+        // A dry-run sweep over a tree that still lacks frontmatter can skip
+        // reading and hashing a file entirely when its `stat()` hasn't moved
+        // since the last sweep — cargo-style fingerprinting, one level
+        // cheaper than the content-hash check `render_or_cached` falls back
+        // to. A real (non-dry-run) write still needs `content` to prepend
+        // the frontmatter to, so this fast path is dry-run only.
+        if dry_run {
+            let cached = cache.read().unwrap().get_by_fingerprint(path, fingerprint).map(String::from);
+            if let Some(cached) = cached {
+                return Ok(Some(format!("Would add:\n{}", cached)));
+            }
+        }
+
+        let content = fs::read_to_string(path)?;
+
+        if has_frontmatter(&content) {
+            return Ok(None);
+        }
+
+        let frontmatter = self.render_or_cached(path, &content, fingerprint, cache)?;
+
+        if dry_run {
+            return Ok(Some(format!("Would add:\n{}", frontmatter)));
+        }
+
+        let new_content = format!("{}\n\n{}", frontmatter, content);
+        fs::write(path, new_content)?;
+
+        Ok(Some("Added frontmatter".to_string()))
+    }
+
+    /// The rendered frontmatter for `code` at `path`, reusing `cache`'s
+    /// entry when the content hash, resolved parser, and
+    /// [`cache::PARSER_VERSION`] all still match what was cached, and
+    /// recording a fresh render into `cache` on a miss. Only the lookup
+    /// and the insert take the lock; the parse itself runs unlocked so
+    /// concurrent misses don't serialize behind each other.
+    fn render_or_cached(
+        &self,
+        path: &Path,
+        code: &str,
+        fingerprint: Option<cache::Fingerprint>,
+        cache: &RwLock<FrontmatterCache>,
+    ) -> Result<String> {
+        let language_id = self.language_id_for(path).unwrap_or_default();
+        let hash = cache::content_hash(code, &language_id, cache::PARSER_VERSION);
+
+        if let Some(cached) = cache.read().unwrap().get(path, hash) {
+            return Ok(cached.to_string());
+        }
+
+        let parsed = self.parse_content(path, code)?;
+        let rendered =
+            self.format_frontmatter(path, &parsed.metadata, parsed.custom_fields.as_ref())?;
+        cache.write().unwrap().insert(
+            path.to_path_buf(),
+            hash,
+            fingerprint,
+            rendered.clone(),
+            Utc::now().format("%Y-%m-%d").to_string(),
+        );
+        Ok(rendered)
+    }
+
+    /// Recompute `path`'s frontmatter and write it back if it changed.
+    /// Reconciliation with the existing block follows `config.update_strategy`:
+    /// [`UpdateStrategy::Merge`] (the default) overwrites only the
+    /// machine-derived keys and preserves anything hand-added, while
+    /// [`UpdateStrategy::Replace`] regenerates the block from scratch.
     pub fn update(&self, path: &Path, dry_run: bool) -> Result<Option<String>> {
         let content = fs::read_to_string(path)?;
 
@@ -53,11 +206,21 @@ impl FileProcessor {
             content.clone()
         };
 
-        let metadata = self.extract_metadata_from_content(path, &code)?;
-        let custom_fields = self.extract_custom_fields(path, &code);
-        let new_frontmatter = self.format_frontmatter(path, &metadata, custom_fields.as_ref())?;
+        let parsed = self.parse_content(path, &code)?;
 
         if let Some((old_fm, rest)) = extract_frontmatter(&content) {
+            let new_frontmatter = match self.config.update_strategy {
+                UpdateStrategy::Merge => self.format_frontmatter_merged(
+                    path,
+                    &parsed.metadata,
+                    parsed.custom_fields.as_ref(),
+                    &old_fm,
+                )?,
+                UpdateStrategy::Replace => {
+                    self.format_frontmatter(path, &parsed.metadata, parsed.custom_fields.as_ref())?
+                }
+            };
+
             if old_fm.trim() == new_frontmatter.trim() {
                 return Ok(None);
             }
@@ -83,9 +246,9 @@ impl FileProcessor {
         let content = fs::read_to_string(path)?;
 
         if let Some((old_fm, rest)) = extract_frontmatter(&content) {
-            let metadata = self.extract_metadata_from_content(path, &rest)?;
-            let custom_fields = self.extract_custom_fields(path, &rest);
-            let expected_fm = self.format_frontmatter(path, &metadata, custom_fields.as_ref())?;
+            let parsed = self.parse_content(path, &rest)?;
+            let expected_fm =
+                self.format_frontmatter(path, &parsed.metadata, parsed.custom_fields.as_ref())?;
 
             Ok(old_fm.trim() == expected_fm.trim())
         } else {
@@ -93,6 +256,67 @@ impl FileProcessor {
         }
     }
 
+    /// Like [`update`](Self::update), but consults `manifest` first: a file
+    /// whose `stat()` fingerprint (or, failing that, whose content hash)
+    /// still matches what was recorded the last time its frontmatter was
+    /// confirmed in sync skips straight to `Ok(None)` without re-parsing.
+    /// `manifest` sits behind an `RwLock` for the same reason
+    /// [`generate_cached`](Self::generate_cached)'s `cache` does — so a
+    /// parallel sweep can share it safely.
+    pub fn update_cached(
+        &self,
+        path: &Path,
+        dry_run: bool,
+        manifest: &RwLock<SourceManifest>,
+    ) -> Result<Option<String>> {
+        if manifest.read().unwrap().is_unchanged_by_fingerprint(path) {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(path)?;
+        if manifest.read().unwrap().is_unchanged(path, &content) {
+            return Ok(None);
+        }
+
+        let result = self.update(path, dry_run)?;
+
+        if !dry_run {
+            if let Ok(new_content) = fs::read_to_string(path) {
+                manifest
+                    .write()
+                    .unwrap()
+                    .record(path.to_path_buf(), &new_content);
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Like [`validate`](Self::validate), but consults `manifest` first: a
+    /// file whose fingerprint or content hash still matches what was
+    /// recorded the last time it validated successfully is trusted to still
+    /// be valid without re-parsing. A confirmed-valid file is recorded into
+    /// `manifest` so the next pass can skip it too.
+    pub fn validate_cached(&self, path: &Path, manifest: &RwLock<SourceManifest>) -> Result<bool> {
+        if manifest.read().unwrap().is_unchanged_by_fingerprint(path) {
+            return Ok(true);
+        }
+
+        let content = fs::read_to_string(path)?;
+        if manifest.read().unwrap().is_unchanged(path, &content) {
+            return Ok(true);
+        }
+
+        let valid = self.validate(path)?;
+        if valid {
+            manifest
+                .write()
+                .unwrap()
+                .record(path.to_path_buf(), &content);
+        }
+        Ok(valid)
+    }
+
     /// Extract metadata from a file (public for manifest generation)
     pub fn extract_metadata(&self, path: &Path) -> Result<Option<Metadata>> {
         let content = std::fs::read_to_string(path)?;
@@ -101,7 +325,7 @@ impl FileProcessor {
         } else {
             content
         };
-        Ok(Some(self.extract_metadata_from_content(path, &code)?))
+        Ok(Some(self.parse_content(path, &code)?.metadata))
     }
 
     /// Extract custom fields from a file's source code
@@ -110,29 +334,49 @@ impl FileProcessor {
         path: &Path,
         content: &str,
     ) -> Option<HashMap<String, serde_json::Value>> {
-        let extension = path.extension().and_then(|ext| ext.to_str())?;
-        let mut parser = self.registry.get_parser(extension).ok()?;
-        // We need to parse first to populate internal state, then get custom fields
-        let _ = parser.parse(content).ok()?;
-        parser.custom_fields(content)
+        self.parse_content(path, content).ok()?.custom_fields
     }
 
     /// Get the language ID for a file extension
-    #[allow(dead_code)]
     pub fn language_id_for(&self, path: &Path) -> Option<String> {
         let extension = path.extension().and_then(|ext| ext.to_str())?;
         let parser = self.registry.get_parser(extension).ok()?;
         Some(parser.language_id().to_string())
     }
 
-    fn extract_metadata_from_content(&self, path: &Path, content: &str) -> Result<Metadata> {
+    /// Parse a file's content in a single pass, dispatching to the
+    /// registered parser for its extension. Returns a well-defined error
+    /// (not a panic) when the extension has no registered parser, so
+    /// mixed-language trees can be walked uniformly and callers decide how
+    /// to handle "unsupported" themselves.
+    fn parse_content(&self, path: &Path, content: &str) -> Result<ParseResult> {
         let extension = path
             .extension()
             .and_then(|ext| ext.to_str())
             .context("Invalid file extension")?;
 
-        let mut parser = self.registry.get_parser(extension)?;
-        parser.parse(content)
+        let mut parser = self
+            .registry
+            .get_parser(extension)
+            .with_context(|| format!("Unsupported language for {}", path.display()))?;
+        let mut result = parser.parse(content)?;
+
+        let exports = result.metadata.export_names();
+        let rule_fields = self.rules.custom_fields(&RuleContext {
+            exports: &exports,
+            imports: &result.metadata.imports,
+            dependencies: &result.metadata.dependencies,
+            loc: result.metadata.loc,
+            path: path.to_str().unwrap_or_default(),
+        });
+        if !rule_fields.is_empty() {
+            result
+                .custom_fields
+                .get_or_insert_with(HashMap::new)
+                .extend(rule_fields);
+        }
+
+        Ok(result)
     }
 
     fn format_frontmatter(
@@ -141,6 +385,34 @@ impl FileProcessor {
         metadata: &Metadata,
         custom_fields: Option<&HashMap<String, serde_json::Value>>,
     ) -> Result<String> {
+        Ok(self.build_frontmatter(path, metadata, custom_fields)?.render())
+    }
+
+    /// Like [`format_frontmatter`](Self::format_frontmatter), but when
+    /// `previous` is a frontmatter block this crate rendered before, carry
+    /// over anything hand-edited into it rather than clobbering the whole
+    /// block. `previous` that doesn't parse (not one of ours, or from
+    /// before this format existed) falls back to a plain regeneration.
+    fn format_frontmatter_merged(
+        &self,
+        path: &Path,
+        metadata: &Metadata,
+        custom_fields: Option<&HashMap<String, serde_json::Value>>,
+        previous: &str,
+    ) -> Result<String> {
+        let frontmatter = self.build_frontmatter(path, metadata, custom_fields)?;
+        match Frontmatter::parse(previous) {
+            Ok(parsed) => Ok(frontmatter.render_merged(&parsed)),
+            Err(_) => Ok(frontmatter.render()),
+        }
+    }
+
+    fn build_frontmatter(
+        &self,
+        path: &Path,
+        metadata: &Metadata,
+        custom_fields: Option<&HashMap<String, serde_json::Value>>,
+    ) -> Result<Frontmatter> {
         let extension = path
             .extension()
             .and_then(|ext| ext.to_str())
@@ -169,18 +441,54 @@ impl FileProcessor {
             .ok()
             .map(|p| p.language_id().to_string());
 
-        let frontmatter = Frontmatter::new(
+        let mut merged_fields = custom_fields.cloned();
+        if language_id.as_deref() == Some("rust") {
+            if let Some(ctx) = cargo_workspace::crate_context_for(path) {
+                let fields = merged_fields.get_or_insert_with(HashMap::new);
+                fields.insert(
+                    "crate_name".to_string(),
+                    serde_json::Value::String(ctx.name),
+                );
+                fields.insert(
+                    "crate_version".to_string(),
+                    serde_json::Value::String(ctx.version),
+                );
+                fields.insert(
+                    "crate_edition".to_string(),
+                    serde_json::Value::String(ctx.edition),
+                );
+                fields.insert(
+                    "crate_features".to_string(),
+                    serde_json::Value::Array(
+                        ctx.features.into_iter().map(serde_json::Value::String).collect(),
+                    ),
+                );
+            }
+        }
+
+        Ok(Frontmatter::new(
             relative_path.display().to_string(),
             metadata.clone(),
             language,
         )
         .with_version("v0.2")
-        .with_custom_fields(language_id.as_deref(), custom_fields);
-
-        Ok(frontmatter.render())
+        .with_custom_fields(language_id.as_deref(), merged_fields.as_ref()))
     }
 }
 
+/// Line-comment prefixes `has_frontmatter`/`extract_frontmatter` recognize
+/// as opening a header, tried in addition to the [`BLOCK_DELIMITERS`]
+/// open/close pairs. Kept in sync with [`CommentStyle::for_language_id`],
+/// but these two functions take raw content with no language attached (a
+/// file might not even have a registered parser), so detection stays
+/// structural rather than language-keyed: any known delimiter on the first
+/// line is accepted.
+const LINE_PREFIXES: &[&str] = &["//", "#", "--"];
+
+/// Block-comment open/close pairs `has_frontmatter`/`extract_frontmatter`
+/// recognize, alongside [`LINE_PREFIXES`].
+const BLOCK_DELIMITERS: &[(&str, &str)] = &[("/*", "*/"), ("<!--", "-->")];
+
 fn has_frontmatter(content: &str) -> bool {
     let lines: Vec<&str> = content.lines().collect();
     if lines.len() < 2 {
@@ -188,7 +496,11 @@ fn has_frontmatter(content: &str) -> bool {
     }
 
     let first = lines[0].trim();
-    (first.starts_with("//") || first.starts_with("#")) && first.contains("--- FMM ---")
+    if !first.contains("--- FMM ---") {
+        return false;
+    }
+    LINE_PREFIXES.iter().any(|prefix| first.starts_with(prefix))
+        || BLOCK_DELIMITERS.iter().any(|(open, _)| first.starts_with(open))
 }
 
 fn extract_frontmatter(content: &str) -> Option<(String, String)> {
@@ -198,17 +510,31 @@ fn extract_frontmatter(content: &str) -> Option<(String, String)> {
     }
 
     let first = lines[0].trim();
-    if !((first.starts_with("//") || first.starts_with("#")) && first.contains("--- FMM ---")) {
+    if !first.contains("--- FMM ---") {
+        return None;
+    }
+
+    let block_close = BLOCK_DELIMITERS
+        .iter()
+        .find(|(open, _)| first.starts_with(open))
+        .map(|(_, close)| *close);
+    let is_line_style = LINE_PREFIXES.iter().any(|prefix| first.starts_with(prefix));
+    if block_close.is_none() && !is_line_style {
         return None;
     }
 
     let mut end_idx = None;
     for (i, line) in lines.iter().enumerate().skip(1) {
         let trimmed = line.trim();
-        if (trimmed.starts_with("//") || trimmed.starts_with("#"))
-            && trimmed.ends_with("---")
-            && !trimmed.contains("FMM")
-        {
+        let closes_header = match block_close {
+            Some(close) => trimmed.ends_with(close) && trimmed.contains("---") && !trimmed.contains("FMM"),
+            None => {
+                LINE_PREFIXES.iter().any(|prefix| trimmed.starts_with(prefix))
+                    && trimmed.ends_with("---")
+                    && !trimmed.contains("FMM")
+            }
+        };
+        if closes_header {
             end_idx = Some(i);
             break;
         }
@@ -270,6 +596,75 @@ def foo(): pass"#;
         assert!(!has_frontmatter(content));
     }
 
+    #[test]
+    fn test_has_frontmatter_css_block_comment() {
+        let content = r#"/* --- FMM ---
+file: test.css
+loc: 3
+--- */
+
+body { color: red; }"#;
+        assert!(has_frontmatter(content));
+    }
+
+    #[test]
+    fn test_has_frontmatter_html_block_comment() {
+        let content = r#"<!-- --- FMM ---
+file: test.html
+loc: 1
+--- -->
+
+<div></div>"#;
+        assert!(has_frontmatter(content));
+    }
+
+    #[test]
+    fn test_has_frontmatter_sql_line_comment() {
+        let content = r#"-- --- FMM ---
+-- file: test.sql
+-- loc: 1
+-- ---
+
+SELECT 1;"#;
+        assert!(has_frontmatter(content));
+    }
+
+    #[test]
+    fn parse_content_injects_rule_computed_custom_field() {
+        let mut config = Config::default();
+        config.rules = vec![
+            "if contains(imports, \"jwt\") && count(exports) > 0 then security_sensitive = true"
+                .to_string(),
+        ];
+        let processor = FileProcessor::new(&config, Path::new("."));
+
+        let result = processor
+            .parse_content(Path::new("auth.rs"), "use jwt;\npub fn decode() {}")
+            .unwrap();
+
+        assert_eq!(
+            result.custom_fields.unwrap().get("security_sensitive"),
+            Some(&serde_json::Value::Bool(true))
+        );
+    }
+
+    #[test]
+    fn admits_drops_files_matching_an_exclude_rule() {
+        let mut config = Config::default();
+        config.rules = vec!["if ends_with(path, \"_generated.rs\") then exclude".to_string()];
+        let processor = FileProcessor::new(&config, Path::new("."));
+
+        let result = processor
+            .parse_content(Path::new("schema_generated.rs"), "pub fn hello() {}")
+            .unwrap();
+        assert!(!processor.admits(Path::new("schema_generated.rs"), &result.metadata));
+
+        let result = processor
+            .parse_content(Path::new("schema.rs"), "pub fn hello() {}")
+            .unwrap();
+        assert!(processor.admits(Path::new("schema.rs"), &result.metadata));
+    }
+
     #[test]
     fn test_extract_frontmatter_new_format() {
         let content = r#"// --- FMM ---
@@ -302,4 +697,72 @@ export function bar() {}"#;
         let content = "export function foo() {}";
         assert!(extract_frontmatter(content).is_none());
     }
+
+    #[test]
+    fn test_extract_frontmatter_css_block_comment() {
+        let content = r#"/* --- FMM ---
+file: test.css
+loc: 3
+--- */
+
+body { color: red; }"#;
+
+        let (fm, rest) = extract_frontmatter(content).unwrap();
+        assert!(fm.starts_with("/* --- FMM ---"));
+        assert!(fm.ends_with("--- */"));
+        assert_eq!(rest, "body { color: red; }");
+    }
+
+    #[test]
+    fn test_extract_frontmatter_html_block_comment() {
+        let content = r#"<!-- --- FMM ---
+file: test.html
+loc: 1
+--- -->
+
+<div></div>"#;
+
+        let (fm, rest) = extract_frontmatter(content).unwrap();
+        assert!(fm.starts_with("<!-- --- FMM ---"));
+        assert!(fm.ends_with("--- -->"));
+        assert_eq!(rest, "<div></div>");
+    }
+
+    #[test]
+    fn test_extract_frontmatter_sql_line_comment() {
+        let content = r#"-- --- FMM ---
+-- file: test.sql
+-- loc: 1
+-- ---
+
+SELECT 1;"#;
+
+        let (fm, rest) = extract_frontmatter(content).unwrap();
+        assert!(fm.contains("-- file: test.sql"));
+        assert_eq!(rest, "SELECT 1;");
+    }
+
+    #[test]
+    fn apply_header_round_trips_for_a_css_block_comment_style() {
+        let meta = Metadata {
+            exports: Vec::new(),
+            imports: Vec::new(),
+            dependencies: Vec::new(),
+            loc: 1,
+            re_exports: Vec::new(),
+            test_loc: 0,
+            symbols: Vec::new(),
+            diagnostics: crate::parser::Diagnostics::default(),
+        };
+        let style = CommentStyle::Block {
+            open: "/*",
+            close: "*/",
+        };
+        let source = "body { color: red; }";
+
+        let with_header = apply_header(source, &meta, style);
+        assert!(has_frontmatter(&with_header));
+        let (_, stripped) = extract_frontmatter(&with_header).unwrap();
+        assert_eq!(stripped, source);
+    }
 }
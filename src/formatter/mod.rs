@@ -1,7 +1,49 @@
+use anyhow::{Context, Result};
 use chrono::Utc;
-use std::collections::HashMap;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
 
-use crate::parser::Metadata;
+use crate::parser::{ExportKind, Metadata};
+
+/// Top-level keys `render`/`render_merged` always recompute from the
+/// current parse — never carried over from a previous file, since they
+/// describe facts about the source rather than anything a user would hand-
+/// edit. `exports` is handled separately: it's a nested section, not a
+/// scalar/flow field.
+const MACHINE_FIELDS: &[&str] = &["file", "fmm", "imports", "dependencies", "loc", "modified"];
+
+/// Serialization format for [`Frontmatter::render_as`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Yaml,
+    Json,
+    Toml,
+}
+
+/// Intermediate typed model shared by every [`Format`] — field ordering and
+/// escaping are decided once here, and each backend just serializes it
+/// through serde instead of building its own string.
+#[derive(Debug, Clone, Serialize)]
+struct FrontmatterModel {
+    file: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fmm: Option<String>,
+    /// `name -> [start_line, end_line]`, preserved as a real array rather
+    /// than a flattened string.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    exports: BTreeMap<String, [usize; 2]>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    imports: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    dependencies: Vec<String>,
+    loc: usize,
+    modified: String,
+    /// `language_id -> { field -> value }`, flattened so the language
+    /// section renders as a real nested object keyed by its language id
+    /// rather than a fixed field name.
+    #[serde(flatten)]
+    language_section: BTreeMap<String, BTreeMap<String, serde_json::Value>>,
+}
 
 pub struct Frontmatter {
     file_path: String,
@@ -57,16 +99,36 @@ impl Frontmatter {
             lines.push(format!("fmm: {}", version));
         }
 
-        // Exports as YAML map with [start, end] line ranges
+        // Exports as YAML map with [start, end] line ranges. An entry whose
+        // parser also reported a `kind`/`signature`/`doc` (see
+        // [`crate::parser::ExportEntry`]) gets a nested block instead of the
+        // compact flow array, so that extra detail stays easy to spot
+        // without disturbing the common case — most parsers haven't been
+        // migrated to populate them.
         if !self.metadata.exports.is_empty() {
             lines.push("exports:".to_string());
             for entry in &self.metadata.exports {
-                lines.push(format!(
-                    "  {}: [{}, {}]",
-                    yaml_escape(&entry.name),
-                    entry.start_line,
-                    entry.end_line
-                ));
+                if entry.kind.is_none() && entry.signature.is_none() && entry.doc.is_none() {
+                    lines.push(format!(
+                        "  {}: [{}, {}]",
+                        yaml_escape(&entry.name),
+                        entry.start_line,
+                        entry.end_line
+                    ));
+                    continue;
+                }
+
+                lines.push(format!("  {}:", yaml_escape(&entry.name)));
+                lines.push(format!("    lines: [{}, {}]", entry.start_line, entry.end_line));
+                if let Some(kind) = entry.kind {
+                    lines.push(format!("    kind: {}", export_kind_str(kind)));
+                }
+                if let Some(ref signature) = entry.signature {
+                    lines.push(format!("    signature: {}", yaml_escape(signature)));
+                }
+                if let Some(ref doc) = entry.doc {
+                    lines.push(format!("    doc: {}", yaml_escape(doc)));
+                }
             }
         }
 
@@ -111,21 +173,330 @@ impl Frontmatter {
 
         lines.join("\n")
     }
+
+    /// Build the typed model [`render_as`](Self::render_as) serializes.
+    fn to_model(&self) -> FrontmatterModel {
+        let exports = self
+            .metadata
+            .exports
+            .iter()
+            .map(|entry| (entry.name.clone(), [entry.start_line, entry.end_line]))
+            .collect();
+
+        let mut language_section = BTreeMap::new();
+        if let Some((ref lang_id, ref fields)) = self.custom_fields {
+            let nested: BTreeMap<String, serde_json::Value> =
+                fields.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+            language_section.insert(lang_id.clone(), nested);
+        }
+
+        FrontmatterModel {
+            file: self.file_path.clone(),
+            fmm: self.version.clone(),
+            exports,
+            imports: self.metadata.imports.clone(),
+            dependencies: self.metadata.dependencies.clone(),
+            loc: self.metadata.loc,
+            modified: self.modified.clone(),
+            language_section,
+        }
+    }
+
+    /// Render this frontmatter in `format`. `Yaml` reuses the hand-rolled
+    /// [`render`](Self::render) path (its escaping is purpose-built for
+    /// YAML scalars); `Json`/`Toml` serialize the shared typed model, so
+    /// field ordering and escaping stay consistent across formats.
+    pub fn render_as(&self, format: Format) -> String {
+        match format {
+            Format::Yaml => self.render(),
+            Format::Json => serde_json::to_string_pretty(&self.to_model()).unwrap_or_default(),
+            Format::Toml => toml::to_string_pretty(&self.to_model()).unwrap_or_default(),
+        }
+    }
+
+    /// Parse a previously rendered `render()` document back into its
+    /// key/value structure, so a later regeneration can recover whatever a
+    /// user added by hand. Only understands the hand-rolled YAML subset
+    /// `render` itself emits (a flat `key: value`/`key: [flow, array]` line,
+    /// or a `key:` line followed by indented `  key: value` lines) — an
+    /// empty line ends the document, matching where `render` stops.
+    pub fn parse(existing: &str) -> Result<ParsedFrontmatter> {
+        let mut lines = existing.lines();
+        let marker = lines.next().context("frontmatter document is empty")?;
+        if marker.trim() != "---" {
+            anyhow::bail!("frontmatter must start with a YAML document marker (`---`)");
+        }
+
+        let mut fields: Vec<(String, String)> = Vec::new();
+        let mut sections: Vec<(String, Vec<(String, String)>)> = Vec::new();
+
+        for line in lines {
+            if line.trim().is_empty() {
+                break;
+            }
+            if let Some(rest) = line.strip_prefix("  ") {
+                // A bare `key:` with no value (e.g. a nested export block's
+                // `greet:` header) has no machine use here — `exports` is
+                // never carried over by `render_merged` in the first place
+                // — so it's recorded with an empty value rather than
+                // failing the whole parse over content this type doesn't
+                // need to round-trip.
+                let (key, value) = match rest.strip_suffix(':') {
+                    Some(key) if !rest.trim_start().is_empty() && !key.contains(':') => {
+                        (key.to_string(), String::new())
+                    }
+                    _ => split_key_value(rest)
+                        .with_context(|| format!("malformed section entry: {line:?}"))?,
+                };
+                sections
+                    .last_mut()
+                    .with_context(|| format!("{line:?} has no enclosing section"))?
+                    .1
+                    .push((key, value));
+            } else if let Some(name) = line.strip_suffix(':') {
+                sections.push((name.to_string(), Vec::new()));
+            } else {
+                let (key, value) = split_key_value(line)
+                    .with_context(|| format!("malformed frontmatter line: {line:?}"))?;
+                fields.push((key, value));
+            }
+        }
+
+        Ok(ParsedFrontmatter { fields, sections })
+    }
+
+    /// Render this (freshly computed) frontmatter, but carry over anything
+    /// `previous` held that the machine doesn't own: unrecognized top-level
+    /// keys (e.g. a hand-written `summary:`/`owner:` line) and unrecognized
+    /// keys inside the language section. Machine-owned fields (`file`,
+    /// `exports`, `imports`, `dependencies`, `loc`, `modified`, `fmm`, and
+    /// any key this run's custom fields set) always come from `self` — the
+    /// previous file only fills in what this run didn't compute. Carried-
+    /// over keys are emitted in sorted order so unrelated hand-edits don't
+    /// shuffle position across runs.
+    pub fn render_merged(&self, previous: &ParsedFrontmatter) -> String {
+        let mut lines: Vec<String> = self.render().lines().map(str::to_string).collect();
+
+        let mut extra_fields: Vec<&(String, String)> = previous
+            .fields
+            .iter()
+            .filter(|(key, _)| !MACHINE_FIELDS.contains(&key.as_str()))
+            .collect();
+        extra_fields.sort_by(|a, b| a.0.cmp(&b.0));
+        for (key, value) in extra_fields {
+            lines.push(format!("{key}: {value}"));
+        }
+
+        let own_language: Option<&str> = self.custom_fields.as_ref().map(|(lang, _)| lang.as_str());
+
+        // Sections the machine doesn't own at all this run (a stale
+        // language section after a file's language changed, or any
+        // section this struct never renders) survive verbatim.
+        for (name, entries) in &previous.sections {
+            if name == "exports" || Some(name.as_str()) == own_language {
+                continue;
+            }
+            lines.push(format!("{name}:"));
+            let mut entries = entries.clone();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            for (key, value) in entries {
+                lines.push(format!("  {key}: {value}"));
+            }
+        }
+
+        // Extra keys inside the language section this run *does* own merge
+        // in alongside the machine-computed ones, rather than replacing the
+        // whole section.
+        if let Some((lang, fields)) = &self.custom_fields {
+            if let Some((_, prev_entries)) = previous.sections.iter().find(|(name, _)| name == lang)
+            {
+                let mut extra: Vec<&(String, String)> = prev_entries
+                    .iter()
+                    .filter(|(key, _)| !fields.contains_key(key))
+                    .collect();
+                extra.sort_by(|a, b| a.0.cmp(&b.0));
+                if !extra.is_empty() {
+                    let header = format!("{lang}:");
+                    let mut insert_at = lines
+                        .iter()
+                        .rposition(|l| l == &header)
+                        .map(|pos| pos + 1)
+                        .unwrap_or(lines.len());
+                    while insert_at < lines.len() && lines[insert_at].starts_with("  ") {
+                        insert_at += 1;
+                    }
+                    for (key, value) in extra.into_iter().rev() {
+                        lines.insert(insert_at, format!("  {key}: {value}"));
+                    }
+                }
+            }
+        }
+
+        lines.join("\n")
+    }
+}
+
+fn split_key_value(line: &str) -> Option<(String, String)> {
+    let (key, value) = line.split_once(": ")?;
+    Some((key.to_string(), value.to_string()))
 }
 
-/// Quote a string if it contains YAML-special characters that would break parsing.
-/// Returns the original string unmodified when safe, or wraps it in single quotes.
+/// A previously rendered frontmatter document, parsed back into its
+/// top-level scalar/flow fields and nested sections (`exports:` and the
+/// language section), each keeping source order for the keys `merge`
+/// doesn't recognize as machine-owned.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParsedFrontmatter {
+    fields: Vec<(String, String)>,
+    sections: Vec<(String, Vec<(String, String)>)>,
+}
+
+fn export_kind_str(kind: ExportKind) -> &'static str {
+    match kind {
+        ExportKind::Function => "function",
+        ExportKind::Class => "class",
+        ExportKind::Interface => "interface",
+        ExportKind::Const => "const",
+        ExportKind::Type => "type",
+    }
+}
+
+/// Quote `s` whenever emitting it bare would either break YAML flow syntax
+/// or cause a YAML 1.1 parser to resolve it to a non-string (null/bool/int/
+/// float) instead of the string it actually is. Multi-line strings are
+/// emitted double-quoted with escapes rather than breaking the document.
 fn yaml_escape(s: &str) -> String {
-    const SPECIAL: &[char] = &[
-        ':', '#', '[', ']', '{', '}', ',', '&', '*', '!', '|', '>', '\'', '"', '%', '@', '`',
-    ];
-    if s.is_empty() || s.contains(SPECIAL) {
+    if s.contains('\n') {
+        return format!("\"{}\"", escape_double_quoted(s));
+    }
+    if needs_quoting(s) {
         format!("'{}'", s.replace('\'', "''"))
     } else {
         s.to_string()
     }
 }
 
+fn escape_double_quoted(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\t', "\\t")
+}
+
+/// True when emitting `s` bare would either break YAML flow syntax (the
+/// fixed punctuation set, plus a leading indicator character) or cause it
+/// to reparse as something other than a string.
+fn needs_quoting(s: &str) -> bool {
+    const SPECIAL: &[char] = &[
+        ':', '#', '[', ']', '{', '}', ',', '&', '*', '!', '|', '>', '\'', '"', '%', '@', '`',
+    ];
+    s.is_empty()
+        || s.contains(SPECIAL)
+        || starts_with_indicator(s)
+        || s.trim() != s
+        || would_reparse_as_non_string(s)
+}
+
+fn starts_with_indicator(s: &str) -> bool {
+    matches!(
+        s.chars().next(),
+        Some('-' | '?' | ':' | ',' | '[' | ']' | '{' | '}' | '#' | '&' | '*' | '!' | '|' | '>' | '\'' | '"' | '%' | '@' | '`')
+    )
+}
+
+/// Classifies `s` the way a YAML 1.1 core-schema parser would: would
+/// reparsing this bare scalar yield null/bool/int/float instead of a string?
+fn would_reparse_as_non_string(s: &str) -> bool {
+    is_null_like(s) || is_bool_like(s) || is_int_like(s) || is_float_like(s)
+}
+
+fn is_null_like(s: &str) -> bool {
+    matches!(s.to_ascii_lowercase().as_str(), "" | "~" | "null")
+}
+
+fn is_bool_like(s: &str) -> bool {
+    matches!(
+        s.to_ascii_lowercase().as_str(),
+        "true" | "false" | "yes" | "no" | "on" | "off"
+    )
+}
+
+fn strip_sign(s: &str) -> &str {
+    match s.chars().next() {
+        Some('+' | '-') => &s[1..],
+        _ => s,
+    }
+}
+
+/// Decimal (with `_` separators), hex (`0x`), octal (`0o`), or sexagesimal
+/// (`1:30`) integer literal, per the YAML 1.1 core schema.
+fn is_int_like(s: &str) -> bool {
+    let rest = strip_sign(s);
+    if rest.is_empty() {
+        return false;
+    }
+    if let Some(hex) = rest.strip_prefix("0x").or_else(|| rest.strip_prefix("0X")) {
+        return !hex.is_empty() && hex.chars().all(|c| c.is_ascii_hexdigit() || c == '_');
+    }
+    if let Some(oct) = rest.strip_prefix("0o").or_else(|| rest.strip_prefix("0O")) {
+        return !oct.is_empty() && oct.chars().all(|c| ('0'..='7').contains(&c) || c == '_');
+    }
+    if rest.contains(':') {
+        let parts: Vec<&str> = rest.split(':').collect();
+        return parts.len() > 1 && parts.iter().all(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit()));
+    }
+    rest.chars().any(|c| c.is_ascii_digit()) && rest.chars().all(|c| c.is_ascii_digit() || c == '_')
+}
+
+/// Decimal/exponent float, `.inf`/`.nan`, or sexagesimal float (`1:30.5`),
+/// per the YAML 1.1 core schema.
+fn is_float_like(s: &str) -> bool {
+    if matches!(s.to_ascii_lowercase().as_str(), ".inf" | "+.inf" | "-.inf" | ".nan") {
+        return true;
+    }
+
+    let rest = strip_sign(s);
+    if rest.is_empty() {
+        return false;
+    }
+
+    if rest.contains(':') {
+        let parts: Vec<&str> = rest.split(':').collect();
+        return match parts.last() {
+            Some(last) => {
+                parts.len() > 1
+                    && last.contains('.')
+                    && parts.iter().all(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit() || c == '.'))
+            }
+            None => false,
+        };
+    }
+
+    if !rest.contains('.') && !rest.contains('e') && !rest.contains('E') {
+        return false;
+    }
+
+    let mut chars = rest.chars().peekable();
+    let mut seen_digit = false;
+    let mut seen_dot = false;
+    let mut seen_exp = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '0'..='9' => seen_digit = true,
+            '_' => {}
+            '.' if !seen_dot && !seen_exp => seen_dot = true,
+            'e' | 'E' if seen_digit && !seen_exp => {
+                seen_exp = true;
+                if matches!(chars.peek(), Some('+') | Some('-')) {
+                    chars.next();
+                }
+            }
+            _ => return false,
+        }
+    }
+    seen_digit
+}
+
 /// Format a serde_json::Value for YAML-like output.
 fn format_value(value: &serde_json::Value) -> String {
     match value {
@@ -162,6 +533,18 @@ mod tests {
         ExportEntry::new(name.to_string(), start, end)
     }
 
+    fn entry_with_signature(
+        name: &str,
+        start: usize,
+        end: usize,
+        kind: ExportKind,
+        signature: Option<&str>,
+    ) -> ExportEntry {
+        ExportEntry::new(name.to_string(), start, end)
+            .with_kind(Some(kind))
+            .with_signature(signature.map(str::to_string))
+    }
+
     #[test]
     fn test_sidecar_output() {
         let metadata = Metadata {
@@ -356,6 +739,69 @@ mod tests {
         assert_eq!(yaml_escape("it's:here"), "'it''s:here'");
     }
 
+    #[test]
+    fn yaml_escape_quotes_type_ambiguous_scalars() {
+        for s in [
+            "true", "false", "null", "~", "yes", "no", "on", "off", "123", "0x1f", "1_000",
+            "1.0", ".inf", "1:30",
+        ] {
+            let escaped = yaml_escape(s);
+            assert_ne!(escaped, s, "{s:?} should have been quoted");
+        }
+    }
+
+    #[test]
+    fn yaml_escape_quotes_leading_trailing_whitespace() {
+        assert_eq!(yaml_escape(" leading"), "' leading'");
+        assert_eq!(yaml_escape("trailing "), "'trailing '");
+    }
+
+    #[test]
+    fn yaml_escape_quotes_leading_indicator_characters() {
+        assert_eq!(yaml_escape("-dash"), "'-dash'");
+        assert_eq!(yaml_escape("?query"), "'?query'");
+    }
+
+    #[test]
+    fn yaml_escape_leaves_ordinary_numeric_looking_identifiers_alone() {
+        // Not actually numeric per the YAML core schema — should stay bare.
+        assert_eq!(yaml_escape("react-dom"), "react-dom");
+        assert_eq!(yaml_escape("v1.2.3-beta"), "v1.2.3-beta");
+    }
+
+    #[test]
+    fn yaml_escape_emits_multiline_strings_as_escaped_double_quotes() {
+        let escaped = yaml_escape("line one\nline two");
+        assert_eq!(escaped, "\"line one\\nline two\"");
+
+        let doc = format!("key: {escaped}");
+        let parsed: serde_yaml::Value = serde_yaml::from_str(&doc).unwrap();
+        assert_eq!(parsed["key"].as_str().unwrap(), "line one\nline two");
+    }
+
+    #[test]
+    fn yaml_escape_round_trips_through_serde_yaml_as_a_string() {
+        // Every one of these would resolve to a non-string type (or break
+        // flow syntax) if emitted bare — after escaping, each must reparse
+        // back to the identical string value.
+        let cases = [
+            "true", "false", "null", "~", "yes", "no", "on", "off", "123", "0x1f", "0o17",
+            "1_000", "1.0", "-1.5e10", ".inf", "-.inf", ".nan", "1:30", "1:30.5", "", " pad",
+            "pad ", "-dash", "?query", "key:value", "foo#bar", "[array]", "@scope/pkg",
+            "plain-string", "./relative/path",
+        ];
+        for s in cases {
+            let escaped = yaml_escape(s);
+            let doc = format!("key: {escaped}");
+            let parsed: serde_yaml::Value = serde_yaml::from_str(&doc).unwrap();
+            assert_eq!(
+                parsed["key"].as_str().unwrap(),
+                s,
+                "round-trip mismatch for {s:?} (escaped as {escaped:?})"
+            );
+        }
+    }
+
     #[test]
     fn render_starts_with_yaml_document_marker() {
         let metadata = Metadata {
@@ -368,6 +814,83 @@ mod tests {
         assert!(fm.render().starts_with("---\n"));
     }
 
+    #[test]
+    fn export_with_kind_renders_as_a_nested_block() {
+        let metadata = Metadata {
+            exports: vec![entry_with_signature(
+                "greet",
+                1,
+                3,
+                ExportKind::Function,
+                Some("(name: string): string"),
+            )],
+            imports: vec![],
+            dependencies: vec![],
+            loc: 3,
+        };
+        let fm = Frontmatter::new("greet.ts".to_string(), metadata);
+        let rendered = fm.render();
+
+        assert!(rendered.contains("  greet:\n"));
+        assert!(rendered.contains("    lines: [1, 3]"));
+        assert!(rendered.contains("    kind: function"));
+        assert!(rendered.contains("    signature: '(name: string): string'"));
+    }
+
+    #[test]
+    fn export_without_kind_keeps_the_compact_flow_array() {
+        let metadata = Metadata {
+            exports: vec![entry("foo", 1, 3)],
+            imports: vec![],
+            dependencies: vec![],
+            loc: 3,
+        };
+        let fm = Frontmatter::new("foo.ts".to_string(), metadata);
+        assert!(fm.render().contains("  foo: [1, 3]"));
+    }
+
+    #[test]
+    fn export_doc_renders_as_a_nested_field() {
+        let metadata = Metadata {
+            exports: vec![ExportEntry::new("greet".to_string(), 1, 3)
+                .with_doc(Some("Greets someone.".to_string()))],
+            imports: vec![],
+            dependencies: vec![],
+            loc: 3,
+        };
+        let fm = Frontmatter::new("greet.ts".to_string(), metadata);
+        let rendered = fm.render();
+
+        assert!(rendered.contains("  greet:\n"));
+        assert!(rendered.contains("    lines: [1, 3]"));
+        assert!(rendered.contains("    doc: Greets someone."));
+    }
+
+    #[test]
+    fn parse_tolerates_a_nested_export_block() {
+        let metadata = Metadata {
+            exports: vec![entry_with_signature(
+                "greet",
+                1,
+                3,
+                ExportKind::Function,
+                Some("(name: string): string"),
+            )],
+            imports: vec![],
+            dependencies: vec![],
+            loc: 3,
+        };
+        let fm = Frontmatter::new("greet.ts".to_string(), metadata);
+        let rendered = fm.render();
+
+        // `exports` is never carried over by `render_merged`, but a nested
+        // export block still shouldn't make the whole document unparsable —
+        // an unrelated hand-added field must survive.
+        let previous = format!("{rendered}\nowner: alice");
+        let parsed = Frontmatter::parse(&previous).unwrap();
+        assert!(parsed.fields.iter().any(|(k, v)| k == "owner" && v == "alice"));
+    }
+
     #[test]
     fn special_chars_roundtrip_through_yaml() {
         let metadata = Metadata {
@@ -392,4 +915,195 @@ mod tests {
         // Export names are map keys
         assert!(parsed["exports"]["Config:Base"].is_sequence());
     }
+
+    #[test]
+    fn render_as_json_preserves_export_ranges_and_language_section() {
+        let metadata = Metadata {
+            exports: vec![entry("MyStruct", 5, 15)],
+            imports: vec!["std".to_string()],
+            dependencies: vec![],
+            loc: 50,
+        };
+
+        let mut custom = HashMap::new();
+        custom.insert(
+            "unsafe_blocks".to_string(),
+            serde_json::Value::Number(3.into()),
+        );
+
+        let fm = Frontmatter::new("src/lib.rs".to_string(), metadata)
+            .with_version("v0.3")
+            .with_custom_fields(Some("rust"), Some(&custom));
+
+        let rendered = fm.render_as(Format::Json);
+        let parsed: serde_json::Value = serde_json::from_str(&rendered).unwrap();
+
+        assert_eq!(parsed["file"], "src/lib.rs");
+        assert_eq!(parsed["fmm"], "v0.3");
+        assert_eq!(parsed["exports"]["MyStruct"], serde_json::json!([5, 15]));
+        assert_eq!(parsed["rust"]["unsafe_blocks"], 3);
+    }
+
+    #[test]
+    fn render_as_toml_round_trips_exports_and_loc() {
+        let metadata = Metadata {
+            exports: vec![entry("foo", 1, 3)],
+            imports: vec!["serde".to_string()],
+            dependencies: vec![],
+            loc: 10,
+        };
+
+        let fm = Frontmatter::new("test.rs".to_string(), metadata);
+        let rendered = fm.render_as(Format::Toml);
+        let parsed: toml::Value = toml::from_str(&rendered).unwrap();
+
+        assert_eq!(parsed["file"].as_str().unwrap(), "test.rs");
+        assert_eq!(parsed["loc"].as_integer().unwrap(), 10);
+        let export_range = parsed["exports"]["foo"].as_array().unwrap();
+        assert_eq!(export_range[0].as_integer().unwrap(), 1);
+        assert_eq!(export_range[1].as_integer().unwrap(), 3);
+    }
+
+    #[test]
+    fn render_as_yaml_matches_render() {
+        let metadata = Metadata {
+            exports: vec![entry("foo", 1, 3)],
+            imports: vec![],
+            dependencies: vec![],
+            loc: 10,
+        };
+
+        let fm = Frontmatter::new("test.ts".to_string(), metadata);
+        assert_eq!(fm.render_as(Format::Yaml), fm.render());
+    }
+
+    #[test]
+    fn render_as_json_omits_absent_version_and_language_section() {
+        let metadata = Metadata {
+            exports: vec![],
+            imports: vec![],
+            dependencies: vec![],
+            loc: 1,
+        };
+
+        let fm = Frontmatter::new("test.ts".to_string(), metadata);
+        let rendered = fm.render_as(Format::Json);
+        let parsed: serde_json::Value = serde_json::from_str(&rendered).unwrap();
+
+        assert!(parsed.get("fmm").is_none());
+        assert!(parsed.as_object().unwrap().keys().all(|k| k != "rust" && k != "python"));
+    }
+
+    #[test]
+    fn parse_recovers_known_fields_and_a_hand_added_line() {
+        let doc = "---\nfile: test.ts\nloc: 10\nmodified: 2026-01-01\nsummary: hand-written note\n";
+        let parsed = Frontmatter::parse(doc).unwrap();
+        assert!(parsed
+            .fields
+            .contains(&("file".to_string(), "test.ts".to_string())));
+        assert!(parsed
+            .fields
+            .contains(&("summary".to_string(), "hand-written note".to_string())));
+    }
+
+    #[test]
+    fn parse_recovers_a_nested_section() {
+        let doc = "---\nfile: src/lib.rs\nloc: 10\nmodified: 2026-01-01\nrust:\n  unsafe_blocks: 3\n  owner: alice\n";
+        let parsed = Frontmatter::parse(doc).unwrap();
+        let rust_section = parsed
+            .sections
+            .iter()
+            .find(|(name, _)| name == "rust")
+            .unwrap();
+        assert!(rust_section
+            .1
+            .contains(&("owner".to_string(), "alice".to_string())));
+    }
+
+    #[test]
+    fn parse_rejects_documents_missing_the_yaml_marker() {
+        assert!(Frontmatter::parse("file: test.ts\nloc: 1\n").is_err());
+    }
+
+    #[test]
+    fn render_merged_preserves_a_hand_added_top_level_field() {
+        let metadata = Metadata {
+            exports: vec![entry("foo", 1, 3)],
+            imports: vec![],
+            dependencies: vec![],
+            loc: 10,
+        };
+        let previous = Frontmatter::parse(
+            "---\nfile: test.ts\nloc: 1\nmodified: 2025-01-01\nsummary: hand-written note\n",
+        )
+        .unwrap();
+
+        let fm = Frontmatter::new("test.ts".to_string(), metadata);
+        let merged = fm.render_merged(&previous);
+
+        assert!(merged.contains("summary: hand-written note"));
+        assert!(merged.contains("loc: 10")); // machine-owned value wins
+    }
+
+    #[test]
+    fn render_merged_preserves_an_extra_key_in_the_language_section() {
+        let metadata = Metadata {
+            exports: vec![entry("MyStruct", 5, 15)],
+            imports: vec![],
+            dependencies: vec![],
+            loc: 50,
+        };
+        let previous = Frontmatter::parse(
+            "---\nfile: src/lib.rs\nloc: 10\nmodified: 2025-01-01\nrust:\n  unsafe_blocks: 1\n  owner: alice\n",
+        )
+        .unwrap();
+
+        let mut custom = HashMap::new();
+        custom.insert(
+            "unsafe_blocks".to_string(),
+            serde_json::Value::Number(3.into()),
+        );
+        let fm = Frontmatter::new("src/lib.rs".to_string(), metadata)
+            .with_custom_fields(Some("rust"), Some(&custom));
+        let merged = fm.render_merged(&previous);
+
+        assert!(merged.contains("  unsafe_blocks: 3")); // machine-owned, overwritten
+        assert!(merged.contains("  owner: alice")); // hand-added, carried over
+    }
+
+    #[test]
+    fn render_merged_preserves_a_section_this_run_has_no_opinion_on() {
+        // `fm` computed no custom fields this run, so the previous
+        // `typescript:` section isn't re-derived — it's carried over
+        // verbatim rather than silently dropped.
+        let metadata = Metadata {
+            exports: vec![],
+            imports: vec![],
+            dependencies: vec![],
+            loc: 5,
+        };
+        let previous = Frontmatter::parse(
+            "---\nfile: test.ts\nloc: 1\nmodified: 2025-01-01\ntypescript:\n  decorators: 0\n",
+        )
+        .unwrap();
+
+        let fm = Frontmatter::new("test.ts".to_string(), metadata);
+        let merged = fm.render_merged(&previous);
+
+        assert!(merged.contains("typescript:"));
+        assert!(merged.contains("  decorators: 0"));
+    }
+
+    #[test]
+    fn render_merged_matches_render_when_previous_has_nothing_extra() {
+        let metadata = Metadata {
+            exports: vec![],
+            imports: vec![],
+            dependencies: vec![],
+            loc: 5,
+        };
+        let fm = Frontmatter::new("test.ts".to_string(), metadata);
+        let empty_previous = ParsedFrontmatter::default();
+        assert_eq!(fm.render_merged(&empty_previous), fm.render());
+    }
 }
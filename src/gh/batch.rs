@@ -9,7 +9,10 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::io::{BufRead, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread;
 
 use super::report::{IssueComparisonReport, Savings};
 use super::{fetch_issue, preflight_check, run_issue_compare, IssueRef};
@@ -22,6 +25,23 @@ pub struct CorpusIssue {
     pub url: String,
     #[serde(default)]
     pub tags: Vec<String>,
+    /// Regression floor for this issue, checked by `run_batch` when
+    /// `BatchOptions::gate` is set. Absent means the issue is reported on
+    /// but never fails the batch.
+    #[serde(default)]
+    pub expect: Option<ExpectBounds>,
+}
+
+/// Minimum savings (or maximum cost) a corpus issue must hit to count as a
+/// pass under gate mode. Any bound left `None` is not checked.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExpectBounds {
+    #[serde(default)]
+    pub min_cost_pct: Option<f64>,
+    #[serde(default)]
+    pub min_total_tokens_pct: Option<f64>,
+    #[serde(default)]
+    pub max_cost_usd: Option<f64>,
 }
 
 /// The full corpus file (issues.json).
@@ -53,6 +73,28 @@ pub struct BatchOptions {
     pub max_budget: f64,
     pub dry_run: bool,
     pub resume: bool,
+    /// Number of times to run each corpus issue, so per-metric means carry
+    /// a standard deviation and confidence interval instead of a single
+    /// noisy sample. 1 reproduces the old single-run behavior.
+    pub trials: u32,
+    /// When set, `run_batch` serves live progress on this address for the
+    /// duration of the batch: `/metrics` (Prometheus text exposition) and
+    /// `/status` (JSON). Lets a long corpus run be scraped or dashboarded
+    /// instead of only observed through stdout.
+    pub metrics_addr: Option<SocketAddr>,
+    /// When set, each issue's `expect` bounds (if any) are checked after it
+    /// runs; `run_batch` returns `Ok(1)` if any gated issue regresses below
+    /// its floor, mirroring `run_validate`'s exit-code convention.
+    pub gate: bool,
+    /// When set, each completed issue's `language`/`size` tags are
+    /// recomputed from its `fmm` fix diff (see `classify_diff`) instead of
+    /// trusting whatever was hand-authored in the corpus file, and the
+    /// corpus file is rewritten with the resolved tags.
+    pub auto_tag: bool,
+    /// Companion format to render alongside the always-written
+    /// `proof-dataset.json` (the canonical source of truth). Defaults to
+    /// Markdown; `Json` is a no-op here since it's already covered.
+    pub format: ProofFormat,
 }
 
 // ── Checkpoint (JSONL) ──
@@ -60,6 +102,7 @@ pub struct BatchOptions {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct CheckpointEntry {
     url: String,
+    trial_index: u32,
     status: CheckpointStatus,
     #[serde(skip_serializing_if = "Option::is_none")]
     report: Option<IssueComparisonReport>,
@@ -78,13 +121,16 @@ fn checkpoint_path(output_dir: &Path) -> PathBuf {
     output_dir.join("checkpoint.jsonl")
 }
 
-fn load_checkpoint(output_dir: &Path) -> HashMap<String, CheckpointEntry> {
+/// Keyed on `(url, trial_index)` rather than just `url`, so resuming a
+/// partially-completed trial set reruns only the missing trials instead of
+/// treating the whole issue as done once trial 0 lands.
+fn load_checkpoint(output_dir: &Path) -> HashMap<(String, u32), CheckpointEntry> {
     let path = checkpoint_path(output_dir);
     let mut map = HashMap::new();
     if let Ok(file) = fs::File::open(&path) {
         for line in std::io::BufReader::new(file).lines().map_while(Result::ok) {
             if let Ok(entry) = serde_json::from_str::<CheckpointEntry>(&line) {
-                map.insert(entry.url.clone(), entry);
+                map.insert((entry.url.clone(), entry.trial_index), entry);
             }
         }
     }
@@ -115,22 +161,301 @@ pub struct ProofDataset {
     pub aggregate: AggregateStats,
     pub by_tag: HashMap<String, AggregateStats>,
     pub issues: Vec<IssueComparisonReport>,
+    /// Gate violations found against each issue's `expect` bounds. Empty
+    /// when `BatchOptions::gate` is off or every gated issue passed.
+    #[serde(default)]
+    pub regressions: Vec<Regression>,
+}
+
+/// A single `expect` bound an issue failed to meet under gate mode.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Regression {
+    pub issue_url: String,
+    pub trial_index: u32,
+    pub bound: String,
+    pub expected: String,
+    pub actual: String,
+}
+
+/// Compares one trial's results against its corpus issue's `expect` bounds,
+/// returning a `Regression` per bound that was violated.
+fn check_regressions(
+    issue_url: &str,
+    trial_index: u32,
+    expect: &ExpectBounds,
+    savings: &Savings,
+    fmm_cost_usd: f64,
+) -> Vec<Regression> {
+    let mut regressions = Vec::new();
+
+    if let Some(min_cost_pct) = expect.min_cost_pct {
+        if savings.cost_pct < min_cost_pct {
+            regressions.push(Regression {
+                issue_url: issue_url.to_string(),
+                trial_index,
+                bound: "min_cost_pct".to_string(),
+                expected: format!("≥{:.0}% cost saving", min_cost_pct),
+                actual: format!("{:.0}%", savings.cost_pct),
+            });
+        }
+    }
+
+    if let Some(min_total_tokens_pct) = expect.min_total_tokens_pct {
+        if savings.total_tokens_pct < min_total_tokens_pct {
+            regressions.push(Regression {
+                issue_url: issue_url.to_string(),
+                trial_index,
+                bound: "min_total_tokens_pct".to_string(),
+                expected: format!("≥{:.0}% token reduction", min_total_tokens_pct),
+                actual: format!("{:.0}%", savings.total_tokens_pct),
+            });
+        }
+    }
+
+    if let Some(max_cost_usd) = expect.max_cost_usd {
+        if fmm_cost_usd > max_cost_usd {
+            regressions.push(Regression {
+                issue_url: issue_url.to_string(),
+                trial_index,
+                bound: "max_cost_usd".to_string(),
+                expected: format!("≤${:.2}", max_cost_usd),
+                actual: format!("${:.2}", fmm_cost_usd),
+            });
+        }
+    }
+
+    regressions
+}
+
+/// Mean, sample standard deviation, and 95% confidence interval over a set
+/// of per-trial samples for one metric. With a single sample (no trials to
+/// compare), stddev is 0 and the interval collapses to the mean.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
+pub struct Stat {
+    pub mean: f64,
+    pub stddev: f64,
+    pub ci_low: f64,
+    pub ci_high: f64,
+}
+
+impl Stat {
+    fn from_samples(samples: &[f64]) -> Self {
+        let n = samples.len();
+        if n == 0 {
+            return Self::default();
+        }
+        let mean = samples.iter().sum::<f64>() / n as f64;
+        if n < 2 {
+            return Self {
+                mean,
+                stddev: 0.0,
+                ci_low: mean,
+                ci_high: mean,
+            };
+        }
+
+        let variance =
+            samples.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n as f64 - 1.0);
+        let stddev = variance.sqrt();
+        let half_width = t_quantile_975((n - 1) as u32) * stddev / (n as f64).sqrt();
+
+        Self {
+            mean,
+            stddev,
+            ci_low: mean - half_width,
+            ci_high: mean + half_width,
+        }
+    }
+
+    /// Half-width of the confidence interval, for `mean ± half_width` display.
+    fn ci_half_width(&self) -> f64 {
+        (self.ci_high - self.ci_low) / 2.0
+    }
+}
+
+/// Student's t 0.975 quantile (two-tailed 95% CI) for degrees of freedom
+/// 1..=30; beyond that the t-distribution is close enough to normal that
+/// the usual 1.96 z-value is used instead.
+const T_TABLE_975: [f64; 30] = [
+    12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262, 2.228, 2.201, 2.179, 2.160,
+    2.145, 2.131, 2.120, 2.110, 2.101, 2.093, 2.086, 2.080, 2.074, 2.069, 2.064, 2.060, 2.056,
+    2.052, 2.048, 2.045, 2.042,
+];
+
+fn t_quantile_975(df: u32) -> f64 {
+    match df.checked_sub(1).and_then(|i| T_TABLE_975.get(i as usize)) {
+        Some(&t) => t,
+        None => 1.96,
+    }
+}
+
+/// Result of a paired Wilcoxon signed-rank test on each issue's
+/// `control_cost_usd - fmm_cost_usd` difference — lets a reviewer challenge
+/// the headline savings number ("is that just noise?") with a real answer.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
+pub struct WilcoxonResult {
+    /// Number of non-zero paired differences the test was computed over.
+    pub n: usize,
+    pub w_statistic: f64,
+    pub z: f64,
+    pub p_value: f64,
+}
+
+/// Drops zero differences, ranks `|d_i|` with average ranks for ties, and
+/// compares the smaller of the positive/negative rank sums against its
+/// normal approximation. `n < 10` or all-zero differences return a
+/// `WilcoxonResult::default()` (p_value 0.0), since the normal approximation
+/// isn't reliable at small `n` and there is nothing for it to say.
+fn wilcoxon_signed_rank(differences: &[f64]) -> WilcoxonResult {
+    let mut signed: Vec<(f64, f64)> = differences
+        .iter()
+        .filter(|&&d| d != 0.0)
+        .map(|&d| (d.abs(), d.signum()))
+        .collect();
+    let n = signed.len();
+    if n < 10 {
+        return WilcoxonResult::default();
+    }
+    signed.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+    let mut ranks = vec![0.0; n];
+    let mut i = 0;
+    while i < n {
+        let mut j = i;
+        while j + 1 < n && (signed[j + 1].0 - signed[i].0).abs() < f64::EPSILON {
+            j += 1;
+        }
+        let avg_rank = ((i + 1) + (j + 1)) as f64 / 2.0;
+        ranks[i..=j].fill(avg_rank);
+        i = j + 1;
+    }
+
+    let w_pos: f64 = (0..n).filter(|&k| signed[k].1 > 0.0).map(|k| ranks[k]).sum();
+    let w_neg: f64 = (0..n).filter(|&k| signed[k].1 < 0.0).map(|k| ranks[k]).sum();
+    let w = w_pos.min(w_neg);
+
+    let n_f = n as f64;
+    let mean_w = n_f * (n_f + 1.0) / 4.0;
+    let sd_w = (n_f * (n_f + 1.0) * (2.0 * n_f + 1.0) / 24.0).sqrt();
+    let z = if sd_w > 0.0 { (w - mean_w) / sd_w } else { 0.0 };
+    let p_value = (2.0 * (1.0 - standard_normal_cdf(z.abs()))).clamp(0.0, 1.0);
+
+    WilcoxonResult {
+        n,
+        w_statistic: w,
+        z,
+        p_value,
+    }
+}
+
+/// Error function via the Abramowitz & Stegun 7.1.26 approximation
+/// (max absolute error ~1.5e-7) — avoids pulling in a stats crate for a
+/// single CDF lookup.
+fn erf(x: f64) -> f64 {
+    const A1: f64 = 0.254829592;
+    const A2: f64 = -0.284496736;
+    const A3: f64 = 1.421413741;
+    const A4: f64 = -1.453152027;
+    const A5: f64 = 1.061405429;
+    const P: f64 = 0.3275911;
+
+    let sign = if x < 0.0 { -1.0 } else { 1.0 };
+    let x = x.abs();
+    let t = 1.0 / (1.0 + P * x);
+    let poly = ((((A5 * t + A4) * t + A3) * t + A2) * t + A1) * t;
+    sign * (1.0 - poly * (-x * x).exp())
+}
+
+fn standard_normal_cdf(x: f64) -> f64 {
+    0.5 * (1.0 + erf(x / std::f64::consts::SQRT_2))
+}
+
+/// Percentile bootstrap 95% confidence interval for the mean cost-savings
+/// percentage.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
+pub struct BootstrapCi {
+    pub low: f64,
+    pub high: f64,
+}
+
+const BOOTSTRAP_ITERATIONS: usize = 2000;
+
+/// Minimal xorshift64 PRNG — the repo has no `rand` dependency, and this is
+/// only used to pick resample indices, not for anything security-sensitive.
+struct Xorshift64 {
+    state: u64,
+}
+
+impl Xorshift64 {
+    fn seeded_from_clock() -> Self {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0x9E3779B97F4A7C15);
+        Self {
+            state: if nanos == 0 { 0x9E3779B97F4A7C15 } else { nanos },
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x
+    }
+
+    fn next_index(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+}
+
+/// Resamples `samples` with replacement `BOOTSTRAP_ITERATIONS` times,
+/// recomputes the mean each time, and reports the 2.5th/97.5th percentiles
+/// of that resampled-mean distribution.
+fn bootstrap_ci(samples: &[f64]) -> BootstrapCi {
+    let n = samples.len();
+    if n == 0 {
+        return BootstrapCi::default();
+    }
+    if n == 1 {
+        return BootstrapCi {
+            low: samples[0],
+            high: samples[0],
+        };
+    }
+
+    let mut rng = Xorshift64::seeded_from_clock();
+    let mut means: Vec<f64> = (0..BOOTSTRAP_ITERATIONS)
+        .map(|_| (0..n).map(|_| samples[rng.next_index(n)]).sum::<f64>() / n as f64)
+        .collect();
+    means.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+    let low = means[((0.025 * BOOTSTRAP_ITERATIONS as f64).floor() as usize).min(BOOTSTRAP_ITERATIONS - 1)];
+    let high = means[((0.975 * BOOTSTRAP_ITERATIONS as f64).ceil() as usize).min(BOOTSTRAP_ITERATIONS) - 1];
+    BootstrapCi { low, high }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AggregateStats {
     pub count: usize,
-    pub mean_input_tokens_pct: f64,
-    pub mean_total_tokens_pct: f64,
-    pub mean_cost_pct: f64,
-    pub mean_turns_pct: f64,
-    pub mean_tool_calls_pct: f64,
-    pub mean_files_read_pct: f64,
-    pub mean_duration_pct: f64,
+    pub input_tokens_pct: Stat,
+    pub total_tokens_pct: Stat,
+    pub cost_pct: Stat,
+    pub turns_pct: Stat,
+    pub tool_calls_pct: Stat,
+    pub files_read_pct: Stat,
+    pub duration_pct: Stat,
     pub median_cost_pct: f64,
     pub median_total_tokens_pct: f64,
     pub total_control_cost_usd: f64,
     pub total_fmm_cost_usd: f64,
+    /// Paired significance test on per-issue cost savings; `n` is 0 when
+    /// there were fewer than 10 non-zero differences to test.
+    pub cost_significance: WilcoxonResult,
+    /// 95% bootstrap CI for the mean cost-savings percentage.
+    pub cost_savings_ci: BootstrapCi,
 }
 
 fn aggregate_savings(reports: &[&IssueComparisonReport]) -> AggregateStats {
@@ -138,47 +463,54 @@ fn aggregate_savings(reports: &[&IssueComparisonReport]) -> AggregateStats {
     if n == 0 {
         return AggregateStats {
             count: 0,
-            mean_input_tokens_pct: 0.0,
-            mean_total_tokens_pct: 0.0,
-            mean_cost_pct: 0.0,
-            mean_turns_pct: 0.0,
-            mean_tool_calls_pct: 0.0,
-            mean_files_read_pct: 0.0,
-            mean_duration_pct: 0.0,
+            input_tokens_pct: Stat::default(),
+            total_tokens_pct: Stat::default(),
+            cost_pct: Stat::default(),
+            turns_pct: Stat::default(),
+            tool_calls_pct: Stat::default(),
+            files_read_pct: Stat::default(),
+            duration_pct: Stat::default(),
             median_cost_pct: 0.0,
             median_total_tokens_pct: 0.0,
             total_control_cost_usd: 0.0,
             total_fmm_cost_usd: 0.0,
+            cost_significance: WilcoxonResult::default(),
+            cost_savings_ci: BootstrapCi::default(),
         };
     }
 
-    let sum = |f: fn(&Savings) -> f64| -> f64 {
-        reports.iter().map(|r| f(&r.savings)).sum::<f64>() / n as f64
-    };
+    let samples = |f: fn(&Savings) -> f64| -> Vec<f64> { reports.iter().map(|r| f(&r.savings)).collect() };
 
-    let mut cost_pcts: Vec<f64> = reports.iter().map(|r| r.savings.cost_pct).collect();
+    let mut cost_pcts = samples(|s| s.cost_pct);
     cost_pcts.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
 
-    let mut token_pcts: Vec<f64> = reports.iter().map(|r| r.savings.total_tokens_pct).collect();
+    let mut token_pcts = samples(|s| s.total_tokens_pct);
     token_pcts.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
 
+    let cost_diffs: Vec<f64> = reports
+        .iter()
+        .map(|r| r.control.cost_usd - r.fmm.cost_usd)
+        .collect();
+
     AggregateStats {
         count: n,
-        mean_input_tokens_pct: sum(|s| s.input_tokens_pct),
-        mean_total_tokens_pct: sum(|s| s.total_tokens_pct),
-        mean_cost_pct: sum(|s| s.cost_pct),
-        mean_turns_pct: sum(|s| s.turns_pct),
-        mean_tool_calls_pct: sum(|s| s.tool_calls_pct),
-        mean_files_read_pct: sum(|s| s.files_read_pct),
-        mean_duration_pct: sum(|s| s.duration_pct),
+        input_tokens_pct: Stat::from_samples(&samples(|s| s.input_tokens_pct)),
+        total_tokens_pct: Stat::from_samples(&token_pcts),
+        cost_pct: Stat::from_samples(&cost_pcts),
+        turns_pct: Stat::from_samples(&samples(|s| s.turns_pct)),
+        tool_calls_pct: Stat::from_samples(&samples(|s| s.tool_calls_pct)),
+        files_read_pct: Stat::from_samples(&samples(|s| s.files_read_pct)),
+        duration_pct: Stat::from_samples(&samples(|s| s.duration_pct)),
         median_cost_pct: median(&cost_pcts),
         median_total_tokens_pct: median(&token_pcts),
         total_control_cost_usd: reports.iter().map(|r| r.control.cost_usd).sum(),
         total_fmm_cost_usd: reports.iter().map(|r| r.fmm.cost_usd).sum(),
+        cost_significance: wilcoxon_signed_rank(&cost_diffs),
+        cost_savings_ci: bootstrap_ci(&cost_pcts),
     }
 }
 
-fn median(sorted: &[f64]) -> f64 {
+pub(crate) fn median(sorted: &[f64]) -> f64 {
     let n = sorted.len();
     if n == 0 {
         return 0.0;
@@ -192,19 +524,53 @@ fn median(sorted: &[f64]) -> f64 {
 
 // ── Proof dataset markdown ──
 
+/// Render a [`Stat`] as `42% ± 6%` — the confidence-interval half-width
+/// rounds to 0 when there's only one trial, so single-trial runs render as
+/// a plain-looking `42% ± 0%` rather than needing a separate code path.
+fn fmt_stat_pct(stat: &Stat) -> String {
+    format!("{:.0}% ± {:.0}%", stat.mean, stat.ci_half_width())
+}
+
+/// Group `reports` by issue URL (preserving first-seen order) and aggregate
+/// each issue's trials into one [`AggregateStats`], so a corpus issue run
+/// with `trials > 1` gets a single per-issue row instead of one per trial.
+fn group_reports_by_issue(
+    reports: &[IssueComparisonReport],
+) -> Vec<(&IssueComparisonReport, AggregateStats)> {
+    let mut order: Vec<&str> = Vec::new();
+    let mut grouped: HashMap<&str, Vec<&IssueComparisonReport>> = HashMap::new();
+    for report in reports {
+        grouped
+            .entry(report.issue_url.as_str())
+            .or_insert_with(|| {
+                order.push(report.issue_url.as_str());
+                Vec::new()
+            })
+            .push(report);
+    }
+
+    order
+        .into_iter()
+        .map(|url| {
+            let group = &grouped[url];
+            (group[0], aggregate_savings(group))
+        })
+        .collect()
+}
+
 fn generate_proof_markdown(dataset: &ProofDataset, corpus: &Corpus) -> String {
     let mut md = String::new();
 
     md.push_str("# FMM Proof Dataset\n\n");
     md.push_str(&format!(
         "Across **{}** issues in **{}** repos spanning **{}** languages, \
-         FMM reduced token usage by **{:.0}%** (median {:.0}%), cost by **{:.0}%** (median {:.0}%).\n\n",
+         FMM reduced token usage by **{}** (median {:.0}%), cost by **{}** (median {:.0}%).\n\n",
         dataset.completed,
         count_unique_repos(&dataset.issues),
         count_unique_languages(corpus),
-        dataset.aggregate.mean_total_tokens_pct,
+        fmt_stat_pct(&dataset.aggregate.total_tokens_pct),
         dataset.aggregate.median_total_tokens_pct,
-        dataset.aggregate.mean_cost_pct,
+        fmt_stat_pct(&dataset.aggregate.cost_pct),
         dataset.aggregate.median_cost_pct,
     ));
 
@@ -215,32 +581,34 @@ fn generate_proof_markdown(dataset: &ProofDataset, corpus: &Corpus) -> String {
     md.push_str("| Metric | Mean Reduction | Median Reduction |\n");
     md.push_str("|--------|---------------|------------------|\n");
     md.push_str(&format!(
-        "| Total tokens | {:.0}% | {:.0}% |\n",
-        dataset.aggregate.mean_total_tokens_pct, dataset.aggregate.median_total_tokens_pct
+        "| Total tokens | {} | {:.0}% |\n",
+        fmt_stat_pct(&dataset.aggregate.total_tokens_pct),
+        dataset.aggregate.median_total_tokens_pct
     ));
     md.push_str(&format!(
-        "| Input tokens | {:.0}% | — |\n",
-        dataset.aggregate.mean_input_tokens_pct
+        "| Input tokens | {} | — |\n",
+        fmt_stat_pct(&dataset.aggregate.input_tokens_pct)
     ));
     md.push_str(&format!(
-        "| Cost | {:.0}% | {:.0}% |\n",
-        dataset.aggregate.mean_cost_pct, dataset.aggregate.median_cost_pct
+        "| Cost | {} | {:.0}% |\n",
+        fmt_stat_pct(&dataset.aggregate.cost_pct),
+        dataset.aggregate.median_cost_pct
     ));
     md.push_str(&format!(
-        "| Turns | {:.0}% | — |\n",
-        dataset.aggregate.mean_turns_pct
+        "| Turns | {} | — |\n",
+        fmt_stat_pct(&dataset.aggregate.turns_pct)
     ));
     md.push_str(&format!(
-        "| Tool calls | {:.0}% | — |\n",
-        dataset.aggregate.mean_tool_calls_pct
+        "| Tool calls | {} | — |\n",
+        fmt_stat_pct(&dataset.aggregate.tool_calls_pct)
     ));
     md.push_str(&format!(
-        "| Files read | {:.0}% | — |\n",
-        dataset.aggregate.mean_files_read_pct
+        "| Files read | {} | — |\n",
+        fmt_stat_pct(&dataset.aggregate.files_read_pct)
     ));
     md.push_str(&format!(
-        "| Duration | {:.0}% | — |\n\n",
-        dataset.aggregate.mean_duration_pct
+        "| Duration | {} | — |\n\n",
+        fmt_stat_pct(&dataset.aggregate.duration_pct)
     ));
 
     md.push_str(&format!(
@@ -250,6 +618,21 @@ fn generate_proof_markdown(dataset: &ProofDataset, corpus: &Corpus) -> String {
         dataset.aggregate.total_control_cost_usd + dataset.aggregate.total_fmm_cost_usd,
     ));
 
+    let ci = &dataset.aggregate.cost_savings_ci;
+    let sig = &dataset.aggregate.cost_significance;
+    md.push_str(&format!(
+        "**Cost savings:** median {:.0}%, 95% CI [{:.0}%, {:.0}%]",
+        dataset.aggregate.median_cost_pct, ci.low, ci.high,
+    ));
+    if sig.n > 0 {
+        md.push_str(&format!(
+            ", Wilcoxon signed-rank p = {:.4} (n = {})\n\n",
+            sig.p_value, sig.n
+        ));
+    } else {
+        md.push_str(" (too few paired observations for a significance test)\n\n");
+    }
+
     // By-tag breakdown
     if !dataset.by_tag.is_empty() {
         md.push_str("## By Tag\n\n");
@@ -260,26 +643,26 @@ fn generate_proof_markdown(dataset: &ProofDataset, corpus: &Corpus) -> String {
         for (tag, stats) in &tags {
             md.push_str(&format!(
                 "| {} | {} | {:.0}% | {:.0}% |\n",
-                tag, stats.count, stats.mean_total_tokens_pct, stats.mean_cost_pct
+                tag, stats.count, stats.total_tokens_pct.mean, stats.cost_pct.mean
             ));
         }
         md.push('\n');
     }
 
-    // Per-issue table
+    // Per-issue table (one row per issue, trials folded into mean ± CI)
     md.push_str("## Per-Issue Results\n\n");
     md.push_str("| # | Repo | Issue | Tokens Saved | Cost Saved | Turns Saved |\n");
     md.push_str("|---|------|-------|-------------|------------|-------------|\n");
-    for (i, report) in dataset.issues.iter().enumerate() {
+    for (i, (report, stats)) in group_reports_by_issue(&dataset.issues).iter().enumerate() {
         md.push_str(&format!(
-            "| {} | {} | [#{}]({}) | {:.0}% | {:.0}% | {:.0}% |\n",
+            "| {} | {} | [#{}]({}) | {} | {} | {} |\n",
             i + 1,
             report.repo,
             report.issue_number,
             report.issue_url,
-            report.savings.total_tokens_pct,
-            report.savings.cost_pct,
-            report.savings.turns_pct,
+            fmt_stat_pct(&stats.total_tokens_pct),
+            fmt_stat_pct(&stats.cost_pct),
+            fmt_stat_pct(&stats.turns_pct),
         ));
     }
     md.push('\n');
@@ -291,9 +674,176 @@ fn generate_proof_markdown(dataset: &ProofDataset, corpus: &Corpus) -> String {
         ));
     }
 
+    // Regressions (gate mode only; empty when gate is off or everything passed)
+    if !dataset.regressions.is_empty() {
+        md.push_str("\n## Regressions\n\n");
+        md.push_str("| Issue | Bound | Expected | Actual |\n");
+        md.push_str("|-------|-------|----------|--------|\n");
+        for r in &dataset.regressions {
+            md.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                r.issue_url, r.bound, r.expected, r.actual
+            ));
+        }
+        md.push('\n');
+    }
+
     md
 }
 
+/// Output format for a rendered [`ProofDataset`], selected via
+/// `BatchOptions::format`. `ProofDataset`/`AggregateStats` stay the single
+/// source of truth — each variant is a pure projection of the same data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProofFormat {
+    Json,
+    Markdown,
+    Csv,
+    Html,
+}
+
+impl Default for ProofFormat {
+    fn default() -> Self {
+        ProofFormat::Markdown
+    }
+}
+
+impl ProofFormat {
+    /// File extension to use when writing this format to disk.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ProofFormat::Json => "json",
+            ProofFormat::Markdown => "md",
+            ProofFormat::Csv => "csv",
+            ProofFormat::Html => "html",
+        }
+    }
+}
+
+/// Render `dataset` in the requested `format`.
+pub fn render_proof_dataset(dataset: &ProofDataset, corpus: &Corpus, format: ProofFormat) -> Result<String> {
+    Ok(match format {
+        ProofFormat::Json => serde_json::to_string_pretty(dataset)?,
+        ProofFormat::Markdown => generate_proof_markdown(dataset, corpus),
+        ProofFormat::Csv => generate_proof_csv(dataset),
+        ProofFormat::Html => generate_proof_html(dataset, corpus),
+    })
+}
+
+/// Wrap `value` in quotes and double any embedded quote when it contains a
+/// comma, quote, or newline, per RFC 4180.
+pub(crate) fn csv_escape(value: &str) -> String {
+    if value.contains(',') || value.contains('"') || value.contains('\n') {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Flat per-issue CSV — one row per issue with control/fmm tokens, cost,
+/// turns, tool calls, and savings percentages — trivially importable into a
+/// spreadsheet or notebook for independent verification.
+fn generate_proof_csv(dataset: &ProofDataset) -> String {
+    let mut csv = String::new();
+    csv.push_str(
+        "issue_url,repo,control_input_tokens,control_output_tokens,control_cost_usd,control_turns,control_tool_calls,\
+         fmm_input_tokens,fmm_output_tokens,fmm_cost_usd,fmm_turns,fmm_tool_calls,\
+         input_tokens_pct,total_tokens_pct,cost_pct,turns_pct,tool_calls_pct,files_read_pct,duration_pct\n",
+    );
+    for issue in &dataset.issues {
+        csv.push_str(&format!(
+            "{},{},{},{},{:.4},{},{},{},{},{:.4},{},{},{:.2},{:.2},{:.2},{:.2},{:.2},{:.2},{:.2}\n",
+            csv_escape(&issue.issue_url),
+            csv_escape(&issue.repo),
+            issue.control.input_tokens,
+            issue.control.output_tokens,
+            issue.control.cost_usd,
+            issue.control.turns,
+            issue.control.tool_calls,
+            issue.fmm.input_tokens,
+            issue.fmm.output_tokens,
+            issue.fmm.cost_usd,
+            issue.fmm.turns,
+            issue.fmm.tool_calls,
+            issue.savings.input_tokens_pct,
+            issue.savings.total_tokens_pct,
+            issue.savings.cost_pct,
+            issue.savings.turns_pct,
+            issue.savings.tool_calls_pct,
+            issue.savings.files_read_pct,
+            issue.savings.duration_pct,
+        ));
+    }
+    csv
+}
+
+pub(crate) fn html_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Self-contained HTML page (inline CSS, no external assets) with the
+/// aggregate headline and per-tag breakdown, so the proof can be published
+/// without a Markdown renderer.
+fn generate_proof_html(dataset: &ProofDataset, corpus: &Corpus) -> String {
+    let mut tags: Vec<_> = dataset.by_tag.iter().collect();
+    tags.sort_by_key(|(k, _)| (*k).clone());
+    let mut rows = String::new();
+    for (tag, stats) in &tags {
+        rows.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{:.0}%</td><td>{:.0}%</td></tr>\n",
+            html_escape(tag),
+            stats.count,
+            stats.total_tokens_pct.mean,
+            stats.cost_pct.mean,
+        ));
+    }
+
+    let significance = if dataset.aggregate.cost_significance.n > 0 {
+        format!(
+            ", Wilcoxon signed-rank p = {:.4} (n = {})",
+            dataset.aggregate.cost_significance.p_value, dataset.aggregate.cost_significance.n
+        )
+    } else {
+        String::new()
+    };
+
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>FMM Proof Dataset</title>\n\
+         <style>\n\
+         body {{ font-family: system-ui, sans-serif; max-width: 860px; margin: 2rem auto; padding: 0 1rem; }}\n\
+         table {{ border-collapse: collapse; width: 100%; margin-top: 1rem; }}\n\
+         th, td {{ border: 1px solid #ccc; padding: 0.4rem 0.8rem; text-align: left; }}\n\
+         th {{ background: #f5f5f5; }}\n\
+         </style>\n</head>\n<body>\n\
+         <h1>FMM Proof Dataset</h1>\n\
+         <p>Across <strong>{}</strong> issues in <strong>{}</strong> repos spanning <strong>{}</strong> languages, \
+         FMM reduced token usage by <strong>{}</strong> (median {:.0}%), cost by <strong>{}</strong> (median {:.0}%).</p>\n\
+         <p>Cost savings: median {:.0}%, 95% CI [{:.0}%, {:.0}%]{}</p>\n\
+         <p><em>Generated: {}</em></p>\n\
+         <h2>By Tag</h2>\n\
+         <table>\n<tr><th>Tag</th><th>N</th><th>Mean Token Reduction</th><th>Mean Cost Reduction</th></tr>\n{}</table>\n\
+         </body>\n</html>\n",
+        dataset.completed,
+        count_unique_repos(&dataset.issues),
+        count_unique_languages(corpus),
+        fmt_stat_pct(&dataset.aggregate.total_tokens_pct),
+        dataset.aggregate.median_total_tokens_pct,
+        fmt_stat_pct(&dataset.aggregate.cost_pct),
+        dataset.aggregate.median_cost_pct,
+        dataset.aggregate.median_cost_pct,
+        dataset.aggregate.cost_savings_ci.low,
+        dataset.aggregate.cost_savings_ci.high,
+        significance,
+        html_escape(&dataset.generated_at),
+        rows,
+    )
+}
+
 fn count_unique_repos(reports: &[IssueComparisonReport]) -> usize {
     let repos: std::collections::HashSet<&str> = reports.iter().map(|r| r.repo.as_str()).collect();
     repos.len()
@@ -325,6 +875,168 @@ const LANGUAGES: &[&str] = &[
 const SIZES: &[&str] = &["small", "medium", "large", "massive"];
 const TASK_TYPES: &[&str] = &["bugfix", "feature", "refactor", "perf"];
 
+// ── Auto-tagging (tokei-style language/size classification from diffs) ──
+
+/// Cutoffs, in changed LOC, dividing issues into `SIZES` buckets. The last
+/// bucket (`massive`) has no upper bound.
+#[derive(Debug, Clone, Copy)]
+pub struct SizeCutoffs {
+    pub small_max: usize,
+    pub medium_max: usize,
+    pub large_max: usize,
+}
+
+impl Default for SizeCutoffs {
+    fn default() -> Self {
+        Self {
+            small_max: 20,
+            medium_max: 100,
+            large_max: 500,
+        }
+    }
+}
+
+fn size_bucket(changed_loc: usize, cutoffs: &SizeCutoffs) -> &'static str {
+    if changed_loc <= cutoffs.small_max {
+        "small"
+    } else if changed_loc <= cutoffs.medium_max {
+        "medium"
+    } else if changed_loc <= cutoffs.large_max {
+        "large"
+    } else {
+        "massive"
+    }
+}
+
+/// Minimal per-language comment delimiters — just enough to approximate
+/// tokei's non-blank/non-comment counting without a full tokei dependency.
+struct CommentStyle {
+    line: &'static str,
+    block: Option<(&'static str, &'static str)>,
+}
+
+fn comment_style_for_extension(ext: &str) -> Option<(&'static str, CommentStyle)> {
+    let (lang, style) = match ext {
+        "ts" | "tsx" | "js" | "jsx" => (
+            "typescript",
+            CommentStyle { line: "//", block: Some(("/*", "*/")) },
+        ),
+        "py" => ("python", CommentStyle { line: "#", block: None }),
+        "rs" => ("rust", CommentStyle { line: "//", block: Some(("/*", "*/")) }),
+        "go" => ("go", CommentStyle { line: "//", block: Some(("/*", "*/")) }),
+        "java" => ("java", CommentStyle { line: "//", block: Some(("/*", "*/")) }),
+        "cpp" | "cc" | "c" | "h" | "hpp" => (
+            "cpp",
+            CommentStyle { line: "//", block: Some(("/*", "*/")) },
+        ),
+        "cs" => ("csharp", CommentStyle { line: "//", block: Some(("/*", "*/")) }),
+        "rb" => ("ruby", CommentStyle { line: "#", block: Some(("=begin", "=end")) }),
+        _ => return None,
+    };
+    Some((lang, style))
+}
+
+/// Counts non-blank, non-comment changed lines per language in a unified
+/// diff (tokei-style, but scoped to the `+`/`-` lines), and returns the
+/// dominant language plus the total changed LOC across all recognized
+/// languages.
+fn classify_diff(diff: &str) -> (Option<&'static str>, usize) {
+    let mut loc_by_lang: HashMap<&'static str, usize> = HashMap::new();
+    let mut current: Option<(&'static str, CommentStyle)> = None;
+    let mut in_block_comment = false;
+
+    for line in diff.lines() {
+        if let Some(path) = line.strip_prefix("+++ b/") {
+            let ext = Path::new(path).extension().and_then(|e| e.to_str()).unwrap_or("");
+            current = comment_style_for_extension(ext);
+            in_block_comment = false;
+            continue;
+        }
+        if line.starts_with("--- ")
+            || line.starts_with("+++ ")
+            || line.starts_with("diff --git")
+            || line.starts_with("index ")
+            || line.starts_with("@@")
+        {
+            continue;
+        }
+
+        let Some(content) = line.strip_prefix('+').or_else(|| line.strip_prefix('-')) else {
+            continue;
+        };
+        let Some((lang, style)) = current.as_ref() else {
+            continue;
+        };
+
+        let trimmed = content.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        if let Some((start, end)) = style.block {
+            if in_block_comment {
+                if trimmed.contains(end) {
+                    in_block_comment = false;
+                }
+                continue;
+            }
+            if trimmed.starts_with(start) {
+                if !trimmed.trim_start_matches(start).contains(end) {
+                    in_block_comment = true;
+                }
+                continue;
+            }
+        }
+        if trimmed.starts_with(style.line) {
+            continue;
+        }
+
+        *loc_by_lang.entry(lang).or_insert(0) += 1;
+    }
+
+    let dominant = loc_by_lang.iter().max_by_key(|(_, &count)| count).map(|(&lang, _)| lang);
+    let total = loc_by_lang.values().sum();
+    (dominant, total)
+}
+
+/// Derives `language`/`size` tags for one issue from its `fmm` fix diff,
+/// replacing hand-authored guesses with a reproducible classification.
+pub fn auto_tag_from_diff(diff: &str, cutoffs: &SizeCutoffs) -> Vec<String> {
+    let (language, changed_loc) = classify_diff(diff);
+    let mut tags = Vec::new();
+    if let Some(lang) = language {
+        tags.push(lang.to_string());
+    }
+    tags.push(size_bucket(changed_loc, cutoffs).to_string());
+    tags
+}
+
+/// Recomputes `language`/`size` tags for every issue with a matching report
+/// this run, from that report's `fmm` diff. `TASK_TYPES` tags are left as
+/// hand-authored, since task type isn't derivable from a diff alone. Prints
+/// a line per issue whose tags actually changed.
+fn apply_auto_tags(corpus: &mut Corpus, reports: &[IssueComparisonReport], cutoffs: &SizeCutoffs) {
+    for issue in &mut corpus.issues {
+        let Some(report) = reports.iter().find(|r| r.issue_url == issue.url) else {
+            continue;
+        };
+
+        let manual_tags: Vec<String> = issue
+            .tags
+            .iter()
+            .filter(|t| TASK_TYPES.contains(&t.as_str()))
+            .cloned()
+            .collect();
+        let mut new_tags = auto_tag_from_diff(&report.fmm.diff, cutoffs);
+        new_tags.extend(manual_tags);
+
+        if new_tags != issue.tags {
+            println!("  {} {}: {:?} -> {:?}", "Tags:".dimmed(), issue.url, issue.tags, new_tags);
+            issue.tags = new_tags;
+        }
+    }
+}
+
 const MIN_ISSUES_PER_CATEGORY: usize = 4;
 
 /// Result of validating a single corpus URL via `gh`.
@@ -495,16 +1207,224 @@ pub fn run_validate(corpus_path: &Path) -> Result<i32> {
     }
 }
 
+// ── Corpus balance gate ──
+
+/// Per-category minimum issue counts for [`check_corpus_balance`]. Defaults
+/// to [`MIN_ISSUES_PER_CATEGORY`] for every category, matching the
+/// heuristic `detect_gaps` has always used; override a field to tighten or
+/// relax one category without touching the others.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct CategoryThresholds {
+    pub languages: usize,
+    pub sizes: usize,
+    pub task_types: usize,
+}
+
+impl Default for CategoryThresholds {
+    fn default() -> Self {
+        Self {
+            languages: MIN_ISSUES_PER_CATEGORY,
+            sizes: MIN_ISSUES_PER_CATEGORY,
+            task_types: MIN_ISSUES_PER_CATEGORY,
+        }
+    }
+}
+
+impl CategoryThresholds {
+    /// Minimum for the category labeled as returned by [`tag_distribution`]
+    /// ("Languages", "Sizes", "Task types"). Unrecognized labels fall back
+    /// to [`MIN_ISSUES_PER_CATEGORY`] rather than panicking.
+    fn minimum_for(&self, category: &str) -> usize {
+        match category {
+            "Languages" => self.languages,
+            "Sizes" => self.sizes,
+            "Task types" => self.task_types,
+            _ => MIN_ISSUES_PER_CATEGORY,
+        }
+    }
+}
+
+/// A present tag (count > 0) that falls below its category's floor.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagShortfall {
+    pub category: String,
+    pub tag: String,
+    pub count: usize,
+    pub minimum: usize,
+    pub shortfall: usize,
+}
+
+/// Evaluate `distribution` against `thresholds`, per category. A tag with
+/// zero issues is absent, not a gap, and is never flagged — same semantics
+/// as `detect_gaps`.
+pub fn check_corpus_balance(
+    distribution: &[(&str, Vec<(&str, usize)>)],
+    thresholds: &CategoryThresholds,
+) -> Vec<TagShortfall> {
+    let mut shortfalls = Vec::new();
+    for (category, tags) in distribution {
+        let minimum = thresholds.minimum_for(category);
+        for &(tag, count) in tags {
+            if count > 0 && count < minimum {
+                shortfalls.push(TagShortfall {
+                    category: category.to_string(),
+                    tag: tag.to_string(),
+                    count,
+                    minimum,
+                    shortfall: minimum - count,
+                });
+            }
+        }
+    }
+    shortfalls
+}
+
+/// Machine-readable summary of a [`check_corpus_balance`] run, suitable for
+/// wiring into CI to block an expensive benchmark run on a corpus that has
+/// drifted out of balance.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorpusCheckReport {
+    pub total_issues: usize,
+    pub shortfalls: Vec<TagShortfall>,
+    pub passed: bool,
+}
+
+/// Run the corpus balance gate: load `corpus_path`, evaluate its tag
+/// distribution against `thresholds`, and print a JSON `CorpusCheckReport`.
+/// Returns `Ok(1)` when any present tag falls below its category's floor
+/// (mirrors `run_validate`'s exit-code convention), `Ok(0)` otherwise.
+pub fn run_corpus_check(corpus_path: &Path, thresholds: CategoryThresholds) -> Result<i32> {
+    let corpus = Corpus::load(corpus_path)?;
+    let distribution = tag_distribution(&corpus);
+    let shortfalls = check_corpus_balance(&distribution, &thresholds);
+    let passed = shortfalls.is_empty();
+
+    let report = CorpusCheckReport {
+        total_issues: corpus.issues.len(),
+        shortfalls,
+        passed,
+    };
+    println!("{}", serde_json::to_string_pretty(&report)?);
+
+    Ok(if passed { 0 } else { 1 })
+}
+
 // ── Main batch orchestrator ──
 
-pub fn run_batch(options: BatchOptions) -> Result<()> {
+// ── Live metrics endpoint ──
+
+/// Shared progress snapshot updated by `run_batch`'s main loop after each
+/// `append_checkpoint`, and served by the optional embedded metrics server
+/// (see `BatchOptions::metrics_addr`).
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BatchProgress {
+    pub issues_total: usize,
+    pub issues_completed: usize,
+    pub issues_failed: usize,
+    pub cost_control_usd: f64,
+    pub cost_fmm_usd: f64,
+    pub remaining_budget_usd: f64,
+    pub latest_total_tokens_pct: f64,
+    pub latest_cost_pct: f64,
+}
+
+impl BatchProgress {
+    fn to_prometheus(&self) -> String {
+        format!(
+            "# HELP fmm_batch_issues_total Total runs planned for this batch.\n\
+             # TYPE fmm_batch_issues_total gauge\n\
+             fmm_batch_issues_total {total}\n\
+             # HELP fmm_batch_issues_completed Runs completed successfully so far.\n\
+             # TYPE fmm_batch_issues_completed counter\n\
+             fmm_batch_issues_completed {completed}\n\
+             # HELP fmm_batch_issues_failed Runs that errored so far.\n\
+             # TYPE fmm_batch_issues_failed counter\n\
+             fmm_batch_issues_failed {failed}\n\
+             # HELP fmm_batch_cost_usd Running cost in USD, by variant.\n\
+             # TYPE fmm_batch_cost_usd gauge\n\
+             fmm_batch_cost_usd{{variant=\"control\"}} {control:.4}\n\
+             fmm_batch_cost_usd{{variant=\"fmm\"}} {fmm:.4}\n\
+             # HELP fmm_batch_remaining_budget_usd Budget left before the batch stops itself.\n\
+             # TYPE fmm_batch_remaining_budget_usd gauge\n\
+             fmm_batch_remaining_budget_usd {remaining:.4}\n\
+             # HELP fmm_batch_latest_total_tokens_pct Most recent run's total token reduction vs control.\n\
+             # TYPE fmm_batch_latest_total_tokens_pct gauge\n\
+             fmm_batch_latest_total_tokens_pct {tokens_pct:.2}\n\
+             # HELP fmm_batch_latest_cost_pct Most recent run's cost reduction vs control.\n\
+             # TYPE fmm_batch_latest_cost_pct gauge\n\
+             fmm_batch_latest_cost_pct {cost_pct:.2}\n",
+            total = self.issues_total,
+            completed = self.issues_completed,
+            failed = self.issues_failed,
+            control = self.cost_control_usd,
+            fmm = self.cost_fmm_usd,
+            remaining = self.remaining_budget_usd,
+            tokens_pct = self.latest_total_tokens_pct,
+            cost_pct = self.latest_cost_pct,
+        )
+    }
+}
+
+/// Spawns a minimal HTTP server on a background thread that serves
+/// `/metrics` and `/status` from the shared `progress` snapshot for as long
+/// as the batch runs. The thread is intentionally left detached — it dies
+/// with the process once `run_batch` returns.
+fn spawn_metrics_server(addr: SocketAddr, progress: Arc<Mutex<BatchProgress>>) -> Result<()> {
+    let listener = TcpListener::bind(addr)
+        .with_context(|| format!("Failed to bind metrics server on {}", addr))?;
+    println!("  {} http://{}/metrics (and /status)", "Metrics:".bold(), addr);
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            handle_metrics_connection(stream, &progress);
+        }
+    });
+    Ok(())
+}
+
+fn handle_metrics_connection(mut stream: TcpStream, progress: &Arc<Mutex<BatchProgress>>) {
+    let path = {
+        let mut reader = std::io::BufReader::new(&stream);
+        let mut request_line = String::new();
+        if reader.read_line(&mut request_line).is_err() {
+            return;
+        }
+        request_line.split_whitespace().nth(1).unwrap_or("/").to_string()
+    };
+
+    let snapshot = progress.lock().map(|p| p.clone()).unwrap_or_default();
+    let (status, content_type, body) = match path.as_str() {
+        "/metrics" => (
+            "200 OK",
+            "text/plain; version=0.0.4",
+            snapshot.to_prometheus(),
+        ),
+        "/status" => (
+            "200 OK",
+            "application/json",
+            serde_json::to_string(&snapshot).unwrap_or_else(|_| "{}".to_string()),
+        ),
+        _ => ("404 Not Found", "text/plain", "not found\n".to_string()),
+    };
+
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    );
+    let _ = stream.write_all(response.as_bytes());
+}
+
+pub fn run_batch(options: BatchOptions) -> Result<i32> {
     preflight_check()?;
 
-    let corpus = Corpus::load(&options.corpus_path)?;
+    let mut corpus = Corpus::load(&options.corpus_path)?;
 
     // Dry run: show plan + cost estimate
     if options.dry_run {
-        return print_dry_run(&corpus, &options);
+        print_dry_run(&corpus, &options)?;
+        return Ok(0);
     }
 
     fs::create_dir_all(&options.output_dir)?;
@@ -524,10 +1444,13 @@ pub fn run_batch(options: BatchOptions) -> Result<()> {
         HashMap::new()
     };
 
-    let total = corpus.issues.len();
+    let trials = options.trials.max(1);
+    let total_issues = corpus.issues.len();
+    let total_runs = total_issues * trials as usize;
     let mut reports: Vec<IssueComparisonReport> = Vec::new();
     let mut failed = 0usize;
     let mut total_cost = 0.0f64;
+    let mut regressions: Vec<Regression> = Vec::new();
 
     // Collect reports from checkpoint
     for entry in checkpoint.values() {
@@ -539,8 +1462,8 @@ pub fn run_batch(options: BatchOptions) -> Result<()> {
         }
     }
 
-    // Per-issue budget: divide remaining budget evenly across remaining issues, capped at $5
-    let remaining = total - checkpoint.len();
+    // Per-run budget: divide remaining budget evenly across remaining runs, capped at $5
+    let remaining = total_runs - checkpoint.len();
     let remaining_budget = options.max_budget - total_cost;
     let per_issue_budget = if remaining > 0 {
         (remaining_budget / remaining as f64).min(5.0)
@@ -549,111 +1472,178 @@ pub fn run_batch(options: BatchOptions) -> Result<()> {
     };
 
     println!(
-        "\n{} Running batch: {} issues, model={}, total_budget=${:.2}, per_issue=${:.2}\n",
+        "\n{} Running batch: {} issues x {} trial(s), model={}, total_budget=${:.2}, per_run=${:.2}\n",
         "BATCH".cyan().bold(),
-        total,
+        total_issues,
+        trials,
         options.model,
         options.max_budget,
         per_issue_budget,
     );
 
-    for (i, issue_entry) in corpus.issues.iter().enumerate() {
-        let label = format!("[{}/{}]", i + 1, total);
+    let progress = Arc::new(Mutex::new(BatchProgress {
+        issues_total: total_runs,
+        issues_completed: reports.len(),
+        issues_failed: failed,
+        cost_control_usd: reports.iter().map(|r| r.control.cost_usd).sum(),
+        cost_fmm_usd: reports.iter().map(|r| r.fmm.cost_usd).sum(),
+        remaining_budget_usd: options.max_budget - total_cost,
+        ..Default::default()
+    }));
+    if let Some(addr) = options.metrics_addr {
+        spawn_metrics_server(addr, Arc::clone(&progress))?;
+    }
+
+    'issues: for (i, issue_entry) in corpus.issues.iter().enumerate() {
+        for trial_index in 0..trials {
+            let label = if trials > 1 {
+                format!("[{}/{} trial {}/{}]", i + 1, total_issues, trial_index + 1, trials)
+            } else {
+                format!("[{}/{}]", i + 1, total_issues)
+            };
+
+            // Skip if already in checkpoint
+            if checkpoint.contains_key(&(issue_entry.url.clone(), trial_index)) {
+                println!(
+                    "  {} {} — {} (checkpoint)",
+                    label.dimmed(),
+                    issue_entry.url,
+                    "SKIP".yellow()
+                );
+                continue;
+            }
+
+            // Budget guard
+            if total_cost >= options.max_budget {
+                println!(
+                    "\n{} Budget exhausted (${:.2} >= ${:.2}), stopping batch",
+                    "STOP".red().bold(),
+                    total_cost,
+                    options.max_budget,
+                );
+                break 'issues;
+            }
 
-        // Skip if already in checkpoint
-        if checkpoint.contains_key(&issue_entry.url) {
             println!(
-                "  {} {} — {} (checkpoint)",
-                label.dimmed(),
+                "\n{} {} {}",
+                label.cyan().bold(),
+                "Processing".green(),
                 issue_entry.url,
-                "SKIP".yellow()
             );
-            continue;
-        }
-
-        // Budget guard
-        if total_cost >= options.max_budget {
-            println!(
-                "\n{} Budget exhausted (${:.2} >= ${:.2}), stopping batch",
-                "STOP".red().bold(),
-                total_cost,
-                options.max_budget,
-            );
-            break;
-        }
-
-        println!(
-            "\n{} {} {}",
-            label.cyan().bold(),
-            "Processing".green(),
-            issue_entry.url,
-        );
-
-        match run_single_issue(
-            &issue_entry.url,
-            &options.model,
-            options.max_turns,
-            per_issue_budget,
-        ) {
-            Ok(report) => {
-                let cost = report.control.cost_usd + report.fmm.cost_usd;
-                total_cost += cost;
 
-                println!(
-                    "  {} tokens: {:.0}%, cost: {:.0}%, ${:.4}",
-                    "OK".green().bold(),
-                    report.savings.total_tokens_pct,
-                    report.savings.cost_pct,
-                    cost,
-                );
-
-                // Save individual report
-                let saved = report.save(&options.output_dir)?;
-                for path in &saved {
-                    println!("  {} {}", "Saved:".dimmed(), path.dimmed());
+            match run_single_issue(
+                &issue_entry.url,
+                &options.model,
+                options.max_turns,
+                per_issue_budget,
+            ) {
+                Ok(report) => {
+                    let cost = report.control.cost_usd + report.fmm.cost_usd;
+                    total_cost += cost;
+
+                    println!(
+                        "  {} tokens: {:.0}%, cost: {:.0}%, ${:.4}",
+                        "OK".green().bold(),
+                        report.savings.total_tokens_pct,
+                        report.savings.cost_pct,
+                        cost,
+                    );
+
+                    // Save individual report
+                    let saved = report.save(&options.output_dir)?;
+                    for path in &saved {
+                        println!("  {} {}", "Saved:".dimmed(), path.dimmed());
+                    }
+
+                    append_checkpoint(
+                        &options.output_dir,
+                        &CheckpointEntry {
+                            url: issue_entry.url.clone(),
+                            trial_index,
+                            status: CheckpointStatus::Done,
+                            report: Some(report.clone()),
+                            error: None,
+                        },
+                    )?;
+
+                    if let Ok(mut p) = progress.lock() {
+                        p.issues_completed += 1;
+                        p.cost_control_usd += report.control.cost_usd;
+                        p.cost_fmm_usd += report.fmm.cost_usd;
+                        p.remaining_budget_usd = options.max_budget - total_cost;
+                        p.latest_total_tokens_pct = report.savings.total_tokens_pct;
+                        p.latest_cost_pct = report.savings.cost_pct;
+                    }
+
+                    if options.gate {
+                        if let Some(expect) = &issue_entry.expect {
+                            let violations = check_regressions(
+                                &issue_entry.url,
+                                trial_index,
+                                expect,
+                                &report.savings,
+                                report.fmm.cost_usd,
+                            );
+                            for v in &violations {
+                                println!(
+                                    "  {} {} — expected {}, got {}",
+                                    "REGRESSION".red().bold(),
+                                    v.bound,
+                                    v.expected,
+                                    v.actual,
+                                );
+                            }
+                            regressions.extend(violations);
+                        }
+                    }
+
+                    reports.push(report);
+                }
+                Err(e) => {
+                    failed += 1;
+                    let err_msg = format!("{:#}", e);
+                    println!("  {} {}", "FAIL".red().bold(), err_msg);
+
+                    append_checkpoint(
+                        &options.output_dir,
+                        &CheckpointEntry {
+                            url: issue_entry.url.clone(),
+                            trial_index,
+                            status: CheckpointStatus::Failed,
+                            report: None,
+                            error: Some(err_msg),
+                        },
+                    )?;
+
+                    if let Ok(mut p) = progress.lock() {
+                        p.issues_failed += 1;
+                        p.remaining_budget_usd = options.max_budget - total_cost;
+                    }
                 }
-
-                append_checkpoint(
-                    &options.output_dir,
-                    &CheckpointEntry {
-                        url: issue_entry.url.clone(),
-                        status: CheckpointStatus::Done,
-                        report: Some(report.clone()),
-                        error: None,
-                    },
-                )?;
-
-                reports.push(report);
-            }
-            Err(e) => {
-                failed += 1;
-                let err_msg = format!("{:#}", e);
-                println!("  {} {}", "FAIL".red().bold(), err_msg);
-
-                append_checkpoint(
-                    &options.output_dir,
-                    &CheckpointEntry {
-                        url: issue_entry.url.clone(),
-                        status: CheckpointStatus::Failed,
-                        report: None,
-                        error: Some(err_msg),
-                    },
-                )?;
             }
         }
     }
 
+    if options.auto_tag {
+        let cutoffs = SizeCutoffs::default();
+        apply_auto_tags(&mut corpus, &reports, &cutoffs);
+        fs::write(&options.corpus_path, serde_json::to_string_pretty(&corpus)?)?;
+        println!("  {} {}", "Retagged:".bold(), options.corpus_path.display());
+    }
+
     // Aggregate and write proof dataset
     let report_refs: Vec<&IssueComparisonReport> = reports.iter().collect();
     let aggregate = aggregate_savings(&report_refs);
 
-    // Build by-tag breakdown
+    // Build by-tag breakdown (every trial report for the issue, not just the first)
     let mut by_tag: HashMap<String, Vec<&IssueComparisonReport>> = HashMap::new();
     for issue_entry in &corpus.issues {
-        if let Some(report) = reports.iter().find(|r| r.issue_url == issue_entry.url) {
-            for tag in &issue_entry.tags {
-                by_tag.entry(tag.clone()).or_default().push(report);
-            }
+        let issue_reports: Vec<&IssueComparisonReport> = reports
+            .iter()
+            .filter(|r| r.issue_url == issue_entry.url)
+            .collect();
+        for tag in &issue_entry.tags {
+            by_tag.entry(tag.clone()).or_default().extend(&issue_reports);
         }
     }
     let by_tag_stats: HashMap<String, AggregateStats> = by_tag
@@ -663,12 +1653,13 @@ pub fn run_batch(options: BatchOptions) -> Result<()> {
 
     let dataset = ProofDataset {
         generated_at: chrono::Utc::now().to_rfc3339(),
-        corpus_size: total,
+        corpus_size: total_issues,
         completed: reports.len(),
         failed,
         aggregate,
         by_tag: by_tag_stats,
         issues: reports,
+        regressions,
     };
 
     // Write proof-dataset.json
@@ -677,32 +1668,54 @@ pub fn run_batch(options: BatchOptions) -> Result<()> {
     fs::write(&json_path, &json)?;
     println!("\n  {} {}", "Saved:".bold(), json_path.display());
 
-    // Write proof-dataset.md
-    let md_path = options.output_dir.join("proof-dataset.md");
-    let markdown = generate_proof_markdown(&dataset, &corpus);
-    fs::write(&md_path, &markdown)?;
-    println!("  {} {}", "Saved:".bold(), md_path.display());
+    // Write the companion format (Markdown by default; `Json` is a no-op
+    // since proof-dataset.json above already covers it).
+    if options.format != ProofFormat::Json {
+        let rendered = render_proof_dataset(&dataset, &corpus, options.format)?;
+        let rendered_path = options
+            .output_dir
+            .join(format!("proof-dataset.{}", options.format.extension()));
+        fs::write(&rendered_path, &rendered)?;
+        println!("  {} {}", "Saved:".bold(), rendered_path.display());
+    }
+
+    // Append to the longitudinal metrics history alongside this run's output
+    super::metrics_history::record_metrics_history(
+        &options.output_dir,
+        &dataset,
+        dataset.generated_at.clone(),
+    )?;
 
     // Final summary
     println!("\n{}", "=".repeat(64).dimmed());
     println!("{}", "Batch Complete".green().bold());
     println!("{}", "=".repeat(64).dimmed());
     println!(
-        "  {} {}/{} completed, {} failed",
+        "  {} {}/{} runs completed, {} failed",
         "Results:".bold(),
         dataset.completed,
-        total,
+        total_runs,
         failed,
     );
     println!(
-        "  {} Mean token reduction: {:.0}%, Mean cost reduction: {:.0}%",
+        "  {} Token reduction: {}, cost reduction: {}",
         "Savings:".bold(),
-        dataset.aggregate.mean_total_tokens_pct,
-        dataset.aggregate.mean_cost_pct,
+        fmt_stat_pct(&dataset.aggregate.total_tokens_pct),
+        fmt_stat_pct(&dataset.aggregate.cost_pct),
     );
     println!("  {} ${:.2}", "Total cost:".bold(), total_cost,);
 
-    Ok(())
+    if options.gate && !dataset.regressions.is_empty() {
+        println!(
+            "\n{} {} gate violation(s) — see Regressions in {}",
+            "✗".red().bold(),
+            dataset.regressions.len(),
+            json_path.display(),
+        );
+        Ok(1)
+    } else {
+        Ok(0)
+    }
 }
 
 /// Run a single issue comparison (fetch + compare).
@@ -714,15 +1727,30 @@ fn run_single_issue(
 ) -> Result<IssueComparisonReport> {
     let issue_ref = IssueRef::parse(url)?;
     let issue = fetch_issue(url)?;
-    run_issue_compare(url, &issue, &issue_ref, model, max_turns, max_budget)
+    run_issue_compare(
+        url,
+        &issue,
+        &issue_ref,
+        model,
+        max_turns,
+        max_budget,
+        None,
+        None,
+        1,
+    )
 }
 
 fn print_dry_run(corpus: &Corpus, options: &BatchOptions) -> Result<()> {
     println!("{}", "DRY RUN — Batch Plan".cyan().bold());
     println!("{}", "=".repeat(64).dimmed());
 
+    let trials = options.trials.max(1);
+
     println!("\n  {} {}", "Corpus:".bold(), options.corpus_path.display());
     println!("  {} {}", "Issues:".bold(), corpus.issues.len());
+    if trials > 1 {
+        println!("  {} {}", "Trials per issue:".bold(), trials);
+    }
     println!("  {} {}", "Model:".bold(), options.model);
     println!(
         "  {} ${:.2}",
@@ -747,16 +1775,18 @@ fn print_dry_run(corpus: &Corpus, options: &BatchOptions) -> Result<()> {
         }
     }
 
-    // Cost estimate: capped by total budget, but each issue runs 2 Claude invocations
-    let per_issue_est = 5.0_f64.min(options.max_budget / corpus.issues.len().max(1) as f64);
-    let total_est = (per_issue_est * 2.0 * corpus.issues.len() as f64).min(options.max_budget);
+    // Cost estimate: capped by total budget, but each run is 2 Claude invocations
+    // (control + fmm), repeated `trials` times per issue.
+    let total_runs = corpus.issues.len() * trials as usize;
+    let per_run_est = 5.0_f64.min(options.max_budget / total_runs.max(1) as f64);
+    let total_est = (per_run_est * 2.0 * total_runs as f64).min(options.max_budget);
     println!(
-        "\n  {} ${:.2} (budget cap: ${:.2}, est ${:.2}/issue x 2 variants x {} issues)",
+        "\n  {} ${:.2} (budget cap: ${:.2}, est ${:.2}/run x 2 variants x {} runs)",
         "Est. max cost:".bold(),
         total_est,
         options.max_budget,
-        per_issue_est,
-        corpus.issues.len(),
+        per_run_est,
+        total_runs,
     );
 
     println!("\n  {}", "Issues:".bold());
@@ -777,10 +1807,363 @@ fn print_dry_run(corpus: &Corpus, options: &BatchOptions) -> Result<()> {
     Ok(())
 }
 
+// ── Parameter tuning (Nelder-Mead) ──
+
+/// Inclusive search bounds for one dimension of a tuning vector.
+#[derive(Debug, Clone, Copy)]
+pub struct TuneDim {
+    pub min: f64,
+    pub max: f64,
+}
+
+/// Options for `run_tune`. Searches `run_single_issue`'s existing knobs —
+/// `max_turns` and `max_budget` — rather than inventing parameters nothing
+/// downstream consumes.
+pub struct TuneOptions {
+    pub corpus_path: PathBuf,
+    pub output_dir: PathBuf,
+    pub model: String,
+    /// Fraction (0 exclusive, 1 inclusive) of the corpus, taken from the front, held out for
+    /// tuning; each simplex vertex is scored by running every issue in this
+    /// subset once.
+    pub holdout_fraction: f64,
+    pub max_turns_bounds: TuneDim,
+    pub max_budget_bounds: TuneDim,
+    /// Minimum fraction of the holdout that must succeed for a vertex's
+    /// `mean_cost_pct` to be trusted; vertices below this are penalized.
+    pub min_success_rate: f64,
+    /// Stop the search once cumulative holdout spend reaches this.
+    pub max_search_budget: f64,
+    /// Stop once the simplex's per-dimension-normalized diameter drops
+    /// below this.
+    pub diameter_tolerance: f64,
+    pub max_iterations: u32,
+}
+
+/// One fully-evaluated vertex: the rounded config tried, what it scored,
+/// and whether it cleared `min_success_rate`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TunePoint {
+    pub max_turns: u32,
+    pub max_budget_usd: f64,
+    pub mean_cost_pct: f64,
+    pub success_rate: f64,
+    pub objective: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TuningReport {
+    pub evaluated: Vec<TunePoint>,
+    pub recommended: TunePoint,
+}
+
+/// Rounds a raw `[turns, budget]` vertex to the nearest valid integer turn
+/// count and cent, clamped to bounds — both so evaluations are reproducible
+/// and so the evaluation cache keys on something hashable.
+fn round_vertex(vertex: [f64; 2], turns_bounds: TuneDim, budget_bounds: TuneDim) -> (u32, f64) {
+    let turns = vertex[0]
+        .round()
+        .clamp(turns_bounds.min, turns_bounds.max) as u32;
+    let budget_cents = (vertex[1] * 100.0)
+        .round()
+        .clamp(budget_bounds.min * 100.0, budget_bounds.max * 100.0);
+    (turns, budget_cents / 100.0)
+}
+
+/// Evaluates one config against the holdout subset, memoized by rounded
+/// `(max_turns, max_budget_usd)` so the simplex never pays twice for a
+/// config it has already tried (reflection/expansion routinely revisit
+/// nearby points).
+#[allow(clippy::too_many_arguments)]
+fn evaluate_tune_vertex(
+    vertex: [f64; 2],
+    turns_bounds: TuneDim,
+    budget_bounds: TuneDim,
+    holdout: &[CorpusIssue],
+    model: &str,
+    min_success_rate: f64,
+    cache: &mut HashMap<(u32, u64), TunePoint>,
+    search_spend: &mut f64,
+) -> TunePoint {
+    let (max_turns, max_budget_usd) = round_vertex(vertex, turns_bounds, budget_bounds);
+    let key = (max_turns, (max_budget_usd * 100.0).round() as u64);
+    if let Some(point) = cache.get(&key) {
+        return point.clone();
+    }
+
+    let mut cost_pcts = Vec::new();
+    let mut successes = 0usize;
+    for issue in holdout {
+        match run_single_issue(&issue.url, model, max_turns, max_budget_usd) {
+            Ok(report) => {
+                *search_spend += report.control.cost_usd + report.fmm.cost_usd;
+                cost_pcts.push(report.savings.cost_pct);
+                successes += 1;
+            }
+            Err(_) => {}
+        }
+    }
+
+    let success_rate = if holdout.is_empty() {
+        0.0
+    } else {
+        successes as f64 / holdout.len() as f64
+    };
+    let mean_cost_pct = if cost_pcts.is_empty() {
+        0.0
+    } else {
+        cost_pcts.iter().sum::<f64>() / cost_pcts.len() as f64
+    };
+    // Minimizing: maximize mean_cost_pct, but a floor violation dominates
+    // the score so the simplex is pushed back toward the feasible region
+    // rather than silently rewarded for an unreliable config.
+    let shortfall = (min_success_rate - success_rate).max(0.0);
+    let objective = shortfall * 1000.0 - mean_cost_pct;
+
+    let point = TunePoint {
+        max_turns,
+        max_budget_usd,
+        mean_cost_pct,
+        success_rate,
+        objective,
+    };
+    cache.insert(key, point.clone());
+    point
+}
+
+fn simplex_diameter(vertices: &[[f64; 2]], turns_bounds: TuneDim, budget_bounds: TuneDim) -> f64 {
+    let turns_range = (turns_bounds.max - turns_bounds.min).max(1.0);
+    let budget_range = (budget_bounds.max - budget_bounds.min).max(0.01);
+    let mut max_dist = 0.0f64;
+    for i in 0..vertices.len() {
+        for j in (i + 1)..vertices.len() {
+            let dt = (vertices[i][0] - vertices[j][0]) / turns_range;
+            let db = (vertices[i][1] - vertices[j][1]) / budget_range;
+            let dist = (dt * dt + db * db).sqrt();
+            max_dist = max_dist.max(dist);
+        }
+    }
+    max_dist
+}
+
+/// Searches `max_turns` / `max_budget` via Nelder-Mead simplex to maximize
+/// mean `cost_pct` on a held-out corpus subset, subject to the holdout's
+/// success rate staying above `min_success_rate`. Writes `tuning-report.json`
+/// under `options.output_dir` and returns the same report.
+pub fn run_tune(options: TuneOptions) -> Result<TuningReport> {
+    preflight_check()?;
+
+    let corpus = Corpus::load(&options.corpus_path)?;
+    fs::create_dir_all(&options.output_dir)?;
+
+    let holdout_size = ((corpus.issues.len() as f64 * options.holdout_fraction.clamp(0.0, 1.0))
+        .ceil() as usize)
+        .clamp(1, corpus.issues.len());
+    let holdout: Vec<CorpusIssue> = corpus.issues[..holdout_size].to_vec();
+
+    println!(
+        "\n{} Tuning over {} holdout issue(s), model={}\n",
+        "TUNE".cyan().bold(),
+        holdout.len(),
+        options.model,
+    );
+
+    let turns_bounds = options.max_turns_bounds;
+    let budget_bounds = options.max_budget_bounds;
+
+    // Initial simplex: the bounds' midpoint, plus one vertex per dimension
+    // nudged a quarter of that dimension's range.
+    let mid = [
+        (turns_bounds.min + turns_bounds.max) / 2.0,
+        (budget_bounds.min + budget_bounds.max) / 2.0,
+    ];
+    let turns_step = (turns_bounds.max - turns_bounds.min) * 0.25;
+    let budget_step = (budget_bounds.max - budget_bounds.min) * 0.25;
+    let mut vertices: Vec<[f64; 2]> = vec![
+        mid,
+        [mid[0] + turns_step, mid[1]],
+        [mid[0], mid[1] + budget_step],
+    ];
+
+    let mut cache: HashMap<(u32, u64), TunePoint> = HashMap::new();
+    let mut search_spend = 0.0f64;
+    let mut evaluated: Vec<TunePoint> = Vec::new();
+
+    const ALPHA: f64 = 1.0; // reflection
+    const GAMMA: f64 = 2.0; // expansion
+    const RHO: f64 = 0.5; // contraction
+    const SIGMA: f64 = 0.5; // shrink
+
+    for iteration in 0..options.max_iterations {
+        if search_spend >= options.max_search_budget {
+            println!(
+                "{} Search budget exhausted (${:.2}), stopping at iteration {}",
+                "STOP".red().bold(),
+                search_spend,
+                iteration,
+            );
+            break;
+        }
+        if simplex_diameter(&vertices, turns_bounds, budget_bounds) < options.diameter_tolerance {
+            println!("{} Simplex converged at iteration {}", "DONE".green().bold(), iteration);
+            break;
+        }
+
+        let mut scored: Vec<(TunePoint, [f64; 2])> = vertices
+            .iter()
+            .map(|&v| {
+                let point = evaluate_tune_vertex(
+                    v,
+                    turns_bounds,
+                    budget_bounds,
+                    &holdout,
+                    &options.model,
+                    options.min_success_rate,
+                    &mut cache,
+                    &mut search_spend,
+                );
+                evaluated.push(point.clone());
+                (point, v)
+            })
+            .collect();
+        scored.sort_by(|a, b| a.0.objective.partial_cmp(&b.0.objective).unwrap());
+
+        let best = scored[0].1;
+        let second_worst = scored[scored.len() - 2].0.objective;
+        let worst_point = scored[scored.len() - 1].0.clone();
+        let worst = scored[scored.len() - 1].1;
+
+        println!(
+            "  [{}] best: turns={} budget=${:.2} cost_saved={:.0}% (obj {:.2})",
+            iteration + 1,
+            scored[0].0.max_turns,
+            scored[0].0.max_budget_usd,
+            scored[0].0.mean_cost_pct,
+            scored[0].0.objective,
+        );
+
+        let centroid = {
+            let sum = vertices
+                .iter()
+                .filter(|&&v| v != worst)
+                .fold([0.0, 0.0], |acc, v| [acc[0] + v[0], acc[1] + v[1]]);
+            let n = (vertices.len() - 1) as f64;
+            [sum[0] / n, sum[1] / n]
+        };
+
+        let reflected = [
+            centroid[0] + ALPHA * (centroid[0] - worst[0]),
+            centroid[1] + ALPHA * (centroid[1] - worst[1]),
+        ];
+        let reflected_point = evaluate_tune_vertex(
+            reflected,
+            turns_bounds,
+            budget_bounds,
+            &holdout,
+            &options.model,
+            options.min_success_rate,
+            &mut cache,
+            &mut search_spend,
+        );
+        evaluated.push(reflected_point.clone());
+
+        let best_objective = scored[0].0.objective;
+        let new_worst = if reflected_point.objective < best_objective {
+            // Reflection beat the best — try pushing further out.
+            let expanded = [
+                centroid[0] + GAMMA * (reflected[0] - centroid[0]),
+                centroid[1] + GAMMA * (reflected[1] - centroid[1]),
+            ];
+            let expanded_point = evaluate_tune_vertex(
+                expanded,
+                turns_bounds,
+                budget_bounds,
+                &holdout,
+                &options.model,
+                options.min_success_rate,
+                &mut cache,
+                &mut search_spend,
+            );
+            evaluated.push(expanded_point.clone());
+            if expanded_point.objective < reflected_point.objective {
+                expanded
+            } else {
+                reflected
+            }
+        } else if reflected_point.objective < second_worst {
+            reflected
+        } else {
+            let contracted = [
+                centroid[0] + RHO * (worst[0] - centroid[0]),
+                centroid[1] + RHO * (worst[1] - centroid[1]),
+            ];
+            let contracted_point = evaluate_tune_vertex(
+                contracted,
+                turns_bounds,
+                budget_bounds,
+                &holdout,
+                &options.model,
+                options.min_success_rate,
+                &mut cache,
+                &mut search_spend,
+            );
+            evaluated.push(contracted_point.clone());
+            if contracted_point.objective < worst_point.objective {
+                contracted
+            } else {
+                // Shrink every vertex toward the best.
+                vertices = vertices
+                    .iter()
+                    .map(|&v| [
+                        best[0] + SIGMA * (v[0] - best[0]),
+                        best[1] + SIGMA * (v[1] - best[1]),
+                    ])
+                    .collect();
+                continue;
+            }
+        };
+
+        let worst_idx = vertices.iter().position(|&v| v == worst).unwrap();
+        vertices[worst_idx] = new_worst;
+    }
+
+    evaluated.sort_by(|a, b| a.objective.partial_cmp(&b.objective).unwrap());
+    let recommended = evaluated
+        .first()
+        .cloned()
+        .unwrap_or(TunePoint {
+            max_turns: mid[0] as u32,
+            max_budget_usd: mid[1],
+            mean_cost_pct: 0.0,
+            success_rate: 0.0,
+            objective: f64::MAX,
+        });
+
+    let report = TuningReport {
+        evaluated,
+        recommended: recommended.clone(),
+    };
+
+    let report_path = options.output_dir.join("tuning-report.json");
+    fs::write(&report_path, serde_json::to_string_pretty(&report)?)?;
+
+    println!(
+        "\n{} max_turns={} max_budget=${:.2} (mean cost saved {:.0}%, success rate {:.0}%)",
+        "Recommended:".green().bold(),
+        recommended.max_turns,
+        recommended.max_budget_usd,
+        recommended.mean_cost_pct,
+        recommended.success_rate * 100.0,
+    );
+    println!("  {} {}", "Saved:".bold(), report_path.display());
+
+    Ok(report)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::gh::report::{IssueComparisonReport, ReportInput};
+    use crate::gh::report::{ChangeSummary, IssueComparisonReport, ReportInput};
     use crate::metrics::RunMetrics;
     use std::collections::HashMap as StdHashMap;
     use tempfile::TempDir;
@@ -821,10 +2204,14 @@ mod tests {
             model: "sonnet",
             max_budget_usd: 5.0,
             max_turns: 30,
-            control_metrics: &control,
-            fmm_metrics: &fmm,
+            control_trials: &[control],
+            fmm_trials: &[fmm],
             control_diff: "",
             fmm_diff: "",
+            control_changes: ChangeSummary::default(),
+            fmm_changes: ChangeSummary::default(),
+            profile: None,
+            pricing: None,
         })
     }
 
@@ -867,6 +2254,66 @@ mod tests {
         assert!(corpus.issues[0].tags.is_empty());
     }
 
+    #[test]
+    fn corpus_parse_with_expect_bounds() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("issues.json");
+        fs::write(
+            &path,
+            r#"{"issues":[
+                {"url":"https://github.com/o/r/issues/1","expect":{"min_cost_pct":40.0}}
+            ]}"#,
+        )
+        .unwrap();
+
+        let corpus = Corpus::load(&path).unwrap();
+        let expect = corpus.issues[0].expect.as_ref().unwrap();
+        assert_eq!(expect.min_cost_pct, Some(40.0));
+        assert_eq!(expect.min_total_tokens_pct, None);
+        assert_eq!(expect.max_cost_usd, None);
+    }
+
+    #[test]
+    fn check_regressions_passes_within_bounds() {
+        let report = make_report(1, 10000, 0.50, 3000, 0.15);
+        let expect = ExpectBounds {
+            min_cost_pct: Some(20.0),
+            min_total_tokens_pct: Some(20.0),
+            max_cost_usd: Some(1.0),
+        };
+        let violations = check_regressions(
+            &report.issue_url,
+            0,
+            &expect,
+            &report.savings,
+            report.fmm.cost_usd,
+        );
+        assert!(violations.is_empty());
+    }
+
+    #[test]
+    fn check_regressions_flags_each_violated_bound() {
+        let report = make_report(1, 10000, 0.50, 3000, 0.15);
+        let expect = ExpectBounds {
+            min_cost_pct: Some(99.0),
+            min_total_tokens_pct: Some(99.0),
+            max_cost_usd: Some(0.0),
+        };
+        let violations = check_regressions(
+            &report.issue_url,
+            2,
+            &expect,
+            &report.savings,
+            report.fmm.cost_usd,
+        );
+        assert_eq!(violations.len(), 3);
+        assert!(violations.iter().all(|v| v.trial_index == 2));
+        let bounds: Vec<&str> = violations.iter().map(|v| v.bound.as_str()).collect();
+        assert!(bounds.contains(&"min_cost_pct"));
+        assert!(bounds.contains(&"min_total_tokens_pct"));
+        assert!(bounds.contains(&"max_cost_usd"));
+    }
+
     #[test]
     fn aggregate_savings_calculates_means() {
         let r1 = make_report(1, 10000, 0.50, 3000, 0.15);
@@ -875,8 +2322,8 @@ mod tests {
 
         let stats = aggregate_savings(&reports);
         assert_eq!(stats.count, 2);
-        assert!(stats.mean_input_tokens_pct > 0.0);
-        assert!(stats.mean_cost_pct > 0.0);
+        assert!(stats.input_tokens_pct.mean > 0.0);
+        assert!(stats.cost_pct.mean > 0.0);
         assert!(stats.total_control_cost_usd > 0.0);
         assert!(stats.total_fmm_cost_usd > 0.0);
     }
@@ -885,7 +2332,75 @@ mod tests {
     fn aggregate_savings_empty() {
         let stats = aggregate_savings(&[]);
         assert_eq!(stats.count, 0);
-        assert_eq!(stats.mean_cost_pct, 0.0);
+        assert_eq!(stats.cost_pct.mean, 0.0);
+        assert_eq!(stats.cost_significance.n, 0);
+    }
+
+    #[test]
+    fn wilcoxon_signed_rank_below_min_n_returns_default() {
+        let differences = vec![1.0, 2.0, -1.0, 3.0];
+        let result = wilcoxon_signed_rank(&differences);
+        assert_eq!(result.n, 0);
+        assert_eq!(result.p_value, 0.0);
+    }
+
+    #[test]
+    fn wilcoxon_signed_rank_flags_consistent_savings() {
+        // FMM cheaper on every issue by a growing margin — should read as
+        // strongly significant (small p-value).
+        let differences: Vec<f64> = (1..=12).map(|i| i as f64 * 0.1).collect();
+        let result = wilcoxon_signed_rank(&differences);
+        assert_eq!(result.n, 12);
+        assert!(result.p_value < 0.05, "expected p < 0.05, got {}", result.p_value);
+    }
+
+    #[test]
+    fn wilcoxon_signed_rank_mixed_signs_not_significant() {
+        // Savings coin-flip between issues — should not read as significant.
+        let differences: Vec<f64> = (0..12)
+            .map(|i| if i % 2 == 0 { 1.0 } else { -1.0 })
+            .collect();
+        let result = wilcoxon_signed_rank(&differences);
+        assert_eq!(result.n, 12);
+        assert!(result.p_value > 0.05);
+    }
+
+    #[test]
+    fn bootstrap_ci_single_sample_is_a_point() {
+        let ci = bootstrap_ci(&[42.0]);
+        assert_eq!(ci.low, 42.0);
+        assert_eq!(ci.high, 42.0);
+    }
+
+    #[test]
+    fn bootstrap_ci_brackets_the_sample_mean() {
+        let samples = vec![10.0, 20.0, 30.0, 40.0, 50.0];
+        let ci = bootstrap_ci(&samples);
+        let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+        assert!(ci.low <= mean && mean <= ci.high, "{:?} should bracket {}", ci, mean);
+    }
+
+    #[test]
+    fn aggregate_savings_stddev_and_ci_widen_with_variance() {
+        let r1 = make_report(1, 10000, 0.50, 3000, 0.10);
+        let r2 = make_report(2, 10000, 0.50, 3000, 0.40);
+        let reports: Vec<&IssueComparisonReport> = vec![&r1, &r2];
+
+        let stats = aggregate_savings(&reports);
+        assert!(stats.cost_pct.stddev > 0.0);
+        assert!(stats.cost_pct.ci_low < stats.cost_pct.mean);
+        assert!(stats.cost_pct.ci_high > stats.cost_pct.mean);
+    }
+
+    #[test]
+    fn aggregate_savings_single_sample_has_zero_width_ci() {
+        let r1 = make_report(1, 10000, 0.50, 3000, 0.15);
+        let reports: Vec<&IssueComparisonReport> = vec![&r1];
+
+        let stats = aggregate_savings(&reports);
+        assert_eq!(stats.cost_pct.stddev, 0.0);
+        assert_eq!(stats.cost_pct.ci_low, stats.cost_pct.mean);
+        assert_eq!(stats.cost_pct.ci_high, stats.cost_pct.mean);
     }
 
     #[test]
@@ -915,6 +2430,7 @@ mod tests {
 
         let entry = CheckpointEntry {
             url: "https://github.com/o/r/issues/1".to_string(),
+            trial_index: 0,
             status: CheckpointStatus::Done,
             report: Some(report),
             error: None,
@@ -924,11 +2440,9 @@ mod tests {
 
         let cp = load_checkpoint(tmp.path());
         assert_eq!(cp.len(), 1);
-        assert!(cp.contains_key("https://github.com/o/r/issues/1"));
-        assert_eq!(
-            cp["https://github.com/o/r/issues/1"].status,
-            CheckpointStatus::Done
-        );
+        let key = ("https://github.com/o/r/issues/1".to_string(), 0);
+        assert!(cp.contains_key(&key));
+        assert_eq!(cp[&key].status, CheckpointStatus::Done);
     }
 
     #[test]
@@ -938,6 +2452,7 @@ mod tests {
         for i in 1..=3 {
             let entry = CheckpointEntry {
                 url: format!("https://github.com/o/r/issues/{}", i),
+                trial_index: 0,
                 status: CheckpointStatus::Done,
                 report: Some(make_report(i, 10000, 0.50, 3000, 0.15)),
                 error: None,
@@ -949,12 +2464,34 @@ mod tests {
         assert_eq!(cp.len(), 3);
     }
 
+    #[test]
+    fn checkpoint_keys_on_url_and_trial_index() {
+        let tmp = TempDir::new().unwrap();
+
+        for trial_index in 0..2 {
+            let entry = CheckpointEntry {
+                url: "https://github.com/o/r/issues/1".to_string(),
+                trial_index,
+                status: CheckpointStatus::Done,
+                report: Some(make_report(1, 10000, 0.50, 3000, 0.15)),
+                error: None,
+            };
+            append_checkpoint(tmp.path(), &entry).unwrap();
+        }
+
+        let cp = load_checkpoint(tmp.path());
+        assert_eq!(cp.len(), 2);
+        assert!(cp.contains_key(&("https://github.com/o/r/issues/1".to_string(), 0)));
+        assert!(cp.contains_key(&("https://github.com/o/r/issues/1".to_string(), 1)));
+    }
+
     #[test]
     fn checkpoint_failed_entry() {
         let tmp = TempDir::new().unwrap();
 
         let entry = CheckpointEntry {
             url: "https://github.com/o/r/issues/99".to_string(),
+            trial_index: 0,
             status: CheckpointStatus::Failed,
             report: None,
             error: Some("fetch failed".to_string()),
@@ -962,11 +2499,9 @@ mod tests {
         append_checkpoint(tmp.path(), &entry).unwrap();
 
         let cp = load_checkpoint(tmp.path());
-        assert_eq!(
-            cp["https://github.com/o/r/issues/99"].status,
-            CheckpointStatus::Failed
-        );
-        assert!(cp["https://github.com/o/r/issues/99"].error.is_some());
+        let key = ("https://github.com/o/r/issues/99".to_string(), 0);
+        assert_eq!(cp[&key].status, CheckpointStatus::Failed);
+        assert!(cp[&key].error.is_some());
     }
 
     #[test]
@@ -979,10 +2514,12 @@ mod tests {
                 CorpusIssue {
                     url: r1.issue_url.clone(),
                     tags: vec!["rust".to_string()],
+                    expect: None,
                 },
                 CorpusIssue {
                     url: r2.issue_url.clone(),
                     tags: vec!["python".to_string()],
+                    expect: None,
                 },
             ],
         };
@@ -998,6 +2535,7 @@ mod tests {
             aggregate,
             by_tag: HashMap::new(),
             issues: vec![r1, r2],
+            regressions: Vec::new(),
         };
 
         let md = generate_proof_markdown(&dataset, &corpus);
@@ -1008,6 +2546,84 @@ mod tests {
         assert!(md.contains("test/repo"));
     }
 
+    fn make_proof_dataset_for_rendering() -> (ProofDataset, Corpus) {
+        let r1 = make_report(1, 10000, 0.50, 3000, 0.15);
+        let r2 = make_report(2, 8000, 0.40, 4000, 0.20);
+
+        let corpus = Corpus {
+            issues: vec![
+                CorpusIssue {
+                    url: r1.issue_url.clone(),
+                    tags: vec!["rust".to_string()],
+                    expect: None,
+                },
+                CorpusIssue {
+                    url: r2.issue_url.clone(),
+                    tags: vec!["python".to_string()],
+                    expect: None,
+                },
+            ],
+        };
+
+        let report_refs: Vec<&IssueComparisonReport> = vec![&r1, &r2];
+        let aggregate = aggregate_savings(&report_refs);
+        let mut by_tag = HashMap::new();
+        by_tag.insert("rust".to_string(), aggregate_savings(&[&r1]));
+
+        let dataset = ProofDataset {
+            generated_at: "2026-01-31T00:00:00Z".to_string(),
+            corpus_size: 2,
+            completed: 2,
+            failed: 0,
+            aggregate,
+            by_tag,
+            issues: vec![r1, r2],
+            regressions: Vec::new(),
+        };
+        (dataset, corpus)
+    }
+
+    #[test]
+    fn render_proof_dataset_json_round_trips_dataset() {
+        let (dataset, corpus) = make_proof_dataset_for_rendering();
+        let rendered = render_proof_dataset(&dataset, &corpus, ProofFormat::Json).unwrap();
+        let parsed: ProofDataset = serde_json::from_str(&rendered).unwrap();
+        assert_eq!(parsed.completed, dataset.completed);
+        assert_eq!(parsed.issues.len(), 2);
+    }
+
+    #[test]
+    fn generate_proof_csv_has_one_row_per_issue() {
+        let (dataset, _corpus) = make_proof_dataset_for_rendering();
+        let csv = generate_proof_csv(&dataset);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3); // header + 2 issues
+        assert!(lines[0].starts_with("issue_url,repo,"));
+        assert!(lines[1].contains("test/repo"));
+    }
+
+    #[test]
+    fn csv_escape_quotes_fields_with_commas() {
+        assert_eq!(csv_escape("plain"), "plain");
+        assert_eq!(csv_escape("a,b"), "\"a,b\"");
+        assert_eq!(csv_escape("a\"b"), "\"a\"\"b\"");
+    }
+
+    #[test]
+    fn generate_proof_html_includes_headline_and_tag_table() {
+        let (dataset, corpus) = make_proof_dataset_for_rendering();
+        let html = generate_proof_html(&dataset, &corpus);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("FMM Proof Dataset"));
+        assert!(html.contains("<table>"));
+        assert!(html.contains("rust"));
+    }
+
+    #[test]
+    fn html_escape_neutralizes_markup() {
+        assert_eq!(html_escape("<script>"), "&lt;script&gt;");
+    }
+
     // ── Validation / health report tests ──
 
     fn make_corpus(tags_list: Vec<Vec<&str>>) -> Corpus {
@@ -1018,11 +2634,101 @@ mod tests {
                 .map(|(i, tags)| CorpusIssue {
                     url: format!("https://github.com/o/r/issues/{}", i + 1),
                     tags: tags.into_iter().map(String::from).collect(),
+                    expect: None,
                 })
                 .collect(),
         }
     }
 
+    #[test]
+    fn size_bucket_respects_cutoffs() {
+        let cutoffs = SizeCutoffs { small_max: 20, medium_max: 100, large_max: 500 };
+        assert_eq!(size_bucket(5, &cutoffs), "small");
+        assert_eq!(size_bucket(20, &cutoffs), "small");
+        assert_eq!(size_bucket(21, &cutoffs), "medium");
+        assert_eq!(size_bucket(100, &cutoffs), "medium");
+        assert_eq!(size_bucket(300, &cutoffs), "large");
+        assert_eq!(size_bucket(5000, &cutoffs), "massive");
+    }
+
+    #[test]
+    fn classify_diff_picks_dominant_language_and_skips_comments() {
+        let diff = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,6 @@
++// a comment, should not count
++fn added_one() {}
++fn added_two() {}
+ fn unchanged() {}
+-fn removed() {}
+";
+        let (lang, loc) = classify_diff(diff);
+        assert_eq!(lang, Some("rust"));
+        // 2 added real lines + 1 removed real line = 3; the comment is skipped.
+        assert_eq!(loc, 3);
+    }
+
+    #[test]
+    fn classify_diff_ignores_unrecognized_extensions() {
+        let diff = "\
+diff --git a/README.md b/README.md
+--- a/README.md
++++ b/README.md
+@@ -1 +1,2 @@
++Some added prose.
+";
+        let (lang, loc) = classify_diff(diff);
+        assert_eq!(lang, None);
+        assert_eq!(loc, 0);
+    }
+
+    #[test]
+    fn auto_tag_from_diff_combines_language_and_size() {
+        let diff = "\
+diff --git a/main.py b/main.py
+--- a/main.py
++++ b/main.py
+@@ -1 +1,2 @@
++print('hello')
+";
+        let cutoffs = SizeCutoffs::default();
+        let tags = auto_tag_from_diff(diff, &cutoffs);
+        assert_eq!(tags, vec!["python".to_string(), "small".to_string()]);
+    }
+
+    #[test]
+    fn apply_auto_tags_preserves_task_type_and_rewrites_language_size() {
+        let mut corpus = Corpus {
+            issues: vec![CorpusIssue {
+                url: "https://github.com/o/r/issues/1".to_string(),
+                tags: vec!["python".to_string(), "large".to_string(), "bugfix".to_string()],
+                expect: None,
+            }],
+        };
+        let mut report = make_report(1, 10000, 0.50, 3000, 0.15);
+        report.issue_url = "https://github.com/o/r/issues/1".to_string();
+        report.fmm.diff = "\
+diff --git a/main.rs b/main.rs
+--- a/main.rs
++++ b/main.rs
+@@ -1 +1,2 @@
++fn added() {}
+"
+        .to_string();
+
+        apply_auto_tags(&mut corpus, &[report], &SizeCutoffs::default());
+
+        let tags = &corpus.issues[0].tags;
+        assert!(tags.contains(&"rust".to_string()));
+        assert!(tags.contains(&"small".to_string()));
+        assert!(tags.contains(&"bugfix".to_string()));
+        assert!(!tags.contains(&"python".to_string()));
+        assert!(!tags.contains(&"large".to_string()));
+    }
+
     #[test]
     fn tag_distribution_counts_correctly() {
         let corpus = make_corpus(vec![
@@ -1076,6 +2782,7 @@ mod tests {
             issues: vec![CorpusIssue {
                 url: "https://github.com/o/r/issues/1".to_string(),
                 tags: vec![],
+                expect: None,
             }],
         };
         let dist = tag_distribution(&corpus);
@@ -1158,4 +2865,69 @@ mod tests {
         let row = format_distribution_row(&tags);
         assert_eq!(row, "");
     }
+
+    #[test]
+    fn check_corpus_balance_flags_tags_below_threshold() {
+        let mut tags_list = Vec::new();
+        for _ in 0..2 {
+            tags_list.push(vec!["rust", "small", "bugfix"]);
+        }
+        let corpus = make_corpus(tags_list);
+        let distribution = tag_distribution(&corpus);
+
+        let shortfalls = check_corpus_balance(&distribution, &CategoryThresholds::default());
+
+        let rust_shortfall = shortfalls.iter().find(|s| s.tag == "rust").unwrap();
+        assert_eq!(rust_shortfall.category, "Languages");
+        assert_eq!(rust_shortfall.count, 2);
+        assert_eq!(rust_shortfall.minimum, MIN_ISSUES_PER_CATEGORY);
+        assert_eq!(rust_shortfall.shortfall, MIN_ISSUES_PER_CATEGORY - 2);
+    }
+
+    #[test]
+    fn check_corpus_balance_ignores_absent_tags() {
+        let mut tags_list = Vec::new();
+        for _ in 0..4 {
+            tags_list.push(vec!["rust", "small", "bugfix"]);
+        }
+        let corpus = make_corpus(tags_list);
+        let distribution = tag_distribution(&corpus);
+
+        let shortfalls = check_corpus_balance(&distribution, &CategoryThresholds::default());
+
+        // "python" has zero issues — absent, not a gap — so it must not appear.
+        assert!(!shortfalls.iter().any(|s| s.tag == "python"));
+    }
+
+    #[test]
+    fn check_corpus_balance_respects_per_category_overrides() {
+        let mut tags_list = Vec::new();
+        for _ in 0..4 {
+            tags_list.push(vec!["rust", "small", "bugfix"]);
+        }
+        let corpus = make_corpus(tags_list);
+        let distribution = tag_distribution(&corpus);
+
+        let thresholds = CategoryThresholds {
+            languages: 10,
+            sizes: 1,
+            task_types: 1,
+        };
+        let shortfalls = check_corpus_balance(&distribution, &thresholds);
+
+        assert!(shortfalls.iter().any(|s| s.tag == "rust" && s.category == "Languages"));
+        assert!(!shortfalls.iter().any(|s| s.tag == "small"));
+        assert!(!shortfalls.iter().any(|s| s.tag == "bugfix"));
+    }
+
+    #[test]
+    fn run_corpus_check_exits_nonzero_on_shortfall() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("issues.json");
+        let corpus = make_corpus(vec![vec!["rust", "small", "bugfix"]]);
+        fs::write(&path, serde_json::to_string(&corpus).unwrap()).unwrap();
+
+        let exit_code = run_corpus_check(&path, CategoryThresholds::default()).unwrap();
+        assert_eq!(exit_code, 1);
+    }
 }
@@ -0,0 +1,402 @@
+//! Abstraction over how the `gh` workspace talks to git.
+//!
+//! [`ShellGitBackend`] is the original implementation: every operation
+//! shells out to the `git` binary and scrapes its stderr on failure.
+//! [`GixBackend`] does the same work through `gix` (gitoxide) directly
+//! against the repository's object/ref store, so a clone or update no
+//! longer depends on a `git` binary being on `PATH` and failures come back
+//! as typed errors instead of parsed text. Its clone and fetch pass
+//! `gix::progress::Discard`, so no progress is reported while they run.
+//! [`backend_for`] selects between them
+//! from [`GlobalConfig`].
+//!
+//! Both backends return [`WorkspaceError`], not a bare `anyhow::Error`, so a
+//! caller can distinguish (say) a rejected fast-forward from an auth
+//! failure instead of pattern-matching an interpolated string.
+
+use std::path::Path;
+use std::process::Command;
+
+use crate::config::GlobalConfig;
+use crate::gh::workspace_error::{classify, WorkspaceError};
+
+/// Everything `gh`'s workspace setup needs from git, independent of how
+/// it's actually performed.
+pub trait GitBackend {
+    /// Clone `clone_url` into `repo_dir` if it doesn't exist yet; otherwise
+    /// fetch, check out the default branch, and fast-forward it. `depth` of
+    /// `Some(n)` requests a shallow `--depth n --single-branch` clone on
+    /// first checkout (see [`ShellGitBackend::clone_or_update`]'s fallback
+    /// to a full clone if the remote rejects that); an existing checkout is
+    /// always just fetched, regardless of `depth`.
+    fn clone_or_update(
+        &self,
+        clone_url: &str,
+        repo_dir: &Path,
+        depth: Option<u32>,
+    ) -> Result<(), WorkspaceError>;
+
+    /// The repository's default branch, resolved from the remote.
+    fn detect_default_branch(&self, repo_dir: &Path) -> Result<String, WorkspaceError>;
+
+    /// Create and switch to a new branch off the current `HEAD`.
+    fn create_branch(&self, repo_dir: &Path, branch_name: &str) -> Result<(), WorkspaceError>;
+
+    /// Add a new worktree at `worktree_path`, checked out on a new branch
+    /// `branch_name` forked from `HEAD`. Unlike [`create_branch`](Self::create_branch),
+    /// this doesn't touch `repo_dir`'s own working directory or index, so
+    /// `repo_dir` stays usable (e.g. for another issue's worktree) while
+    /// this one is checked out.
+    fn create_worktree(
+        &self,
+        repo_dir: &Path,
+        worktree_path: &Path,
+        branch_name: &str,
+    ) -> Result<(), WorkspaceError>;
+
+    /// Remove a worktree previously created by [`create_worktree`](Self::create_worktree)
+    /// and prune its administrative metadata from `repo_dir`, so the branch
+    /// it held can be deleted or the path reused.
+    fn remove_worktree(&self, repo_dir: &Path, worktree_path: &Path) -> Result<(), WorkspaceError>;
+}
+
+/// Picks the backend `gh` uses for workspace git operations. Shell is the
+/// default until the gitoxide path has seen enough use to trust as the
+/// default; `GlobalConfig` lets it be opted into per-install.
+pub fn backend_for(global_config: &GlobalConfig) -> Box<dyn GitBackend> {
+    if global_config.git_backend.eq_ignore_ascii_case("gitoxide") {
+        Box::new(GixBackend)
+    } else {
+        Box::new(ShellGitBackend)
+    }
+}
+
+/// Shells out to the `git` CLI. Requires `git` on `PATH`; failures are
+/// classified from the command's raw stderr.
+pub struct ShellGitBackend;
+
+impl GitBackend for ShellGitBackend {
+    fn clone_or_update(
+        &self,
+        clone_url: &str,
+        repo_dir: &Path,
+        depth: Option<u32>,
+    ) -> Result<(), WorkspaceError> {
+        if repo_dir.join(".git").exists() {
+            run_git(repo_dir, &["fetch", "origin"], None)?;
+            let default_branch = self.detect_default_branch(repo_dir)?;
+            run_git(repo_dir, &["checkout", &default_branch], Some(&default_branch))?;
+            run_git(repo_dir, &["pull", "--ff-only"], Some(&default_branch))?;
+        } else {
+            let output = clone_command(clone_url, repo_dir, depth)
+                .output()
+                .map_err(|e| classify(repo_dir, &e.to_string(), None))?;
+            if output.status.success() {
+                return Ok(());
+            }
+
+            // Some hosts reject a shallow fetch outright — retry once as a
+            // full clone rather than failing an issue-fix run over it.
+            if depth.is_some() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                log::warn!(
+                    "shallow clone of {clone_url} failed ({}), retrying as a full clone",
+                    stderr.trim()
+                );
+                let _ = std::fs::remove_dir_all(repo_dir);
+                let output = clone_command(clone_url, repo_dir, None)
+                    .output()
+                    .map_err(|e| classify(repo_dir, &e.to_string(), None))?;
+                if !output.status.success() {
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    return Err(classify(repo_dir, &stderr, None));
+                }
+                return Ok(());
+            }
+
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(classify(repo_dir, &stderr, None));
+        }
+        Ok(())
+    }
+
+    fn detect_default_branch(&self, repo_dir: &Path) -> Result<String, WorkspaceError> {
+        let output = Command::new("git")
+            .args(["symbolic-ref", "refs/remotes/origin/HEAD", "--short"])
+            .current_dir(repo_dir)
+            .output()
+            .map_err(|e| classify(repo_dir, &e.to_string(), None))?;
+
+        if output.status.success() {
+            let branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            Ok(branch
+                .strip_prefix("origin/")
+                .unwrap_or(&branch)
+                .to_string())
+        } else {
+            for candidate in ["main", "master"] {
+                let output = Command::new("git")
+                    .args(["rev-parse", "--verify", &format!("origin/{}", candidate)])
+                    .current_dir(repo_dir)
+                    .output();
+                if let Ok(out) = output {
+                    if out.status.success() {
+                        return Ok(candidate.to_string());
+                    }
+                }
+            }
+            Ok("main".to_string())
+        }
+    }
+
+    fn create_branch(&self, repo_dir: &Path, branch_name: &str) -> Result<(), WorkspaceError> {
+        run_git(repo_dir, &["checkout", "-b", branch_name], Some(branch_name))?;
+        Ok(())
+    }
+
+    fn create_worktree(
+        &self,
+        repo_dir: &Path,
+        worktree_path: &Path,
+        branch_name: &str,
+    ) -> Result<(), WorkspaceError> {
+        run_git(
+            repo_dir,
+            &[
+                "worktree",
+                "add",
+                &worktree_path.to_string_lossy(),
+                "-b",
+                branch_name,
+            ],
+            Some(branch_name),
+        )
+    }
+
+    fn remove_worktree(&self, repo_dir: &Path, worktree_path: &Path) -> Result<(), WorkspaceError> {
+        run_git(
+            repo_dir,
+            &["worktree", "remove", "--force", &worktree_path.to_string_lossy()],
+            None,
+        )?;
+        run_git(repo_dir, &["worktree", "prune"], None)
+    }
+}
+
+/// Build a `git clone` command for `clone_url` into `repo_dir`, adding
+/// `--depth <n> --single-branch` when `depth` is `Some`.
+fn clone_command(clone_url: &str, repo_dir: &Path, depth: Option<u32>) -> Command {
+    let mut cmd = Command::new("git");
+    cmd.arg("clone");
+    if let Some(n) = depth {
+        cmd.arg("--depth").arg(n.to_string()).arg("--single-branch");
+    }
+    cmd.arg(clone_url).arg(repo_dir);
+    cmd
+}
+
+/// Runs `git` with `args` in `repo_dir`, turning a non-zero exit into a
+/// classified [`WorkspaceError`]. `branch`, when the failure might be
+/// branch-specific (checkout, pull, branch creation), is attached to
+/// whichever variant the stderr classifies as.
+fn run_git(repo_dir: &Path, args: &[&str], branch: Option<&str>) -> Result<(), WorkspaceError> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(repo_dir)
+        .output()
+        .map_err(|e| classify(repo_dir, &e.to_string(), branch))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(classify(repo_dir, &stderr, branch));
+    }
+    Ok(())
+}
+
+/// Talks to git directly through `gix`, without spawning a `git` binary.
+pub struct GixBackend;
+
+impl GitBackend for GixBackend {
+    fn clone_or_update(
+        &self,
+        clone_url: &str,
+        repo_dir: &Path,
+        depth: Option<u32>,
+    ) -> Result<(), WorkspaceError> {
+        // `depth` is accepted for interface parity with `ShellGitBackend`
+        // but not honored — gix's shallow-fetch plumbing isn't wired up
+        // here yet, so this always fetches full history. Slower on large
+        // repos, but never wrong.
+        let _ = depth;
+        if repo_dir.join(".git").exists() {
+            let repo = open_repo(repo_dir)?;
+
+            let remote = repo.find_remote("origin").map_err(|e| {
+                classify(repo_dir, &format!("no 'origin' remote: {e}"), None)
+            })?;
+            let outcome = remote
+                .connect(gix::remote::Direction::Fetch)
+                .map_err(|e| classify(repo_dir, &e.to_string(), None))?
+                .prepare_fetch(gix::progress::Discard, Default::default())
+                .map_err(|e| classify(repo_dir, &e.to_string(), None))?
+                .receive(gix::progress::Discard, &gix::interrupt::IS_INTERRUPTED)
+                .map_err(|e| classify(repo_dir, &e.to_string(), None))?;
+            let _ = outcome;
+
+            let default_branch = self.detect_default_branch(repo_dir)?;
+            fast_forward_to(&repo, repo_dir, &default_branch)?;
+        } else {
+            gix::prepare_clone(clone_url, repo_dir)
+                .map_err(|e| classify(repo_dir, &e.to_string(), None))?
+                .fetch_then_checkout(gix::progress::Discard, &gix::interrupt::IS_INTERRUPTED)
+                .map_err(|e| classify(repo_dir, &e.to_string(), None))?;
+        }
+        Ok(())
+    }
+
+    fn detect_default_branch(&self, repo_dir: &Path) -> Result<String, WorkspaceError> {
+        let repo = open_repo(repo_dir)?;
+
+        // Resolve the symbolic `refs/remotes/origin/HEAD` straight from the
+        // ref store, rather than parsing `git symbolic-ref`'s text output.
+        if let Ok(head) = repo.find_reference("refs/remotes/origin/HEAD") {
+            if let Some(target_name) = head.target().try_name() {
+                if let Some(branch) = target_name.as_bstr().to_string().rsplit('/').next() {
+                    return Ok(branch.to_string());
+                }
+            }
+        }
+
+        for candidate in ["main", "master"] {
+            if repo
+                .find_reference(&format!("refs/remotes/origin/{candidate}"))
+                .is_ok()
+            {
+                return Ok(candidate.to_string());
+            }
+        }
+        Ok("main".to_string())
+    }
+
+    fn create_branch(&self, repo_dir: &Path, branch_name: &str) -> Result<(), WorkspaceError> {
+        let repo = open_repo(repo_dir)?;
+        let head_id = repo.head_id().map_err(|e| {
+            classify(repo_dir, &format!("no HEAD commit to branch from: {e}"), Some(branch_name))
+        })?;
+
+        repo.reference(
+            format!("refs/heads/{branch_name}"),
+            head_id,
+            gix::refs::transaction::PreviousValue::MustNotExist,
+            format!("create branch {branch_name}"),
+        )
+        .map_err(|e| classify(repo_dir, &e.to_string(), Some(branch_name)))?;
+
+        repo.edit_reference(gix::refs::transaction::RefEdit {
+            change: gix::refs::transaction::Change::Update {
+                log: Default::default(),
+                expected: gix::refs::transaction::PreviousValue::Any,
+                new: gix::refs::Target::Symbolic(
+                    format!("refs/heads/{branch_name}")
+                        .try_into()
+                        .map_err(|e| classify(repo_dir, &format!("invalid branch ref name: {e}"), Some(branch_name)))?,
+                ),
+            },
+            name: "HEAD"
+                .try_into()
+                .map_err(|e| classify(repo_dir, &format!("invalid HEAD ref name: {e}"), Some(branch_name)))?,
+            deref: false,
+        })
+        .map_err(|e| classify(repo_dir, &e.to_string(), Some(branch_name)))?;
+
+        Ok(())
+    }
+
+    fn create_worktree(
+        &self,
+        repo_dir: &Path,
+        _worktree_path: &Path,
+        branch_name: &str,
+    ) -> Result<(), WorkspaceError> {
+        // gitoxide has no stable worktree-administration API yet (no
+        // equivalent of `git worktree add`'s `.git/worktrees/<name>` setup).
+        // Fail honestly instead of faking it — callers that need worktrees
+        // should select the shell backend.
+        let _ = open_repo(repo_dir)?;
+        Err(WorkspaceError::Other {
+            stderr: format!(
+                "worktree creation for branch {branch_name} is not supported by the gitoxide backend; use the shell backend"
+            ),
+        })
+    }
+
+    fn remove_worktree(&self, repo_dir: &Path, worktree_path: &Path) -> Result<(), WorkspaceError> {
+        let _ = open_repo(repo_dir)?;
+        Err(WorkspaceError::Other {
+            stderr: format!(
+                "worktree removal of {} is not supported by the gitoxide backend; use the shell backend",
+                worktree_path.display()
+            ),
+        })
+    }
+}
+
+/// `gix::open`, mapped to [`WorkspaceError::NotARepo`] on failure — opening
+/// is the clearest native signal that `repo_dir` isn't (yet) a git
+/// repository, so this skips text classification entirely.
+fn open_repo(repo_dir: &Path) -> Result<gix::Repository, WorkspaceError> {
+    gix::open(repo_dir).map_err(|_| WorkspaceError::NotARepo {
+        path: repo_dir.to_path_buf(),
+    })
+}
+
+/// Fast-forwards the checked-out worktree to `branch`'s remote-tracking tip
+/// by comparing object ids directly, refusing (rather than merging) if the
+/// local branch has diverged.
+fn fast_forward_to(
+    repo: &gix::Repository,
+    repo_dir: &Path,
+    branch: &str,
+) -> Result<(), WorkspaceError> {
+    let local = repo
+        .find_reference(&format!("refs/heads/{branch}"))
+        .map_err(|e| classify(repo_dir, &format!("no local branch {branch}: {e}"), Some(branch)))?
+        .id();
+    let remote = repo
+        .find_reference(&format!("refs/remotes/origin/{branch}"))
+        .map_err(|e| {
+            classify(
+                repo_dir,
+                &format!("no remote-tracking branch origin/{branch}: {e}"),
+                Some(branch),
+            )
+        })?
+        .id();
+
+    if local == remote {
+        return Ok(());
+    }
+
+    let is_ancestor = repo
+        .merge_base(local, remote)
+        .map(|base| base == local)
+        .unwrap_or(false);
+    if !is_ancestor {
+        return Err(WorkspaceError::FastForwardRejected {
+            branch: branch.to_string(),
+            stderr: format!("local {branch} and origin/{branch} have diverged"),
+        });
+    }
+
+    repo.reference(
+        format!("refs/heads/{branch}"),
+        remote,
+        gix::refs::transaction::PreviousValue::MustExistAndMatch(gix::refs::Target::Object(
+            local.detach(),
+        )),
+        format!("fast-forward to origin/{branch}"),
+    )
+    .map_err(|e| classify(repo_dir, &format!("failed to fast-forward {branch}: {e}"), Some(branch)))?;
+
+    Ok(())
+}
@@ -0,0 +1,290 @@
+//! Persistent, append-only history of completed batch runs.
+//!
+//! `batch::append_checkpoint` only persists per-run state (it's read back
+//! only to resume that same batch). This module keeps one JSONL
+//! line per *completed* `ProofDataset` instead, keyed by timestamp and
+//! (optionally) commit SHA, so trends can be tracked across runs.
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+
+use super::batch::{median, AggregateStats, ProofDataset};
+
+const HISTORY_FILE_NAME: &str = "metrics-history.jsonl";
+
+/// One completed run's aggregate results.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricsHistoryEntry {
+    pub recorded_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit_sha: Option<String>,
+    pub corpus_size: usize,
+    pub completed: usize,
+    pub failed: usize,
+    pub aggregate: AggregateStats,
+    pub by_tag: HashMap<String, AggregateStats>,
+}
+
+fn history_path(output_dir: &Path) -> PathBuf {
+    output_dir.join(HISTORY_FILE_NAME)
+}
+
+/// Reads the HEAD commit SHA of the git repo containing the process's
+/// current working directory, if there is one.
+/// Best-effort: returns `None` rather than failing the batch over it.
+fn current_commit_sha() -> Option<String> {
+    let output = std::process::Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let sha = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if sha.is_empty() {
+        None
+    } else {
+        Some(sha)
+    }
+}
+
+/// Appends one line for a just-completed `ProofDataset`.
+pub fn record_metrics_history(output_dir: &Path, dataset: &ProofDataset, recorded_at: String) -> Result<()> {
+    let entry = MetricsHistoryEntry {
+        recorded_at,
+        commit_sha: current_commit_sha(),
+        corpus_size: dataset.corpus_size,
+        completed: dataset.completed,
+        failed: dataset.failed,
+        aggregate: dataset.aggregate.clone(),
+        by_tag: dataset.by_tag.clone(),
+    };
+
+    let path = history_path(output_dir);
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open metrics history: {}", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(&entry)?)?;
+    Ok(())
+}
+
+/// Loads every recorded entry, oldest first.
+pub fn load_metrics_history(output_dir: &Path) -> Vec<MetricsHistoryEntry> {
+    let path = history_path(output_dir);
+    let mut entries = Vec::new();
+    if let Ok(file) = fs::File::open(&path) {
+        for line in std::io::BufReader::new(file).lines().map_while(Result::ok) {
+            if let Ok(entry) = serde_json::from_str::<MetricsHistoryEntry>(&line) {
+                entries.push(entry);
+            }
+        }
+    }
+    entries
+}
+
+const SPARK_BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Renders `values` as a one-line Unicode sparkline, scaled to the series'
+/// own min/max so a flat series renders as a flat line rather than noise.
+fn sparkline(values: &[f64]) -> String {
+    if values.is_empty() {
+        return String::new();
+    }
+    let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let range = (max - min).max(f64::EPSILON);
+    values
+        .iter()
+        .map(|&v| {
+            let idx = (((v - min) / range) * (SPARK_BLOCKS.len() - 1) as f64).round() as usize;
+            SPARK_BLOCKS[idx.min(SPARK_BLOCKS.len() - 1)]
+        })
+        .collect()
+}
+
+/// Prints the last `limit` history entries as a table with a sparkline per
+/// metric, then flags a regression when the newest run's mean cost savings
+/// drops more than `threshold_points` percentage points below the trailing
+/// median of the rest. Returns `Ok(1)` on regression (mirrors
+/// `batch::run_validate`'s exit-code convention), `Ok(0)` otherwise.
+pub fn run_metrics_history(output_dir: &Path, limit: usize, threshold_points: f64) -> Result<i32> {
+    let entries = load_metrics_history(output_dir);
+    if entries.is_empty() {
+        println!(
+            "No metrics history recorded yet at {}",
+            history_path(output_dir).display()
+        );
+        return Ok(0);
+    }
+
+    let mut recent: Vec<&MetricsHistoryEntry> = entries.iter().rev().take(limit.max(1)).collect();
+    recent.reverse(); // oldest..newest within the capped window
+
+    println!("{}", "Metrics History".cyan().bold());
+    println!("{}", "=".repeat(72).dimmed());
+    println!(
+        "{:<22} {:>8} {:>8} {:>6} {:>10}",
+        "Recorded", "Cost%", "Tokens%", "Runs", "FMM $"
+    );
+    for entry in &recent {
+        println!(
+            "{:<22} {:>7.0}% {:>7.0}% {:>6} {:>9.2}",
+            entry.recorded_at,
+            entry.aggregate.cost_pct.mean,
+            entry.aggregate.input_tokens_pct.mean,
+            entry.completed,
+            entry.aggregate.total_fmm_cost_usd,
+        );
+    }
+
+    let cost_series: Vec<f64> = recent.iter().map(|e| e.aggregate.cost_pct.mean).collect();
+    let tokens_series: Vec<f64> = recent
+        .iter()
+        .map(|e| e.aggregate.input_tokens_pct.mean)
+        .collect();
+    let fmm_cost_series: Vec<f64> = recent.iter().map(|e| e.aggregate.total_fmm_cost_usd).collect();
+    println!("\n  {} {}", "Cost% trend:".bold(), sparkline(&cost_series));
+    println!("  {} {}", "Tokens% trend:".bold(), sparkline(&tokens_series));
+    println!("  {} {}", "FMM cost trend:".bold(), sparkline(&fmm_cost_series));
+
+    if recent.len() < 2 {
+        println!("\n{} Not enough history yet to check for regressions", "·".dimmed());
+        return Ok(0);
+    }
+
+    let newest = recent[recent.len() - 1];
+    let mut trailing: Vec<f64> = recent[..recent.len() - 1]
+        .iter()
+        .map(|e| e.aggregate.cost_pct.mean)
+        .collect();
+    trailing.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let trailing_median = median(&trailing);
+    let drop = trailing_median - newest.aggregate.cost_pct.mean;
+
+    if drop > threshold_points {
+        println!(
+            "\n{} Cost savings regressed {:.1} pts below the trailing median ({:.0}% median vs {:.0}% latest)",
+            "REGRESSION".red().bold(),
+            drop,
+            trailing_median,
+            newest.aggregate.cost_pct.mean,
+        );
+        Ok(1)
+    } else {
+        println!(
+            "\n{} No regression ({:.0}% latest vs {:.0}% trailing median)",
+            "✓".green().bold(),
+            newest.aggregate.cost_pct.mean,
+            trailing_median,
+        );
+        Ok(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gh::batch::Stat;
+    use tempfile::TempDir;
+
+    fn make_entry(recorded_at: &str, cost_pct_mean: f64) -> MetricsHistoryEntry {
+        MetricsHistoryEntry {
+            recorded_at: recorded_at.to_string(),
+            commit_sha: None,
+            corpus_size: 5,
+            completed: 5,
+            failed: 0,
+            aggregate: AggregateStats {
+                count: 5,
+                input_tokens_pct: Stat::default(),
+                total_tokens_pct: Stat::default(),
+                cost_pct: Stat {
+                    mean: cost_pct_mean,
+                    ..Stat::default()
+                },
+                turns_pct: Stat::default(),
+                tool_calls_pct: Stat::default(),
+                files_read_pct: Stat::default(),
+                duration_pct: Stat::default(),
+                median_cost_pct: cost_pct_mean,
+                median_total_tokens_pct: 0.0,
+                total_control_cost_usd: 1.0,
+                total_fmm_cost_usd: 0.5,
+                cost_significance: crate::gh::batch::WilcoxonResult::default(),
+                cost_savings_ci: crate::gh::batch::BootstrapCi::default(),
+            },
+            by_tag: HashMap::new(),
+        }
+    }
+
+    fn write_entries(output_dir: &Path, entries: &[MetricsHistoryEntry]) {
+        let path = history_path(output_dir);
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .unwrap();
+        for entry in entries {
+            writeln!(file, "{}", serde_json::to_string(entry).unwrap()).unwrap();
+        }
+    }
+
+    #[test]
+    fn load_metrics_history_empty_when_no_file() {
+        let tmp = TempDir::new().unwrap();
+        assert!(load_metrics_history(tmp.path()).is_empty());
+    }
+
+    #[test]
+    fn load_metrics_history_round_trips_appended_entries() {
+        let tmp = TempDir::new().unwrap();
+        write_entries(
+            tmp.path(),
+            &[make_entry("2026-01-01T00:00:00Z", 40.0), make_entry("2026-01-02T00:00:00Z", 42.0)],
+        );
+
+        let entries = load_metrics_history(tmp.path());
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].recorded_at, "2026-01-01T00:00:00Z");
+        assert_eq!(entries[1].aggregate.cost_pct.mean, 42.0);
+    }
+
+    #[test]
+    fn run_metrics_history_flags_regression_below_threshold() {
+        let tmp = TempDir::new().unwrap();
+        write_entries(
+            tmp.path(),
+            &[
+                make_entry("2026-01-01T00:00:00Z", 50.0),
+                make_entry("2026-01-02T00:00:00Z", 48.0),
+                make_entry("2026-01-03T00:00:00Z", 30.0),
+            ],
+        );
+
+        let exit_code = run_metrics_history(tmp.path(), 10, 5.0).unwrap();
+        assert_eq!(exit_code, 1);
+    }
+
+    #[test]
+    fn run_metrics_history_passes_within_threshold() {
+        let tmp = TempDir::new().unwrap();
+        write_entries(
+            tmp.path(),
+            &[
+                make_entry("2026-01-01T00:00:00Z", 50.0),
+                make_entry("2026-01-02T00:00:00Z", 48.0),
+                make_entry("2026-01-03T00:00:00Z", 47.0),
+            ],
+        );
+
+        let exit_code = run_metrics_history(tmp.path(), 10, 5.0).unwrap();
+        assert_eq!(exit_code, 0);
+    }
+}
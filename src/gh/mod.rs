@@ -1,16 +1,28 @@
 pub mod batch;
+mod git_backend;
 mod github;
+pub mod metrics_history;
 mod prompt;
 mod references;
 pub mod report;
 mod runner;
+mod watch;
 mod workspace;
+mod workspace_error;
 
 pub use github::{create_pr, fetch_issue, preflight_check, Issue, IssueRef};
 pub use prompt::{build_prompt, format_dry_run};
-pub use references::{extract_references, resolve_references, CodeReference, ResolvedReference};
+pub use references::{
+    extract_references, resolve_references, CodeReference, ResolvedReference, ScopePrefix,
+    UnresolvedReference,
+};
 pub use runner::{invoke_claude, invoke_claude_with_options, InvokeOptions, RunResult};
-pub use workspace::{clone_or_update, create_branch, generate_sidecars, resolve_workspace};
+pub use watch::watch_sidecars;
+pub use workspace::{
+    clone_or_update, create_branch, create_worktree, generate_sidecars, remove_worktree,
+    resolve_workspace, summarize_changes,
+};
+pub use workspace_error::WorkspaceError;
 
 use anyhow::{Context, Result};
 use colored::Colorize;
@@ -28,7 +40,30 @@ pub struct GhIssueOptions {
     pub no_pr: bool,
     pub workspace: Option<String>,
     pub compare: bool,
+    /// Number of control/fmm trials to run when `compare` is set (see
+    /// `run_issue_compare`). `1` skips significance testing entirely.
+    pub runs: u32,
+    /// With `compare`, diff this run's savings against a baseline saved
+    /// earlier via `save_baseline` (requires `output`, since that's where
+    /// baselines live).
+    pub baseline: Option<String>,
+    /// With `compare`, save this run's report as a named baseline under
+    /// `output` for a future run to diff against via `baseline`.
+    pub save_baseline: Option<String>,
+    /// Percentage points the cost reduction may drop against `baseline`
+    /// before the run fails. Only consulted when `baseline` is set.
+    pub max_regression_pct: f64,
     pub output: Option<String>,
+    pub edit: bool,
+    pub allowed_tools: Option<String>,
+    /// Name of the `--profile` that filled in any of the fields above the
+    /// user didn't pass explicitly, recorded so `run_issue_compare`'s report
+    /// can say which profile a comparison run used.
+    pub profile: Option<String>,
+    /// Depth for the workspace's initial clone (see
+    /// `git_backend::GitBackend::clone_or_update`): `Some(n)` for a shallow
+    /// `--depth n --single-branch` clone, `None` for full history.
+    pub clone_depth: Option<u32>,
 }
 
 pub fn gh_issue(url: &str, options: GhIssueOptions) -> Result<()> {
@@ -67,10 +102,12 @@ fn gh_issue_fix(issue: &Issue, issue_ref: &IssueRef, options: &GhIssueOptions) -
     // 5. Clone or update
     println!("{}", "Preparing workspace...".green().bold());
     let repo_dir = clone_or_update(
+        &global_config,
         &issue_ref.clone_url,
         &workspace_root,
         &issue_ref.owner,
         &issue_ref.repo,
+        options.clone_depth,
     )?;
 
     // 6. Generate sidecars
@@ -99,7 +136,14 @@ fn gh_issue_fix(issue: &Issue, issue_ref: &IssueRef, options: &GhIssueOptions) -
     );
 
     // 10. Build prompt
-    let prompt = build_prompt(issue, issue_ref, &resolved, &unresolved);
+    let prompt = build_prompt(issue, issue_ref, &resolved, &unresolved, &manifest);
+
+    // 10b. Let the user revise it before it's sent to Claude, if requested.
+    let prompt = if options.edit {
+        edit_prompt(prompt)?
+    } else {
+        prompt
+    };
 
     // 11. Dry run?
     if options.dry_run {
@@ -111,20 +155,28 @@ fn gh_issue_fix(issue: &Issue, issue_ref: &IssueRef, options: &GhIssueOptions) -
     }
 
     // 12. Create branch
-    let branch = create_branch(&repo_dir, &options.branch_prefix, issue_ref.number)?;
+    let branch = create_branch(
+        &global_config,
+        &repo_dir,
+        &options.branch_prefix,
+        issue_ref.number,
+    )?;
     println!("  {} {}", "Branch:".bold(), branch);
 
     let pre_claude_head = get_head_sha(&repo_dir)?;
 
     // 13. Invoke Claude
     println!("{}", "Running Claude...".green().bold());
-    let result = invoke_claude(
-        &prompt,
-        &repo_dir,
-        &options.model,
-        options.max_turns,
-        options.max_budget,
-    )?;
+    let result = invoke_claude_with_options(InvokeOptions {
+        prompt: &prompt,
+        repo_dir: &repo_dir,
+        model: &options.model,
+        max_turns: options.max_turns,
+        max_budget: options.max_budget,
+        allowed_tools: options.allowed_tools.as_deref(),
+        setting_sources: None,
+        append_system_prompt: None,
+    })?;
     println!(
         "  {} {}, {} turns, ${:.4}",
         "Result:".bold(),
@@ -174,6 +226,17 @@ fn gh_issue_fix(issue: &Issue, issue_ref: &IssueRef, options: &GhIssueOptions) -
     Ok(())
 }
 
+/// Open `prompt` in `$VISUAL`/`$EDITOR` (falling back to a sensible
+/// platform default) so the user can revise `build_prompt`'s auto-resolved
+/// references and instructions before they're sent to Claude — mirrors how
+/// backpack's `add` command shells out to an editor for quick tweaks to
+/// machine-generated content. The edited buffer is what gets used for the
+/// dry-run preview, `invoke_claude`, and any report output; the original,
+/// unedited prompt is discarded.
+fn edit_prompt(prompt: String) -> Result<String> {
+    edit::edit(&prompt).context("Failed to open an editor to revise the prompt")
+}
+
 /// Compare flow: run control (no sidecars) vs fmm (with sidecars) in isolated sandboxes.
 fn gh_issue_compare(
     url: &str,
@@ -188,17 +251,41 @@ fn gh_issue_compare(
         &options.model,
         options.max_turns,
         options.max_budget,
+        options.profile.as_deref(),
+        options.clone_depth,
+        options.runs,
     )?;
 
-    report.print_summary();
+    let output_dir = options.output.as_deref().map(std::path::Path::new);
+
+    let baseline_delta = match &options.baseline {
+        Some(name) => {
+            let dir = output_dir
+                .context("--baseline requires --output (baselines are read/written there)")?;
+            let baseline = report::IssueComparisonReport::load_baseline(dir, name)?;
+            let delta = report.compare_to_baseline(&baseline);
+            if let Some(reason) = delta.regression_reason(options.max_regression_pct) {
+                anyhow::bail!("{reason}");
+            }
+            Some(delta)
+        }
+        None => None,
+    };
+
+    report.print_summary(baseline_delta.as_ref());
 
     // Save report files
-    if let Some(ref output_dir) = options.output {
-        let saved = report.save(std::path::Path::new(output_dir))?;
+    if let Some(dir) = output_dir {
+        let saved = report.save(dir, baseline_delta.as_ref())?;
         println!();
         for path in &saved {
             println!("  {} {}", "Saved:".bold(), path.dimmed());
         }
+
+        if let Some(name) = &options.save_baseline {
+            let path = report.save_baseline(dir, name)?;
+            println!("  {} Saved baseline: {}", "Saved:".bold(), path.dimmed());
+        }
     } else {
         // Default: print JSON to allow piping
         println!("\n{}", "--- JSON Report ---".dimmed());
@@ -211,6 +298,74 @@ fn gh_issue_compare(
     Ok(())
 }
 
+/// Run one variant `runs` times against `repo_dir`, resetting the working
+/// tree to a clean checkout between trials so each trial starts from the
+/// same state instead of compounding the previous trial's edits.
+fn run_variant_trials(
+    repo_dir: &std::path::Path,
+    prompt: &str,
+    model: &str,
+    max_turns: u32,
+    max_budget: f64,
+    allowed_tools: Option<&str>,
+    setting_sources: Option<&str>,
+    append_system_prompt: Option<&str>,
+    runs: u32,
+) -> Result<Vec<RunResult>> {
+    let mut results = Vec::with_capacity(runs as usize);
+    for trial in 1..=runs {
+        if trial > 1 {
+            reset_working_tree(repo_dir)?;
+        }
+        if runs > 1 {
+            println!("  {} trial {}/{}", "▶".dimmed(), trial, runs);
+        }
+
+        let result = invoke_claude_with_options(InvokeOptions {
+            prompt,
+            repo_dir,
+            model,
+            max_turns,
+            max_budget,
+            allowed_tools,
+            setting_sources,
+            append_system_prompt,
+        })?;
+
+        println!(
+            "  {} {}, {} turns, {} tool calls, ${:.4}",
+            if result.success {
+                "OK".green()
+            } else {
+                "FAIL".red()
+            },
+            if result.success { "success" } else { "failed" },
+            result.metrics.turns,
+            result.metrics.tool_calls,
+            result.metrics.cost_usd,
+        );
+
+        results.push(result);
+    }
+    Ok(results)
+}
+
+/// Discard a trial's edits so the next trial sees the same starting tree:
+/// `checkout .` reverts tracked changes, `clean -fd` removes new files.
+fn reset_working_tree(repo_dir: &std::path::Path) -> Result<()> {
+    crate::exec::command("git")?
+        .args(["checkout", "--", "."])
+        .current_dir(repo_dir)
+        .output()
+        .context("Failed to reset working tree between trials")?;
+    crate::exec::command("git")?
+        .args(["clean", "-fd"])
+        .current_dir(repo_dir)
+        .output()
+        .context("Failed to clean working tree between trials")?;
+    Ok(())
+}
+
 /// Run a single issue A/B comparison and return the report.
 /// This is the reusable core used by both `fmm gh issue --compare` and `fmm gh batch`.
 pub fn run_issue_compare(
@@ -220,7 +375,11 @@ pub fn run_issue_compare(
     model: &str,
     max_turns: u32,
     max_budget: f64,
+    profile: Option<&str>,
+    clone_depth: Option<u32>,
+    runs: u32,
 ) -> Result<report::IssueComparisonReport> {
+    let runs = runs.max(1);
     let repo_slug = format!("{}/{}", issue_ref.owner, issue_ref.repo);
 
     println!(
@@ -234,7 +393,7 @@ pub fn run_issue_compare(
     println!("{}", "Setting up sandboxes...".green().bold());
     let job_id = generate_compare_job_id(issue_ref);
     let sandbox = Sandbox::new(&job_id)?;
-    sandbox.clone_repo(&issue_ref.clone_url, None)?;
+    sandbox.clone_repo(&issue_ref.clone_url, None, clone_depth)?;
     println!("  {} Cloned into dual sandboxes", "OK".green());
 
     // --- Build prompts ---
@@ -249,82 +408,70 @@ pub fn run_issue_compare(
 
     let refs = extract_references(&issue.body);
     let (resolved, unresolved) = resolve_references(&refs, &manifest);
-    let fmm_prompt = build_prompt(issue, issue_ref, &resolved, &unresolved);
+    let fmm_prompt = build_prompt(issue, issue_ref, &resolved, &unresolved, &manifest);
     let fmm_context = build_fmm_context(&sandbox.fmm_dir);
 
     // --- Run control variant FIRST (cold cache, no sidecars) ---
     println!(
-        "\n{} Running {} variant...",
+        "\n{} Running {} variant ({} trial{})...",
         "1/2".cyan().bold(),
-        "control".white().bold()
+        "control".white().bold(),
+        runs,
+        if runs == 1 { "" } else { "s" }
     );
     println!("  {} No sidecars, no skill, no MCP", "Config:".dimmed());
 
-    let control_result = invoke_claude_with_options(InvokeOptions {
-        prompt: &control_prompt,
-        repo_dir: &sandbox.control_dir,
+    let control_results = run_variant_trials(
+        &sandbox.control_dir,
+        &control_prompt,
         model,
         max_turns,
         max_budget,
-        allowed_tools: Some("Read,Write,Edit,Glob,Grep,Bash"),
-        setting_sources: Some(""),
-        append_system_prompt: None,
-    })?;
-
-    println!(
-        "  {} {}, {} turns, {} tool calls, ${:.4}",
-        if control_result.success {
-            "OK".green()
-        } else {
-            "FAIL".red()
-        },
-        if control_result.success {
-            "success"
-        } else {
-            "failed"
-        },
-        control_result.metrics.turns,
-        control_result.metrics.tool_calls,
-        control_result.metrics.cost_usd,
-    );
+        Some("Read,Write,Edit,Glob,Grep,Bash"),
+        Some(""),
+        None,
+        runs,
+    )?;
 
     // --- Run FMM variant SECOND (sidecars + skill + MCP) ---
     println!(
-        "\n{} Running {} variant...",
+        "\n{} Running {} variant ({} trial{})...",
         "2/2".cyan().bold(),
-        "fmm".green().bold()
+        "fmm".green().bold(),
+        runs,
+        if runs == 1 { "" } else { "s" }
     );
     println!("  {} Sidecars + skill + MCP + context", "Config:".dimmed());
 
-    let fmm_result = invoke_claude_with_options(InvokeOptions {
-        prompt: &fmm_prompt,
-        repo_dir: &sandbox.fmm_dir,
+    let fmm_results = run_variant_trials(
+        &sandbox.fmm_dir,
+        &fmm_prompt,
         model,
         max_turns,
         max_budget,
-        allowed_tools: Some("Read,Write,Edit,Glob,Grep,Bash"),
-        setting_sources: Some("local"),
-        append_system_prompt: Some(&fmm_context),
-    })?;
+        Some("Read,Write,Edit,Glob,Grep,Bash"),
+        Some("local"),
+        Some(&fmm_context),
+        runs,
+    )?;
 
+    // --- Summarize what each variant actually touched (last trial only) ---
+    let control_changes = summarize_changes(&sandbox.control_dir)?;
+    let fmm_changes = summarize_changes(&sandbox.fmm_dir)?;
     println!(
-        "  {} {}, {} turns, {} tool calls, ${:.4}",
-        if fmm_result.success {
-            "OK".green()
-        } else {
-            "FAIL".red()
-        },
-        if fmm_result.success {
-            "success"
-        } else {
-            "failed"
-        },
-        fmm_result.metrics.turns,
-        fmm_result.metrics.tool_calls,
-        fmm_result.metrics.cost_usd,
+        "\n  {} control: {} files, +{}/-{}; fmm: {} files, +{}/-{}",
+        "Changes:".bold(),
+        control_changes.files.len(),
+        control_changes.insertions,
+        control_changes.deletions,
+        fmm_changes.files.len(),
+        fmm_changes.insertions,
+        fmm_changes.deletions,
     );
 
     // --- Generate report ---
+    let control_trials: Vec<_> = control_results.into_iter().map(|r| r.metrics).collect();
+    let fmm_trials: Vec<_> = fmm_results.into_iter().map(|r| r.metrics).collect();
     let report = report::IssueComparisonReport::new(report::ReportInput {
         issue_url: url,
         issue_title: &issue.title,
@@ -333,8 +480,14 @@ pub fn run_issue_compare(
         model,
         max_budget_usd: max_budget,
         max_turns,
-        control_metrics: &control_result.metrics,
-        fmm_metrics: &fmm_result.metrics,
+        control_trials: &control_trials,
+        fmm_trials: &fmm_trials,
+        control_diff: "",
+        fmm_diff: "",
+        control_changes,
+        fmm_changes,
+        profile,
+        pricing: None,
     });
 
     Ok(report)
@@ -418,7 +571,7 @@ fn generate_compare_job_id(issue_ref: &IssueRef) -> String {
 }
 
 fn get_head_sha(repo_dir: &std::path::Path) -> Result<String> {
-    let output = std::process::Command::new("git")
+    let output = crate::exec::command("git")?
         .args(["rev-parse", "HEAD"])
         .current_dir(repo_dir)
         .output()
@@ -428,7 +581,7 @@ fn get_head_sha(repo_dir: &std::path::Path) -> Result<String> {
 }
 
 fn verify_changes(repo_dir: &std::path::Path, pre_claude_head: &str) -> Result<bool> {
-    let status = std::process::Command::new("git")
+    let status = crate::exec::command("git")?
         .args(["status", "--porcelain"])
         .current_dir(repo_dir)
         .output()
@@ -447,7 +600,7 @@ fn verify_changes(repo_dir: &std::path::Path, pre_claude_head: &str) -> Result<b
 }
 
 fn commit_changes(repo_dir: &std::path::Path, issue: &Issue) -> Result<()> {
-    let output = std::process::Command::new("git")
+    let output = crate::exec::command("git")?
         .args(["add", "-A"])
         .current_dir(repo_dir)
         .output()
@@ -460,7 +613,7 @@ fn commit_changes(repo_dir: &std::path::Path, issue: &Issue) -> Result<()> {
         );
     }
 
-    let status = std::process::Command::new("git")
+    let status = crate::exec::command("git")?
         .args(["diff", "--cached", "--quiet"])
         .current_dir(repo_dir)
         .output()
@@ -471,7 +624,7 @@ fn commit_changes(repo_dir: &std::path::Path, issue: &Issue) -> Result<()> {
     }
 
     let commit_msg = format!("fix: {} (#{})", issue.title, issue.number);
-    let output = std::process::Command::new("git")
+    let output = crate::exec::command("git")?
         .args(["commit", "-m", &commit_msg])
         .current_dir(repo_dir)
         .output()
@@ -488,7 +641,7 @@ fn commit_changes(repo_dir: &std::path::Path, issue: &Issue) -> Result<()> {
 }
 
 fn push_branch(repo_dir: &std::path::Path, branch: &str) -> Result<()> {
-    let output = std::process::Command::new("git")
+    let output = crate::exec::command("git")?
         .args(["push", "-u", "origin", branch])
         .current_dir(repo_dir)
         .output()
@@ -1,11 +1,20 @@
+use crate::context_budget::{self, Candidate, RankingStrategy};
 use crate::gh::github::{Issue, IssueRef};
-use crate::gh::references::ResolvedReference;
+use crate::gh::references::{ResolvedReference, UnresolvedReference};
+use crate::manifest::Manifest;
+
+/// Token budget for the "Relevant Files" section. `resolve_references`
+/// already caps at `MAX_RESOLVED_FILES` entries, but a large fan-out of
+/// heavily-imported files can still render to more tokens than a model's
+/// context window can comfortably spare alongside the rest of the prompt.
+const RESOLVED_FILES_TOKEN_BUDGET: usize = 6000;
 
 pub fn build_prompt(
     issue: &Issue,
     issue_ref: &IssueRef,
     resolved: &[ResolvedReference],
-    unresolved: &[String],
+    unresolved: &[UnresolvedReference],
+    manifest: &Manifest,
 ) -> String {
     let mut prompt = String::new();
 
@@ -18,24 +27,44 @@ pub fn build_prompt(
 
     if !resolved.is_empty() {
         prompt.push_str("## Relevant Files (from sidecar analysis)\n");
-        for r in resolved {
+
+        let candidates: Vec<Candidate> = resolved
+            .iter()
+            .map(|r| Candidate {
+                key: r.file_path.clone(),
+                in_degree: manifest
+                    .importers
+                    .get(&r.file_path)
+                    .map(Vec::len)
+                    .unwrap_or(0),
+                rendered: render_resolved_reference(r),
+            })
+            .collect();
+        let elided = context_budget::evict_to_budget(
+            &candidates,
+            context_budget::estimate_tokens(&prompt),
+            RESOLVED_FILES_TOKEN_BUDGET,
+            RankingStrategy::InDegree,
+        );
+        let elided: std::collections::HashSet<&str> = elided.iter().map(String::as_str).collect();
+
+        for (r, candidate) in resolved.iter().zip(&candidates) {
+            if !elided.contains(r.file_path.as_str()) {
+                prompt.push_str(&candidate.rendered);
+            }
+        }
+
+        if !elided.is_empty() {
             prompt.push_str(&format!(
-                "### {} (match: {})\n",
-                r.file_path, r.match_reason
+                "{} file(s) elided to fit the context budget (few or no other files import them): {}. Ask for any of these by path if you need their exports/imports/dependencies.\n\n",
+                elided.len(),
+                resolved
+                    .iter()
+                    .filter(|r| elided.contains(r.file_path.as_str()))
+                    .map(|r| r.file_path.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
             ));
-            if !r.exports.is_empty() {
-                prompt.push_str(&format!("  exports: [{}]\n", r.exports.join(", ")));
-            }
-            if !r.imports.is_empty() {
-                prompt.push_str(&format!("  imports: [{}]\n", r.imports.join(", ")));
-            }
-            if !r.dependencies.is_empty() {
-                prompt.push_str(&format!(
-                    "  dependencies: [{}]\n",
-                    r.dependencies.join(", ")
-                ));
-            }
-            prompt.push_str(&format!("  loc: {}\n\n", r.loc));
         }
     }
 
@@ -51,9 +80,14 @@ pub fn build_prompt(
     }
 
     prompt.push_str("## Instructions\n");
-    prompt.push_str("The file metadata above (exports, imports, dependencies, LOC) is from sidecar analysis.\n");
-    prompt.push_str("Use it to understand the codebase structure WITHOUT reading source files.\n\n");
-    prompt.push_str("1. Study the metadata above to understand which files are relevant and how they connect\n");
+    prompt.push_str(
+        "The file metadata above (exports, imports, dependencies, LOC) is from sidecar analysis.\n",
+    );
+    prompt
+        .push_str("Use it to understand the codebase structure WITHOUT reading source files.\n\n");
+    prompt.push_str(
+        "1. Study the metadata above to understand which files are relevant and how they connect\n",
+    );
     prompt.push_str("2. Do NOT read source files just to explore — the metadata already tells you what each file exports and imports\n");
     prompt.push_str("3. ONLY read a source file when you are ready to edit it\n");
     prompt.push_str("4. Make minimal changes to fix the issue\n");
@@ -66,10 +100,31 @@ pub fn build_prompt(
     prompt
 }
 
+/// Render one `ResolvedReference` as it appears in the "Relevant Files"
+/// section: a heading plus its exports/imports/dependencies/loc, omitting
+/// any field that's empty.
+fn render_resolved_reference(r: &ResolvedReference) -> String {
+    let mut out = format!("### {} (match: {})\n", r.file_path, r.match_reason);
+    if !r.exports.is_empty() {
+        out.push_str(&format!("  exports: [{}]\n", r.exports.join(", ")));
+    }
+    if !r.imports.is_empty() {
+        out.push_str(&format!("  imports: [{}]\n", r.imports.join(", ")));
+    }
+    if !r.dependencies.is_empty() {
+        out.push_str(&format!(
+            "  dependencies: [{}]\n",
+            r.dependencies.join(", ")
+        ));
+    }
+    out.push_str(&format!("  loc: {}\n\n", r.loc));
+    out
+}
+
 pub fn format_dry_run(
     issue: &Issue,
     resolved: &[ResolvedReference],
-    unresolved: &[String],
+    unresolved: &[UnresolvedReference],
     prompt: &str,
 ) -> String {
     let mut out = String::new();
@@ -134,7 +189,13 @@ mod tests {
 
     #[test]
     fn prompt_includes_issue_info() {
-        let prompt = build_prompt(&mock_issue(), &mock_issue_ref(), &[], &[]);
+        let prompt = build_prompt(
+            &mock_issue(),
+            &mock_issue_ref(),
+            &[],
+            &[],
+            &Manifest::default(),
+        );
         assert!(prompt.contains("#42"));
         assert!(prompt.contains("owner/repo"));
         assert!(prompt.contains("Fix login bug"));
@@ -151,7 +212,13 @@ mod tests {
             loc: 100,
             match_reason: "exact file path".to_string(),
         }];
-        let prompt = build_prompt(&mock_issue(), &mock_issue_ref(), &resolved, &[]);
+        let prompt = build_prompt(
+            &mock_issue(),
+            &mock_issue_ref(),
+            &resolved,
+            &[],
+            &Manifest::default(),
+        );
         assert!(prompt.contains("src/auth.ts"));
         assert!(prompt.contains("exports: [login]"));
         assert!(prompt.contains("imports: [express]"));
@@ -168,7 +235,13 @@ mod tests {
             loc: 100,
             match_reason: "exact file path".to_string(),
         }];
-        let prompt = build_prompt(&mock_issue(), &mock_issue_ref(), &resolved, &[]);
+        let prompt = build_prompt(
+            &mock_issue(),
+            &mock_issue_ref(),
+            &resolved,
+            &[],
+            &Manifest::default(),
+        );
         // Should NOT contain actual source code
         assert!(!prompt.contains("function login("));
         assert!(!prompt.contains("import express"));
@@ -176,20 +249,79 @@ mod tests {
 
     #[test]
     fn prompt_lists_unresolved() {
-        let unresolved = vec!["symbol: unknownFn".to_string()];
-        let prompt = build_prompt(&mock_issue(), &mock_issue_ref(), &[], &unresolved);
+        let unresolved = vec![UnresolvedReference {
+            kind: "symbol",
+            query: "unknownFn".to_string(),
+            suggestions: vec![],
+        }];
+        let prompt = build_prompt(
+            &mock_issue(),
+            &mock_issue_ref(),
+            &[],
+            &unresolved,
+            &Manifest::default(),
+        );
         assert!(prompt.contains("Unresolved References"));
         assert!(prompt.contains("symbol: unknownFn"));
     }
 
+    #[test]
+    fn prompt_renders_unresolved_suggestions() {
+        let unresolved = vec![UnresolvedReference {
+            kind: "symbol",
+            query: "getADRStat".to_string(),
+            suggestions: vec!["getADRStatus".to_string()],
+        }];
+        let prompt = build_prompt(
+            &mock_issue(),
+            &mock_issue_ref(),
+            &[],
+            &unresolved,
+            &Manifest::default(),
+        );
+        assert!(prompt.contains("did you mean: getADRStatus?"));
+    }
+
     #[test]
     fn prompt_has_instructions() {
-        let prompt = build_prompt(&mock_issue(), &mock_issue_ref(), &[], &[]);
+        let prompt = build_prompt(
+            &mock_issue(),
+            &mock_issue_ref(),
+            &[],
+            &[],
+            &Manifest::default(),
+        );
         assert!(prompt.contains("Instructions"));
         assert!(prompt.contains("ONLY read a source file when you are ready to edit it"));
         assert!(prompt.contains("minimal changes"));
     }
 
+    #[test]
+    fn prompt_elides_low_in_degree_files_over_budget() {
+        let mut resolved = Vec::new();
+        for i in 0..200 {
+            resolved.push(ResolvedReference {
+                file_path: format!("src/file_{i}.ts"),
+                exports: vec![format!("thing_{i}")],
+                imports: vec!["express".to_string(); 20],
+                dependencies: vec![],
+                loc: 100,
+                match_reason: "dependency fan-out".to_string(),
+            });
+        }
+        // Make the first file a hub every other file imports, so it must
+        // survive the budget cut even though it isn't first in the list.
+        let mut manifest = Manifest::default();
+        manifest.importers.insert(
+            "src/file_0.ts".to_string(),
+            (1..200).map(|i| format!("src/file_{i}.ts")).collect(),
+        );
+
+        let prompt = build_prompt(&mock_issue(), &mock_issue_ref(), &resolved, &[], &manifest);
+        assert!(prompt.contains("src/file_0.ts"));
+        assert!(prompt.contains("elided to fit the context budget"));
+    }
+
     #[test]
     fn dry_run_output_complete() {
         let resolved = vec![ResolvedReference {
@@ -200,8 +332,18 @@ mod tests {
             loc: 50,
             match_reason: "entry point".to_string(),
         }];
-        let unresolved = vec!["symbol: missing".to_string()];
-        let prompt = build_prompt(&mock_issue(), &mock_issue_ref(), &resolved, &unresolved);
+        let unresolved = vec![UnresolvedReference {
+            kind: "symbol",
+            query: "missing".to_string(),
+            suggestions: vec![],
+        }];
+        let prompt = build_prompt(
+            &mock_issue(),
+            &mock_issue_ref(),
+            &resolved,
+            &unresolved,
+            &Manifest::default(),
+        );
         let output = format_dry_run(&mock_issue(), &resolved, &unresolved, &prompt);
 
         assert!(output.contains("DRY RUN"));
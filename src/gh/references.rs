@@ -16,6 +16,40 @@ pub enum CodeReference {
         language: Option<String>,
         content: String,
     },
+    /// A backtick-wrapped path containing `*`/`**`/`?`, e.g. `` `src/auth/*.ts` ``
+    /// or `` `src/**/handlers.ts` `` — expanded against `manifest.files` by
+    /// [`resolve_references`] via [`glob_match`].
+    Glob {
+        pattern: String,
+    },
+    /// A backtick-wrapped `prefix:base` directive, e.g. `` `rootfilesin:src/auth` ``
+    /// or `` `path:src/auth` `` — only [`ScopePrefix`]'s fixed allowlist of
+    /// prefixes is ever recognized, so this never triggers arbitrary
+    /// pattern matching from untrusted issue text.
+    Scoped {
+        prefix: ScopePrefix,
+        base: String,
+    },
+}
+
+/// The closed allowlist of directive prefixes [`CodeReference::Scoped`]
+/// recognizes inside backticks — anything else falls through to normal
+/// path/symbol handling.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ScopePrefix {
+    /// `rootfilesin:<dir>` — files directly under `<dir>/`, no recursion.
+    RootFilesIn,
+    /// `path:<dir>` — every manifest file under the `<dir>/` subtree.
+    Path,
+}
+
+impl ScopePrefix {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ScopePrefix::RootFilesIn => "rootfilesin",
+            ScopePrefix::Path => "path",
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -28,6 +62,29 @@ pub struct ResolvedReference {
     pub match_reason: String,
 }
 
+/// A reference from the issue body that `resolve_references` couldn't match
+/// to anything in the manifest, with ranked "did you mean" `suggestions`
+/// drawn from `manifest.export_index`/`manifest.files` via
+/// [`crate::levenshtein::levenshtein_distance`] — lets a prompt recover
+/// from a small typo (`getADRStat` vs `getADRStatus`)
+/// instead of just reporting a dead end.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnresolvedReference {
+    pub kind: &'static str,
+    pub query: String,
+    pub suggestions: Vec<String>,
+}
+
+impl std::fmt::Display for UnresolvedReference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.kind, self.query)?;
+        if !self.suggestions.is_empty() {
+            write!(f, " (did you mean: {}?)", self.suggestions.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
 const COMMON_WORDS: &[&str] = &[
     "the",
     "and",
@@ -133,16 +190,63 @@ pub fn extract_references(body: &str) -> Vec<CodeReference> {
     extract_file_paths(body, &mut refs);
     extract_code_blocks(body, &mut refs);
     extract_symbols(body, &mut refs);
+    extract_globs(body, &mut refs);
+    extract_scoped(body, &mut refs);
 
     refs.into_iter().collect()
 }
 
+/// Backtick-wrapped `rootfilesin:<dir>`/`path:<dir>` directives — the
+/// alternation is the entire allowlist, so an unrecognized prefix (or no
+/// prefix at all) simply never matches and falls through to
+/// `extract_file_paths`/`extract_globs` instead.
+fn extract_scoped(body: &str, refs: &mut HashSet<CodeReference>) {
+    let scoped_re = Regex::new(r"`(rootfilesin|path):([^`\s]+)`").unwrap();
+    for cap in scoped_re.captures_iter(body) {
+        let prefix = match &cap[1] {
+            "rootfilesin" => ScopePrefix::RootFilesIn,
+            "path" => ScopePrefix::Path,
+            _ => continue,
+        };
+        let base = cap[2].trim_end_matches('/').to_string();
+        refs.insert(CodeReference::Scoped { prefix, base });
+    }
+}
+
+/// Backtick-wrapped paths containing `*`/`**`/`?`, e.g. `` `src/auth/*.ts` ``
+/// — kept separate from `extract_file_paths` since a glob pattern isn't a
+/// literal path `resolve_file_path` could ever match.
+fn extract_globs(body: &str, refs: &mut HashSet<CodeReference>) {
+    let glob_re = Regex::new(r"`([^`\s]*[*?][^`\s]*)`").unwrap();
+    for cap in glob_re.captures_iter(body) {
+        let pattern = cap[1].to_string();
+        if !pattern.starts_with("http") && looks_like_file_path_glob(&pattern) {
+            refs.insert(CodeReference::Glob { pattern });
+        }
+    }
+}
+
+/// Same shape check as [`looks_like_file_path`], but for a pattern that may
+/// contain `*`/`**`/`?` — a path separator or a dotted extension is enough
+/// to tell `src/auth/*.ts` apart from stray `*emphasis*` markdown.
+fn looks_like_file_path_glob(s: &str) -> bool {
+    if s.contains('/') {
+        return true;
+    }
+    matches!(s.rfind('.'), Some(pos) if pos + 1 < s.len())
+}
+
 fn extract_file_paths(body: &str, refs: &mut HashSet<CodeReference>) {
     // Backtick-wrapped paths with optional line numbers: `src/foo/bar.ts`, `hooks.js:3035`
     let backtick_re = Regex::new(r"`([^`\s]+\.[a-zA-Z]{1,10}(?::\d+|#L\d+)?)`").unwrap();
     for cap in backtick_re.captures_iter(body) {
         let path = &cap[1];
-        if !path.starts_with("http") {
+        if !path.starts_with("http")
+            && !path.contains('*')
+            && !path.contains('?')
+            && !path.starts_with("rootfilesin:")
+            && !path.starts_with("path:")
+        {
             let (clean_path, line) = parse_line_number(path);
             if looks_like_file_path(&clean_path) {
                 refs.insert(CodeReference::FilePath {
@@ -262,14 +366,85 @@ fn parse_line_number(path: &str) -> (String, Option<u64>) {
     (path.to_string(), None)
 }
 
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Rank `candidates` by [`crate::levenshtein::levenshtein_distance`] to
+/// `query` (case-insensitive), keeping only those within `max(1, query.len()
+/// / 3)` edits and skipping the distance call entirely for a candidate
+/// whose length is already outside that threshold of `query`'s — the
+/// O(files) length check is much cheaper than the O(query × candidate) DP
+/// it's guarding.
+fn suggest_candidates<'a>(query: &str, candidates: impl Iterator<Item = &'a String>) -> Vec<String> {
+    let threshold = (query.chars().count() / 3).max(1);
+    let query_lower = query.to_lowercase();
+
+    let mut scored: Vec<(usize, &str)> = candidates
+        .filter(|c| c.chars().count().abs_diff(query.chars().count()) <= threshold)
+        .filter_map(|c| {
+            let dist = crate::levenshtein::levenshtein_distance(&query_lower, &c.to_lowercase());
+            (dist <= threshold).then_some((dist, c.as_str()))
+        })
+        .collect();
+
+    scored.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(b.1)));
+    scored
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, c)| c.to_string())
+        .collect()
+}
+
+/// Match `candidate` against `pattern` segment by segment (split on `/`):
+/// `*` matches any run of characters within a single segment, `**` matches
+/// zero or more whole segments, `?` matches exactly one character. No regex
+/// compilation per candidate — used by `resolve_references` to expand a
+/// `CodeReference::Glob` against every `manifest.files` key.
+pub fn glob_match(pattern: &str, candidate: &str) -> bool {
+    let pattern_segs: Vec<&str> = pattern.split('/').collect();
+    let candidate_segs: Vec<&str> = candidate.split('/').collect();
+    match_segments(&pattern_segs, &candidate_segs)
+}
+
+fn match_segments(pattern: &[&str], candidate: &[&str]) -> bool {
+    if pattern.is_empty() {
+        return candidate.is_empty();
+    }
+    if pattern[0] == "**" {
+        return (0..=candidate.len()).any(|n| match_segments(&pattern[1..], &candidate[n..]));
+    }
+    match candidate.split_first() {
+        Some((first, rest)) => {
+            match_segment(pattern[0], first) && match_segments(&pattern[1..], rest)
+        }
+        None => false,
+    }
+}
+
+fn match_segment(pattern: &str, candidate: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let c: Vec<char> = candidate.chars().collect();
+    match_chars(&p, &c)
+}
+
+fn match_chars(p: &[char], c: &[char]) -> bool {
+    if p.is_empty() {
+        return c.is_empty();
+    }
+    match p[0] {
+        '*' => (0..=c.len()).any(|n| match_chars(&p[1..], &c[n..])),
+        '?' => !c.is_empty() && match_chars(&p[1..], &c[1..]),
+        ch => !c.is_empty() && c[0] == ch && match_chars(&p[1..], &c[1..]),
+    }
+}
+
 const MAX_RESOLVED_FILES: usize = 20;
 
 pub fn resolve_references(
     refs: &[CodeReference],
     manifest: &Manifest,
-) -> (Vec<ResolvedReference>, Vec<String>) {
+) -> (Vec<ResolvedReference>, Vec<UnresolvedReference>) {
     let mut resolved: Vec<ResolvedReference> = Vec::new();
-    let mut unresolved: Vec<String> = Vec::new();
+    let mut unresolved: Vec<UnresolvedReference> = Vec::new();
     let mut seen_paths: HashSet<String> = HashSet::new();
 
     // Phase 1: Direct matches
@@ -283,7 +458,11 @@ pub fn resolve_references(
                         resolved.push(resolved_ref);
                     }
                 } else {
-                    unresolved.push(format!("file: {}", path));
+                    unresolved.push(UnresolvedReference {
+                        kind: "file",
+                        suggestions: suggest_candidates(path, manifest.files.keys()),
+                        query: path.clone(),
+                    });
                 }
             }
             CodeReference::Symbol { name } => {
@@ -301,7 +480,11 @@ pub fn resolve_references(
                         }
                     }
                 } else {
-                    unresolved.push(format!("symbol: {}", name));
+                    unresolved.push(UnresolvedReference {
+                        kind: "symbol",
+                        suggestions: suggest_candidates(name, manifest.export_index.keys()),
+                        query: name.clone(),
+                    });
                 }
             }
             CodeReference::CodeBlock { content, .. } => {
@@ -323,6 +506,85 @@ pub fn resolve_references(
                     }
                 }
             }
+            CodeReference::Glob { pattern } => {
+                let mut matches: Vec<&String> = manifest
+                    .files
+                    .keys()
+                    .filter(|path| glob_match(pattern, path))
+                    .collect();
+                matches.sort();
+
+                let mut matched_any = false;
+                for file_path in matches {
+                    if resolved.len() >= MAX_RESOLVED_FILES {
+                        break;
+                    }
+                    if !seen_paths.insert(file_path.clone()) {
+                        continue;
+                    }
+                    if let Some(entry) = manifest.files.get(file_path) {
+                        matched_any = true;
+                        resolved.push(ResolvedReference {
+                            file_path: file_path.clone(),
+                            exports: entry.exports.clone(),
+                            imports: entry.imports.clone(),
+                            dependencies: entry.dependencies.clone(),
+                            loc: entry.loc,
+                            match_reason: format!("glob match: {}", pattern),
+                        });
+                    }
+                }
+
+                if !matched_any {
+                    unresolved.push(UnresolvedReference {
+                        kind: "glob",
+                        query: pattern.clone(),
+                        suggestions: Vec::new(),
+                    });
+                }
+            }
+            CodeReference::Scoped { prefix, base } => {
+                let mut matches: Vec<&String> = manifest
+                    .files
+                    .keys()
+                    .filter(|path| match prefix {
+                        ScopePrefix::RootFilesIn => {
+                            path.rsplit_once('/').map(|(dir, _)| dir) == Some(base.as_str())
+                        }
+                        ScopePrefix::Path => path.starts_with(&format!("{base}/")),
+                    })
+                    .collect();
+                matches.sort();
+
+                let mut matched_any = false;
+                for file_path in matches {
+                    if resolved.len() >= MAX_RESOLVED_FILES {
+                        break;
+                    }
+                    if !seen_paths.insert(file_path.clone()) {
+                        continue;
+                    }
+                    if let Some(entry) = manifest.files.get(file_path) {
+                        matched_any = true;
+                        resolved.push(ResolvedReference {
+                            file_path: file_path.clone(),
+                            exports: entry.exports.clone(),
+                            imports: entry.imports.clone(),
+                            dependencies: entry.dependencies.clone(),
+                            loc: entry.loc,
+                            match_reason: format!("{}:{} scope", prefix.as_str(), base),
+                        });
+                    }
+                }
+
+                if !matched_any {
+                    unresolved.push(UnresolvedReference {
+                        kind: "scoped",
+                        query: format!("{}:{}", prefix.as_str(), base),
+                        suggestions: Vec::new(),
+                    });
+                }
+            }
         }
 
         if resolved.len() >= MAX_RESOLVED_FILES {
@@ -334,6 +596,10 @@ pub fn resolve_references(
     if resolved.len() < MAX_RESOLVED_FILES {
         let mut fanout: Vec<ResolvedReference> = Vec::new();
 
+        // Built once, O(files) total, instead of rescanning `manifest.files`
+        // for every already-resolved path below.
+        let dependents_index = manifest.dependents_index();
+
         let current_paths: Vec<String> = resolved.iter().map(|r| r.file_path.clone()).collect();
         for path in &current_paths {
             if let Some(entry) = manifest.files.get(path) {
@@ -357,21 +623,21 @@ pub fn resolve_references(
                 }
 
                 // Downstream dependents
-                for (other_path, other_entry) in &manifest.files {
+                for other_path in dependents_index.get(path).into_iter().flatten() {
                     if seen_paths.contains(other_path) {
                         continue;
                     }
-                    if other_entry.dependencies.iter().any(|d| d == path)
-                        && seen_paths.insert(other_path.clone())
-                    {
-                        fanout.push(ResolvedReference {
-                            file_path: other_path.clone(),
-                            exports: other_entry.exports.clone(),
-                            imports: other_entry.imports.clone(),
-                            dependencies: other_entry.dependencies.clone(),
-                            loc: other_entry.loc,
-                            match_reason: format!("depends on {}", path),
-                        });
+                    if let Some(other_entry) = manifest.files.get(other_path) {
+                        if seen_paths.insert(other_path.clone()) {
+                            fanout.push(ResolvedReference {
+                                file_path: other_path.clone(),
+                                exports: other_entry.exports.clone(),
+                                imports: other_entry.imports.clone(),
+                                dependencies: other_entry.dependencies.clone(),
+                                loc: other_entry.loc,
+                                match_reason: format!("depends on {}", path),
+                            });
+                        }
                     }
                 }
             }
@@ -553,6 +819,7 @@ mod tests {
             "src/auth/login.ts".to_string(),
             FileEntry {
                 exports: vec!["login".to_string(), "logout".to_string()],
+                export_lines: None,
                 imports: vec!["express".to_string()],
                 dependencies: vec!["src/auth/utils.ts".to_string()],
                 loc: 150,
@@ -562,6 +829,7 @@ mod tests {
             "src/auth/utils.ts".to_string(),
             FileEntry {
                 exports: vec!["hashPassword".to_string()],
+                export_lines: None,
                 imports: vec!["bcrypt".to_string()],
                 dependencies: vec![],
                 loc: 40,
@@ -571,6 +839,7 @@ mod tests {
             "src/index.ts".to_string(),
             FileEntry {
                 exports: vec!["app".to_string()],
+                export_lines: None,
                 imports: vec!["express".to_string()],
                 dependencies: vec!["src/auth/login.ts".to_string()],
                 loc: 30,
@@ -588,6 +857,7 @@ mod tests {
             generated: chrono::Utc::now(),
             files,
             export_index,
+            ..Manifest::default()
         }
     }
 
@@ -659,6 +929,174 @@ mod tests {
         assert!(paths.contains(&"src/index.ts"));
     }
 
+    #[test]
+    fn extract_scoped_directives() {
+        let body = "Look at `rootfilesin:src/auth` and also `path:src/auth` for the regression.";
+        let refs = extract_references(body);
+        let scoped: Vec<_> = refs
+            .iter()
+            .filter_map(|r| match r {
+                CodeReference::Scoped { prefix, base } => Some((prefix.clone(), base.as_str())),
+                _ => None,
+            })
+            .collect();
+        assert!(scoped.contains(&(ScopePrefix::RootFilesIn, "src/auth")));
+        assert!(scoped.contains(&(ScopePrefix::Path, "src/auth")));
+    }
+
+    #[test]
+    fn extract_scoped_ignores_unknown_prefix() {
+        let body = "Unrelated `foo:src/auth` directive.";
+        let refs = extract_references(body);
+        assert!(!refs
+            .iter()
+            .any(|r| matches!(r, CodeReference::Scoped { .. })));
+    }
+
+    #[test]
+    fn resolve_rootfilesin_matches_direct_children_only() {
+        let manifest = mock_manifest();
+        let refs = vec![CodeReference::Scoped {
+            prefix: ScopePrefix::RootFilesIn,
+            base: "src/auth".to_string(),
+        }];
+        let (resolved, unresolved) = resolve_references(&refs, &manifest);
+        let paths: Vec<&str> = resolved.iter().map(|r| r.file_path.as_str()).collect();
+        assert!(paths.contains(&"src/auth/login.ts"));
+        assert!(paths.contains(&"src/auth/utils.ts"));
+        assert!(!paths.contains(&"src/index.ts"));
+        assert!(unresolved.is_empty());
+    }
+
+    #[test]
+    fn resolve_path_matches_recursively() {
+        let manifest = mock_manifest();
+        let refs = vec![CodeReference::Scoped {
+            prefix: ScopePrefix::Path,
+            base: "src".to_string(),
+        }];
+        let (resolved, _) = resolve_references(&refs, &manifest);
+        let paths: Vec<&str> = resolved.iter().map(|r| r.file_path.as_str()).collect();
+        assert!(paths.contains(&"src/auth/login.ts"));
+        assert!(paths.contains(&"src/auth/utils.ts"));
+        assert!(paths.contains(&"src/index.ts"));
+    }
+
+    #[test]
+    fn resolve_scoped_with_no_matches_is_unresolved() {
+        let manifest = mock_manifest();
+        let refs = vec![CodeReference::Scoped {
+            prefix: ScopePrefix::Path,
+            base: "nowhere".to_string(),
+        }];
+        let (_, unresolved) = resolve_references(&refs, &manifest);
+        assert_eq!(unresolved.len(), 1);
+        assert_eq!(unresolved[0].kind, "scoped");
+    }
+
+    #[test]
+    fn extract_backtick_glob_patterns() {
+        let body = "The bug is somewhere in `src/auth/*.ts` or `src/**/handlers.ts`.";
+        let refs = extract_references(body);
+        let patterns: Vec<_> = refs
+            .iter()
+            .filter_map(|r| match r {
+                CodeReference::Glob { pattern } => Some(pattern.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert!(patterns.contains(&"src/auth/*.ts"));
+        assert!(patterns.contains(&"src/**/handlers.ts"));
+
+        // Globs shouldn't also show up as literal FilePath references.
+        let paths: Vec<_> = refs
+            .iter()
+            .filter_map(|r| match r {
+                CodeReference::FilePath { path, .. } => Some(path.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert!(!paths.contains(&"src/auth/*.ts"));
+    }
+
+    #[test]
+    fn glob_match_single_star_within_segment() {
+        assert!(glob_match("src/auth/*.ts", "src/auth/login.ts"));
+        assert!(!glob_match("src/auth/*.ts", "src/auth/sub/login.ts"));
+        assert!(!glob_match("src/auth/*.ts", "src/other/login.ts"));
+    }
+
+    #[test]
+    fn glob_match_double_star_spans_segments() {
+        assert!(glob_match("src/**/handlers.ts", "src/handlers.ts"));
+        assert!(glob_match("src/**/handlers.ts", "src/api/v1/handlers.ts"));
+        assert!(!glob_match("src/**/handlers.ts", "src/api/v1/other.ts"));
+    }
+
+    #[test]
+    fn glob_match_question_mark_single_char() {
+        assert!(glob_match("src/file?.ts", "src/file1.ts"));
+        assert!(!glob_match("src/file?.ts", "src/file12.ts"));
+    }
+
+    #[test]
+    fn resolve_glob_expands_matches() {
+        let manifest = mock_manifest();
+        let refs = vec![CodeReference::Glob {
+            pattern: "src/auth/*.ts".to_string(),
+        }];
+        let (resolved, unresolved) = resolve_references(&refs, &manifest);
+        let paths: Vec<&str> = resolved.iter().map(|r| r.file_path.as_str()).collect();
+        assert!(paths.contains(&"src/auth/login.ts"));
+        assert!(paths.contains(&"src/auth/utils.ts"));
+        assert!(!paths.contains(&"src/index.ts"));
+        assert!(unresolved.is_empty());
+        assert!(resolved
+            .iter()
+            .find(|r| r.file_path == "src/auth/login.ts")
+            .unwrap()
+            .match_reason
+            .contains("glob match"));
+    }
+
+    #[test]
+    fn resolve_glob_with_no_matches_is_unresolved() {
+        let manifest = mock_manifest();
+        let refs = vec![CodeReference::Glob {
+            pattern: "nowhere/*.rs".to_string(),
+        }];
+        let (_, unresolved) = resolve_references(&refs, &manifest);
+        assert_eq!(unresolved.len(), 1);
+        assert_eq!(unresolved[0].kind, "glob");
+    }
+
+    #[test]
+    fn unresolved_symbol_carries_suggestions() {
+        let manifest = mock_manifest();
+        let refs = vec![CodeReference::Symbol {
+            name: "loginn".to_string(),
+        }];
+        let (_, unresolved) = resolve_references(&refs, &manifest);
+        assert_eq!(unresolved.len(), 1);
+        assert_eq!(unresolved[0].kind, "symbol");
+        assert!(unresolved[0].suggestions.contains(&"login".to_string()));
+    }
+
+    #[test]
+    fn unresolved_file_path_carries_suggestions() {
+        let manifest = mock_manifest();
+        let refs = vec![CodeReference::FilePath {
+            path: "src/auth/loginn.ts".to_string(),
+            line: None,
+        }];
+        let (_, unresolved) = resolve_references(&refs, &manifest);
+        assert_eq!(unresolved.len(), 1);
+        assert_eq!(unresolved[0].kind, "file");
+        assert!(unresolved[0]
+            .suggestions
+            .contains(&"src/auth/login.ts".to_string()));
+    }
+
     #[test]
     fn cap_at_20_files() {
         let mut files = HashMap::new();
@@ -670,6 +1108,7 @@ mod tests {
                 path.clone(),
                 FileEntry {
                     exports: vec![export.clone()],
+                    export_lines: None,
                     imports: vec![],
                     dependencies: vec![],
                     loc: 10,
@@ -683,6 +1122,7 @@ mod tests {
             generated: chrono::Utc::now(),
             files,
             export_index,
+            ..Manifest::default()
         };
 
         let refs: Vec<CodeReference> = (0..30)
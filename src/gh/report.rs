@@ -1,11 +1,15 @@
-//! Comparison report for `fmm gh issue --compare` — markdown + JSON output.
+//! Comparison report for `fmm gh issue --compare` — markdown, JSON, HTML, and
+//! CSV output.
 
+use anyhow::{Context, Result};
 use colored::Colorize;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use crate::compare::stats::{MetricComparison, Significance};
+use crate::gh::batch::{csv_escape, html_escape, median};
 use crate::metrics::RunMetrics;
 
 /// Complete A/B comparison report for a single GitHub issue.
@@ -18,14 +22,128 @@ pub struct IssueComparisonReport {
     pub model: String,
     pub max_budget_usd: f64,
     pub max_turns: u32,
+    /// Name of the `--profile` that supplied `model`/`max_turns`/
+    /// `max_budget` (or `None` if none was passed), recorded so a comparison
+    /// run can be reproduced exactly.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
     pub timestamp: String,
     pub control: VariantResult,
     pub fmm: VariantResult,
     pub savings: Savings,
+    /// Mean/stddev/significance for the cost comparison across
+    /// `control.trials`/`fmm.trials`, backing the "± stddev" and
+    /// significance phrasing in `verdict` — degenerates to a single-point
+    /// estimate with [`Significance::InsufficientData`] when either side
+    /// only ran one trial.
+    pub cost_stats: MetricComparison,
+    /// Same as `cost_stats` but for total tokens (input + output).
+    pub total_tokens_stats: MetricComparison,
     pub verdict: String,
 }
 
-/// Metrics for one variant (control or fmm).
+/// One file a variant's working tree touched, combining its `git status
+/// --porcelain=v2` bucket with its `git diff --numstat` line counts (zero
+/// for an untracked file, which has nothing to diff against yet).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileChange {
+    pub path: String,
+    /// The pre-rename path, for a status-`2` (renamed/copied) entry.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub renamed_from: Option<String>,
+    pub insertions: u64,
+    pub deletions: u64,
+}
+
+/// Structured summary of what a variant changed in its working tree,
+/// decomposed the way starship's `git_status` segment decomposes a prompt —
+/// staged/modified/renamed/deleted/untracked buckets — plus the per-file
+/// diff stats needed to judge *how* surgical the change was. Built by
+/// [`crate::gh::workspace::summarize_changes`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChangeSummary {
+    pub files: Vec<FileChange>,
+    pub insertions: u64,
+    pub deletions: u64,
+    pub staged: u32,
+    pub modified: u32,
+    pub renamed: u32,
+    pub deleted: u32,
+    pub untracked: u32,
+}
+
+/// Raw metrics from a single run of a variant, kept alongside
+/// [`VariantResult`]'s mean so `print_summary`/`to_markdown` can render
+/// mean ± stddev and [`IssueComparisonReport::cost_stats`] /
+/// `total_tokens_stats` can run a significance test instead of trusting one
+/// noisy point estimate.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VariantTrial {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_creation_tokens: u64,
+    pub cost_usd: f64,
+    pub turns: u32,
+    pub duration_ms: u64,
+    pub tool_calls: u32,
+    pub success: bool,
+}
+
+/// Dollar rate per million tokens for one model, broken out by token kind
+/// the way providers bill them — cache reads and cache writes are
+/// typically much cheaper than a fresh input token, so folding them into a
+/// single "input" rate would understate how much a high-cache-hit run
+/// actually costs.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ModelRates {
+    pub input_per_million: f64,
+    pub output_per_million: f64,
+    pub cache_read_per_million: f64,
+    pub cache_creation_per_million: f64,
+}
+
+/// Per-model pricing, so a previously captured report can be re-priced
+/// against a different model's rates without re-running the agent — see
+/// [`IssueComparisonReport::recost`]. Models with no entry keep whatever
+/// `cost_usd` the run itself reported.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PricingTable {
+    pub rates: HashMap<String, ModelRates>,
+}
+
+impl PricingTable {
+    /// A table with a single model's rates, for the common case of pricing
+    /// one model.
+    pub fn with_rates(model: &str, rates: ModelRates) -> Self {
+        let mut table = Self::default();
+        table.rates.insert(model.to_string(), rates);
+        table
+    }
+
+    /// Recompute a cost in USD from token counts using `model`'s rates, or
+    /// `None` if this table has no entry for `model`.
+    fn cost_for(
+        &self,
+        model: &str,
+        input_tokens: u64,
+        output_tokens: u64,
+        cache_read_tokens: u64,
+        cache_creation_tokens: u64,
+    ) -> Option<f64> {
+        let rates = self.rates.get(model)?;
+        Some(
+            (input_tokens as f64 / 1_000_000.0) * rates.input_per_million
+                + (output_tokens as f64 / 1_000_000.0) * rates.output_per_million
+                + (cache_read_tokens as f64 / 1_000_000.0) * rates.cache_read_per_million
+                + (cache_creation_tokens as f64 / 1_000_000.0) * rates.cache_creation_per_million,
+        )
+    }
+}
+
+/// Metrics for one variant (control or fmm). When built from more than one
+/// trial, every scalar field is the mean across trials — see `trials` for
+/// the raw per-trial samples.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VariantResult {
     pub input_tokens: u64,
@@ -42,31 +160,107 @@ pub struct VariantResult {
     pub tools_by_name: HashMap<String, u32>,
     pub success: bool,
     pub error: Option<String>,
+    pub changes: ChangeSummary,
     #[serde(skip)]
     pub diff: String,
+    /// Per-trial raw samples this `VariantResult`'s scalar fields were
+    /// averaged from. Always non-empty; has one entry for a single-run
+    /// (`--runs 1`) report.
+    pub trials: Vec<VariantTrial>,
 }
 
 impl VariantResult {
-    fn from_metrics(m: &RunMetrics, diff: &str) -> Self {
+    /// Build a `VariantResult` from one or more trials of the same variant.
+    /// Scalar fields become the mean across trials; `tools_by_name`,
+    /// `files_read`, `success`, and `error` come from the last trial (the
+    /// one `diff`/`changes` were captured from), since those aren't
+    /// meaningfully averaged.
+    ///
+    /// When `pricing` has an entry for `model`, each trial's `cost_usd` is
+    /// recomputed from its token counts instead of trusting the run's
+    /// reported value — see [`PricingTable`].
+    fn from_trials(
+        trials: &[RunMetrics],
+        model: &str,
+        pricing: Option<&PricingTable>,
+        diff: &str,
+        changes: ChangeSummary,
+    ) -> Self {
+        let last = trials.last().expect("at least one trial");
         let files_changed = diff.lines().filter(|l| l.starts_with("diff --git")).count() as u32;
+        let n = trials.len() as f64;
+
+        let mean_u64 = |f: fn(&RunMetrics) -> u64| -> u64 {
+            (trials.iter().map(|m| f(m) as f64).sum::<f64>() / n).round() as u64
+        };
+        let mean_u32 = |f: fn(&RunMetrics) -> u32| -> u32 {
+            (trials.iter().map(|m| f(m) as f64).sum::<f64>() / n).round() as u32
+        };
+        let trial_cost = |m: &RunMetrics| -> f64 {
+            pricing
+                .and_then(|p| {
+                    p.cost_for(
+                        model,
+                        m.input_tokens,
+                        m.output_tokens,
+                        m.cache_read_tokens,
+                        m.cache_creation_tokens,
+                    )
+                })
+                .unwrap_or(m.cost_usd)
+        };
 
         Self {
-            input_tokens: m.input_tokens,
-            output_tokens: m.output_tokens,
-            cache_read_tokens: m.cache_read_tokens,
-            cache_creation_tokens: m.cache_creation_tokens,
-            cost_usd: m.cost_usd,
-            turns: m.turns,
-            duration_ms: m.duration_ms,
-            tool_calls: m.tool_calls,
-            read_calls: m.read_calls,
-            files_read: m.files_accessed.len() as u32,
+            input_tokens: mean_u64(|m| m.input_tokens),
+            output_tokens: mean_u64(|m| m.output_tokens),
+            cache_read_tokens: mean_u64(|m| m.cache_read_tokens),
+            cache_creation_tokens: mean_u64(|m| m.cache_creation_tokens),
+            cost_usd: trials.iter().map(trial_cost).sum::<f64>() / n,
+            turns: mean_u32(|m| m.turns),
+            duration_ms: mean_u64(|m| m.duration_ms),
+            tool_calls: mean_u32(|m| m.tool_calls),
+            read_calls: mean_u32(|m| m.read_calls),
+            files_read: last.files_accessed.len() as u32,
             files_changed,
-            tools_by_name: m.tools_by_name.clone(),
-            success: m.success,
-            error: m.error.clone(),
+            tools_by_name: last.tools_by_name.clone(),
+            success: last.success,
+            error: last.error.clone(),
+            changes,
             diff: diff.to_string(),
+            trials: trials
+                .iter()
+                .map(|m| VariantTrial {
+                    input_tokens: m.input_tokens,
+                    output_tokens: m.output_tokens,
+                    cache_read_tokens: m.cache_read_tokens,
+                    cache_creation_tokens: m.cache_creation_tokens,
+                    cost_usd: trial_cost(m),
+                    turns: m.turns,
+                    duration_ms: m.duration_ms,
+                    tool_calls: m.tool_calls,
+                    success: m.success,
+                })
+                .collect(),
+        }
+    }
+
+    /// Recompute every trial's `cost_usd` from `pricing`'s rates for
+    /// `model`, then refresh the mean `cost_usd` to match. A no-op for
+    /// models with no entry in `pricing`.
+    fn recost(&mut self, model: &str, pricing: &PricingTable) {
+        for trial in &mut self.trials {
+            if let Some(cost) = pricing.cost_for(
+                model,
+                trial.input_tokens,
+                trial.output_tokens,
+                trial.cache_read_tokens,
+                trial.cache_creation_tokens,
+            ) {
+                trial.cost_usd = cost;
+            }
         }
+        self.cost_usd =
+            self.trials.iter().map(|t| t.cost_usd).sum::<f64>() / self.trials.len() as f64;
     }
 }
 
@@ -91,18 +285,54 @@ pub struct ReportInput<'a> {
     pub model: &'a str,
     pub max_budget_usd: f64,
     pub max_turns: u32,
-    pub control_metrics: &'a RunMetrics,
-    pub fmm_metrics: &'a RunMetrics,
+    pub control_trials: &'a [RunMetrics],
+    pub fmm_trials: &'a [RunMetrics],
     pub control_diff: &'a str,
     pub fmm_diff: &'a str,
+    pub control_changes: ChangeSummary,
+    pub fmm_changes: ChangeSummary,
+    pub profile: Option<&'a str>,
+    /// Recompute `cost_usd` from token counts using these rates instead of
+    /// trusting the run's reported value — see [`PricingTable`]. `None`
+    /// keeps the reported cost.
+    pub pricing: Option<&'a PricingTable>,
 }
 
 impl IssueComparisonReport {
     pub fn new(input: ReportInput<'_>) -> Self {
-        let control = VariantResult::from_metrics(input.control_metrics, input.control_diff);
-        let fmm = VariantResult::from_metrics(input.fmm_metrics, input.fmm_diff);
+        let control = VariantResult::from_trials(
+            input.control_trials,
+            input.model,
+            input.pricing,
+            input.control_diff,
+            input.control_changes,
+        );
+        let fmm = VariantResult::from_trials(
+            input.fmm_trials,
+            input.model,
+            input.pricing,
+            input.fmm_diff,
+            input.fmm_changes,
+        );
         let savings = Self::calculate_savings(&control, &fmm);
-        let verdict = Self::generate_verdict(&savings);
+
+        let control_cost: Vec<f64> = control.trials.iter().map(|t| t.cost_usd).collect();
+        let fmm_cost: Vec<f64> = fmm.trials.iter().map(|t| t.cost_usd).collect();
+        let cost_stats = MetricComparison::new(&control_cost, &fmm_cost);
+
+        let control_tokens: Vec<f64> = input
+            .control_trials
+            .iter()
+            .map(|m| (m.input_tokens + m.output_tokens) as f64)
+            .collect();
+        let fmm_tokens: Vec<f64> = input
+            .fmm_trials
+            .iter()
+            .map(|m| (m.input_tokens + m.output_tokens) as f64)
+            .collect();
+        let total_tokens_stats = MetricComparison::new(&control_tokens, &fmm_tokens);
+
+        let verdict = Self::generate_verdict(&savings, &cost_stats);
 
         Self {
             issue_url: input.issue_url.to_string(),
@@ -112,10 +342,13 @@ impl IssueComparisonReport {
             model: input.model.to_string(),
             max_budget_usd: input.max_budget_usd,
             max_turns: input.max_turns,
+            profile: input.profile.map(|s| s.to_string()),
             timestamp: chrono::Utc::now().to_rfc3339(),
             control,
             fmm,
             savings,
+            cost_stats,
+            total_tokens_stats,
             verdict,
         }
     }
@@ -135,19 +368,49 @@ impl IssueComparisonReport {
         }
     }
 
-    fn generate_verdict(savings: &Savings) -> String {
-        if savings.total_tokens_pct > 0.0 {
-            format!(
-                "fmm reduced token usage by {:.0}% and cost by {:.0}%.",
-                savings.total_tokens_pct, savings.cost_pct
-            )
-        } else {
-            "fmm did not reduce token usage in this run.".to_string()
+    /// Phrase the savings verdict in terms of `cost_stats`'s significance
+    /// test rather than trusting a single noisy run: with fewer than two
+    /// trials per side there's no variance to test, so the verdict says so
+    /// instead of implying a real effect; otherwise it downgrades to "no
+    /// significant difference" when the control/fmm cost samples overlap.
+    fn generate_verdict(savings: &Savings, cost_stats: &MetricComparison) -> String {
+        if cost_stats.control.n < 2 || cost_stats.fmm.n < 2 {
+            return if savings.total_tokens_pct > 0.0 {
+                format!(
+                    "fmm reduced token usage by {:.0}% and cost by {:.0}% (insufficient trials for a significance test — re-run with --runs 2 or more).",
+                    savings.total_tokens_pct, savings.cost_pct
+                )
+            } else {
+                "fmm did not reduce token usage in this run (insufficient trials for a significance test — re-run with --runs 2 or more).".to_string()
+            };
+        }
+
+        match cost_stats.significance {
+            Significance::Significant if savings.total_tokens_pct > 0.0 => {
+                let stddev_pct = if cost_stats.control.mean > 0.0 {
+                    (cost_stats.fmm.stddev / cost_stats.control.mean) * 100.0
+                } else {
+                    0.0
+                };
+                format!(
+                    "fmm reduced token usage by {:.0}% and cost by {:.0}% ± {:.0}% (significant, p<0.05).",
+                    savings.total_tokens_pct, cost_stats.reduction_pct, stddev_pct
+                )
+            }
+            Significance::Significant => format!(
+                "fmm increased cost by {:.0}% in this run (significant, p<0.05).",
+                cost_stats.reduction_pct.abs()
+            ),
+            Significance::NotSignificant | Significance::InsufficientData => {
+                "fmm showed no significant difference in cost across trials.".to_string()
+            }
         }
     }
 
-    /// Print colorized summary to terminal.
-    pub fn print_summary(&self) {
+    /// Print colorized summary to terminal. `baseline`, if present, appends
+    /// how much this run's cost savings moved since a baseline was captured
+    /// with [`IssueComparisonReport::save_baseline`].
+    pub fn print_summary(&self, baseline: Option<&BaselineDelta>) {
         println!("\n{}", "═".repeat(64).dimmed());
         println!("{}", "fmm gh issue --compare Results".green().bold());
         println!("{}", "═".repeat(64).dimmed());
@@ -166,6 +429,9 @@ impl IssueComparisonReport {
             self.max_budget_usd,
             self.max_turns
         );
+        if let Some(ref profile) = self.profile {
+            println!("  {} {}", "Profile:".bold(), profile);
+        }
 
         println!(
             "\n  {:20} {:>10} {:>10} {:>10} {:>8}",
@@ -244,6 +510,29 @@ impl IssueComparisonReport {
         }
 
         println!("\n  {} {}", "Verdict:".bold(), self.verdict.green());
+
+        if self.cost_stats.control.n > 1 || self.cost_stats.fmm.n > 1 {
+            println!(
+                "  {} control ${:.4} ± ${:.4} (n={}) -> fmm ${:.4} ± ${:.4} (n={}) — {}",
+                "Cost stats:".bold(),
+                self.cost_stats.control.mean,
+                self.cost_stats.control.stddev,
+                self.cost_stats.control.n,
+                self.cost_stats.fmm.mean,
+                self.cost_stats.fmm.stddev,
+                self.cost_stats.fmm.n,
+                self.cost_stats.verdict(),
+            );
+        }
+
+        if let Some(delta) = baseline {
+            println!(
+                "  {} {:+.1}pp vs baseline ({})",
+                "vs baseline:".bold(),
+                delta.cost_reduction_delta_pct,
+                delta.baseline_timestamp,
+            );
+        }
     }
 
     fn print_row(&self, label: &str, control: u64, fmm: u64, savings_pct: f64) {
@@ -325,8 +614,9 @@ impl IssueComparisonReport {
         );
     }
 
-    /// Generate markdown report string.
-    pub fn to_markdown(&self) -> String {
+    /// Generate markdown report string. `baseline`, if present, adds a "vs
+    /// baseline" line (see [`IssueComparisonReport::compare_to_baseline`]).
+    pub fn to_markdown(&self, baseline: Option<&BaselineDelta>) -> String {
         let mut md = String::new();
 
         md.push_str("## fmm gh issue --compare Results\n\n");
@@ -433,6 +723,26 @@ impl IssueComparisonReport {
 
         md.push_str(&format!("**Verdict:** {}\n", self.verdict));
 
+        if self.cost_stats.control.n > 1 || self.cost_stats.fmm.n > 1 {
+            md.push_str(&format!(
+                "\n**Cost stats:** control ${:.4} ± ${:.4} (n={}) → fmm ${:.4} ± ${:.4} (n={}) — {}\n",
+                self.cost_stats.control.mean,
+                self.cost_stats.control.stddev,
+                self.cost_stats.control.n,
+                self.cost_stats.fmm.mean,
+                self.cost_stats.fmm.stddev,
+                self.cost_stats.fmm.n,
+                self.cost_stats.verdict(),
+            ));
+        }
+
+        if let Some(delta) = baseline {
+            md.push_str(&format!(
+                "\n**vs baseline:** {:+.1}pp ({})\n",
+                delta.cost_reduction_delta_pct, delta.baseline_timestamp,
+            ));
+        }
+
         // Truncated diffs
         fn truncated_diff(diff: &str, max_lines: usize) -> String {
             let lines: Vec<&str> = diff.lines().collect();
@@ -460,8 +770,296 @@ impl IssueComparisonReport {
         md
     }
 
-    /// Save report as JSON and/or Markdown files.
-    pub fn save(&self, output_dir: &Path) -> anyhow::Result<Vec<String>> {
+    /// Self-contained HTML page (inline CSS, no external assets): the same
+    /// metric table, tool breakdown, and collapsible diff sections as
+    /// [`IssueComparisonReport::to_markdown`], styled so savings cells read
+    /// green (improvement) or red (regression) — e.g. for pasting into a
+    /// dashboard or PR comment preview without a markdown renderer.
+    pub fn to_html(&self, baseline: Option<&BaselineDelta>) -> String {
+        fn metric_row(label: &str, control: &str, fmm: &str, delta: &str, savings_pct: Option<f64>) -> String {
+            let savings_cell = match savings_pct {
+                Some(pct) if pct > 0.0 => format!("<td class=\"savings-pos\">{:.0}%</td>", pct),
+                Some(pct) if pct < 0.0 => format!("<td class=\"savings-neg\">{:.0}%</td>", pct),
+                Some(pct) => format!("<td>{:.0}%</td>", pct),
+                None => "<td>—</td>".to_string(),
+            };
+            format!(
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td>{}</tr>\n",
+                html_escape(label),
+                control,
+                fmm,
+                delta,
+                savings_cell
+            )
+        }
+
+        let mut rows = String::new();
+        rows.push_str(&metric_row(
+            "Input tokens",
+            &format_number(self.control.input_tokens),
+            &format_number(self.fmm.input_tokens),
+            &delta_str(self.control.input_tokens, self.fmm.input_tokens),
+            Some(self.savings.input_tokens_pct),
+        ));
+        rows.push_str(&metric_row(
+            "Output tokens",
+            &format_number(self.control.output_tokens),
+            &format_number(self.fmm.output_tokens),
+            &delta_str(self.control.output_tokens, self.fmm.output_tokens),
+            None,
+        ));
+        rows.push_str(&metric_row(
+            "Cache read tokens",
+            &format_number(self.control.cache_read_tokens),
+            &format_number(self.fmm.cache_read_tokens),
+            &delta_str_signed(self.control.cache_read_tokens, self.fmm.cache_read_tokens),
+            None,
+        ));
+        rows.push_str(&metric_row(
+            "Total cost",
+            &format!("${:.2}", self.control.cost_usd),
+            &format!("${:.2}", self.fmm.cost_usd),
+            &cost_delta_str(self.control.cost_usd, self.fmm.cost_usd),
+            Some(self.savings.cost_pct),
+        ));
+        rows.push_str(&metric_row(
+            "Turns",
+            &self.control.turns.to_string(),
+            &self.fmm.turns.to_string(),
+            &delta_str(self.control.turns as u64, self.fmm.turns as u64),
+            Some(self.savings.turns_pct),
+        ));
+        rows.push_str(&metric_row(
+            "Tool calls",
+            &self.control.tool_calls.to_string(),
+            &self.fmm.tool_calls.to_string(),
+            &delta_str(self.control.tool_calls as u64, self.fmm.tool_calls as u64),
+            Some(self.savings.tool_calls_pct),
+        ));
+        rows.push_str(&metric_row(
+            "Files read",
+            &self.control.files_read.to_string(),
+            &self.fmm.files_read.to_string(),
+            &delta_str(self.control.files_read as u64, self.fmm.files_read as u64),
+            Some(self.savings.files_read_pct),
+        ));
+        rows.push_str(&metric_row(
+            "Files changed",
+            &self.control.files_changed.to_string(),
+            &self.fmm.files_changed.to_string(),
+            &delta_str(
+                self.control.files_changed as u64,
+                self.fmm.files_changed as u64,
+            ),
+            None,
+        ));
+        rows.push_str(&metric_row(
+            "Duration",
+            &format!("{:.0}s", self.control.duration_ms as f64 / 1000.0),
+            &format!("{:.0}s", self.fmm.duration_ms as f64 / 1000.0),
+            &duration_delta_str(self.control.duration_ms, self.fmm.duration_ms),
+            Some(self.savings.duration_pct),
+        ));
+
+        let mut tool_rows = String::new();
+        if !self.control.tools_by_name.is_empty() || !self.fmm.tools_by_name.is_empty() {
+            let mut all_tools: Vec<&str> = self
+                .control
+                .tools_by_name
+                .keys()
+                .chain(self.fmm.tools_by_name.keys())
+                .map(|s| s.as_str())
+                .collect::<std::collections::HashSet<_>>()
+                .into_iter()
+                .collect();
+            all_tools.sort();
+            for tool in &all_tools {
+                let c = self.control.tools_by_name.get(*tool).copied().unwrap_or(0);
+                let f = self.fmm.tools_by_name.get(*tool).copied().unwrap_or(0);
+                tool_rows.push_str(&format!(
+                    "<tr><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                    html_escape(tool),
+                    c,
+                    f
+                ));
+            }
+        }
+        let tool_section = if tool_rows.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "<h2>Tool Breakdown</h2>\n<table>\n<tr><th>Tool</th><th>Control</th><th>FMM</th></tr>\n{}</table>\n",
+                tool_rows
+            )
+        };
+
+        let cost_stats_section = if self.cost_stats.control.n > 1 || self.cost_stats.fmm.n > 1 {
+            format!(
+                "<p><strong>Cost stats:</strong> control ${:.4} ± ${:.4} (n={}) → fmm ${:.4} ± ${:.4} (n={}) — {}</p>\n",
+                self.cost_stats.control.mean,
+                self.cost_stats.control.stddev,
+                self.cost_stats.control.n,
+                self.cost_stats.fmm.mean,
+                self.cost_stats.fmm.stddev,
+                self.cost_stats.fmm.n,
+                html_escape(&self.cost_stats.verdict()),
+            )
+        } else {
+            String::new()
+        };
+
+        let baseline_section = match baseline {
+            Some(delta) => format!(
+                "<p><strong>vs baseline:</strong> {:+.1}pp ({})</p>\n",
+                delta.cost_reduction_delta_pct,
+                html_escape(&delta.baseline_timestamp)
+            ),
+            None => String::new(),
+        };
+
+        fn truncated_diff_html(diff: &str, max_lines: usize) -> String {
+            let lines: Vec<&str> = diff.lines().collect();
+            if lines.is_empty() {
+                return "(no changes)".to_string();
+            }
+            let shown: Vec<&str> = lines.iter().take(max_lines).copied().collect();
+            let mut out = html_escape(&shown.join("\n"));
+            if lines.len() > max_lines {
+                out.push_str(&format!("\n\n... ({} more lines)", lines.len() - max_lines));
+            }
+            out
+        }
+
+        let diff_section = if !self.control.diff.is_empty() || !self.fmm.diff.is_empty() {
+            format!(
+                "<h2>Diffs</h2>\n\
+                 <details><summary>Control diff</summary>\n<pre>{}</pre></details>\n\
+                 <details><summary>FMM diff</summary>\n<pre>{}</pre></details>\n",
+                truncated_diff_html(&self.control.diff, 100),
+                truncated_diff_html(&self.fmm.diff, 100),
+            )
+        } else {
+            String::new()
+        };
+
+        format!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n\
+             <style>\n\
+             body {{ font-family: system-ui, sans-serif; max-width: 860px; margin: 2rem auto; padding: 0 1rem; }}\n\
+             table {{ border-collapse: collapse; width: 100%; margin-top: 1rem; }}\n\
+             th, td {{ border: 1px solid #ccc; padding: 0.4rem 0.8rem; text-align: left; }}\n\
+             th {{ background: #f5f5f5; }}\n\
+             .savings-pos {{ color: #1a7f37; font-weight: bold; }}\n\
+             .savings-neg {{ color: #cf222e; font-weight: bold; }}\n\
+             pre {{ white-space: pre-wrap; background: #f6f8fa; padding: 0.75rem; border-radius: 4px; }}\n\
+             </style>\n</head>\n<body>\n\
+             <h1>fmm gh issue --compare Results</h1>\n\
+             <p><strong>Issue:</strong> {repo}#{issue_number} — {issue_title}</p>\n\
+             <p><strong>Model:</strong> {model} | <strong>Budget:</strong> ${budget:.2} | <strong>Max turns:</strong> {max_turns}</p>\n\
+             <table>\n<tr><th>Metric</th><th>Control</th><th>FMM</th><th>Delta</th><th>Savings</th></tr>\n{rows}</table>\n\
+             {tool_section}\
+             <p><strong>Verdict:</strong> {verdict}</p>\n\
+             {cost_stats_section}\
+             {baseline_section}\
+             {diff_section}\
+             </body>\n</html>\n",
+            title = html_escape(&format!(
+                "{}#{} — {}",
+                self.repo, self.issue_number, self.issue_title
+            )),
+            repo = html_escape(&self.repo),
+            issue_number = self.issue_number,
+            issue_title = html_escape(&self.issue_title),
+            model = html_escape(&self.model),
+            budget = self.max_budget_usd,
+            max_turns = self.max_turns,
+            rows = rows,
+            tool_section = tool_section,
+            verdict = html_escape(&self.verdict),
+            cost_stats_section = cost_stats_section,
+            baseline_section = baseline_section,
+            diff_section = diff_section,
+        )
+    }
+
+    /// CSV with one row per metric (control/fmm/delta/savings columns), for
+    /// spreadsheet analysis of a single issue's comparison.
+    pub fn to_csv(&self) -> String {
+        fn row(metric: &str, control: f64, fmm: f64, savings_pct: Option<f64>) -> String {
+            let delta = fmm - control;
+            match savings_pct {
+                Some(pct) => format!("{},{:.4},{:.4},{:.4},{:.2}\n", metric, control, fmm, delta, pct),
+                None => format!("{},{:.4},{:.4},{:.4},\n", metric, control, fmm, delta),
+            }
+        }
+
+        let mut csv = String::new();
+        csv.push_str("metric,control,fmm,delta,savings_pct\n");
+        csv.push_str(&row(
+            "Input tokens",
+            self.control.input_tokens as f64,
+            self.fmm.input_tokens as f64,
+            Some(self.savings.input_tokens_pct),
+        ));
+        csv.push_str(&row(
+            "Output tokens",
+            self.control.output_tokens as f64,
+            self.fmm.output_tokens as f64,
+            None,
+        ));
+        csv.push_str(&row(
+            "Cache read tokens",
+            self.control.cache_read_tokens as f64,
+            self.fmm.cache_read_tokens as f64,
+            None,
+        ));
+        csv.push_str(&row(
+            "Total cost",
+            self.control.cost_usd,
+            self.fmm.cost_usd,
+            Some(self.savings.cost_pct),
+        ));
+        csv.push_str(&row(
+            "Turns",
+            self.control.turns as f64,
+            self.fmm.turns as f64,
+            Some(self.savings.turns_pct),
+        ));
+        csv.push_str(&row(
+            "Tool calls",
+            self.control.tool_calls as f64,
+            self.fmm.tool_calls as f64,
+            Some(self.savings.tool_calls_pct),
+        ));
+        csv.push_str(&row(
+            "Files read",
+            self.control.files_read as f64,
+            self.fmm.files_read as f64,
+            Some(self.savings.files_read_pct),
+        ));
+        csv.push_str(&row(
+            "Files changed",
+            self.control.files_changed as f64,
+            self.fmm.files_changed as f64,
+            None,
+        ));
+        csv.push_str(&row(
+            "Duration (s)",
+            self.control.duration_ms as f64 / 1000.0,
+            self.fmm.duration_ms as f64 / 1000.0,
+            Some(self.savings.duration_pct),
+        ));
+        csv
+    }
+
+    /// Save report as JSON, Markdown, HTML, and CSV files. `baseline`, if
+    /// present, is rendered as an extra "vs baseline" line in the markdown
+    /// and HTML.
+    pub fn save(
+        &self,
+        output_dir: &Path,
+        baseline: Option<&BaselineDelta>,
+    ) -> anyhow::Result<Vec<String>> {
         fs::create_dir_all(output_dir)?;
         let mut saved = vec![];
 
@@ -477,9 +1075,17 @@ impl IssueComparisonReport {
         saved.push(json_path.display().to_string());
 
         let md_path = output_dir.join(format!("{}.md", base));
-        fs::write(&md_path, self.to_markdown())?;
+        fs::write(&md_path, self.to_markdown(baseline))?;
         saved.push(md_path.display().to_string());
 
+        let html_path = output_dir.join(format!("{}.html", base));
+        fs::write(&html_path, self.to_html(baseline))?;
+        saved.push(html_path.display().to_string());
+
+        let csv_path = output_dir.join(format!("{}.csv", base));
+        fs::write(&csv_path, self.to_csv())?;
+        saved.push(csv_path.display().to_string());
+
         if !self.control.diff.is_empty() {
             let diff_path = output_dir.join("control.diff");
             fs::write(&diff_path, &self.control.diff)?;
@@ -494,6 +1100,318 @@ impl IssueComparisonReport {
 
         Ok(saved)
     }
+
+    /// Save this report as a named baseline under `output_dir`, so a future
+    /// run can catch a regression in fmm's cost savings with
+    /// [`IssueComparisonReport::compare_to_baseline`] instead of eyeballing
+    /// two reports side by side.
+    pub fn save_baseline(&self, output_dir: &Path, name: &str) -> Result<PathBuf> {
+        fs::create_dir_all(output_dir)?;
+        let path = baseline_path(output_dir, name);
+        fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(path)
+    }
+
+    /// Load a baseline previously written by
+    /// [`IssueComparisonReport::save_baseline`].
+    pub fn load_baseline(output_dir: &Path, name: &str) -> Result<Self> {
+        let path = baseline_path(output_dir, name);
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("no baseline named '{}' at {}", name, path.display()))?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    /// Diff this report's cost savings against a previously captured
+    /// `baseline`, so CI can fail on a regression in *how much* fmm helps
+    /// rather than just an absolute reduction below threshold.
+    pub fn compare_to_baseline(&self, baseline: &IssueComparisonReport) -> BaselineDelta {
+        BaselineDelta {
+            baseline_timestamp: baseline.timestamp.clone(),
+            cost_reduction_delta_pct: self.savings.cost_pct - baseline.savings.cost_pct,
+        }
+    }
+
+    /// Re-price a previously loaded report against `pricing` instead of
+    /// re-running the agent — e.g. to see what this comparison would have
+    /// cost under a different model's rates. Recomputes both variants'
+    /// `cost_usd` (mean and per-trial), then `savings`, `cost_stats`, and
+    /// `verdict` to match.
+    pub fn recost(&mut self, pricing: &PricingTable) {
+        self.control.recost(&self.model, pricing);
+        self.fmm.recost(&self.model, pricing);
+        self.savings = Self::calculate_savings(&self.control, &self.fmm);
+
+        let control_cost: Vec<f64> = self.control.trials.iter().map(|t| t.cost_usd).collect();
+        let fmm_cost: Vec<f64> = self.fmm.trials.iter().map(|t| t.cost_usd).collect();
+        self.cost_stats = MetricComparison::new(&control_cost, &fmm_cost);
+
+        self.verdict = Self::generate_verdict(&self.savings, &self.cost_stats);
+    }
+}
+
+/// Change in fmm's cost-saving effectiveness between this report and a
+/// previously saved [`IssueComparisonReport::save_baseline`], so CI can
+/// catch a regression in *how much* fmm helps rather than just its absolute
+/// reduction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaselineDelta {
+    pub baseline_timestamp: String,
+    /// This run's cost reduction minus the baseline's. Negative means fmm
+    /// is saving less than it used to.
+    pub cost_reduction_delta_pct: f64,
+}
+
+impl BaselineDelta {
+    /// `None` if the cost reduction held within `max_regression_pct`
+    /// percentage points of the baseline, otherwise `Some(reason)` the
+    /// caller can report as a CI failure.
+    pub fn regression_reason(&self, max_regression_pct: f64) -> Option<String> {
+        if -self.cost_reduction_delta_pct > max_regression_pct {
+            Some(format!(
+                "fmm's cost savings regressed by {:.1}pp against baseline (allowed {:.1}pp)",
+                -self.cost_reduction_delta_pct, max_regression_pct
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+fn baseline_path(output_dir: &Path, name: &str) -> PathBuf {
+    output_dir.join(format!("baseline-{}.json", name))
+}
+
+/// Aggregate rollup across a batch of [`IssueComparisonReport`]s, e.g. a
+/// `fmm gh issue --compare` run over every issue in a label or milestone.
+/// `cost_weighted_pct` (the verdict's basis) divides the total dollars saved
+/// by the total control spend, so a few expensive issues aren't drowned out
+/// by many trivial ones the way an unweighted mean-of-percentages would.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SuiteComparisonReport {
+    pub issues: Vec<IssueComparisonReport>,
+    pub mean_cost_pct: f64,
+    pub median_cost_pct: f64,
+    pub mean_total_tokens_pct: f64,
+    pub median_total_tokens_pct: f64,
+    /// Total dollars saved divided by total control spend across all issues.
+    pub cost_weighted_pct: f64,
+    /// Fraction of issues where fmm reduced total token usage.
+    pub win_rate: f64,
+    pub total_cost_saved_usd: f64,
+    pub control_success_count: usize,
+    pub control_failure_count: usize,
+    pub fmm_success_count: usize,
+    pub fmm_failure_count: usize,
+    pub verdict: String,
+}
+
+impl SuiteComparisonReport {
+    pub fn new(issues: Vec<IssueComparisonReport>) -> Self {
+        let n = issues.len();
+
+        let total_control_cost: f64 = issues.iter().map(|r| r.control.cost_usd).sum();
+        let total_fmm_cost: f64 = issues.iter().map(|r| r.fmm.cost_usd).sum();
+        let total_cost_saved_usd = total_control_cost - total_fmm_cost;
+        let cost_weighted_pct = if total_control_cost > 0.0 {
+            (total_cost_saved_usd / total_control_cost) * 100.0
+        } else {
+            0.0
+        };
+
+        let mut cost_pcts: Vec<f64> = issues.iter().map(|r| r.savings.cost_pct).collect();
+        cost_pcts.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let mean_cost_pct = if n > 0 {
+            cost_pcts.iter().sum::<f64>() / n as f64
+        } else {
+            0.0
+        };
+        let median_cost_pct = median(&cost_pcts);
+
+        let mut token_pcts: Vec<f64> = issues.iter().map(|r| r.savings.total_tokens_pct).collect();
+        token_pcts.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let mean_total_tokens_pct = if n > 0 {
+            token_pcts.iter().sum::<f64>() / n as f64
+        } else {
+            0.0
+        };
+        let median_total_tokens_pct = median(&token_pcts);
+
+        let win_rate = if n > 0 {
+            issues
+                .iter()
+                .filter(|r| r.savings.total_tokens_pct > 0.0)
+                .count() as f64
+                / n as f64
+        } else {
+            0.0
+        };
+
+        let control_success_count = issues.iter().filter(|r| r.control.success).count();
+        let fmm_success_count = issues.iter().filter(|r| r.fmm.success).count();
+
+        let verdict = Self::generate_verdict(n, cost_weighted_pct, win_rate);
+
+        Self {
+            control_success_count,
+            control_failure_count: n - control_success_count,
+            fmm_success_count,
+            fmm_failure_count: n - fmm_success_count,
+            issues,
+            mean_cost_pct,
+            median_cost_pct,
+            mean_total_tokens_pct,
+            median_total_tokens_pct,
+            cost_weighted_pct,
+            win_rate,
+            total_cost_saved_usd,
+            verdict,
+        }
+    }
+
+    fn generate_verdict(n: usize, cost_weighted_pct: f64, win_rate: f64) -> String {
+        if n == 0 {
+            return "No issues in this suite.".to_string();
+        }
+        format!(
+            "Across {} issue{}, fmm reduced cost by {:.0}% (cost-weighted) and won on {:.0}% of issues.",
+            n,
+            if n == 1 { "" } else { "s" },
+            cost_weighted_pct,
+            win_rate * 100.0,
+        )
+    }
+
+    /// Print colorized summary to terminal.
+    pub fn print_summary(&self) {
+        println!("\n{}", "═".repeat(64).dimmed());
+        println!("{}", "fmm gh issue --compare Suite Results".green().bold());
+        println!("{}", "═".repeat(64).dimmed());
+
+        println!("\n  {} {}", "Issues:".bold(), self.issues.len());
+        println!(
+            "  {} mean {:.0}% | median {:.0}% | cost-weighted {:.0}%",
+            "Cost savings:".bold(),
+            self.mean_cost_pct,
+            self.median_cost_pct,
+            self.cost_weighted_pct,
+        );
+        println!(
+            "  {} mean {:.0}% | median {:.0}%",
+            "Token savings:".bold(),
+            self.mean_total_tokens_pct,
+            self.median_total_tokens_pct,
+        );
+        println!("  {} {:.0}%", "Win rate:".bold(), self.win_rate * 100.0);
+        println!(
+            "  {} ${:.2}",
+            "Total cost saved:".bold(),
+            self.total_cost_saved_usd
+        );
+        println!(
+            "  {} control {}/{} succeeded | fmm {}/{} succeeded",
+            "Runs:".bold(),
+            self.control_success_count,
+            self.issues.len(),
+            self.fmm_success_count,
+            self.issues.len(),
+        );
+
+        println!("\n  {} {}", "Verdict:".bold(), self.verdict.green());
+    }
+
+    /// Generate markdown report string: an aggregate header row followed by
+    /// a per-issue table.
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::new();
+
+        md.push_str("## fmm gh issue --compare Suite Results\n\n");
+        md.push_str(
+            "| Issues | Mean cost savings | Median cost savings | Cost-weighted | Mean token savings | Median token savings | Win rate | Total cost saved |\n",
+        );
+        md.push_str(
+            "|--------|--------------------|----------------------|----------------|---------------------|------------------------|----------|-------------------|\n",
+        );
+        md.push_str(&format!(
+            "| {} | {:.0}% | {:.0}% | {:.0}% | {:.0}% | {:.0}% | {:.0}% | ${:.2} |\n\n",
+            self.issues.len(),
+            self.mean_cost_pct,
+            self.median_cost_pct,
+            self.cost_weighted_pct,
+            self.mean_total_tokens_pct,
+            self.median_total_tokens_pct,
+            self.win_rate * 100.0,
+            self.total_cost_saved_usd,
+        ));
+
+        md.push_str(&format!("**Verdict:** {}\n\n", self.verdict));
+
+        md.push_str("| Issue | Cost savings | Token savings | Verdict |\n");
+        md.push_str("|-------|---------------|----------------|---------|\n");
+        for issue in &self.issues {
+            md.push_str(&format!(
+                "| {}#{} | {:.0}% | {:.0}% | {} |\n",
+                issue.repo,
+                issue.issue_number,
+                issue.savings.cost_pct,
+                issue.savings.total_tokens_pct,
+                issue.verdict,
+            ));
+        }
+
+        md
+    }
+
+    /// CSV with one row per issue (control/fmm cost, cost savings, token
+    /// savings, verdict), for spreadsheet analysis across a whole suite.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::new();
+        csv.push_str(
+            "issue_url,repo,issue_number,control_cost_usd,fmm_cost_usd,cost_pct,total_tokens_pct,turns_pct,tool_calls_pct,files_read_pct,duration_pct,verdict\n",
+        );
+        for issue in &self.issues {
+            csv.push_str(&format!(
+                "{},{},{},{:.4},{:.4},{:.2},{:.2},{:.2},{:.2},{:.2},{:.2},{}\n",
+                csv_escape(&issue.issue_url),
+                csv_escape(&issue.repo),
+                issue.issue_number,
+                issue.control.cost_usd,
+                issue.fmm.cost_usd,
+                issue.savings.cost_pct,
+                issue.savings.total_tokens_pct,
+                issue.savings.turns_pct,
+                issue.savings.tool_calls_pct,
+                issue.savings.files_read_pct,
+                issue.savings.duration_pct,
+                csv_escape(&issue.verdict),
+            ));
+        }
+        csv
+    }
+
+    /// Save `suite-summary.{json,md,csv}` plus every issue's own report
+    /// files under `output_dir`.
+    pub fn save(&self, output_dir: &Path) -> anyhow::Result<Vec<String>> {
+        fs::create_dir_all(output_dir)?;
+        let mut saved = vec![];
+
+        let json_path = output_dir.join("suite-summary.json");
+        fs::write(&json_path, serde_json::to_string_pretty(self)?)?;
+        saved.push(json_path.display().to_string());
+
+        let md_path = output_dir.join("suite-summary.md");
+        fs::write(&md_path, self.to_markdown())?;
+        saved.push(md_path.display().to_string());
+
+        let csv_path = output_dir.join("suite-summary.csv");
+        fs::write(&csv_path, self.to_csv())?;
+        saved.push(csv_path.display().to_string());
+
+        for issue in &self.issues {
+            saved.extend(issue.save(output_dir, None)?);
+        }
+
+        Ok(saved)
+    }
 }
 
 fn reduction_pct(control: f64, fmm: f64) -> f64 {
@@ -594,10 +1512,14 @@ mod tests {
             model: "sonnet",
             max_budget_usd: 5.0,
             max_turns: 30,
-            control_metrics: &control,
-            fmm_metrics: &fmm,
+            control_trials: &[control],
+            fmm_trials: &[fmm],
             control_diff: "",
             fmm_diff: "",
+            control_changes: ChangeSummary::default(),
+            fmm_changes: ChangeSummary::default(),
+            profile: None,
+            pricing: None,
         });
 
         assert!(report.savings.input_tokens_pct > 70.0);
@@ -619,19 +1541,84 @@ mod tests {
             model: "sonnet",
             max_budget_usd: 5.0,
             max_turns: 30,
-            control_metrics: &control,
-            fmm_metrics: &fmm,
+            control_trials: &[control],
+            fmm_trials: &[fmm],
             control_diff: "",
             fmm_diff: "",
+            control_changes: ChangeSummary::default(),
+            fmm_changes: ChangeSummary::default(),
+            profile: None,
+            pricing: None,
         });
 
-        let md = report.to_markdown();
+        let md = report.to_markdown(None);
         assert!(md.contains("fmm gh issue --compare"));
         assert!(md.contains("o/r#42"));
         assert!(md.contains("SQL injection"));
         assert!(md.contains("Verdict"));
     }
 
+    #[test]
+    fn report_html_escapes_and_colors_savings() {
+        let control = make_metrics(1000, 500, 0.05, 3, 10);
+        let fmm = make_metrics(500, 400, 0.02, 2, 4);
+
+        let report = IssueComparisonReport::new(ReportInput {
+            issue_url: "https://github.com/o/r/issues/42",
+            issue_title: "<script>alert(1)</script>",
+            issue_number: 42,
+            repo: "o/r",
+            model: "sonnet",
+            max_budget_usd: 5.0,
+            max_turns: 30,
+            control_trials: &[control],
+            fmm_trials: &[fmm],
+            control_diff: "",
+            fmm_diff: "",
+            control_changes: ChangeSummary::default(),
+            fmm_changes: ChangeSummary::default(),
+            profile: None,
+            pricing: None,
+        });
+
+        let html = report.to_html(None);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("o/r#42"));
+        assert!(!html.contains("<script>alert(1)</script>"));
+        assert!(html.contains("&lt;script&gt;"));
+        assert!(html.contains("savings-pos"));
+    }
+
+    #[test]
+    fn report_csv_has_one_row_per_metric() {
+        let control = make_metrics(1000, 500, 0.05, 3, 10);
+        let fmm = make_metrics(500, 400, 0.02, 2, 4);
+
+        let report = IssueComparisonReport::new(ReportInput {
+            issue_url: "https://github.com/o/r/issues/1",
+            issue_title: "Bug",
+            issue_number: 1,
+            repo: "o/r",
+            model: "sonnet",
+            max_budget_usd: 5.0,
+            max_turns: 30,
+            control_trials: &[control],
+            fmm_trials: &[fmm],
+            control_diff: "",
+            fmm_diff: "",
+            control_changes: ChangeSummary::default(),
+            fmm_changes: ChangeSummary::default(),
+            profile: None,
+            pricing: None,
+        });
+
+        let csv = report.to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "metric,control,fmm,delta,savings_pct");
+        assert_eq!(lines.len(), 10); // header + 9 metric rows
+        assert!(lines[1].starts_with("Input tokens,"));
+    }
+
     #[test]
     fn report_json_roundtrip() {
         let control = make_metrics(1000, 500, 0.05, 3, 10);
@@ -645,10 +1632,14 @@ mod tests {
             model: "sonnet",
             max_budget_usd: 5.0,
             max_turns: 30,
-            control_metrics: &control,
-            fmm_metrics: &fmm,
+            control_trials: &[control],
+            fmm_trials: &[fmm],
             control_diff: "",
             fmm_diff: "",
+            control_changes: ChangeSummary::default(),
+            fmm_changes: ChangeSummary::default(),
+            profile: None,
+            pricing: None,
         });
 
         let json = serde_json::to_string(&report).unwrap();
@@ -658,6 +1649,62 @@ mod tests {
         assert_eq!(deserialized.fmm.input_tokens, 500);
     }
 
+    #[test]
+    fn report_carries_each_variants_change_summary() {
+        let control = make_metrics(1000, 500, 0.05, 3, 10);
+        let fmm = make_metrics(500, 400, 0.02, 2, 4);
+
+        let report = IssueComparisonReport::new(ReportInput {
+            issue_url: "https://github.com/o/r/issues/1",
+            issue_title: "Bug",
+            issue_number: 1,
+            repo: "o/r",
+            model: "sonnet",
+            max_budget_usd: 5.0,
+            max_turns: 30,
+            control_trials: &[control],
+            fmm_trials: &[fmm],
+            control_diff: "",
+            fmm_diff: "",
+            control_changes: ChangeSummary {
+                files: vec![FileChange {
+                    path: "src/lib.rs".to_string(),
+                    renamed_from: None,
+                    insertions: 200,
+                    deletions: 30,
+                }],
+                insertions: 200,
+                deletions: 30,
+                staged: 1,
+                modified: 1,
+                renamed: 0,
+                deleted: 0,
+                untracked: 0,
+            },
+            fmm_changes: ChangeSummary {
+                files: vec![FileChange {
+                    path: "src/lib.rs".to_string(),
+                    renamed_from: None,
+                    insertions: 18,
+                    deletions: 5,
+                }],
+                insertions: 18,
+                deletions: 5,
+                staged: 1,
+                modified: 1,
+                renamed: 0,
+                deleted: 0,
+                untracked: 0,
+            },
+            profile: None,
+            pricing: None,
+        });
+
+        assert_eq!(report.control.changes.insertions, 200);
+        assert_eq!(report.fmm.changes.insertions, 18);
+        assert!(report.fmm.changes.insertions < report.control.changes.insertions);
+    }
+
     #[test]
     fn reduction_pct_zero_control() {
         assert_eq!(reduction_pct(0.0, 100.0), 0.0);
@@ -683,12 +1730,132 @@ mod tests {
             model: "sonnet",
             max_budget_usd: 5.0,
             max_turns: 30,
-            control_metrics: &control,
-            fmm_metrics: &fmm,
+            control_trials: &[control],
+            fmm_trials: &[fmm],
             control_diff: "",
             fmm_diff: "",
+            control_changes: ChangeSummary::default(),
+            fmm_changes: ChangeSummary::default(),
+            profile: None,
+            pricing: None,
         });
 
         assert!(report.verdict.contains("did not reduce"));
     }
+
+    #[test]
+    fn pricing_table_recomputes_cost_from_tokens() {
+        let control = make_metrics(1_000_000, 500_000, 9.99, 3, 10);
+        let fmm = make_metrics(200_000, 100_000, 9.99, 2, 4);
+        let pricing = PricingTable::with_rates(
+            "sonnet",
+            ModelRates {
+                input_per_million: 3.0,
+                output_per_million: 15.0,
+                cache_read_per_million: 0.3,
+                cache_creation_per_million: 3.75,
+            },
+        );
+
+        let report = IssueComparisonReport::new(ReportInput {
+            issue_url: "https://github.com/o/r/issues/1",
+            issue_title: "Bug",
+            issue_number: 1,
+            repo: "o/r",
+            model: "sonnet",
+            max_budget_usd: 5.0,
+            max_turns: 30,
+            control_trials: &[control],
+            fmm_trials: &[fmm],
+            control_diff: "",
+            fmm_diff: "",
+            control_changes: ChangeSummary::default(),
+            fmm_changes: ChangeSummary::default(),
+            profile: None,
+            pricing: Some(&pricing),
+        });
+
+        // 1M input @ $3/M + 500K output @ $15/M = $3.00 + $7.50.
+        assert!((report.control.cost_usd - 10.50).abs() < 1e-9);
+        // 200K input @ $3/M + 100K output @ $15/M = $0.60 + $1.50.
+        assert!((report.fmm.cost_usd - 2.10).abs() < 1e-9);
+    }
+
+    #[test]
+    fn pricing_table_falls_back_to_reported_cost_for_unknown_model() {
+        let control = make_metrics(1_000_000, 500_000, 9.99, 3, 10);
+        let fmm = make_metrics(200_000, 100_000, 4.44, 2, 4);
+        let pricing = PricingTable::with_rates(
+            "opus",
+            ModelRates {
+                input_per_million: 15.0,
+                output_per_million: 75.0,
+                cache_read_per_million: 1.5,
+                cache_creation_per_million: 18.75,
+            },
+        );
+
+        let report = IssueComparisonReport::new(ReportInput {
+            issue_url: "https://github.com/o/r/issues/1",
+            issue_title: "Bug",
+            issue_number: 1,
+            repo: "o/r",
+            model: "sonnet",
+            max_budget_usd: 5.0,
+            max_turns: 30,
+            control_trials: &[control],
+            fmm_trials: &[fmm],
+            control_diff: "",
+            fmm_diff: "",
+            control_changes: ChangeSummary::default(),
+            fmm_changes: ChangeSummary::default(),
+            profile: None,
+            pricing: Some(&pricing),
+        });
+
+        assert_eq!(report.control.cost_usd, 9.99);
+        assert_eq!(report.fmm.cost_usd, 4.44);
+    }
+
+    #[test]
+    fn recost_updates_cost_dependent_fields() {
+        let control = make_metrics(1_000_000, 500_000, 9.99, 3, 10);
+        let fmm = make_metrics(200_000, 100_000, 9.99, 2, 4);
+
+        let mut report = IssueComparisonReport::new(ReportInput {
+            issue_url: "https://github.com/o/r/issues/1",
+            issue_title: "Bug",
+            issue_number: 1,
+            repo: "o/r",
+            model: "sonnet",
+            max_budget_usd: 5.0,
+            max_turns: 30,
+            control_trials: &[control],
+            fmm_trials: &[fmm],
+            control_diff: "",
+            fmm_diff: "",
+            control_changes: ChangeSummary::default(),
+            fmm_changes: ChangeSummary::default(),
+            profile: None,
+            pricing: None,
+        });
+
+        assert_eq!(report.savings.cost_pct, 0.0);
+
+        let pricing = PricingTable::with_rates(
+            "sonnet",
+            ModelRates {
+                input_per_million: 3.0,
+                output_per_million: 15.0,
+                cache_read_per_million: 0.3,
+                cache_creation_per_million: 3.75,
+            },
+        );
+        report.recost(&pricing);
+
+        assert!((report.control.cost_usd - 10.50).abs() < 1e-9);
+        assert!((report.fmm.cost_usd - 2.10).abs() < 1e-9);
+        assert!(report.savings.cost_pct > 70.0);
+        assert!(report.verdict.contains("reduced"));
+    }
 }
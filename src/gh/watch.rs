@@ -0,0 +1,75 @@
+//! Incremental sidecar regeneration for long-running `gh` automation sessions.
+//!
+//! [`generate_sidecars`](super::generate_sidecars) shells out to `fmm generate`
+//! once, which re-walks and re-parses the whole repo. [`watch_sidecars`] instead
+//! builds one [`FileProcessor`] up front and keeps it alive for the life of the
+//! watch, so a long session only pays the parser/query-pack setup cost once and
+//! re-parses just the files that actually changed.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use notify::RecursiveMode;
+use notify_debouncer_full::{new_debouncer, DebounceEventResult};
+
+use crate::config::Config;
+use crate::extractor::FileProcessor;
+
+/// Run the initial `generate` pass over `repo_dir`, then keep watching it for
+/// file changes (debounced by `debounce_ms`) and re-run [`FileProcessor::update`]
+/// on each changed path, until `should_stop` returns `true`. Polls `should_stop`
+/// every `debounce_ms` so callers can wire up a ctrlc flag, a deadline, or a
+/// "the agent session finished" signal without this module knowing which.
+pub fn watch_sidecars(
+    repo_dir: &Path,
+    debounce_ms: u64,
+    mut should_stop: impl FnMut() -> bool,
+) -> Result<()> {
+    let config = Config::load_from_dir(repo_dir).unwrap_or_default();
+    let processor = FileProcessor::new(&config, repo_dir);
+
+    for file in walk_source_files(repo_dir) {
+        processor
+            .update(&file, false)
+            .with_context(|| format!("Failed to generate sidecar for {}", file.display()))?;
+    }
+
+    let (tx, rx) = std::sync::mpsc::channel::<Vec<PathBuf>>();
+    let mut debouncer = new_debouncer(
+        Duration::from_millis(debounce_ms),
+        None,
+        move |result: DebounceEventResult| {
+            if let Ok(events) = result {
+                let paths = events.into_iter().flat_map(|e| e.paths).collect();
+                let _ = tx.send(paths);
+            }
+        },
+    )?;
+    debouncer.watch(repo_dir, RecursiveMode::Recursive)?;
+
+    while !should_stop() {
+        if let Ok(paths) = rx.recv_timeout(Duration::from_millis(debounce_ms)) {
+            for path in paths {
+                if !path.is_file() {
+                    continue;
+                }
+                if let Err(e) = processor.update(&path, false) {
+                    log::warn!("Failed to update sidecar for {}: {}", path.display(), e);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn walk_source_files(root: &Path) -> Vec<PathBuf> {
+    ignore::WalkBuilder::new(root)
+        .standard_filters(true)
+        .build()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_some_and(|ft| ft.is_file()))
+        .map(|entry| entry.into_path())
+        .collect()
+}
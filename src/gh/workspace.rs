@@ -1,8 +1,12 @@
 use anyhow::{Context, Result};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use crate::config::GlobalConfig;
+use crate::gh::git_backend::GitBackend;
+use crate::gh::report::{ChangeSummary, FileChange};
+use crate::gh::workspace_error::WorkspaceError;
 
 pub fn resolve_workspace(
     global_config: &GlobalConfig,
@@ -19,108 +23,23 @@ pub fn resolve_workspace(
     Ok(workspace)
 }
 
+/// Clone or update a repo into `workspace_root` using the `GitBackend`
+/// selected by `global_config` (shell `git` by default, or `gix` when
+/// configured), returning the directory it was checked out to.
 pub fn clone_or_update(
+    global_config: &GlobalConfig,
     clone_url: &str,
     workspace_root: &Path,
     owner: &str,
     repo: &str,
+    depth: Option<u32>,
 ) -> Result<PathBuf> {
     let repo_dir = workspace_root.join(format!("{}-{}", owner, repo));
-
-    if repo_dir.join(".git").exists() {
-        // Update existing clone
-        let output = Command::new("git")
-            .args(["fetch", "origin"])
-            .current_dir(&repo_dir)
-            .output()
-            .context("Failed to git fetch")?;
-
-        if !output.status.success() {
-            anyhow::bail!(
-                "git fetch failed: {}",
-                String::from_utf8_lossy(&output.stderr)
-            );
-        }
-
-        // Determine default branch
-        let default_branch = detect_default_branch(&repo_dir)?;
-
-        let output = Command::new("git")
-            .args(["checkout", &default_branch])
-            .current_dir(&repo_dir)
-            .output()
-            .context("Failed to git checkout")?;
-
-        if !output.status.success() {
-            anyhow::bail!(
-                "git checkout {} failed: {}",
-                default_branch,
-                String::from_utf8_lossy(&output.stderr)
-            );
-        }
-
-        let output = Command::new("git")
-            .args(["pull", "--ff-only"])
-            .current_dir(&repo_dir)
-            .output()
-            .context("Failed to git pull")?;
-
-        if !output.status.success() {
-            anyhow::bail!(
-                "git pull failed: {}",
-                String::from_utf8_lossy(&output.stderr)
-            );
-        }
-    } else {
-        // Fresh clone (full history for branching)
-        let output = Command::new("git")
-            .args(["clone", clone_url, &repo_dir.to_string_lossy()])
-            .output()
-            .context("Failed to git clone")?;
-
-        if !output.status.success() {
-            anyhow::bail!(
-                "git clone failed: {}",
-                String::from_utf8_lossy(&output.stderr)
-            );
-        }
-    }
-
+    let backend = crate::gh::git_backend::backend_for(global_config);
+    backend.clone_or_update(clone_url, &repo_dir, depth)?;
     Ok(repo_dir)
 }
 
-fn detect_default_branch(repo_dir: &Path) -> Result<String> {
-    let output = Command::new("git")
-        .args(["symbolic-ref", "refs/remotes/origin/HEAD", "--short"])
-        .current_dir(repo_dir)
-        .output()
-        .context("Failed to detect default branch")?;
-
-    if output.status.success() {
-        let branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
-        // Strip "origin/" prefix
-        Ok(branch
-            .strip_prefix("origin/")
-            .unwrap_or(&branch)
-            .to_string())
-    } else {
-        // Fallback: try main, then master
-        for candidate in ["main", "master"] {
-            let output = Command::new("git")
-                .args(["rev-parse", "--verify", &format!("origin/{}", candidate)])
-                .current_dir(repo_dir)
-                .output();
-
-            if let Ok(out) = output {
-                if out.status.success() {
-                    return Ok(candidate.to_string());
-                }
-            }
-        }
-        Ok("main".to_string())
-    }
-}
-
 pub fn generate_sidecars(repo_dir: &Path) -> Result<()> {
     let fmm_binary = std::env::current_exe().context("Failed to get current executable")?;
 
@@ -132,28 +51,187 @@ pub fn generate_sidecars(repo_dir: &Path) -> Result<()> {
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("fmm generate failed: {}", stderr);
+        return Err(WorkspaceError::GenerateFailed {
+            stderr: stderr.to_string(),
+        }
+        .into());
     }
 
     Ok(())
 }
 
-pub fn create_branch(repo_dir: &Path, prefix: &str, issue_number: u64) -> Result<String> {
+pub fn create_branch(
+    global_config: &GlobalConfig,
+    repo_dir: &Path,
+    prefix: &str,
+    issue_number: u64,
+) -> Result<String> {
     let branch_name = format!("{}/fix-issue-{}", prefix, issue_number);
+    let backend = crate::gh::git_backend::backend_for(global_config);
+    backend.create_branch(repo_dir, &branch_name)?;
+    Ok(branch_name)
+}
 
-    let output = Command::new("git")
-        .args(["checkout", "-b", &branch_name])
+/// Like [`create_branch`], but checks the new branch out into its own
+/// worktree under `{owner}-{repo}.worktrees/fix-issue-{n}` (a sibling of
+/// `repo_dir`, named after it) instead of `repo_dir`'s own working
+/// directory. This is what lets several issues be worked on at once against
+/// one clone: each worktree has its own index and checked-out files but
+/// shares `repo_dir`'s object store, and [`generate_sidecars`] runs over
+/// the returned path exactly like it would over `repo_dir` itself.
+pub fn create_worktree(
+    global_config: &GlobalConfig,
+    repo_dir: &Path,
+    prefix: &str,
+    issue_number: u64,
+) -> Result<PathBuf> {
+    let branch_name = format!("{}/fix-issue-{}", prefix, issue_number);
+    let worktree_path = worktree_path_for(repo_dir, issue_number)?;
+
+    std::fs::create_dir_all(
+        worktree_path
+            .parent()
+            .context("worktree path has no parent")?,
+    )
+    .with_context(|| format!("Failed to create worktrees dir for {}", repo_dir.display()))?;
+
+    let backend = crate::gh::git_backend::backend_for(global_config);
+    backend.create_worktree(repo_dir, &worktree_path, &branch_name)?;
+    Ok(worktree_path)
+}
+
+/// Remove a worktree created by [`create_worktree`] once its issue is done,
+/// so its branch and worktree metadata can be reused or deleted.
+pub fn remove_worktree(
+    global_config: &GlobalConfig,
+    repo_dir: &Path,
+    worktree_path: &Path,
+) -> Result<()> {
+    let backend = crate::gh::git_backend::backend_for(global_config);
+    backend.remove_worktree(repo_dir, worktree_path)?;
+    Ok(())
+}
+
+/// Summarize what `repo_dir`'s working tree changed, for the `fmm gh issue
+/// --compare` report: `git status --porcelain=v2` gives the
+/// staged/modified/renamed/deleted/untracked buckets (first status letter is
+/// the index, second is the worktree, matching the XY codes `git-status(1)`
+/// documents), and `git diff --numstat` gives per-file insertion/deletion
+/// counts to fold in. Modeled on how starship's `git_status` segment reads
+/// the same porcelain output to decide which glyphs to show.
+pub fn summarize_changes(repo_dir: &Path) -> Result<ChangeSummary> {
+    let status = run_git_capture(repo_dir, &["status", "--porcelain=v2"])?;
+    let numstat = run_git_capture(repo_dir, &["diff", "--numstat"])?;
+
+    let mut insertions_by_path: HashMap<String, u64> = HashMap::new();
+    let mut deletions_by_path: HashMap<String, u64> = HashMap::new();
+    for line in numstat.lines() {
+        let mut fields = line.splitn(3, '\t');
+        let insertions: u64 = fields.next().unwrap_or("0").parse().unwrap_or(0);
+        let deletions: u64 = fields.next().unwrap_or("0").parse().unwrap_or(0);
+        let path = fields.next().unwrap_or("").to_string();
+        insertions_by_path.insert(path.clone(), insertions);
+        deletions_by_path.insert(path, deletions);
+    }
+
+    let mut files = Vec::new();
+    let mut summary = ChangeSummary::default();
+
+    for line in status.lines() {
+        let mut fields = line.split(' ');
+        match fields.next() {
+            Some("1") => {
+                let xy = fields.next().unwrap_or("..");
+                count_xy(xy, &mut summary);
+                // `1 XY sub mH mI mW hH hI <path>` — path is the 9th field.
+                let path = fields.nth(6).unwrap_or("").to_string();
+                files.push(file_change(path, None, &insertions_by_path, &deletions_by_path));
+            }
+            Some("2") => {
+                let xy = fields.next().unwrap_or("..");
+                count_xy(xy, &mut summary);
+                summary.renamed += 1;
+                // `2 XY sub mH mI mW hH hI score <path>\t<origPath>`.
+                let tail = fields.nth(7).unwrap_or("");
+                let mut paths = tail.splitn(2, '\t');
+                let path = paths.next().unwrap_or("").to_string();
+                let renamed_from = paths.next().map(|s| s.to_string());
+                files.push(file_change(path, renamed_from, &insertions_by_path, &deletions_by_path));
+            }
+            Some("?") => {
+                summary.untracked += 1;
+                let path = fields.next().unwrap_or("").to_string();
+                files.push(FileChange {
+                    path,
+                    renamed_from: None,
+                    insertions: 0,
+                    deletions: 0,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    summary.insertions = files.iter().map(|f| f.insertions).sum();
+    summary.deletions = files.iter().map(|f| f.deletions).sum();
+    summary.files = files;
+
+    Ok(summary)
+}
+
+/// Fold one `status --porcelain=v2` entry's `XY` code into `summary`'s
+/// staged/modified/deleted buckets — `X` (index) and `Y` (worktree) are
+/// counted independently since a file can be e.g. staged-modified and then
+/// further modified in the worktree.
+fn count_xy(xy: &str, summary: &mut ChangeSummary) {
+    let mut chars = xy.chars();
+    let index = chars.next().unwrap_or('.');
+    let worktree = chars.next().unwrap_or('.');
+
+    if index != '.' {
+        summary.staged += 1;
+    }
+    if worktree == 'M' {
+        summary.modified += 1;
+    }
+    if index == 'D' || worktree == 'D' {
+        summary.deleted += 1;
+    }
+}
+
+fn file_change(
+    path: String,
+    renamed_from: Option<String>,
+    insertions_by_path: &HashMap<String, u64>,
+    deletions_by_path: &HashMap<String, u64>,
+) -> FileChange {
+    FileChange {
+        insertions: insertions_by_path.get(&path).copied().unwrap_or(0),
+        deletions: deletions_by_path.get(&path).copied().unwrap_or(0),
+        renamed_from,
+        path,
+    }
+}
+
+fn run_git_capture(repo_dir: &Path, args: &[&str]) -> Result<String> {
+    let output = crate::exec::command("git")?
+        .args(args)
         .current_dir(repo_dir)
         .output()
-        .context("Failed to create branch")?;
+        .with_context(|| format!("Failed to run git {}", args.join(" ")))?;
 
-    if !output.status.success() {
-        anyhow::bail!(
-            "git checkout -b {} failed: {}",
-            branch_name,
-            String::from_utf8_lossy(&output.stderr)
-        );
-    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
 
-    Ok(branch_name)
+fn worktree_path_for(repo_dir: &Path, issue_number: u64) -> Result<PathBuf> {
+    let dir_name = repo_dir
+        .file_name()
+        .context("repo_dir has no file name")?
+        .to_string_lossy()
+        .to_string();
+    let worktrees_root = repo_dir
+        .parent()
+        .context("repo_dir has no parent")?
+        .join(format!("{dir_name}.worktrees"));
+    Ok(worktrees_root.join(format!("fix-issue-{}", issue_number)))
 }
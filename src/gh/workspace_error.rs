@@ -0,0 +1,127 @@
+//! Structured failures for the git operations in [`super::git_backend`] and
+//! [`super::workspace`].
+//!
+//! Every one of those operations used to collapse into `anyhow::bail!` with
+//! an interpolated stderr string, so a caller like `gh_issue_fix` couldn't
+//! tell a transient network blip from a conflict that needs a human. These
+//! variants carry the raw stderr (or equivalent) as context, so a caller can
+//! match on the kind of failure and decide: retry, fall back, or surface the
+//! message as-is.
+//!
+//! [`WorkspaceError`] implements [`std::error::Error`], so `anyhow`'s blanket
+//! `From<E: std::error::Error + Send + Sync + 'static>` already converts it —
+//! existing `?` usage inside a function returning `anyhow::Result` keeps
+//! working with no code changes there.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug)]
+pub enum WorkspaceError {
+    /// Git couldn't authenticate against the remote (missing/invalid credentials).
+    AuthFailed { stderr: String },
+    /// The remote host couldn't be reached (DNS, network, timeout).
+    RemoteUnreachable { stderr: String },
+    /// A fast-forward-only pull was rejected because the local branch has
+    /// diverged from its remote-tracking branch.
+    FastForwardRejected { branch: String, stderr: String },
+    /// `create_branch` was asked to create a branch that already exists.
+    BranchExists { branch: String, stderr: String },
+    /// The worktree has uncommitted changes that a checkout would clobber.
+    DirtyWorkingTree { stderr: String },
+    /// `path` isn't a git repository.
+    NotARepo { path: PathBuf },
+    /// `fmm generate` failed inside the cloned repo.
+    GenerateFailed { stderr: String },
+    /// A git failure that didn't match any of the above; still structured
+    /// enough to retain the raw message instead of losing it to a `bail!`.
+    Other { stderr: String },
+}
+
+impl fmt::Display for WorkspaceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WorkspaceError::AuthFailed { stderr } => {
+                write!(f, "git authentication failed: {stderr}")
+            }
+            WorkspaceError::RemoteUnreachable { stderr } => {
+                write!(f, "remote unreachable: {stderr}")
+            }
+            WorkspaceError::FastForwardRejected { branch, stderr } => {
+                write!(f, "cannot fast-forward {branch}, local and remote have diverged: {stderr}")
+            }
+            WorkspaceError::BranchExists { branch, stderr } => {
+                write!(f, "branch {branch} already exists: {stderr}")
+            }
+            WorkspaceError::DirtyWorkingTree { stderr } => {
+                write!(f, "working tree has uncommitted changes: {stderr}")
+            }
+            WorkspaceError::NotARepo { path } => {
+                write!(f, "{} is not a git repository", path.display())
+            }
+            WorkspaceError::GenerateFailed { stderr } => {
+                write!(f, "fmm generate failed: {stderr}")
+            }
+            WorkspaceError::Other { stderr } => write!(f, "git operation failed: {stderr}"),
+        }
+    }
+}
+
+impl std::error::Error for WorkspaceError {}
+
+/// Classify a git stderr/error message into the most specific
+/// [`WorkspaceError`] variant it matches, falling back to [`WorkspaceError::Other`].
+/// `repo_dir` is attached to [`WorkspaceError::NotARepo`] when the message
+/// indicates `repo_dir` isn't a git repository; `branch` is attached to the
+/// two variants that care which branch was involved. Pass `None` for either
+/// when the failing operation doesn't have that context.
+pub fn classify(repo_dir: &Path, message: &str, branch: Option<&str>) -> WorkspaceError {
+    let lower = message.to_lowercase();
+    let branch = || branch.unwrap_or("").to_string();
+
+    if lower.contains("not a git repository") {
+        WorkspaceError::NotARepo {
+            path: repo_dir.to_path_buf(),
+        }
+    } else if lower.contains("authentication failed")
+        || lower.contains("could not read username")
+        || lower.contains("could not read password")
+        || lower.contains("permission denied (publickey)")
+    {
+        WorkspaceError::AuthFailed {
+            stderr: message.to_string(),
+        }
+    } else if lower.contains("could not resolve host")
+        || lower.contains("could not connect")
+        || lower.contains("connection timed out")
+        || lower.contains("network is unreachable")
+    {
+        WorkspaceError::RemoteUnreachable {
+            stderr: message.to_string(),
+        }
+    } else if lower.contains("non-fast-forward")
+        || lower.contains("not possible to fast-forward")
+        || lower.contains("diverged")
+    {
+        WorkspaceError::FastForwardRejected {
+            branch: branch(),
+            stderr: message.to_string(),
+        }
+    } else if lower.contains("already exists") {
+        WorkspaceError::BranchExists {
+            branch: branch(),
+            stderr: message.to_string(),
+        }
+    } else if lower.contains("uncommitted changes")
+        || lower.contains("local changes")
+        || lower.contains("would be overwritten by checkout")
+    {
+        WorkspaceError::DirtyWorkingTree {
+            stderr: message.to_string(),
+        }
+    } else {
+        WorkspaceError::Other {
+            stderr: message.to_string(),
+        }
+    }
+}
@@ -0,0 +1,52 @@
+//! Shared Levenshtein edit-distance DP backing every "did you mean"
+//! suggestion in the crate ([`crate::manifest::Manifest::suggest_exports`],
+//! `gh::references`'s reference-path suggestions, and `cli`'s
+//! `--export`/`--imports`/`--depends-on`/subcommand suggestions) — one
+//! implementation instead of three drifting copies.
+
+/// Classic edit-distance DP between `a` and `b` (insertion, deletion, and
+/// substitution each cost 1), computed with a single reused row instead of
+/// a full matrix.
+pub fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let b_chars: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b_chars.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut diag = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b_chars.iter().enumerate() {
+            let prev_row_j = row[j + 1];
+            let cost = if ca == cb { 0 } else { 1 };
+            row[j + 1] = (row[j + 1] + 1).min(row[j] + 1).min(diag + cost);
+            diag = prev_row_j;
+        }
+    }
+
+    row[b_chars.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_for_identical_strings() {
+        assert_eq!(levenshtein_distance("abc", "abc"), 0);
+    }
+
+    #[test]
+    fn counts_a_transposition_as_two_substitutions() {
+        assert_eq!(levenshtein_distance("createStore", "createStoer"), 2);
+    }
+
+    #[test]
+    fn counts_insertions_and_deletions() {
+        assert_eq!(levenshtein_distance("foo", "foobar"), 3);
+        assert_eq!(levenshtein_distance("foobar", "foo"), 3);
+    }
+
+    #[test]
+    fn kitten_sitting_is_three() {
+        assert_eq!(levenshtein_distance("kitten", "sitting"), 3);
+    }
+}
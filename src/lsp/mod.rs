@@ -0,0 +1,436 @@
+//! Language Server Protocol front end over the same sidecar index that
+//! backs `crate::mcp`'s tools, so an editor gets workspace symbols,
+//! document symbols, go-to-definition, and find-references without a
+//! language-specific LSP implementation of its own.
+//!
+//! Speaks LSP's `Content-Length`-framed JSON-RPC over stdio — distinct from
+//! the MCP server's newline-delimited framing in [`crate::mcp`] — but reuses
+//! [`Manifest`] wholesale rather than building a second index.
+
+use crate::manifest::{ExportLines, Manifest};
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::io::{BufRead, Read, Write};
+use std::path::{Path, PathBuf};
+
+pub struct LspServer {
+    manifest: Option<Manifest>,
+    root: PathBuf,
+}
+
+impl Default for LspServer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LspServer {
+    pub fn new() -> Self {
+        Self::with_root(std::env::current_dir().unwrap_or_default())
+    }
+
+    /// Same as [`Self::new`] but with an explicit workspace root, so tests
+    /// can point the server at a fixture tree without `chdir`-ing.
+    pub fn with_root(root: PathBuf) -> Self {
+        let manifest = Manifest::load_from_sidecars(&root).ok();
+        Self { manifest, root }
+    }
+
+    pub fn run(&mut self) -> Result<()> {
+        let stdin = std::io::stdin();
+        let mut reader = stdin.lock();
+        let stdout = std::io::stdout();
+        let mut writer = stdout.lock();
+
+        while let Some(message) = read_message(&mut reader)? {
+            if message.get("method").and_then(Value::as_str) == Some("exit") {
+                break;
+            }
+            if let Some(response) = self.handle_message(&message) {
+                write_message(&mut writer, &response)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Dispatch one decoded LSP message. Returns `None` for notifications
+    /// (no `id`), which per the LSP spec never get a response.
+    fn handle_message(&mut self, message: &Value) -> Option<Value> {
+        let id = message.get("id").cloned()?;
+        let method = message.get("method").and_then(Value::as_str).unwrap_or("");
+        let params = message.get("params").cloned().unwrap_or(json!({}));
+
+        let result = match method {
+            "initialize" => Ok(self.handle_initialize()),
+            "shutdown" => Ok(Value::Null),
+            "workspace/symbol" => Ok(self.workspace_symbol(&params)),
+            "textDocument/documentSymbol" => Ok(self.document_symbol(&params)),
+            "textDocument/definition" => Ok(self.definition(&params)),
+            "textDocument/references" => Ok(self.references(&params)),
+            _ => Err(json!({
+                "code": -32601,
+                "message": format!("Method not found: {method}"),
+            })),
+        };
+
+        Some(match result {
+            Ok(value) => json!({"jsonrpc": "2.0", "id": id, "result": value}),
+            Err(error) => json!({"jsonrpc": "2.0", "id": id, "error": error}),
+        })
+    }
+
+    fn handle_initialize(&self) -> Value {
+        json!({
+            "capabilities": {
+                "workspaceSymbolProvider": true,
+                "documentSymbolProvider": true,
+                "definitionProvider": true,
+                "referencesProvider": true,
+            },
+            "serverInfo": {
+                "name": "fmm",
+                "version": env!("CARGO_PKG_VERSION"),
+            },
+        })
+    }
+
+    /// `workspace/symbol`: every export whose name contains `query`
+    /// (case-insensitive; an empty query matches everything), mapped onto
+    /// [`Manifest::export_index`].
+    fn workspace_symbol(&self, params: &Value) -> Value {
+        let query = params.get("query").and_then(Value::as_str).unwrap_or("");
+        let Some(manifest) = &self.manifest else {
+            return json!([]);
+        };
+        let query_lower = query.to_lowercase();
+
+        let mut symbols: Vec<(String, Value)> = manifest
+            .export_index
+            .iter()
+            .filter(|(name, _)| query.is_empty() || name.to_lowercase().contains(&query_lower))
+            .map(|(name, file)| {
+                let lines = manifest
+                    .export_locations
+                    .get(name)
+                    .and_then(|loc| loc.lines.as_ref());
+                let symbol = json!({
+                    "name": name,
+                    "kind": 12, // Function — the index doesn't distinguish symbol kinds
+                    "location": self.location_for(file, lines),
+                });
+                (name.clone(), symbol)
+            })
+            .collect();
+        symbols.sort_by(|a, b| a.0.cmp(&b.0));
+
+        json!(symbols.into_iter().map(|(_, s)| s).collect::<Vec<_>>())
+    }
+
+    /// `textDocument/documentSymbol`: a file's exports, in sidecar order,
+    /// each positioned at its v0.3 `[start, end]` line range if the sidecar
+    /// recorded one.
+    fn document_symbol(&self, params: &Value) -> Value {
+        let Some((manifest, file)) = self.manifest_and_file(params) else {
+            return json!([]);
+        };
+        let Some(entry) = manifest.files.get(&file) else {
+            return json!([]);
+        };
+
+        let symbols: Vec<Value> = entry
+            .exports
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let lines = entry.export_lines.as_ref().and_then(|el| el.get(i));
+                let range = self.range_for(lines);
+                json!({
+                    "name": name,
+                    "kind": 12,
+                    "range": range,
+                    "selectionRange": range,
+                })
+            })
+            .collect();
+
+        json!(symbols)
+    }
+
+    /// `textDocument/definition`: resolves the identifier under the cursor
+    /// against [`Manifest::export_index`] first (own or another file's
+    /// export), then against the current file's `dependencies`/`imports`
+    /// (e.g. the `types` in `./types` jumps to the file it resolves to via
+    /// [`Manifest::resolve_dependency`]).
+    fn definition(&self, params: &Value) -> Value {
+        let Some((manifest, file)) = self.manifest_and_file(params) else {
+            return Value::Null;
+        };
+        let Some((line, character)) = position_of(params) else {
+            return Value::Null;
+        };
+        let Some(word) = word_at(&self.root.join(&file), line, character) else {
+            return Value::Null;
+        };
+
+        if let Some(target_file) = manifest.export_index.get(&word) {
+            let lines = manifest
+                .export_locations
+                .get(&word)
+                .and_then(|loc| loc.lines.as_ref());
+            return self.location_for(target_file, lines);
+        }
+
+        if let Some(entry) = manifest.files.get(&file) {
+            for spec in entry.dependencies.iter().chain(entry.imports.iter()) {
+                if !spec.contains(&word) {
+                    continue;
+                }
+                if let Some(target) = manifest.resolve_dependency(&file, spec) {
+                    return self.location_for(&target, None);
+                }
+            }
+        }
+
+        Value::Null
+    }
+
+    /// `textDocument/references`: downstream dependents of the requested
+    /// file, reusing [`Manifest::dependents_of`] — the same reverse index
+    /// `fmm_dependency_graph` reports as `downstream`.
+    fn references(&self, params: &Value) -> Value {
+        let Some((manifest, file)) = self.manifest_and_file(params) else {
+            return json!([]);
+        };
+
+        let locations: Vec<Value> = manifest
+            .dependents_of(&file)
+            .into_iter()
+            .map(|dependent| self.location_for(dependent, None))
+            .collect();
+
+        json!(locations)
+    }
+
+    fn manifest_and_file(&self, params: &Value) -> Option<(&Manifest, String)> {
+        let manifest = self.manifest.as_ref()?;
+        let uri = params
+            .get("textDocument")
+            .and_then(|t| t.get("uri"))
+            .and_then(Value::as_str)?;
+        let file = self.uri_to_relative(uri)?;
+        Some((manifest, file))
+    }
+
+    fn file_uri(&self, relative: &str) -> String {
+        format!("file://{}", self.root.join(relative).display())
+    }
+
+    fn uri_to_relative(&self, uri: &str) -> Option<String> {
+        let path = uri.strip_prefix("file://")?;
+        Path::new(path)
+            .strip_prefix(&self.root)
+            .ok()
+            .map(|p| p.display().to_string())
+    }
+
+    fn range_for(&self, lines: Option<&ExportLines>) -> Value {
+        // `FileEntry`/`ExportLocation` line numbers are 1-based; LSP
+        // positions are 0-based.
+        let (start, end) = lines
+            .map(|l| (l.start.saturating_sub(1), l.end.saturating_sub(1)))
+            .unwrap_or((0, 0));
+        json!({
+            "start": {"line": start, "character": 0},
+            "end": {"line": end, "character": 0},
+        })
+    }
+
+    fn location_for(&self, file: &str, lines: Option<&ExportLines>) -> Value {
+        json!({
+            "uri": self.file_uri(file),
+            "range": self.range_for(lines),
+        })
+    }
+}
+
+fn position_of(params: &Value) -> Option<(usize, usize)> {
+    let position = params.get("position")?;
+    let line = position.get("line")?.as_u64()? as usize;
+    let character = position.get("character")?.as_u64()? as usize;
+    Some((line, character))
+}
+
+/// The identifier (`[A-Za-z0-9_]+`) touching `(line, character)` in `path`,
+/// or `None` if the cursor isn't over one.
+fn word_at(path: &Path, line: usize, character: usize) -> Option<String> {
+    let content = std::fs::read_to_string(path).ok()?;
+    let line_text = content.lines().nth(line)?;
+    let chars: Vec<char> = line_text.chars().collect();
+    if chars.is_empty() {
+        return None;
+    }
+
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    let character = character.min(chars.len() - 1);
+    if !is_ident(chars[character]) {
+        return None;
+    }
+
+    let start = (0..=character)
+        .rev()
+        .find(|&i| !is_ident(chars[i]))
+        .map(|i| i + 1)
+        .unwrap_or(0);
+    let end = (character..chars.len())
+        .find(|&i| !is_ident(chars[i]))
+        .unwrap_or(chars.len());
+
+    Some(chars[start..end].iter().collect())
+}
+
+/// Read one `Content-Length`-framed LSP message from `reader`, or `Ok(None)`
+/// on a clean EOF between messages.
+fn read_message<R: BufRead>(reader: &mut R) -> Result<Option<Value>> {
+    let mut content_length: Option<usize> = None;
+
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some(value) = line.strip_prefix("Content-Length:") {
+            content_length = Some(
+                value
+                    .trim()
+                    .parse()
+                    .context("Invalid Content-Length header")?,
+            );
+        }
+    }
+
+    let length = content_length.context("Message is missing a Content-Length header")?;
+    let mut body = vec![0u8; length];
+    reader.read_exact(&mut body)?;
+    serde_json::from_slice(&body)
+        .context("Invalid LSP message body")
+        .map(Some)
+}
+
+/// Write `value` framed the way `read_message` expects to read it back.
+fn write_message<W: Write>(writer: &mut W, value: &Value) -> Result<()> {
+    let body = serde_json::to_string(value)?;
+    write!(writer, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn fixture() -> (TempDir, LspServer) {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join("types.ts"),
+            "export interface User {}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            tmp.path().join("types.ts.fmm"),
+            "file: types.ts\nexports:\n  User: [1, 1]\nloc: 1\n",
+        )
+        .unwrap();
+        std::fs::write(
+            tmp.path().join("auth.ts"),
+            "import { User } from './types';\n\nexport function validateUser(u: User) {}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            tmp.path().join("auth.ts.fmm"),
+            "file: auth.ts\nexports:\n  validateUser: [3, 3]\nimports: []\ndependencies: [./types]\nloc: 3\n",
+        )
+        .unwrap();
+
+        let server = LspServer::with_root(tmp.path().to_path_buf());
+        (tmp, server)
+    }
+
+    #[test]
+    fn initialize_advertises_capabilities() {
+        let (_tmp, server) = fixture();
+        let caps = server.handle_initialize();
+        assert_eq!(caps["capabilities"]["workspaceSymbolProvider"], json!(true));
+        assert_eq!(caps["capabilities"]["referencesProvider"], json!(true));
+    }
+
+    #[test]
+    fn workspace_symbol_filters_by_query() {
+        let (_tmp, server) = fixture();
+        let result = server.workspace_symbol(&json!({"query": "validate"}));
+        let names: Vec<&str> = result
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|s| s["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["validateUser"]);
+    }
+
+    #[test]
+    fn document_symbol_lists_the_files_exports() {
+        let (tmp, server) = fixture();
+        let uri = format!("file://{}", tmp.path().join("auth.ts").display());
+        let result = server.document_symbol(&json!({"textDocument": {"uri": uri}}));
+        let symbols = result.as_array().unwrap();
+        assert_eq!(symbols.len(), 1);
+        assert_eq!(symbols[0]["name"], json!("validateUser"));
+        assert_eq!(symbols[0]["range"]["start"]["line"], json!(2));
+    }
+
+    #[test]
+    fn definition_resolves_an_export_name() {
+        let (tmp, server) = fixture();
+        let uri = format!("file://{}", tmp.path().join("auth.ts").display());
+        // "validateUser" starts at column 16 of its own definition line.
+        let params = json!({
+            "textDocument": {"uri": uri},
+            "position": {"line": 2, "character": 16}
+        });
+        let location = server.definition(&params);
+        assert!(location["uri"].as_str().unwrap().ends_with("auth.ts"));
+    }
+
+    #[test]
+    fn definition_resolves_an_import_through_dependencies() {
+        let (tmp, server) = fixture();
+        let uri = format!("file://{}", tmp.path().join("auth.ts").display());
+        // "types" inside `'./types'` on the import line: not an export name,
+        // so it resolves through auth.ts's `./types` dependency.
+        let params = json!({
+            "textDocument": {"uri": uri},
+            "position": {"line": 0, "character": 24}
+        });
+        let location = server.definition(&params);
+        assert!(location["uri"].as_str().unwrap().ends_with("types.ts"));
+    }
+
+    #[test]
+    fn references_returns_downstream_dependents() {
+        let (tmp, server) = fixture();
+        let uri = format!("file://{}", tmp.path().join("types.ts").display());
+        let result = server.references(&json!({"textDocument": {"uri": uri}}));
+        let files: Vec<&str> = result
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|loc| loc["uri"].as_str().unwrap())
+            .collect();
+        assert!(files.iter().any(|f| f.ends_with("auth.ts")));
+    }
+}
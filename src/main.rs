@@ -1,13 +1,33 @@
 use anyhow::Result;
-use clap::{CommandFactory, Parser as ClapParser};
+use clap::{CommandFactory, Parser as ClapParser, ValueEnum};
 use colored::Colorize;
-use fmm::cli::{self, Cli, Commands, GhSubcommand, OutputFormat};
+use fmm::cli::{self, Cli, Commands, ContextRanking, GhSubcommand, OutputFormat};
 use fmm::compare;
+use fmm::config::ProjectDefaults;
 use fmm::gh;
+use fmm::lsp;
 use fmm::mcp;
 
 fn main() -> Result<()> {
-    let cli_args = Cli::parse();
+    let config = fmm::config::Config::load().unwrap_or_default();
+    let args = cli::expand_aliases(std::env::args().collect(), &config)?;
+    let cli_args = match Cli::try_parse_from(&args) {
+        Ok(cli_args) => cli_args,
+        Err(err) => {
+            if err.kind() == clap::error::ErrorKind::InvalidSubcommand {
+                if let Some(suggestion) = args.get(1).and_then(|a| cli::suggest_subcommand(a)) {
+                    eprintln!("{}", err);
+                    eprintln!(
+                        "  {} a similarly named subcommand exists: '{}'",
+                        "tip:".cyan(),
+                        suggestion
+                    );
+                    std::process::exit(2);
+                }
+            }
+            err.exit();
+        }
+    };
 
     if cli_args.markdown_help {
         let markdown = clap_markdown::help_markdown::<Cli>();
@@ -16,17 +36,7 @@ fn main() -> Result<()> {
     }
 
     if let Some(out_dir) = cli_args.generate_man_pages {
-        std::fs::create_dir_all(&out_dir)?;
-        let cmd = Cli::command();
-        clap_mangen::generate_to(cmd, &out_dir)?;
-        let count = std::fs::read_dir(&out_dir)?
-            .filter_map(|e| e.ok())
-            .filter(|e| {
-                e.path()
-                    .extension()
-                    .is_some_and(|ext| ext.to_str() == Some("1"))
-            })
-            .count();
+        let count = cli::generate_man_pages(&out_dir)?;
         eprintln!("Generated {} man page(s) in {}", count, out_dir.display());
         return Ok(());
     }
@@ -40,41 +50,104 @@ fn main() -> Result<()> {
     };
 
     match command {
-        Commands::Generate { path, dry_run } => {
+        Commands::Generate {
+            path,
+            dry_run,
+            changed,
+            include,
+            exclude,
+        } => {
             println!("{}", "Generating sidecars...".green().bold());
-            cli::generate(&path, dry_run)?;
+            cli::generate(&path, dry_run, changed.as_deref(), &include, &exclude)?;
         }
-        Commands::Update { path, dry_run } => {
+        Commands::Update {
+            path,
+            dry_run,
+            changed,
+            include,
+            exclude,
+            no_cache,
+        } => {
             println!("{}", "Updating sidecars...".green().bold());
-            cli::update(&path, dry_run)?;
+            cli::update(
+                &path,
+                dry_run,
+                changed.as_deref(),
+                &include,
+                &exclude,
+                no_cache,
+            )?;
         }
-        Commands::Validate { path } => {
+        Commands::Validate {
+            path,
+            changed,
+            include,
+            exclude,
+            no_cache,
+            dependents,
+        } => {
             println!("{}", "Validating sidecars...".green().bold());
-            cli::validate(&path)?;
+            cli::validate(
+                &path,
+                changed.as_deref(),
+                &include,
+                &exclude,
+                no_cache,
+                dependents.as_deref(),
+            )?;
         }
-        Commands::Clean { path, dry_run } => {
+        Commands::Clean {
+            path,
+            dry_run,
+            include,
+            exclude,
+        } => {
             println!("{}", "Cleaning sidecars...".green().bold());
-            cli::clean(&path, dry_run)?;
+            cli::clean(&path, dry_run, &include, &exclude)?;
+        }
+        Commands::Watch { path, debounce } => {
+            println!("{}", "Watching for changes...".green().bold());
+            cli::watch(&path, debounce)?;
+        }
+        Commands::Format {
+            path,
+            check,
+            dry_run,
+        } => {
+            cli::format(&path, check, dry_run)?;
         }
         Commands::Init {
             skill,
             mcp,
+            hooks,
             all,
             no_generate,
+            dry_run,
         } => {
-            cli::init(skill, mcp, all, no_generate)?;
+            cli::init(skill, mcp, hooks, all, no_generate, dry_run)?;
         }
         Commands::Status => {
             cli::status()?;
         }
+        Commands::Config { schema } => {
+            cli::config(schema)?;
+        }
         Commands::Search {
+            query,
             export,
             imports,
             loc,
             depends_on,
             json,
+            pick,
         } => {
-            cli::search(export, imports, loc, depends_on, json)?;
+            cli::search(query, export, imports, loc, depends_on, json, pick)?;
+        }
+        Commands::Check { json } => {
+            cli::check(json)?;
+        }
+        Commands::Graph { json } => {
+            cli::graph(json)?;
         }
         Commands::Gh { subcommand } => match subcommand {
             GhSubcommand::Issue {
@@ -87,18 +160,56 @@ fn main() -> Result<()> {
                 no_pr,
                 workspace,
                 compare,
+                runs,
+                baseline,
+                save_baseline,
+                max_regression,
                 output,
+                edit,
+                profile,
+                depth,
+                full_clone,
             } => {
+                let root = std::env::current_dir()?;
+                let defaults = ProjectDefaults::load_layered(&root);
+                let global_config = fmm::config::GlobalConfig::load();
+                let selected_profile = profile
+                    .as_deref()
+                    .and_then(|name| global_config.profiles.get(name).cloned());
                 let options = gh::GhIssueOptions {
-                    model,
-                    max_turns,
-                    max_budget,
+                    model: model
+                        .or_else(|| selected_profile.as_ref().and_then(|p| p.model.clone()))
+                        .or(defaults.gh.model)
+                        .unwrap_or_else(|| "sonnet".to_string()),
+                    max_turns: max_turns
+                        .or(selected_profile.as_ref().and_then(|p| p.max_turns))
+                        .or(defaults.gh.max_turns)
+                        .unwrap_or(30),
+                    max_budget: max_budget
+                        .or(selected_profile.as_ref().and_then(|p| p.max_budget))
+                        .or(defaults.gh.max_budget)
+                        .unwrap_or(5.0),
                     dry_run,
-                    branch_prefix,
+                    branch_prefix: branch_prefix
+                        .or_else(|| {
+                            selected_profile
+                                .as_ref()
+                                .and_then(|p| p.branch_prefix.clone())
+                        })
+                        .or(defaults.gh.branch_prefix)
+                        .unwrap_or_else(|| "fmm".to_string()),
                     no_pr,
                     workspace,
                     compare,
+                    runs: runs.max(1),
+                    baseline,
+                    save_baseline,
+                    max_regression_pct: max_regression,
                     output,
+                    edit,
+                    allowed_tools: selected_profile.as_ref().and_then(|p| p.allowed_tools.clone()),
+                    profile,
+                    clone_depth: if full_clone { None } else { Some(depth) },
                 };
                 gh::gh_issue(&url, options)?;
             }
@@ -107,8 +218,22 @@ fn main() -> Result<()> {
             let mut server = mcp::McpServer::new();
             server.run()?;
         }
-        Commands::Completions { shell } => {
-            clap_complete::generate(shell, &mut Cli::command(), "fmm", &mut std::io::stdout());
+        Commands::Lsp => {
+            let mut server = lsp::LspServer::new();
+            server.run()?;
+        }
+        Commands::Completions {
+            shell,
+            complete_exports,
+        } => {
+            if complete_exports {
+                cli::complete_exports(&mut std::io::stdout())?;
+            } else if let Some(shell) = shell {
+                cli::generate_completions(shell, &mut std::io::stdout());
+            }
+        }
+        Commands::Tidy { check } => {
+            cli::tidy(check)?;
         }
         Commands::Compare {
             url,
@@ -116,17 +241,45 @@ fn main() -> Result<()> {
             src_path,
             tasks,
             runs,
+            concurrency,
+            context_token_budget,
+            context_ranking,
             output,
             format,
+            min_reduction,
             max_budget,
             no_cache,
+            force_refresh,
+            incremental,
             quick,
+            rag,
             model,
+            backend,
+            depth,
+            full_clone,
+            container,
         } => {
+            let root = std::env::current_dir()?;
+            let defaults = ProjectDefaults::load_layered(&root);
+            let format = format
+                .or_else(|| {
+                    defaults
+                        .compare
+                        .format
+                        .as_deref()
+                        .and_then(|s| OutputFormat::from_str(s, true).ok())
+                })
+                .unwrap_or(OutputFormat::Both);
             let report_format = match format {
                 OutputFormat::Json => compare::ReportFormat::Json,
                 OutputFormat::Markdown => compare::ReportFormat::Markdown,
                 OutputFormat::Both => compare::ReportFormat::Both,
+                OutputFormat::Junit => compare::ReportFormat::Junit,
+                OutputFormat::Jsonl => compare::ReportFormat::Jsonl,
+            };
+            let context_ranking = match context_ranking {
+                ContextRanking::InDegree => fmm::context_budget::RankingStrategy::InDegree,
+                ContextRanking::Arbitrary => fmm::context_budget::RankingStrategy::Arbitrary,
             };
 
             let options = compare::CompareOptions {
@@ -134,16 +287,41 @@ fn main() -> Result<()> {
                 src_path,
                 task_set: tasks,
                 runs,
+                concurrency,
+                context_token_budget,
+                context_ranking,
                 output,
                 format: report_format,
-                max_budget,
+                min_reduction,
+                max_budget: max_budget.or(defaults.compare.max_budget).unwrap_or(10.0),
                 use_cache: !no_cache,
+                force_refresh,
+                incremental,
                 quick,
+                rag,
                 model,
+                backend,
+                clone_depth: if full_clone { None } else { Some(depth) },
+                container,
             };
 
             compare::compare(&url, options)?;
         }
+        Commands::FetchGrammar {
+            name,
+            url,
+            git_ref,
+            extensions,
+            manifest,
+        } => {
+            cli::fetch_grammar(
+                &name,
+                url.as_deref(),
+                git_ref.as_deref(),
+                &extensions,
+                &manifest,
+            )?;
+        }
     }
 
     Ok(())
@@ -2,11 +2,31 @@ use anyhow::Result;
 use chrono::{DateTime, Utc};
 use ignore::WalkBuilder;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
+use std::time::SystemTime;
 
 use crate::parser::Metadata;
 
+mod sqlite_cache;
+
+/// Name of the on-disk cache file `load_from_sidecars` reads and writes,
+/// kept at the root passed in (next to `.fmmrc.json`, not inside it).
+const CACHE_FILE_NAME: &str = ".fmm-cache.json";
+
+/// Extensions tried, in order, when resolving an extension-less relative
+/// import spec (e.g. `./database`) against `Manifest::files` keys.
+const RESOLVE_EXTENSIONS: &[&str] = &["ts", "tsx", "js", "jsx", "py", "rs", "go"];
+
+/// On-disk cache pairing a built [`Manifest`] with the sidecar mtimes it was
+/// built from, so the next `load_from_sidecars` can skip re-parsing sidecars
+/// that haven't changed.
+#[derive(Debug, Serialize, Deserialize)]
+struct ManifestCache {
+    mtimes: HashMap<String, SystemTime>,
+    manifest: Manifest,
+}
+
 /// Typed representation of a `.fmm` sidecar file for serde_yaml deserialization.
 /// Handles both v0.2 (exports as list) and v0.3 (exports as map with line ranges).
 #[derive(Debug, Deserialize)]
@@ -73,7 +93,9 @@ pub struct ExportLocation {
 }
 
 /// In-memory index built from sidecar files.
-/// No longer persisted to disk — built on-the-fly from `**/*.fmm` sidecars.
+/// Built on-the-fly from `**/*.fmm` sidecars; `load_from_sidecars` caches
+/// the result at `.fmm-cache.json` so unchanged sidecars don't need
+/// reparsing on the next call (see [`ManifestCache`]).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Manifest {
@@ -85,6 +107,13 @@ pub struct Manifest {
     /// Maps export name -> full location (file + lines)
     #[serde(skip_serializing_if = "HashMap::is_empty", default)]
     pub export_locations: HashMap<String, ExportLocation>,
+    /// Reverse index: file path -> files whose `dependencies`/`imports`
+    /// resolve to it. Lets a caller answer "who imports this file?" before
+    /// editing it. Rebuilt wholesale by [`Self::reindex_importers`] rather
+    /// than patched incrementally, so it's never left inconsistent by
+    /// forward references between files added in the same pass.
+    #[serde(skip_serializing_if = "HashMap::is_empty", default)]
+    pub importers: HashMap<String, Vec<String>>,
 }
 
 impl Manifest {
@@ -94,15 +123,39 @@ impl Manifest {
             generated: Utc::now(),
             files: HashMap::new(),
             export_index: HashMap::new(),
+            importers: HashMap::new(),
             export_locations: HashMap::new(),
         }
     }
 
-    /// Build an in-memory index by reading all `*.fmm` sidecar files under root.
+    /// Build an in-memory index by reading all `*.fmm` sidecar files under
+    /// root, reusing the on-disk cache at `<root>/.fmm-cache.json` for any
+    /// sidecar whose mtime hasn't changed since it was last cached.
     pub fn load_from_sidecars(root: &Path) -> Result<Self> {
-        let mut manifest = Self::new();
+        Self::load_from_sidecars_with_cache(root, false)
+    }
+
+    /// Same as [`Self::load_from_sidecars`], but with `force_rebuild` to
+    /// ignore the cache entirely and reparse every sidecar from scratch.
+    pub fn load_from_sidecars_with_cache(root: &Path, force_rebuild: bool) -> Result<Self> {
+        let cache_path = root.join(CACHE_FILE_NAME);
+
+        let cached = if force_rebuild {
+            None
+        } else {
+            std::fs::read_to_string(&cache_path)
+                .ok()
+                .and_then(|s| serde_json::from_str::<ManifestCache>(&s).ok())
+        };
+
+        let mut manifest = cached
+            .as_ref()
+            .map(|c| c.manifest.clone())
+            .unwrap_or_else(Self::new);
+        let mut mtimes = cached.map(|c| c.mtimes).unwrap_or_default();
 
         let walker = WalkBuilder::new(root).standard_filters(true).build();
+        let mut seen: HashSet<String> = HashSet::new();
 
         for entry in walker.filter_map(|e| e.ok()) {
             let path = entry.path();
@@ -110,45 +163,110 @@ impl Manifest {
                 continue;
             }
 
+            let key = sidecar_key(path, root);
+            seen.insert(key.clone());
+
+            let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+            if let (false, Some(modified), Some(prev)) = (force_rebuild, modified, mtimes.get(&key)) {
+                if modified == *prev && manifest.files.contains_key(&key) {
+                    continue;
+                }
+            }
+
             let content = match std::fs::read_to_string(path) {
                 Ok(c) => c,
                 Err(_) => continue,
             };
 
             if let Some((file_path, file_entry)) = parse_sidecar(&content) {
-                let key = if !file_path.is_empty() {
-                    file_path
-                } else {
-                    let source_path = path.with_extension("");
-                    source_path
-                        .strip_prefix(root)
-                        .unwrap_or(&source_path)
-                        .display()
-                        .to_string()
-                };
-
-                for (i, export) in file_entry.exports.iter().enumerate() {
-                    manifest.export_index.insert(export.clone(), key.clone());
-                    let lines = file_entry
-                        .export_lines
-                        .as_ref()
-                        .and_then(|el| el.get(i))
-                        .cloned();
-                    manifest.export_locations.insert(
-                        export.clone(),
-                        ExportLocation {
-                            file: key.clone(),
-                            lines,
-                        },
-                    );
+                let key = if !file_path.is_empty() { file_path } else { key };
+                seen.insert(key.clone());
+                manifest.insert_file_entry(key.clone(), file_entry);
+                if let Some(modified) = modified {
+                    mtimes.insert(key, modified);
                 }
-                manifest.files.insert(key, file_entry);
             }
         }
 
+        let stale: Vec<String> = manifest
+            .files
+            .keys()
+            .filter(|k| !seen.contains(*k))
+            .cloned()
+            .collect();
+        for key in stale {
+            manifest.remove_file(&key);
+            mtimes.remove(&key);
+        }
+
+        manifest.write_cache(&cache_path, mtimes);
+
         Ok(manifest)
     }
 
+    /// Same as [`Self::load_from_sidecars`], but backed by a persistent
+    /// SQLite index at `db_path` (see [`sqlite_cache`]) instead of the
+    /// whole-manifest JSON cache at `.fmm-cache.json`. Only sidecars whose
+    /// mtime/size changed since the last run are reparsed; everything else
+    /// loads straight from the database, without reading and rewriting the
+    /// whole JSON cache on every load. The tree is still walked and every
+    /// sidecar stat'ed.
+    pub fn load_cached(root: &Path, db_path: &Path) -> Result<Self> {
+        sqlite_cache::load_cached(root, db_path)
+    }
+
+    /// Insert or replace a file's entry in the index from an already-parsed
+    /// sidecar, clearing any stale `export_index`/`export_locations` entries
+    /// it previously owned (mirrors [`Self::add_file`], but takes the
+    /// sidecar's own [`FileEntry`] instead of freshly-parsed [`Metadata`]).
+    fn insert_file_entry(&mut self, key: String, file_entry: FileEntry) {
+        if let Some(old_entry) = self.files.get(&key) {
+            for old_export in &old_entry.exports {
+                if self.export_index.get(old_export) == Some(&key) {
+                    self.export_index.remove(old_export);
+                    self.export_locations.remove(old_export);
+                }
+            }
+        }
+
+        for (i, export) in file_entry.exports.iter().enumerate() {
+            self.export_index.insert(export.clone(), key.clone());
+            let lines = file_entry
+                .export_lines
+                .as_ref()
+                .and_then(|el| el.get(i))
+                .cloned();
+            self.export_locations.insert(
+                export.clone(),
+                ExportLocation {
+                    file: key.clone(),
+                    lines,
+                },
+            );
+        }
+
+        self.files.insert(key, file_entry);
+        self.reindex_importers();
+    }
+
+    /// Write the cache back atomically (temp file + rename) so a crash
+    /// mid-write can't leave a truncated `.fmm-cache.json` behind. Best
+    /// effort: an unwritable root just means the next run rebuilds from
+    /// scratch, which isn't worth failing the caller over.
+    fn write_cache(&self, cache_path: &Path, mtimes: HashMap<String, SystemTime>) {
+        let cache = ManifestCache {
+            mtimes,
+            manifest: self.clone(),
+        };
+        let Ok(json) = serde_json::to_string(&cache) else {
+            return;
+        };
+        let tmp_path = cache_path.with_extension("json.tmp");
+        if std::fs::write(&tmp_path, json).is_ok() {
+            let _ = std::fs::rename(&tmp_path, cache_path);
+        }
+    }
+
     /// Add or update a file entry in the index
     pub fn add_file(&mut self, path: &str, metadata: Metadata) {
         if let Some(old_entry) = self.files.get(path) {
@@ -192,6 +310,7 @@ impl Manifest {
 
         self.files
             .insert(path.to_string(), FileEntry::from(metadata));
+        self.reindex_importers();
     }
 
     pub fn remove_file(&mut self, path: &str) {
@@ -201,6 +320,90 @@ impl Manifest {
                 self.export_locations.remove(&export);
             }
         }
+        self.reindex_importers();
+    }
+
+    /// Reverse of every file's `dependencies`, built in one O(files) pass:
+    /// `path -> [files that list path as a dependency]`. Unlike `importers`
+    /// (which additionally resolves relative import specifiers via
+    /// `resolve_dependency_target`), this only matches a `dependencies`
+    /// entry that's already an exact manifest key — the same matching the
+    /// naive per-path rescan it replaces used. Callers that need one-shot
+    /// downstream lookups across many paths (like `resolve_references`'s
+    /// dependency fan-out) should build this once and reuse it, rather than
+    /// rescanning `files` for every path.
+    pub fn dependents_index(&self) -> HashMap<String, Vec<String>> {
+        let mut index: HashMap<String, Vec<String>> = HashMap::new();
+        for (path, entry) in &self.files {
+            for dep in &entry.dependencies {
+                index.entry(dep.clone()).or_default().push(path.clone());
+            }
+        }
+        index
+    }
+
+    /// Files whose `dependencies`/`imports` resolve to `path` — the direct
+    /// "blast radius" of changing it.
+    pub fn dependents_of(&self, path: &str) -> Vec<&String> {
+        self.importers
+            .get(path)
+            .map(|v| v.iter().collect())
+            .unwrap_or_default()
+    }
+
+    /// Resolve a single `dependencies`/`imports` entry belonging to
+    /// `importer` (e.g. `./types`) to the manifest key it points at — the
+    /// same resolution [`Self::reindex_importers`] uses to build
+    /// `importers`, exposed for callers (like the LSP server's
+    /// go-to-definition) that need to resolve one spec on demand rather
+    /// than walk the whole reverse index.
+    pub fn resolve_dependency(&self, importer: &str, spec: &str) -> Option<String> {
+        resolve_dependency_target(importer, spec, &self.files)
+    }
+
+    /// Transitive closure of [`Self::dependents_of`]: every file reachable
+    /// by following reverse dependency edges from `path`, however many hops
+    /// away, with a visited set guarding against import cycles.
+    pub fn transitive_dependents_of(&self, path: &str) -> Vec<String> {
+        let mut visited: HashSet<String> = HashSet::new();
+        let mut queue: Vec<String> = self
+            .dependents_of(path)
+            .into_iter()
+            .cloned()
+            .collect();
+
+        while let Some(next) = queue.pop() {
+            if !visited.insert(next.clone()) {
+                continue;
+            }
+            for dependent in self.dependents_of(&next) {
+                if !visited.contains(dependent) {
+                    queue.push(dependent.clone());
+                }
+            }
+        }
+
+        visited.into_iter().collect()
+    }
+
+    /// Rebuild `importers` from scratch by resolving every file's
+    /// `dependencies` and `imports` against `files`. Rebuilding wholesale
+    /// (rather than patching edges in/out incrementally) keeps the index
+    /// correct regardless of the order files are added in — an edge from
+    /// A to B is only resolvable once B's entry exists in `files`.
+    fn reindex_importers(&mut self) {
+        self.importers.clear();
+        for (importer, entry) in &self.files {
+            for spec in entry.dependencies.iter().chain(entry.imports.iter()) {
+                let Some(target) = resolve_dependency_target(importer, spec, &self.files) else {
+                    continue;
+                };
+                let dependents = self.importers.entry(target).or_default();
+                if !dependents.contains(importer) {
+                    dependents.push(importer.clone());
+                }
+            }
+        }
     }
 
     pub fn touch(&mut self) {
@@ -235,6 +438,85 @@ impl Manifest {
     pub fn file_paths(&self) -> Vec<&String> {
         self.files.keys().collect()
     }
+
+    /// Re-parse a single sidecar on disk and patch it into the index,
+    /// without touching any other file's entry. Used by watchers (e.g. the
+    /// MCP server's hot-reload) that see one `.fmm` create/modify event at a
+    /// time and want to avoid paying for a full [`Self::load_from_sidecars`]
+    /// rescan on every keystroke-triggered save. Returns `false` if the
+    /// sidecar is missing or fails to parse, in which case the caller should
+    /// fall back to [`Self::remove_sidecar`] or a full rescan.
+    pub fn update_sidecar(&mut self, sidecar_path: &Path, root: &Path) -> bool {
+        let Ok(content) = std::fs::read_to_string(sidecar_path) else {
+            return false;
+        };
+        let Some((file_path, file_entry)) = parse_sidecar(&content) else {
+            return false;
+        };
+        let key = if !file_path.is_empty() {
+            file_path
+        } else {
+            sidecar_key(sidecar_path, root)
+        };
+        self.insert_file_entry(key, file_entry);
+        true
+    }
+
+    /// Drop the file entry owned by a deleted sidecar, mirroring
+    /// [`Self::update_sidecar`]'s key resolution for the create/modify case.
+    pub fn remove_sidecar(&mut self, sidecar_path: &Path, root: &Path) {
+        let key = sidecar_key(sidecar_path, root);
+        self.remove_file(&key);
+    }
+
+    /// "Did you mean" suggestions for an export name that isn't in
+    /// `export_index`, mirroring cargo's fuzzy hints for mistyped
+    /// subcommands. Compares `query` case-insensitively against every known
+    /// export name by Levenshtein distance, keeps candidates within
+    /// `max(1, query.len() / 3)` edits, and returns up to `max` of them
+    /// ordered by distance then alphabetically.
+    pub fn suggest_exports(&self, query: &str, max: usize) -> Vec<(String, f64)> {
+        nearest_matches(query, self.export_index.keys().map(String::as_str), max)
+    }
+
+    /// Same idea as [`Manifest::suggest_exports`] but over file paths, for
+    /// callers (like the MCP `file_info` tool) that look up a file rather
+    /// than an export and want a hint when the path is slightly off.
+    pub fn suggest_files(&self, query: &str, max: usize) -> Vec<(String, f64)> {
+        nearest_matches(query, self.files.keys().map(String::as_str), max)
+    }
+}
+
+/// Shared implementation behind [`Manifest::suggest_exports`] and
+/// [`Manifest::suggest_files`]: compares `query` case-insensitively against
+/// every `candidates` entry by Levenshtein distance, keeps those within
+/// `max(1, query.len() / 3)` edits, and returns up to `max` ordered by
+/// distance then alphabetically.
+fn nearest_matches<'a>(
+    query: &str,
+    candidates: impl Iterator<Item = &'a str>,
+    max: usize,
+) -> Vec<(String, f64)> {
+    let query_lower = query.to_lowercase();
+    let threshold = std::cmp::max(1, query.len() / 3);
+
+    let mut matches: Vec<(String, usize)> = candidates
+        .filter_map(|name| {
+            let distance = crate::levenshtein::levenshtein_distance(&query_lower, &name.to_lowercase());
+            if distance <= threshold {
+                Some((name.to_string(), distance))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    matches.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+    matches
+        .into_iter()
+        .take(max)
+        .map(|(name, distance)| (name, distance as f64))
+        .collect()
 }
 
 impl Default for Manifest {
@@ -243,6 +525,76 @@ impl Default for Manifest {
     }
 }
 
+/// Resolve a `dependencies`/`imports` spec from `importer` to the
+/// `files` key it refers to, if any. Bare specs (package names like
+/// `crypto`, not paths) never resolve — they point outside the repo.
+/// Relative specs are joined against `importer`'s directory and matched
+/// against `files`, trying the spec as-is, then with each of
+/// [`RESOLVE_EXTENSIONS`] appended, then as an `index.<ext>` inside it —
+/// mirroring how a bundler resolves an extension-less module specifier.
+fn resolve_dependency_target(
+    importer: &str,
+    spec: &str,
+    files: &HashMap<String, FileEntry>,
+) -> Option<String> {
+    if !(spec.starts_with("./") || spec.starts_with("../")) {
+        return None;
+    }
+
+    let importer_dir = Path::new(importer).parent().unwrap_or(Path::new(""));
+    let joined = normalize_relative_path(&importer_dir.join(spec));
+
+    if files.contains_key(&joined) {
+        return Some(joined);
+    }
+    for ext in RESOLVE_EXTENSIONS {
+        let with_ext = format!("{joined}.{ext}");
+        if files.contains_key(&with_ext) {
+            return Some(with_ext);
+        }
+        let index = format!("{joined}/index.{ext}");
+        if files.contains_key(&index) {
+            return Some(index);
+        }
+    }
+    None
+}
+
+/// Collapse `.`/`..` components in a joined relative path without touching
+/// the filesystem (the path may not exist as typed — an import spec is
+/// usually missing its file extension).
+fn normalize_relative_path(path: &Path) -> String {
+    let mut parts: Vec<&std::ffi::OsStr> = Vec::new();
+    for component in path.components() {
+        match component {
+            std::path::Component::ParentDir => {
+                parts.pop();
+            }
+            std::path::Component::CurDir => {}
+            std::path::Component::Normal(part) => parts.push(part),
+            _ => {}
+        }
+    }
+    parts
+        .iter()
+        .map(|part| part.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Best-effort key for a sidecar before its contents are parsed: the source
+/// file's path relative to `root`, derived by stripping the sidecar's
+/// `.fmm` extension. Overridden by the sidecar's own `file:` field once
+/// parsed, when that field is present.
+fn sidecar_key(sidecar_path: &Path, root: &Path) -> String {
+    let source_path = sidecar_path.with_extension("");
+    source_path
+        .strip_prefix(root)
+        .unwrap_or(&source_path)
+        .display()
+        .to_string()
+}
+
 /// Parse a sidecar YAML file into (file_path, FileEntry).
 /// Handles both v0.2 (exports as list) and v0.3 (exports as map with line ranges).
 fn parse_sidecar(content: &str) -> Option<(String, FileEntry)> {
@@ -305,6 +657,7 @@ fn parse_sidecar(content: &str) -> Option<(String, FileEntry)> {
 mod tests {
     use super::*;
     use crate::parser::ExportEntry;
+    use tempfile::TempDir;
 
     fn entry(name: &str, start: usize, end: usize) -> ExportEntry {
         ExportEntry::new(name.to_string(), start, end)
@@ -482,4 +835,229 @@ modified: 2026-01-30"#;
         assert!(!manifest.export_index.contains_key("bar"));
         assert_eq!(manifest.file_count(), 1);
     }
+
+    #[test]
+    fn test_manifest_suggest_exports_finds_close_match() {
+        let mut manifest = Manifest::new();
+
+        let metadata = Metadata {
+            exports: vec![entry("validateUser", 5, 20), entry("createSession", 22, 45)],
+            imports: vec![],
+            dependencies: vec![],
+            loc: 50,
+        };
+        manifest.add_file("src/auth.ts", metadata);
+
+        let suggestions = manifest.suggest_exports("validateuser", 5);
+        assert_eq!(suggestions[0].0, "validateUser");
+        assert_eq!(suggestions[0].1, 0.0);
+    }
+
+    #[test]
+    fn test_manifest_suggest_exports_respects_max_and_threshold() {
+        let mut manifest = Manifest::new();
+
+        let metadata = Metadata {
+            exports: vec![
+                entry("createSession", 1, 5),
+                entry("createSessionToken", 7, 12),
+                entry("destroyEverything", 14, 20),
+            ],
+            imports: vec![],
+            dependencies: vec![],
+            loc: 30,
+        };
+        manifest.add_file("src/auth.ts", metadata);
+
+        let suggestions = manifest.suggest_exports("createSession", 1);
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].0, "createSession");
+
+        // Nothing shares a short enough edit distance with a short, unrelated query.
+        assert!(manifest.suggest_exports("xyz", 5).is_empty());
+    }
+
+    #[test]
+    fn test_load_from_sidecars_writes_and_reuses_cache() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join("auth.ts.fmm"),
+            "file: auth.ts\nexports: [validateUser]\nloc: 10\n",
+        )
+        .unwrap();
+
+        let manifest = Manifest::load_from_sidecars(tmp.path()).unwrap();
+        assert!(manifest.has_file("auth.ts"));
+        assert!(tmp.path().join(".fmm-cache.json").exists());
+
+        // Reloading without touching the sidecar should reuse the cached entry.
+        let reloaded = Manifest::load_from_sidecars(tmp.path()).unwrap();
+        assert!(reloaded.has_file("auth.ts"));
+        assert_eq!(
+            reloaded.export_index.get("validateUser"),
+            Some(&"auth.ts".to_string())
+        );
+    }
+
+    #[test]
+    fn test_load_from_sidecars_drops_removed_sidecars() {
+        let tmp = TempDir::new().unwrap();
+        let sidecar = tmp.path().join("auth.ts.fmm");
+        std::fs::write(&sidecar, "file: auth.ts\nexports: [validateUser]\nloc: 10\n").unwrap();
+
+        Manifest::load_from_sidecars(tmp.path()).unwrap();
+        std::fs::remove_file(&sidecar).unwrap();
+
+        let manifest = Manifest::load_from_sidecars(tmp.path()).unwrap();
+        assert!(!manifest.has_file("auth.ts"));
+        assert!(!manifest.export_index.contains_key("validateUser"));
+    }
+
+    #[test]
+    fn test_load_from_sidecars_force_rebuild_ignores_cache() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join("auth.ts.fmm"),
+            "file: auth.ts\nexports: [validateUser]\nloc: 10\n",
+        )
+        .unwrap();
+
+        Manifest::load_from_sidecars(tmp.path()).unwrap();
+        let manifest = Manifest::load_from_sidecars_with_cache(tmp.path(), true).unwrap();
+        assert!(manifest.has_file("auth.ts"));
+    }
+
+    #[test]
+    fn test_manifest_dependents_of_resolves_relative_dependency() {
+        let mut manifest = Manifest::new();
+
+        manifest.add_file(
+            "src/database.ts",
+            Metadata {
+                exports: vec![entry("connect", 1, 5)],
+                imports: vec![],
+                dependencies: vec![],
+                loc: 10,
+            },
+        );
+        manifest.add_file(
+            "src/auth.ts",
+            Metadata {
+                exports: vec![entry("validateUser", 1, 5)],
+                imports: vec![],
+                dependencies: vec!["./database".to_string()],
+                loc: 20,
+            },
+        );
+
+        assert_eq!(
+            manifest.dependents_of("src/database.ts"),
+            vec!["src/auth.ts"]
+        );
+        assert!(manifest.dependents_of("src/auth.ts").is_empty());
+    }
+
+    #[test]
+    fn test_dependents_index_is_exact_match_reverse_of_dependencies() {
+        let mut manifest = Manifest::new();
+        manifest.add_file(
+            "src/database.ts",
+            Metadata {
+                exports: vec![],
+                imports: vec![],
+                dependencies: vec![],
+                loc: 10,
+            },
+        );
+        manifest.add_file(
+            "src/auth.ts",
+            Metadata {
+                exports: vec![],
+                imports: vec![],
+                // Already an exact manifest key, unlike the relative
+                // specifiers `dependents_of` resolves.
+                dependencies: vec!["src/database.ts".to_string()],
+                loc: 20,
+            },
+        );
+
+        let index = manifest.dependents_index();
+        assert_eq!(
+            index.get("src/database.ts"),
+            Some(&vec!["src/auth.ts".to_string()])
+        );
+        assert!(index.get("src/auth.ts").is_none());
+    }
+
+    #[test]
+    fn test_manifest_transitive_dependents_follows_chain_without_cycling() {
+        let mut manifest = Manifest::new();
+
+        manifest.add_file(
+            "src/database.ts",
+            Metadata {
+                exports: vec![],
+                imports: vec![],
+                dependencies: vec!["./auth".to_string()],
+                loc: 10,
+            },
+        );
+        manifest.add_file(
+            "src/auth.ts",
+            Metadata {
+                exports: vec![],
+                imports: vec![],
+                dependencies: vec!["./session".to_string()],
+                loc: 10,
+            },
+        );
+        manifest.add_file(
+            "src/session.ts",
+            Metadata {
+                exports: vec![],
+                imports: vec![],
+                dependencies: vec!["./database".to_string()],
+                loc: 10,
+            },
+        );
+
+        let mut dependents = manifest.transitive_dependents_of("src/database.ts");
+        dependents.sort();
+        assert_eq!(
+            dependents,
+            vec![
+                "src/auth.ts".to_string(),
+                "src/database.ts".to_string(),
+                "src/session.ts".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_manifest_remove_file_drops_reverse_edges() {
+        let mut manifest = Manifest::new();
+
+        manifest.add_file(
+            "src/database.ts",
+            Metadata {
+                exports: vec![],
+                imports: vec![],
+                dependencies: vec![],
+                loc: 10,
+            },
+        );
+        manifest.add_file(
+            "src/auth.ts",
+            Metadata {
+                exports: vec![],
+                imports: vec![],
+                dependencies: vec!["./database".to_string()],
+                loc: 10,
+            },
+        );
+        assert_eq!(manifest.dependents_of("src/database.ts").len(), 1);
+
+        manifest.remove_file("src/auth.ts");
+        assert!(manifest.dependents_of("src/database.ts").is_empty());
+    }
 }
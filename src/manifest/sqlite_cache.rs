@@ -0,0 +1,289 @@
+//! Optional SQLite-backed persistence for [`Manifest`], for trees large
+//! enough that [`Manifest::load_from_sidecars`] reading and rewriting its
+//! whole JSON cache on each start shows up as the bottleneck. Stores one
+//! row per sidecar (mtime, size, and its parsed [`FileEntry`] as JSON) plus
+//! a table materializing `export_index`, so a warm start only reparses and
+//! rewrites the rows for sidecars whose mtime or size actually changed.
+//!
+//! Pairs naturally with a hot-reload watcher (see `crate::mcp`): an
+//! incremental patch from a filesystem event and an incremental rescan from
+//! this cache use the same unit of work, one sidecar at a time.
+
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use super::{parse_sidecar, sidecar_key, FileEntry, Manifest};
+
+pub(super) fn load_cached(root: &Path, db_path: &Path) -> Result<Manifest> {
+    let conn = open(db_path)?;
+    let mut manifest = Manifest::new();
+    let known = read_stats(&conn)?;
+    let mut seen: HashSet<String> = HashSet::new();
+
+    let walker = ignore::WalkBuilder::new(root).standard_filters(true).build();
+    for entry in walker.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("fmm") {
+            continue;
+        }
+
+        let key = sidecar_key(path, root);
+        seen.insert(key.clone());
+
+        let Ok(meta) = std::fs::metadata(path) else {
+            continue;
+        };
+        let mtime = meta.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+        let size = meta.len();
+
+        if let Some((prev_mtime, prev_size)) = known.get(&key) {
+            if *prev_mtime == mtime && *prev_size == size {
+                if let Some((file_path, file_entry)) = read_entry(&conn, &key)? {
+                    manifest.insert_file_entry(file_path, file_entry);
+                    continue;
+                }
+            }
+        }
+
+        let Ok(content) = std::fs::read_to_string(path) else {
+            continue;
+        };
+        let Some((file_path, file_entry)) = parse_sidecar(&content) else {
+            continue;
+        };
+        let file_path = if !file_path.is_empty() {
+            file_path
+        } else {
+            key.clone()
+        };
+
+        write_entry(&conn, &key, &file_path, mtime, size, &file_entry)?;
+        manifest.insert_file_entry(file_path, file_entry);
+    }
+
+    let stale: Vec<&String> = known.keys().filter(|k| !seen.contains(*k)).collect();
+    for key in stale {
+        delete_entry(&conn, key)?;
+    }
+
+    Ok(manifest)
+}
+
+fn open(db_path: &Path) -> Result<Connection> {
+    let conn = Connection::open(db_path)
+        .with_context(|| format!("Failed to open index database at {}", db_path.display()))?;
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS files (
+            key TEXT PRIMARY KEY,
+            file_path TEXT NOT NULL,
+            mtime_secs INTEGER NOT NULL,
+            mtime_nanos INTEGER NOT NULL,
+            size INTEGER NOT NULL,
+            entry_json TEXT NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS export_index (
+            export TEXT PRIMARY KEY,
+            file_key TEXT NOT NULL
+        );",
+    )?;
+    Ok(conn)
+}
+
+fn read_stats(conn: &Connection) -> Result<HashMap<String, (SystemTime, u64)>> {
+    let mut stmt = conn.prepare("SELECT key, mtime_secs, mtime_nanos, size FROM files")?;
+    let rows = stmt.query_map([], |row| {
+        let key: String = row.get(0)?;
+        let secs: i64 = row.get(1)?;
+        let nanos: u32 = row.get(2)?;
+        let size: i64 = row.get(3)?;
+        Ok((key, secs, nanos, size))
+    })?;
+
+    let mut stats = HashMap::new();
+    for row in rows {
+        let (key, secs, nanos, size) = row?;
+        let mtime = SystemTime::UNIX_EPOCH + Duration::new(secs.max(0) as u64, nanos);
+        stats.insert(key, (mtime, size.max(0) as u64));
+    }
+    Ok(stats)
+}
+
+fn read_entry(conn: &Connection, key: &str) -> Result<Option<(String, FileEntry)>> {
+    let mut stmt = conn.prepare("SELECT file_path, entry_json FROM files WHERE key = ?1")?;
+    let mut rows = stmt.query(params![key])?;
+    let Some(row) = rows.next()? else {
+        return Ok(None);
+    };
+    let file_path: String = row.get(0)?;
+    let entry_json: String = row.get(1)?;
+    let entry: FileEntry =
+        serde_json::from_str(&entry_json).context("Corrupt cached index entry")?;
+    Ok(Some((file_path, entry)))
+}
+
+fn write_entry(
+    conn: &Connection,
+    key: &str,
+    file_path: &str,
+    mtime: SystemTime,
+    size: u64,
+    entry: &FileEntry,
+) -> Result<()> {
+    let since_epoch = mtime.duration_since(SystemTime::UNIX_EPOCH).unwrap_or_default();
+    let entry_json = serde_json::to_string(entry)?;
+
+    conn.execute(
+        "INSERT INTO files (key, file_path, mtime_secs, mtime_nanos, size, entry_json)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+         ON CONFLICT(key) DO UPDATE SET
+            file_path = excluded.file_path,
+            mtime_secs = excluded.mtime_secs,
+            mtime_nanos = excluded.mtime_nanos,
+            size = excluded.size,
+            entry_json = excluded.entry_json",
+        params![
+            key,
+            file_path,
+            since_epoch.as_secs() as i64,
+            since_epoch.subsec_nanos(),
+            size as i64,
+            entry_json,
+        ],
+    )?;
+
+    conn.execute("DELETE FROM export_index WHERE file_key = ?1", params![key])?;
+    for export in &entry.exports {
+        conn.execute(
+            "INSERT OR REPLACE INTO export_index (export, file_key) VALUES (?1, ?2)",
+            params![export, key],
+        )?;
+    }
+
+    Ok(())
+}
+
+fn delete_entry(conn: &Connection, key: &str) -> Result<()> {
+    conn.execute("DELETE FROM files WHERE key = ?1", params![key])?;
+    conn.execute("DELETE FROM export_index WHERE file_key = ?1", params![key])?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::ExportLines;
+    use tempfile::TempDir;
+
+    fn write_sidecar(tmp: &TempDir, name: &str, content: &str) -> std::path::PathBuf {
+        let path = tmp.path().join(name);
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn load_cached_parses_a_fresh_sidecar() {
+        let tmp = TempDir::new().unwrap();
+        write_sidecar(
+            &tmp,
+            "auth.ts.fmm",
+            "file: auth.ts\nexports: [validateUser]\nloc: 10\n",
+        );
+        let db_path = tmp.path().join("index.db");
+
+        let manifest = load_cached(tmp.path(), &db_path).unwrap();
+        assert!(manifest.has_file("auth.ts"));
+        assert_eq!(
+            manifest.export_index.get("validateUser"),
+            Some(&"auth.ts".to_string())
+        );
+    }
+
+    #[test]
+    fn load_cached_keeps_the_entry_on_a_second_load_with_nothing_changed() {
+        let tmp = TempDir::new().unwrap();
+        let sidecar = write_sidecar(
+            &tmp,
+            "auth.ts.fmm",
+            "file: auth.ts\nexports: [validateUser]\nloc: 10\n",
+        );
+        let db_path = tmp.path().join("index.db");
+        load_cached(tmp.path(), &db_path).unwrap();
+
+        // Corrupt the sidecar on disk without touching its mtime/size isn't
+        // practical in a test, so instead confirm the entry is still indexed
+        // on a second load with nothing changed.
+        let second = load_cached(tmp.path(), &db_path).unwrap();
+        assert_eq!(
+            second.export_index.get("validateUser"),
+            Some(&"auth.ts".to_string())
+        );
+        drop(sidecar);
+    }
+
+    #[test]
+    fn load_cached_picks_up_an_edited_sidecar() {
+        let tmp = TempDir::new().unwrap();
+        let sidecar = write_sidecar(
+            &tmp,
+            "auth.ts.fmm",
+            "file: auth.ts\nexports: [validateUser]\nloc: 10\n",
+        );
+        let db_path = tmp.path().join("index.db");
+        load_cached(tmp.path(), &db_path).unwrap();
+
+        std::fs::write(
+            &sidecar,
+            "file: auth.ts\nexports: [validateSession]\nloc: 12\n",
+        )
+        .unwrap();
+        let manifest = load_cached(tmp.path(), &db_path).unwrap();
+
+        assert!(manifest.export_index.contains_key("validateSession"));
+        assert!(!manifest.export_index.contains_key("validateUser"));
+    }
+
+    #[test]
+    fn load_cached_drops_rows_for_deleted_sidecars() {
+        let tmp = TempDir::new().unwrap();
+        let sidecar = write_sidecar(
+            &tmp,
+            "auth.ts.fmm",
+            "file: auth.ts\nexports: [validateUser]\nloc: 10\n",
+        );
+        let db_path = tmp.path().join("index.db");
+        load_cached(tmp.path(), &db_path).unwrap();
+
+        std::fs::remove_file(&sidecar).unwrap();
+        let manifest = load_cached(tmp.path(), &db_path).unwrap();
+
+        assert!(!manifest.has_file("auth.ts"));
+        let conn = open(&db_path).unwrap();
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM files", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn write_entry_persists_export_lines_through_entry_json() {
+        let tmp = TempDir::new().unwrap();
+        let db_path = tmp.path().join("index.db");
+        let conn = open(&db_path).unwrap();
+        let entry = FileEntry {
+            exports: vec!["createSession".to_string()],
+            export_lines: Some(vec![ExportLines { start: 5, end: 20 }]),
+            imports: vec![],
+            dependencies: vec![],
+            loc: 30,
+        };
+
+        write_entry(&conn, "src/auth", "src/auth.ts", SystemTime::now(), 100, &entry).unwrap();
+        let (file_path, loaded) = read_entry(&conn, "src/auth").unwrap().unwrap();
+
+        assert_eq!(file_path, "src/auth.ts");
+        assert_eq!(loaded.export_lines, entry.export_lines);
+    }
+}
@@ -1,12 +1,24 @@
-use crate::manifest::Manifest;
+use crate::manifest::{ExportLines, Manifest};
 use anyhow::Result;
+use notify::RecursiveMode;
+use notify_debouncer_full::{new_debouncer, DebounceEventResult};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
 use std::io::{self, BufRead, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 const PROTOCOL_VERSION: &str = "2024-11-05";
 
+/// Version of the `{ "format_version", "tool", "data" }` envelope every tool
+/// result is wrapped in. Bump only on breaking shape changes — additive
+/// fields don't need a bump. Advertised in `initialize`'s `serverInfo` and
+/// checked against the `format_version` a `tools/call` request may pin.
+const FORMAT_VERSION: u64 = 1;
+
 #[derive(Debug, Deserialize)]
 struct JsonRpcRequest {
     #[serde(rename = "jsonrpc")]
@@ -38,6 +50,7 @@ struct FileInfoArgs {
 #[derive(Debug, Deserialize)]
 struct DependencyGraphArgs {
     file: String,
+    path: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -47,6 +60,14 @@ struct SearchArgs {
     depends_on: Option<String>,
     min_loc: Option<usize>,
     max_loc: Option<usize>,
+    path: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReadSymbolArgs {
+    name: String,
+    context: Option<usize>,
+    render: Option<bool>,
 }
 
 #[derive(Debug, Serialize)]
@@ -78,6 +99,61 @@ struct Tool {
 pub struct McpServer {
     manifest: Option<Manifest>,
     root: PathBuf,
+    /// `.fmm` sidecar paths the background watcher spawned by [`Self::run`]
+    /// has observed change since the last [`Self::sync_manifest`] call.
+    /// Shared with the watcher's debounce callback, so the lock is only ever
+    /// held for the cheap insert/drain, never across a parse.
+    pending_sidecar_changes: Arc<Mutex<HashSet<PathBuf>>>,
+    /// Access policy applied when a `tools/call` carries no `token`, or one
+    /// this server doesn't recognize. `None` (the default) is fully open —
+    /// single-tenant embedders that never call [`Self::set_default_policy`]
+    /// see no behavior change from before this existed.
+    default_policy: Option<ToolPolicy>,
+    /// Per-client policies keyed by the `token` a `tools/call` request may
+    /// carry alongside `name`/`arguments`, for hosts that expose this server
+    /// to more than one agent at different trust levels.
+    token_policies: HashMap<String, ToolPolicy>,
+}
+
+/// What a client is allowed to do: which tool names it may invoke (`None`
+/// = all), and which part of the indexed tree its file-scoped tools may see
+/// (`None` = repo-wide). Built with [`ToolPolicy::build`], which compiles
+/// the path patterns once up front using the same glob/regex syntax as the
+/// `path` filter on `fmm_search`/`fmm_dependency_graph` — see
+/// `compile_path_filter`.
+pub struct ToolPolicy {
+    allowed_tools: Option<HashSet<String>>,
+    allowed_paths: Option<Vec<Regex>>,
+}
+
+impl ToolPolicy {
+    pub fn build(
+        allowed_tools: Option<HashSet<String>>,
+        allowed_path_globs: Option<Vec<String>>,
+    ) -> Result<Self, String> {
+        let allowed_paths = allowed_path_globs
+            .map(|globs| {
+                globs
+                    .iter()
+                    .map(|g| compile_path_filter(g))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .transpose()?;
+        Ok(ToolPolicy {
+            allowed_tools,
+            allowed_paths,
+        })
+    }
+
+    fn permits_tool(&self, name: &str) -> bool {
+        self.allowed_tools.as_ref().is_none_or(|set| set.contains(name))
+    }
+
+    fn permits_path(&self, path: &str) -> bool {
+        self.allowed_paths
+            .as_ref()
+            .is_none_or(|patterns| patterns.iter().any(|re| re.is_match(path)))
+    }
 }
 
 impl Default for McpServer {
@@ -89,13 +165,80 @@ impl Default for McpServer {
 impl McpServer {
     pub fn new() -> Self {
         // Safe default: empty path is harmless; MCP server will report "no sidecars" if cwd fails
-        let root = std::env::current_dir().unwrap_or_default();
-        let manifest = Manifest::load_from_sidecars(&root).ok();
-        Self { manifest, root }
+        Self::with_root(std::env::current_dir().unwrap_or_default())
+    }
+
+    /// Same as [`Self::new`] but with an explicit host root instead of the
+    /// process's current directory — lets tests (and embedders) point the
+    /// server at a fixture tree without `chdir`-ing the whole test process.
+    pub fn with_root(root: PathBuf) -> Self {
+        let manifest = load_manifest(&root).ok();
+        Self {
+            manifest,
+            root,
+            pending_sidecar_changes: Arc::new(Mutex::new(HashSet::new())),
+            default_policy: None,
+            token_policies: HashMap::new(),
+        }
+    }
+
+    /// Set the policy enforced on `tools/call` requests that carry no
+    /// `token`, or one not registered via [`Self::register_token_policy`].
+    pub fn set_default_policy(&mut self, policy: ToolPolicy) {
+        self.default_policy = Some(policy);
+    }
+
+    /// Scope a specific client's calls: `tools/call` requests carrying
+    /// `"token": token` are checked against `policy` instead of the default.
+    pub fn register_token_policy(&mut self, token: impl Into<String>, policy: ToolPolicy) {
+        self.token_policies.insert(token.into(), policy);
+    }
+
+    fn resolve_policy(&self, token: Option<&str>) -> Option<&ToolPolicy> {
+        token
+            .and_then(|t| self.token_policies.get(t))
+            .or(self.default_policy.as_ref())
     }
 
+    /// Full rebuild: re-walks every `.fmm` sidecar under `root` via
+    /// `load_manifest`. Used at startup and as the fallback when [`Self::sync_manifest`]
+    /// can't patch a change in place (no manifest yet, or a sidecar that
+    /// fails to parse).
     fn reload(&mut self) {
-        self.manifest = Manifest::load_from_sidecars(&self.root).ok();
+        self.manifest = load_manifest(&self.root).ok();
+    }
+
+    /// Patches in every sidecar the background watcher (see [`Self::run`])
+    /// has recorded as changed since the last call, instead of paying for a
+    /// full [`Self::reload`] before every `tools/call` the way this server
+    /// used to. Falls back to a full reload if there's no manifest yet to
+    /// patch, or if a changed sidecar fails to parse.
+    fn sync_manifest(&mut self) {
+        let changed: Vec<PathBuf> = {
+            let mut pending = self.pending_sidecar_changes.lock().unwrap();
+            pending.drain().collect()
+        };
+        if changed.is_empty() {
+            return;
+        }
+
+        let mut needs_full_reload = self.manifest.is_none();
+        if let Some(manifest) = self.manifest.as_mut() {
+            for path in &changed {
+                if path.exists() {
+                    if !manifest.update_sidecar(path, &self.root) {
+                        needs_full_reload = true;
+                        break;
+                    }
+                } else {
+                    manifest.remove_sidecar(path, &self.root);
+                }
+            }
+        }
+
+        if needs_full_reload {
+            self.reload();
+        }
     }
 
     fn require_manifest(&self) -> Result<&Manifest, String> {
@@ -108,45 +251,104 @@ impl McpServer {
         let stdin = io::stdin();
         let mut stdout = io::stdout();
 
+        // Kept alive for the life of this loop — dropping the debouncer
+        // stops the watch. A watcher that fails to start (e.g. the root
+        // vanished) just means sidecars won't hot-reload; tools/call still
+        // works off whichever manifest was last loaded, same as before this
+        // existed.
+        let pending = Arc::clone(&self.pending_sidecar_changes);
+        let _debouncer = new_debouncer(
+            Duration::from_millis(300),
+            None,
+            move |result: DebounceEventResult| {
+                if let Ok(events) = result {
+                    let mut pending = pending.lock().unwrap();
+                    for event in events {
+                        for path in &event.paths {
+                            if path.extension().and_then(|e| e.to_str()) == Some("fmm") {
+                                pending.insert(path.clone());
+                            }
+                        }
+                    }
+                }
+            },
+        )
+        .ok()
+        .and_then(|mut debouncer| {
+            debouncer
+                .watch(&self.root, RecursiveMode::Recursive)
+                .ok()
+                .map(|_| debouncer)
+        });
+
         for line in stdin.lock().lines() {
             let line = line?;
             if line.is_empty() {
                 continue;
             }
 
-            let request: JsonRpcRequest = match serde_json::from_str(&line) {
-                Ok(req) => req,
-                Err(e) => {
-                    let error_response = JsonRpcResponse {
-                        jsonrpc: "2.0".to_string(),
-                        id: Value::Null,
-                        result: None,
-                        error: Some(JsonRpcError {
-                            code: -32700,
-                            message: format!("Parse error: {}", e),
-                            data: None,
-                        }),
-                    };
-                    writeln!(stdout, "{}", serde_json::to_string(&error_response)?)?;
-                    stdout.flush()?;
-                    continue;
-                }
+            if let Some(payload) = self.process_line(&line) {
+                writeln!(stdout, "{}", serde_json::to_string(&payload)?)?;
+                stdout.flush()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Handle one line of input, which per JSON-RPC 2.0 is either a single
+    /// request object or a *batch*: a top-level array of request objects.
+    /// Returns the JSON payload to write back (a single response object, or
+    /// an array of responses for a batch), or `None` if nothing should be
+    /// written (a lone notification, or an all-notification batch).
+    fn process_line(&mut self, line: &str) -> Option<Value> {
+        let raw: Value = match serde_json::from_str(line) {
+            Ok(v) => v,
+            Err(e) => return Some(parse_error_response(&e)),
+        };
+
+        if let Value::Array(items) = raw {
+            if items.is_empty() {
+                return Some(serde_json::to_value(invalid_request_error()).unwrap());
+            }
+
+            let requests: Vec<JsonRpcRequest> = match items
+                .into_iter()
+                .map(serde_json::from_value)
+                .collect::<Result<Vec<_>, _>>()
+            {
+                Ok(reqs) => reqs,
+                Err(e) => return Some(parse_error_response(&e)),
             };
 
-            // Rebuild index from sidecars before handling tool calls
-            if request.method == "tools/call" {
-                self.reload();
+            // Patch in whatever the watcher has seen change, once per
+            // batch rather than once per element.
+            if requests.iter().any(|r| r.method == "tools/call") {
+                self.sync_manifest();
             }
 
-            let response = self.handle_request(&request);
+            let responses: Vec<JsonRpcResponse> = requests
+                .iter()
+                .filter_map(|r| self.handle_request(r))
+                .collect();
 
-            if let Some(resp) = response {
-                writeln!(stdout, "{}", serde_json::to_string(&resp)?)?;
-                stdout.flush()?;
+            if responses.is_empty() {
+                return None;
             }
+            return Some(serde_json::to_value(responses).unwrap());
         }
 
-        Ok(())
+        let request: JsonRpcRequest = match serde_json::from_value(raw) {
+            Ok(req) => req,
+            Err(e) => return Some(parse_error_response(&e)),
+        };
+
+        if request.method == "tools/call" {
+            self.sync_manifest();
+        }
+
+        self.handle_request(&request)
+            .map(|resp| serde_json::to_value(resp).unwrap())
     }
 
     fn handle_request(&mut self, request: &JsonRpcRequest) -> Option<JsonRpcResponse> {
@@ -189,7 +391,8 @@ impl McpServer {
             },
             "serverInfo": {
                 "name": "fmm",
-                "version": env!("CARGO_PKG_VERSION")
+                "version": env!("CARGO_PKG_VERSION"),
+                "formatVersion": FORMAT_VERSION
             }
         }))
     }
@@ -250,6 +453,10 @@ impl McpServer {
                         "file": {
                             "type": "string",
                             "description": "File path to analyze — returns all upstream dependencies and downstream dependents"
+                        },
+                        "path": {
+                            "type": "string",
+                            "description": "Restrict upstream/downstream to files under this glob (e.g. 'src/db/**', '**/*.ts'), or an anchored regex prefixed with 're:'"
                         }
                     },
                     "required": ["file"]
@@ -257,7 +464,7 @@ impl McpServer {
             },
             Tool {
                 name: "fmm_search".to_string(),
-                description: "Search files by structural criteria: exported symbol, imported package, local dependency, or LOC range. Filters combine with AND logic. Use for 'which files use crypto?', 'what depends on auth?'.".to_string(),
+                description: "Search files by structural criteria: exported symbol, imported package, local dependency, LOC range, or path. Filters combine with AND logic. Use for 'which files use crypto?', 'what depends on auth?'.".to_string(),
                 input_schema: json!({
                     "type": "object",
                     "properties": {
@@ -280,10 +487,36 @@ impl McpServer {
                         "max_loc": {
                             "type": "integer",
                             "description": "Maximum lines of code — find files smaller than this"
+                        },
+                        "path": {
+                            "type": "string",
+                            "description": "Scope results to files matching this glob (e.g. 'src/auth/**', '**/*.ts'), or an anchored regex prefixed with 're:'"
                         }
                     }
                 }),
             },
+            Tool {
+                name: "fmm_read_symbol".to_string(),
+                description: "Read an export's declared source range without grepping or reading the whole file. Optionally widen the window with 'context' lines of surrounding code, or get a line-numbered 'render' with the symbol's own lines marked.".to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "name": {
+                            "type": "string",
+                            "description": "Exact export name to read"
+                        },
+                        "context": {
+                            "type": "integer",
+                            "description": "Lines of surrounding source to include on each side of the symbol's range (clamped to file bounds)"
+                        },
+                        "render": {
+                            "type": "boolean",
+                            "description": "Also return a gutter-annotated snippet: each line prefixed with its 1-based line number, symbol lines marked with '>'"
+                        }
+                    },
+                    "required": ["name"]
+                }),
+            },
             // fmm_get_manifest and fmm_project_overview REMOVED —
             // dumping the entire index is an anti-pattern (ALP-396).
             // Use targeted tools: fmm_lookup_export, fmm_search, fmm_dependency_graph.
@@ -309,30 +542,71 @@ impl McpServer {
                     data: None,
                 })?;
 
+        if let Some(requested) = params.get("format_version").and_then(|v| v.as_u64()) {
+            if requested != FORMAT_VERSION {
+                return Ok(json!({
+                    "content": [{
+                        "type": "text",
+                        "text": format!(
+                            "Unsupported format_version {requested}: this server implements format_version {FORMAT_VERSION}"
+                        )
+                    }],
+                    "isError": true
+                }));
+            }
+        }
+
         let arguments = params.get("arguments").cloned().unwrap_or(json!({}));
+        let token = params.get("token").and_then(|v| v.as_str());
+        let policy = self.resolve_policy(token);
+
+        if let Some(policy) = policy {
+            if !policy.permits_tool(tool_name) {
+                return Ok(json!({
+                    "content": [{
+                        "type": "text",
+                        "text": format!("Tool '{tool_name}' is not permitted for this client")
+                    }],
+                    "isError": true
+                }));
+            }
+        }
 
         let result = match tool_name {
             // Original tools
             "fmm_lookup_export" => self.tool_lookup_export(&arguments),
             "fmm_list_exports" => self.tool_list_exports(&arguments),
             "fmm_file_info" => self.tool_file_info(&arguments),
-            "fmm_dependency_graph" => self.tool_dependency_graph(&arguments),
-            "fmm_search" => self.tool_search(&arguments),
+            "fmm_dependency_graph" => self.tool_dependency_graph(&arguments, policy),
+            "fmm_search" => self.tool_search(&arguments, policy),
+            "fmm_read_symbol" => self.tool_read_symbol(&arguments, policy),
             // Legacy aliases
             "fmm_find_export" => self.tool_lookup_export(&arguments),
             "fmm_find_symbol" => self.tool_lookup_export(&arguments),
             "fmm_file_metadata" => self.tool_file_info(&arguments),
-            "fmm_analyze_dependencies" => self.tool_dependency_graph(&arguments),
+            "fmm_analyze_dependencies" => self.tool_dependency_graph(&arguments, policy),
             _ => Err(format!("Unknown tool: {}", tool_name)),
         };
 
         match result {
-            Ok(text) => Ok(json!({
-                "content": [{
-                    "type": "text",
-                    "text": text
-                }]
-            })),
+            Ok(data) => {
+                let envelope = json!({
+                    "format_version": FORMAT_VERSION,
+                    "tool": tool_name,
+                    "data": data,
+                });
+                let text = serde_json::to_string_pretty(&envelope).map_err(|e| JsonRpcError {
+                    code: -32603,
+                    message: e.to_string(),
+                    data: None,
+                })?;
+                Ok(json!({
+                    "content": [{
+                        "type": "text",
+                        "text": text
+                    }]
+                }))
+            }
             Err(e) => Ok(json!({
                 "content": [{
                     "type": "text",
@@ -343,7 +617,7 @@ impl McpServer {
         }
     }
 
-    fn tool_lookup_export(&self, args: &Value) -> Result<String, String> {
+    fn tool_lookup_export(&self, args: &Value) -> Result<Value, String> {
         let manifest = self.require_manifest()?;
 
         let args: LookupExportArgs =
@@ -352,20 +626,22 @@ impl McpServer {
         match manifest.export_index.get(&args.name) {
             Some(file_path) => {
                 let entry = manifest.files.get(file_path);
-                let result = json!({
+                Ok(json!({
                     "file": file_path,
                     "exports": entry.map(|e| &e.exports),
                     "imports": entry.map(|e| &e.imports),
                     "dependencies": entry.map(|e| &e.dependencies),
                     "loc": entry.map(|e| e.loc),
-                });
-                serde_json::to_string_pretty(&result).map_err(|e| e.to_string())
+                }))
             }
-            None => Err(format!("Export '{}' not found", args.name)),
+            None => Err(not_found_with_suggestions(
+                &format!("Export '{}' not found", args.name),
+                manifest.suggest_exports(&args.name, 5),
+            )),
         }
     }
 
-    fn tool_list_exports(&self, args: &Value) -> Result<String, String> {
+    fn tool_list_exports(&self, args: &Value) -> Result<Value, String> {
         let manifest = self.require_manifest()?;
 
         let args: ListExportsArgs =
@@ -374,13 +650,10 @@ impl McpServer {
         if let Some(ref file_path) = args.file {
             // List exports from a specific file
             match manifest.files.get(file_path) {
-                Some(entry) => {
-                    let result = json!({
-                        "file": file_path,
-                        "exports": entry.exports,
-                    });
-                    serde_json::to_string_pretty(&result).map_err(|e| e.to_string())
-                }
+                Some(entry) => Ok(json!({
+                    "file": file_path,
+                    "exports": entry.exports,
+                })),
                 None => Err(format!("File '{}' not found in manifest", file_path)),
             }
         } else if let Some(ref pat) = args.pattern {
@@ -397,7 +670,7 @@ impl McpServer {
                 .iter()
                 .map(|(name, path)| json!({"export": name, "file": path}))
                 .collect();
-            serde_json::to_string_pretty(&result).map_err(|e| e.to_string())
+            Ok(json!(result))
         } else {
             // List all exports (grouped by file)
             let mut by_file: Vec<(&String, Vec<&String>)> = Vec::new();
@@ -412,50 +685,78 @@ impl McpServer {
                 .iter()
                 .map(|(path, exports)| json!({"file": path, "exports": exports}))
                 .collect();
-            serde_json::to_string_pretty(&result).map_err(|e| e.to_string())
+            Ok(json!(result))
         }
     }
 
-    fn tool_file_info(&self, args: &Value) -> Result<String, String> {
+    fn tool_file_info(&self, args: &Value) -> Result<Value, String> {
         let manifest = self.require_manifest()?;
 
         let args: FileInfoArgs =
             serde_json::from_value(args.clone()).map_err(|e| format!("Invalid arguments: {e}"))?;
 
         match manifest.files.get(&args.file) {
-            Some(entry) => {
-                let result = json!({
-                    "file": args.file,
-                    "exports": entry.exports,
-                    "imports": entry.imports,
-                    "dependencies": entry.dependencies,
-                    "loc": entry.loc,
-                });
-                serde_json::to_string_pretty(&result).map_err(|e| e.to_string())
-            }
-            None => Err(format!("File '{}' not found in manifest", args.file)),
+            Some(entry) => Ok(json!({
+                "file": args.file,
+                "exports": entry.exports,
+                "imports": entry.imports,
+                "dependencies": entry.dependencies,
+                "loc": entry.loc,
+            })),
+            None => Err(not_found_with_suggestions(
+                &format!("File '{}' not found in manifest", args.file),
+                manifest.suggest_files(&args.file, 5),
+            )),
         }
     }
 
-    fn tool_dependency_graph(&self, args: &Value) -> Result<String, String> {
+    fn tool_dependency_graph(
+        &self,
+        args: &Value,
+        policy: Option<&ToolPolicy>,
+    ) -> Result<Value, String> {
         let manifest = self.require_manifest()?;
 
         let args: DependencyGraphArgs =
             serde_json::from_value(args.clone()).map_err(|e| format!("Invalid arguments: {e}"))?;
 
+        if policy.is_some_and(|p| !p.permits_path(&args.file)) {
+            return Err(format!("File '{}' not found in manifest", args.file));
+        }
+
         let entry = manifest
             .files
             .get(&args.file)
             .ok_or_else(|| format!("File '{}' not found in manifest", args.file))?;
 
-        // Upstream: files this file depends on (its dependencies)
-        let upstream: Vec<&str> = entry.dependencies.iter().map(|s| s.as_str()).collect();
+        let path_filter = args.path.as_deref().map(compile_path_filter).transpose()?;
+        let path_allowed = |path: &str| {
+            path_filter.as_ref().is_none_or(|re| re.is_match(path))
+                && policy.is_none_or(|p| p.permits_path(path))
+        };
+
+        // Upstream: files this file depends on (its dependencies), scoped to
+        // `path` by resolving each spec to the manifest key it points at.
+        // Specs that don't resolve to an indexed file are dropped once a
+        // `path` filter is active — there's no key left to test it against.
+        let upstream: Vec<&str> = entry
+            .dependencies
+            .iter()
+            .map(|s| s.as_str())
+            .filter(|dep| {
+                (path_filter.is_none() && policy.is_none())
+                    || manifest
+                        .resolve_dependency(&args.file, dep)
+                        .is_some_and(|resolved| path_allowed(&resolved))
+            })
+            .collect();
 
         // Downstream: files that depend on this file
         let mut downstream: Vec<&String> = manifest
             .files
             .iter()
             .filter(|(path, _)| path.as_str() != args.file)
+            .filter(|(path, _)| path_allowed(path))
             .filter(|(path, e)| {
                 e.dependencies
                     .iter()
@@ -465,21 +766,26 @@ impl McpServer {
             .collect();
         downstream.sort();
 
-        let result = json!({
+        Ok(json!({
             "file": args.file,
             "upstream": upstream,
             "downstream": downstream,
             "imports": entry.imports,
-        });
-        serde_json::to_string_pretty(&result).map_err(|e| e.to_string())
+        }))
     }
 
-    fn tool_search(&self, args: &Value) -> Result<String, String> {
+    fn tool_search(&self, args: &Value, policy: Option<&ToolPolicy>) -> Result<Value, String> {
         let manifest = self.require_manifest()?;
 
         let args: SearchArgs =
             serde_json::from_value(args.clone()).map_err(|e| format!("Invalid arguments: {e}"))?;
 
+        let path_filter = args.path.as_deref().map(compile_path_filter).transpose()?;
+        let path_allowed = |path: &str| {
+            path_filter.as_ref().is_none_or(|re| re.is_match(path))
+                && policy.is_none_or(|p| p.permits_path(path))
+        };
+
         let mut results: Vec<(&String, &crate::manifest::FileEntry)> = Vec::new();
 
         let has_export = args.export.is_some();
@@ -488,9 +794,19 @@ impl McpServer {
 
         // Search by export
         if let Some(ref export) = args.export {
-            if let Some(file_path) = manifest.export_index.get(export.as_str()) {
-                if let Some(entry) = manifest.files.get(file_path) {
-                    results.push((file_path, entry));
+            match manifest.export_index.get(export.as_str()) {
+                Some(file_path) => {
+                    if path_allowed(file_path) {
+                        if let Some(entry) = manifest.files.get(file_path) {
+                            results.push((file_path, entry));
+                        }
+                    }
+                }
+                None => {
+                    return Err(not_found_with_suggestions(
+                        &format!("Export '{}' not found", export),
+                        manifest.suggest_exports(export, 5),
+                    ));
                 }
             }
         }
@@ -498,10 +814,11 @@ impl McpServer {
         // Search by imports
         if let Some(ref import_name) = args.imports {
             for (file_path, entry) in &manifest.files {
-                if entry
-                    .imports
-                    .iter()
-                    .any(|i| i.contains(import_name.as_str()))
+                if path_allowed(file_path)
+                    && entry
+                        .imports
+                        .iter()
+                        .any(|i| i.contains(import_name.as_str()))
                     && !results.iter().any(|(f, _)| *f == file_path)
                 {
                     results.push((file_path, entry));
@@ -512,10 +829,11 @@ impl McpServer {
         // Search by depends_on
         if let Some(ref dep_path) = args.depends_on {
             for (file_path, entry) in &manifest.files {
-                if entry
-                    .dependencies
-                    .iter()
-                    .any(|d| d.contains(dep_path.as_str()))
+                if path_allowed(file_path)
+                    && entry
+                        .dependencies
+                        .iter()
+                        .any(|d| d.contains(dep_path.as_str()))
                     && !results.iter().any(|(f, _)| *f == file_path)
                 {
                     results.push((file_path, entry));
@@ -526,7 +844,9 @@ impl McpServer {
         if args.min_loc.is_some() || args.max_loc.is_some() {
             if results.is_empty() && !has_export && !has_imports && !has_depends_on {
                 for (file_path, entry) in &manifest.files {
-                    results.push((file_path, entry));
+                    if path_allowed(file_path) {
+                        results.push((file_path, entry));
+                    }
                 }
             }
 
@@ -537,7 +857,7 @@ impl McpServer {
             });
         }
 
-        // If no filters, return all
+        // If no filters, return all (scoped to `path`, if given)
         if !has_export
             && !has_imports
             && !has_depends_on
@@ -545,7 +865,9 @@ impl McpServer {
             && args.max_loc.is_none()
         {
             for (file_path, entry) in &manifest.files {
-                results.push((file_path, entry));
+                if path_allowed(file_path) {
+                    results.push((file_path, entry));
+                }
             }
         }
 
@@ -562,8 +884,180 @@ impl McpServer {
             })
             .collect();
 
-        serde_json::to_string_pretty(&output).map_err(|e| e.to_string())
+        Ok(json!(output))
+    }
+
+    /// `fmm_read_symbol`: read the source lines an export's sidecar range
+    /// points at, optionally widened by `context` lines on each side
+    /// (clamped to the file's bounds) and/or rendered with a line-number
+    /// gutter marking which lines belong to the symbol itself.
+    fn tool_read_symbol(&self, args: &Value, policy: Option<&ToolPolicy>) -> Result<Value, String> {
+        let manifest = self.require_manifest()?;
+
+        let args: ReadSymbolArgs =
+            serde_json::from_value(args.clone()).map_err(|e| format!("Invalid arguments: {e}"))?;
+
+        let location = manifest.export_locations.get(&args.name).ok_or_else(|| {
+            not_found_with_suggestions(
+                &format!("Export '{}' not found", args.name),
+                manifest.suggest_exports(&args.name, 5),
+            )
+        })?;
+
+        // A symbol outside the caller's policy is reported exactly like a
+        // missing one — no suggestions, so the response doesn't confirm the
+        // symbol exists in a part of the tree the client can't see.
+        if policy.is_some_and(|p| !p.permits_path(&location.file)) {
+            return Err(format!("Export '{}' not found", args.name));
+        }
+        let symbol_lines = location.lines.as_ref().ok_or_else(|| {
+            format!(
+                "No line range recorded for '{}' — its sidecar predates v0.3 line tracking",
+                args.name
+            )
+        })?;
+
+        let content = std::fs::read_to_string(self.root.join(&location.file))
+            .map_err(|e| format!("Failed to read '{}': {e}", location.file))?;
+        let file_lines: Vec<&str> = content.lines().collect();
+        let last_line = file_lines.len().saturating_sub(1);
+
+        let context = args.context.unwrap_or(0);
+        let window_start = symbol_lines.start.saturating_sub(1).saturating_sub(context);
+        let window_end = (symbol_lines.end.saturating_sub(1) + context).min(last_line);
+
+        let slice = file_lines
+            .get(window_start..=window_end)
+            .unwrap_or_default();
+
+        let mut result = json!({
+            "file": location.file,
+            "lines": [symbol_lines.start, symbol_lines.end],
+            "source": slice.join("\n"),
+            "context_lines": [window_start + 1, window_end + 1],
+        });
+
+        if args.render.unwrap_or(false) {
+            result["render"] = json!(render_snippet(slice, window_start + 1, symbol_lines));
+        }
+
+        Ok(result)
+    }
+}
+
+/// Load the sidecar index for `root`, using the SQLite-backed
+/// [`Manifest::load_cached`] when `FMM_INDEX_DB` names a database path —
+/// worthwhile on a large monorepo where reading and rewriting the JSON
+/// cache of [`Manifest::load_from_sidecars`] on every load is slow — and
+/// falling back to it otherwise.
+fn load_manifest(root: &Path) -> Result<Manifest> {
+    match std::env::var_os("FMM_INDEX_DB") {
+        Some(db_path) => Manifest::load_cached(root, Path::new(&db_path)),
+        None => Manifest::load_from_sidecars(root),
+    }
+}
+
+/// Build a `-32700` Parse error response for a line (or batch element) that
+/// didn't deserialize as a `JsonRpcRequest`.
+fn parse_error_response(e: &serde_json::Error) -> Value {
+    serde_json::to_value(JsonRpcResponse {
+        jsonrpc: "2.0".to_string(),
+        id: Value::Null,
+        result: None,
+        error: Some(JsonRpcError {
+            code: -32700,
+            message: format!("Parse error: {}", e),
+            data: None,
+        }),
+    })
+    .unwrap()
+}
+
+/// `-32600` Invalid Request error for a JSON-RPC batch that is an empty
+/// array, as required by the spec.
+fn invalid_request_error() -> JsonRpcResponse {
+    JsonRpcResponse {
+        jsonrpc: "2.0".to_string(),
+        id: Value::Null,
+        result: None,
+        error: Some(JsonRpcError {
+            code: -32600,
+            message: "Invalid Request: empty batch".to_string(),
+            data: None,
+        }),
+    }
+}
+
+/// Append a cargo-style "Did you mean: a, b?" hint to a not-found message,
+/// built from [`Manifest::suggest_exports`]/[`Manifest::suggest_files`]
+/// results, so the agent doesn't have to fall back to grep for a near-miss
+/// name.
+fn not_found_with_suggestions(message: &str, suggestions: Vec<(String, f64)>) -> String {
+    if suggestions.is_empty() {
+        return message.to_string();
+    }
+
+    let names: Vec<&str> = suggestions.iter().map(|(name, _)| name.as_str()).collect();
+    format!("{message}. Did you mean: {}?", names.join(", "))
+}
+
+/// Render `lines` (starting at 1-based `first_lineno`) with a line-number
+/// gutter, marking the rows inside `symbol_lines` with a leading `>` so the
+/// boundary between the symbol and its surrounding context is visible at a
+/// glance.
+fn render_snippet(lines: &[&str], first_lineno: usize, symbol_lines: &ExportLines) -> String {
+    let last_lineno = first_lineno + lines.len().saturating_sub(1);
+    let width = last_lineno.to_string().len();
+
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            let lineno = first_lineno + i;
+            let marker = if lineno >= symbol_lines.start && lineno <= symbol_lines.end {
+                '>'
+            } else {
+                ' '
+            };
+            format!("{marker} {lineno:>width$} | {line}")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Compile a `path` filter value shared by `fmm_search` and
+/// `fmm_dependency_graph`: glob syntax by default (`src/auth/**` matches any
+/// depth under `src/auth`, `**/*.ts` matches a `.ts` file at any depth, `*`
+/// matches within a single path segment), or an anchored regex when the
+/// pattern is prefixed with `re:`. Compiled once per tool call and checked
+/// against normalized manifest keys, never a directory walk, so there's no
+/// need to reach for `ignore::overrides::Override` the way `cli::search`'s
+/// `--type` globs do.
+fn compile_path_filter(pattern: &str) -> Result<Regex, String> {
+    if let Some(raw) = pattern.strip_prefix("re:") {
+        return Regex::new(raw).map_err(|e| format!("Invalid path regex '{raw}': {e}"));
+    }
+
+    let mut regex = String::from("^");
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                regex.push_str(".*");
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            '.' | '+' | '(' | ')' | '|' | '^' | '$' | '[' | ']' | '{' | '}' | '\\' => {
+                regex.push('\\');
+                regex.push(c);
+            }
+            _ => regex.push(c),
+        }
     }
+    regex.push('$');
+
+    Regex::new(&regex).map_err(|e| format!("Invalid path glob '{pattern}': {e}"))
 }
 
 /// Check if a dependency path from `dependent_file` resolves to `target_file`.
@@ -611,6 +1105,27 @@ fn dep_matches(dep: &str, target_file: &str, dependent_file: &str) -> bool {
 mod tests {
     use super::*;
 
+    #[test]
+    fn not_found_with_suggestions_appends_hint() {
+        let message = not_found_with_suggestions(
+            "Export 'validateAuth' not found",
+            vec![
+                ("validateAuthToken".to_string(), 4.0),
+                ("validateAll".to_string(), 5.0),
+            ],
+        );
+        assert_eq!(
+            message,
+            "Export 'validateAuth' not found. Did you mean: validateAuthToken, validateAll?"
+        );
+    }
+
+    #[test]
+    fn not_found_with_suggestions_leaves_bare_message_when_empty() {
+        let message = not_found_with_suggestions("Export 'xyz' not found", vec![]);
+        assert_eq!(message, "Export 'xyz' not found");
+    }
+
     #[test]
     fn dep_matches_relative_path() {
         // dep "./types" from "src/index.ts" resolves to "src/types"
@@ -671,4 +1186,348 @@ mod tests {
         let server = McpServer::new();
         assert!(server.root.is_absolute() || server.root.as_os_str().is_empty());
     }
+
+    #[test]
+    fn process_line_batch_returns_array_of_responses() {
+        let mut server = McpServer::new();
+        let line = r#"[{"jsonrpc":"2.0","id":1,"method":"ping"},{"jsonrpc":"2.0","id":2,"method":"ping"}]"#;
+        let payload = server.process_line(line).unwrap();
+        let responses = payload.as_array().unwrap();
+        assert_eq!(responses.len(), 2);
+        assert_eq!(responses[0]["id"], json!(1));
+        assert_eq!(responses[1]["id"], json!(2));
+    }
+
+    #[test]
+    fn process_line_batch_of_only_notifications_emits_nothing() {
+        let mut server = McpServer::new();
+        let line = r#"[{"jsonrpc":"2.0","method":"notifications/initialized"}]"#;
+        assert!(server.process_line(line).is_none());
+    }
+
+    #[test]
+    fn process_line_empty_batch_is_invalid_request() {
+        let mut server = McpServer::new();
+        let payload = server.process_line("[]").unwrap();
+        assert_eq!(payload["error"]["code"], json!(-32600));
+    }
+
+    #[test]
+    fn process_line_single_request_still_works() {
+        let mut server = McpServer::new();
+        let line = r#"{"jsonrpc":"2.0","id":1,"method":"ping"}"#;
+        let payload = server.process_line(line).unwrap();
+        assert_eq!(payload["id"], json!(1));
+        assert_eq!(payload["result"], json!({}));
+    }
+
+    #[test]
+    fn with_root_loads_sidecars_from_the_given_directory() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join("auth.ts.fmm"),
+            "file: auth.ts\nexports: [validateUser]\nloc: 10\n",
+        )
+        .unwrap();
+
+        let server = McpServer::with_root(tmp.path().to_path_buf());
+        assert!(server.require_manifest().unwrap().has_file("auth.ts"));
+    }
+
+    #[test]
+    fn sync_manifest_patches_in_a_changed_sidecar_without_a_full_reload() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let sidecar = tmp.path().join("auth.ts.fmm");
+        std::fs::write(&sidecar, "file: auth.ts\nexports: [validateUser]\nloc: 10\n").unwrap();
+
+        let mut server = McpServer::with_root(tmp.path().to_path_buf());
+        assert!(server
+            .require_manifest()
+            .unwrap()
+            .export_index
+            .contains_key("validateUser"));
+
+        std::fs::write(&sidecar, "file: auth.ts\nexports: [validateSession]\nloc: 12\n").unwrap();
+        server
+            .pending_sidecar_changes
+            .lock()
+            .unwrap()
+            .insert(sidecar.clone());
+        server.sync_manifest();
+
+        let manifest = server.require_manifest().unwrap();
+        assert!(manifest.export_index.contains_key("validateSession"));
+        assert!(!manifest.export_index.contains_key("validateUser"));
+    }
+
+    #[test]
+    fn read_symbol_returns_exact_range_by_default() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join("auth.ts"),
+            "line1\nline2\nexport function validateUser() {}\nline4\nline5\n",
+        )
+        .unwrap();
+        std::fs::write(
+            tmp.path().join("auth.ts.fmm"),
+            "file: auth.ts\nexports:\n  validateUser: [3, 3]\nloc: 5\n",
+        )
+        .unwrap();
+
+        let server = McpServer::with_root(tmp.path().to_path_buf());
+        let result = server
+            .tool_read_symbol(&json!({"name": "validateUser"}), None)
+            .unwrap();
+        assert_eq!(result["lines"], json!([3, 3]));
+        assert_eq!(result["source"], json!("export function validateUser() {}"));
+        assert_eq!(result["context_lines"], json!([3, 3]));
+    }
+
+    #[test]
+    fn read_symbol_widens_window_by_context_and_renders_markers() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join("auth.ts"),
+            "line1\nline2\nexport function validateUser() {}\nline4\nline5\n",
+        )
+        .unwrap();
+        std::fs::write(
+            tmp.path().join("auth.ts.fmm"),
+            "file: auth.ts\nexports:\n  validateUser: [3, 3]\nloc: 5\n",
+        )
+        .unwrap();
+
+        let server = McpServer::with_root(tmp.path().to_path_buf());
+        let result = server
+            .tool_read_symbol(&json!({"name": "validateUser", "context": 1, "render": true}), None)
+            .unwrap();
+        assert_eq!(result["context_lines"], json!([2, 4]));
+        let render = result["render"].as_str().unwrap();
+        assert!(render.contains("> 3 | export function validateUser() {}"));
+        assert!(render.contains("  2 | line2"));
+    }
+
+    #[test]
+    fn compile_path_filter_glob_matches_subtree() {
+        let re = compile_path_filter("src/auth/**").unwrap();
+        assert!(re.is_match("src/auth/login.ts"));
+        assert!(re.is_match("src/auth/nested/session.ts"));
+        assert!(!re.is_match("src/db/connection.ts"));
+    }
+
+    #[test]
+    fn compile_path_filter_glob_star_stays_within_a_segment() {
+        let re = compile_path_filter("**/*.ts").unwrap();
+        assert!(re.is_match("src/auth/login.ts"));
+        assert!(re.is_match("login.ts"));
+        assert!(!re.is_match("src/auth/login.tsx"));
+    }
+
+    #[test]
+    fn compile_path_filter_regex_prefix_bypasses_glob_translation() {
+        let re = compile_path_filter("re:^src/(auth|db)/").unwrap();
+        assert!(re.is_match("src/auth/login.ts"));
+        assert!(re.is_match("src/db/connection.ts"));
+        assert!(!re.is_match("src/ui/login.ts"));
+    }
+
+    #[test]
+    fn compile_path_filter_invalid_regex_errors() {
+        assert!(compile_path_filter("re:(unterminated").is_err());
+    }
+
+    fn path_scoping_fixture() -> McpServer {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join("login.ts.fmm"),
+            "file: src/auth/login.ts\nexports: [login]\ndependencies: [../db/connection]\nloc: 10\n",
+        )
+        .unwrap();
+        std::fs::write(
+            tmp.path().join("connection.ts.fmm"),
+            "file: src/db/connection.ts\nexports: [connect]\nloc: 20\n",
+        )
+        .unwrap();
+        let server = McpServer::with_root(tmp.path().to_path_buf());
+        // Keep the TempDir alive for the server's lifetime by leaking it —
+        // these are short-lived test fixtures, not a long-running process.
+        std::mem::forget(tmp);
+        server
+    }
+
+    #[test]
+    fn tool_search_path_filter_scopes_to_subtree() {
+        let server = path_scoping_fixture();
+        let result = server
+            .tool_search(&json!({"path": "src/auth/**"}), None)
+            .unwrap();
+        let files: Vec<&str> = result
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| r["file"].as_str().unwrap())
+            .collect();
+        assert_eq!(files, vec!["src/auth/login.ts"]);
+    }
+
+    #[test]
+    fn tool_search_path_filter_combines_with_export_filter() {
+        let server = path_scoping_fixture();
+        let result = server
+            .tool_search(&json!({"export": "connect", "path": "src/auth/**"}), None)
+            .unwrap();
+        assert_eq!(result.as_array().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn tool_dependency_graph_path_filter_scopes_downstream() {
+        let server = path_scoping_fixture();
+        let result = server
+            .tool_dependency_graph(&json!({"file": "src/db/connection.ts", "path": "src/auth/**"}), None)
+            .unwrap();
+        assert_eq!(result["downstream"], json!(["src/auth/login.ts"]));
+
+        let result = server
+            .tool_dependency_graph(&json!({"file": "src/db/connection.ts", "path": "src/db/**"}), None)
+            .unwrap();
+        assert_eq!(result["downstream"], json!([]));
+    }
+
+    #[test]
+    fn tool_dependency_graph_path_filter_scopes_upstream() {
+        let server = path_scoping_fixture();
+        let result = server
+            .tool_dependency_graph(&json!({"file": "src/auth/login.ts", "path": "src/db/**"}), None)
+            .unwrap();
+        assert_eq!(result["upstream"], json!(["../db/connection"]));
+
+        let result = server
+            .tool_dependency_graph(&json!({"file": "src/auth/login.ts", "path": "src/auth/**"}), None)
+            .unwrap();
+        assert_eq!(result["upstream"], json!([] as [&str; 0]));
+    }
+
+    #[test]
+    fn tool_dependency_graph_policy_scopes_upstream_with_no_path_arg() {
+        let server = path_scoping_fixture();
+        let policy = ToolPolicy::build(None, Some(vec!["src/db/**".to_string()])).unwrap();
+
+        let result = server
+            .tool_dependency_graph(&json!({"file": "src/auth/login.ts"}), Some(&policy))
+            .unwrap();
+        assert_eq!(result["upstream"], json!(["../db/connection"]));
+
+        let policy = ToolPolicy::build(None, Some(vec!["src/auth/**".to_string()])).unwrap();
+        let result = server
+            .tool_dependency_graph(&json!({"file": "src/auth/login.ts"}), Some(&policy))
+            .unwrap();
+        assert_eq!(result["upstream"], json!([] as [&str; 0]));
+    }
+
+    #[test]
+    fn tool_policy_permits_tool_is_open_by_default() {
+        let policy = ToolPolicy::build(None, None).unwrap();
+        assert!(policy.permits_tool("fmm_search"));
+        assert!(policy.permits_tool("anything"));
+    }
+
+    #[test]
+    fn tool_policy_permits_tool_restricts_to_the_allowed_set() {
+        let mut allowed = HashSet::new();
+        allowed.insert("fmm_search".to_string());
+        let policy = ToolPolicy::build(Some(allowed), None).unwrap();
+        assert!(policy.permits_tool("fmm_search"));
+        assert!(!policy.permits_tool("fmm_read_symbol"));
+    }
+
+    #[test]
+    fn tool_policy_permits_path_restricts_to_the_allowed_globs() {
+        let policy = ToolPolicy::build(None, Some(vec!["src/auth/**".to_string()])).unwrap();
+        assert!(policy.permits_path("src/auth/login.ts"));
+        assert!(!policy.permits_path("src/db/connection.ts"));
+    }
+
+    #[test]
+    fn handle_tool_call_rejects_a_tool_outside_the_default_policy() {
+        let mut server = path_scoping_fixture();
+        let mut allowed = HashSet::new();
+        allowed.insert("fmm_search".to_string());
+        server.set_default_policy(ToolPolicy::build(Some(allowed), None).unwrap());
+
+        let result = server
+            .handle_tool_call(&Some(json!({"name": "fmm_read_symbol", "arguments": {}})))
+            .unwrap();
+        assert_eq!(result["isError"], json!(true));
+        assert!(result["content"][0]["text"]
+            .as_str()
+            .unwrap()
+            .contains("not permitted"));
+    }
+
+    #[test]
+    fn handle_tool_call_scopes_search_results_to_the_default_policy() {
+        let mut server = path_scoping_fixture();
+        server.set_default_policy(
+            ToolPolicy::build(None, Some(vec!["src/auth/**".to_string()])).unwrap(),
+        );
+
+        let response = server
+            .handle_tool_call(&Some(json!({"name": "fmm_search", "arguments": {}})))
+            .unwrap();
+        let text = response["content"][0]["text"].as_str().unwrap();
+        let envelope: Value = serde_json::from_str(text).unwrap();
+        let files: Vec<&str> = envelope["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| r["file"].as_str().unwrap())
+            .collect();
+        assert_eq!(files, vec!["src/auth/login.ts"]);
+    }
+
+    #[test]
+    fn handle_tool_call_rejects_reading_a_symbol_outside_a_token_policy() {
+        let mut server = path_scoping_fixture();
+        server.register_token_policy(
+            "readonly-auth",
+            ToolPolicy::build(None, Some(vec!["src/auth/**".to_string()])).unwrap(),
+        );
+
+        let response = server
+            .handle_tool_call(&Some(json!({
+                "name": "fmm_lookup_export",
+                "arguments": {"name": "connect"},
+                "token": "readonly-auth"
+            })))
+            .unwrap();
+        // fmm_lookup_export isn't one of the path-scoped tools, so this just
+        // confirms an in-scope token still reaches dispatch.
+        assert!(response["isError"].is_null());
+
+        let denied = server
+            .tool_read_symbol(
+                &json!({"name": "connect"}),
+                server.resolve_policy(Some("readonly-auth")),
+            )
+            .unwrap_err();
+        assert_eq!(denied, "Export 'connect' not found");
+    }
+
+    #[test]
+    fn sync_manifest_removes_a_deleted_sidecars_entry() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let sidecar = tmp.path().join("auth.ts.fmm");
+        std::fs::write(&sidecar, "file: auth.ts\nexports: [validateUser]\nloc: 10\n").unwrap();
+
+        let mut server = McpServer::with_root(tmp.path().to_path_buf());
+        std::fs::remove_file(&sidecar).unwrap();
+        server
+            .pending_sidecar_changes
+            .lock()
+            .unwrap()
+            .insert(sidecar);
+        server.sync_manifest();
+
+        assert!(!server.require_manifest().unwrap().has_file("auth.ts"));
+    }
 }
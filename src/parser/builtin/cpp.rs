@@ -175,10 +175,94 @@ impl CppParser {
         namespaces.sort();
         namespaces
     }
+
+    /// Base-class edges for every declared `class`/`struct`, however
+    /// deeply nested. C++ doesn't distinguish an implemented interface
+    /// from a base class at the grammar level (both are just base
+    /// specifiers), so every edge is recorded as `extends`.
+    fn extract_type_relations(
+        &self,
+        source: &str,
+        root_node: tree_sitter::Node,
+    ) -> Vec<serde_json::Value> {
+        let mut relations = Vec::new();
+        Self::collect_type_relations(root_node, source.as_bytes(), &mut relations);
+        relations
+    }
+
+    fn collect_type_relations(
+        node: tree_sitter::Node,
+        source_bytes: &[u8],
+        relations: &mut Vec<serde_json::Value>,
+    ) {
+        if matches!(node.kind(), "class_specifier" | "struct_specifier") {
+            if let Some(name_node) = node.child_by_field_name("name") {
+                if let Ok(type_name) = name_node.utf8_text(source_bytes) {
+                    if let Some(clause) = node.child_by_field_name("base_class_clause") {
+                        let mut cursor = clause.walk();
+                        for base in clause.named_children(&mut cursor) {
+                            if base.kind() == "access_specifier" {
+                                continue;
+                            }
+                            let (target, generic_args) =
+                                Self::cpp_type_parts(base, source_bytes);
+                            if target.is_empty() {
+                                continue;
+                            }
+                            relations.push(serde_json::json!({
+                                "type": type_name,
+                                "relation": "extends",
+                                "target": target,
+                                "generic_args": generic_args,
+                            }));
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            Self::collect_type_relations(child, source_bytes, relations);
+        }
+    }
+
+    /// Splits a base-class specifier into its base name and template
+    /// arguments, e.g. `Base<int>` (a `template_type` node) becomes
+    /// `("Base", ["int"])`.
+    fn cpp_type_parts(node: tree_sitter::Node, source_bytes: &[u8]) -> (String, Vec<String>) {
+        if node.kind() == "template_type" {
+            let base = node
+                .child_by_field_name("name")
+                .and_then(|n| n.utf8_text(source_bytes).ok())
+                .unwrap_or("")
+                .to_string();
+            let mut args = Vec::new();
+            if let Some(list) = node.child_by_field_name("arguments") {
+                let mut cursor = list.walk();
+                for arg in list.named_children(&mut cursor) {
+                    if let Ok(text) = arg.utf8_text(source_bytes) {
+                        args.push(text.to_string());
+                    }
+                }
+            }
+            (base, args)
+        } else {
+            (
+                node.utf8_text(source_bytes).unwrap_or("").to_string(),
+                Vec::new(),
+            )
+        }
+    }
 }
 
 impl Parser for CppParser {
     fn parse(&mut self, source: &str) -> Result<ParseResult> {
+        #[cfg(feature = "tracing")]
+        let _span = super::query_helpers::trace::parse_span("cpp", source.len()).entered();
+        #[cfg(feature = "tracing")]
+        let started_at = std::time::Instant::now();
+
         let tree = self
             .parser
             .parse(source, None)
@@ -190,20 +274,38 @@ impl Parser for CppParser {
         let dependencies = self.extract_dependencies(source, root_node);
         let loc = source.lines().count();
 
+        #[cfg(feature = "tracing")]
+        super::query_helpers::trace::record_parse_complete(
+            started_at.elapsed(),
+            exports.len(),
+            imports.len(),
+            dependencies.len(),
+            super::query_helpers::trace::count_unmatched_nodes(root_node),
+        );
+
         let namespaces = self.extract_namespaces(source, root_node);
-        let custom_fields = if namespaces.is_empty() {
+        let type_relations = self.extract_type_relations(source, root_node);
+        let custom_fields = if namespaces.is_empty() && type_relations.is_empty() {
             None
         } else {
             let mut fields = HashMap::new();
-            fields.insert(
-                "namespaces".to_string(),
-                serde_json::Value::Array(
-                    namespaces
-                        .into_iter()
-                        .map(serde_json::Value::String)
-                        .collect(),
-                ),
-            );
+            if !namespaces.is_empty() {
+                fields.insert(
+                    "namespaces".to_string(),
+                    serde_json::Value::Array(
+                        namespaces
+                            .into_iter()
+                            .map(serde_json::Value::String)
+                            .collect(),
+                    ),
+                );
+            }
+            if !type_relations.is_empty() {
+                fields.insert(
+                    "type_relations".to_string(),
+                    serde_json::Value::Array(type_relations),
+                );
+            }
             Some(fields)
         };
 
@@ -213,6 +315,10 @@ impl Parser for CppParser {
                 imports,
                 dependencies,
                 loc,
+                re_exports: Vec::new(),
+                test_loc: 0,
+                symbols: Vec::new(),
+                diagnostics: crate::parser::collect_diagnostics(root_node),
             },
             custom_fields,
         })
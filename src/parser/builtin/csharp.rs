@@ -234,10 +234,243 @@ impl CSharpParser {
         attributes.sort();
         attributes
     }
+
+    /// Base-list edges for every declared class/interface/struct, however
+    /// deeply nested. C#'s `: Base, IFoo, IBar` syntax doesn't distinguish
+    /// a base class from an implemented interface at the grammar level, so
+    /// this falls back to the standard naming convention: an entry whose
+    /// name looks like `IFoo` (capital `I` followed by another capital) is
+    /// recorded as `implements`, everything else as `extends`.
+    fn extract_type_relations(
+        &self,
+        source: &str,
+        root_node: tree_sitter::Node,
+    ) -> Vec<serde_json::Value> {
+        let mut relations = Vec::new();
+        Self::collect_type_relations(root_node, source.as_bytes(), &mut relations);
+        relations
+    }
+
+    fn collect_type_relations(
+        node: tree_sitter::Node,
+        source_bytes: &[u8],
+        relations: &mut Vec<serde_json::Value>,
+    ) {
+        if matches!(
+            node.kind(),
+            "class_declaration" | "interface_declaration" | "struct_declaration"
+        ) {
+            if let Some(name_node) = node.child_by_field_name("name") {
+                if let Ok(type_name) = name_node.utf8_text(source_bytes) {
+                    if let Some(bases) = node.child_by_field_name("bases") {
+                        let mut cursor = bases.walk();
+                        for base in bases.named_children(&mut cursor) {
+                            let (target, generic_args) =
+                                Self::csharp_type_parts(base, source_bytes);
+                            if target.is_empty() {
+                                continue;
+                            }
+                            let relation = if Self::looks_like_interface_name(&target) {
+                                "implements"
+                            } else {
+                                "extends"
+                            };
+                            relations.push(serde_json::json!({
+                                "type": type_name,
+                                "relation": relation,
+                                "target": target,
+                                "generic_args": generic_args,
+                            }));
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            Self::collect_type_relations(child, source_bytes, relations);
+        }
+    }
+
+    fn looks_like_interface_name(name: &str) -> bool {
+        let mut chars = name.chars();
+        matches!(chars.next(), Some('I')) && matches!(chars.next(), Some(c) if c.is_ascii_uppercase())
+    }
+
+    /// Splits a base-list entry into its base name and generic arguments,
+    /// e.g. `IRepository<User>` (a `generic_name` node) becomes
+    /// `("IRepository", ["User"])`.
+    fn csharp_type_parts(node: tree_sitter::Node, source_bytes: &[u8]) -> (String, Vec<String>) {
+        if node.kind() == "generic_name" {
+            let base = node
+                .child_by_field_name("name")
+                .and_then(|n| n.utf8_text(source_bytes).ok())
+                .unwrap_or("")
+                .to_string();
+            let mut args = Vec::new();
+            if let Some(type_args) = node.child_by_field_name("type_arguments") {
+                let mut cursor = type_args.walk();
+                for arg in type_args.named_children(&mut cursor) {
+                    if let Ok(text) = arg.utf8_text(source_bytes) {
+                        args.push(text.to_string());
+                    }
+                }
+            }
+            (base, args)
+        } else {
+            (
+                node.utf8_text(source_bytes).unwrap_or("").to_string(),
+                Vec::new(),
+            )
+        }
+    }
+
+    /// HTTP verb implied by an ASP.NET attribute name, where known
+    /// statically. `Route` carries a path but no verb on its own.
+    fn verb_for_attribute(name: &str) -> Option<&'static str> {
+        match name {
+            "HttpGet" => Some("GET"),
+            "HttpPost" => Some("POST"),
+            "HttpPut" => Some("PUT"),
+            "HttpDelete" => Some("DELETE"),
+            "HttpPatch" => Some("PATCH"),
+            _ => None,
+        }
+    }
+
+    fn is_routing_attribute(name: &str) -> bool {
+        Self::verb_for_attribute(name).is_some() || name == "Route"
+    }
+
+    fn string_literal_value(node: tree_sitter::Node, source_bytes: &[u8]) -> Option<String> {
+        let text = node.utf8_text(source_bytes).ok()?;
+        Some(text.trim_matches('"').to_string())
+    }
+
+    /// First string argument of an attribute's argument list, e.g. the
+    /// `"/{id}"` in `[HttpGet("/{id}")]`.
+    fn attribute_path_arg(args_node: tree_sitter::Node, source_bytes: &[u8]) -> Option<String> {
+        let mut cursor = args_node.walk();
+        for arg in args_node.children(&mut cursor) {
+            if arg.kind() != "attribute_argument" {
+                continue;
+            }
+            let mut arg_cursor = arg.walk();
+            for child in arg.children(&mut arg_cursor) {
+                if child.kind() == "string_literal" {
+                    return Self::string_literal_value(child, source_bytes);
+                }
+            }
+        }
+        None
+    }
+
+    /// Reads the routing attributes (`[HttpGet(...)]`, `[Route(...)]`) off a
+    /// declaration's `attribute_lists`, returning `(verb, path)`.
+    fn routing_attributes_on(
+        decl_node: tree_sitter::Node,
+        source_bytes: &[u8],
+    ) -> (Option<String>, Option<String>) {
+        let mut verb = None;
+        let mut path = None;
+
+        let mut cursor = decl_node.walk();
+        for child in decl_node.children(&mut cursor) {
+            if child.kind() != "attribute_list" {
+                continue;
+            }
+            let mut attr_cursor = child.walk();
+            for attr in child.children(&mut attr_cursor) {
+                if attr.kind() != "attribute" {
+                    continue;
+                }
+                let Some(name_node) = attr.child_by_field_name("name") else {
+                    continue;
+                };
+                let Ok(name) = name_node.utf8_text(source_bytes) else {
+                    continue;
+                };
+                if !Self::is_routing_attribute(name) {
+                    continue;
+                }
+                if let Some(v) = Self::verb_for_attribute(name) {
+                    verb = Some(v.to_string());
+                }
+                if let Some(args_node) = attr.child_by_field_name("arguments") {
+                    if let Some(p) = Self::attribute_path_arg(args_node, source_bytes) {
+                        path = Some(p);
+                    }
+                }
+            }
+        }
+        (verb, path)
+    }
+
+    /// Handler routes from `[ApiController]`-style classes: the HTTP verb,
+    /// the composed URL path (class-level `[Route]` prefix, with
+    /// `[controller]` substituted for the controller's conventional name,
+    /// plus the method-level path), and the declaring class.
+    fn extract_routes(&self, source: &str, root_node: tree_sitter::Node) -> Vec<serde_json::Value> {
+        let source_bytes = source.as_bytes();
+        let mut routes = Vec::new();
+
+        let mut cursor = QueryCursor::new();
+        let mut iter = cursor.matches(&self.class_query, root_node, source_bytes);
+        let mut class_decls = Vec::new();
+        while let Some(m) = iter.next() {
+            for capture in m.captures {
+                if let Some(class_decl) = capture.node.parent() {
+                    class_decls.push(class_decl);
+                }
+            }
+        }
+
+        for class_decl in class_decls {
+            let class_name = class_decl
+                .child_by_field_name("name")
+                .and_then(|n| n.utf8_text(source_bytes).ok())
+                .unwrap_or("");
+            let controller_name = class_name.strip_suffix("Controller").unwrap_or(class_name);
+            let (_, class_path) = Self::routing_attributes_on(class_decl, source_bytes);
+            let prefix = class_path
+                .map(|p| p.replace("[controller]", controller_name))
+                .unwrap_or_default();
+
+            let Some(body) = class_decl.child_by_field_name("body") else {
+                continue;
+            };
+            let mut body_cursor = body.walk();
+            for member in body.children(&mut body_cursor) {
+                if member.kind() != "method_declaration" {
+                    continue;
+                }
+                let (verb, path) = Self::routing_attributes_on(member, source_bytes);
+                let Some(verb) = verb else { continue };
+                let method_name = member
+                    .child_by_field_name("name")
+                    .and_then(|n| n.utf8_text(source_bytes).ok())
+                    .unwrap_or("");
+                let full_path = format!("{}{}", prefix, path.unwrap_or_default());
+                routes.push(serde_json::json!({
+                    "method": verb,
+                    "path": full_path,
+                    "handler": format!("{}.{}", class_name, method_name),
+                }));
+            }
+        }
+
+        routes
+    }
 }
 
 impl Parser for CSharpParser {
     fn parse(&mut self, source: &str) -> Result<ParseResult> {
+        #[cfg(feature = "tracing")]
+        let _span = super::query_helpers::trace::parse_span("csharp", source.len()).entered();
+        #[cfg(feature = "tracing")]
+        let started_at = std::time::Instant::now();
+
         let tree = self
             .parser
             .parse(source, None)
@@ -248,8 +481,19 @@ impl Parser for CSharpParser {
         let imports = self.extract_imports(source, root_node);
         let loc = source.lines().count();
 
+        #[cfg(feature = "tracing")]
+        super::query_helpers::trace::record_parse_complete(
+            started_at.elapsed(),
+            exports.len(),
+            imports.len(),
+            0,
+            super::query_helpers::trace::count_unmatched_nodes(root_node),
+        );
+
         let namespaces = self.extract_namespaces(source, root_node);
         let attributes = self.extract_attributes(source, root_node);
+        let routes = self.extract_routes(source, root_node);
+        let type_relations = self.extract_type_relations(source, root_node);
 
         let mut custom_fields_map = HashMap::new();
         if !namespaces.is_empty() {
@@ -274,6 +518,15 @@ impl Parser for CSharpParser {
                 ),
             );
         }
+        if !routes.is_empty() {
+            custom_fields_map.insert("routes".to_string(), serde_json::Value::Array(routes));
+        }
+        if !type_relations.is_empty() {
+            custom_fields_map.insert(
+                "type_relations".to_string(),
+                serde_json::Value::Array(type_relations),
+            );
+        }
 
         let custom_fields = if custom_fields_map.is_empty() {
             None
@@ -287,6 +540,10 @@ impl Parser for CSharpParser {
                 imports,
                 dependencies: Vec::new(),
                 loc,
+                re_exports: Vec::new(),
+                test_loc: 0,
+                symbols: Vec::new(),
+                diagnostics: crate::parser::collect_diagnostics(root_node),
             },
             custom_fields,
         })
@@ -389,6 +646,38 @@ public class Config {
         assert!(names.contains(&"Required"));
     }
 
+    #[test]
+    fn parse_csharp_aspnet_routes() {
+        let mut parser = CSharpParser::new().unwrap();
+        let source = r#"
+[Route("api/[controller]")]
+public class UserController {
+    [HttpGet("{id}")]
+    public User GetUser() { return null; }
+
+    [HttpPost]
+    public User CreateUser() { return null; }
+}
+"#;
+        let result = parser.parse(source).unwrap();
+        let fields = result.custom_fields.unwrap();
+        let routes = fields.get("routes").unwrap().as_array().unwrap();
+
+        let get_route = routes
+            .iter()
+            .find(|r| r["handler"] == "UserController.GetUser")
+            .unwrap();
+        assert_eq!(get_route["method"], "GET");
+        assert_eq!(get_route["path"], "api/User{id}");
+
+        let post_route = routes
+            .iter()
+            .find(|r| r["handler"] == "UserController.CreateUser")
+            .unwrap();
+        assert_eq!(post_route["method"], "POST");
+        assert_eq!(post_route["path"], "api/User");
+    }
+
     #[test]
     fn parse_csharp_empty() {
         let mut parser = CSharpParser::new().unwrap();
@@ -1,5 +1,6 @@
 use crate::parser::{Metadata, ParseResult, Parser};
 use anyhow::Result;
+use std::collections::HashMap;
 use streaming_iterator::StreamingIterator;
 use tree_sitter::{Language, Parser as TSParser, Query, QueryCursor};
 
@@ -10,6 +11,10 @@ pub struct GoParser {
     const_query: Query,
     var_query: Query,
     import_query: Query,
+    method_query: Query,
+    interface_query: Query,
+    struct_tag_query: Query,
+    type_param_query: Query,
 }
 
 impl GoParser {
@@ -50,6 +55,32 @@ impl GoParser {
         )
         .map_err(|e| anyhow::anyhow!("Failed to compile import query: {}", e))?;
 
+        let method_query = Query::new(
+            &language,
+            "(method_declaration
+                receiver: (parameter_list (parameter_declaration type: (_) @receiver_type))
+                name: (field_identifier) @name)",
+        )
+        .map_err(|e| anyhow::anyhow!("Failed to compile method query: {}", e))?;
+
+        let interface_query = Query::new(
+            &language,
+            "(type_spec name: (type_identifier) @name type: (interface_type))",
+        )
+        .map_err(|e| anyhow::anyhow!("Failed to compile interface query: {}", e))?;
+
+        let struct_tag_query = Query::new(
+            &language,
+            "(field_declaration tag: (raw_string_literal) @tag)",
+        )
+        .map_err(|e| anyhow::anyhow!("Failed to compile struct tag query: {}", e))?;
+
+        let type_param_query = Query::new(
+            &language,
+            "(type_parameter_declaration name: (identifier) @name)",
+        )
+        .map_err(|e| anyhow::anyhow!("Failed to compile type param query: {}", e))?;
+
         Ok(Self {
             parser,
             func_query,
@@ -57,6 +88,10 @@ impl GoParser {
             const_query,
             var_query,
             import_query,
+            method_query,
+            interface_query,
+            struct_tag_query,
+            type_param_query,
         })
     }
 
@@ -68,12 +103,26 @@ impl GoParser {
         let mut exports = Vec::new();
         let source_bytes = source.as_bytes();
 
-        let queries = [
-            &self.func_query,
-            &self.type_query,
-            &self.const_query,
-            &self.var_query,
-        ];
+        // Top-level functions: test functions are classified separately,
+        // see `extract_test_functions`, and excluded here.
+        let mut cursor = QueryCursor::new();
+        let mut iter = cursor.matches(&self.func_query, root_node, source_bytes);
+        while let Some(m) = iter.next() {
+            for capture in m.captures {
+                if let Ok(text) = capture.node.utf8_text(source_bytes) {
+                    let is_test = capture
+                        .node
+                        .parent()
+                        .is_some_and(|decl| self.is_test_function(decl, text, source_bytes));
+                    if Self::is_exported(text) && !is_test && !exports.contains(&text.to_string())
+                    {
+                        exports.push(text.to_string());
+                    }
+                }
+            }
+        }
+
+        let queries = [&self.type_query, &self.const_query, &self.var_query];
 
         for query in queries {
             let mut cursor = QueryCursor::new();
@@ -134,6 +183,164 @@ impl GoParser {
         dependencies.sort();
         (imports, dependencies)
     }
+
+    /// Exported methods, qualified as `Type.Method` when the receiver type
+    /// is unambiguous (a bare `*` or an unparsable receiver falls back to
+    /// just the method name).
+    fn extract_methods(&self, source: &str, root_node: tree_sitter::Node) -> Vec<String> {
+        let mut methods = Vec::new();
+        let source_bytes = source.as_bytes();
+
+        let mut cursor = QueryCursor::new();
+        let mut iter = cursor.matches(&self.method_query, root_node, source_bytes);
+        while let Some(m) = iter.next() {
+            let mut name = None;
+            let mut receiver_type = None;
+            for capture in m.captures {
+                let Ok(text) = capture.node.utf8_text(source_bytes) else {
+                    continue;
+                };
+                match self.method_query.capture_names()[capture.index as usize] {
+                    "name" => name = Some(text),
+                    "receiver_type" => receiver_type = Some(text.trim_start_matches('*')),
+                    _ => {}
+                }
+            }
+            if let Some(name) = name {
+                if Self::is_exported(name) {
+                    let qualified = match receiver_type {
+                        Some(ty) if !ty.is_empty() => format!("{ty}.{name}"),
+                        _ => name.to_string(),
+                    };
+                    if !methods.contains(&qualified) {
+                        methods.push(qualified);
+                    }
+                }
+            }
+        }
+
+        methods.sort();
+        methods
+    }
+
+    /// Exported interface type names (`type Name interface { ... }`).
+    fn extract_interfaces(&self, source: &str, root_node: tree_sitter::Node) -> Vec<String> {
+        let mut interfaces = Vec::new();
+        let source_bytes = source.as_bytes();
+
+        let mut cursor = QueryCursor::new();
+        let mut iter = cursor.matches(&self.interface_query, root_node, source_bytes);
+        while let Some(m) = iter.next() {
+            for capture in m.captures {
+                if let Ok(text) = capture.node.utf8_text(source_bytes) {
+                    if Self::is_exported(text) && !interfaces.contains(&text.to_string()) {
+                        interfaces.push(text.to_string());
+                    }
+                }
+            }
+        }
+
+        interfaces.sort();
+        interfaces
+    }
+
+    /// Raw struct field tags (e.g. `` `json:"name"` ``), backticks stripped.
+    fn extract_struct_tags(&self, source: &str, root_node: tree_sitter::Node) -> Vec<String> {
+        let mut tags = Vec::new();
+        let source_bytes = source.as_bytes();
+
+        let mut cursor = QueryCursor::new();
+        let mut iter = cursor.matches(&self.struct_tag_query, root_node, source_bytes);
+        while let Some(m) = iter.next() {
+            for capture in m.captures {
+                if let Ok(text) = capture.node.utf8_text(source_bytes) {
+                    let tag = text.trim_matches('`').to_string();
+                    if !tags.contains(&tag) {
+                        tags.push(tag);
+                    }
+                }
+            }
+        }
+
+        tags
+    }
+
+    /// Type parameter names from Go 1.18+ generics, e.g. the `T` in
+    /// `func Map[T any](...)` or `type Stack[T any] struct { ... }`.
+    fn extract_type_params(&self, source: &str, root_node: tree_sitter::Node) -> Vec<String> {
+        let mut params = Vec::new();
+        let source_bytes = source.as_bytes();
+
+        let mut cursor = QueryCursor::new();
+        let mut iter = cursor.matches(&self.type_param_query, root_node, source_bytes);
+        while let Some(m) = iter.next() {
+            for capture in m.captures {
+                if let Ok(text) = capture.node.utf8_text(source_bytes) {
+                    if !params.contains(&text.to_string()) {
+                        params.push(text.to_string());
+                    }
+                }
+            }
+        }
+
+        params
+    }
+
+    /// `go test` convention: `func TestXxx(t *testing.T)` — an exported name
+    /// with a `Test` prefix taking a single `*testing.T` parameter.
+    fn is_test_function(&self, node: tree_sitter::Node, name: &str, source_bytes: &[u8]) -> bool {
+        if !name.starts_with("Test") || name.chars().nth(4).is_some_and(|c| c.is_lowercase()) {
+            return false;
+        }
+        let Some(params) = node.child_by_field_name("parameters") else {
+            return false;
+        };
+        params
+            .utf8_text(source_bytes)
+            .is_ok_and(|text| text.contains("testing.T"))
+    }
+
+    fn extract_test_functions(&self, source: &str, root_node: tree_sitter::Node) -> Vec<String> {
+        let mut names = Vec::new();
+        let source_bytes = source.as_bytes();
+        let mut cursor = QueryCursor::new();
+        let mut iter = cursor.matches(&self.func_query, root_node, source_bytes);
+        while let Some(m) = iter.next() {
+            for capture in m.captures {
+                if let Ok(name) = capture.node.utf8_text(source_bytes) {
+                    if let Some(func_decl) = capture.node.parent() {
+                        if self.is_test_function(func_decl, name, source_bytes)
+                            && !names.contains(&name.to_string())
+                        {
+                            names.push(name.to_string());
+                        }
+                    }
+                }
+            }
+        }
+        names.sort();
+        names
+    }
+
+    fn test_loc(&self, source: &str, root_node: tree_sitter::Node) -> usize {
+        let source_bytes = source.as_bytes();
+        let mut total = 0;
+        let mut cursor = QueryCursor::new();
+        let mut iter = cursor.matches(&self.func_query, root_node, source_bytes);
+        while let Some(m) = iter.next() {
+            for capture in m.captures {
+                if let Ok(name) = capture.node.utf8_text(source_bytes) {
+                    if let Some(func_decl) = capture.node.parent() {
+                        if self.is_test_function(func_decl, name, source_bytes) {
+                            total +=
+                                func_decl.end_position().row - func_decl.start_position().row + 1;
+                        }
+                    }
+                }
+            }
+        }
+        total
+    }
 }
 
 impl Parser for GoParser {
@@ -144,9 +351,54 @@ impl Parser for GoParser {
             .ok_or_else(|| anyhow::anyhow!("Failed to parse Go source"))?;
 
         let root_node = tree.root_node();
-        let exports = self.extract_exports(source, root_node);
+        let mut exports = self.extract_exports(source, root_node);
         let (imports, dependencies) = self.extract_imports(source, root_node);
         let loc = source.lines().count();
+        let test_functions = self.extract_test_functions(source, root_node);
+        let test_loc = self.test_loc(source, root_node);
+
+        let methods = self.extract_methods(source, root_node);
+        let interfaces = self.extract_interfaces(source, root_node);
+        let struct_tags = self.extract_struct_tags(source, root_node);
+        let type_params = self.extract_type_params(source, root_node);
+
+        for method in &methods {
+            if !exports.contains(method) {
+                exports.push(method.clone());
+            }
+        }
+        exports.sort();
+
+        let has_custom = !test_functions.is_empty()
+            || !methods.is_empty()
+            || !interfaces.is_empty()
+            || !struct_tags.is_empty()
+            || !type_params.is_empty();
+
+        let custom_fields = if !has_custom {
+            None
+        } else {
+            let mut fields = HashMap::new();
+            if !test_functions.is_empty() {
+                fields.insert(
+                    "test_functions".to_string(),
+                    serde_json::json!({
+                        "count": test_functions.len(),
+                        "names": test_functions,
+                    }),
+                );
+            }
+            if !interfaces.is_empty() {
+                fields.insert("interfaces".to_string(), serde_json::json!(interfaces));
+            }
+            if !struct_tags.is_empty() {
+                fields.insert("struct_tags".to_string(), serde_json::json!(struct_tags));
+            }
+            if !type_params.is_empty() {
+                fields.insert("type_params".to_string(), serde_json::json!(type_params));
+            }
+            Some(fields)
+        };
 
         Ok(ParseResult {
             metadata: Metadata {
@@ -154,8 +406,12 @@ impl Parser for GoParser {
                 imports,
                 dependencies,
                 loc,
+                re_exports: Vec::new(),
+                test_loc,
+                symbols: Vec::new(),
+                diagnostics: crate::parser::collect_diagnostics(root_node),
             },
-            custom_fields: None,
+            custom_fields,
         })
     }
 
@@ -268,4 +524,129 @@ var localVar = "hidden"
         assert!(result.metadata.exports.is_empty());
         assert!(result.metadata.imports.is_empty());
     }
+
+    #[test]
+    fn parse_go_test_functions_excluded_from_exports() {
+        let mut parser = GoParser::new().unwrap();
+        let source = r#"
+package main
+
+import "testing"
+
+func ExportedFunc() {}
+
+func TestExportedFunc(t *testing.T) {
+    ExportedFunc()
+}
+"#;
+        let result = parser.parse(source).unwrap();
+        assert!(result
+            .metadata
+            .exports
+            .contains(&"ExportedFunc".to_string()));
+        assert!(!result
+            .metadata
+            .exports
+            .contains(&"TestExportedFunc".to_string()));
+
+        let fields = result.custom_fields.unwrap();
+        let test_functions = fields.get("test_functions").unwrap();
+        assert_eq!(test_functions.get("count").unwrap().as_u64(), Some(1));
+        let names = test_functions.get("names").unwrap().as_array().unwrap();
+        assert_eq!(
+            names,
+            &vec![serde_json::Value::String("TestExportedFunc".to_string())]
+        );
+    }
+
+    #[test]
+    fn parse_go_receiver_methods_qualified_and_exported() {
+        let mut parser = GoParser::new().unwrap();
+        let source = r#"
+package main
+
+type Handler struct {
+    count int
+}
+
+func NewHandler() *Handler {
+    return &Handler{}
+}
+
+func (h *Handler) ServeHTTP() {}
+
+func (h *Handler) unexportedHelper() {}
+"#;
+        let result = parser.parse(source).unwrap();
+        assert!(result
+            .metadata
+            .exports
+            .contains(&"Handler.ServeHTTP".to_string()));
+        assert!(!result
+            .metadata
+            .exports
+            .contains(&"Handler.unexportedHelper".to_string()));
+
+        let fields = result.custom_fields.unwrap();
+        assert!(fields.get("interfaces").is_none());
+    }
+
+    #[test]
+    fn parse_go_generic_function_captures_type_params() {
+        let mut parser = GoParser::new().unwrap();
+        let source = r#"
+package main
+
+func Map[T any, U any](items []T, f func(T) U) []U {
+    result := make([]U, len(items))
+    for i, item := range items {
+        result[i] = f(item)
+    }
+    return result
+}
+"#;
+        let result = parser.parse(source).unwrap();
+        let fields = result.custom_fields.expect("should have custom fields");
+        let type_params = fields.get("type_params").unwrap().as_array().unwrap();
+        let names: Vec<&str> = type_params.iter().map(|v| v.as_str().unwrap()).collect();
+        assert!(names.contains(&"T"));
+        assert!(names.contains(&"U"));
+    }
+
+    #[test]
+    fn parse_go_exported_interfaces_and_struct_tags() {
+        let mut parser = GoParser::new().unwrap();
+        let source = r#"
+package main
+
+type Service interface {
+    Start() error
+}
+
+type Config struct {
+    Name string `json:"name"`
+    internal string
+}
+"#;
+        let result = parser.parse(source).unwrap();
+        let fields = result.custom_fields.expect("should have custom fields");
+
+        let interfaces = fields.get("interfaces").unwrap().as_array().unwrap();
+        let interface_names: Vec<&str> = interfaces.iter().map(|v| v.as_str().unwrap()).collect();
+        assert!(interface_names.contains(&"Service"));
+
+        let struct_tags = fields.get("struct_tags").unwrap().as_array().unwrap();
+        let tag_values: Vec<&str> = struct_tags.iter().map(|v| v.as_str().unwrap()).collect();
+        assert!(tag_values.contains(&"json:\"name\""));
+    }
+
+    #[test]
+    fn parse_go_test_loc_split_from_production_loc() {
+        let mut parser = GoParser::new().unwrap();
+        let source = "package main\n\nimport \"testing\"\n\nfunc TestSomething(t *testing.T) {\n    t.Log(\"ok\")\n}\n";
+        let result = parser.parse(source).unwrap();
+        assert_eq!(result.metadata.loc, source.lines().count());
+        assert!(result.metadata.test_loc > 0);
+        assert!(result.metadata.test_loc < result.metadata.loc);
+    }
 }
@@ -126,22 +126,65 @@ impl JavaParser {
             }
         }
 
+        // Nested/inner classes, interfaces, enums, and records, however
+        // deeply nested, plus any of those four kinds this pass's
+        // top-level-only queries above don't reach (record_declaration has
+        // no dedicated query at all).
+        let mut declaration_names = Vec::new();
+        Self::collect_declaration_names(root_node, source_bytes, &mut declaration_names);
+        for name in declaration_names {
+            if !exports.contains(&name) {
+                exports.push(name);
+            }
+        }
+
         exports.sort();
         exports.dedup();
         exports
     }
 
+    /// Names of every class/interface/enum/record declared anywhere in the
+    /// file, at any nesting depth.
+    fn collect_declaration_names(node: tree_sitter::Node, source_bytes: &[u8], names: &mut Vec<String>) {
+        if Self::declaration_kind(node).is_some() {
+            if let Some(name_node) = node.child_by_field_name("name") {
+                if let Ok(name) = name_node.utf8_text(source_bytes) {
+                    names.push(name.to_string());
+                }
+            }
+        }
+
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            Self::collect_declaration_names(child, source_bytes, names);
+        }
+    }
+
+    fn declaration_kind(node: tree_sitter::Node) -> Option<&'static str> {
+        match node.kind() {
+            "class_declaration" => Some("class"),
+            "interface_declaration" => Some("interface"),
+            "enum_declaration" => Some("enum"),
+            "record_declaration" => Some("record"),
+            _ => None,
+        }
+    }
+
     fn has_public_modifier(&self, node: tree_sitter::Node, source_bytes: &[u8]) -> bool {
+        Self::has_modifier(node, "public", source_bytes)
+    }
+
+    fn has_modifier(node: tree_sitter::Node, modifier_name: &str, source_bytes: &[u8]) -> bool {
         let mut cursor = node.walk();
         for child in node.children(&mut cursor) {
             if child.kind() == "modifiers" {
                 let mut mod_cursor = child.walk();
                 for modifier in child.children(&mut mod_cursor) {
-                    if modifier.kind() == "public" {
+                    if modifier.kind() == modifier_name {
                         return true;
                     }
                     if let Ok(text) = modifier.utf8_text(source_bytes) {
-                        if text == "public" {
+                        if text == modifier_name {
                             return true;
                         }
                     }
@@ -215,10 +258,393 @@ impl JavaParser {
         annotations.sort();
         annotations
     }
+
+    /// Inheritance and interface-implementation edges for every declared
+    /// class/interface in the file, however deeply nested: `extends` for a
+    /// superclass, `implements` for each entry in a `super_interfaces`
+    /// clause. Each edge records the generic arguments on its target type
+    /// (e.g. `Validator<String>` records `target: "Validator"`,
+    /// `generic_args: ["String"]`) so callers can assemble a class
+    /// hierarchy across an entire polyglot codebase.
+    fn extract_type_relations(
+        &self,
+        source: &str,
+        root_node: tree_sitter::Node,
+    ) -> Vec<serde_json::Value> {
+        let mut relations = Vec::new();
+        Self::collect_type_relations(root_node, source.as_bytes(), &mut relations);
+        relations
+    }
+
+    fn collect_type_relations(
+        node: tree_sitter::Node,
+        source_bytes: &[u8],
+        relations: &mut Vec<serde_json::Value>,
+    ) {
+        if matches!(node.kind(), "class_declaration" | "interface_declaration") {
+            if let Some(name_node) = node.child_by_field_name("name") {
+                if let Ok(type_name) = name_node.utf8_text(source_bytes) {
+                    if let Some(superclass) = node.child_by_field_name("superclass") {
+                        if let Some(type_node) = superclass.child_by_field_name("type") {
+                            let (target, generic_args) =
+                                Self::java_type_parts(type_node, source_bytes);
+                            relations.push(serde_json::json!({
+                                "type": type_name,
+                                "relation": "extends",
+                                "target": target,
+                                "generic_args": generic_args,
+                            }));
+                        }
+                    }
+
+                    let interfaces = node
+                        .child_by_field_name("interfaces")
+                        .or_else(|| node.child_by_field_name("extends"));
+                    if let Some(interfaces) = interfaces {
+                        if let Some(type_list) = interfaces.child_by_field_name("type_list") {
+                            let mut cursor = type_list.walk();
+                            for type_node in type_list.named_children(&mut cursor) {
+                                let (target, generic_args) =
+                                    Self::java_type_parts(type_node, source_bytes);
+                                relations.push(serde_json::json!({
+                                    "type": type_name,
+                                    "relation": "implements",
+                                    "target": target,
+                                    "generic_args": generic_args,
+                                }));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            Self::collect_type_relations(child, source_bytes, relations);
+        }
+    }
+
+    /// Splits a `_type` node into its base name and generic arguments,
+    /// e.g. `Validator<String>` (a `generic_type` node) becomes
+    /// `("Validator", ["String"])`; a plain `type_identifier` just
+    /// returns its text with no arguments.
+    fn java_type_parts(node: tree_sitter::Node, source_bytes: &[u8]) -> (String, Vec<String>) {
+        if node.kind() == "generic_type" {
+            let mut base = String::new();
+            let mut args = Vec::new();
+            let mut cursor = node.walk();
+            for child in node.named_children(&mut cursor) {
+                if child.kind() == "type_arguments" {
+                    let mut arg_cursor = child.walk();
+                    for arg in child.named_children(&mut arg_cursor) {
+                        if let Ok(text) = arg.utf8_text(source_bytes) {
+                            args.push(text.to_string());
+                        }
+                    }
+                } else if base.is_empty() {
+                    if let Ok(text) = child.utf8_text(source_bytes) {
+                        base = text.to_string();
+                    }
+                }
+            }
+            (base, args)
+        } else {
+            (
+                node.utf8_text(source_bytes).unwrap_or("").to_string(),
+                Vec::new(),
+            )
+        }
+    }
+
+    /// Every class/interface/enum/record declared anywhere in the file, as
+    /// structured metadata: its name, declaration kind, whether it's
+    /// `sealed`, and whether it's nested inside another type declaration
+    /// rather than declared at the top level of the file. Complements
+    /// [`Self::extract_type_relations`], which gives each entry's
+    /// supertypes.
+    fn extract_declarations(&self, source: &str, root_node: tree_sitter::Node) -> Vec<serde_json::Value> {
+        let mut declarations = Vec::new();
+        Self::collect_declarations(root_node, source.as_bytes(), false, &mut declarations);
+        declarations
+    }
+
+    fn collect_declarations(
+        node: tree_sitter::Node,
+        source_bytes: &[u8],
+        nested: bool,
+        declarations: &mut Vec<serde_json::Value>,
+    ) {
+        let kind = Self::declaration_kind(node);
+        if let Some(kind) = kind {
+            if let Some(name_node) = node.child_by_field_name("name") {
+                if let Ok(name) = name_node.utf8_text(source_bytes) {
+                    declarations.push(serde_json::json!({
+                        "name": name,
+                        "kind": kind,
+                        "sealed": Self::has_modifier(node, "sealed", source_bytes),
+                        "nested": nested,
+                    }));
+                }
+            }
+        }
+
+        let child_is_nested = nested || kind.is_some();
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            Self::collect_declarations(child, source_bytes, child_is_nested, declarations);
+        }
+    }
+
+    /// Structured form of every `@Annotation(...)` application in the file
+    /// that carries at least one argument — bare marker annotations (e.g.
+    /// `@Override`) already appear by name only in the `annotations` field.
+    /// `@RequestMapping("/users")` becomes `{"name": "RequestMapping",
+    /// "arguments": {"value": "/users"}}`; a single positional argument (no
+    /// `key = value` pairs) is recorded under the key `"value"`.
+    fn extract_annotation_arguments(
+        &self,
+        source: &str,
+        root_node: tree_sitter::Node,
+    ) -> Vec<serde_json::Value> {
+        let mut results = Vec::new();
+        Self::collect_annotation_arguments(root_node, source.as_bytes(), &mut results);
+        results
+    }
+
+    fn collect_annotation_arguments(
+        node: tree_sitter::Node,
+        source_bytes: &[u8],
+        results: &mut Vec<serde_json::Value>,
+    ) {
+        if node.kind() == "annotation" {
+            if let Some(name_node) = node.child_by_field_name("name") {
+                if let Ok(name) = name_node.utf8_text(source_bytes) {
+                    let arguments =
+                        Self::annotation_argument_map(node.child_by_field_name("arguments"), source_bytes);
+                    if !arguments.is_empty() {
+                        results.push(serde_json::json!({
+                            "name": name,
+                            "arguments": arguments,
+                        }));
+                    }
+                }
+            }
+        }
+
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            Self::collect_annotation_arguments(child, source_bytes, results);
+        }
+    }
+
+    fn annotation_argument_map(
+        args_node: Option<tree_sitter::Node>,
+        source_bytes: &[u8],
+    ) -> serde_json::Map<String, serde_json::Value> {
+        let mut map = serde_json::Map::new();
+        let Some(args_node) = args_node else {
+            return map;
+        };
+        let mut cursor = args_node.walk();
+        for arg in args_node.children(&mut cursor) {
+            match arg.kind() {
+                "string_literal" => {
+                    if let Some(value) = Self::string_literal_value(arg, source_bytes) {
+                        map.insert("value".to_string(), serde_json::Value::String(value));
+                    }
+                }
+                "element_value_pair" => {
+                    let Some(key_node) = arg.child_by_field_name("key") else {
+                        continue;
+                    };
+                    let Ok(key) = key_node.utf8_text(source_bytes) else {
+                        continue;
+                    };
+                    let Some(value_node) = arg.child_by_field_name("value") else {
+                        continue;
+                    };
+                    let value = Self::string_literal_value(value_node, source_bytes).unwrap_or_else(|| {
+                        value_node.utf8_text(source_bytes).unwrap_or("").to_string()
+                    });
+                    map.insert(key.to_string(), serde_json::Value::String(value));
+                }
+                _ => {}
+            }
+        }
+        map
+    }
+
+    /// HTTP verb implied by a Spring mapping annotation name, where known
+    /// statically. `RequestMapping` needs its `method=` argument inspected,
+    /// so it resolves to `None` here.
+    fn verb_for_mapping_annotation(name: &str) -> Option<&'static str> {
+        match name {
+            "GetMapping" => Some("GET"),
+            "PostMapping" => Some("POST"),
+            "PutMapping" => Some("PUT"),
+            "DeleteMapping" => Some("DELETE"),
+            "PatchMapping" => Some("PATCH"),
+            _ => None,
+        }
+    }
+
+    fn is_mapping_annotation(name: &str) -> bool {
+        Self::verb_for_mapping_annotation(name).is_some() || name == "RequestMapping"
+    }
+
+    fn string_literal_value(node: tree_sitter::Node, source_bytes: &[u8]) -> Option<String> {
+        let text = node.utf8_text(source_bytes).ok()?;
+        Some(text.trim_matches('"').to_string())
+    }
+
+    /// Reads the `value`/`path` and `method` arguments off a mapping
+    /// annotation's `(name, arguments)`, e.g. `@GetMapping("/x")` or
+    /// `@RequestMapping(value = "/x", method = RequestMethod.POST)`.
+    fn mapping_annotation_args(
+        name_node: tree_sitter::Node,
+        args_node: Option<tree_sitter::Node>,
+        source_bytes: &[u8],
+    ) -> (Option<String>, Option<String>) {
+        let name = name_node.utf8_text(source_bytes).unwrap_or("");
+        let mut path = None;
+        let mut verb = Self::verb_for_mapping_annotation(name).map(str::to_string);
+
+        let Some(args_node) = args_node else {
+            return (path, verb);
+        };
+        let mut cursor = args_node.walk();
+        for arg in args_node.children(&mut cursor) {
+            match arg.kind() {
+                "string_literal" => {
+                    path = Self::string_literal_value(arg, source_bytes);
+                }
+                "element_value_pair" => {
+                    let Some(key_node) = arg.child_by_field_name("key") else {
+                        continue;
+                    };
+                    let Ok(key) = key_node.utf8_text(source_bytes) else {
+                        continue;
+                    };
+                    let Some(value_node) = arg.child_by_field_name("value") else {
+                        continue;
+                    };
+                    match key {
+                        "value" | "path" => {
+                            path = Self::string_literal_value(value_node, source_bytes);
+                        }
+                        "method" => {
+                            if let Ok(text) = value_node.utf8_text(source_bytes) {
+                                verb = text.rsplit('.').next().map(|v| v.to_string());
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+        (path, verb)
+    }
+
+    /// Finds the mapping annotation (if any) on a declaration's `modifiers`
+    /// child, returning `(verb, path)`. A class-level `@RequestMapping`
+    /// without a verb argument only contributes a path prefix.
+    fn mapping_annotation_on(
+        decl_node: tree_sitter::Node,
+        source_bytes: &[u8],
+    ) -> Option<(Option<String>, Option<String>)> {
+        let mut cursor = decl_node.walk();
+        for child in decl_node.children(&mut cursor) {
+            if child.kind() != "modifiers" {
+                continue;
+            }
+            let mut mod_cursor = child.walk();
+            for modifier in child.children(&mut mod_cursor) {
+                let (name_node, args_node) = match modifier.kind() {
+                    "marker_annotation" => (modifier.child_by_field_name("name"), None),
+                    "annotation" => (
+                        modifier.child_by_field_name("name"),
+                        modifier.child_by_field_name("arguments"),
+                    ),
+                    _ => continue,
+                };
+                let Some(name_node) = name_node else { continue };
+                let Ok(name) = name_node.utf8_text(source_bytes) else {
+                    continue;
+                };
+                if !Self::is_mapping_annotation(name) {
+                    continue;
+                }
+                let (path, verb) = Self::mapping_annotation_args(name_node, args_node, source_bytes);
+                return Some((verb, path));
+            }
+        }
+        None
+    }
+
+    /// Handler routes from Spring `@RestController`/`@Controller` classes:
+    /// the HTTP verb, the composed URL path (class prefix + method path),
+    /// and the declaring class, one entry per mapped method.
+    fn extract_routes(&self, source: &str, root_node: tree_sitter::Node) -> Vec<serde_json::Value> {
+        let source_bytes = source.as_bytes();
+        let mut routes = Vec::new();
+
+        let mut cursor = QueryCursor::new();
+        let mut iter = cursor.matches(&self.class_query, root_node, source_bytes);
+        let mut class_decls = Vec::new();
+        while let Some(m) = iter.next() {
+            for capture in m.captures {
+                if let Some(class_decl) = capture.node.parent() {
+                    class_decls.push(class_decl);
+                }
+            }
+        }
+
+        for class_decl in class_decls {
+            let class_name = class_decl
+                .child_by_field_name("name")
+                .and_then(|n| n.utf8_text(source_bytes).ok())
+                .unwrap_or("");
+            let prefix = Self::mapping_annotation_on(class_decl, source_bytes)
+                .and_then(|(_, path)| path)
+                .unwrap_or_default();
+
+            let Some(body) = class_decl.child_by_field_name("body") else {
+                continue;
+            };
+            let mut body_cursor = body.walk();
+            for member in body.children(&mut body_cursor) {
+                if member.kind() != "method_declaration" {
+                    continue;
+                }
+                let Some((verb, path)) = Self::mapping_annotation_on(member, source_bytes) else {
+                    continue;
+                };
+                let method_name = member
+                    .child_by_field_name("name")
+                    .and_then(|n| n.utf8_text(source_bytes).ok())
+                    .unwrap_or("");
+                let full_path = format!("{}{}", prefix, path.unwrap_or_default());
+                routes.push(serde_json::json!({
+                    "method": verb.unwrap_or_else(|| "GET".to_string()),
+                    "path": full_path,
+                    "handler": format!("{}.{}", class_name, method_name),
+                }));
+            }
+        }
+
+        routes
+    }
 }
 
 impl Parser for JavaParser {
     fn parse(&mut self, source: &str) -> Result<ParseResult> {
+        #[cfg(feature = "tracing")]
+        let _span = super::query_helpers::trace::parse_span("java", source.len()).entered();
+        #[cfg(feature = "tracing")]
+        let started_at = std::time::Instant::now();
+
         let tree = self
             .parser
             .parse(source, None)
@@ -227,23 +653,77 @@ impl Parser for JavaParser {
         let root_node = tree.root_node();
         let exports = self.extract_exports(source, root_node);
         let imports = self.extract_imports(source, root_node);
-        let dependencies = self.extract_dependencies(source, root_node);
+        let mut dependencies = self.extract_dependencies(source, root_node);
         let loc = source.lines().count();
 
+        #[cfg(feature = "tracing")]
+        super::query_helpers::trace::record_parse_complete(
+            started_at.elapsed(),
+            exports.len(),
+            imports.len(),
+            dependencies.len(),
+            super::query_helpers::trace::count_unmatched_nodes(root_node),
+        );
+
         let annotations = self.extract_annotations(source, root_node);
-        let custom_fields = if annotations.is_empty() {
+        let routes = self.extract_routes(source, root_node);
+        let type_relations = self.extract_type_relations(source, root_node);
+        let declarations = self.extract_declarations(source, root_node);
+        let annotation_arguments = self.extract_annotation_arguments(source, root_node);
+
+        // A class/interface's supertypes are dependencies too, same as an
+        // import, so the manifest's dependency graph can link a subclass
+        // to its base even when both live in the same file.
+        for relation in &type_relations {
+            if let Some(target) = relation.get("target").and_then(|t| t.as_str()) {
+                if !target.is_empty() && !dependencies.contains(&target.to_string()) {
+                    dependencies.push(target.to_string());
+                }
+            }
+        }
+        dependencies.sort();
+
+        let custom_fields = if annotations.is_empty()
+            && routes.is_empty()
+            && type_relations.is_empty()
+            && declarations.is_empty()
+            && annotation_arguments.is_empty()
+        {
             None
         } else {
             let mut fields = HashMap::new();
-            fields.insert(
-                "annotations".to_string(),
-                serde_json::Value::Array(
-                    annotations
-                        .into_iter()
-                        .map(serde_json::Value::String)
-                        .collect(),
-                ),
-            );
+            if !annotations.is_empty() {
+                fields.insert(
+                    "annotations".to_string(),
+                    serde_json::Value::Array(
+                        annotations
+                            .into_iter()
+                            .map(serde_json::Value::String)
+                            .collect(),
+                    ),
+                );
+            }
+            if !routes.is_empty() {
+                fields.insert("routes".to_string(), serde_json::Value::Array(routes));
+            }
+            if !type_relations.is_empty() {
+                fields.insert(
+                    "type_relations".to_string(),
+                    serde_json::Value::Array(type_relations),
+                );
+            }
+            if !declarations.is_empty() {
+                fields.insert(
+                    "declarations".to_string(),
+                    serde_json::Value::Array(declarations),
+                );
+            }
+            if !annotation_arguments.is_empty() {
+                fields.insert(
+                    "annotation_arguments".to_string(),
+                    serde_json::Value::Array(annotation_arguments),
+                );
+            }
             Some(fields)
         };
 
@@ -253,6 +733,10 @@ impl Parser for JavaParser {
                 imports,
                 dependencies,
                 loc,
+                re_exports: Vec::new(),
+                test_loc: 0,
+                symbols: Vec::new(),
+                diagnostics: crate::parser::collect_diagnostics(root_node),
             },
             custom_fields,
         })
@@ -348,6 +832,119 @@ public enum Status {
         assert!(result.metadata.exports.contains(&"Status".to_string()));
     }
 
+    #[test]
+    fn parse_java_spring_routes() {
+        let mut parser = JavaParser::new().unwrap();
+        let source = r#"
+@RestController
+@RequestMapping("/api/users")
+public class UserController {
+    @GetMapping("/{id}")
+    public User getUser() { return null; }
+
+    @PostMapping
+    public User createUser() { return null; }
+
+    @RequestMapping(value = "/{id}", method = RequestMethod.DELETE)
+    public void deleteUser() {}
+}
+"#;
+        let result = parser.parse(source).unwrap();
+        let fields = result.custom_fields.unwrap();
+        let routes = fields.get("routes").unwrap().as_array().unwrap();
+
+        let get_route = routes
+            .iter()
+            .find(|r| r["handler"] == "UserController.getUser")
+            .unwrap();
+        assert_eq!(get_route["method"], "GET");
+        assert_eq!(get_route["path"], "/api/users/{id}");
+
+        let post_route = routes
+            .iter()
+            .find(|r| r["handler"] == "UserController.createUser")
+            .unwrap();
+        assert_eq!(post_route["method"], "POST");
+        assert_eq!(post_route["path"], "/api/users");
+
+        let delete_route = routes
+            .iter()
+            .find(|r| r["handler"] == "UserController.deleteUser")
+            .unwrap();
+        assert_eq!(delete_route["method"], "DELETE");
+        assert_eq!(delete_route["path"], "/api/users/{id}");
+    }
+
+    #[test]
+    fn parse_java_nested_and_record_declarations() {
+        let mut parser = JavaParser::new().unwrap();
+        let source = r#"
+public sealed class Shape permits Circle {
+    public static final class Inner {}
+}
+
+record Circle(double radius) {}
+"#;
+        let result = parser.parse(source).unwrap();
+        assert!(result.metadata.exports.contains(&"Shape".to_string()));
+        assert!(result.metadata.exports.contains(&"Inner".to_string()));
+        assert!(result.metadata.exports.contains(&"Circle".to_string()));
+
+        let fields = result.custom_fields.unwrap();
+        let declarations = fields.get("declarations").unwrap().as_array().unwrap();
+
+        let shape = declarations
+            .iter()
+            .find(|d| d["name"] == "Shape")
+            .unwrap();
+        assert_eq!(shape["kind"], "class");
+        assert_eq!(shape["sealed"], true);
+        assert_eq!(shape["nested"], false);
+
+        let inner = declarations
+            .iter()
+            .find(|d| d["name"] == "Inner")
+            .unwrap();
+        assert_eq!(inner["nested"], true);
+
+        let circle = declarations
+            .iter()
+            .find(|d| d["name"] == "Circle")
+            .unwrap();
+        assert_eq!(circle["kind"], "record");
+    }
+
+    #[test]
+    fn parse_java_extends_become_dependencies() {
+        let mut parser = JavaParser::new().unwrap();
+        let source = r#"
+public class AdminUser extends User implements Serializable {
+}
+"#;
+        let result = parser.parse(source).unwrap();
+        assert!(result.metadata.dependencies.contains(&"User".to_string()));
+        assert!(result
+            .metadata
+            .dependencies
+            .contains(&"Serializable".to_string()));
+    }
+
+    #[test]
+    fn parse_java_annotation_arguments_are_structured() {
+        let mut parser = JavaParser::new().unwrap();
+        let source = r#"
+@RestController
+@RequestMapping("/api/users")
+public class UserController {
+}
+"#;
+        let result = parser.parse(source).unwrap();
+        let fields = result.custom_fields.unwrap();
+        let args = fields.get("annotation_arguments").unwrap().as_array().unwrap();
+        let mapping = args.iter().find(|a| a["name"] == "RequestMapping").unwrap();
+        assert_eq!(mapping["arguments"]["value"], "/api/users");
+    }
+
     #[test]
     fn parse_java_empty() {
         let mut parser = JavaParser::new().unwrap();
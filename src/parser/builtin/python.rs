@@ -1,5 +1,5 @@
 use super::query_helpers::collect_matches;
-use crate::parser::{Metadata, ParseResult, Parser};
+use crate::parser::{Metadata, ParseResult, Parser, ReExportEntry};
 use anyhow::Result;
 use std::collections::{HashMap, HashSet};
 use streaming_iterator::StreamingIterator;
@@ -106,8 +106,9 @@ impl PythonParser {
             }
         };
 
-        // Top-level function definitions
-        collect_filtered(&self.func_query, |_| true);
+        // Top-level function definitions (test functions are classified
+        // separately, see `extract_test_functions`, and excluded here)
+        collect_filtered(&self.func_query, |name| !Self::is_test_name(name));
 
         // Top-level class definitions
         collect_filtered(&self.class_query, |_| true);
@@ -210,6 +211,128 @@ impl PythonParser {
         collect_matches(&self.relative_import_query, root_node, source.as_bytes())
     }
 
+    /// Relative imports (`from ._api import get`) that are also re-surfaced
+    /// in `__all__` — the package is forwarding a symbol it imported rather
+    /// than defined, the Python counterpart of a JS barrel file.
+    fn extract_re_exports(&self, source: &str, root_node: tree_sitter::Node) -> Vec<ReExportEntry> {
+        let Some(dunder_all) = self.extract_dunder_all(source, root_node) else {
+            return Vec::new();
+        };
+        let all_set: HashSet<&str> = dunder_all.iter().map(|s| s.as_str()).collect();
+        let source_bytes = source.as_bytes();
+        let mut re_exports = Vec::new();
+
+        let mut cursor = root_node.walk();
+        for child in root_node.children(&mut cursor) {
+            if child.kind() != "import_from_statement" {
+                continue;
+            }
+            let Some(module_node) = child.child_by_field_name("module_name") else {
+                continue;
+            };
+            if module_node.kind() != "relative_import" {
+                continue;
+            }
+            let Ok(module_text) = module_node.utf8_text(source_bytes) else {
+                continue;
+            };
+
+            let mut inner = child.walk();
+            for item in child.children(&mut inner) {
+                match item.kind() {
+                    "dotted_name" => {
+                        if let Ok(name) = item.utf8_text(source_bytes) {
+                            if all_set.contains(name) {
+                                re_exports
+                                    .push(ReExportEntry::new(name.to_string(), module_text.to_string()));
+                            }
+                        }
+                    }
+                    "aliased_import" => {
+                        let name = item
+                            .child_by_field_name("name")
+                            .and_then(|n| n.utf8_text(source_bytes).ok());
+                        let alias = item
+                            .child_by_field_name("alias")
+                            .and_then(|n| n.utf8_text(source_bytes).ok());
+                        if let (Some(name), Some(alias)) = (name, alias) {
+                            if all_set.contains(alias) {
+                                re_exports.push(
+                                    ReExportEntry::new(alias.to_string(), module_text.to_string())
+                                        .with_alias(Some(name.to_string())),
+                                );
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        re_exports.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+        re_exports
+    }
+
+    /// `pytest`/`unittest` naming convention: `test_*` functions and methods.
+    fn is_test_name(name: &str) -> bool {
+        name.starts_with("test_") || name == "test"
+    }
+
+    /// Walks the whole tree (module-level `def test_foo` and methods on
+    /// `unittest.TestCase` subclasses alike) collecting test function names.
+    fn collect_test_functions(
+        node: tree_sitter::Node,
+        source_bytes: &[u8],
+        out: &mut Vec<String>,
+    ) {
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            if child.kind() == "function_definition" {
+                if let Some(name_node) = child.child_by_field_name("name") {
+                    if let Ok(name) = name_node.utf8_text(source_bytes) {
+                        if Self::is_test_name(name) {
+                            out.push(name.to_string());
+                        }
+                    }
+                }
+            }
+            Self::collect_test_functions(child, source_bytes, out);
+        }
+    }
+
+    fn extract_test_functions(&self, source: &str, root_node: tree_sitter::Node) -> Vec<String> {
+        let mut names = Vec::new();
+        Self::collect_test_functions(root_node, source.as_bytes(), &mut names);
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    /// Line span of every `test_*` function/method, already counted in `loc`.
+    fn test_loc(&self, source: &str, root_node: tree_sitter::Node) -> usize {
+        let source_bytes = source.as_bytes();
+        let mut total = 0;
+        let mut stack = vec![root_node];
+        while let Some(node) = stack.pop() {
+            let mut cursor = node.walk();
+            for child in node.children(&mut cursor) {
+                if child.kind() == "function_definition" {
+                    let is_test = child
+                        .child_by_field_name("name")
+                        .and_then(|n| n.utf8_text(source_bytes).ok())
+                        .is_some_and(Self::is_test_name);
+                    if is_test {
+                        let span = child.end_position().row - child.start_position().row + 1;
+                        total += span;
+                        continue;
+                    }
+                }
+                stack.push(child);
+            }
+        }
+        total
+    }
+
     fn extract_decorators(&self, source: &str, root_node: tree_sitter::Node) -> Vec<String> {
         let source_bytes = source.as_bytes();
         let simple = collect_matches(&self.decorator_query, root_node, source_bytes);
@@ -235,22 +358,36 @@ impl Parser for PythonParser {
         let exports = self.extract_exports(source, root_node);
         let imports = self.extract_imports(source, root_node);
         let dependencies = self.extract_dependencies(source, root_node);
+        let re_exports = self.extract_re_exports(source, root_node);
         let loc = source.lines().count();
+        let test_functions = self.extract_test_functions(source, root_node);
+        let test_loc = self.test_loc(source, root_node);
 
         let decorators = self.extract_decorators(source, root_node);
-        let custom_fields = if decorators.is_empty() {
+        let custom_fields = if decorators.is_empty() && test_functions.is_empty() {
             None
         } else {
             let mut fields = HashMap::new();
-            fields.insert(
-                "decorators".to_string(),
-                serde_json::Value::Array(
-                    decorators
-                        .into_iter()
-                        .map(serde_json::Value::String)
-                        .collect(),
-                ),
-            );
+            if !decorators.is_empty() {
+                fields.insert(
+                    "decorators".to_string(),
+                    serde_json::Value::Array(
+                        decorators
+                            .into_iter()
+                            .map(serde_json::Value::String)
+                            .collect(),
+                    ),
+                );
+            }
+            if !test_functions.is_empty() {
+                fields.insert(
+                    "test_functions".to_string(),
+                    serde_json::json!({
+                        "count": test_functions.len(),
+                        "names": test_functions,
+                    }),
+                );
+            }
             Some(fields)
         };
 
@@ -260,6 +397,10 @@ impl Parser for PythonParser {
                 imports,
                 dependencies,
                 loc,
+                re_exports,
+                test_loc,
+                symbols: Vec::new(),
+                diagnostics: crate::parser::collect_diagnostics(root_node),
             },
             custom_fields,
         })
@@ -369,6 +510,46 @@ class _InternalClass:
         assert_eq!(result.metadata.exports, vec!["PublicClass", "public_func"]);
     }
 
+    #[test]
+    fn parse_python_httpx_style_init_tracks_re_exports() {
+        let mut parser = PythonParser::new().unwrap();
+        let source = r#"
+from ._api import get
+from ._api import post as send_post
+from ._models import Response
+
+__all__ = ["get", "send_post", "Response"]
+"#;
+        let result = parser.parse(source).unwrap();
+        assert_eq!(result.metadata.exports, vec!["Response", "get", "send_post"]);
+
+        let get = result
+            .metadata
+            .re_exports
+            .iter()
+            .find(|r| r.symbol == "get")
+            .unwrap();
+        assert_eq!(get.source_module, "._api");
+        assert!(get.alias.is_none());
+
+        let send_post = result
+            .metadata
+            .re_exports
+            .iter()
+            .find(|r| r.symbol == "send_post")
+            .unwrap();
+        assert_eq!(send_post.source_module, "._api");
+        assert_eq!(send_post.alias.as_deref(), Some("post"));
+
+        let response = result
+            .metadata
+            .re_exports
+            .iter()
+            .find(|r| r.symbol == "Response")
+            .unwrap();
+        assert_eq!(response.source_module, "._models");
+    }
+
     #[test]
     fn parse_python_aliased_import() {
         let mut parser = PythonParser::new().unwrap();
@@ -395,4 +576,29 @@ def also_public():
         assert_eq!(result.metadata.exports, vec!["only_this"]);
         assert!(!result.metadata.exports.contains(&"also_public".to_string()));
     }
+
+    #[test]
+    fn parse_python_test_functions_excluded_from_exports() {
+        let mut parser = PythonParser::new().unwrap();
+        let source = "def public():\n    pass\n\ndef test_public():\n    assert True\n";
+        let result = parser.parse(source).unwrap();
+        assert!(result.metadata.exports.contains(&"public".to_string()));
+        assert!(!result.metadata.exports.contains(&"test_public".to_string()));
+
+        let fields = result.custom_fields.unwrap();
+        let test_functions = fields.get("test_functions").unwrap();
+        assert_eq!(test_functions.get("count").unwrap().as_u64(), Some(1));
+        let names = test_functions.get("names").unwrap().as_array().unwrap();
+        assert_eq!(names, &vec![serde_json::Value::String("test_public".to_string())]);
+    }
+
+    #[test]
+    fn parse_python_test_loc_split_from_production_loc() {
+        let mut parser = PythonParser::new().unwrap();
+        let source = "class MyTests:\n    def test_one(self):\n        assert True\n\ndef helper():\n    pass\n";
+        let result = parser.parse(source).unwrap();
+        assert_eq!(result.metadata.loc, source.lines().count());
+        assert!(result.metadata.test_loc > 0);
+        assert!(result.metadata.test_loc < result.metadata.loc);
+    }
 }
@@ -3,6 +3,62 @@ use std::collections::HashSet;
 use streaming_iterator::StreamingIterator;
 use tree_sitter::{Query, QueryCursor};
 
+/// Tracing instrumentation shared by the tree-sitter-backed builtin
+/// parsers. Gated behind the `tracing` feature so a parser built without
+/// it pays nothing for span bookkeeping or event formatting.
+#[cfg(feature = "tracing")]
+pub mod trace {
+    use tree_sitter::Node;
+
+    /// Open a span for a single `parse` call, recording the language and
+    /// the source size so per-language performance regressions show up
+    /// directly in a `tracing-subscriber`/`tracing-forest` hierarchy.
+    pub fn parse_span(language: &'static str, source_bytes: usize) -> tracing::Span {
+        tracing::info_span!("parse", language, source_bytes)
+    }
+
+    /// Count tree-sitter `ERROR`/`MISSING` nodes anywhere under `root`.
+    /// These are the nodes no extraction query can ever match, since
+    /// tree-sitter itself couldn't assign them a real grammar rule, as
+    /// opposed to a query simply not covering a construct. Today these are
+    /// silently dropped; surfacing the count gives users a signal for why
+    /// a file yielded fewer symbols than expected.
+    pub fn count_unmatched_nodes(root: Node) -> usize {
+        let mut cursor = root.walk();
+        let mut stack = vec![root];
+        let mut count = 0;
+        while let Some(node) = stack.pop() {
+            if node.is_error() || node.is_missing() {
+                count += 1;
+            }
+            stack.extend(node.children(&mut cursor));
+        }
+        count
+    }
+
+    /// Emit a structured event summarizing a completed parse: how long it
+    /// took and how many exports/imports/dependencies it found, plus the
+    /// unmatched-node count from [`count_unmatched_nodes`].
+    #[allow(clippy::too_many_arguments)]
+    pub fn record_parse_complete(
+        duration: std::time::Duration,
+        exports: usize,
+        imports: usize,
+        dependencies: usize,
+        unmatched_nodes: usize,
+    ) {
+        tracing::event!(
+            tracing::Level::DEBUG,
+            duration_us = duration.as_micros() as u64,
+            exports,
+            imports,
+            dependencies,
+            unmatched_nodes,
+            "parse complete"
+        );
+    }
+}
+
 /// Collect unique text from all captures of a query, returned as a sorted Vec.
 pub fn collect_matches(
     query: &Query,
@@ -77,11 +133,14 @@ pub fn collect_matches_with_lines(
                 let name = text.to_string();
                 if seen.insert(name.clone()) {
                     let decl = top_level_ancestor(capture.node);
-                    results.push(ExportEntry::new(
+                    let entry = ExportEntry::new(
                         name,
                         decl.start_position().row + 1,
                         decl.end_position().row + 1,
-                    ));
+                    )
+                    .with_doc(preceding_doc_comment(decl, source_bytes))
+                    .with_signature(declaration_signature(decl, source_bytes));
+                    results.push(entry);
                 }
             }
         }
@@ -90,6 +149,105 @@ pub fn collect_matches_with_lines(
     results
 }
 
+/// The doc comment attached to `decl`, language-agnostically: a single
+/// block comment (`/** ... */`, Python `"""..."""`) directly above it with
+/// no blank line in between, or — failing that — the contiguous run of
+/// single-line comments (`//` or `#`) ending on the line just above it,
+/// grouped the way a comment-block collector would (walking back sibling by
+/// sibling until the run breaks). Returns `None` if the preceding sibling
+/// isn't a comment, is separated from `decl` by a blank line, or is a block
+/// comment that isn't doc-style.
+fn preceding_doc_comment(decl: tree_sitter::Node, source_bytes: &[u8]) -> Option<String> {
+    let sibling = decl.prev_sibling()?;
+    if sibling.kind() != "comment" || !directly_above(sibling, decl) {
+        return None;
+    }
+    let text = sibling.utf8_text(source_bytes).ok()?;
+
+    if let Some(body) = strip_doc_block_comment(text) {
+        return (!body.is_empty()).then_some(body);
+    }
+    if !is_line_comment(text) {
+        return None;
+    }
+
+    let mut lines = vec![strip_line_comment(text)];
+    let mut current = sibling;
+    while let Some(prev) = current.prev_sibling() {
+        if prev.kind() != "comment" || !directly_above(prev, current) {
+            break;
+        }
+        let Ok(prev_text) = prev.utf8_text(source_bytes) else {
+            break;
+        };
+        if !is_line_comment(prev_text) {
+            break;
+        }
+        lines.push(strip_line_comment(prev_text));
+        current = prev;
+    }
+    lines.reverse();
+    let joined = lines.join("\n");
+    (!joined.trim().is_empty()).then_some(joined)
+}
+
+/// Whether `upper` ends on the line directly above where `lower` starts,
+/// i.e. there's no blank line (or anything else) between them.
+fn directly_above(upper: tree_sitter::Node, lower: tree_sitter::Node) -> bool {
+    upper.end_position().row + 1 == lower.start_position().row
+}
+
+fn strip_doc_block_comment(text: &str) -> Option<String> {
+    let inner = text
+        .strip_prefix("/**")
+        .and_then(|rest| rest.strip_suffix("*/"))
+        .or_else(|| {
+            text.strip_prefix("\"\"\"")
+                .and_then(|rest| rest.strip_suffix("\"\"\""))
+        })?;
+    let lines: Vec<&str> = inner
+        .lines()
+        .map(|line| line.trim().trim_start_matches('*').trim())
+        .filter(|line| !line.is_empty())
+        .collect();
+    Some(lines.join("\n"))
+}
+
+fn is_line_comment(text: &str) -> bool {
+    text.starts_with("//") || text.starts_with('#')
+}
+
+fn strip_line_comment(text: &str) -> String {
+    text.trim_start_matches('#')
+        .trim_start_matches("//")
+        .trim_start()
+        .to_string()
+}
+
+/// `decl`'s signature: the text from its start up to (but not including)
+/// its body, found by locating the first child whose kind looks like a
+/// block/body (`block`, `suite`, or any `*_body`, covering the Rust/TS/Go
+/// and Python/Ruby conventions respectively). Falls back to `decl`'s own
+/// first line when no such child exists (e.g. a one-line `const`
+/// declaration with no block to exclude).
+fn declaration_signature(decl: tree_sitter::Node, source_bytes: &[u8]) -> Option<String> {
+    let mut cursor = decl.walk();
+    let body = decl.children(&mut cursor).find(|child| {
+        let kind = child.kind();
+        kind == "block" || kind == "suite" || kind.ends_with("_body")
+    });
+
+    let text = match body {
+        Some(body) if body.start_byte() > decl.start_byte() => {
+            std::str::from_utf8(&source_bytes[decl.start_byte()..body.start_byte()]).ok()?
+        }
+        _ => decl.utf8_text(source_bytes).ok()?.lines().next()?,
+    };
+
+    let signature = text.trim().trim_end_matches(['{', ':']).trim();
+    (!signature.is_empty()).then(|| signature.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -194,6 +352,73 @@ mod tests {
         assert!(results.is_empty());
     }
 
+    #[test]
+    fn collect_matches_with_lines_captures_a_leading_block_doc_comment() {
+        let (lang, mut parser) = setup_ts();
+        let source = "/**\n * Adds two numbers.\n */\nexport function add(a, b) {}";
+        let tree = parser.parse(source, None).unwrap();
+        let query = Query::new(
+            &lang,
+            "(export_statement (function_declaration name: (identifier) @name))",
+        )
+        .unwrap();
+
+        let results = collect_matches_with_lines(&query, tree.root_node(), source.as_bytes());
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].doc.as_deref(), Some("Adds two numbers."));
+    }
+
+    #[test]
+    fn collect_matches_with_lines_groups_contiguous_line_comments() {
+        let (lang, mut parser) = setup_ts();
+        let source = "// First line.\n// Second line.\nexport function add(a, b) {}";
+        let tree = parser.parse(source, None).unwrap();
+        let query = Query::new(
+            &lang,
+            "(export_statement (function_declaration name: (identifier) @name))",
+        )
+        .unwrap();
+
+        let results = collect_matches_with_lines(&query, tree.root_node(), source.as_bytes());
+        assert_eq!(results.len(), 1);
+        assert_eq!(
+            results[0].doc.as_deref(),
+            Some("First line.\nSecond line.")
+        );
+    }
+
+    #[test]
+    fn collect_matches_with_lines_ignores_a_comment_separated_by_a_blank_line() {
+        let (lang, mut parser) = setup_ts();
+        let source = "// Unrelated comment.\n\nexport function add(a, b) {}";
+        let tree = parser.parse(source, None).unwrap();
+        let query = Query::new(
+            &lang,
+            "(export_statement (function_declaration name: (identifier) @name))",
+        )
+        .unwrap();
+
+        let results = collect_matches_with_lines(&query, tree.root_node(), source.as_bytes());
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].doc, None);
+    }
+
+    #[test]
+    fn collect_matches_with_lines_captures_the_signature_without_the_body() {
+        let (lang, mut parser) = setup_ts();
+        let source = "export function add(a, b) {\n  return a + b;\n}";
+        let tree = parser.parse(source, None).unwrap();
+        let query = Query::new(
+            &lang,
+            "(export_statement (function_declaration name: (identifier) @name))",
+        )
+        .unwrap();
+
+        let results = collect_matches_with_lines(&query, tree.root_node(), source.as_bytes());
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].signature.as_deref(), Some("export function add(a, b)"));
+    }
+
     #[test]
     fn collect_named_matches_deduplicates_and_sorts() {
         let (lang, mut parser) = setup_ts();
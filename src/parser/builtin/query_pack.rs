@@ -0,0 +1,244 @@
+use crate::config::query_pack::LanguageQueryPack;
+use crate::parser::ExportEntry;
+use anyhow::Result;
+use std::collections::HashMap;
+use tree_sitter::{Language, Query};
+
+use super::query_helpers::{collect_matches, collect_matches_with_lines};
+
+/// A [`LanguageQueryPack`]'s queries, compiled against one parser's
+/// [`tree_sitter::Language`] and validated up front so a bad query
+/// surfaces at construction time — tagged with its field name — instead
+/// of silently matching nothing on every parse.
+#[derive(Default)]
+pub struct CompiledQueryPack {
+    exports_query: Option<Query>,
+    imports_query: Option<Query>,
+    dependencies_query: Option<Query>,
+    custom_fields: Vec<(String, Query)>,
+}
+
+impl CompiledQueryPack {
+    /// Compile every query in `pack` against `language`. `pack` of `None`
+    /// (no `fmm.queries.toml`, or no entry for this language) compiles to
+    /// an empty pack that contributes nothing.
+    pub fn compile(language: &Language, pack: Option<&LanguageQueryPack>) -> Result<Self> {
+        let Some(pack) = pack else {
+            return Ok(Self::default());
+        };
+
+        let compile_named = |name: &str, query_str: &str| -> Result<Query> {
+            Query::new(language, query_str)
+                .map_err(|e| anyhow::anyhow!("query pack: failed to compile '{}' query: {}", name, e))
+        };
+
+        let exports_query = pack
+            .exports
+            .as_deref()
+            .map(|q| compile_named("exports", q))
+            .transpose()?;
+        let imports_query = pack
+            .imports
+            .as_deref()
+            .map(|q| compile_named("imports", q))
+            .transpose()?;
+        let dependencies_query = pack
+            .dependencies
+            .as_deref()
+            .map(|q| compile_named("dependencies", q))
+            .transpose()?;
+
+        let mut custom_fields = Vec::with_capacity(pack.custom_fields.len());
+        for (name, query_str) in &pack.custom_fields {
+            let query = compile_named(name, query_str)?;
+            custom_fields.push((name.clone(), query));
+        }
+        Ok(Self {
+            exports_query,
+            imports_query,
+            dependencies_query,
+            custom_fields,
+        })
+    }
+
+    /// Matches of the configured `exports` query, as [`ExportEntry`]s with
+    /// line ranges — the form [`crate::parser::Metadata::exports`] wants.
+    /// Empty when no `exports` query is configured.
+    pub fn exports(&self, root_node: tree_sitter::Node, source_bytes: &[u8]) -> Vec<ExportEntry> {
+        match &self.exports_query {
+            Some(query) => collect_matches_with_lines(query, root_node, source_bytes),
+            None => Vec::new(),
+        }
+    }
+
+    /// Matches of the configured `exports` query as plain names — for a
+    /// caller whose own `exports` field is a flat name list rather than
+    /// [`ExportEntry`]s. Empty when no `exports` query is configured.
+    pub fn export_names(&self, root_node: tree_sitter::Node, source_bytes: &[u8]) -> Vec<String> {
+        match &self.exports_query {
+            Some(query) => collect_matches(query, root_node, source_bytes),
+            None => Vec::new(),
+        }
+    }
+
+    /// Matches of the configured `imports` query. Empty when none is
+    /// configured.
+    pub fn imports(&self, root_node: tree_sitter::Node, source_bytes: &[u8]) -> Vec<String> {
+        match &self.imports_query {
+            Some(query) => collect_matches(query, root_node, source_bytes),
+            None => Vec::new(),
+        }
+    }
+
+    /// Matches of the configured `dependencies` query. Empty when none is
+    /// configured.
+    pub fn dependencies(&self, root_node: tree_sitter::Node, source_bytes: &[u8]) -> Vec<String> {
+        match &self.dependencies_query {
+            Some(query) => collect_matches(query, root_node, source_bytes),
+            None => Vec::new(),
+        }
+    }
+
+    /// Run every compiled query against `root_node`, returning one JSON
+    /// array of matched capture text per field name, ready to merge into
+    /// [`crate::parser::ParseResult::custom_fields`]. A query with no
+    /// matches contributes no key at all.
+    pub fn run(
+        &self,
+        root_node: tree_sitter::Node,
+        source_bytes: &[u8],
+    ) -> HashMap<String, serde_json::Value> {
+        self.custom_fields
+            .iter()
+            .filter_map(|(name, query)| {
+                let matches = collect_matches(query, root_node, source_bytes);
+                if matches.is_empty() {
+                    None
+                } else {
+                    Some((
+                        name.clone(),
+                        serde_json::Value::Array(
+                            matches.into_iter().map(serde_json::Value::String).collect(),
+                        ),
+                    ))
+                }
+            })
+            .collect()
+    }
+
+    /// Whether this pack has nothing configured at all — no `exports`,
+    /// `imports`, `dependencies`, or `custom_fields` query.
+    pub fn is_empty(&self) -> bool {
+        self.exports_query.is_none()
+            && self.imports_query.is_none()
+            && self.dependencies_query.is_none()
+            && self.custom_fields.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap as StdHashMap;
+    use tree_sitter::Parser as TSParser;
+
+    fn ruby_language() -> Language {
+        tree_sitter_ruby::LANGUAGE.into()
+    }
+
+    #[test]
+    fn compile_with_no_pack_is_empty() {
+        let pack = CompiledQueryPack::compile(&ruby_language(), None).unwrap();
+        assert!(pack.is_empty());
+    }
+
+    #[test]
+    fn compile_rejects_invalid_query_with_name() {
+        let mut custom_fields = StdHashMap::new();
+        custom_fields.insert("broken".to_string(), "(not a valid query".to_string());
+        let language_pack = LanguageQueryPack { custom_fields, ..Default::default() };
+
+        let err = CompiledQueryPack::compile(&ruby_language(), Some(&language_pack)).unwrap_err();
+        assert!(err.to_string().contains("broken"));
+    }
+
+    #[test]
+    fn run_collects_matches_under_field_name() {
+        let language = ruby_language();
+        let mut custom_fields = StdHashMap::new();
+        custom_fields.insert(
+            "associations".to_string(),
+            "(call method: (identifier) @method (#match? @method \"^(has_many|belongs_to)$\"))"
+                .to_string(),
+        );
+        let language_pack = LanguageQueryPack { custom_fields, ..Default::default() };
+        let pack = CompiledQueryPack::compile(&language, Some(&language_pack)).unwrap();
+
+        let mut parser = TSParser::new();
+        parser.set_language(&language).unwrap();
+        let source = "class Post\n  belongs_to :author\n  has_many :comments\nend\n";
+        let tree = parser.parse(source, None).unwrap();
+
+        let fields = pack.run(tree.root_node(), source.as_bytes());
+        let matches = fields.get("associations").unwrap().as_array().unwrap();
+        assert_eq!(matches.len(), 2);
+    }
+
+    #[test]
+    fn run_omits_fields_with_no_matches() {
+        let language = ruby_language();
+        let mut custom_fields = StdHashMap::new();
+        custom_fields.insert(
+            "associations".to_string(),
+            "(call method: (identifier) @method (#match? @method \"^(has_many|belongs_to)$\"))"
+                .to_string(),
+        );
+        let language_pack = LanguageQueryPack { custom_fields, ..Default::default() };
+        let pack = CompiledQueryPack::compile(&language, Some(&language_pack)).unwrap();
+
+        let mut parser = TSParser::new();
+        parser.set_language(&language).unwrap();
+        let source = "class Post\nend\n";
+        let tree = parser.parse(source, None).unwrap();
+
+        let fields = pack.run(tree.root_node(), source.as_bytes());
+        assert!(fields.is_empty());
+    }
+
+    #[test]
+    fn exports_query_returns_entries_with_line_ranges() {
+        let language = ruby_language();
+        let language_pack = LanguageQueryPack {
+            exports: Some("(method name: (identifier) @export)".to_string()),
+            ..Default::default()
+        };
+        let pack = CompiledQueryPack::compile(&language, Some(&language_pack)).unwrap();
+
+        let mut parser = TSParser::new();
+        parser.set_language(&language).unwrap();
+        let source = "def greet\nend\n";
+        let tree = parser.parse(source, None).unwrap();
+
+        let entries = pack.exports(tree.root_node(), source.as_bytes());
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].name, "greet");
+
+        let names = pack.export_names(tree.root_node(), source.as_bytes());
+        assert_eq!(names, vec!["greet"]);
+    }
+
+    #[test]
+    fn imports_and_dependencies_queries_default_to_empty_when_unconfigured() {
+        let pack = CompiledQueryPack::compile(&ruby_language(), None).unwrap();
+        let mut parser = TSParser::new();
+        parser.set_language(&ruby_language()).unwrap();
+        let source = "require 'set'\n";
+        let tree = parser.parse(source, None).unwrap();
+
+        assert!(pack.imports(tree.root_node(), source.as_bytes()).is_empty());
+        assert!(pack
+            .dependencies(tree.root_node(), source.as_bytes())
+            .is_empty());
+        assert!(pack.is_empty());
+    }
+}
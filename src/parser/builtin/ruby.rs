@@ -1,32 +1,42 @@
-use crate::parser::{Metadata, ParseResult, Parser};
+use super::query_pack::CompiledQueryPack;
+use crate::config::query_pack::LanguageQueryPack;
+use crate::parser::{Metadata, ParseResult, Parser, Symbol, SymbolKind, Visibility};
 use anyhow::Result;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use streaming_iterator::StreamingIterator;
-use tree_sitter::{Language, Parser as TSParser, Query, QueryCursor};
+use tree_sitter::{InputEdit, Language, Parser as TSParser, Query, QueryCursor, Tree};
 
 pub struct RubyParser {
     parser: TSParser,
-    class_query: Query,
-    module_query: Query,
     method_query: Query,
     require_query: Query,
     require_relative_query: Query,
+    /// User-supplied `custom_fields` queries from `fmm.queries.toml`
+    /// (e.g. Rails `has_many`/`belongs_to`), run alongside the hard-coded
+    /// extraction below. Empty when no query pack is configured.
+    query_pack: CompiledQueryPack,
+    /// Previous parse tree per file path, reused by [`RubyParser::parse_edit`]
+    /// so tree-sitter only re-derives the edited region instead of the
+    /// whole file.
+    trees: HashMap<PathBuf, Tree>,
 }
 
 impl RubyParser {
     pub fn new() -> Result<Self> {
+        Self::with_query_pack(None)
+    }
+
+    /// Like [`RubyParser::new`], additionally running `query_pack`'s
+    /// `custom_fields` queries (if any) on every parse. Pass `None` when no
+    /// `fmm.queries.toml` entry exists for `"ruby"`.
+    pub fn with_query_pack(query_pack: Option<&LanguageQueryPack>) -> Result<Self> {
         let language: Language = tree_sitter_ruby::LANGUAGE.into();
         let mut parser = TSParser::new();
         parser
             .set_language(&language)
             .map_err(|e| anyhow::anyhow!("Failed to set Ruby language: {}", e))?;
 
-        let class_query = Query::new(&language, "(program (class name: (constant) @name))")
-            .map_err(|e| anyhow::anyhow!("Failed to compile class query: {}", e))?;
-
-        let module_query = Query::new(&language, "(program (module name: (constant) @name))")
-            .map_err(|e| anyhow::anyhow!("Failed to compile module query: {}", e))?;
-
         let method_query = Query::new(&language, "(program (method name: (identifier) @name))")
             .map_err(|e| anyhow::anyhow!("Failed to compile method query: {}", e))?;
 
@@ -42,13 +52,15 @@ impl RubyParser {
         )
         .map_err(|e| anyhow::anyhow!("Failed to compile require_relative query: {}", e))?;
 
+        let query_pack = CompiledQueryPack::compile(&language, query_pack)?;
+
         Ok(Self {
             parser,
-            class_query,
-            module_query,
             method_query,
             require_query,
             require_relative_query,
+            query_pack,
+            trees: HashMap::new(),
         })
     }
 
@@ -56,49 +68,371 @@ impl RubyParser {
         let mut exports = Vec::new();
         let source_bytes = source.as_bytes();
 
-        // Top-level classes
+        // Classes and modules, qualified by their enclosing scope (so a
+        // `class Bar` nested inside `module Foo` is exported as
+        // `Foo::Bar`, not just `Bar`).
+        let mut scope = Vec::new();
+        Self::collect_scoped_exports(root_node, source_bytes, &mut scope, &mut exports);
+
+        // Top-level methods (not starting with _)
         let mut cursor = QueryCursor::new();
-        let mut iter = cursor.matches(&self.class_query, root_node, source_bytes);
+        let mut iter = cursor.matches(&self.method_query, root_node, source_bytes);
         while let Some(m) = iter.next() {
             for capture in m.captures {
                 if let Ok(text) = capture.node.utf8_text(source_bytes) {
-                    if !exports.contains(&text.to_string()) {
-                        exports.push(text.to_string());
+                    let name = text.to_string();
+                    if !name.starts_with('_') && !exports.contains(&name) {
+                        exports.push(name);
                     }
                 }
             }
         }
 
-        // Top-level modules
-        let mut cursor = QueryCursor::new();
-        let mut iter = cursor.matches(&self.module_query, root_node, source_bytes);
-        while let Some(m) = iter.next() {
-            for capture in m.captures {
-                if let Ok(text) = capture.node.utf8_text(source_bytes) {
-                    if !exports.contains(&text.to_string()) {
-                        exports.push(text.to_string());
+        // Reader/writer methods synthesized by attr_accessor/attr_reader/attr_writer
+        for name in Self::collect_attr_methods(root_node, source_bytes) {
+            if !exports.contains(&name) {
+                exports.push(name);
+            }
+        }
+
+        exports.sort();
+        exports.dedup();
+        exports
+    }
+
+    /// Walks `class`/`module` declarations maintaining a stack of
+    /// enclosing scope names, emitting each as its fully-qualified path
+    /// joined by `::`. Reopening the same class/module twice (e.g. a
+    /// second `class Foo` block later in the file) is deduped by the
+    /// `exports.contains` check. `class << self` opens a singleton body
+    /// without introducing a new scope name, so it recurses without
+    /// pushing onto the stack.
+    fn collect_scoped_exports(
+        node: tree_sitter::Node,
+        source_bytes: &[u8],
+        scope: &mut Vec<String>,
+        exports: &mut Vec<String>,
+    ) {
+        match node.kind() {
+            "class" | "module" => {
+                let pushed = node
+                    .child_by_field_name("name")
+                    .map(|name_node| {
+                        let segments = Self::constant_path(name_node, source_bytes);
+                        let pushed = segments.len();
+                        scope.extend(segments);
+                        pushed
+                    })
+                    .unwrap_or(0);
+
+                if pushed > 0 {
+                    let qualified = scope.join("::");
+                    if !exports.contains(&qualified) {
+                        exports.push(qualified);
                     }
                 }
+
+                let mut cursor = node.walk();
+                for child in node.children(&mut cursor) {
+                    Self::collect_scoped_exports(child, source_bytes, scope, exports);
+                }
+
+                for _ in 0..pushed {
+                    scope.pop();
+                }
+            }
+            _ => {
+                let mut cursor = node.walk();
+                for child in node.children(&mut cursor) {
+                    Self::collect_scoped_exports(child, source_bytes, scope, exports);
+                }
             }
         }
+    }
 
-        // Top-level methods (not starting with _)
-        let mut cursor = QueryCursor::new();
-        let mut iter = cursor.matches(&self.method_query, root_node, source_bytes);
-        while let Some(m) = iter.next() {
-            for capture in m.captures {
-                if let Ok(text) = capture.node.utf8_text(source_bytes) {
-                    let name = text.to_string();
-                    if !name.starts_with('_') && !exports.contains(&name) {
-                        exports.push(name);
+    /// Splits a class/module name node into its path segments, so both a
+    /// simple `constant` (`Foo`) and a compact `scope_resolution`
+    /// (`Foo::Bar`) push the right number of names onto the scope stack.
+    fn constant_path(node: tree_sitter::Node, source_bytes: &[u8]) -> Vec<String> {
+        match node.kind() {
+            "scope_resolution" => node
+                .utf8_text(source_bytes)
+                .unwrap_or("")
+                .split("::")
+                .filter(|s| !s.is_empty())
+                .map(|s| s.to_string())
+                .collect(),
+            _ => node
+                .utf8_text(source_bytes)
+                .map(|s| vec![s.to_string()])
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Typed symbol table alongside the legacy `extract_exports` name list:
+    /// classes/modules (qualified the same way `collect_scoped_exports`
+    /// qualifies them) and every method, tagged with the `private`/
+    /// `protected`/`public` visibility in effect where it's defined.
+    fn collect_symbols(
+        node: tree_sitter::Node,
+        source_bytes: &[u8],
+        scope: &mut Vec<String>,
+        symbols: &mut Vec<Symbol>,
+    ) {
+        match node.kind() {
+            "class" | "module" => {
+                let kind = if node.kind() == "class" {
+                    SymbolKind::Class
+                } else {
+                    SymbolKind::Module
+                };
+                let pushed = node
+                    .child_by_field_name("name")
+                    .map(|name_node| {
+                        let segments = Self::constant_path(name_node, source_bytes);
+                        let pushed = segments.len();
+                        scope.extend(segments);
+                        pushed
+                    })
+                    .unwrap_or(0);
+
+                if pushed > 0 {
+                    symbols.push(Symbol::new(
+                        scope.join("::"),
+                        kind,
+                        Visibility::Public,
+                        (node.start_byte(), node.end_byte()),
+                    ));
+                }
+
+                if let Some(body) = node.child_by_field_name("body") {
+                    Self::collect_body_symbols(body, source_bytes, scope, symbols);
+                }
+
+                for _ in 0..pushed {
+                    scope.pop();
+                }
+            }
+            "singleton_class" => {
+                if let Some(body) = node.child_by_field_name("body") {
+                    Self::collect_body_symbols(body, source_bytes, scope, symbols);
+                }
+            }
+            "method" => {
+                if let Some(name_node) = node.child_by_field_name("name") {
+                    if let Ok(name) = name_node.utf8_text(source_bytes) {
+                        let visibility = if name.starts_with('_') {
+                            Visibility::Private
+                        } else {
+                            Visibility::Public
+                        };
+                        symbols.push(Symbol::new(
+                            name.to_string(),
+                            SymbolKind::Method,
+                            visibility,
+                            (node.start_byte(), node.end_byte()),
+                        ));
                     }
                 }
             }
+            _ => {
+                let mut cursor = node.walk();
+                for child in node.children(&mut cursor) {
+                    Self::collect_symbols(child, source_bytes, scope, symbols);
+                }
+            }
         }
+    }
 
-        exports.sort();
-        exports.dedup();
-        exports
+    /// Walks a `class`/`module`/`class << self` body tracking the current
+    /// `private`/`protected`/`public` state — the default flips after a
+    /// bare `private`/`protected`/`public` call — and emits each `method`
+    /// statement with that visibility. Non-method statements (nested
+    /// classes, DSL calls) recurse back through [`Self::collect_symbols`].
+    fn collect_body_symbols(
+        body: tree_sitter::Node,
+        source_bytes: &[u8],
+        scope: &mut Vec<String>,
+        symbols: &mut Vec<Symbol>,
+    ) {
+        let mut visibility = Visibility::Public;
+        for stmt in Self::body_statements(body) {
+            match stmt.kind() {
+                "identifier" => {
+                    if let Ok(text) = stmt.utf8_text(source_bytes) {
+                        visibility = match text {
+                            "public" => Visibility::Public,
+                            "private" => Visibility::Private,
+                            "protected" => Visibility::Restricted,
+                            _ => visibility,
+                        };
+                    }
+                }
+                "method" => {
+                    if let Some(name_node) = stmt.child_by_field_name("name") {
+                        if let Ok(name) = name_node.utf8_text(source_bytes) {
+                            symbols.push(Symbol::new(
+                                name.to_string(),
+                                SymbolKind::Method,
+                                visibility,
+                                (stmt.start_byte(), stmt.end_byte()),
+                            ));
+                        }
+                    }
+                }
+                _ => Self::collect_symbols(stmt, source_bytes, scope, symbols),
+            }
+        }
+    }
+
+    /// Statements of a `class`/`module` body, unwrapping the
+    /// `body_statement` node tree-sitter wraps multi-statement bodies in.
+    /// A single-statement body isn't wrapped at all, so that statement is
+    /// returned directly.
+    fn body_statements(body: tree_sitter::Node) -> Vec<tree_sitter::Node> {
+        if body.kind() == "body_statement" {
+            let mut cursor = body.walk();
+            body.children(&mut cursor).collect()
+        } else {
+            vec![body]
+        }
+    }
+
+    /// Reader/writer method names synthesized by `attr_accessor`,
+    /// `attr_reader`, and `attr_writer` DSL calls, respecting a preceding
+    /// bare `private`/`protected` statement in the same body (methods
+    /// declared under either are left out of the public surface).
+    fn collect_attr_methods(node: tree_sitter::Node, source_bytes: &[u8]) -> Vec<String> {
+        let mut methods = Vec::new();
+
+        if matches!(node.kind(), "class" | "module") {
+            if let Some(body) = node.child_by_field_name("body") {
+                let mut visibility_is_public = true;
+                for stmt in Self::body_statements(body) {
+                    match stmt.kind() {
+                        "identifier" => {
+                            if let Ok(text) = stmt.utf8_text(source_bytes) {
+                                match text {
+                                    "public" => visibility_is_public = true,
+                                    "private" | "protected" => visibility_is_public = false,
+                                    _ => {}
+                                }
+                            }
+                        }
+                        "call" if visibility_is_public => {
+                            if let Some((name, args)) = Self::dsl_call_parts(stmt, source_bytes) {
+                                let symbols = Self::symbol_args(args, source_bytes);
+                                match name.as_str() {
+                                    "attr_accessor" => {
+                                        for sym in &symbols {
+                                            methods.push(sym.clone());
+                                            methods.push(format!("{sym}="));
+                                        }
+                                    }
+                                    "attr_reader" => methods.extend(symbols),
+                                    "attr_writer" => {
+                                        methods.extend(symbols.iter().map(|s| format!("{s}=")))
+                                    }
+                                    _ => {}
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            methods.extend(Self::collect_attr_methods(child, source_bytes));
+        }
+
+        methods
+    }
+
+    /// Splits a bare `call` node (no receiver) into its method name and
+    /// `argument_list` node, e.g. `attr_accessor :name, :email` becomes
+    /// `("attr_accessor", <argument_list>)`.
+    fn dsl_call_parts<'a>(
+        node: tree_sitter::Node<'a>,
+        source_bytes: &[u8],
+    ) -> Option<(String, tree_sitter::Node<'a>)> {
+        let mut cursor = node.walk();
+        let mut method_name = None;
+        let mut args_node = None;
+        for child in node.children(&mut cursor) {
+            if child.kind() == "identifier" && method_name.is_none() {
+                method_name = child.utf8_text(source_bytes).ok().map(|s| s.to_string());
+            }
+            if child.kind() == "argument_list" {
+                args_node = Some(child);
+            }
+        }
+        Some((method_name?, args_node?))
+    }
+
+    /// The `:symbol` arguments of an `argument_list`, with the leading
+    /// `:` stripped.
+    fn symbol_args(args_node: tree_sitter::Node, source_bytes: &[u8]) -> Vec<String> {
+        let mut cursor = args_node.walk();
+        args_node
+            .named_children(&mut cursor)
+            .filter(|n| n.kind() == "simple_symbol")
+            .filter_map(|n| n.utf8_text(source_bytes).ok())
+            .map(|s| s.trim_start_matches(':').to_string())
+            .collect()
+    }
+
+    /// Rails/ActiveRecord association and validation DSL calls
+    /// (`has_many`, `belongs_to`, `has_one`, `validates`, `scope`) found
+    /// anywhere in a class/module body, as `{kind, name, options}`: `kind`
+    /// is the DSL method name, `name` is its first argument, and `options`
+    /// are the remaining arguments' raw source text.
+    fn extract_associations(&self, source: &str, root_node: tree_sitter::Node) -> Vec<serde_json::Value> {
+        let mut associations = Vec::new();
+        Self::collect_associations(root_node, source.as_bytes(), &mut associations);
+        associations
+    }
+
+    fn collect_associations(
+        node: tree_sitter::Node,
+        source_bytes: &[u8],
+        associations: &mut Vec<serde_json::Value>,
+    ) {
+        if node.kind() == "call" {
+            if let Some((name, args_node)) = Self::dsl_call_parts(node, source_bytes) {
+                if matches!(
+                    name.as_str(),
+                    "has_many" | "belongs_to" | "has_one" | "validates" | "scope"
+                ) {
+                    let mut cursor = args_node.walk();
+                    let named_args: Vec<tree_sitter::Node> =
+                        args_node.named_children(&mut cursor).collect();
+                    let assoc_name = named_args
+                        .first()
+                        .and_then(|n| n.utf8_text(source_bytes).ok())
+                        .map(|s| s.trim_start_matches(':').trim_matches('"').to_string())
+                        .unwrap_or_default();
+                    let options: Vec<serde_json::Value> = named_args
+                        .iter()
+                        .skip(1)
+                        .filter_map(|n| n.utf8_text(source_bytes).ok())
+                        .map(|s| serde_json::Value::String(s.to_string()))
+                        .collect();
+                    associations.push(serde_json::json!({
+                        "kind": name,
+                        "name": assoc_name,
+                        "options": options,
+                    }));
+                }
+            }
+        }
+
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            Self::collect_associations(child, source_bytes, associations);
+        }
     }
 
     fn extract_imports(&self, source: &str, root_node: tree_sitter::Node) -> Vec<String> {
@@ -196,44 +530,369 @@ impl RubyParser {
             self.collect_mixins(child, source_bytes, mixins);
         }
     }
+
+    /// Unified inheritance/mixin edges for every declared class/module:
+    /// `extends` for a `class Foo < Bar` superclass, and `include`/
+    /// `extend`/`prepend` for the matching DSL calls, each attributed to
+    /// its nearest enclosing class/module. This is the same data as
+    /// [`RubyParser::extract_mixins`], just attributed per declaring type
+    /// instead of flattened into one bag.
+    fn extract_type_relations(
+        &self,
+        source: &str,
+        root_node: tree_sitter::Node,
+    ) -> Vec<serde_json::Value> {
+        let mut relations = Vec::new();
+        self.collect_type_relations(root_node, source.as_bytes(), None, &mut relations);
+        relations
+    }
+
+    fn collect_type_relations(
+        &self,
+        node: tree_sitter::Node,
+        source_bytes: &[u8],
+        current_type: Option<&str>,
+        relations: &mut Vec<serde_json::Value>,
+    ) {
+        let mut enclosing_type = current_type.map(|s| s.to_string());
+
+        match node.kind() {
+            "class" | "module" => {
+                if let Some(name_node) = node.child_by_field_name("name") {
+                    if let Ok(name) = name_node.utf8_text(source_bytes) {
+                        enclosing_type = Some(name.to_string());
+
+                        if node.kind() == "class" {
+                            if let Some(superclass) = node.child_by_field_name("superclass") {
+                                if let Some(target_node) =
+                                    superclass.named_child(superclass.named_child_count().saturating_sub(1))
+                                {
+                                    if let Ok(target) = target_node.utf8_text(source_bytes) {
+                                        relations.push(serde_json::json!({
+                                            "type": name,
+                                            "relation": "extends",
+                                            "target": target,
+                                            "generic_args": Vec::<String>::new(),
+                                        }));
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            "call" => {
+                if let Some(type_name) = current_type {
+                    let mut cursor = node.walk();
+                    let mut method_name = None;
+                    for child in node.children(&mut cursor) {
+                        if child.kind() == "identifier" {
+                            if let Ok(text) = child.utf8_text(source_bytes) {
+                                if text == "include" || text == "extend" || text == "prepend" {
+                                    method_name = Some(text.to_string());
+                                }
+                            }
+                        }
+                        if child.kind() == "argument_list" && method_name.is_some() {
+                            let mut arg_cursor = child.walk();
+                            for arg in child.children(&mut arg_cursor) {
+                                if arg.kind() == "constant" || arg.kind() == "scope_resolution" {
+                                    if let Ok(text) = arg.utf8_text(source_bytes) {
+                                        relations.push(serde_json::json!({
+                                            "type": type_name,
+                                            "relation": method_name.clone().unwrap(),
+                                            "target": text,
+                                            "generic_args": Vec::<String>::new(),
+                                        }));
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            self.collect_type_relations(child, source_bytes, enclosing_type.as_deref(), relations);
+        }
+    }
+
+    /// Rails `config/routes.rb` DSL: `get`/`post`/`put`/`patch`/`delete`
+    /// calls map straight to a verb, and `resources`/`resource` expand to
+    /// the conventional RESTful action set for that resource name.
+    fn extract_routes(&self, source: &str, root_node: tree_sitter::Node) -> Vec<serde_json::Value> {
+        let mut routes = Vec::new();
+        self.collect_routes(root_node, source.as_bytes(), &mut routes);
+        routes
+    }
+
+    fn collect_routes(
+        &self,
+        node: tree_sitter::Node,
+        source_bytes: &[u8],
+        routes: &mut Vec<serde_json::Value>,
+    ) {
+        if node.kind() == "call" {
+            if let Some(method_node) = node.child_by_field_name("method") {
+                if let Ok(method) = method_node.utf8_text(source_bytes) {
+                    let args = node.child_by_field_name("arguments");
+                    match method {
+                        "get" | "post" | "put" | "patch" | "delete" => {
+                            if let Some(args) = args {
+                                if let Some(path) = Self::first_route_literal(args, source_bytes) {
+                                    let handler = Self::keyword_arg(args, source_bytes, "to")
+                                        .unwrap_or_else(|| path.trim_start_matches('/').to_string());
+                                    routes.push(serde_json::json!({
+                                        "method": method.to_uppercase(),
+                                        "path": path,
+                                        "handler": handler,
+                                    }));
+                                }
+                            }
+                        }
+                        "resources" | "resource" => {
+                            if let Some(args) = args {
+                                if let Some(name) = Self::first_route_literal(args, source_bytes) {
+                                    routes.extend(Self::resourceful_routes(&name));
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            self.collect_routes(child, source_bytes, routes);
+        }
+    }
+
+    /// First plain string or symbol argument of a DSL call, e.g. the
+    /// `'/users'` in `get '/users', to: 'users#index'` or the `:users` in
+    /// `resources :users`.
+    fn first_route_literal(args_node: tree_sitter::Node, source_bytes: &[u8]) -> Option<String> {
+        let mut cursor = args_node.walk();
+        for arg in args_node.children(&mut cursor) {
+            match arg.kind() {
+                "string" => {
+                    if let Ok(text) = arg.utf8_text(source_bytes) {
+                        return Some(text.trim_matches('\'').trim_matches('"').to_string());
+                    }
+                }
+                "simple_symbol" => {
+                    if let Ok(text) = arg.utf8_text(source_bytes) {
+                        return Some(text.trim_start_matches(':').to_string());
+                    }
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// Value of a `key: value` keyword argument in a DSL call's arguments,
+    /// e.g. `to:` in `get '/users', to: 'users#index'`.
+    fn keyword_arg(args_node: tree_sitter::Node, source_bytes: &[u8], key: &str) -> Option<String> {
+        let mut cursor = args_node.walk();
+        for arg in args_node.children(&mut cursor) {
+            if arg.kind() != "pair" {
+                continue;
+            }
+            let Some(key_node) = arg.child_by_field_name("key") else {
+                continue;
+            };
+            let Ok(key_text) = key_node.utf8_text(source_bytes) else {
+                continue;
+            };
+            if key_text.trim_end_matches(':') != key {
+                continue;
+            }
+            let value_node = arg.child_by_field_name("value")?;
+            let value_text = value_node.utf8_text(source_bytes).ok()?;
+            return Some(value_text.trim_matches('\'').trim_matches('"').to_string());
+        }
+        None
+    }
+
+    /// The conventional RESTful action set Rails' router expands a
+    /// `resources :name` call into.
+    fn resourceful_routes(name: &str) -> Vec<serde_json::Value> {
+        let controller = format!("{}#", name);
+        vec![
+            ("GET", format!("/{}", name), format!("{}index", controller)),
+            (
+                "GET",
+                format!("/{}/:id", name),
+                format!("{}show", controller),
+            ),
+            ("POST", format!("/{}", name), format!("{}create", controller)),
+            (
+                "PATCH",
+                format!("/{}/:id", name),
+                format!("{}update", controller),
+            ),
+            (
+                "DELETE",
+                format!("/{}/:id", name),
+                format!("{}destroy", controller),
+            ),
+        ]
+        .into_iter()
+        .map(|(method, path, handler)| {
+            serde_json::json!({ "method": method, "path": path, "handler": handler })
+        })
+        .collect()
+    }
 }
 
-impl Parser for RubyParser {
-    fn parse(&mut self, source: &str) -> Result<ParseResult> {
-        let tree = self
-            .parser
-            .parse(source, None)
-            .ok_or_else(|| anyhow::anyhow!("Failed to parse Ruby source"))?;
+impl RubyParser {
+    /// Builds the full [`ParseResult`] from an already-parsed tree, shared
+    /// by [`Parser::parse`] (fresh tree every call) and
+    /// [`Parser::parse_edit`] (reused/incrementally-edited tree).
+    fn build_result(&self, source: &str, tree: &Tree) -> ParseResult {
+        #[cfg(feature = "tracing")]
+        let started_at = std::time::Instant::now();
 
         let root_node = tree.root_node();
-        let exports = self.extract_exports(source, root_node);
-        let imports = self.extract_imports(source, root_node);
-        let dependencies = self.extract_dependencies(source, root_node);
+        let source_bytes = source.as_bytes();
+        let mut exports = self.extract_exports(source, root_node);
+        let mut imports = self.extract_imports(source, root_node);
+        let mut dependencies = self.extract_dependencies(source, root_node);
+
+        // A configured `fmm.queries.toml` exports/imports/dependencies query
+        // extends these rather than replacing them.
+        for name in self.query_pack.export_names(root_node, source_bytes) {
+            if !exports.contains(&name) {
+                exports.push(name);
+            }
+        }
+        exports.sort();
+
+        for name in self.query_pack.imports(root_node, source_bytes) {
+            if !imports.contains(&name) {
+                imports.push(name);
+            }
+        }
+        imports.sort();
+
+        for dep in self.query_pack.dependencies(root_node, source_bytes) {
+            if !dependencies.contains(&dep) {
+                dependencies.push(dep);
+            }
+        }
+        dependencies.sort();
+
         let loc = source.lines().count();
 
+        let symbols = {
+            let mut symbols = Vec::new();
+            let mut scope = Vec::new();
+            Self::collect_symbols(root_node, source_bytes, &mut scope, &mut symbols);
+            symbols
+        };
+
+        #[cfg(feature = "tracing")]
+        super::query_helpers::trace::record_parse_complete(
+            started_at.elapsed(),
+            exports.len(),
+            imports.len(),
+            dependencies.len(),
+            super::query_helpers::trace::count_unmatched_nodes(root_node),
+        );
+
         let mixins = self.extract_mixins(source, root_node);
-        let custom_fields = if mixins.is_empty() {
+        let routes = self.extract_routes(source, root_node);
+        let type_relations = self.extract_type_relations(source, root_node);
+        let associations = self.extract_associations(source, root_node);
+        let pack_fields = self.query_pack.run(root_node, source_bytes);
+        let custom_fields = if mixins.is_empty()
+            && routes.is_empty()
+            && type_relations.is_empty()
+            && associations.is_empty()
+            && pack_fields.is_empty()
+        {
             None
         } else {
             let mut fields = HashMap::new();
-            fields.insert(
-                "mixins".to_string(),
-                serde_json::Value::Array(
-                    mixins.into_iter().map(serde_json::Value::String).collect(),
-                ),
-            );
+            if !mixins.is_empty() {
+                fields.insert(
+                    "mixins".to_string(),
+                    serde_json::Value::Array(
+                        mixins.into_iter().map(serde_json::Value::String).collect(),
+                    ),
+                );
+            }
+            if !routes.is_empty() {
+                fields.insert("routes".to_string(), serde_json::Value::Array(routes));
+            }
+            if !type_relations.is_empty() {
+                fields.insert(
+                    "type_relations".to_string(),
+                    serde_json::Value::Array(type_relations),
+                );
+            }
+            if !associations.is_empty() {
+                fields.insert(
+                    "associations".to_string(),
+                    serde_json::Value::Array(associations),
+                );
+            }
+            fields.extend(pack_fields);
             Some(fields)
         };
 
-        Ok(ParseResult {
+        ParseResult {
             metadata: Metadata {
                 exports,
                 imports,
                 dependencies,
                 loc,
+                re_exports: Vec::new(),
+                test_loc: 0,
+                symbols,
+                diagnostics: crate::parser::collect_diagnostics(root_node),
             },
             custom_fields,
-        })
+        }
+    }
+}
+
+impl Parser for RubyParser {
+    fn parse(&mut self, source: &str) -> Result<ParseResult> {
+        let tree = self
+            .parser
+            .parse(source, None)
+            .ok_or_else(|| anyhow::anyhow!("Failed to parse Ruby source"))?;
+
+        Ok(self.build_result(source, &tree))
+    }
+
+    fn parse_edit(
+        &mut self,
+        path: &Path,
+        new_source: &str,
+        edits: &[InputEdit],
+    ) -> Result<ParseResult> {
+        let mut old_tree = self.trees.remove(path);
+        if let Some(tree) = old_tree.as_mut() {
+            for edit in edits {
+                tree.edit(edit);
+            }
+        }
+
+        let tree = self
+            .parser
+            .parse(new_source, old_tree.as_ref())
+            .ok_or_else(|| anyhow::anyhow!("Failed to parse Ruby source"))?;
+
+        let result = self.build_result(new_source, &tree);
+        self.trees.insert(path.to_path_buf(), tree);
+        Ok(result)
     }
 
     fn language_id(&self) -> &'static str {
@@ -291,6 +950,176 @@ end
         assert!(result.metadata.exports.contains(&"Cacheable".to_string()));
     }
 
+    #[test]
+    fn parse_ruby_nested_classes_are_qualified() {
+        let mut parser = RubyParser::new().unwrap();
+        let source = r#"
+module Api
+  class RequestHandler
+    def call
+    end
+  end
+end
+"#;
+        let result = parser.parse(source).unwrap();
+        assert!(result.metadata.exports.contains(&"Api".to_string()));
+        assert!(result
+            .metadata
+            .exports
+            .contains(&"Api::RequestHandler".to_string()));
+        assert!(!result
+            .metadata
+            .exports
+            .contains(&"RequestHandler".to_string()));
+    }
+
+    #[test]
+    fn parse_ruby_compact_class_path_is_qualified() {
+        let mut parser = RubyParser::new().unwrap();
+        let source = r#"
+class Api::RequestHandler
+  def call
+  end
+end
+"#;
+        let result = parser.parse(source).unwrap();
+        assert!(result
+            .metadata
+            .exports
+            .contains(&"Api::RequestHandler".to_string()));
+    }
+
+    #[test]
+    fn parse_ruby_reopened_class_is_deduped() {
+        let mut parser = RubyParser::new().unwrap();
+        let source = r#"
+class User
+  def name
+  end
+end
+
+class User
+  def email
+  end
+end
+"#;
+        let result = parser.parse(source).unwrap();
+        let count = result
+            .metadata
+            .exports
+            .iter()
+            .filter(|e| *e == "User")
+            .count();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn parse_ruby_singleton_class_does_not_double_qualify() {
+        let mut parser = RubyParser::new().unwrap();
+        let source = r#"
+class User
+  class << self
+    def find_by_id(id)
+    end
+  end
+end
+"#;
+        let result = parser.parse(source).unwrap();
+        assert!(result.metadata.exports.contains(&"User".to_string()));
+        assert!(!result
+            .metadata
+            .exports
+            .iter()
+            .any(|e| e.contains("User::User") || e.contains("self")));
+    }
+
+    #[test]
+    fn symbol_table_tracks_class_and_methods() {
+        let mut parser = RubyParser::new().unwrap();
+        let source = r#"
+class User
+  def name
+  end
+end
+"#;
+        let result = parser.parse(source).unwrap();
+        let class_symbol = result
+            .metadata
+            .symbols
+            .iter()
+            .find(|s| s.name == "User")
+            .unwrap();
+        assert_eq!(class_symbol.kind, SymbolKind::Class);
+        assert_eq!(class_symbol.visibility, Visibility::Public);
+
+        let method_symbol = result
+            .metadata
+            .symbols
+            .iter()
+            .find(|s| s.name == "name")
+            .unwrap();
+        assert_eq!(method_symbol.kind, SymbolKind::Method);
+        assert_eq!(method_symbol.visibility, Visibility::Public);
+    }
+
+    #[test]
+    fn symbol_table_tracks_private_and_protected_methods() {
+        let mut parser = RubyParser::new().unwrap();
+        let source = r#"
+class Account
+  def balance
+  end
+
+  private
+
+  def transfer
+  end
+
+  protected
+
+  def audit_log
+  end
+end
+"#;
+        let result = parser.parse(source).unwrap();
+        let kind_of = |name: &str| {
+            result
+                .metadata
+                .symbols
+                .iter()
+                .find(|s| s.name == name)
+                .map(|s| s.visibility)
+                .unwrap()
+        };
+        assert_eq!(kind_of("balance"), Visibility::Public);
+        assert_eq!(kind_of("transfer"), Visibility::Private);
+        assert_eq!(kind_of("audit_log"), Visibility::Restricted);
+    }
+
+    #[test]
+    fn symbol_table_qualifies_nested_module() {
+        let mut parser = RubyParser::new().unwrap();
+        let source = r#"
+module Api
+  class RequestHandler
+    def call
+    end
+  end
+end
+"#;
+        let result = parser.parse(source).unwrap();
+        assert!(result
+            .metadata
+            .symbols
+            .iter()
+            .any(|s| s.name == "Api" && s.kind == SymbolKind::Module));
+        assert!(result
+            .metadata
+            .symbols
+            .iter()
+            .any(|s| s.name == "Api::RequestHandler" && s.kind == SymbolKind::Class));
+    }
+
     #[test]
     fn parse_ruby_top_level_methods() {
         let mut parser = RubyParser::new().unwrap();
@@ -352,6 +1181,40 @@ end
         assert!(names.contains(&"Validatable"));
     }
 
+    #[test]
+    fn parse_ruby_rails_dsl_routes() {
+        let mut parser = RubyParser::new().unwrap();
+        let source = r#"
+Rails.application.routes.draw do
+  get '/health', to: 'health#check'
+  resources :users
+end
+"#;
+        let result = parser.parse(source).unwrap();
+        let fields = result.custom_fields.unwrap();
+        let routes = fields.get("routes").unwrap().as_array().unwrap();
+
+        let health = routes
+            .iter()
+            .find(|r| r["path"] == "/health")
+            .unwrap();
+        assert_eq!(health["method"], "GET");
+        assert_eq!(health["handler"], "health#check");
+
+        let index = routes
+            .iter()
+            .find(|r| r["handler"] == "users#index")
+            .unwrap();
+        assert_eq!(index["method"], "GET");
+        assert_eq!(index["path"], "/users");
+
+        let create = routes
+            .iter()
+            .find(|r| r["handler"] == "users#create")
+            .unwrap();
+        assert_eq!(create["method"], "POST");
+    }
+
     #[test]
     fn parse_ruby_empty() {
         let mut parser = RubyParser::new().unwrap();
@@ -359,4 +1222,24 @@ end
         assert!(result.metadata.exports.is_empty());
         assert!(result.metadata.imports.is_empty());
     }
+
+    #[test]
+    fn parse_edit_reuses_tree_and_picks_up_new_export() {
+        use crate::parser::cache::derive_edit;
+        use std::path::Path;
+
+        let mut parser = RubyParser::new().unwrap();
+        let path = Path::new("user.rb");
+
+        let old_source = "class User\nend\n";
+        let new_source = "class User\nend\n\nclass Account\nend\n";
+        let edit = derive_edit(old_source, new_source).unwrap();
+
+        let first = parser.parse_edit(path, old_source, &[]).unwrap();
+        assert!(first.metadata.exports.contains(&"User".to_string()));
+
+        let second = parser.parse_edit(path, new_source, &[edit]).unwrap();
+        assert!(second.metadata.exports.contains(&"User".to_string()));
+        assert!(second.metadata.exports.contains(&"Account".to_string()));
+    }
 }
@@ -1,10 +1,23 @@
 use super::query_helpers::top_level_ancestor;
-use crate::parser::{ExportEntry, Metadata, ParseResult, Parser};
+use crate::parser::{
+    ExportEntry, Metadata, ParseOptions, ParseResult, Parser, ReExportEntry, Visibility,
+};
 use anyhow::Result;
 use std::collections::{HashMap, HashSet};
 use streaming_iterator::StreamingIterator;
 use tree_sitter::{Language, Parser as TSParser, Query, QueryCursor};
 
+/// Parsed form of a `#[cfg(...)]` predicate, evaluated by
+/// `RustParser::cfg_predicate_holds` against `ParseOptions.active_cfg`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CfgExpr {
+    /// A bare option (`unix`) or `key=value` pair (`target_os=linux`).
+    Atom(String),
+    All(Vec<CfgExpr>),
+    Any(Vec<CfgExpr>),
+    Not(Box<CfgExpr>),
+}
+
 pub struct RustParser {
     parser: TSParser,
     export_queries: Vec<Query>,
@@ -13,6 +26,8 @@ pub struct RustParser {
     lifetime_query: Query,
     async_query: Query,
     derive_query: Query,
+    macro_def_query: Query,
+    macro_invocation_query: Query,
 }
 
 impl RustParser {
@@ -23,15 +38,18 @@ impl RustParser {
             .set_language(&language)
             .map_err(|e| anyhow::anyhow!("Failed to set Rust language: {}", e))?;
 
+        // The visibility modifier is optional in each query so items with no
+        // modifier at all (private by default) are still captured instead of
+        // silently dropped — see `classify_visibility`.
         let export_query_strs = [
-            "(function_item (visibility_modifier) @vis name: (identifier) @name)",
-            "(struct_item (visibility_modifier) @vis name: (type_identifier) @name)",
-            "(enum_item (visibility_modifier) @vis name: (type_identifier) @name)",
-            "(trait_item (visibility_modifier) @vis name: (type_identifier) @name)",
-            "(type_item (visibility_modifier) @vis name: (type_identifier) @name)",
-            "(const_item (visibility_modifier) @vis name: (identifier) @name)",
-            "(static_item (visibility_modifier) @vis name: (identifier) @name)",
-            "(mod_item (visibility_modifier) @vis name: (identifier) @name)",
+            "(function_item (visibility_modifier)? @vis name: (identifier) @name)",
+            "(struct_item (visibility_modifier)? @vis name: (type_identifier) @name)",
+            "(enum_item (visibility_modifier)? @vis name: (type_identifier) @name)",
+            "(trait_item (visibility_modifier)? @vis name: (type_identifier) @name)",
+            "(type_item (visibility_modifier)? @vis name: (type_identifier) @name)",
+            "(const_item (visibility_modifier)? @vis name: (identifier) @name)",
+            "(static_item (visibility_modifier)? @vis name: (identifier) @name)",
+            "(mod_item (visibility_modifier)? @vis name: (identifier) @name)",
         ];
 
         let export_queries: Vec<Query> = export_query_strs
@@ -68,6 +86,13 @@ impl RustParser {
         )
         .map_err(|e| anyhow::anyhow!("Failed to compile derive query: {}", e))?;
 
+        let macro_def_query = Query::new(&language, "(macro_definition name: (identifier) @name)")
+            .map_err(|e| anyhow::anyhow!("Failed to compile macro_definition query: {}", e))?;
+
+        let macro_invocation_query =
+            Query::new(&language, "(macro_invocation macro: (identifier) @name)")
+                .map_err(|e| anyhow::anyhow!("Failed to compile macro_invocation query: {}", e))?;
+
         Ok(Self {
             parser,
             export_queries,
@@ -76,9 +101,352 @@ impl RustParser {
             lifetime_query,
             async_query,
             derive_query,
+            macro_def_query,
+            macro_invocation_query,
         })
     }
 
+    /// Names of every `macro_rules!` definition in the file.
+    fn extract_macro_definitions(&self, source: &str, root_node: tree_sitter::Node) -> Vec<String> {
+        super::query_helpers::collect_named_matches(
+            &self.macro_def_query,
+            "name",
+            root_node,
+            source.as_bytes(),
+        )
+    }
+
+    /// `#[macro_export] macro_rules! foo { ... }` — a declarative macro
+    /// marked exported joins the file's public API surface just like `pub
+    /// fn`/`pub struct`, rather than vanishing because `macro_rules!` has
+    /// no `pub` keyword of its own.
+    fn extract_exported_macros(
+        &self,
+        source_bytes: &[u8],
+        root_node: tree_sitter::Node,
+    ) -> Vec<ExportEntry> {
+        let mut exports = Vec::new();
+        let mut cursor = root_node.walk();
+        for child in root_node.children(&mut cursor) {
+            if child.kind() != "macro_definition" {
+                continue;
+            }
+            if !Self::preceding_has_attribute(child, source_bytes, "macro_export") {
+                continue;
+            }
+            let Some(name_node) = child.child_by_field_name("name") else {
+                continue;
+            };
+            let Ok(name) = name_node.utf8_text(source_bytes) else {
+                continue;
+            };
+            let doc = Self::preceding_doc(child, source_bytes);
+            exports.push(
+                ExportEntry::new(
+                    name.to_string(),
+                    child.start_position().row + 1,
+                    child.end_position().row + 1,
+                )
+                .with_doc(doc),
+            );
+        }
+        exports
+    }
+
+    /// Every macro invoked (`name!(...)`) grouped by name with its
+    /// invocation count, so a module built mostly out of e.g.
+    /// `lazy_static!`/`json!` calls shows up as more than an empty shell.
+    fn extract_macro_invocations(
+        &self,
+        source: &str,
+        root_node: tree_sitter::Node,
+    ) -> Vec<(String, usize)> {
+        let source_bytes = source.as_bytes();
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        let capture_names = self.macro_invocation_query.capture_names();
+        let mut cursor = QueryCursor::new();
+        let mut iter = cursor.matches(&self.macro_invocation_query, root_node, source_bytes);
+        while let Some(m) = iter.next() {
+            for capture in m.captures {
+                let idx = capture.index as usize;
+                if idx < capture_names.len() && capture_names[idx] == "name" {
+                    if let Ok(name) = capture.node.utf8_text(source_bytes) {
+                        *counts.entry(name.to_string()).or_insert(0) += 1;
+                    }
+                }
+            }
+        }
+        let mut counts: Vec<(String, usize)> = counts.into_iter().collect();
+        counts.sort_by(|a, b| a.0.cmp(&b.0));
+        counts
+    }
+
+    /// Whether an `ExportEntry`'s `#[cfg(...)]` gate evaluates true under
+    /// `cfg(test)` — i.e. the item only exists inside `#[cfg(test)] mod
+    /// tests { ... }` (which `preceding_cfg` already attaches to nested
+    /// items via their mod's own attribute) or is itself directly
+    /// `#[cfg(test)]`-gated. Such items are test-only and shouldn't count
+    /// as part of the file's production API surface.
+    fn is_cfg_test(cfg: &str) -> bool {
+        Self::cfg_predicate_holds(cfg, &["test".to_string()])
+    }
+
+    /// Names of every function treated as test code: those directly
+    /// annotated `#[test]`, or nested anywhere inside a `#[cfg(test)]`
+    /// module (the `mod tests { #[test] fn ... }` idiom).
+    fn extract_test_functions(
+        &self,
+        source_bytes: &[u8],
+        root_node: tree_sitter::Node,
+    ) -> Vec<String> {
+        let mut names = Vec::new();
+        Self::collect_test_functions(root_node, source_bytes, false, &mut names);
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    fn collect_test_functions(
+        node: tree_sitter::Node,
+        source_bytes: &[u8],
+        in_test_module: bool,
+        out: &mut Vec<String>,
+    ) {
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            let child_in_test = match child.kind() {
+                "function_item" => {
+                    let is_test =
+                        in_test_module || Self::preceding_has_attribute(child, source_bytes, "test");
+                    if is_test {
+                        if let Some(name) = child
+                            .child_by_field_name("name")
+                            .and_then(|n| n.utf8_text(source_bytes).ok())
+                        {
+                            out.push(name.to_string());
+                        }
+                    }
+                    is_test
+                }
+                "mod_item" => {
+                    in_test_module
+                        || Self::preceding_cfg(child, source_bytes)
+                            .as_deref()
+                            .is_some_and(Self::is_cfg_test)
+                }
+                _ => in_test_module,
+            };
+            Self::collect_test_functions(child, source_bytes, child_in_test, out);
+        }
+    }
+
+    /// Lines of code belonging to test code: the full span of every
+    /// top-level `#[cfg(test)]` module plus every top-level standalone
+    /// `#[test]` function, which already covers any test function nested
+    /// inside a test module without double-counting.
+    fn test_loc(&self, source_bytes: &[u8], root_node: tree_sitter::Node) -> usize {
+        let mut total = 0;
+        let mut cursor = root_node.walk();
+        for child in root_node.children(&mut cursor) {
+            let is_test_block = match child.kind() {
+                "mod_item" => Self::preceding_cfg(child, source_bytes)
+                    .as_deref()
+                    .is_some_and(Self::is_cfg_test),
+                "function_item" => Self::preceding_has_attribute(child, source_bytes, "test"),
+                _ => false,
+            };
+            if is_test_block {
+                total += child.end_position().row - child.start_position().row + 1;
+            }
+        }
+        total
+    }
+
+    /// Classify a `visibility_modifier`'s text (or its absence) into a tier.
+    /// `pub` is fully public; `pub(crate)` is crate-visible; any other
+    /// `pub(...)` restriction (`pub(super)`, `pub(in ...)`) is `Restricted`;
+    /// no modifier at all means the item is private to its module.
+    fn classify_visibility(vis_text: Option<&str>) -> Visibility {
+        match vis_text {
+            None => Visibility::Private,
+            Some("pub") => Visibility::Public,
+            Some(text) if text.starts_with("pub(crate)") => Visibility::Crate,
+            Some(_) => Visibility::Restricted,
+        }
+    }
+
+    /// Look at the sibling immediately before `decl` (walking up past any
+    /// other attribute items) for a `#[cfg(...)]`, returning its inner
+    /// expression text if found. Items are typically preceded by doc
+    /// comments and attributes as separate siblings under the same parent,
+    /// so this only needs to scan backwards one level.
+    fn preceding_cfg<'a>(decl: tree_sitter::Node<'a>, source_bytes: &'a [u8]) -> Option<String> {
+        let mut sibling = decl.prev_sibling();
+        while let Some(node) = sibling {
+            if node.kind() != "attribute_item" {
+                break;
+            }
+            if let Some(cfg) = Self::cfg_expr_of(node, source_bytes) {
+                return Some(cfg);
+            }
+            sibling = node.prev_sibling();
+        }
+        None
+    }
+
+    fn cfg_expr_of(attribute_item: tree_sitter::Node, source_bytes: &[u8]) -> Option<String> {
+        let mut cursor = attribute_item.walk();
+        for child in attribute_item.children(&mut cursor) {
+            if child.kind() != "attribute" {
+                continue;
+            }
+            let mut inner = child.walk();
+            let mut is_cfg = false;
+            let mut args_text = None;
+            for grand in child.children(&mut inner) {
+                match grand.kind() {
+                    "identifier" => {
+                        if grand.utf8_text(source_bytes) == Ok("cfg") {
+                            is_cfg = true;
+                        }
+                    }
+                    "token_tree" => {
+                        args_text = grand.utf8_text(source_bytes).ok();
+                    }
+                    _ => {}
+                }
+            }
+            if is_cfg {
+                return args_text
+                    .map(|t| t.trim_start_matches('(').trim_end_matches(')').to_string());
+            }
+        }
+        None
+    }
+
+    /// Whether any `#[attr]` immediately preceding `decl` is named `attr_name`
+    /// (e.g. `#[macro_use]` ahead of an `extern crate`).
+    fn preceding_has_attribute(
+        decl: tree_sitter::Node,
+        source_bytes: &[u8],
+        attr_name: &str,
+    ) -> bool {
+        let mut sibling = decl.prev_sibling();
+        while let Some(node) = sibling {
+            if node.kind() != "attribute_item" {
+                break;
+            }
+            if Self::attribute_item_name(node, source_bytes).as_deref() == Some(attr_name) {
+                return true;
+            }
+            sibling = node.prev_sibling();
+        }
+        false
+    }
+
+    /// The attribute's name, e.g. `macro_use` for `#[macro_use]` or `cfg`
+    /// for `#[cfg(test)]` — ignores any arguments.
+    fn attribute_item_name(
+        attribute_item: tree_sitter::Node,
+        source_bytes: &[u8],
+    ) -> Option<String> {
+        let mut cursor = attribute_item.walk();
+        for child in attribute_item.children(&mut cursor) {
+            if child.kind() != "attribute" {
+                continue;
+            }
+            let mut inner = child.walk();
+            for grand in child.children(&mut inner) {
+                if grand.kind() == "identifier" {
+                    return grand.utf8_text(source_bytes).ok().map(|s| s.to_string());
+                }
+            }
+        }
+        None
+    }
+
+    /// Collect contiguous `///` line doc comments preceding `decl`
+    /// (skipping over any `#[attr]` items in between, e.g. `#[cfg(...)]`
+    /// ahead of the doc comment), in source order, with the `///` marker
+    /// and one leading space stripped.
+    fn preceding_doc(decl: tree_sitter::Node, source_bytes: &[u8]) -> Option<String> {
+        let mut lines = Vec::new();
+        let mut sibling = decl.prev_sibling();
+        while let Some(node) = sibling {
+            match node.kind() {
+                "attribute_item" => {
+                    sibling = node.prev_sibling();
+                    continue;
+                }
+                "line_comment" => {
+                    let Ok(text) = node.utf8_text(source_bytes) else {
+                        break;
+                    };
+                    if !text.starts_with("///") {
+                        break;
+                    }
+                    lines.push(text.trim_start_matches("///").trim_start().to_string());
+                    sibling = node.prev_sibling();
+                }
+                _ => break,
+            }
+        }
+        if lines.is_empty() {
+            return None;
+        }
+        lines.reverse();
+        Some(lines.join("\n"))
+    }
+
+    /// `extract_exports`'s query order, used to tag each match with its
+    /// item kind for the `visibility` custom-field inventory below.
+    const EXPORT_QUERY_KINDS: &'static [&'static str] = &[
+        "fn", "struct", "enum", "trait", "type", "const", "static", "mod",
+    ];
+
+    /// Machine-readable public-API inventory: `{ name, kind, visibility }`
+    /// for every top-level and nested fn/struct/enum/trait/type/const/
+    /// static/mod item, from a single parse pass over the same queries
+    /// used for `exports`.
+    fn extract_visibility_inventory(
+        &self,
+        source: &str,
+        root_node: tree_sitter::Node,
+    ) -> Vec<serde_json::Value> {
+        let source_bytes = source.as_bytes();
+        let mut inventory = Vec::new();
+
+        for (query, kind) in self.export_queries.iter().zip(Self::EXPORT_QUERY_KINDS) {
+            let capture_names = query.capture_names();
+            let mut cursor = QueryCursor::new();
+            let mut iter = cursor.matches(query, root_node, source_bytes);
+            while let Some(m) = iter.next() {
+                let vis_capture = m.captures.iter().find(|c| {
+                    let idx = c.index as usize;
+                    idx < capture_names.len() && capture_names[idx] == "vis"
+                });
+                let name_capture = m.captures.iter().find(|c| {
+                    let idx = c.index as usize;
+                    idx < capture_names.len() && capture_names[idx] == "name"
+                });
+                if let Some(name) = name_capture {
+                    if let Ok(name_text) = name.node.utf8_text(source_bytes) {
+                        let vis_text =
+                            vis_capture.and_then(|v| v.node.utf8_text(source_bytes).ok());
+                        let visibility = Self::classify_visibility(vis_text);
+                        inventory.push(serde_json::json!({
+                            "name": name_text,
+                            "kind": kind,
+                            "visibility": format!("{:?}", visibility).to_lowercase(),
+                        }));
+                    }
+                }
+            }
+        }
+
+        inventory
+    }
+
     fn extract_exports(&self, source: &str, root_node: tree_sitter::Node) -> Vec<ExportEntry> {
         let mut seen = HashSet::new();
         let mut exports = Vec::new();
@@ -98,21 +466,25 @@ impl RustParser {
                     idx < capture_names.len() && capture_names[idx] == "name"
                 });
 
-                if let (Some(vis), Some(name)) = (vis_capture, name_capture) {
-                    if let Ok(vis_text) = vis.node.utf8_text(source_bytes) {
-                        if vis_text != "pub" {
-                            continue;
-                        }
-                    }
+                if let Some(name) = name_capture {
+                    let vis_text = vis_capture.and_then(|v| v.node.utf8_text(source_bytes).ok());
+                    let visibility = Self::classify_visibility(vis_text);
                     if let Ok(text) = name.node.utf8_text(source_bytes) {
                         let name_str = text.to_string();
                         if seen.insert(name_str.clone()) {
                             let decl = top_level_ancestor(name.node);
-                            exports.push(ExportEntry::new(
-                                name_str,
-                                decl.start_position().row + 1,
-                                decl.end_position().row + 1,
-                            ));
+                            let cfg = Self::preceding_cfg(decl, source_bytes);
+                            let doc = Self::preceding_doc(decl, source_bytes);
+                            exports.push(
+                                ExportEntry::with_visibility(
+                                    name_str,
+                                    decl.start_position().row + 1,
+                                    decl.end_position().row + 1,
+                                    visibility,
+                                )
+                                .with_cfg(cfg)
+                                .with_doc(doc),
+                            );
                         }
                     }
                 }
@@ -222,6 +594,628 @@ impl RustParser {
         None
     }
 
+    /// Expand every `use` declaration into its full, flattened symbol
+    /// paths — `use a::b::{c, d as e};` becomes `["a::b::c", "a::b::d"]`
+    /// — instead of stopping at the outermost root segment. This powers
+    /// the intra-crate module dependency graph: paths rooted at `crate`,
+    /// `self`, or `super` are edges to other modules in this crate.
+    fn extract_full_use_paths(
+        &self,
+        source_bytes: &[u8],
+        root_node: tree_sitter::Node,
+    ) -> Vec<String> {
+        let mut paths = Vec::new();
+        let mut cursor = root_node.walk();
+        for child in root_node.children(&mut cursor) {
+            if child.kind() == "use_declaration" {
+                let mut inner = child.walk();
+                for grand in child.children(&mut inner) {
+                    if matches!(
+                        grand.kind(),
+                        "scoped_identifier" | "scoped_use_list" | "identifier" | "use_list"
+                    ) {
+                        Self::flatten_use_tree(source_bytes, grand, String::new(), &mut paths);
+                    }
+                }
+            }
+        }
+        paths.sort();
+        paths.dedup();
+        paths
+    }
+
+    /// Recursively expand a `use` tree fragment, accumulating the path
+    /// prefix seen so far and emitting one entry per leaf.
+    fn flatten_use_tree(
+        source_bytes: &[u8],
+        node: tree_sitter::Node,
+        prefix: String,
+        out: &mut Vec<String>,
+    ) {
+        match node.kind() {
+            "scoped_identifier" => {
+                let mut cursor = node.walk();
+                let mut path_prefix = prefix;
+                for child in node.children(&mut cursor) {
+                    match child.kind() {
+                        "identifier" | "crate" | "super" | "self" => {
+                            if let Ok(text) = child.utf8_text(source_bytes) {
+                                path_prefix = if path_prefix.is_empty() {
+                                    text.to_string()
+                                } else {
+                                    format!("{path_prefix}::{text}")
+                                };
+                            }
+                        }
+                        "scoped_identifier" => {
+                            Self::flatten_use_tree(source_bytes, child, path_prefix.clone(), out);
+                            return;
+                        }
+                        _ => {}
+                    }
+                }
+                out.push(path_prefix);
+            }
+            "scoped_use_list" => {
+                let mut cursor = node.walk();
+                let mut path_prefix = prefix;
+                for child in node.children(&mut cursor) {
+                    match child.kind() {
+                        "identifier" | "crate" | "super" | "self" | "scoped_identifier" => {
+                            if child.kind() == "scoped_identifier" {
+                                // Leading path before the final `::{...}`.
+                                if let Ok(text) = child.utf8_text(source_bytes) {
+                                    path_prefix = if path_prefix.is_empty() {
+                                        text.to_string()
+                                    } else {
+                                        format!("{path_prefix}::{text}")
+                                    };
+                                }
+                            } else if let Ok(text) = child.utf8_text(source_bytes) {
+                                path_prefix = if path_prefix.is_empty() {
+                                    text.to_string()
+                                } else {
+                                    format!("{path_prefix}::{text}")
+                                };
+                            }
+                        }
+                        "use_list" => {
+                            Self::flatten_use_tree(source_bytes, child, path_prefix.clone(), out);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            "use_list" => {
+                let mut cursor = node.walk();
+                for child in node.children(&mut cursor) {
+                    match child.kind() {
+                        "identifier" | "scoped_identifier" | "scoped_use_list" | "use_list" => {
+                            Self::flatten_use_tree(source_bytes, child, prefix.clone(), out);
+                        }
+                        "use_as_clause" => {
+                            // `name as alias` — only the original name matters
+                            // for the dependency graph.
+                            if let Some(name) = child.child(0) {
+                                Self::flatten_use_tree(source_bytes, name, prefix.clone(), out);
+                            }
+                        }
+                        "self" => {
+                            out.push(prefix.clone());
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            "identifier" | "self" | "super" | "crate" => {
+                if let Ok(text) = node.utf8_text(source_bytes) {
+                    out.push(if prefix.is_empty() {
+                        text.to_string()
+                    } else {
+                        format!("{prefix}::{text}")
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// `pub use path::Name;` / `pub use path::Name as Alias;` / `pub use
+    /// path::{Name, Other as Alias};` — re-export edges back to the module
+    /// each forwarded symbol is actually defined in, the Rust counterpart
+    /// of a TypeScript barrel file.
+    fn extract_re_exports(&self, source_bytes: &[u8], root_node: tree_sitter::Node) -> Vec<ReExportEntry> {
+        let mut re_exports = Vec::new();
+        let mut cursor = root_node.walk();
+        for child in root_node.children(&mut cursor) {
+            if child.kind() != "use_declaration" {
+                continue;
+            }
+            if !Self::has_pub_visibility(child, source_bytes) {
+                continue;
+            }
+            let mut inner = child.walk();
+            for argument in child.children(&mut inner) {
+                if matches!(
+                    argument.kind(),
+                    "scoped_identifier"
+                        | "scoped_use_list"
+                        | "identifier"
+                        | "use_as_clause"
+                        | "use_list"
+                        | "self"
+                ) {
+                    Self::collect_use_leaves_with_alias(
+                        source_bytes,
+                        argument,
+                        String::new(),
+                        &mut re_exports,
+                    );
+                }
+            }
+        }
+        re_exports.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+        re_exports
+    }
+
+    /// Does `node` (a `use_declaration`) carry a leading `pub`?
+    fn has_pub_visibility(node: tree_sitter::Node, source_bytes: &[u8]) -> bool {
+        let mut cursor = node.walk();
+        node.children(&mut cursor).any(|c| {
+            c.kind() == "visibility_modifier"
+                && c.utf8_text(source_bytes).ok() == Some("pub")
+        })
+    }
+
+    /// Recursively expand a `use` tree fragment into `ReExportEntry`
+    /// leaves, mirroring `flatten_use_tree` but keeping track of the
+    /// `as alias` on each leaf instead of discarding it.
+    fn collect_use_leaves_with_alias(
+        source_bytes: &[u8],
+        node: tree_sitter::Node,
+        prefix: String,
+        out: &mut Vec<ReExportEntry>,
+    ) {
+        match node.kind() {
+            "scoped_identifier" => {
+                if let Ok(text) = node.utf8_text(source_bytes) {
+                    let full = if prefix.is_empty() {
+                        text.to_string()
+                    } else {
+                        format!("{prefix}::{text}")
+                    };
+                    Self::push_use_leaf(full, None, out);
+                }
+            }
+            "scoped_use_list" => {
+                let path_prefix = node
+                    .child_by_field_name("path")
+                    .and_then(|p| p.utf8_text(source_bytes).ok())
+                    .map(|p| {
+                        if prefix.is_empty() {
+                            p.to_string()
+                        } else {
+                            format!("{prefix}::{p}")
+                        }
+                    })
+                    .unwrap_or(prefix);
+                if let Some(list) = node.child_by_field_name("list") {
+                    Self::collect_use_leaves_with_alias(source_bytes, list, path_prefix, out);
+                }
+            }
+            "use_list" => {
+                let mut cursor = node.walk();
+                for child in node.children(&mut cursor) {
+                    match child.kind() {
+                        "identifier" | "scoped_identifier" | "scoped_use_list" | "use_list" => {
+                            Self::collect_use_leaves_with_alias(
+                                source_bytes,
+                                child,
+                                prefix.clone(),
+                                out,
+                            );
+                        }
+                        "use_as_clause" => {
+                            Self::collect_use_leaves_with_alias(
+                                source_bytes,
+                                child,
+                                prefix.clone(),
+                                out,
+                            );
+                        }
+                        "self" => Self::push_use_leaf(prefix.clone(), None, out),
+                        _ => {}
+                    }
+                }
+            }
+            "use_as_clause" => {
+                let path = node.child_by_field_name("path");
+                let alias = node.child_by_field_name("alias");
+                if let (Some(path), Some(alias)) = (path, alias) {
+                    if let (Ok(path_text), Ok(alias_text)) =
+                        (path.utf8_text(source_bytes), alias.utf8_text(source_bytes))
+                    {
+                        let full = if prefix.is_empty() {
+                            path_text.to_string()
+                        } else {
+                            format!("{prefix}::{path_text}")
+                        };
+                        Self::push_use_leaf(full, Some(alias_text.to_string()), out);
+                    }
+                }
+            }
+            "identifier" | "self" | "super" | "crate" => {
+                if let Ok(text) = node.utf8_text(source_bytes) {
+                    let full = if prefix.is_empty() {
+                        text.to_string()
+                    } else {
+                        format!("{prefix}::{text}")
+                    };
+                    Self::push_use_leaf(full, None, out);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Split a fully-expanded use path (e.g. `crate::foo::Bar`) into its
+    /// defining module and original name, and record it as a re-export
+    /// edge. `visible_as`, when set (from a `Bar as Baz` rename), becomes
+    /// the reported `symbol` with the original name tracked as `alias`.
+    fn push_use_leaf(full_path: String, visible_as: Option<String>, out: &mut Vec<ReExportEntry>) {
+        let Some((source_module, original_name)) = full_path.rsplit_once("::") else {
+            return;
+        };
+        let entry = match visible_as {
+            Some(visible_name) => ReExportEntry::new(visible_name, source_module.to_string())
+                .with_alias(Some(original_name.to_string())),
+            None => ReExportEntry::new(original_name.to_string(), source_module.to_string()),
+        };
+        out.push(entry);
+    }
+
+    /// Intra-crate module edges: every fully-expanded use path rooted at
+    /// `crate`, `self`, or `super`, which refers to another module in this
+    /// same crate rather than an external dependency.
+    fn extract_module_edges(
+        &self,
+        source_bytes: &[u8],
+        root_node: tree_sitter::Node,
+    ) -> Vec<String> {
+        self.extract_full_use_paths(source_bytes, root_node)
+            .into_iter()
+            .filter(|p| {
+                p.starts_with("crate::") || p.starts_with("self::") || p.starts_with("super::")
+            })
+            .collect()
+    }
+
+    /// Names of `extern_crate_declaration`s annotated with `#[macro_use]` —
+    /// pre-2018-edition crates like `diesel`/`serde_derive` use this to
+    /// bring their macros into scope, instead of a `#[macro_use]` on a
+    /// `use` item.
+    fn extract_macro_use_crates(
+        &self,
+        source_bytes: &[u8],
+        root_node: tree_sitter::Node,
+    ) -> Vec<String> {
+        let mut crates = Vec::new();
+        let mut cursor = root_node.walk();
+        for child in root_node.children(&mut cursor) {
+            if child.kind() != "extern_crate_declaration" {
+                continue;
+            }
+            if !Self::preceding_has_attribute(child, source_bytes, "macro_use") {
+                continue;
+            }
+            let mut inner = child.walk();
+            for c in child.children(&mut inner) {
+                if c.kind() == "identifier" {
+                    if let Ok(name) = c.utf8_text(source_bytes) {
+                        crates.push(name.to_string());
+                    }
+                }
+            }
+        }
+        crates.sort();
+        crates.dedup();
+        crates
+    }
+
+    /// Top-level `mod name;` module *declarations* (no `{ ... }` body,
+    /// pointing at another file) — the module tree, as distinct from the
+    /// `mod` entries in `exports`/`visibility`, which also include inline
+    /// `mod name { ... }` definitions.
+    fn extract_module_declarations(
+        &self,
+        source_bytes: &[u8],
+        root_node: tree_sitter::Node,
+    ) -> Vec<String> {
+        let mut modules = Vec::new();
+        let mut cursor = root_node.walk();
+        for child in root_node.children(&mut cursor) {
+            if child.kind() != "mod_item" || child.child_by_field_name("body").is_some() {
+                continue;
+            }
+            if let Some(name) = child.child_by_field_name("name") {
+                if let Ok(name) = name.utf8_text(source_bytes) {
+                    modules.push(name.to_string());
+                }
+            }
+        }
+        modules.sort();
+        modules.dedup();
+        modules
+    }
+
+    /// `#[cfg(...)]` predicate, if any, gating each top-level `extern crate`
+    /// declaration — the counterpart to `ExportEntry.cfg` for imports, used
+    /// to evaluate `ParseOptions.active_cfg` against extern-crate imports.
+    fn extract_extern_crate_cfgs(
+        &self,
+        source_bytes: &[u8],
+        root_node: tree_sitter::Node,
+    ) -> HashMap<String, String> {
+        let mut cfgs = HashMap::new();
+        let mut cursor = root_node.walk();
+        for child in root_node.children(&mut cursor) {
+            if child.kind() != "extern_crate_declaration" {
+                continue;
+            }
+            let Some(cfg) = Self::preceding_cfg(child, source_bytes) else {
+                continue;
+            };
+            let mut inner = child.walk();
+            for c in child.children(&mut inner) {
+                if c.kind() == "identifier" {
+                    if let Ok(name) = c.utf8_text(source_bytes) {
+                        cfgs.insert(name.to_string(), cfg.clone());
+                    }
+                }
+            }
+        }
+        cfgs
+    }
+
+    /// Evaluate a raw `#[cfg(...)]` predicate (e.g. `unix`, `not(windows)`,
+    /// `all(unix, feature = "foo")`) against a flat list of active
+    /// identifiers (bare options like `"unix"`/`"test"`, or `"key=value"`
+    /// pairs like `"target_os=linux"`). A predicate this parser doesn't
+    /// recognize is treated as satisfied, so unfamiliar syntax never hides a
+    /// symbol the caller didn't ask to filter out.
+    fn cfg_predicate_holds(predicate: &str, active_cfg: &[String]) -> bool {
+        match Self::parse_cfg_expr(predicate.trim()) {
+            Some(expr) => Self::eval_cfg_expr(&expr, active_cfg),
+            None => true,
+        }
+    }
+
+    fn parse_cfg_expr(text: &str) -> Option<CfgExpr> {
+        let text = text.trim();
+        if let Some(inner) = text.strip_prefix("all(").and_then(|s| s.strip_suffix(')')) {
+            return Some(CfgExpr::All(
+                Self::split_top_level_args(inner)
+                    .into_iter()
+                    .map(|arg| Self::parse_cfg_expr(arg).unwrap_or(CfgExpr::Atom(arg.to_string())))
+                    .collect(),
+            ));
+        }
+        if let Some(inner) = text.strip_prefix("any(").and_then(|s| s.strip_suffix(')')) {
+            return Some(CfgExpr::Any(
+                Self::split_top_level_args(inner)
+                    .into_iter()
+                    .map(|arg| Self::parse_cfg_expr(arg).unwrap_or(CfgExpr::Atom(arg.to_string())))
+                    .collect(),
+            ));
+        }
+        if let Some(inner) = text.strip_prefix("not(").and_then(|s| s.strip_suffix(')')) {
+            return Some(CfgExpr::Not(Box::new(
+                Self::parse_cfg_expr(inner).unwrap_or(CfgExpr::Atom(inner.to_string())),
+            )));
+        }
+        if text.is_empty() || text.contains('(') {
+            return None;
+        }
+        Some(CfgExpr::Atom(Self::normalize_cfg_atom(text)))
+    }
+
+    /// Split `a, b = "c", all(d, e)` on top-level commas only, leaving
+    /// anything nested inside parens intact.
+    fn split_top_level_args(text: &str) -> Vec<&str> {
+        let mut args = Vec::new();
+        let mut depth = 0usize;
+        let mut start = 0usize;
+        for (i, ch) in text.char_indices() {
+            match ch {
+                '(' => depth += 1,
+                ')' => depth = depth.saturating_sub(1),
+                ',' if depth == 0 => {
+                    args.push(text[start..i].trim());
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        let tail = text[start..].trim();
+        if !tail.is_empty() {
+            args.push(tail);
+        }
+        args
+    }
+
+    /// Normalize `key = "value"` to `key=value`, and leave bare identifiers
+    /// (`unix`, `test`) untouched, so it compares directly against entries
+    /// in `ParseOptions.active_cfg`.
+    fn normalize_cfg_atom(text: &str) -> String {
+        match text.split_once('=') {
+            Some((key, value)) => {
+                let value = value.trim().trim_matches('"');
+                format!("{}={}", key.trim(), value)
+            }
+            None => text.trim().to_string(),
+        }
+    }
+
+    fn eval_cfg_expr(expr: &CfgExpr, active_cfg: &[String]) -> bool {
+        match expr {
+            CfgExpr::Atom(atom) => active_cfg.iter().any(|a| a == atom),
+            CfgExpr::All(exprs) => exprs.iter().all(|e| Self::eval_cfg_expr(e, active_cfg)),
+            CfgExpr::Any(exprs) => exprs.iter().any(|e| Self::eval_cfg_expr(e, active_cfg)),
+            CfgExpr::Not(inner) => !Self::eval_cfg_expr(inner, active_cfg),
+        }
+    }
+
+    /// Scan line and block comments for `TODO`/`FIXME`/`XXX`/`HACK`
+    /// markers, plus bare `todo!`/`unimplemented!` macro invocations,
+    /// returning `{ kind, line, text }` objects in source order. String
+    /// literals are skipped entirely so a marker word inside a string
+    /// doesn't get mistaken for a debt annotation.
+    fn extract_markers(
+        &self,
+        source: &str,
+        root_node: tree_sitter::Node,
+    ) -> Vec<serde_json::Value> {
+        const KEYWORDS: &[&str] = &["TODO", "FIXME", "XXX", "HACK"];
+        let mut markers = Vec::new();
+        Self::walk_for_markers(root_node, source.as_bytes(), KEYWORDS, &mut markers);
+        markers.sort_by_key(|m| m["line"].as_u64().unwrap_or(0));
+        markers
+    }
+
+    fn walk_for_markers(
+        node: tree_sitter::Node,
+        source_bytes: &[u8],
+        keywords: &[&str],
+        out: &mut Vec<serde_json::Value>,
+    ) {
+        match node.kind() {
+            "line_comment" | "block_comment" => {
+                if let Ok(text) = node.utf8_text(source_bytes) {
+                    let line = node.start_position().row + 1;
+                    for keyword in keywords {
+                        if let Some(idx) = text.find(keyword) {
+                            let message = text[idx + keyword.len()..]
+                                .trim_start_matches(':')
+                                .trim()
+                                .trim_end_matches("*/")
+                                .trim()
+                                .to_string();
+                            out.push(serde_json::json!({
+                                "kind": keyword,
+                                "line": line,
+                                "text": message,
+                            }));
+                        }
+                    }
+                }
+            }
+            "macro_invocation" => {
+                if let Ok(text) = node.utf8_text(source_bytes) {
+                    for name in ["todo", "unimplemented"] {
+                        if text.starts_with(&format!("{name}!")) {
+                            let line = node.start_position().row + 1;
+                            let message = text
+                                .splitn(2, '(')
+                                .nth(1)
+                                .unwrap_or("")
+                                .trim_end_matches(')')
+                                .trim_matches('"')
+                                .to_string();
+                            out.push(serde_json::json!({
+                                "kind": name,
+                                "line": line,
+                                "text": message,
+                            }));
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        let mut child_cursor = node.walk();
+        for child in node.children(&mut child_cursor) {
+            Self::walk_for_markers(child, source_bytes, keywords, out);
+        }
+    }
+
+    /// Group consecutive `///`, `//!`, or `//` comment lines into blocks
+    /// (modeled on rust-analyzer's `CommentBlock` source-gen utility), and
+    /// separately collect `// region: NAME` / `// endregion[: NAME]`
+    /// delimited sections. A blank line always breaks a comment run; an
+    /// unterminated region still yields its contents up to EOF.
+    fn extract_doc_blocks_and_regions(
+        source: &str,
+    ) -> (
+        Vec<serde_json::Value>,
+        serde_json::Map<String, serde_json::Value>,
+    ) {
+        let mut doc_blocks = Vec::new();
+        let mut regions = serde_json::Map::new();
+
+        let mut current_block: Option<(usize, Vec<String>)> = None;
+        let mut open_region: Option<(String, Vec<String>)> = None;
+
+        let flush_block = |block: &mut Option<(usize, Vec<String>)>,
+                           out: &mut Vec<serde_json::Value>| {
+            if let Some((line, lines)) = block.take() {
+                out.push(serde_json::json!({
+                    "line": line,
+                    "contents": lines.join("\n"),
+                }));
+            }
+        };
+
+        for (idx, raw_line) in source.lines().enumerate() {
+            let line_no = idx + 1;
+            let trimmed = raw_line.trim_start();
+
+            if let Some(rest) = trimmed
+                .strip_prefix("// region:")
+                .or_else(|| trimmed.strip_prefix("//region:"))
+            {
+                flush_block(&mut current_block, &mut doc_blocks);
+                open_region = Some((rest.trim().to_string(), Vec::new()));
+                continue;
+            }
+            if trimmed.starts_with("// endregion") || trimmed.starts_with("//endregion") {
+                if let Some((name, lines)) = open_region.take() {
+                    regions.insert(name, serde_json::Value::String(lines.join("\n")));
+                }
+                continue;
+            }
+            if let Some((_, lines)) = open_region.as_mut() {
+                lines.push(raw_line.to_string());
+                continue;
+            }
+
+            let comment_body = if let Some(rest) = trimmed.strip_prefix("///") {
+                Some(rest.strip_prefix(' ').unwrap_or(rest))
+            } else if let Some(rest) = trimmed.strip_prefix("//!") {
+                Some(rest.strip_prefix(' ').unwrap_or(rest))
+            } else if let Some(rest) = trimmed.strip_prefix("//") {
+                Some(rest.strip_prefix(' ').unwrap_or(rest))
+            } else {
+                None
+            };
+
+            match comment_body {
+                Some(body) => {
+                    let block = current_block.get_or_insert_with(|| (line_no, Vec::new()));
+                    block.1.push(body.to_string());
+                }
+                None => flush_block(&mut current_block, &mut doc_blocks),
+            }
+        }
+
+        flush_block(&mut current_block, &mut doc_blocks);
+        if let Some((name, lines)) = open_region.take() {
+            regions.insert(name, serde_json::Value::String(lines.join("\n")));
+        }
+
+        (doc_blocks, regions)
+    }
+
     fn count_unsafe_blocks(&self, source: &str, root_node: tree_sitter::Node) -> usize {
         let source_bytes = source.as_bytes();
         let mut cursor = QueryCursor::new();
@@ -366,22 +1360,54 @@ impl Parser for RustParser {
 
         let root_node = tree.root_node();
 
-        let exports = self.extract_exports(source, root_node);
+        let mut exports = self.extract_exports(source, root_node);
+        exports.extend(self.extract_exported_macros(source.as_bytes(), root_node));
+        exports.sort_by(|a, b| a.name.cmp(&b.name));
+        exports.dedup_by(|a, b| a.name == b.name);
+        exports.retain(|e| !e.cfg.as_deref().is_some_and(Self::is_cfg_test));
         let imports = self.extract_imports(source, root_node);
         let dependencies = self.extract_dependencies(source, root_node);
+        let re_exports = self.extract_re_exports(source.as_bytes(), root_node);
         let loc = source.lines().count();
+        let test_functions = self.extract_test_functions(source.as_bytes(), root_node);
+        let test_loc = self.test_loc(source.as_bytes(), root_node);
 
         let unsafe_count = self.count_unsafe_blocks(source, root_node);
         let derives = self.extract_derives(source, root_node);
         let trait_impls = self.extract_trait_impls(source, root_node);
         let lifetimes = self.extract_lifetimes(source, root_node);
         let async_count = self.count_async_functions(source, root_node);
+        let macro_definitions = self.extract_macro_definitions(source, root_node);
+        let macro_invocations = self.extract_macro_invocations(source, root_node);
+        let use_paths = self.extract_full_use_paths(source.as_bytes(), root_node);
+        let module_edges = self.extract_module_edges(source.as_bytes(), root_node);
+        let markers = self.extract_markers(source, root_node);
+        let (doc_blocks, regions) = Self::extract_doc_blocks_and_regions(source);
+        let visibility_inventory = self.extract_visibility_inventory(source, root_node);
+        let macro_use_crates = self.extract_macro_use_crates(source.as_bytes(), root_node);
+        let modules = self.extract_module_declarations(source.as_bytes(), root_node);
+
+        let cfg_gated: HashMap<String, String> = exports
+            .iter()
+            .filter_map(|e| e.cfg.as_ref().map(|cfg| (e.name.clone(), cfg.clone())))
+            .collect();
 
         let has_custom = unsafe_count > 0
             || !derives.is_empty()
             || !trait_impls.is_empty()
             || !lifetimes.is_empty()
-            || async_count > 0;
+            || async_count > 0
+            || !cfg_gated.is_empty()
+            || !macro_definitions.is_empty()
+            || !macro_invocations.is_empty()
+            || !use_paths.is_empty()
+            || !markers.is_empty()
+            || !doc_blocks.is_empty()
+            || !regions.is_empty()
+            || !visibility_inventory.is_empty()
+            || !macro_use_crates.is_empty()
+            || !modules.is_empty()
+            || !test_functions.is_empty();
 
         let custom_fields = if !has_custom {
             None
@@ -429,6 +1455,107 @@ impl Parser for RustParser {
                     serde_json::Value::Number(async_count.into()),
                 );
             }
+            if !cfg_gated.is_empty() {
+                fields.insert(
+                    "cfg_gated".to_string(),
+                    serde_json::Value::Object(
+                        cfg_gated
+                            .into_iter()
+                            .map(|(name, cfg)| (name, serde_json::Value::String(cfg)))
+                            .collect(),
+                    ),
+                );
+            }
+            if !macro_definitions.is_empty() {
+                fields.insert(
+                    "macro_definitions".to_string(),
+                    serde_json::Value::Array(
+                        macro_definitions
+                            .into_iter()
+                            .map(serde_json::Value::String)
+                            .collect(),
+                    ),
+                );
+            }
+            if !macro_invocations.is_empty() {
+                fields.insert(
+                    "macro_invocations".to_string(),
+                    serde_json::Value::Object(
+                        macro_invocations
+                            .into_iter()
+                            .map(|(name, count)| (name, serde_json::Value::Number(count.into())))
+                            .collect(),
+                    ),
+                );
+            }
+            if !use_paths.is_empty() {
+                fields.insert(
+                    "use_paths".to_string(),
+                    serde_json::Value::Array(
+                        use_paths
+                            .into_iter()
+                            .map(serde_json::Value::String)
+                            .collect(),
+                    ),
+                );
+            }
+            if !module_edges.is_empty() {
+                fields.insert(
+                    "module_edges".to_string(),
+                    serde_json::Value::Array(
+                        module_edges
+                            .into_iter()
+                            .map(serde_json::Value::String)
+                            .collect(),
+                    ),
+                );
+            }
+            if !markers.is_empty() {
+                fields.insert("markers".to_string(), serde_json::Value::Array(markers));
+            }
+            if !doc_blocks.is_empty() {
+                fields.insert(
+                    "doc_blocks".to_string(),
+                    serde_json::Value::Array(doc_blocks),
+                );
+            }
+            if !regions.is_empty() {
+                fields.insert("regions".to_string(), serde_json::Value::Object(regions));
+            }
+            if !visibility_inventory.is_empty() {
+                fields.insert(
+                    "visibility".to_string(),
+                    serde_json::Value::Array(visibility_inventory),
+                );
+            }
+            if !macro_use_crates.is_empty() {
+                fields.insert(
+                    "macro_use_crates".to_string(),
+                    serde_json::Value::Array(
+                        macro_use_crates
+                            .into_iter()
+                            .map(serde_json::Value::String)
+                            .collect(),
+                    ),
+                );
+            }
+            if !modules.is_empty() {
+                fields.insert(
+                    "modules".to_string(),
+                    serde_json::Value::Array(
+                        modules.into_iter().map(serde_json::Value::String).collect(),
+                    ),
+                );
+            }
+            if !test_functions.is_empty() {
+                fields.insert(
+                    "test_functions".to_string(),
+                    serde_json::json!({
+                        "count": test_functions.len(),
+                        "names": test_functions,
+                    }),
+                );
+            }
             Some(fields)
         };
 
@@ -438,11 +1565,43 @@ impl Parser for RustParser {
                 imports,
                 dependencies,
                 loc,
+                re_exports,
+                test_loc,
+                symbols: Vec::new(),
+                diagnostics: crate::parser::collect_diagnostics(root_node),
             },
             custom_fields,
         })
     }
 
+    fn parse_with_options(&mut self, source: &str, options: &ParseOptions) -> Result<ParseResult> {
+        let mut result = self.parse(source)?;
+        if options.active_cfg.is_empty() {
+            return Ok(result);
+        }
+
+        result.metadata.exports.retain(|e| {
+            e.cfg
+                .as_deref()
+                .map(|cfg| Self::cfg_predicate_holds(cfg, &options.active_cfg))
+                .unwrap_or(true)
+        });
+
+        let tree = self
+            .parser
+            .parse(source, None)
+            .ok_or_else(|| anyhow::anyhow!("Failed to parse Rust source"))?;
+        let extern_crate_cfgs = self.extract_extern_crate_cfgs(source.as_bytes(), tree.root_node());
+        result.metadata.imports.retain(|name| {
+            extern_crate_cfgs
+                .get(name)
+                .map(|cfg| Self::cfg_predicate_holds(cfg, &options.active_cfg))
+                .unwrap_or(true)
+        });
+
+        Ok(result)
+    }
+
     fn language_id(&self) -> &'static str {
         "rust"
     }
@@ -469,10 +1628,14 @@ mod tests {
             .metadata
             .export_names()
             .contains(&"world".to_string()));
-        assert!(!result
+        // Private items are tracked, not discarded, but tagged accordingly.
+        let private = result
             .metadata
-            .export_names()
-            .contains(&"private".to_string()));
+            .exports
+            .iter()
+            .find(|e| e.name == "private")
+            .expect("private fn should still be recorded");
+        assert_eq!(private.visibility, Visibility::Private);
     }
 
     #[test]
@@ -482,10 +1645,278 @@ mod tests {
         let result = parser.parse(source).unwrap();
         assert!(result.metadata.export_names().contains(&"Foo".to_string()));
         assert!(result.metadata.export_names().contains(&"Bar".to_string()));
-        assert!(!result
+        let private = result
             .metadata
-            .export_names()
-            .contains(&"Private".to_string()));
+            .exports
+            .iter()
+            .find(|e| e.name == "Private")
+            .expect("private struct should still be recorded");
+        assert_eq!(private.visibility, Visibility::Private);
+    }
+
+    #[test]
+    fn parse_rust_cfg_gated_items() {
+        let mut parser = RustParser::new().unwrap();
+        let source =
+            "#[cfg(target_os = \"linux\")]\npub fn linux_only() {}\npub fn everywhere() {}";
+        let result = parser.parse(source).unwrap();
+        let linux_only = result
+            .metadata
+            .exports
+            .iter()
+            .find(|e| e.name == "linux_only")
+            .unwrap();
+        assert_eq!(linux_only.cfg.as_deref(), Some("target_os = \"linux\""));
+        let everywhere = result
+            .metadata
+            .exports
+            .iter()
+            .find(|e| e.name == "everywhere")
+            .unwrap();
+        assert!(everywhere.cfg.is_none());
+
+        let custom = result.custom_fields.unwrap();
+        assert!(custom.contains_key("cfg_gated"));
+    }
+
+    #[test]
+    fn parse_rust_doc_blocks_and_regions() {
+        let mut parser = RustParser::new().unwrap();
+        let source = "/// Line one.\n/// Line two.\npub fn hello() {}\n\n// region: setup\nfn helper() {}\n// endregion: setup\n";
+        let result = parser.parse(source).unwrap();
+        let custom = result.custom_fields.unwrap();
+        let blocks = custom.get("doc_blocks").unwrap().as_array().unwrap();
+        assert!(blocks
+            .iter()
+            .any(|b| b["contents"] == "Line one.\nLine two." && b["line"] == 1));
+        let regions = custom.get("regions").unwrap().as_object().unwrap();
+        assert_eq!(
+            regions.get("setup").unwrap().as_str().unwrap(),
+            "fn helper() {}"
+        );
+    }
+
+    #[test]
+    fn parse_rust_visibility_inventory() {
+        let mut parser = RustParser::new().unwrap();
+        let source = "pub fn hello() {}\npub(crate) struct Inner;\nmod helpers {\n    fn private_fn() {}\n}\n";
+        let result = parser.parse(source).unwrap();
+        let custom = result.custom_fields.unwrap();
+        let inventory = custom.get("visibility").unwrap().as_array().unwrap();
+
+        let hello = inventory.iter().find(|e| e["name"] == "hello").unwrap();
+        assert_eq!(hello["kind"], "fn");
+        assert_eq!(hello["visibility"], "public");
+
+        let inner = inventory.iter().find(|e| e["name"] == "Inner").unwrap();
+        assert_eq!(inner["kind"], "struct");
+        assert_eq!(inner["visibility"], "crate");
+
+        let helpers = inventory.iter().find(|e| e["name"] == "helpers").unwrap();
+        assert_eq!(helpers["kind"], "mod");
+        assert_eq!(helpers["visibility"], "private");
+
+        let private_fn = inventory
+            .iter()
+            .find(|e| e["name"] == "private_fn")
+            .unwrap();
+        assert_eq!(private_fn["visibility"], "private");
+    }
+
+    #[test]
+    fn parse_rust_extracts_todo_markers() {
+        let mut parser = RustParser::new().unwrap();
+        let source = "// TODO: wire this up\nfn main() {\n    let s = \"TODO in a string\";\n    todo!(\"finish this\");\n}";
+        let result = parser.parse(source).unwrap();
+        let custom = result.custom_fields.unwrap();
+        let markers = custom.get("markers").unwrap().as_array().unwrap();
+        assert!(markers
+            .iter()
+            .any(|m| m["kind"] == "TODO" && m["line"] == 1));
+        assert!(markers.iter().any(|m| m["kind"] == "todo"));
+        // The marker word inside the string literal must not produce a
+        // second "TODO" entry anchored at line 3.
+        assert!(!markers.iter().any(|m| m["line"] == 3));
+    }
+
+    #[test]
+    fn parse_rust_expands_use_trees_and_module_edges() {
+        let mut parser = RustParser::new().unwrap();
+        let source = "use crate::config::{Config, Loader as L};\nuse std::collections::HashMap;";
+        let result = parser.parse(source).unwrap();
+        let custom = result.custom_fields.unwrap();
+        let use_paths: Vec<String> = custom
+            .get("use_paths")
+            .unwrap()
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v.as_str().unwrap().to_string())
+            .collect();
+        assert!(use_paths.contains(&"crate::config::Config".to_string()));
+        assert!(use_paths.contains(&"crate::config::Loader".to_string()));
+        assert!(use_paths.contains(&"std::collections::HashMap".to_string()));
+
+        let module_edges: Vec<String> = custom
+            .get("module_edges")
+            .unwrap()
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v.as_str().unwrap().to_string())
+            .collect();
+        assert!(module_edges.contains(&"crate::config::Config".to_string()));
+        assert!(!module_edges.iter().any(|p| p.starts_with("std::")));
+    }
+
+    #[test]
+    fn parse_rust_pub_use_tracked_as_re_exports() {
+        let mut parser = RustParser::new().unwrap();
+        let source = "pub use crate::config::Config;\nuse crate::internal::Helper;\n";
+        let result = parser.parse(source).unwrap();
+        assert_eq!(result.metadata.re_exports.len(), 1);
+        let entry = &result.metadata.re_exports[0];
+        assert_eq!(entry.symbol, "Config");
+        assert_eq!(entry.source_module, "crate::config");
+        assert!(entry.alias.is_none());
+    }
+
+    #[test]
+    fn parse_rust_pub_use_alias_and_list_tracked() {
+        let mut parser = RustParser::new().unwrap();
+        let source = "pub use crate::config::{Config, Loader as L};\n";
+        let result = parser.parse(source).unwrap();
+        let names: Vec<&str> = result
+            .metadata
+            .re_exports
+            .iter()
+            .map(|r| r.symbol.as_str())
+            .collect();
+        assert!(names.contains(&"Config"));
+        assert!(names.contains(&"L"));
+
+        let renamed = result
+            .metadata
+            .re_exports
+            .iter()
+            .find(|r| r.symbol == "L")
+            .unwrap();
+        assert_eq!(renamed.source_module, "crate::config");
+        assert_eq!(renamed.alias.as_deref(), Some("Loader"));
+    }
+
+    #[test]
+    fn parse_rust_doc_comments_and_intra_doc_links() {
+        let mut parser = RustParser::new().unwrap();
+        let source = "/// Greets the caller.\n///\n/// See [`world`] for the other half.\npub fn hello() {}\npub fn world() {}";
+        let result = parser.parse(source).unwrap();
+        let hello = result
+            .metadata
+            .exports
+            .iter()
+            .find(|e| e.name == "hello")
+            .unwrap();
+        assert_eq!(
+            hello.doc.as_deref(),
+            Some("Greets the caller.\n\nSee [`world`] for the other half.")
+        );
+        assert_eq!(hello.doc_links, vec!["world".to_string()]);
+    }
+
+    #[test]
+    fn parse_rust_macro_definitions_and_invocations() {
+        let mut parser = RustParser::new().unwrap();
+        let source =
+            "macro_rules! my_macro { () => {} }\nfn main() { my_macro!(); println!(\"hi\"); }";
+        let result = parser.parse(source).unwrap();
+        let custom = result.custom_fields.unwrap();
+        let defs = custom.get("macro_definitions").unwrap().as_array().unwrap();
+        assert!(defs.contains(&serde_json::Value::String("my_macro".to_string())));
+        let invocations = custom.get("macro_invocations").unwrap().as_object().unwrap();
+        assert_eq!(invocations.get("my_macro").unwrap().as_u64(), Some(1));
+        assert_eq!(invocations.get("println").unwrap().as_u64(), Some(1));
+    }
+
+    #[test]
+    fn parse_rust_macro_invocations_grouped_with_counts() {
+        let mut parser = RustParser::new().unwrap();
+        let source = r#"
+fn build() -> u32 {
+    lazy_static!(CONFIG);
+    let a = json!({ "a": 1 });
+    let b = json!({ "b": 2 });
+    a + b
+}
+"#;
+        let result = parser.parse(source).unwrap();
+        let custom = result.custom_fields.unwrap();
+        let invocations = custom.get("macro_invocations").unwrap().as_object().unwrap();
+        assert_eq!(invocations.get("lazy_static").unwrap().as_u64(), Some(1));
+        assert_eq!(invocations.get("json").unwrap().as_u64(), Some(2));
+    }
+
+    #[test]
+    fn parse_rust_macro_export_joins_export_names() {
+        let mut parser = RustParser::new().unwrap();
+        let source = "#[macro_export]\nmacro_rules! public_macro { () => {} }\nmacro_rules! private_macro { () => {} }";
+        let result = parser.parse(source).unwrap();
+        let names = result.metadata.export_names();
+        assert!(names.contains(&"public_macro".to_string()));
+        assert!(!names.contains(&"private_macro".to_string()));
+    }
+
+    #[test]
+    fn parse_rust_cfg_test_module_excluded_from_exports() {
+        let mut parser = RustParser::new().unwrap();
+        let source = r#"
+pub fn production() {}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn it_works() {
+        assert!(true);
+    }
+}
+"#;
+        let result = parser.parse(source).unwrap();
+        let names = result.metadata.export_names();
+        assert!(names.contains(&"production".to_string()));
+        assert!(!names.contains(&"it_works".to_string()));
+    }
+
+    #[test]
+    fn parse_rust_test_functions_reported_with_count_and_names() {
+        let mut parser = RustParser::new().unwrap();
+        let source = r#"
+pub fn production() {}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn it_works() {}
+
+    #[test]
+    fn it_also_works() {}
+}
+"#;
+        let result = parser.parse(source).unwrap();
+        let custom = result.custom_fields.unwrap();
+        let test_functions = custom.get("test_functions").unwrap();
+        assert_eq!(test_functions.get("count").unwrap().as_u64(), Some(2));
+        let names = test_functions.get("names").unwrap().as_array().unwrap();
+        assert!(names.contains(&serde_json::Value::String("it_works".to_string())));
+        assert!(names.contains(&serde_json::Value::String("it_also_works".to_string())));
+    }
+
+    #[test]
+    fn parse_rust_test_loc_split_from_production_loc() {
+        let mut parser = RustParser::new().unwrap();
+        let source = "pub fn production() {}\n\n#[cfg(test)]\nmod tests {\n    #[test]\n    fn it_works() {}\n}\n";
+        let result = parser.parse(source).unwrap();
+        assert_eq!(result.metadata.loc, source.lines().count());
+        assert!(result.metadata.test_loc > 0);
+        assert!(result.metadata.test_loc < result.metadata.loc);
     }
 
     #[test]
@@ -520,23 +1951,22 @@ mod tests {
     }
 
     #[test]
-    fn parse_rust_pub_crate_excluded() {
+    fn parse_rust_pub_crate_visibility_tiers() {
         let mut parser = RustParser::new().unwrap();
         let source =
             "pub fn visible() {}\npub(crate) fn internal() {}\npub(super) fn parent_only() {}";
         let result = parser.parse(source).unwrap();
-        assert!(result
-            .metadata
-            .export_names()
-            .contains(&"visible".to_string()));
-        assert!(!result
-            .metadata
-            .export_names()
-            .contains(&"internal".to_string()));
-        assert!(!result
-            .metadata
-            .export_names()
-            .contains(&"parent_only".to_string()));
+        let visibility_of = |name: &str| {
+            result
+                .metadata
+                .exports
+                .iter()
+                .find(|e| e.name == name)
+                .map(|e| e.visibility)
+        };
+        assert_eq!(visibility_of("visible"), Some(Visibility::Public));
+        assert_eq!(visibility_of("internal"), Some(Visibility::Crate));
+        assert_eq!(visibility_of("parent_only"), Some(Visibility::Restricted));
     }
 
     #[test]
@@ -646,4 +2076,162 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn rust_custom_fields_macro_use_crates() {
+        let mut parser = RustParser::new().unwrap();
+        let source = "#[macro_use]\nextern crate diesel;\n#[macro_use]\nextern crate serde_derive;\nextern crate serde_json;\n";
+        let result = parser.parse(source).unwrap();
+        let fields = result.custom_fields.unwrap();
+        let macro_use_crates: Vec<&str> = fields
+            .get("macro_use_crates")
+            .unwrap()
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v.as_str().unwrap())
+            .collect();
+        assert!(macro_use_crates.contains(&"diesel"));
+        assert!(macro_use_crates.contains(&"serde_derive"));
+        assert!(!macro_use_crates.contains(&"serde_json"));
+    }
+
+    #[test]
+    fn rust_custom_fields_module_declarations() {
+        let mut parser = RustParser::new().unwrap();
+        let source = "#[macro_use]\nextern crate diesel;\n\npub mod database;\npub mod web;\nmod internal;\nmod with_body {\n    pub fn helper() {}\n}\n";
+        let result = parser.parse(source).unwrap();
+        let fields = result.custom_fields.unwrap();
+        let modules: Vec<&str> = fields
+            .get("modules")
+            .unwrap()
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v.as_str().unwrap())
+            .collect();
+        assert!(modules.contains(&"database"));
+        assert!(modules.contains(&"web"));
+        assert!(modules.contains(&"internal"));
+        assert!(!modules.contains(&"with_body"));
+    }
+
+    #[test]
+    fn rust_no_macro_use_crates_without_attribute() {
+        let mut parser = RustParser::new().unwrap();
+        let source = "extern crate serde_json;\npub fn hello() {}";
+        let result = parser.parse(source).unwrap();
+        let has_macro_use_crates = result
+            .custom_fields
+            .map(|fields| fields.contains_key("macro_use_crates"))
+            .unwrap_or(false);
+        assert!(!has_macro_use_crates);
+    }
+
+    #[test]
+    fn parse_with_options_keeps_everything_when_active_cfg_is_empty() {
+        let mut parser = RustParser::new().unwrap();
+        let source = "#[cfg(unix)]\npub fn check() -> bool { true }\n#[cfg(windows)]\npub fn check() -> bool { false }\n";
+        let result = parser
+            .parse_with_options(source, &ParseOptions::default())
+            .unwrap();
+        assert_eq!(result.metadata.export_names().len(), 2);
+    }
+
+    #[test]
+    fn parse_with_options_filters_cfg_unix_windows_dual_definition() {
+        let mut parser = RustParser::new().unwrap();
+        let source = "#[cfg(unix)]\npub fn check() -> bool { true }\n#[cfg(windows)]\npub fn check() -> bool { false }\npub fn always() {}\n";
+
+        let unix_only = parser
+            .parse_with_options(
+                source,
+                &ParseOptions {
+                    active_cfg: vec!["unix".to_string()],
+                },
+            )
+            .unwrap();
+        let unix_names = unix_only.metadata.export_names();
+        assert_eq!(unix_names.len(), 2);
+        assert!(unix_names.contains(&"always".to_string()));
+        assert!(unix_names.contains(&"check".to_string()));
+        assert_eq!(
+            unix_only
+                .metadata
+                .exports
+                .iter()
+                .find(|e| e.name == "check")
+                .unwrap()
+                .cfg
+                .as_deref(),
+            Some("unix")
+        );
+
+        let windows_only = parser
+            .parse_with_options(
+                source,
+                &ParseOptions {
+                    active_cfg: vec!["windows".to_string()],
+                },
+            )
+            .unwrap();
+        let windows_names = windows_only.metadata.export_names();
+        assert_eq!(windows_names.len(), 2);
+        assert!(windows_names.contains(&"always".to_string()));
+        assert!(windows_names.contains(&"check".to_string()));
+    }
+
+    #[test]
+    fn parse_with_options_evaluates_all_any_not_predicates() {
+        let mut parser = RustParser::new().unwrap();
+        let source = "#[cfg(all(unix, target_os = \"linux\"))]\npub fn linux_unix() {}\n#[cfg(any(windows, target_os = \"linux\"))]\npub fn windows_or_linux() {}\n#[cfg(not(windows))]\npub fn not_windows() {}\n";
+
+        let linux = parser
+            .parse_with_options(
+                source,
+                &ParseOptions {
+                    active_cfg: vec!["unix".to_string(), "target_os=linux".to_string()],
+                },
+            )
+            .unwrap();
+        let names = linux.metadata.export_names();
+        assert!(names.contains(&"linux_unix".to_string()));
+        assert!(names.contains(&"windows_or_linux".to_string()));
+        assert!(names.contains(&"not_windows".to_string()));
+
+        let windows = parser
+            .parse_with_options(
+                source,
+                &ParseOptions {
+                    active_cfg: vec!["windows".to_string()],
+                },
+            )
+            .unwrap();
+        let names = windows.metadata.export_names();
+        assert!(!names.contains(&"linux_unix".to_string()));
+        assert!(names.contains(&"windows_or_linux".to_string()));
+        assert!(!names.contains(&"not_windows".to_string()));
+    }
+
+    #[test]
+    fn parse_with_options_filters_cfg_gated_extern_crate_imports() {
+        let mut parser = RustParser::new().unwrap();
+        let source =
+            "#[cfg(unix)]\nextern crate libc;\n#[cfg(windows)]\nextern crate winapi;\nextern crate serde_json;\n";
+
+        let unix_only = parser
+            .parse_with_options(
+                source,
+                &ParseOptions {
+                    active_cfg: vec!["unix".to_string()],
+                },
+            )
+            .unwrap();
+        assert!(unix_only.metadata.imports.contains(&"libc".to_string()));
+        assert!(!unix_only.metadata.imports.contains(&"winapi".to_string()));
+        assert!(unix_only
+            .metadata
+            .imports
+            .contains(&"serde_json".to_string()));
+    }
 }
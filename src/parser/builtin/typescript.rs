@@ -1,20 +1,42 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
-use crate::parser::{ExportEntry, Metadata, ParseResult, Parser};
+use crate::config::query_pack::LanguageQueryPack;
+use crate::parser::{
+    ExportEntry, ExportKind, Metadata, ParseResult, Parser, ReExportEntry, Symbol, SymbolKind,
+    Visibility,
+};
 use anyhow::Result;
 use streaming_iterator::StreamingIterator;
-use tree_sitter::{Language, Parser as TSParser, Query, QueryCursor};
+use tree_sitter::{InputEdit, Language, Parser as TSParser, Query, QueryCursor, Tree};
 
 use super::query_helpers::collect_matches_with_lines;
+use super::query_pack::CompiledQueryPack;
 
 pub struct TypeScriptParser {
     parser: TSParser,
     export_queries: Vec<Query>,
     import_query: Query,
+    test_call_query: Query,
+    /// User-supplied `custom_fields` queries from `fmm.queries.toml` (e.g.
+    /// decorator names), run alongside the hard-coded extraction below.
+    /// Empty when no query pack is configured.
+    query_pack: CompiledQueryPack,
+    /// Previous parse tree per file path, reused by
+    /// [`TypeScriptParser::parse_edit`] so tree-sitter only re-derives the
+    /// edited region instead of the whole file.
+    trees: HashMap<PathBuf, Tree>,
 }
 
 impl TypeScriptParser {
     pub fn new() -> Result<Self> {
+        Self::with_query_pack(None)
+    }
+
+    /// Like [`TypeScriptParser::new`], additionally running `query_pack`'s
+    /// `custom_fields` queries (if any) on every parse. Pass `None` when no
+    /// `fmm.queries.toml` entry exists for `"typescript"`.
+    pub fn with_query_pack(query_pack: Option<&LanguageQueryPack>) -> Result<Self> {
         let language: Language = tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into();
         let mut parser = TSParser::new();
         parser
@@ -27,6 +49,10 @@ impl TypeScriptParser {
             "(export_statement (class_declaration name: (type_identifier) @name))",
             "(export_statement (interface_declaration name: (type_identifier) @name))",
             "(export_statement (export_clause (export_specifier name: (identifier) @name)))",
+            "(export_statement (type_alias_declaration name: (type_identifier) @name))",
+            "(export_statement (enum_declaration name: (identifier) @name))",
+            "(export_statement (abstract_class_declaration name: (type_identifier) @name))",
+            "(export_statement (internal_module name: (identifier) @name))",
         ];
 
         let export_queries: Vec<Query> = export_query_strs
@@ -38,10 +64,21 @@ impl TypeScriptParser {
         let import_query = Query::new(&language, r#"(import_statement source: (string) @source)"#)
             .map_err(|e| anyhow::anyhow!("Failed to compile import query: {}", e))?;
 
+        let test_call_query = Query::new(
+            &language,
+            "(call_expression function: (identifier) @fn arguments: (arguments . (string) @desc)) @call",
+        )
+        .map_err(|e| anyhow::anyhow!("Failed to compile test_call query: {}", e))?;
+
+        let query_pack = CompiledQueryPack::compile(&language, query_pack)?;
+
         Ok(Self {
             parser,
             export_queries,
             import_query,
+            test_call_query,
+            query_pack,
+            trees: HashMap::new(),
         })
     }
 
@@ -58,10 +95,287 @@ impl TypeScriptParser {
             }
         }
 
+        if let Some(entry) = Self::extract_default_export(source, root_node) {
+            if seen.insert(entry.name.clone()) {
+                exports.push(entry);
+            }
+        }
+
+        let signatures = Self::exported_declaration_signatures(source, root_node);
+        exports = exports
+            .into_iter()
+            .map(|entry| match signatures.get(&entry.name) {
+                Some((kind, signature, doc)) => entry
+                    .with_kind(Some(*kind))
+                    .with_signature(signature.clone())
+                    .with_doc(doc.clone()),
+                None => entry,
+            })
+            .collect();
+
         exports.sort_by(|a, b| a.name.cmp(&b.name));
         exports
     }
 
+    /// `name -> (kind, signature, doc)` for every top-level declaration
+    /// directly under an `export_statement` (including `export default
+    /// ...`), mirroring [`TypeScriptParser::extract_symbols`]'s traversal
+    /// but additionally capturing a one-line signature from the declaration
+    /// node — the parameter list and return type for a function, the
+    /// heritage clause for a class or interface, the aliased type for a
+    /// type alias — and the JSDoc block (`/** ... */`) immediately
+    /// preceding the `export_statement`, if any. Exported names with no
+    /// matching declaration here (re-export specifiers) are simply absent
+    /// from the map.
+    fn exported_declaration_signatures(
+        source: &str,
+        root_node: tree_sitter::Node,
+    ) -> HashMap<String, (ExportKind, Option<String>, Option<String>)> {
+        let source_bytes = source.as_bytes();
+        let mut signatures = HashMap::new();
+
+        let mut cursor = root_node.walk();
+        for child in root_node.children(&mut cursor) {
+            if child.kind() != "export_statement" {
+                continue;
+            }
+            let doc = Self::preceding_jsdoc(child, source_bytes);
+            let mut inner = child.walk();
+            for decl in child.children(&mut inner) {
+                if let Some((name, kind, signature)) =
+                    Self::declaration_signature(decl, source_bytes)
+                {
+                    signatures.insert(name, (kind, signature, doc.clone()));
+                }
+            }
+        }
+
+        signatures
+    }
+
+    /// The JSDoc block comment (`/** ... */`) immediately preceding `node`,
+    /// if any, with the `/**`/`*/` delimiters and each line's leading `*`
+    /// stripped. A line comment (`//`) or a block comment that isn't JSDoc
+    /// style (`/* ... */` without the doubled `*`) doesn't count — only
+    /// `/**` is treated as documentation, matching the JSDoc convention
+    /// tools like TypeDoc and VS Code's hover already key off of.
+    fn preceding_jsdoc(node: tree_sitter::Node, source_bytes: &[u8]) -> Option<String> {
+        let sibling = node.prev_sibling()?;
+        if sibling.kind() != "comment" {
+            return None;
+        }
+        let text = sibling.utf8_text(source_bytes).ok()?;
+        if !text.starts_with("/**") {
+            return None;
+        }
+        let inner = text.trim_start_matches("/**").trim_end_matches("*/");
+        let lines: Vec<&str> = inner
+            .lines()
+            .map(|line| line.trim().trim_start_matches('*').trim())
+            .filter(|line| !line.is_empty())
+            .collect();
+        if lines.is_empty() {
+            return None;
+        }
+        Some(lines.join("\n"))
+    }
+
+    /// Maps a top-level declaration node to its name, [`ExportKind`], and a
+    /// one-line signature, or `None` for nodes this isn't meaningful for
+    /// (`export`/`default` keywords, enums, modules, re-export clauses).
+    fn declaration_signature(
+        node: tree_sitter::Node,
+        source_bytes: &[u8],
+    ) -> Option<(String, ExportKind, Option<String>)> {
+        match node.kind() {
+            "function_declaration" | "generator_function_declaration" => {
+                let name = node
+                    .child_by_field_name("name")?
+                    .utf8_text(source_bytes)
+                    .ok()?
+                    .to_string();
+                let params = node
+                    .child_by_field_name("parameters")
+                    .and_then(|n| n.utf8_text(source_bytes).ok())
+                    .unwrap_or("()");
+                let return_type = node
+                    .child_by_field_name("return_type")
+                    .and_then(|n| n.utf8_text(source_bytes).ok())
+                    .unwrap_or("");
+                Some((name, ExportKind::Function, Some(format!("{params}{return_type}"))))
+            }
+            "class_declaration" | "abstract_class_declaration" => {
+                let name = node
+                    .child_by_field_name("name")?
+                    .utf8_text(source_bytes)
+                    .ok()?
+                    .to_string();
+                let mut inner = node.walk();
+                let heritage = node
+                    .children(&mut inner)
+                    .find(|c| c.kind() == "class_heritage")
+                    .and_then(|n| n.utf8_text(source_bytes).ok())
+                    .map(|s| s.to_string());
+                Some((name, ExportKind::Class, heritage))
+            }
+            "interface_declaration" => {
+                let name = node
+                    .child_by_field_name("name")?
+                    .utf8_text(source_bytes)
+                    .ok()?
+                    .to_string();
+                let mut inner = node.walk();
+                let extends = node
+                    .children(&mut inner)
+                    .find(|c| c.kind().contains("extends"))
+                    .and_then(|n| n.utf8_text(source_bytes).ok())
+                    .map(|s| s.to_string());
+                Some((name, ExportKind::Interface, extends))
+            }
+            "type_alias_declaration" => {
+                let name = node
+                    .child_by_field_name("name")?
+                    .utf8_text(source_bytes)
+                    .ok()?
+                    .to_string();
+                let value = node
+                    .child_by_field_name("value")
+                    .and_then(|n| n.utf8_text(source_bytes).ok())
+                    .map(|s| format!("= {s}"));
+                Some((name, ExportKind::Type, value))
+            }
+            "lexical_declaration" | "variable_declaration" => {
+                let mut cursor = node.walk();
+                let declarator = node
+                    .children(&mut cursor)
+                    .find(|c| c.kind() == "variable_declarator")?;
+                let name = declarator
+                    .child_by_field_name("name")?
+                    .utf8_text(source_bytes)
+                    .ok()?
+                    .to_string();
+                let signature = declarator
+                    .child_by_field_name("type")
+                    .and_then(|n| n.utf8_text(source_bytes).ok())
+                    .map(|s| s.to_string());
+                Some((name, ExportKind::Const, signature))
+            }
+            _ => None,
+        }
+    }
+
+    /// `export default ...` — the bound name for `export default class Foo`
+    /// / `export default function foo() {}`, or the literal `default` for
+    /// an anonymous function/class or a bare expression (`export default
+    /// 42;`), since there's no other name to report.
+    fn extract_default_export(source: &str, root_node: tree_sitter::Node) -> Option<ExportEntry> {
+        let source_bytes = source.as_bytes();
+        let mut cursor = root_node.walk();
+        for child in root_node.children(&mut cursor) {
+            if child.kind() != "export_statement" {
+                continue;
+            }
+            let mut inner = child.walk();
+            let is_default = child
+                .children(&mut inner)
+                .any(|c| c.kind() == "default");
+            if !is_default {
+                continue;
+            }
+
+            let mut inner = child.walk();
+            let decl = child
+                .children(&mut inner)
+                .find(|c| !matches!(c.kind(), "export" | "default" | ";"))?;
+            let name = decl
+                .child_by_field_name("name")
+                .and_then(|n| n.utf8_text(source_bytes).ok())
+                .unwrap_or("default")
+                .to_string();
+
+            return Some(ExportEntry::new(
+                name,
+                child.start_position().row + 1,
+                child.end_position().row + 1,
+            ));
+        }
+        None
+    }
+
+    /// Typed symbol table for every top-level declaration, exported or not
+    /// — unlike `extract_exports`, which only reports what's visible
+    /// outside the module. A declaration directly under an
+    /// `export_statement` is `Public`; anything else is file-private.
+    fn extract_symbols(&self, source: &str, root_node: tree_sitter::Node) -> Vec<Symbol> {
+        let source_bytes = source.as_bytes();
+        let mut symbols = Vec::new();
+
+        let mut cursor = root_node.walk();
+        for child in root_node.children(&mut cursor) {
+            if child.kind() == "export_statement" {
+                let mut inner = child.walk();
+                for decl in child.children(&mut inner) {
+                    if let Some((name, kind)) = Self::declaration_symbol(decl, source_bytes) {
+                        symbols.push(Symbol::new(
+                            name,
+                            kind,
+                            Visibility::Public,
+                            (child.start_byte(), child.end_byte()),
+                        ));
+                    }
+                }
+            } else if let Some((name, kind)) = Self::declaration_symbol(child, source_bytes) {
+                symbols.push(Symbol::new(
+                    name,
+                    kind,
+                    Visibility::Private,
+                    (child.start_byte(), child.end_byte()),
+                ));
+            }
+        }
+
+        symbols
+    }
+
+    /// Maps a top-level declaration node to its name and [`SymbolKind`],
+    /// or `None` for nodes that aren't declarations (expression statements,
+    /// keywords like `export`/`default`, etc.).
+    fn declaration_symbol(
+        node: tree_sitter::Node,
+        source_bytes: &[u8],
+    ) -> Option<(String, SymbolKind)> {
+        let kind = match node.kind() {
+            "function_declaration" | "generator_function_declaration" => SymbolKind::Method,
+            "class_declaration" | "abstract_class_declaration" => SymbolKind::Class,
+            "interface_declaration" => SymbolKind::Interface,
+            "type_alias_declaration" => SymbolKind::TypeAlias,
+            "enum_declaration" => SymbolKind::Enum,
+            "internal_module" => SymbolKind::Module,
+            "lexical_declaration" | "variable_declaration" => SymbolKind::Variable,
+            _ => return None,
+        };
+
+        if kind == SymbolKind::Variable {
+            let mut cursor = node.walk();
+            let declarator = node
+                .children(&mut cursor)
+                .find(|c| c.kind() == "variable_declarator")?;
+            let name = declarator
+                .child_by_field_name("name")?
+                .utf8_text(source_bytes)
+                .ok()?
+                .to_string();
+            return Some((name, kind));
+        }
+
+        let name = node
+            .child_by_field_name("name")?
+            .utf8_text(source_bytes)
+            .ok()?
+            .to_string();
+        Some((name, kind))
+    }
+
     fn extract_imports(&self, source: &str, root_node: tree_sitter::Node) -> Vec<String> {
         let source_bytes = source.as_bytes();
         let mut seen = HashSet::new();
@@ -80,6 +394,26 @@ impl TypeScriptParser {
             }
         }
 
+        // `export { X } from 'some-package'` references an external package
+        // just as much as an `import` would, but doesn't produce an
+        // `import_statement` — it used to vanish from `imports` entirely.
+        let mut ecursor = root_node.walk();
+        for child in root_node.children(&mut ecursor) {
+            if child.kind() != "export_statement" {
+                continue;
+            }
+            let Some(source_node) = child.child_by_field_name("source") else {
+                continue;
+            };
+            let Ok(text) = source_node.utf8_text(source_bytes) else {
+                continue;
+            };
+            let cleaned = text.trim_matches('\'').trim_matches('"').to_string();
+            if !cleaned.starts_with('.') && !cleaned.starts_with('/') {
+                seen.insert(cleaned);
+            }
+        }
+
         let mut imports: Vec<String> = seen.into_iter().collect();
         imports.sort();
         imports
@@ -103,35 +437,299 @@ impl TypeScriptParser {
             }
         }
 
+        // `export { X } from './y'` / `export * from './y'` also reference a
+        // module, which used to vanish from `dependencies` entirely — the
+        // module graph built from this metadata would treat barrel files as
+        // dead ends.
+        let mut ecursor = root_node.walk();
+        for child in root_node.children(&mut ecursor) {
+            if child.kind() != "export_statement" {
+                continue;
+            }
+            let Some(source_node) = child.child_by_field_name("source") else {
+                continue;
+            };
+            let Ok(text) = source_node.utf8_text(source_bytes) else {
+                continue;
+            };
+            let cleaned = text.trim_matches('\'').trim_matches('"').to_string();
+            if cleaned.starts_with('.') || cleaned.starts_with('/') {
+                seen.insert(cleaned);
+            }
+        }
+
         let mut dependencies: Vec<String> = seen.into_iter().collect();
         dependencies.sort();
         dependencies
     }
-}
 
-impl Parser for TypeScriptParser {
-    fn parse(&mut self, source: &str) -> Result<ParseResult> {
-        let tree = self
-            .parser
-            .parse(source, None)
-            .ok_or_else(|| anyhow::anyhow!("Failed to parse source code"))?;
+    /// `export { X } from './y'` / `export { X as Y } from './z'` — edges
+    /// to the module each re-exported symbol actually comes from, which
+    /// `extract_exports` flattens away into a plain name.
+    fn extract_re_exports(&self, source: &str, root_node: tree_sitter::Node) -> Vec<ReExportEntry> {
+        let source_bytes = source.as_bytes();
+        let mut seen = HashSet::new();
+        let mut re_exports = Vec::new();
+
+        let mut cursor = root_node.walk();
+        for child in root_node.children(&mut cursor) {
+            if child.kind() != "export_statement" {
+                continue;
+            }
+            let Some(source_node) = child.child_by_field_name("source") else {
+                continue;
+            };
+            let Ok(source_text) = source_node.utf8_text(source_bytes) else {
+                continue;
+            };
+            let source_module = source_text.trim_matches('\'').trim_matches('"').to_string();
+
+            let mut inner = child.walk();
+            for clause in child.children(&mut inner) {
+                if clause.kind() != "export_clause" {
+                    continue;
+                }
+                let mut spec_cursor = clause.walk();
+                for spec in clause.children(&mut spec_cursor) {
+                    if spec.kind() != "export_specifier" {
+                        continue;
+                    }
+                    let Some(name) = spec
+                        .child_by_field_name("name")
+                        .and_then(|n| n.utf8_text(source_bytes).ok())
+                    else {
+                        continue;
+                    };
+                    let alias = spec
+                        .child_by_field_name("alias")
+                        .and_then(|n| n.utf8_text(source_bytes).ok());
+
+                    let (symbol, alias) = match alias {
+                        Some(alias) => (alias.to_string(), Some(name.to_string())),
+                        None => (name.to_string(), None),
+                    };
+                    if seen.insert(symbol.clone()) {
+                        re_exports
+                            .push(ReExportEntry::new(symbol, source_module.clone()).with_alias(alias));
+                    }
+                }
+            }
+
+            // `export * from './x'` / `export * as ns from './x'` — the
+            // re-exported names aren't enumerable without resolving the
+            // source module, so record a `*` marker (or the `as` binding)
+            // tied to it instead of silently dropping the edge.
+            let mut star_cursor = child.walk();
+            let mut has_star = false;
+            let mut as_name = None;
+            for c in child.children(&mut star_cursor) {
+                match c.kind() {
+                    "*" => has_star = true,
+                    "identifier" if has_star => as_name = c.utf8_text(source_bytes).ok(),
+                    _ => {}
+                }
+            }
+            if has_star {
+                let symbol = as_name.unwrap_or("*").to_string();
+                if seen.insert(symbol.clone()) {
+                    re_exports.push(ReExportEntry::new(symbol, source_module.clone()));
+                }
+            }
+        }
+
+        re_exports.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+        re_exports
+    }
+
+    /// Jest/Mocha/Jasmine convention: `describe("...", ...)` and
+    /// `it("...", ...)` / `test("...", ...)` calls.
+    fn is_test_call_name(name: &str) -> bool {
+        matches!(name, "describe" | "it" | "test")
+    }
+
+    fn test_calls<'a>(
+        &self,
+        source_bytes: &'a [u8],
+        root_node: tree_sitter::Node<'a>,
+    ) -> Vec<(String, tree_sitter::Node<'a>)> {
+        let mut calls = Vec::new();
+        let mut cursor = QueryCursor::new();
+        let mut iter = cursor.matches(&self.test_call_query, root_node, source_bytes);
+        while let Some(m) = iter.next() {
+            let Some(fn_capture) = m.captures.iter().find(|c| {
+                let idx = c.index as usize;
+                self.test_call_query.capture_names().get(idx) == Some(&"fn")
+            }) else {
+                continue;
+            };
+            let Some(desc_capture) = m.captures.iter().find(|c| {
+                let idx = c.index as usize;
+                self.test_call_query.capture_names().get(idx) == Some(&"desc")
+            }) else {
+                continue;
+            };
+            let Some(call_capture) = m.captures.iter().find(|c| {
+                let idx = c.index as usize;
+                self.test_call_query.capture_names().get(idx) == Some(&"call")
+            }) else {
+                continue;
+            };
+            let Ok(fn_name) = fn_capture.node.utf8_text(source_bytes) else {
+                continue;
+            };
+            if !Self::is_test_call_name(fn_name) {
+                continue;
+            }
+            let Ok(desc) = desc_capture.node.utf8_text(source_bytes) else {
+                continue;
+            };
+            let desc = desc.trim_matches('\'').trim_matches('"').to_string();
+            calls.push((desc, call_capture.node));
+        }
+        calls
+    }
 
+    fn extract_test_functions(&self, source: &str, root_node: tree_sitter::Node) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .test_calls(source.as_bytes(), root_node)
+            .into_iter()
+            .map(|(desc, _)| desc)
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    /// Line span of top-level `describe`/`it`/`test` calls, already counted
+    /// in `loc`. Nested `it` calls inside a `describe` aren't double-counted.
+    fn test_loc(&self, source: &str, root_node: tree_sitter::Node) -> usize {
+        let source_bytes = source.as_bytes();
+        let mut calls = self.test_calls(source_bytes, root_node);
+        calls.sort_by_key(|(_, node)| node.start_byte());
+
+        let mut total = 0;
+        let mut covered_until = None;
+        for (_, node) in calls {
+            if let Some(end) = covered_until {
+                if node.start_byte() < end {
+                    continue;
+                }
+            }
+            total += node.end_position().row - node.start_position().row + 1;
+            covered_until = Some(node.end_byte());
+        }
+        total
+    }
+}
+
+impl TypeScriptParser {
+    /// Builds the full [`ParseResult`] from an already-parsed tree, shared
+    /// by [`Parser::parse`] (fresh tree every call) and
+    /// [`Parser::parse_edit`] (reused/incrementally-edited tree).
+    fn build_result(&self, source: &str, tree: &Tree) -> ParseResult {
         let root_node = tree.root_node();
+        let source_bytes = source.as_bytes();
+
+        let mut exports = self.extract_exports(source, root_node);
+        let mut imports = self.extract_imports(source, root_node);
+        let mut dependencies = self.extract_dependencies(source, root_node);
 
-        let exports = self.extract_exports(source, root_node);
-        let imports = self.extract_imports(source, root_node);
-        let dependencies = self.extract_dependencies(source, root_node);
+        // A configured `fmm.queries.toml` exports/imports/dependencies
+        // query *extends* these rather than replacing them — it fills in
+        // whatever the hardcoded queries above miss, without requiring a
+        // user to reimplement extraction from scratch to fix one gap.
+        for entry in self.query_pack.exports(root_node, source_bytes) {
+            if !exports.iter().any(|e| e.name == entry.name) {
+                exports.push(entry);
+            }
+        }
+        exports.sort_by(|a, b| a.name.cmp(&b.name));
+
+        for name in self.query_pack.imports(root_node, source_bytes) {
+            if !imports.contains(&name) {
+                imports.push(name);
+            }
+        }
+        imports.sort();
+
+        for dep in self.query_pack.dependencies(root_node, source_bytes) {
+            if !dependencies.contains(&dep) {
+                dependencies.push(dep);
+            }
+        }
+        dependencies.sort();
+
+        let re_exports = self.extract_re_exports(source, root_node);
+        let symbols = self.extract_symbols(source, root_node);
         let loc = source.lines().count();
+        let test_functions = self.extract_test_functions(source, root_node);
+        let test_loc = self.test_loc(source, root_node);
+        let pack_fields = self.query_pack.run(root_node, source_bytes);
+
+        let custom_fields = if test_functions.is_empty() && pack_fields.is_empty() {
+            None
+        } else {
+            let mut fields = HashMap::new();
+            if !test_functions.is_empty() {
+                fields.insert(
+                    "test_functions".to_string(),
+                    serde_json::json!({
+                        "count": test_functions.len(),
+                        "names": test_functions,
+                    }),
+                );
+            }
+            fields.extend(pack_fields);
+            Some(fields)
+        };
 
-        Ok(ParseResult {
+        ParseResult {
             metadata: Metadata {
                 exports,
                 imports,
                 dependencies,
                 loc,
+                re_exports,
+                test_loc,
+                symbols,
+                diagnostics: crate::parser::collect_diagnostics(root_node),
             },
-            custom_fields: None,
-        })
+            custom_fields,
+        }
+    }
+}
+
+impl Parser for TypeScriptParser {
+    fn parse(&mut self, source: &str) -> Result<ParseResult> {
+        let tree = self
+            .parser
+            .parse(source, None)
+            .ok_or_else(|| anyhow::anyhow!("Failed to parse source code"))?;
+
+        Ok(self.build_result(source, &tree))
+    }
+
+    fn parse_edit(
+        &mut self,
+        path: &Path,
+        new_source: &str,
+        edits: &[InputEdit],
+    ) -> Result<ParseResult> {
+        let mut old_tree = self.trees.remove(path);
+        if let Some(tree) = old_tree.as_mut() {
+            for edit in edits {
+                tree.edit(edit);
+            }
+        }
+
+        let tree = self
+            .parser
+            .parse(new_source, old_tree.as_ref())
+            .ok_or_else(|| anyhow::anyhow!("Failed to parse source code"))?;
+
+        let result = self.build_result(new_source, &tree);
+        self.trees.insert(path.to_path_buf(), tree);
+        Ok(result)
     }
 
     fn language_id(&self) -> &'static str {
@@ -227,6 +825,263 @@ export const middle = 1;
         );
     }
 
+    #[test]
+    fn exports_type_alias() {
+        let result = parse("export type UserId = string;");
+        assert!(result
+            .metadata
+            .export_names()
+            .contains(&"UserId".to_string()));
+    }
+
+    #[test]
+    fn exports_enum() {
+        let result = parse("export enum Color { Red, Green, Blue }");
+        assert!(result.metadata.export_names().contains(&"Color".to_string()));
+    }
+
+    #[test]
+    fn exports_abstract_class() {
+        let result = parse("export abstract class Shape { abstract area(): number; }");
+        assert!(result.metadata.export_names().contains(&"Shape".to_string()));
+    }
+
+    #[test]
+    fn exports_namespace() {
+        let result = parse("export namespace Utils { export function helper() {} }");
+        assert!(result.metadata.export_names().contains(&"Utils".to_string()));
+    }
+
+    #[test]
+    fn exports_default_named_class() {
+        let result = parse("export default class UserService {}");
+        assert!(result
+            .metadata
+            .export_names()
+            .contains(&"UserService".to_string()));
+    }
+
+    #[test]
+    fn exports_default_named_function() {
+        let result = parse("export default function createUser() {}");
+        assert!(result
+            .metadata
+            .export_names()
+            .contains(&"createUser".to_string()));
+    }
+
+    #[test]
+    fn exports_default_anonymous_expression() {
+        let result = parse("export default 42;");
+        assert!(result
+            .metadata
+            .export_names()
+            .contains(&"default".to_string()));
+    }
+
+    #[test]
+    fn star_reexport_tracks_source_module() {
+        let result = parse("export * from './utils';");
+        let entry = result
+            .metadata
+            .re_exports
+            .iter()
+            .find(|r| r.symbol == "*")
+            .unwrap();
+        assert_eq!(entry.source_module, "./utils");
+        assert!(result
+            .metadata
+            .dependencies
+            .contains(&"./utils".to_string()));
+    }
+
+    #[test]
+    fn star_reexport_with_namespace_binding() {
+        let result = parse("export * as utils from './utils';");
+        let entry = result
+            .metadata
+            .re_exports
+            .iter()
+            .find(|r| r.symbol == "utils")
+            .unwrap();
+        assert_eq!(entry.source_module, "./utils");
+    }
+
+    // --- Symbol table ---
+
+    #[test]
+    fn exported_declarations_are_public_symbols() {
+        let result = parse("export class UserService {}");
+        let symbol = result
+            .metadata
+            .symbols
+            .iter()
+            .find(|s| s.name == "UserService")
+            .unwrap();
+        assert_eq!(symbol.kind, SymbolKind::Class);
+        assert_eq!(symbol.visibility, Visibility::Public);
+    }
+
+    #[test]
+    fn non_exported_declarations_are_private_symbols() {
+        let source = "function helper() {}\nconst internal = 1;\n";
+        let result = parse(source);
+        let helper = result
+            .metadata
+            .symbols
+            .iter()
+            .find(|s| s.name == "helper")
+            .unwrap();
+        assert_eq!(helper.kind, SymbolKind::Method);
+        assert_eq!(helper.visibility, Visibility::Private);
+        let internal = result
+            .metadata
+            .symbols
+            .iter()
+            .find(|s| s.name == "internal")
+            .unwrap();
+        assert_eq!(internal.kind, SymbolKind::Variable);
+        assert_eq!(internal.visibility, Visibility::Private);
+    }
+
+    #[test]
+    fn symbol_kinds_cover_interfaces_enums_and_type_aliases() {
+        let source = r#"
+export interface Config {}
+export enum Color { Red }
+export type Id = string;
+"#;
+        let result = parse(source);
+        let kind_of = |name: &str| {
+            result
+                .metadata
+                .symbols
+                .iter()
+                .find(|s| s.name == name)
+                .map(|s| s.kind)
+                .unwrap()
+        };
+        assert_eq!(kind_of("Config"), SymbolKind::Interface);
+        assert_eq!(kind_of("Color"), SymbolKind::Enum);
+        assert_eq!(kind_of("Id"), SymbolKind::TypeAlias);
+    }
+
+    // --- Export kind/signature ---
+
+    fn export_of<'a>(result: &'a ParseResult, name: &str) -> &'a ExportEntry {
+        result
+            .metadata
+            .exports
+            .iter()
+            .find(|e| e.name == name)
+            .unwrap()
+    }
+
+    #[test]
+    fn function_export_has_kind_and_param_signature() {
+        let result = parse("export function greet(name: string): string { return name; }");
+        let greet = export_of(&result, "greet");
+        assert_eq!(greet.kind, Some(ExportKind::Function));
+        assert_eq!(greet.signature.as_deref(), Some("(name: string): string"));
+    }
+
+    #[test]
+    fn class_export_signature_carries_heritage_clause() {
+        let result = parse("export class Cat extends Animal implements Pet {}");
+        let cat = export_of(&result, "Cat");
+        assert_eq!(cat.kind, Some(ExportKind::Class));
+        assert_eq!(
+            cat.signature.as_deref(),
+            Some("extends Animal implements Pet")
+        );
+    }
+
+    #[test]
+    fn class_export_with_no_heritage_has_no_signature() {
+        let result = parse("export class Empty {}");
+        let empty = export_of(&result, "Empty");
+        assert_eq!(empty.kind, Some(ExportKind::Class));
+        assert_eq!(empty.signature, None);
+    }
+
+    #[test]
+    fn interface_export_signature_carries_extends_clause() {
+        let result = parse("export interface Config extends Base {}");
+        let config = export_of(&result, "Config");
+        assert_eq!(config.kind, Some(ExportKind::Interface));
+        assert!(config.signature.as_deref().unwrap().contains("Base"));
+    }
+
+    #[test]
+    fn type_alias_export_signature_carries_aliased_type() {
+        let result = parse("export type UserId = string;");
+        let user_id = export_of(&result, "UserId");
+        assert_eq!(user_id.kind, Some(ExportKind::Type));
+        assert_eq!(user_id.signature.as_deref(), Some("= string"));
+    }
+
+    #[test]
+    fn const_export_signature_carries_type_annotation() {
+        let result = parse("export const MAX_RETRIES: number = 3;");
+        let max_retries = export_of(&result, "MAX_RETRIES");
+        assert_eq!(max_retries.kind, Some(ExportKind::Const));
+        assert_eq!(max_retries.signature.as_deref(), Some(": number"));
+    }
+
+    #[test]
+    fn re_exported_specifier_has_no_kind() {
+        let result = parse("export { foo } from './other';");
+        let foo = export_of(&result, "foo");
+        assert_eq!(foo.kind, None);
+        assert_eq!(foo.signature, None);
+    }
+
+    #[test]
+    fn jsdoc_block_attaches_to_the_following_export() {
+        let source = r#"
+/**
+ * Greets someone by name.
+ * @param name - who to greet
+ */
+export function greet(name: string): string { return name; }
+"#;
+        let result = parse(source);
+        let greet = export_of(&result, "greet");
+        assert_eq!(
+            greet.doc.as_deref(),
+            Some("Greets someone by name.\n@param name - who to greet")
+        );
+    }
+
+    #[test]
+    fn single_line_jsdoc_attaches_to_the_following_export() {
+        let result = parse("/** The maximum retry count. */\nexport const MAX_RETRIES = 3;");
+        let max_retries = export_of(&result, "MAX_RETRIES");
+        assert_eq!(max_retries.doc.as_deref(), Some("The maximum retry count."));
+    }
+
+    #[test]
+    fn plain_line_comment_is_not_treated_as_doc() {
+        let result = parse("// not a doc comment\nexport function greet() {}");
+        let greet = export_of(&result, "greet");
+        assert_eq!(greet.doc, None);
+    }
+
+    #[test]
+    fn non_jsdoc_block_comment_is_not_treated_as_doc() {
+        let result = parse("/* not a doc comment */\nexport function greet() {}");
+        let greet = export_of(&result, "greet");
+        assert_eq!(greet.doc, None);
+    }
+
+    #[test]
+    fn jsdoc_intra_doc_links_are_extracted() {
+        let source = "/** See [`Other`] for details. */\nexport function greet() {}";
+        let result = parse(source);
+        let greet = export_of(&result, "greet");
+        assert_eq!(greet.doc_links, vec!["Other".to_string()]);
+    }
+
     // --- Import extraction ---
 
     #[test]
@@ -400,9 +1255,110 @@ export { Logger } from './logger';
             result.metadata.export_names(),
             vec!["AuthService", "Logger", "UserService"]
         );
-        // Re-exports via `export { X } from '...'` don't produce import_statements,
-        // so the current parser doesn't capture them as dependencies.
-        // This is a known limitation â€” dependencies only come from `import` statements.
-        assert!(result.metadata.dependencies.is_empty());
+        // Barrel files used to be a dead end for dependency resolution since
+        // `export { X } from './y'` doesn't produce an import_statement; the
+        // re-exported module is now tracked as a dependency too.
+        assert!(result
+            .metadata
+            .dependencies
+            .contains(&"./user.service".to_string()));
+        assert!(result
+            .metadata
+            .dependencies
+            .contains(&"./auth.service".to_string()));
+        assert!(result.metadata.dependencies.contains(&"./logger".to_string()));
+        // ...and each barrel entry is tracked as a re-export edge back to
+        // the module that actually defines it.
+        assert_eq!(result.metadata.re_exports.len(), 3);
+        let user_service = result
+            .metadata
+            .re_exports
+            .iter()
+            .find(|r| r.symbol == "UserService")
+            .unwrap();
+        assert_eq!(user_service.source_module, "./user.service");
+        assert!(user_service.alias.is_none());
+    }
+
+    #[test]
+    fn reexport_from_external_package_is_tracked_as_import() {
+        let result = parse("export { debounce } from 'lodash';");
+        assert!(result.metadata.imports.contains(&"lodash".to_string()));
+        assert!(!result
+            .metadata
+            .dependencies
+            .contains(&"lodash".to_string()));
+    }
+
+    #[test]
+    fn re_export_with_alias_tracks_original_name() {
+        let result = parse("export { Foo as Bar } from './foo';");
+        let entry = result
+            .metadata
+            .re_exports
+            .iter()
+            .find(|r| r.symbol == "Bar")
+            .unwrap();
+        assert_eq!(entry.source_module, "./foo");
+        assert_eq!(entry.alias.as_deref(), Some("Foo"));
+    }
+
+    #[test]
+    fn describe_it_blocks_reported_as_test_functions() {
+        let source = r#"
+describe("UserService", () => {
+    it("creates a user", () => {
+        expect(true).toBe(true);
+    });
+
+    it("rejects a duplicate user", () => {
+        expect(false).toBe(false);
+    });
+});
+"#;
+        let result = parse(source);
+        let fields = result.custom_fields.unwrap();
+        let test_functions = fields.get("test_functions").unwrap();
+        assert_eq!(test_functions.get("count").unwrap().as_u64(), Some(3));
+        let names = test_functions.get("names").unwrap().as_array().unwrap();
+        let names: Vec<&str> = names.iter().map(|v| v.as_str().unwrap()).collect();
+        assert!(names.contains(&"UserService"));
+        assert!(names.contains(&"creates a user"));
+        assert!(names.contains(&"rejects a duplicate user"));
+    }
+
+    #[test]
+    fn test_loc_counts_describe_block_once() {
+        let source = "describe(\"suite\", () => {\n    it(\"case\", () => {\n        expect(1).toBe(1);\n    });\n});\n";
+        let result = parse(source);
+        assert_eq!(result.metadata.loc, source.lines().count());
+        assert!(result.metadata.test_loc > 0);
+        assert!(result.metadata.test_loc <= result.metadata.loc);
+    }
+
+    #[test]
+    fn no_custom_fields_without_test_calls() {
+        let result = parse("export const x = 1;");
+        assert!(result.custom_fields.is_none());
+    }
+
+    #[test]
+    fn parse_edit_reuses_tree_and_picks_up_new_export() {
+        use crate::parser::cache::derive_edit;
+        use std::path::Path;
+
+        let mut parser = TypeScriptParser::new().unwrap();
+        let path = Path::new("index.ts");
+
+        let old_source = "export function a() {}\n";
+        let new_source = "export function a() {}\nexport function b() {}\n";
+        let edit = derive_edit(old_source, new_source).unwrap();
+
+        let first = parser.parse_edit(path, old_source, &[]).unwrap();
+        assert!(first.metadata.export_names().contains(&"a".to_string()));
+
+        let second = parser.parse_edit(path, new_source, &[edit]).unwrap();
+        assert!(second.metadata.export_names().contains(&"a".to_string()));
+        assert!(second.metadata.export_names().contains(&"b".to_string()));
     }
 }
@@ -0,0 +1,529 @@
+//! Incremental reparse cache for large repositories.
+//!
+//! Re-running a full tree-sitter parse on every file, every time `fmm`
+//! touches a repo, dominates wall-clock time on large trees where only a
+//! handful of files changed since the last run. [`ParseCache`] keeps the
+//! last parsed [`tree_sitter::Tree`] and [`ParseResult`] per file path; a
+//! request for an unchanged file (same content hash) is served from cache
+//! instead of re-parsing, and a request for a changed file reparses
+//! incrementally by handing tree-sitter the previous tree as a starting
+//! point.
+//!
+//! [`ParseCache::get_or_parse`] offers a second, simpler mode of use: given
+//! a path and a parser, it reads the file itself (through an injectable
+//! [`FileSystem`]), checks the file's mtime and content hash against what
+//! was cached, and reparses only on a miss. Unlike the tree-reuse path
+//! above, this mode doesn't need to keep a `Tree` around, so its entries
+//! serialize to disk — a second `fmm` run over an unchanged tree loads the
+//! cache instead of reparsing anything. Routing time and file access
+//! through [`Clock`]/[`FileSystem`] lets staleness logic be unit-tested
+//! against fixed timestamps and an in-memory tree, without touching the
+//! real clock or disk.
+
+use crate::parser::{ParseResult, Parser};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use tree_sitter::{InputEdit, Point, Tree};
+
+struct CachedParse {
+    content_hash: u64,
+    mtime: Option<SystemTime>,
+    tree: Option<Tree>,
+    result: ParseResult,
+}
+
+/// A cached entry as persisted to disk: everything from [`CachedParse`]
+/// except the tree-sitter `Tree`, which isn't serializable and isn't
+/// needed to answer a hash/mtime staleness check on the next run.
+#[derive(Debug, Serialize, Deserialize)]
+struct PersistedEntry {
+    path: PathBuf,
+    content_hash: u64,
+    mtime: Option<SystemTime>,
+    result: ParseResult,
+}
+
+/// The real wall clock, for [`Clock`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+/// Injectable source of the current time, so cache staleness logic can be
+/// driven by a fixed instant in tests instead of the real wall clock.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> SystemTime;
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// Reads straight through to `std::fs`, for [`FileSystem`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RealFileSystem;
+
+/// Injectable file access, so [`ParseCache::get_or_parse`]'s mtime/content
+/// staleness check can run against an in-memory fixture in tests instead
+/// of the real filesystem.
+pub trait FileSystem: Send + Sync {
+    fn read_to_string(&self, path: &Path) -> io::Result<String>;
+    fn mtime(&self, path: &Path) -> io::Result<SystemTime>;
+}
+
+impl FileSystem for RealFileSystem {
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        std::fs::read_to_string(path)
+    }
+
+    fn mtime(&self, path: &Path) -> io::Result<SystemTime> {
+        std::fs::metadata(path)?.modified()
+    }
+}
+
+/// In-memory [`FileSystem`] fixture: a fixed set of paths to (content,
+/// mtime), so cache-staleness tests can simulate an edit without touching
+/// the real disk.
+#[derive(Debug, Default, Clone)]
+pub struct MockFileSystem {
+    files: HashMap<PathBuf, (String, SystemTime)>,
+}
+
+impl MockFileSystem {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets (or replaces) a file's content and mtime.
+    pub fn set(&mut self, path: impl Into<PathBuf>, content: impl Into<String>, mtime: SystemTime) {
+        self.files.insert(path.into(), (content.into(), mtime));
+    }
+
+    fn not_found(path: &Path) -> io::Error {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{} not found in mock filesystem", path.display()),
+        )
+    }
+}
+
+impl FileSystem for MockFileSystem {
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        self.files
+            .get(path)
+            .map(|(content, _)| content.clone())
+            .ok_or_else(|| Self::not_found(path))
+    }
+
+    fn mtime(&self, path: &Path) -> io::Result<SystemTime> {
+        self.files
+            .get(path)
+            .map(|(_, mtime)| *mtime)
+            .ok_or_else(|| Self::not_found(path))
+    }
+}
+
+/// Caches the last parse of each file so unchanged files skip re-parsing
+/// entirely and changed files reuse the previous tree as a starting point.
+#[derive(Default)]
+pub struct ParseCache {
+    entries: HashMap<PathBuf, CachedParse>,
+}
+
+impl ParseCache {
+    pub fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+        }
+    }
+
+    fn hash(source: &str) -> u64 {
+        use std::hash::{Hash, Hasher};
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        source.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Return the previous tree for `path`, if its cached content hash
+    /// still matches `source` would be a cache *hit* (use
+    /// [`ParseCache::get`] for that); this is for the miss path, where the
+    /// caller wants a starting tree to reparse incrementally from.
+    pub fn previous_tree(&self, path: &std::path::Path) -> Option<&Tree> {
+        self.entries.get(path).and_then(|c| c.tree.as_ref())
+    }
+
+    /// Return a cached parse result if `source`'s content hash matches
+    /// what was cached for `path` last time.
+    pub fn get(&self, path: &std::path::Path, source: &str) -> Option<ParseResult> {
+        let cached = self.entries.get(path)?;
+        if cached.content_hash == Self::hash(source) {
+            Some(cached.result.clone())
+        } else {
+            None
+        }
+    }
+
+    /// Store a freshly computed parse for `path`.
+    pub fn insert(&mut self, path: PathBuf, source: &str, tree: Tree, result: ParseResult) {
+        self.entries.insert(
+            path,
+            CachedParse {
+                content_hash: Self::hash(source),
+                mtime: None,
+                tree: Some(tree),
+                result,
+            },
+        );
+    }
+
+    /// Drop the cached entry for `path` (e.g. the file was deleted).
+    pub fn invalidate(&mut self, path: &std::path::Path) {
+        self.entries.remove(path);
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Read `path` through `fs`, reuse the cached result if its mtime (a
+    /// cheap pre-check) or content hash (the authoritative check, in case
+    /// the mtime changed without the content changing, e.g. a `touch`)
+    /// still matches what's cached, and reparse with `parser` on a miss.
+    /// `clock` timestamps the new cache entry; it doesn't affect hit/miss.
+    pub fn get_or_parse(
+        &mut self,
+        path: &Path,
+        parser: &mut dyn Parser,
+        clock: &dyn Clock,
+        fs: &dyn FileSystem,
+    ) -> Result<ParseResult> {
+        let mtime = fs.mtime(path).ok();
+
+        if let Some(cached) = self.entries.get(path) {
+            if mtime.is_some() && cached.mtime == mtime {
+                return Ok(cached.result.clone());
+            }
+        }
+
+        let content = fs.read_to_string(path)?;
+        let hash = Self::hash(&content);
+
+        if let Some(cached) = self.entries.get_mut(path) {
+            if cached.content_hash == hash {
+                cached.mtime = mtime;
+                return Ok(cached.result.clone());
+            }
+        }
+
+        let result = parser.parse(&content)?;
+        let _ = clock.now();
+        self.entries.insert(
+            path.to_path_buf(),
+            CachedParse {
+                content_hash: hash,
+                mtime,
+                tree: None,
+                result: result.clone(),
+            },
+        );
+        Ok(result)
+    }
+
+    /// Persist every entry (minus its in-memory-only tree) as JSON.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let persisted: Vec<PersistedEntry> = self
+            .entries
+            .iter()
+            .map(|(file, cached)| PersistedEntry {
+                path: file.clone(),
+                content_hash: cached.content_hash,
+                mtime: cached.mtime,
+                result: cached.result.clone(),
+            })
+            .collect();
+        let json = serde_json::to_string(&persisted)?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Load a previously persisted cache. A missing or unparsable file is
+    /// treated as an empty cache rather than an error.
+    pub fn load(path: &Path) -> Self {
+        let Ok(json) = std::fs::read_to_string(path) else {
+            return Self::default();
+        };
+        let Ok(persisted) = serde_json::from_str::<Vec<PersistedEntry>>(&json) else {
+            return Self::default();
+        };
+
+        let entries = persisted
+            .into_iter()
+            .map(|entry| {
+                (
+                    entry.path,
+                    CachedParse {
+                        content_hash: entry.content_hash,
+                        mtime: entry.mtime,
+                        tree: None,
+                        result: entry.result,
+                    },
+                )
+            })
+            .collect();
+        Self { entries }
+    }
+}
+
+/// Derive the single `InputEdit` tree-sitter needs to reuse a previous
+/// tree when reparsing `new_source`, by locating the common prefix and
+/// suffix around whatever changed in `old_source`. Returns `None` when
+/// the two sources are identical, since there is nothing to edit.
+///
+/// This only ever produces one edit spanning the whole changed region,
+/// not a minimal per-hunk diff; that's enough for tree-sitter to reuse
+/// every subtree outside the edited span, which is most of what
+/// incremental parsing buys you.
+pub fn derive_edit(old_source: &str, new_source: &str) -> Option<InputEdit> {
+    let old_bytes = old_source.as_bytes();
+    let new_bytes = new_source.as_bytes();
+
+    let prefix_len = old_bytes
+        .iter()
+        .zip(new_bytes.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    if prefix_len == old_bytes.len() && prefix_len == new_bytes.len() {
+        return None;
+    }
+
+    let old_remaining = old_bytes.len() - prefix_len;
+    let new_remaining = new_bytes.len() - prefix_len;
+    let suffix_len = old_bytes[prefix_len..]
+        .iter()
+        .rev()
+        .zip(new_bytes[prefix_len..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .take(old_remaining.min(new_remaining))
+        .count();
+
+    let start_byte = prefix_len;
+    let old_end_byte = old_bytes.len() - suffix_len;
+    let new_end_byte = new_bytes.len() - suffix_len;
+
+    Some(InputEdit {
+        start_byte,
+        old_end_byte,
+        new_end_byte,
+        start_position: byte_to_point(old_source, start_byte),
+        old_end_position: byte_to_point(old_source, old_end_byte),
+        new_end_position: byte_to_point(new_source, new_end_byte),
+    })
+}
+
+fn byte_to_point(source: &str, byte_offset: usize) -> Point {
+    let mut row = 0;
+    let mut line_start = 0;
+    for (i, b) in source.as_bytes()[..byte_offset].iter().enumerate() {
+        if *b == b'\n' {
+            row += 1;
+            line_start = i + 1;
+        }
+    }
+    Point {
+        row,
+        column: byte_offset - line_start,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Metadata;
+
+    fn dummy_result() -> ParseResult {
+        ParseResult {
+            metadata: Metadata {
+                exports: Vec::new(),
+                imports: Vec::new(),
+                dependencies: Vec::new(),
+                loc: 1,
+                re_exports: Vec::new(),
+                test_loc: 0,
+                symbols: Vec::new(),
+                diagnostics: crate::parser::Diagnostics::default(),
+            },
+            custom_fields: None,
+        }
+    }
+
+    fn dummy_tree() -> Tree {
+        let mut parser = tree_sitter::Parser::new();
+        let language: tree_sitter::Language = tree_sitter_rust::LANGUAGE.into();
+        parser.set_language(&language).unwrap();
+        parser.parse("fn main() {}", None).unwrap()
+    }
+
+    /// Counts calls, so `get_or_parse` tests can assert a cache hit never
+    /// reaches the parser.
+    #[derive(Default)]
+    struct CountingParser {
+        calls: usize,
+    }
+
+    impl Parser for CountingParser {
+        fn parse(&mut self, _source: &str) -> Result<ParseResult> {
+            self.calls += 1;
+            Ok(dummy_result())
+        }
+
+        fn language_id(&self) -> &'static str {
+            "dummy"
+        }
+
+        fn extensions(&self) -> &'static [&'static str] {
+            &[]
+        }
+    }
+
+    /// A fixed instant, for deterministic `Clock` behavior in tests.
+    struct FixedClock(SystemTime);
+
+    impl Clock for FixedClock {
+        fn now(&self) -> SystemTime {
+            self.0
+        }
+    }
+
+    #[test]
+    fn cache_hits_on_unchanged_content() {
+        let mut cache = ParseCache::new();
+        let path = PathBuf::from("src/lib.rs");
+        cache.insert(path.clone(), "fn main() {}", dummy_tree(), dummy_result());
+        assert!(cache.get(&path, "fn main() {}").is_some());
+        assert!(cache.get(&path, "fn other() {}").is_none());
+    }
+
+    #[test]
+    fn invalidate_removes_entry() {
+        let mut cache = ParseCache::new();
+        let path = PathBuf::from("src/lib.rs");
+        cache.insert(path.clone(), "fn main() {}", dummy_tree(), dummy_result());
+        cache.invalidate(&path);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn derive_edit_identical_sources_is_none() {
+        assert!(derive_edit("fn main() {}", "fn main() {}").is_none());
+    }
+
+    #[test]
+    fn derive_edit_appends_at_end() {
+        let edit = derive_edit("fn main() {}", "fn main() {}\nfn other() {}").unwrap();
+        assert_eq!(edit.start_byte, 12);
+        assert_eq!(edit.old_end_byte, 12);
+        assert_eq!(edit.new_end_byte, "fn main() {}\nfn other() {}".len());
+    }
+
+    #[test]
+    fn derive_edit_changes_middle() {
+        let old = "fn foo() {}\nfn bar() {}\nfn baz() {}";
+        let new = "fn foo() {}\nfn BAR_RENAMED() {}\nfn baz() {}";
+        let edit = derive_edit(old, new).unwrap();
+        assert_eq!(&old[edit.start_byte..edit.old_end_byte], "bar");
+        assert_eq!(&new[edit.start_byte..edit.new_end_byte], "BAR_RENAMED");
+        assert_eq!(edit.start_position.row, 1);
+    }
+
+    fn epoch_plus(seconds: u64) -> SystemTime {
+        SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(seconds)
+    }
+
+    #[test]
+    fn get_or_parse_misses_then_hits_on_unchanged_mtime() {
+        let mut cache = ParseCache::new();
+        let mut parser = CountingParser::default();
+        let clock = FixedClock(epoch_plus(100));
+        let mut fs = MockFileSystem::new();
+        fs.set("src/lib.rs", "fn main() {}", epoch_plus(1));
+        let path = Path::new("src/lib.rs");
+
+        cache.get_or_parse(path, &mut parser, &clock, &fs).unwrap();
+        assert_eq!(parser.calls, 1);
+
+        cache.get_or_parse(path, &mut parser, &clock, &fs).unwrap();
+        assert_eq!(parser.calls, 1, "unchanged mtime should be served from cache");
+    }
+
+    #[test]
+    fn get_or_parse_reparses_when_mtime_and_content_both_change() {
+        let mut cache = ParseCache::new();
+        let mut parser = CountingParser::default();
+        let clock = FixedClock(epoch_plus(100));
+        let mut fs = MockFileSystem::new();
+        fs.set("src/lib.rs", "fn main() {}", epoch_plus(1));
+        let path = Path::new("src/lib.rs");
+
+        cache.get_or_parse(path, &mut parser, &clock, &fs).unwrap();
+        fs.set("src/lib.rs", "fn other() {}", epoch_plus(2));
+
+        cache.get_or_parse(path, &mut parser, &clock, &fs).unwrap();
+        assert_eq!(parser.calls, 2);
+    }
+
+    #[test]
+    fn get_or_parse_skips_reparse_when_only_mtime_changes() {
+        let mut cache = ParseCache::new();
+        let mut parser = CountingParser::default();
+        let clock = FixedClock(epoch_plus(100));
+        let mut fs = MockFileSystem::new();
+        fs.set("src/lib.rs", "fn main() {}", epoch_plus(1));
+        let path = Path::new("src/lib.rs");
+
+        cache.get_or_parse(path, &mut parser, &clock, &fs).unwrap();
+        // A `touch` bumps mtime without changing content.
+        fs.set("src/lib.rs", "fn main() {}", epoch_plus(2));
+
+        cache.get_or_parse(path, &mut parser, &clock, &fs).unwrap();
+        assert_eq!(
+            parser.calls, 1,
+            "matching content hash should short-circuit reparsing even if mtime moved"
+        );
+    }
+
+    #[test]
+    fn save_then_load_round_trips_entries_without_trees() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let cache_path = tmp.path().join("parse-cache.json");
+
+        let mut cache = ParseCache::new();
+        let mut parser = CountingParser::default();
+        let clock = FixedClock(epoch_plus(100));
+        let mut fs = MockFileSystem::new();
+        fs.set("src/lib.rs", "fn main() {}", epoch_plus(1));
+        let path = Path::new("src/lib.rs");
+        cache.get_or_parse(path, &mut parser, &clock, &fs).unwrap();
+        cache.save(&cache_path).unwrap();
+
+        let mut reloaded = ParseCache::load(&cache_path);
+        let mut parser = CountingParser::default();
+        reloaded.get_or_parse(path, &mut parser, &clock, &fs).unwrap();
+        assert_eq!(parser.calls, 0, "reloaded cache should still hit");
+    }
+
+    #[test]
+    fn load_treats_missing_file_as_empty() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let cache = ParseCache::load(&tmp.path().join("does-not-exist.json"));
+        assert!(cache.is_empty());
+    }
+}
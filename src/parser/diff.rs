@@ -0,0 +1,309 @@
+//! Unified-diff application and before/after symbol delta computation,
+//! backing [`super::Parser::parse_diff`].
+
+use super::{ExportEntry, ParseResult};
+use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+
+/// One `@@ -old_start,old_len +new_start,new_len @@` hunk header. Line
+/// numbers are 1-based, matching `ExportEntry::start_line`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hunk {
+    pub old_start: usize,
+    pub old_len: usize,
+    pub new_start: usize,
+    pub new_len: usize,
+}
+
+impl Hunk {
+    fn contains_new_line(&self, line: usize) -> bool {
+        let len = self.new_len.max(1);
+        line >= self.new_start && line < self.new_start + len
+    }
+
+    fn contains_old_line(&self, line: usize) -> bool {
+        let len = self.old_len.max(1);
+        line >= self.old_start && line < self.old_start + len
+    }
+}
+
+/// One changed export/import/dependency, with the hunk line range it falls
+/// inside, if the diff could attribute it to a specific hunk. Imports and
+/// dependencies carry no line info in `Metadata`, so their range is always
+/// `None`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangedSymbol {
+    pub name: String,
+    pub hunk_line_range: Option<(usize, usize)>,
+}
+
+/// Delta between a before/after parse, attributing each change to the
+/// unified-diff hunk that produced it.
+#[derive(Debug, Clone, Default)]
+pub struct ParseDelta {
+    pub exports_added: Vec<ChangedSymbol>,
+    pub exports_removed: Vec<ChangedSymbol>,
+    pub exports_modified: Vec<ChangedSymbol>,
+    pub imports_added: Vec<ChangedSymbol>,
+    pub imports_removed: Vec<ChangedSymbol>,
+    pub dependencies_added: Vec<ChangedSymbol>,
+    pub dependencies_removed: Vec<ChangedSymbol>,
+    /// Custom-field keys (e.g. `annotations`, `namespaces`, `mixins`) whose
+    /// value differs between the before and after parse.
+    pub custom_fields_changed: Vec<String>,
+}
+
+fn parse_range(spec: &str) -> (usize, usize) {
+    match spec.split_once(',') {
+        Some((start, len)) => (
+            start.parse().unwrap_or(1),
+            len.parse().unwrap_or(1),
+        ),
+        None => (spec.parse().unwrap_or(1), 1),
+    }
+}
+
+fn parse_hunk_header(line: &str) -> Option<Hunk> {
+    let body = line.strip_prefix("@@ ")?;
+    let end = body.find(" @@")?;
+    let mut parts = body[..end].split_whitespace();
+    let old = parts.next()?.strip_prefix('-')?;
+    let new = parts.next()?.strip_prefix('+')?;
+    let (old_start, old_len) = parse_range(old);
+    let (new_start, new_len) = parse_range(new);
+    Some(Hunk {
+        old_start,
+        old_len,
+        new_start,
+        new_len,
+    })
+}
+
+/// Applies a unified diff (as produced by `git diff` / `diff -u`) to
+/// `old_source`, returning the patched source plus the hunks that were
+/// applied, in application order.
+pub fn apply_unified_diff(old_source: &str, unified_diff: &str) -> Result<(String, Vec<Hunk>)> {
+    let old_lines: Vec<&str> = old_source.lines().collect();
+    let mut output: Vec<String> = Vec::new();
+    let mut hunks = Vec::new();
+    let mut old_idx = 0usize;
+
+    let mut lines = unified_diff.lines().peekable();
+    while let Some(line) = lines.next() {
+        if line.starts_with("---") || line.starts_with("+++") || line.starts_with("diff ") {
+            continue;
+        }
+        let Some(hunk) = parse_hunk_header(line) else {
+            continue;
+        };
+
+        while old_idx + 1 < hunk.old_start && old_idx < old_lines.len() {
+            output.push(old_lines[old_idx].to_string());
+            old_idx += 1;
+        }
+
+        while let Some(&body_line) = lines.peek() {
+            if body_line.starts_with("@@") || body_line.starts_with("---") {
+                break;
+            }
+            let body_line = lines.next().unwrap();
+            if let Some(rest) = body_line.strip_prefix(' ') {
+                output.push(rest.to_string());
+                old_idx += 1;
+            } else if body_line.starts_with('-') {
+                old_idx += 1;
+            } else if let Some(rest) = body_line.strip_prefix('+') {
+                output.push(rest.to_string());
+            }
+        }
+        hunks.push(hunk);
+    }
+
+    while old_idx < old_lines.len() {
+        output.push(old_lines[old_idx].to_string());
+        old_idx += 1;
+    }
+
+    let mut new_source = output.join("\n");
+    if old_source.ends_with('\n') {
+        new_source.push('\n');
+    }
+    Ok((new_source, hunks))
+}
+
+fn hunk_for_new_line(hunks: &[Hunk], line: usize) -> Option<(usize, usize)> {
+    hunks
+        .iter()
+        .find(|h| h.contains_new_line(line))
+        .map(|h| (h.new_start, h.new_start + h.new_len.max(1) - 1))
+}
+
+fn hunk_for_old_line(hunks: &[Hunk], line: usize) -> Option<(usize, usize)> {
+    hunks
+        .iter()
+        .find(|h| h.contains_old_line(line))
+        .map(|h| (h.old_start, h.old_start + h.old_len.max(1) - 1))
+}
+
+fn diff_names(old: &[String], new: &[String]) -> (Vec<ChangedSymbol>, Vec<ChangedSymbol>) {
+    let old_set: HashSet<&str> = old.iter().map(String::as_str).collect();
+    let new_set: HashSet<&str> = new.iter().map(String::as_str).collect();
+
+    let mut added: Vec<String> = new_set.difference(&old_set).map(|s| s.to_string()).collect();
+    let mut removed: Vec<String> = old_set.difference(&new_set).map(|s| s.to_string()).collect();
+    added.sort();
+    removed.sort();
+
+    (
+        added
+            .into_iter()
+            .map(|name| ChangedSymbol {
+                name,
+                hunk_line_range: None,
+            })
+            .collect(),
+        removed
+            .into_iter()
+            .map(|name| ChangedSymbol {
+                name,
+                hunk_line_range: None,
+            })
+            .collect(),
+    )
+}
+
+/// Computes the delta between a before/after parse, mapping each changed
+/// export to the hunk (in whichever source it belongs to) that produced it.
+pub fn compute_delta(old: &ParseResult, new: &ParseResult, hunks: &[Hunk]) -> ParseDelta {
+    let mut delta = ParseDelta::default();
+
+    let old_exports: HashMap<&str, &ExportEntry> = old
+        .metadata
+        .exports
+        .iter()
+        .map(|e| (e.name.as_str(), e))
+        .collect();
+    let new_exports: HashMap<&str, &ExportEntry> = new
+        .metadata
+        .exports
+        .iter()
+        .map(|e| (e.name.as_str(), e))
+        .collect();
+
+    for (name, entry) in &new_exports {
+        match old_exports.get(name) {
+            None => delta.exports_added.push(ChangedSymbol {
+                name: name.to_string(),
+                hunk_line_range: hunk_for_new_line(hunks, entry.start_line),
+            }),
+            Some(old_entry) => {
+                if old_entry != entry {
+                    delta.exports_modified.push(ChangedSymbol {
+                        name: name.to_string(),
+                        hunk_line_range: hunk_for_new_line(hunks, entry.start_line),
+                    });
+                }
+            }
+        }
+    }
+    for (name, entry) in &old_exports {
+        if !new_exports.contains_key(name) {
+            delta.exports_removed.push(ChangedSymbol {
+                name: name.to_string(),
+                hunk_line_range: hunk_for_old_line(hunks, entry.start_line),
+            });
+        }
+    }
+    delta.exports_added.sort_by(|a, b| a.name.cmp(&b.name));
+    delta.exports_removed.sort_by(|a, b| a.name.cmp(&b.name));
+    delta.exports_modified.sort_by(|a, b| a.name.cmp(&b.name));
+
+    (delta.imports_added, delta.imports_removed) =
+        diff_names(&old.metadata.imports, &new.metadata.imports);
+    (delta.dependencies_added, delta.dependencies_removed) =
+        diff_names(&old.metadata.dependencies, &new.metadata.dependencies);
+
+    let old_fields = old.custom_fields.as_ref();
+    let new_fields = new.custom_fields.as_ref();
+    let mut keys: HashSet<&str> = HashSet::new();
+    if let Some(fields) = old_fields {
+        keys.extend(fields.keys().map(String::as_str));
+    }
+    if let Some(fields) = new_fields {
+        keys.extend(fields.keys().map(String::as_str));
+    }
+    let mut changed: Vec<String> = keys
+        .into_iter()
+        .filter(|key| {
+            old_fields.and_then(|f| f.get(*key)) != new_fields.and_then(|f| f.get(*key))
+        })
+        .map(|s| s.to_string())
+        .collect();
+    changed.sort();
+    delta.custom_fields_changed = changed;
+
+    delta
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::builtin::rust::RustParser;
+    use crate::parser::builtin::java::JavaParser;
+    use crate::parser::Parser as _;
+
+    #[test]
+    fn applies_simple_addition_hunk() {
+        let old_source = "fn a() {}\nfn b() {}\n";
+        let diff = "--- a/lib.rs\n+++ b/lib.rs\n@@ -1,2 +1,3 @@\n fn a() {}\n+fn c() {}\n fn b() {}\n";
+        let (new_source, hunks) = apply_unified_diff(old_source, diff).unwrap();
+        assert_eq!(new_source, "fn a() {}\nfn c() {}\nfn b() {}\n");
+        assert_eq!(hunks.len(), 1);
+        assert_eq!(hunks[0].new_start, 1);
+        assert_eq!(hunks[0].new_len, 3);
+    }
+
+    #[test]
+    fn applies_removal_hunk() {
+        let old_source = "fn a() {}\nfn b() {}\nfn c() {}\n";
+        let diff = "--- a/lib.rs\n+++ b/lib.rs\n@@ -1,3 +1,2 @@\n fn a() {}\n-fn b() {}\n fn c() {}\n";
+        let (new_source, _) = apply_unified_diff(old_source, diff).unwrap();
+        assert_eq!(new_source, "fn a() {}\nfn c() {}\n");
+    }
+
+    #[test]
+    fn parse_diff_reports_added_export_with_hunk_range() {
+        let mut parser = RustParser::new().unwrap();
+        let old_source = "pub fn a() {}\n";
+        let diff =
+            "--- a/lib.rs\n+++ b/lib.rs\n@@ -1,1 +1,2 @@\n pub fn a() {}\n+pub fn b() {}\n";
+        let delta = parser.parse_diff(old_source, diff).unwrap();
+        assert_eq!(delta.exports_added.len(), 1);
+        assert_eq!(delta.exports_added[0].name, "b");
+        assert_eq!(delta.exports_added[0].hunk_line_range, Some((1, 2)));
+        assert!(delta.exports_removed.is_empty());
+    }
+
+    #[test]
+    fn parse_diff_reports_removed_export() {
+        let mut parser = RustParser::new().unwrap();
+        let old_source = "pub fn a() {}\npub fn b() {}\n";
+        let diff =
+            "--- a/lib.rs\n+++ b/lib.rs\n@@ -1,2 +1,1 @@\n pub fn a() {}\n-pub fn b() {}\n";
+        let delta = parser.parse_diff(old_source, diff).unwrap();
+        assert_eq!(delta.exports_removed.len(), 1);
+        assert_eq!(delta.exports_removed[0].name, "b");
+        assert!(delta.exports_added.is_empty());
+    }
+
+    #[test]
+    fn parse_diff_reports_custom_field_change() {
+        let mut parser = JavaParser::new().unwrap();
+        let old_source = "@Deprecated\npublic class Foo {}\n";
+        let diff = "--- a/Foo.java\n+++ b/Foo.java\n@@ -1,2 +1,2 @@\n-@Deprecated\n+@Service\n public class Foo {}\n";
+        let delta = parser.parse_diff(old_source, diff).unwrap();
+        assert!(delta
+            .custom_fields_changed
+            .contains(&"annotations".to_string()));
+    }
+}
@@ -0,0 +1,468 @@
+//! Runtime loading of external tree-sitter grammars.
+//!
+//! Every builtin parser (`JavaParser`, `TypeScriptParser`, etc.) bundles a
+//! hand-written extraction pass compiled into this binary, so adding a
+//! language normally means a new release. This module instead reads a
+//! `languages.toml`-style manifest mapping a grammar name to its file
+//! extensions and the location of a compiled tree-sitter parser
+//! (`.so`/`.dylib`/`.dll`), `dlopen`s it, resolves its `tree_sitter_<lang>`
+//! constructor symbol, and registers a [`GrammarParser`] driven by
+//! whatever `custom_fields` queries [`crate::config::query_pack`] has
+//! configured for that language — no new Rust code or release required.
+//!
+//! A grammar whose library fails to load (missing file, blacklisted
+//! platform, bad symbol) is skipped with a logged reason; the rest of the
+//! manifest, and every builtin parser, still register normally.
+
+use crate::config::query_pack::QueryPackConfig;
+use crate::parser::builtin::query_pack::CompiledQueryPack;
+use crate::parser::{Metadata, ParseResult, Parser, ParserRegistry};
+use anyhow::{Context, Result};
+use libloading::{Library, Symbol};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tree_sitter::{Language, Parser as TSParser};
+
+/// One `[[grammar]]` entry in `languages.toml`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrammarEntry {
+    /// Grammar name, also used as the reported `language_id` and (absent
+    /// an explicit `symbol`) to derive the `tree_sitter_<name>` constructor
+    /// symbol.
+    pub name: String,
+    /// File extensions this grammar should be registered for.
+    pub extensions: Vec<String>,
+    /// Local path to a compiled `.so`/`.dylib`/`.dll`, or an `http(s)://`
+    /// URL. A URL must already be cached under `~/.fmm/grammars/` (see
+    /// `fmm fetch-grammar`) — this loader never downloads anything itself.
+    pub library: String,
+    /// Constructor symbol to resolve, overriding the `tree_sitter_<name>`
+    /// default. Needed when a grammar's crate names its symbol
+    /// differently (e.g. `tree_sitter_typescript` exposing
+    /// `tree_sitter_typescript` and `tree_sitter_tsx` side by side).
+    #[serde(default)]
+    pub symbol: Option<String>,
+    /// `std::env::consts::OS` values this grammar is known not to build or
+    /// load on (mirroring the blacklist an external grammar's own
+    /// `build.rs` would carry). Skipped with a log line rather than
+    /// attempted and left to fail.
+    #[serde(default)]
+    pub blacklist: Vec<String>,
+}
+
+impl GrammarEntry {
+    fn blacklisted_here(&self) -> bool {
+        self.blacklist.iter().any(|os| os == std::env::consts::OS)
+    }
+
+    fn symbol_name(&self) -> String {
+        self.symbol
+            .clone()
+            .unwrap_or_else(|| format!("tree_sitter_{}", self.name))
+    }
+}
+
+/// The full `languages.toml` manifest: every grammar a user wants loaded
+/// at runtime.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GrammarManifest {
+    #[serde(default, rename = "grammar")]
+    pub grammars: Vec<GrammarEntry>,
+}
+
+impl GrammarManifest {
+    /// Load `path`. A missing or invalid manifest is treated as empty —
+    /// runtime grammars are opt-in, not required.
+    pub fn load(path: &Path) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|content| toml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Write this manifest back out as `languages.toml`, replacing it
+    /// wholesale. Used by `fmm fetch-grammar` after adding or updating an
+    /// entry.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let toml = toml::to_string_pretty(self).context("serializing languages.toml")?;
+        std::fs::write(path, toml)
+            .with_context(|| format!("writing {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Insert or replace the entry for `entry.name`, keeping the rest of
+    /// the manifest untouched.
+    pub fn upsert(&mut self, entry: GrammarEntry) {
+        self.grammars.retain(|g| g.name != entry.name);
+        self.grammars.push(entry);
+    }
+}
+
+/// `~/.fmm/grammars/`, where `fmm fetch-grammar` caches downloaded
+/// libraries and where a `library = "https://..."` manifest entry is
+/// looked up.
+pub(crate) fn grammar_cache_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".fmm").join("grammars"))
+}
+
+pub(crate) fn library_extension() -> &'static str {
+    if cfg!(target_os = "windows") {
+        "dll"
+    } else if cfg!(target_os = "macos") {
+        "dylib"
+    } else {
+        "so"
+    }
+}
+
+/// Resolve a `GrammarEntry::library` to a path `libloading` can open. A
+/// plain path is used as-is; a URL is redirected to its cached copy under
+/// [`grammar_cache_dir`], erroring out (rather than fetching it) if that
+/// copy doesn't exist yet.
+fn resolve_library_path(entry: &GrammarEntry) -> Result<PathBuf> {
+    if !entry.library.starts_with("http://") && !entry.library.starts_with("https://") {
+        return Ok(PathBuf::from(&entry.library));
+    }
+
+    let cached = grammar_cache_dir()
+        .map(|dir| dir.join(format!("{}.{}", entry.name, library_extension())));
+    match cached {
+        Some(path) if path.is_file() => Ok(path),
+        _ => anyhow::bail!(
+            "grammar '{}' points at {}; run `fmm fetch-grammar {}` to download and cache it locally first",
+            entry.name,
+            entry.library,
+            entry.name
+        ),
+    }
+}
+
+/// Resolve and call a grammar library's `tree_sitter_<name>` constructor
+/// to get its [`Language`].
+///
+/// # Safety
+/// The caller is trusting `library` to export a symbol matching the
+/// `unsafe extern "C" fn() -> *const ()` tree-sitter language-constructor
+/// ABI, the same contract every `tree-sitter-<lang>` crate's generated
+/// bindings rely on. A library that doesn't will produce undefined
+/// behavior; there is no way to check this from the symbol name alone.
+unsafe fn load_language(library: &Library, symbol_name: &str) -> Result<Language> {
+    let ctor: Symbol<unsafe extern "C" fn() -> *const ()> = library
+        .get(symbol_name.as_bytes())
+        .with_context(|| format!("resolving {symbol_name} in grammar library"))?;
+    Ok(Language::from_raw(ctor()))
+}
+
+/// Holds every grammar `Library` loaded this process so their code stays
+/// mapped for as long as any [`GrammarParser`] built from it might still
+/// be alive — intentionally leaked for the process lifetime rather than
+/// freed, the same tradeoff [`crate::parser::plugin::LoadedPlugins`]
+/// makes for plugin libraries.
+#[allow(dead_code)]
+pub struct LoadedGrammars {
+    libraries: Vec<Library>,
+    /// Grammar names that registered successfully.
+    pub loaded: Vec<String>,
+}
+
+/// Read `manifest_path`, `dlopen` each non-blacklisted grammar, and
+/// register a [`GrammarParser`] for it in `registry`. A grammar that fails
+/// to load (bad path, bad symbol, blacklisted platform) is skipped with a
+/// logged reason; it never aborts the rest of the manifest or disturbs
+/// the builtin parsers already registered in `registry`.
+pub fn load_grammars(
+    registry: &mut ParserRegistry,
+    manifest_path: &Path,
+    query_packs: &QueryPackConfig,
+) -> LoadedGrammars {
+    let manifest = GrammarManifest::load(manifest_path);
+    let mut libraries = Vec::new();
+    let mut loaded = Vec::new();
+
+    for entry in &manifest.grammars {
+        if entry.blacklisted_here() {
+            eprintln!(
+                "fmm: skipping grammar '{}': blacklisted on {}",
+                entry.name,
+                std::env::consts::OS
+            );
+            continue;
+        }
+
+        match load_one_grammar(entry, query_packs) {
+            Ok((library, language, query_pack)) => {
+                register_grammar(registry, entry, language, query_pack);
+                libraries.push(library);
+                loaded.push(entry.name.clone());
+            }
+            Err(err) => {
+                eprintln!(
+                    "fmm: skipping grammar '{}': {err:#} (falling back to builtin parsers for its extensions, if any)",
+                    entry.name
+                );
+            }
+        }
+    }
+
+    LoadedGrammars { libraries, loaded }
+}
+
+fn load_one_grammar(
+    entry: &GrammarEntry,
+    query_packs: &QueryPackConfig,
+) -> Result<(Library, Language, Arc<CompiledQueryPack>)> {
+    let library_path = resolve_library_path(entry)?;
+    // SAFETY: load_language's contract applies; we immediately use the
+    // resulting Language and never call anything else from this library.
+    let library = unsafe {
+        Library::new(&library_path)
+            .with_context(|| format!("loading grammar library {}", library_path.display()))?
+    };
+    let language = unsafe { load_language(&library, &entry.symbol_name())? };
+    let query_pack = CompiledQueryPack::compile(&language, query_packs.for_language(&entry.name))?;
+    Ok((library, language, Arc::new(query_pack)))
+}
+
+fn register_grammar(
+    registry: &mut ParserRegistry,
+    entry: &GrammarEntry,
+    language: Language,
+    query_pack: Arc<CompiledQueryPack>,
+) {
+    // Leaked for the process lifetime, mirroring `libraries` above: the
+    // `Parser` trait reports `language_id`/`extensions` as `&'static`, but
+    // a runtime-loaded grammar only learns its name and extensions from
+    // the manifest, not the type system.
+    let language_id: &'static str = Box::leak(entry.name.clone().into_boxed_str());
+    let extensions: Vec<&'static str> = entry
+        .extensions
+        .iter()
+        .map(|ext| &*Box::leak(ext.clone().into_boxed_str()))
+        .collect();
+    let extensions: &'static [&'static str] = Box::leak(extensions.into_boxed_slice());
+
+    registry.register(extensions, move || {
+        Ok(Box::new(GrammarParser::new(
+            language.clone(),
+            language_id,
+            extensions,
+            Arc::clone(&query_pack),
+        )?) as Box<dyn Parser>)
+    });
+}
+
+/// Generic [`Parser`] over a runtime-loaded [`Language`]. With no
+/// hand-written extraction pass of its own, everything it reports —
+/// `exports`, `imports`, `dependencies`, and `custom_fields` — comes from
+/// whatever `fmm.queries.toml` configures for this language; an entry with
+/// nothing configured parses to an empty [`Metadata`] apart from `loc`.
+pub struct GrammarParser {
+    parser: TSParser,
+    language_id: &'static str,
+    extensions: &'static [&'static str],
+    query_pack: Arc<CompiledQueryPack>,
+}
+
+impl GrammarParser {
+    fn new(
+        language: Language,
+        language_id: &'static str,
+        extensions: &'static [&'static str],
+        query_pack: Arc<CompiledQueryPack>,
+    ) -> Result<Self> {
+        let mut parser = TSParser::new();
+        parser
+            .set_language(&language)
+            .map_err(|e| anyhow::anyhow!("Failed to set {} language: {}", language_id, e))?;
+        Ok(Self {
+            parser,
+            language_id,
+            extensions,
+            query_pack,
+        })
+    }
+}
+
+impl Parser for GrammarParser {
+    fn parse(&mut self, source: &str) -> Result<ParseResult> {
+        let tree = self
+            .parser
+            .parse(source, None)
+            .ok_or_else(|| anyhow::anyhow!("Failed to parse source code"))?;
+
+        let root_node = tree.root_node();
+        let source_bytes = source.as_bytes();
+        let exports = self.query_pack.exports(root_node, source_bytes);
+        let imports = self.query_pack.imports(root_node, source_bytes);
+        let dependencies = self.query_pack.dependencies(root_node, source_bytes);
+
+        let pack_fields = self.query_pack.run(root_node, source_bytes);
+        let custom_fields = if pack_fields.is_empty() {
+            None
+        } else {
+            Some(pack_fields)
+        };
+
+        Ok(ParseResult {
+            metadata: Metadata {
+                exports,
+                imports,
+                dependencies,
+                loc: source.lines().count(),
+                re_exports: Vec::new(),
+                test_loc: 0,
+                symbols: Vec::new(),
+                diagnostics: crate::parser::collect_diagnostics(root_node),
+            },
+            custom_fields,
+        })
+    }
+
+    fn language_id(&self) -> &'static str {
+        self.language_id
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        self.extensions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn manifest_load_returns_default_for_missing_file() {
+        let tmp = TempDir::new().unwrap();
+        let manifest = GrammarManifest::load(&tmp.path().join("languages.toml"));
+        assert!(manifest.grammars.is_empty());
+    }
+
+    #[test]
+    fn manifest_load_parses_grammar_entries() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("languages.toml");
+        std::fs::write(
+            &path,
+            r#"
+            [[grammar]]
+            name = "kotlin"
+            extensions = ["kt", "kts"]
+            library = "/opt/fmm-grammars/libtree-sitter-kotlin.so"
+            blacklist = ["windows"]
+
+            [[grammar]]
+            name = "swift"
+            extensions = ["swift"]
+            library = "https://example.com/libtree-sitter-swift.so"
+            symbol = "tree_sitter_swift_v2"
+            "#,
+        )
+        .unwrap();
+
+        let manifest = GrammarManifest::load(&path);
+        assert_eq!(manifest.grammars.len(), 2);
+        assert_eq!(manifest.grammars[0].name, "kotlin");
+        assert_eq!(manifest.grammars[0].extensions, vec!["kt", "kts"]);
+        assert_eq!(manifest.grammars[0].blacklist, vec!["windows"]);
+        assert_eq!(
+            manifest.grammars[1].symbol.as_deref(),
+            Some("tree_sitter_swift_v2")
+        );
+    }
+
+    #[test]
+    fn blacklisted_here_matches_current_os() {
+        let entry = GrammarEntry {
+            name: "kotlin".to_string(),
+            extensions: vec!["kt".to_string()],
+            library: "/tmp/libtree-sitter-kotlin.so".to_string(),
+            symbol: None,
+            blacklist: vec![std::env::consts::OS.to_string()],
+        };
+        assert!(entry.blacklisted_here());
+
+        let entry = GrammarEntry {
+            blacklist: vec!["some-platform-that-does-not-exist".to_string()],
+            ..entry
+        };
+        assert!(!entry.blacklisted_here());
+    }
+
+    #[test]
+    fn symbol_name_defaults_to_tree_sitter_prefixed_name() {
+        let entry = GrammarEntry {
+            name: "kotlin".to_string(),
+            extensions: vec!["kt".to_string()],
+            library: "/tmp/libtree-sitter-kotlin.so".to_string(),
+            symbol: None,
+            blacklist: Vec::new(),
+        };
+        assert_eq!(entry.symbol_name(), "tree_sitter_kotlin");
+
+        let entry = GrammarEntry {
+            symbol: Some("custom_symbol".to_string()),
+            ..entry
+        };
+        assert_eq!(entry.symbol_name(), "custom_symbol");
+    }
+
+    #[test]
+    fn remote_library_without_cache_errors_with_fetch_hint() {
+        let entry = GrammarEntry {
+            name: "swift".to_string(),
+            extensions: vec!["swift".to_string()],
+            library: "https://example.com/libtree-sitter-swift.so".to_string(),
+            symbol: None,
+            blacklist: Vec::new(),
+        };
+        let err = resolve_library_path(&entry).unwrap_err();
+        assert!(err.to_string().contains("fetch-grammar"));
+    }
+
+    #[test]
+    fn grammar_parser_reports_declarative_exports_and_custom_fields() {
+        use crate::config::query_pack::LanguageQueryPack;
+
+        let language: Language = tree_sitter_ruby::LANGUAGE.into();
+        let pack = LanguageQueryPack {
+            exports: Some("(method name: (identifier) @export)".to_string()),
+            ..Default::default()
+        };
+        let query_pack = Arc::new(CompiledQueryPack::compile(&language, Some(&pack)).unwrap());
+        let extensions: &'static [&'static str] = &["rb"];
+
+        let mut parser =
+            GrammarParser::new(language, "ruby", extensions, query_pack).unwrap();
+        let result = parser.parse("def greet\nend\n").unwrap();
+
+        assert_eq!(result.metadata.exports.len(), 1);
+        assert_eq!(result.metadata.exports[0].name, "greet");
+        assert_eq!(parser.language_id(), "ruby");
+        assert_eq!(parser.extensions(), &["rb"]);
+    }
+
+    #[test]
+    fn load_grammars_skips_missing_library_without_panicking() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("languages.toml");
+        std::fs::write(
+            &path,
+            r#"
+            [[grammar]]
+            name = "nonexistent"
+            extensions = ["xyz"]
+            library = "/no/such/file.so"
+            "#,
+        )
+        .unwrap();
+
+        let mut registry = ParserRegistry::new();
+        let query_packs = QueryPackConfig::default();
+        let loaded = load_grammars(&mut registry, &path, &query_packs);
+        assert!(loaded.loaded.is_empty());
+        assert!(!registry.has_parser("xyz"));
+    }
+}
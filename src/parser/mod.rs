@@ -1,29 +1,438 @@
 pub mod builtin;
+pub mod cache;
+pub mod diff;
+pub mod grammar;
 pub mod plugin;
+pub mod static_plugin;
+pub mod wasm_plugin;
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::Path;
+use tree_sitter::InputEdit;
+
+/// Visibility tier of an exported item, from most to least restrictive.
+/// Parsers previously discarded anything that wasn't `pub` (or the
+/// language's equivalent); tracking the tier instead lets callers decide
+/// for themselves whether crate-internal or private items are relevant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Visibility {
+    /// Fully public (`pub`, Python's undecorated top-level names, etc.).
+    Public,
+    /// Visible within the crate/package but not re-exported (`pub(crate)`).
+    Crate,
+    /// Visible to a restricted path (`pub(super)`, `pub(in path)`).
+    Restricted,
+    /// Not exported at all outside its defining module.
+    Private,
+}
+
+impl Default for Visibility {
+    fn default() -> Self {
+        Visibility::Public
+    }
+}
+
+/// The declaration shape behind an [`ExportEntry`], coarse enough to cover
+/// the handful of top-level forms a module can export. Narrower than
+/// [`SymbolKind`] (no `Module`/`Enum`/`Method` split) since this only needs
+/// to distinguish what a frontmatter reader would want a one-line signature
+/// for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportKind {
+    Function,
+    Class,
+    Interface,
+    Const,
+    Type,
+}
+
+/// A single exported symbol: its name, source line range, and visibility.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ExportEntry {
+    pub name: String,
+    pub start_line: usize,
+    pub end_line: usize,
+    #[serde(default, skip_serializing_if = "is_public")]
+    pub visibility: Visibility,
+    /// The raw `#[cfg(...)]` expression gating this item, if any (e.g.
+    /// `target_os = "linux"`). `None` means the item is unconditionally
+    /// compiled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cfg: Option<String>,
+    /// The leading doc comment (`///` or `//!`) attached to this item,
+    /// with comment markers stripped, joined by newlines.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
+    /// Intra-doc links referenced from `doc` (e.g. `` [`Foo`] `` or
+    /// `` [`bar::baz`] ``), extracted for downstream link resolution.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub doc_links: Vec<String>,
+    /// What kind of declaration this export is, for parsers that report it.
+    /// `None` for a re-exported specifier or a parser that hasn't been
+    /// migrated to populate this.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<ExportKind>,
+    /// A one-line signature captured from the declaration: parameters and
+    /// return type for a function, `extends`/`implements` for a class or
+    /// interface, the aliased type for a type alias. `None` when `kind`
+    /// is `None`, or the declaration has nothing worth summarizing (e.g. a
+    /// class with no heritage clause).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+fn is_public(v: &Visibility) -> bool {
+    *v == Visibility::Public
+}
+
+impl ExportEntry {
+    /// Construct a public export entry — the common case for the builtin
+    /// parsers, which historically only ever saw `pub` items.
+    pub fn new(name: String, start_line: usize, end_line: usize) -> Self {
+        Self {
+            name,
+            start_line,
+            end_line,
+            visibility: Visibility::Public,
+            cfg: None,
+            doc: None,
+            doc_links: Vec::new(),
+            kind: None,
+            signature: None,
+        }
+    }
+
+    /// Construct an export entry with an explicit visibility tier.
+    pub fn with_visibility(
+        name: String,
+        start_line: usize,
+        end_line: usize,
+        visibility: Visibility,
+    ) -> Self {
+        Self {
+            name,
+            start_line,
+            end_line,
+            visibility,
+            cfg: None,
+            doc: None,
+            doc_links: Vec::new(),
+            kind: None,
+            signature: None,
+        }
+    }
+
+    /// Attach a `#[cfg(...)]` expression to this entry.
+    pub fn with_cfg(mut self, cfg: Option<String>) -> Self {
+        self.cfg = cfg;
+        self
+    }
+
+    /// Attach a doc comment to this entry, extracting any intra-doc links
+    /// (`` [`name`] ``) into `doc_links` along the way.
+    pub fn with_doc(mut self, doc: Option<String>) -> Self {
+        if let Some(text) = &doc {
+            self.doc_links = extract_doc_links(text);
+        }
+        self.doc = doc;
+        self
+    }
+
+    /// Attach the declaration kind backing this export.
+    pub fn with_kind(mut self, kind: Option<ExportKind>) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Attach a one-line signature captured from the declaration.
+    pub fn with_signature(mut self, signature: Option<String>) -> Self {
+        self.signature = signature;
+        self
+    }
+}
+
+/// Pull out intra-doc link targets of the form `` [`name`] `` or
+/// `` [`a::b::c`] `` from a doc comment body.
+fn extract_doc_links(doc: &str) -> Vec<String> {
+    let mut links = Vec::new();
+    let mut rest = doc;
+    while let Some(start) = rest.find("[`") {
+        let after = &rest[start + 2..];
+        if let Some(end) = after.find("`]") {
+            links.push(after[..end].to_string());
+            rest = &after[end + 2..];
+        } else {
+            break;
+        }
+    }
+    links
+}
+
+/// A symbol re-exported from another module rather than defined locally —
+/// TypeScript `export { X } from './y'`, Rust `pub use crate::foo::Bar;`,
+/// or a Python relative import re-surfaced via `__all__`. Tracked
+/// separately from `imports`/`dependencies` so a consumer can follow the
+/// symbol back to where it's actually defined instead of the edge
+/// vanishing, which otherwise breaks dependency-graph construction for
+/// index/barrel modules.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ReExportEntry {
+    /// The name visible to importers of this module.
+    pub symbol: String,
+    /// The module the symbol is re-exported from (e.g. `./user.service`,
+    /// `crate::foo`, `._api`).
+    pub source_module: String,
+    /// The symbol's original name in `source_module`, if re-exported under
+    /// a different name (`export { Foo as Bar }`, `pub use foo::Foo as Bar;`).
+    /// `None` when `symbol` is re-exported unchanged.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+}
+
+impl ReExportEntry {
+    pub fn new(symbol: String, source_module: String) -> Self {
+        Self {
+            symbol,
+            source_module,
+            alias: None,
+        }
+    }
+
+    pub fn with_alias(mut self, alias: Option<String>) -> Self {
+        self.alias = alias;
+        self
+    }
+}
+
+/// What kind of declaration a [`Symbol`] represents. Coarser than each
+/// language's own grammar (TypeScript's `function_declaration` and Ruby's
+/// `def` both become `Method`), since the point of this enum is a
+/// cross-language vocabulary a downstream tool can filter on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SymbolKind {
+    Class,
+    Module,
+    Method,
+    Interface,
+    TypeAlias,
+    Enum,
+    Variable,
+}
+
+/// A declared symbol with enough structure to tell a Ruby module from a
+/// top-level method, or a TS interface from a function — information
+/// `Metadata.exports`'s flat name list drops entirely. The byte `span` lets
+/// a downstream tool jump straight to the definition instead of just
+/// knowing a name exists somewhere in the file.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Symbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    pub visibility: Visibility,
+    pub span: (usize, usize),
+}
+
+impl Symbol {
+    pub fn new(name: String, kind: SymbolKind, visibility: Visibility, span: (usize, usize)) -> Self {
+        Self {
+            name,
+            kind,
+            visibility,
+            span,
+        }
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Metadata {
-    pub exports: Vec<String>,
+    pub exports: Vec<ExportEntry>,
     pub imports: Vec<String>,
     pub dependencies: Vec<String>,
     pub loc: usize,
+    /// Symbols this file re-exports from elsewhere rather than defines
+    /// itself. Empty for parsers/files with no re-export syntax.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub re_exports: Vec<ReExportEntry>,
+    /// Lines of code belonging to test code (`#[test]`/`#[cfg(test)]`
+    /// functions and modules, `def test_*`, `func TestXxx`, `describe`/`it`
+    /// blocks), already counted in `loc` above. Subtract from `loc` for a
+    /// production-only count. Zero for parsers/files with no test code.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub test_loc: usize,
+    /// Typed symbol table alongside the legacy `exports` name list, for
+    /// parsers that report kind/visibility/span. Empty for parsers that
+    /// haven't been migrated yet.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub symbols: Vec<Symbol>,
+    /// Parse-quality summary: every ERROR/MISSING node tree-sitter's error
+    /// recovery inserted while parsing this file. Omitted from serialized
+    /// output when the parse was clean, so it costs nothing in the common
+    /// case but flags a badly-damaged file instead of treating its
+    /// (possibly empty) `exports`/`imports` as authoritative.
+    #[serde(default, skip_serializing_if = "Diagnostics::is_clean")]
+    pub diagnostics: Diagnostics,
+}
+
+fn is_zero(n: &usize) -> bool {
+    *n == 0
+}
+
+/// A single `ERROR` or `MISSING` node tree-sitter's parser inserted while
+/// recovering from malformed input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticKind {
+    /// A span tree-sitter couldn't assign to any grammar rule.
+    Error,
+    /// A token tree-sitter synthesized to complete a production (e.g. a
+    /// missing `;` or closing brace).
+    Missing,
+}
+
+/// One `ERROR`/`MISSING` node from [`collect_diagnostics`], located by both
+/// byte offsets (for slicing the source) and line/column (for display).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub kind: DiagnosticKind,
+    pub start_byte: usize,
+    pub end_byte: usize,
+    pub start_line: usize,
+    pub start_column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
+/// Parse-quality summary for a file, built by [`collect_diagnostics`] and
+/// attached to [`Metadata::diagnostics`]. `has_errors`/`error_count` are
+/// cheap to check without inspecting `entries` itself.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostics {
+    pub has_errors: bool,
+    pub error_count: usize,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub entries: Vec<Diagnostic>,
+}
+
+impl Diagnostics {
+    fn is_clean(&self) -> bool {
+        !self.has_errors
+    }
+}
+
+/// Walk `node`'s subtree collecting every `ERROR`/`MISSING` node, so every
+/// builtin parser reports parse quality the same way regardless of
+/// language. A clean parse returns `Diagnostics::default()`.
+pub fn collect_diagnostics(node: tree_sitter::Node) -> Diagnostics {
+    let mut entries = Vec::new();
+    collect_diagnostics_into(node, &mut entries);
+    Diagnostics {
+        has_errors: !entries.is_empty(),
+        error_count: entries.len(),
+        entries,
+    }
+}
+
+fn collect_diagnostics_into(node: tree_sitter::Node, out: &mut Vec<Diagnostic>) {
+    if node.is_error() || node.is_missing() {
+        let start = node.start_position();
+        let end = node.end_position();
+        out.push(Diagnostic {
+            kind: if node.is_missing() {
+                DiagnosticKind::Missing
+            } else {
+                DiagnosticKind::Error
+            },
+            start_byte: node.start_byte(),
+            end_byte: node.end_byte(),
+            start_line: start.row,
+            start_column: start.column,
+            end_line: end.row,
+            end_column: end.column,
+        });
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_diagnostics_into(child, out);
+    }
+}
+
+impl Metadata {
+    /// Convenience accessor for just the export names, dropping line range
+    /// and visibility — the common case for callers that only care "is
+    /// this symbol exported".
+    pub fn export_names(&self) -> Vec<String> {
+        self.exports.iter().map(|e| e.name.clone()).collect()
+    }
 }
 
 /// Result of parsing a source file: metadata plus optional language-specific fields.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParseResult {
     pub metadata: Metadata,
     pub custom_fields: Option<HashMap<String, serde_json::Value>>,
 }
 
+/// Options that narrow what a parse pass reports, beyond the unconditional
+/// full extraction `Parser::parse` does.
+#[derive(Debug, Clone, Default)]
+pub struct ParseOptions {
+    /// Feature/target identifiers (e.g. `"unix"`, `"test"`, `"target_os=linux"`)
+    /// considered active for this parse. When non-empty, a parser that
+    /// understands conditional compilation (e.g. Rust's `#[cfg(...)]`) may
+    /// evaluate each gated item's predicate against this list and drop
+    /// symbols that wouldn't be compiled under it. Empty (the default) keeps
+    /// every symbol regardless of its gate.
+    pub active_cfg: Vec<String>,
+}
+
 pub trait Parser: Send + Sync {
     /// Parse source in a single tree-sitter pass, returning metadata and custom fields together.
     fn parse(&mut self, source: &str) -> Result<ParseResult>;
 
+    /// Parse source, then narrow the result according to `options`. Parsers
+    /// that have nothing conditional to evaluate (most languages, and Rust
+    /// when `options.active_cfg` is empty) can rely on the default, which
+    /// just forwards to `parse`.
+    fn parse_with_options(&mut self, source: &str, options: &ParseOptions) -> Result<ParseResult> {
+        let _ = options;
+        self.parse(source)
+    }
+
+    /// Apply a unified diff to `old_source`, parse both the before and
+    /// after states, and return a delta attributing each added, removed,
+    /// or modified symbol to the hunk it falls in. Lets a caller update an
+    /// O(changed symbols) index instead of re-walking the whole file on
+    /// every commit. Every builtin parser gets this for free by running
+    /// `parse` twice; none currently need anything smarter.
+    fn parse_diff(&mut self, old_source: &str, unified_diff: &str) -> Result<diff::ParseDelta> {
+        let (new_source, hunks) = diff::apply_unified_diff(old_source, unified_diff)?;
+        let old_result = self.parse(old_source)?;
+        let new_result = self.parse(&new_source)?;
+        Ok(diff::compute_delta(&old_result, &new_result, &hunks))
+    }
+
+    /// Incrementally reparse a file whose content changed, letting
+    /// tree-sitter reuse the parts of the tree this parser last produced
+    /// for `path` instead of rebuilding it from scratch. `edits` must
+    /// describe the text changes since that parse, in document order; see
+    /// [`cache::derive_edit`] for deriving a single edit from an (old,
+    /// new) source pair. Parsers that don't keep a per-path tree cache
+    /// fall back to a full [`Parser::parse`].
+    fn parse_edit(
+        &mut self,
+        path: &Path,
+        new_source: &str,
+        edits: &[InputEdit],
+    ) -> Result<ParseResult> {
+        let _ = (path, edits);
+        self.parse(new_source)
+    }
+
     /// The language identifier used in frontmatter sections (e.g., "rust", "python").
     fn language_id(&self) -> &'static str;
 
@@ -45,13 +454,65 @@ impl ParserRegistry {
         }
     }
 
-    /// Create a registry pre-loaded with all builtin parsers.
+    /// Create a registry pre-loaded with all builtin parsers, plus
+    /// whatever statically-linked, native, and WASM plugins this process
+    /// discovers — see [`Self::load_external_plugins`].
     pub fn with_builtins() -> Self {
         let mut registry = Self::new();
         registry.register_builtin();
+        registry.load_external_plugins();
+        registry
+    }
+
+    /// Like [`ParserRegistry::with_builtins`], additionally compiling each
+    /// language's `fmm.queries.toml` entry (if any) into the parsers that
+    /// support user query packs today — [`builtin::ruby::RubyParser`] and
+    /// [`builtin::typescript::TypeScriptParser`] — so their matches merge
+    /// into `custom_fields` on every parse.
+    pub fn with_builtins_and_query_packs(query_packs: &crate::config::query_pack::QueryPackConfig) -> Self {
+        let mut registry = Self::new();
+        registry.register_builtin();
+
+        let ruby_pack = query_packs.for_language("ruby").cloned();
+        registry.register(&["rb"], move || {
+            Ok(Box::new(builtin::ruby::RubyParser::with_query_pack(
+                ruby_pack.as_ref(),
+            )?))
+        });
+
+        let typescript_pack = query_packs.for_language("typescript").cloned();
+        registry.register(&["ts", "tsx", "js", "jsx"], move || {
+            Ok(Box::new(builtin::typescript::TypeScriptParser::with_query_pack(
+                typescript_pack.as_ref(),
+            )?))
+        });
+
+        registry.load_external_plugins();
         registry
     }
 
+    /// Register every statically-linked plugin compiled into this binary
+    /// ([`static_plugin::load_static_plugins`]), then scan
+    /// `~/.fmm/plugins/` for native dylib and WASM plugins
+    /// ([`plugin::load_plugins`], [`wasm_plugin::load_wasm_plugins`]) and
+    /// register whatever it finds. A missing plugin directory, or one with
+    /// nothing in it, is a silent no-op — plugins are opt-in. Each
+    /// dynamically loaded library is leaked for the process lifetime (see
+    /// [`plugin::LoadedPlugins`]), since the parsers it registered keep
+    /// referencing its code for as long as the registry is alive.
+    fn load_external_plugins(&mut self) {
+        static_plugin::load_static_plugins(self);
+
+        match plugin::load_plugins(self) {
+            Ok(loaded) => std::mem::forget(loaded),
+            Err(err) => eprintln!("fmm: skipping plugin directory: {err:#}"),
+        }
+
+        if let Err(err) = wasm_plugin::load_wasm_plugins(self) {
+            eprintln!("fmm: skipping wasm plugin directory: {err:#}");
+        }
+    }
+
     /// Register a parser factory for a set of extensions.
     pub fn register<F>(&mut self, extensions: &[&str], factory: F)
     where
@@ -104,6 +565,15 @@ impl ParserRegistry {
         self.register(&["rb"], || Ok(Box::new(builtin::ruby::RubyParser::new()?)));
     }
 
+    /// Remove the factories registered for the given extensions, if any.
+    /// Used when hot-reloading a plugin to drop its stale registrations
+    /// before the new version re-registers under the same extensions.
+    pub fn unregister(&mut self, extensions: &[&str]) {
+        for ext in extensions {
+            self.factories.remove(*ext);
+        }
+    }
+
     /// Get a new parser instance for the given file extension.
     pub fn get_parser(&self, extension: &str) -> Result<Box<dyn Parser>> {
         let factory = self
@@ -134,6 +604,58 @@ impl Default for ParserRegistry {
     }
 }
 
+thread_local! {
+    /// One parser per extension, kept alive for the lifetime of the thread
+    /// that first needed it. `rayon` reuses a fixed pool of worker threads,
+    /// so across a parallelized batch this cache is populated once per
+    /// (worker, extension) pair rather than once per file.
+    static THREAD_PARSERS: std::cell::RefCell<HashMap<String, Box<dyn Parser>>> =
+        std::cell::RefCell::new(HashMap::new());
+}
+
+/// Reuses one parser instance per thread per extension across a
+/// `rayon`-parallelized batch, instead of [`ParserRegistry::get_parser`]
+/// allocating a fresh `Box<dyn Parser>` — and, for grammar-backed parsers,
+/// reloading the tree-sitter grammar — on every call. `Parser::parse` takes
+/// `&mut self`, so a parser can't be shared *concurrently*, but parsers are
+/// `Send + Sync` and each rayon worker only ever calls into its own
+/// thread-local entry, so there's no contention to synchronize.
+#[derive(Clone)]
+pub struct ParserPool {
+    registry: std::sync::Arc<ParserRegistry>,
+}
+
+impl ParserPool {
+    /// Wrap a registry for thread-local reuse. Takes ownership since the
+    /// pool, not the registry, is the thing callers hold onto and share
+    /// (typically via a single `ParserPool` cloned into each rayon closure).
+    pub fn new(registry: ParserRegistry) -> Self {
+        Self {
+            registry: std::sync::Arc::new(registry),
+        }
+    }
+
+    /// Parse `source` (the contents of `path`) using this thread's cached
+    /// parser for `path`'s extension, constructing and caching one first if
+    /// this is the thread's first file of that type.
+    pub fn parse_file(&self, path: &Path, source: &str) -> Result<ParseResult> {
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .ok_or_else(|| anyhow::anyhow!("No file extension: {}", path.display()))?;
+
+        THREAD_PARSERS.with(|cache| {
+            let mut cache = cache.borrow_mut();
+            if !cache.contains_key(extension) {
+                let parser = self.registry.get_parser(extension)?;
+                cache.insert(extension.to_string(), parser);
+            }
+            // Just inserted if absent, so this lookup can't miss.
+            cache.get_mut(extension).unwrap().parse(source)
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -166,7 +688,7 @@ mod tests {
         let registry = ParserRegistry::with_builtins();
         let mut parser = registry.get_parser("ts").unwrap();
         let result = parser.parse("export function hello() {}").unwrap();
-        assert_eq!(result.metadata.exports, vec!["hello"]);
+        assert_eq!(result.metadata.export_names(), vec!["hello"]);
     }
 
     #[test]
@@ -176,8 +698,9 @@ mod tests {
         let result = parser
             .parse("def hello():\n    pass\n\ndef world():\n    pass")
             .unwrap();
-        assert!(result.metadata.exports.contains(&"hello".to_string()));
-        assert!(result.metadata.exports.contains(&"world".to_string()));
+        let names = result.metadata.export_names();
+        assert!(names.contains(&"hello".to_string()));
+        assert!(names.contains(&"world".to_string()));
     }
 
     #[test]
@@ -185,7 +708,7 @@ mod tests {
         let registry = ParserRegistry::with_builtins();
         let mut parser = registry.get_parser("rs").unwrap();
         let result = parser.parse("pub fn hello() {}").unwrap();
-        assert_eq!(result.metadata.exports, vec!["hello"]);
+        assert_eq!(result.metadata.export_names(), vec!["hello"]);
     }
 
     #[test]
@@ -214,4 +737,87 @@ mod tests {
         assert!(registry.has_parser("py"));
         assert!(registry.has_parser("rs"));
     }
+
+    #[test]
+    fn parser_pool_parses_via_cached_parser() {
+        let pool = ParserPool::new(ParserRegistry::with_builtins());
+        let result = pool
+            .parse_file(Path::new("main.rs"), "pub fn hello() {}")
+            .unwrap();
+        assert_eq!(result.metadata.export_names(), vec!["hello"]);
+    }
+
+    #[test]
+    fn parser_pool_reuses_cached_parser_across_calls() {
+        let pool = ParserPool::new(ParserRegistry::with_builtins());
+        let first = pool
+            .parse_file(Path::new("a.rs"), "pub fn first() {}")
+            .unwrap();
+        let second = pool
+            .parse_file(Path::new("b.rs"), "pub fn second() {}")
+            .unwrap();
+        assert_eq!(first.metadata.export_names(), vec!["first"]);
+        assert_eq!(second.metadata.export_names(), vec!["second"]);
+    }
+
+    #[test]
+    fn parser_pool_routes_mixed_extensions_independently() {
+        let pool = ParserPool::new(ParserRegistry::with_builtins());
+        let rs = pool
+            .parse_file(Path::new("main.rs"), "pub fn hello() {}")
+            .unwrap();
+        let ts = pool
+            .parse_file(Path::new("main.ts"), "export function hello() {}")
+            .unwrap();
+        assert_eq!(rs.metadata.export_names(), vec!["hello"]);
+        assert_eq!(ts.metadata.export_names(), vec!["hello"]);
+    }
+
+    #[test]
+    fn parser_pool_errors_on_missing_extension() {
+        let pool = ParserPool::new(ParserRegistry::with_builtins());
+        assert!(pool.parse_file(Path::new("Makefile"), "all:").is_err());
+    }
+
+    #[test]
+    fn parser_pool_errors_on_unknown_extension() {
+        let pool = ParserPool::new(ParserRegistry::with_builtins());
+        assert!(pool.parse_file(Path::new("main.zig"), "").is_err());
+    }
+
+    #[test]
+    fn with_builtins_and_query_packs_runs_ruby_custom_field() {
+        use crate::config::query_pack::{LanguageQueryPack, QueryPackConfig};
+
+        let mut custom_fields = HashMap::new();
+        custom_fields.insert(
+            "associations".to_string(),
+            "(call method: (identifier) @method (#match? @method \"^(has_many|belongs_to)$\"))"
+                .to_string(),
+        );
+        let mut languages = HashMap::new();
+        languages.insert(
+            "ruby".to_string(),
+            LanguageQueryPack { custom_fields, ..Default::default() },
+        );
+        let query_packs = QueryPackConfig { languages };
+
+        let registry = ParserRegistry::with_builtins_and_query_packs(&query_packs);
+        let mut parser = registry.get_parser("rb").unwrap();
+        let result = parser
+            .parse("class Post\n  has_many :comments\nend\n")
+            .unwrap();
+
+        let fields = result.custom_fields.unwrap();
+        let associations = fields.get("associations").unwrap().as_array().unwrap();
+        assert_eq!(associations.len(), 1);
+    }
+
+    #[test]
+    fn with_builtins_and_query_packs_leaves_other_languages_untouched() {
+        let query_packs = crate::config::query_pack::QueryPackConfig::default();
+        let registry = ParserRegistry::with_builtins_and_query_packs(&query_packs);
+        assert!(registry.has_parser("py"));
+        assert!(registry.has_parser("rs"));
+    }
 }
@@ -1,18 +1,275 @@
 //! Plugin loading for external parsers.
 //!
-//! This module will eventually support dynamic library loading from ~/.fmm/plugins/.
-//! For now, it provides the API surface that external plugins will implement.
+//! Plugins are dynamic libraries (`.so`/`.dylib`/`.dll`) discovered in
+//! `~/.fmm/plugins/`. Each plugin exports an `extern "C" fn plugin_entry`
+//! symbol that receives a [`Registrar`] and uses it to register parser
+//! factories into the host [`ParserRegistry`] without touching its
+//! internals directly.
 //!
 //! See docs/plugin-architecture.md for the full design.
 
-use crate::parser::ParserRegistry;
-use anyhow::Result;
+use crate::parser::{Parser, ParserRegistry};
+use anyhow::{Context, Result};
+use libloading::{Library, Symbol};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
-/// Scan the plugin directory and register any discovered parsers.
-/// Currently a no-op stub — external plugins are not yet supported.
-#[allow(dead_code)]
-pub fn load_plugins(_registry: &mut ParserRegistry) -> Result<()> {
-    // Future: scan ~/.fmm/plugins/ for .dylib/.so/.dll files,
-    // validate metadata, and register their parser factories.
+/// Handed to a plugin's entry point so it can register parser factories
+/// without reaching into `ParserRegistry` internals.
+pub trait Registrar {
+    /// Register a parser factory under the given extensions.
+    fn register_parser(
+        &mut self,
+        extensions: &[&str],
+        factory: Box<dyn Fn() -> Result<Box<dyn Parser>> + Send + Sync>,
+    );
+}
+
+struct RegistrarImpl<'a> {
+    registry: &'a mut ParserRegistry,
+    registered: Vec<String>,
+}
+
+impl Registrar for RegistrarImpl<'_> {
+    fn register_parser(
+        &mut self,
+        extensions: &[&str],
+        factory: Box<dyn Fn() -> Result<Box<dyn Parser>> + Send + Sync>,
+    ) {
+        self.registry.register(extensions, move || factory());
+        self.registered.extend(extensions.iter().map(|e| e.to_string()));
+    }
+}
+
+/// Signature that every plugin must export as `plugin_entry`.
+type PluginEntry = unsafe extern "C" fn(&mut dyn Registrar);
+
+/// Version handshake a plugin must export as a `static FMM_PLUGIN_DECL`
+/// symbol. `load_plugins` refuses to call `plugin_entry` unless all three
+/// fields match what this build of `fmm` was compiled with, since Rust has
+/// no stable ABI and a mismatched plugin can silently corrupt memory.
+#[repr(C)]
+pub struct PluginDeclaration {
+    pub fmm_version: &'static str,
+    pub rustc_version: &'static str,
+    pub core_api_version: u32,
+}
+
+/// Bump whenever the `Registrar`/`Parser` trait contract changes in a way
+/// that would break existing plugins.
+pub const CORE_API_VERSION: u32 = 1;
+
+/// The version stamps this build of `fmm` expects a plugin to match.
+pub const FMM_VERSION: &str = env!("CARGO_PKG_VERSION");
+pub const RUSTC_VERSION: &str = env!("FMM_RUSTC_VERSION");
+
+fn check_declaration(decl: &PluginDeclaration, path: &Path) -> Result<()> {
+    if decl.fmm_version != FMM_VERSION {
+        anyhow::bail!(
+            "plugin {} was built against fmm {}, but this is fmm {}",
+            path.display(),
+            decl.fmm_version,
+            FMM_VERSION
+        );
+    }
+    if decl.rustc_version != RUSTC_VERSION {
+        anyhow::bail!(
+            "plugin {} was built with rustc {}, but this binary was built with {}",
+            path.display(),
+            decl.rustc_version,
+            RUSTC_VERSION
+        );
+    }
+    if decl.core_api_version != CORE_API_VERSION {
+        anyhow::bail!(
+            "plugin {} targets core API version {}, but this binary supports version {}",
+            path.display(),
+            decl.core_api_version,
+            CORE_API_VERSION
+        );
+    }
     Ok(())
 }
+
+/// Plugin authors invoke this to emit both the `FMM_PLUGIN_DECL` handshake
+/// symbol and the `plugin_entry` wrapper with the correct version stamps,
+/// so the two can't drift out of sync by hand.
+#[macro_export]
+macro_rules! export_plugin {
+    ($register:expr) => {
+        #[no_mangle]
+        pub static FMM_PLUGIN_DECL: $crate::parser::plugin::PluginDeclaration =
+            $crate::parser::plugin::PluginDeclaration {
+                fmm_version: env!("CARGO_PKG_VERSION"),
+                rustc_version: env!("FMM_RUSTC_VERSION"),
+                core_api_version: $crate::parser::plugin::CORE_API_VERSION,
+            };
+
+        #[no_mangle]
+        pub unsafe extern "C" fn plugin_entry(registrar: &mut dyn $crate::parser::plugin::Registrar) {
+            let register: fn(&mut dyn $crate::parser::plugin::Registrar) = $register;
+            register(registrar);
+        }
+    };
+}
+
+/// Holds every `Library` loaded this process so their code/vtables stay
+/// mapped for as long as any parser they produced might still be alive.
+/// Dropping a `Library` while its code is still referenced is UB, so these
+/// are intentionally leaked for the process lifetime rather than freed.
+#[allow(dead_code)]
+pub struct LoadedPlugins {
+    libraries: Vec<Library>,
+    /// Extensions registered by each plugin file, so a hot reload can
+    /// surgically unregister exactly what the old version of that file
+    /// provided before the new version re-registers.
+    pub(crate) provided: HashMap<PathBuf, Vec<String>>,
+}
+
+fn plugin_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".fmm").join("plugins"))
+}
+
+fn is_plugin_file(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("so") | Some("dylib") | Some("dll")
+    )
+}
+
+/// Scan the plugin directory and register any discovered parsers.
+///
+/// Each candidate file is loaded independently; a single bad or
+/// incompatible plugin returns an error from its own load attempt but does
+/// not abort the scan, so the rest of the plugins still get registered.
+pub fn load_plugins(registry: &mut ParserRegistry) -> Result<LoadedPlugins> {
+    let mut libraries = Vec::new();
+    let mut provided = HashMap::new();
+
+    let Some(dir) = plugin_dir() else {
+        return Ok(LoadedPlugins { libraries, provided });
+    };
+    if !dir.is_dir() {
+        return Ok(LoadedPlugins { libraries, provided });
+    }
+
+    for entry in std::fs::read_dir(&dir).with_context(|| format!("reading {}", dir.display()))? {
+        let entry = entry?;
+        let path = entry.path();
+        if !is_plugin_file(&path) {
+            continue;
+        }
+
+        match load_one_plugin(&path, registry) {
+            Ok((lib, extensions)) => {
+                libraries.push(lib);
+                provided.insert(path, extensions);
+            }
+            Err(err) => {
+                eprintln!("fmm: skipping plugin {}: {err:#}", path.display());
+            }
+        }
+    }
+
+    Ok(LoadedPlugins { libraries, provided })
+}
+
+fn load_one_plugin(path: &Path, registry: &mut ParserRegistry) -> Result<(Library, Vec<String>)> {
+    // SAFETY: we immediately resolve and call a symbol with the expected
+    // `plugin_entry` signature; the caller is responsible for only
+    // pointing ~/.fmm/plugins/ at plugins built against this crate.
+    unsafe {
+        let library = Library::new(path)
+            .with_context(|| format!("loading plugin library {}", path.display()))?;
+
+        let decl: Symbol<*const PluginDeclaration> = library
+            .get(b"FMM_PLUGIN_DECL")
+            .with_context(|| format!("resolving FMM_PLUGIN_DECL in {}", path.display()))?;
+        check_declaration(&**decl, path)?;
+
+        let entry: Symbol<PluginEntry> = library
+            .get(b"plugin_entry")
+            .with_context(|| format!("resolving plugin_entry in {}", path.display()))?;
+
+        let mut registrar = RegistrarImpl {
+            registry,
+            registered: Vec::new(),
+        };
+        entry(&mut registrar);
+
+        Ok((library, registrar.registered))
+    }
+}
+
+/// Hot-reload support: watches `~/.fmm/plugins/` and reloads a plugin file
+/// when it changes on disk, without restarting `fmm`. Gated behind the
+/// `plugin-hot-reload` feature since production builds should pay nothing
+/// for a filesystem watcher they don't use.
+#[cfg(feature = "plugin-hot-reload")]
+pub mod hot_reload {
+    use super::*;
+    use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+    use std::sync::mpsc::channel;
+    use std::sync::{Arc, Mutex};
+
+    /// Watches the plugin directory and reloads individual plugin files as
+    /// they change, rather than restarting the whole scan. Unloading the
+    /// previous `Library` is deferred until no parser instance created
+    /// from it is still alive, since dropping it earlier would invalidate
+    /// any vtable/code pointers those instances still reference.
+    pub fn watch_plugins(registry: Arc<Mutex<ParserRegistry>>, loaded: Arc<Mutex<LoadedPlugins>>) -> Result<RecommendedWatcher> {
+        let Some(dir) = plugin_dir() else {
+            anyhow::bail!("no home directory to resolve ~/.fmm/plugins/");
+        };
+
+        let (tx, rx) = channel();
+        let mut watcher = notify::recommended_watcher(tx)?;
+        watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+
+        std::thread::spawn(move || {
+            for event in rx {
+                let Ok(event) = event else { continue };
+                for path in event.paths {
+                    if !is_plugin_file(&path) {
+                        continue;
+                    }
+                    reload_one(&registry, &loaded, &path);
+                }
+            }
+        });
+
+        Ok(watcher)
+    }
+
+    fn reload_one(
+        registry: &Arc<Mutex<ParserRegistry>>,
+        loaded: &Arc<Mutex<LoadedPlugins>>,
+        path: &Path,
+    ) {
+        let mut registry = registry.lock().unwrap();
+        let mut loaded_guard = loaded.lock().unwrap();
+
+        if let Some(old_extensions) = loaded_guard.provided.remove(path) {
+            let exts: Vec<&str> = old_extensions.iter().map(|s| s.as_str()).collect();
+            registry.unregister(&exts);
+            // The previous `Library` stays in `loaded_guard.libraries`
+            // (not removed) until the process exits, since any parser
+            // instances it produced may still be alive and referencing
+            // its code.
+        }
+
+        if !path.exists() {
+            return;
+        }
+
+        match load_one_plugin(path, &mut registry) {
+            Ok((lib, extensions)) => {
+                loaded_guard.libraries.push(lib);
+                loaded_guard.provided.insert(path.to_path_buf(), extensions);
+            }
+            Err(err) => {
+                eprintln!("fmm: failed to reload plugin {}: {err:#}", path.display());
+            }
+        }
+    }
+}
@@ -0,0 +1,57 @@
+//! Compile-time static plugin registration.
+//!
+//! Dynamic (dylib/WASM) plugins require shipping loose plugin files
+//! alongside the `fmm` binary, which some deployments can't do — instead
+//! they want to statically link a closed-source parser directly into the
+//! binary. This module provides that path: any crate linked into the
+//! final binary can append a [`StaticParserPlugin`] descriptor to the
+//! distributed slice [`PARSER_PLUGINS`] via [`register_static_parser!`],
+//! and [`load_static_plugins`] drains the slice into a [`ParserRegistry`]
+//! at startup.
+//!
+//! Unlike [`crate::parser::plugin::load_plugins`], this requires no
+//! runtime `dlopen` and fully inlines, at the cost of needing the parser's
+//! source (or a linkable static library) available at `fmm`'s own build
+//! time.
+
+use crate::parser::{Parser, ParserRegistry};
+use anyhow::Result;
+
+/// A single statically-registered parser factory, collected into
+/// [`PARSER_PLUGINS`] by [`register_static_parser!`].
+pub struct StaticParserPlugin {
+    pub extensions: &'static [&'static str],
+    pub factory: fn() -> Result<Box<dyn Parser>>,
+}
+
+/// Distributed slice of every statically-linked parser plugin. Populated
+/// at link time by any crate that invokes [`register_static_parser!`];
+/// `fmm` itself never pushes to this directly.
+#[linkme::distributed_slice]
+pub static PARSER_PLUGINS: [StaticParserPlugin] = [..];
+
+/// Register a parser factory into [`PARSER_PLUGINS`] at link time.
+///
+/// ```ignore
+/// register_static_parser!(["proprietary"], || Ok(Box::new(MyParser::new()?)));
+/// ```
+#[macro_export]
+macro_rules! register_static_parser {
+    ($extensions:expr, $factory:expr) => {
+        #[linkme::distributed_slice($crate::parser::static_plugin::PARSER_PLUGINS)]
+        static __FMM_STATIC_PARSER_PLUGIN: $crate::parser::static_plugin::StaticParserPlugin =
+            $crate::parser::static_plugin::StaticParserPlugin {
+                extensions: $extensions,
+                factory: $factory,
+            };
+    };
+}
+
+/// Drain [`PARSER_PLUGINS`] into `registry` at startup, alongside the
+/// builtin and dynamically-loaded parsers.
+pub fn load_static_plugins(registry: &mut ParserRegistry) {
+    for plugin in PARSER_PLUGINS.iter() {
+        let factory = plugin.factory;
+        registry.register(plugin.extensions, move || factory());
+    }
+}
@@ -0,0 +1,166 @@
+//! WASM plugin runtime — a safe, cross-version alternative to the native
+//! dylib backend in [`crate::parser::plugin`].
+//!
+//! Native plugins are unsafe and locked to the rustc/ABI version they were
+//! built with. WASM modules, in contrast, run sandboxed (no ambient
+//! filesystem or network access) and keep working across `fmm` rebuilds.
+//! The tradeoff is the cost of marshaling buffers across the linear-memory
+//! boundary.
+//!
+//! Guest contract: a module exports
+//! - `parse(ptr: i32, len: i32) -> i64` — the low 32 bits of the return
+//!   value are the output pointer, the high 32 bits are the output length,
+//!   both into the guest's own linear memory.
+//! - `parser_metadata() -> i64` — same pointer/length packing, pointing at
+//!   a UTF-8 JSON blob `{"language_id": ..., "extensions": [...]}`.
+//! - `memory` — the guest's linear memory, for the host to read/write.
+
+use crate::parser::{Metadata, ParseResult, Parser};
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use wasmtime::{Engine, Instance, Memory, Module, Store, TypedFunc};
+
+#[derive(Debug, Deserialize)]
+struct WasmParserMetadata {
+    language_id: String,
+    extensions: Vec<String>,
+}
+
+/// A parser backed by a sandboxed WASM module.
+pub struct WasmParser {
+    engine: Engine,
+    module: Module,
+    metadata: WasmParserMetadata,
+}
+
+impl WasmParser {
+    fn load(path: &Path) -> Result<Self> {
+        let engine = Engine::default();
+        let module = Module::from_file(&engine, path)
+            .with_context(|| format!("compiling wasm module {}", path.display()))?;
+
+        let mut store = Store::new(&engine, ());
+        let instance = Instance::new(&mut store, &module, &[])
+            .with_context(|| format!("instantiating wasm module {}", path.display()))?;
+
+        let metadata = call_metadata(&mut store, &instance)
+            .with_context(|| format!("reading parser_metadata from {}", path.display()))?;
+
+        Ok(Self {
+            engine,
+            module,
+            metadata,
+        })
+    }
+}
+
+fn call_metadata(
+    store: &mut Store<()>,
+    instance: &Instance,
+) -> Result<WasmParserMetadata> {
+    let memory = instance
+        .get_memory(&mut *store, "memory")
+        .context("guest module does not export `memory`")?;
+    let func: TypedFunc<(), i64> = instance
+        .get_typed_func(&mut *store, "parser_metadata")
+        .context("guest module does not export `parser_metadata`")?;
+    let packed = func.call(&mut *store, ())?;
+    let bytes = read_packed(store, &memory, packed)?;
+    Ok(serde_json::from_slice(&bytes)?)
+}
+
+fn read_packed(store: &Store<()>, memory: &Memory, packed: i64) -> Result<Vec<u8>> {
+    let ptr = (packed as u64 & 0xFFFF_FFFF) as usize;
+    let len = ((packed as u64) >> 32) as usize;
+    let data = memory.data(store);
+    data.get(ptr..ptr + len)
+        .map(|s| s.to_vec())
+        .context("guest returned an out-of-bounds pointer/length")
+}
+
+impl Parser for WasmParser {
+    fn parse(&mut self, source: &str) -> Result<ParseResult> {
+        let mut store = Store::new(&self.engine, ());
+        let instance = Instance::new(&mut store, &self.module, &[])?;
+        let memory = instance
+            .get_memory(&mut store, "memory")
+            .context("guest module does not export `memory`")?;
+
+        // Guests are expected to reserve their input buffer at a fixed,
+        // well-known offset; the host writes the source there before
+        // invoking `parse`.
+        let input_ptr = 0usize;
+        let bytes = source.as_bytes();
+        memory
+            .write(&mut store, input_ptr, bytes)
+            .context("writing source into guest memory")?;
+
+        let parse_fn: TypedFunc<(i32, i32), i64> =
+            instance.get_typed_func(&mut store, "parse")?;
+        let packed = parse_fn.call(&mut store, (input_ptr as i32, bytes.len() as i32))?;
+        let output = read_packed(&store, &memory, packed)?;
+
+        let metadata: Metadata = serde_json::from_slice(&output)
+            .context("guest `parse` output was not valid JSON metadata")?;
+
+        Ok(ParseResult {
+            metadata,
+            custom_fields: None,
+        })
+    }
+
+    fn language_id(&self) -> &'static str {
+        Box::leak(self.metadata.language_id.clone().into_boxed_str())
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        let leaked: Vec<&'static str> = self
+            .metadata
+            .extensions
+            .iter()
+            .map(|e| &*Box::leak(e.clone().into_boxed_str()))
+            .collect();
+        Box::leak(leaked.into_boxed_slice())
+    }
+}
+
+fn wasm_plugin_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".fmm").join("plugins"))
+}
+
+/// Scan `~/.fmm/plugins/` for `.wasm` modules and register each as a
+/// parser. As with the native loader, one bad module is skipped with a
+/// logged error rather than aborting the whole scan.
+pub fn load_wasm_plugins(registry: &mut crate::parser::ParserRegistry) -> Result<()> {
+    let Some(dir) = wasm_plugin_dir() else {
+        return Ok(());
+    };
+    if !dir.is_dir() {
+        return Ok(());
+    }
+
+    for entry in std::fs::read_dir(&dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("wasm") {
+            continue;
+        }
+
+        match WasmParser::load(&path) {
+            Ok(parser) => {
+                let extensions: Vec<&str> =
+                    parser.metadata.extensions.iter().map(|s| s.as_str()).collect();
+                let path = path.clone();
+                registry.register(&extensions, move || {
+                    WasmParser::load(&path).map(|p| Box::new(p) as Box<dyn Parser>)
+                });
+            }
+            Err(err) => {
+                eprintln!("fmm: skipping wasm plugin {}: {err:#}", path.display());
+            }
+        }
+    }
+
+    Ok(())
+}
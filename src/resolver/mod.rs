@@ -0,0 +1,982 @@
+//! Cross-file module resolution.
+//!
+//! Every builtin parser extracts raw dependency strings (`./config`,
+//! `../database`, `github.com/jackc/pgx/v5/pgxpool`, `crate`, relative
+//! Python imports, ...) but nothing connects them back to the files that
+//! actually define them. This module takes a set of already-parsed files
+//! plus the paths they were read from and produces a directed graph: nodes
+//! are modules, edges are resolved dependency relationships, split into
+//! `Internal` (resolved to another scanned file) and `External` (an
+//! unresolved library/package) targets.
+//!
+//! Resolution is language-aware because each ecosystem's module system is
+//! different: TypeScript specifiers resolve against the importing file's
+//! directory with an extension/`index.*` fallback; Python relative imports
+//! resolve dot-prefixed packages against the package directory; Rust
+//! `crate`/`super`/`self` paths resolve against the module tree; Go import
+//! paths resolve against the module root declared in `go.mod`; Ruby
+//! `require_relative` paths resolve against the requiring file's directory,
+//! falling back to the nearest ancestor `lib/` directory.
+
+use anyhow::Result;
+use ignore::WalkBuilder;
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+
+use crate::parser::{Metadata, ParserRegistry};
+
+/// A single parsed file handed to the resolver: the path it was read from
+/// (relative to the scan root, matching how `dependencies` specifiers are
+/// expressed) and the metadata its language parser already extracted.
+#[derive(Debug, Clone)]
+pub struct ModuleFile {
+    pub path: PathBuf,
+    pub metadata: Metadata,
+    /// Language-specific custom fields from `ParseResult`, when richer
+    /// edges than `metadata.dependencies` are available (e.g. Rust's
+    /// fully-qualified `module_edges`).
+    pub custom_fields: Option<HashMap<String, serde_json::Value>>,
+}
+
+impl ModuleFile {
+    pub fn new(path: impl Into<PathBuf>, metadata: Metadata) -> Self {
+        Self {
+            path: path.into(),
+            metadata,
+            custom_fields: None,
+        }
+    }
+
+    pub fn with_custom_fields(
+        mut self,
+        custom_fields: Option<HashMap<String, serde_json::Value>>,
+    ) -> Self {
+        self.custom_fields = custom_fields;
+        self
+    }
+}
+
+/// Where a resolved dependency edge points.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum EdgeTarget {
+    /// Resolved to another file within the scanned set.
+    Internal(PathBuf),
+    /// Not resolvable to a scanned file — an external package/library,
+    /// kept as the raw specifier the parser reported.
+    External(String),
+}
+
+/// One resolved dependency edge, from the importing file to its target.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edge {
+    pub from: PathBuf,
+    pub target: EdgeTarget,
+}
+
+/// Knobs that need information beyond what a single file's metadata can
+/// provide. Everything defaults to "resolution disabled for that case",
+/// which just means the affected dependencies report as `External`.
+#[derive(Debug, Clone, Default)]
+pub struct ResolverConfig {
+    /// The Go module path from `go.mod`'s `module` directive (e.g.
+    /// `github.com/acme/widgets`), used to tell internal package imports
+    /// (`<module_path>/internal/util`) apart from external ones
+    /// (`github.com/other/pkg`). `None` reports every Go dependency as
+    /// external.
+    pub go_module_path: Option<String>,
+}
+
+/// Directories skipped when walking a tree for [`ModuleGraph::build`],
+/// beyond whatever `.gitignore`/`.fmmignore` already exclude — vendored and
+/// build-output directories that aren't always gitignored but are never
+/// meaningful dependency nodes.
+const SKIP_DIRS: &[&str] = &["target", "node_modules", "vendor", "dist", "build"];
+
+/// Directed graph of module dependencies across a set of parsed files.
+#[derive(Debug, Clone, Default)]
+pub struct ModuleGraph {
+    /// Every file the graph was built from, including ones with no edges
+    /// at all — needed so [`topological_order`](Self::topological_order)
+    /// can place isolated files rather than only ones that import or are
+    /// imported.
+    pub nodes: Vec<PathBuf>,
+    pub edges: Vec<Edge>,
+}
+
+impl ModuleGraph {
+    /// Walk `root` (skipping VCS metadata and `.gitignore`/`.fmmignore`
+    /// exclusions via the same walker [`cli::generate`](crate::cli::generate)
+    /// uses, plus [`SKIP_DIRS`]), parse every file with a registered
+    /// language parser, and resolve the dependency graph across the whole
+    /// tree. A `go.mod` at `root`, if present, supplies the module path Go
+    /// imports resolve against.
+    pub fn build(root: &Path) -> Result<ModuleGraph> {
+        let registry = ParserRegistry::with_builtins();
+        let walker = WalkBuilder::new(root).standard_filters(true).build();
+
+        let mut files = Vec::new();
+        for entry in walker {
+            let entry = entry?;
+            if !entry.file_type().is_some_and(|ft| ft.is_file()) {
+                continue;
+            }
+
+            let path = entry.path();
+            if is_in_skipped_dir(path) {
+                continue;
+            }
+
+            let Some(extension) = path.extension().and_then(|e| e.to_str()) else {
+                continue;
+            };
+            let Ok(mut parser) = registry.get_parser(extension) else {
+                continue;
+            };
+            let Ok(content) = std::fs::read_to_string(path) else {
+                continue;
+            };
+            let Ok(result) = parser.parse(&content) else {
+                continue;
+            };
+
+            let relative = path.strip_prefix(root).unwrap_or(path).to_path_buf();
+            files.push(ModuleFile::new(relative, result.metadata).with_custom_fields(result.custom_fields));
+        }
+
+        let config = ResolverConfig {
+            go_module_path: read_go_module_path(root),
+        };
+        Ok(build_graph(&files, &config))
+    }
+
+    pub fn internal_edges(&self) -> impl Iterator<Item = &Edge> {
+        self.edges
+            .iter()
+            .filter(|e| matches!(e.target, EdgeTarget::Internal(_)))
+    }
+
+    pub fn external_edges(&self) -> impl Iterator<Item = &Edge> {
+        self.edges
+            .iter()
+            .filter(|e| matches!(e.target, EdgeTarget::External(_)))
+    }
+
+    /// Files that depend on `path` — the reverse of `dependencies_of`.
+    /// Answers "what breaks if I change this file's exports".
+    pub fn dependents_of(&self, path: &Path) -> Vec<&Path> {
+        self.internal_edges()
+            .filter(|e| matches!(&e.target, EdgeTarget::Internal(to) if to.as_path() == path))
+            .map(|e| e.from.as_path())
+            .collect()
+    }
+
+    /// Files `path` depends on internally (external/unresolved targets are
+    /// omitted — there's no file to point at).
+    pub fn dependencies_of(&self, path: &Path) -> Vec<&Path> {
+        self.internal_edges()
+            .filter(|e| e.from == path)
+            .filter_map(|e| match &e.target {
+                EdgeTarget::Internal(to) => Some(to.as_path()),
+                EdgeTarget::External(_) => None,
+            })
+            .collect()
+    }
+
+    /// A dependency-respecting order over every node (Kahn's algorithm):
+    /// each file appears only after everything it depends on. Returns the
+    /// cycles found (via [`cycles`](Self::cycles)) instead of an order when
+    /// the graph isn't a DAG.
+    pub fn topological_order(&self) -> std::result::Result<Vec<PathBuf>, Vec<Vec<PathBuf>>> {
+        let mut in_degree: HashMap<&Path, usize> =
+            self.nodes.iter().map(|n| (n.as_path(), 0)).collect();
+        let mut adjacency: HashMap<&Path, Vec<&Path>> = HashMap::new();
+        for edge in self.internal_edges() {
+            if let EdgeTarget::Internal(to) = &edge.target {
+                adjacency
+                    .entry(edge.from.as_path())
+                    .or_default()
+                    .push(to.as_path());
+                in_degree.entry(edge.from.as_path()).or_insert(0);
+                *in_degree.entry(to.as_path()).or_insert(0) += 1;
+            }
+        }
+
+        let mut queue: Vec<&Path> = in_degree
+            .iter()
+            .filter(|(_, &degree)| degree == 0)
+            .map(|(&node, _)| node)
+            .collect();
+        queue.sort();
+
+        let mut order = Vec::new();
+        let mut i = 0;
+        while i < queue.len() {
+            let node = queue[i];
+            i += 1;
+            order.push(node.to_path_buf());
+
+            let mut newly_ready: Vec<&Path> = Vec::new();
+            if let Some(neighbors) = adjacency.get(node) {
+                for &next in neighbors {
+                    let degree = in_degree.get_mut(next).expect("every edge endpoint has an in-degree entry");
+                    *degree -= 1;
+                    if *degree == 0 {
+                        newly_ready.push(next);
+                    }
+                }
+            }
+            newly_ready.sort();
+            queue.extend(newly_ready);
+        }
+
+        if order.len() == in_degree.len() {
+            Ok(order)
+        } else {
+            Err(self.cycles())
+        }
+    }
+
+    /// Detect cycles among internal edges via DFS, returning each cycle as
+    /// the sequence of file paths that form it (the last path links back
+    /// to the first). A module graph can legitimately contain several
+    /// independent cycles, so this returns all of them rather than
+    /// stopping at the first.
+    pub fn cycles(&self) -> Vec<Vec<PathBuf>> {
+        let mut adjacency: HashMap<&Path, Vec<&Path>> = HashMap::new();
+        for edge in self.internal_edges() {
+            if let EdgeTarget::Internal(to) = &edge.target {
+                adjacency
+                    .entry(edge.from.as_path())
+                    .or_default()
+                    .push(to.as_path());
+            }
+        }
+
+        let mut cycles = Vec::new();
+        let mut visited = HashSet::new();
+        let mut on_stack = HashSet::new();
+        let mut stack = Vec::new();
+
+        let mut nodes: Vec<&Path> = adjacency.keys().copied().collect();
+        nodes.sort();
+        for node in nodes {
+            if !visited.contains(node) {
+                visit_for_cycles(
+                    node,
+                    &adjacency,
+                    &mut visited,
+                    &mut on_stack,
+                    &mut stack,
+                    &mut cycles,
+                );
+            }
+        }
+        cycles
+    }
+}
+
+fn visit_for_cycles<'a>(
+    node: &'a Path,
+    adjacency: &HashMap<&'a Path, Vec<&'a Path>>,
+    visited: &mut HashSet<&'a Path>,
+    on_stack: &mut HashSet<&'a Path>,
+    stack: &mut Vec<&'a Path>,
+    cycles: &mut Vec<Vec<PathBuf>>,
+) {
+    visited.insert(node);
+    on_stack.insert(node);
+    stack.push(node);
+
+    if let Some(neighbors) = adjacency.get(node) {
+        for &next in neighbors {
+            if on_stack.contains(next) {
+                if let Some(start) = stack.iter().position(|&p| p == next) {
+                    cycles.push(stack[start..].iter().map(|p| p.to_path_buf()).collect());
+                }
+            } else if !visited.contains(next) {
+                visit_for_cycles(next, adjacency, visited, on_stack, stack, cycles);
+            }
+        }
+    }
+
+    stack.pop();
+    on_stack.remove(node);
+}
+
+/// Build a module dependency graph from a set of already-parsed files.
+pub fn build_graph(files: &[ModuleFile], config: &ResolverConfig) -> ModuleGraph {
+    let known: HashSet<&Path> = files.iter().map(|f| f.path.as_path()).collect();
+    let mut edges = Vec::new();
+
+    for file in files {
+        match language_for(&file.path) {
+            Language::TypeScript => {
+                for specifier in &file.metadata.dependencies {
+                    let target = resolve_typescript(&file.path, specifier, &known);
+                    edges.push(Edge {
+                        from: file.path.clone(),
+                        target,
+                    });
+                }
+            }
+            Language::Python => {
+                for specifier in &file.metadata.dependencies {
+                    let target = resolve_python(&file.path, specifier, &known);
+                    edges.push(Edge {
+                        from: file.path.clone(),
+                        target,
+                    });
+                }
+            }
+            Language::Rust => {
+                for full_path in rust_module_edges(file) {
+                    let target = resolve_rust(&file.path, &full_path, &known);
+                    edges.push(Edge {
+                        from: file.path.clone(),
+                        target,
+                    });
+                }
+            }
+            Language::Go => {
+                for specifier in &file.metadata.dependencies {
+                    let target = resolve_go(specifier, config.go_module_path.as_deref(), &known);
+                    edges.push(Edge {
+                        from: file.path.clone(),
+                        target,
+                    });
+                }
+            }
+            Language::Ruby => {
+                for specifier in &file.metadata.dependencies {
+                    let target = resolve_ruby(&file.path, specifier, &known);
+                    edges.push(Edge {
+                        from: file.path.clone(),
+                        target,
+                    });
+                }
+            }
+            Language::Other => {
+                for specifier in &file.metadata.dependencies {
+                    edges.push(Edge {
+                        from: file.path.clone(),
+                        target: EdgeTarget::External(specifier.clone()),
+                    });
+                }
+            }
+        }
+    }
+
+    ModuleGraph {
+        nodes: files.iter().map(|f| f.path.clone()).collect(),
+        edges,
+    }
+}
+
+/// True if any component of `path` is one of [`SKIP_DIRS`].
+fn is_in_skipped_dir(path: &Path) -> bool {
+    path.components().any(|c| match c {
+        Component::Normal(name) => name
+            .to_str()
+            .is_some_and(|name| SKIP_DIRS.contains(&name)),
+        _ => false,
+    })
+}
+
+/// Reads the module path declared in `root/go.mod`'s `module` directive, if
+/// any — the same value `resolve_go` needs to recognize an import as
+/// belonging to this tree rather than an external dependency.
+fn read_go_module_path(root: &Path) -> Option<String> {
+    let content = std::fs::read_to_string(root.join("go.mod")).ok()?;
+    content.lines().find_map(|line| {
+        line.trim()
+            .strip_prefix("module ")
+            .map(|rest| rest.trim().to_string())
+    })
+}
+
+enum Language {
+    TypeScript,
+    Python,
+    Rust,
+    Go,
+    Ruby,
+    Other,
+}
+
+fn language_for(path: &Path) -> Language {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("ts") | Some("tsx") | Some("js") | Some("jsx") => Language::TypeScript,
+        Some("py") => Language::Python,
+        Some("rs") => Language::Rust,
+        Some("go") => Language::Go,
+        Some("rb") => Language::Ruby,
+        _ => Language::Other,
+    }
+}
+
+/// Collapse `..`/`.` components so a joined path matches how scanned paths
+/// are stored (e.g. `src/a/../b.ts` -> `src/b.ts`).
+fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::ParentDir => {
+                out.pop();
+            }
+            Component::CurDir => {}
+            other => out.push(other.as_os_str()),
+        }
+    }
+    out
+}
+
+const TS_EXTENSIONS: &[&str] = &["ts", "tsx", "js", "jsx"];
+
+/// Resolve a TypeScript/JavaScript relative specifier (`./foo`,
+/// `../bar/baz`) against the importing file's directory, trying each
+/// source extension and then `index.*` inside the specifier as a
+/// directory, mirroring Node's resolution fallback order.
+fn resolve_typescript(from: &Path, specifier: &str, known: &HashSet<&Path>) -> EdgeTarget {
+    let Some(dir) = from.parent() else {
+        return EdgeTarget::External(specifier.to_string());
+    };
+    let base = normalize(&dir.join(specifier));
+
+    if known.contains(base.as_path()) {
+        return EdgeTarget::Internal(base);
+    }
+    for ext in TS_EXTENSIONS {
+        let candidate = base.with_extension(ext);
+        if known.contains(candidate.as_path()) {
+            return EdgeTarget::Internal(candidate);
+        }
+    }
+    for ext in TS_EXTENSIONS {
+        let candidate = base.join(format!("index.{ext}"));
+        if known.contains(candidate.as_path()) {
+            return EdgeTarget::Internal(candidate);
+        }
+    }
+    EdgeTarget::External(specifier.to_string())
+}
+
+/// Resolve a Python relative import (`.api`, `..pkg.mod`) against the
+/// importing file's own package directory: each leading dot past the
+/// first steps up one more parent directory, and the remaining
+/// dot-separated segments are joined onto it as a path.
+fn resolve_python(from: &Path, specifier: &str, known: &HashSet<&Path>) -> EdgeTarget {
+    let dots = specifier.chars().take_while(|&c| c == '.').count();
+    if dots == 0 {
+        return EdgeTarget::External(specifier.to_string());
+    }
+    let remainder = &specifier[dots..];
+
+    let Some(mut dir) = from.parent().map(Path::to_path_buf) else {
+        return EdgeTarget::External(specifier.to_string());
+    };
+    for _ in 1..dots {
+        if !dir.pop() {
+            return EdgeTarget::External(specifier.to_string());
+        }
+    }
+
+    let base = if remainder.is_empty() {
+        dir
+    } else {
+        let mut base = dir;
+        for segment in remainder.split('.') {
+            base.push(segment);
+        }
+        base
+    };
+
+    let module_file = base.with_extension("py");
+    if known.contains(module_file.as_path()) {
+        return EdgeTarget::Internal(module_file);
+    }
+    let package_init = base.join("__init__.py");
+    if known.contains(package_init.as_path()) {
+        return EdgeTarget::Internal(package_init);
+    }
+    EdgeTarget::External(specifier.to_string())
+}
+
+/// Pull Rust's fully-qualified `crate`/`self`/`super` use paths out of the
+/// `module_edges` custom field `RustParser` reports, falling back to the
+/// bare `crate`/`super` roots in `metadata.dependencies` (still useful for
+/// detecting "this file has intra-crate deps", just not resolvable to a
+/// specific target) when the custom field isn't present.
+fn rust_module_edges(file: &ModuleFile) -> Vec<String> {
+    let from_custom = file
+        .custom_fields
+        .as_ref()
+        .and_then(|fields| fields.get("module_edges"))
+        .and_then(|value| value.as_array())
+        .map(|entries| {
+            entries
+                .iter()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect::<Vec<_>>()
+        });
+
+    from_custom.unwrap_or_else(|| file.metadata.dependencies.clone())
+}
+
+/// Resolve a fully-qualified Rust use path (`crate::config::Config`,
+/// `super::helpers::util`, `self::inner`) against the module tree. Without
+/// a crate manifest to anchor on exactly, this uses a pragmatic
+/// heuristic: `self` resolves from the importing file's own directory,
+/// `super` steps up one directory from there, and `crate` anchors at the
+/// nearest ancestor directory literally named `src` (falling back to the
+/// file's own directory if there isn't one). Each remaining segment is
+/// then tried both as `foo.rs` and `foo/mod.rs`, the two on-disk shapes a
+/// Rust module can take, and — since the final segment is often an item
+/// name rather than a module — both with and without that last segment.
+fn resolve_rust(from: &Path, full_path: &str, known: &HashSet<&Path>) -> EdgeTarget {
+    let mut segments = full_path.split("::");
+    let Some(root) = segments.next() else {
+        return EdgeTarget::External(full_path.to_string());
+    };
+
+    let base_dir = match root {
+        "self" => from.parent().map(Path::to_path_buf).unwrap_or_default(),
+        "super" => from
+            .parent()
+            .and_then(Path::parent)
+            .map(Path::to_path_buf)
+            .unwrap_or_default(),
+        "crate" => crate_root_dir(from),
+        _ => return EdgeTarget::External(full_path.to_string()),
+    };
+
+    let rest: Vec<&str> = segments.collect();
+    if rest.is_empty() {
+        return EdgeTarget::External(full_path.to_string());
+    }
+
+    // `crate::foo::Bar` and `crate::foo::bar::Baz` both point at module
+    // `foo`/`foo::bar`; the final segment is often the item name, not a
+    // module, so try resolving with and without the last segment.
+    for drop_last in [false, true] {
+        let module_segments = if drop_last && rest.len() > 1 {
+            &rest[..rest.len() - 1]
+        } else {
+            &rest[..]
+        };
+        let mut module_path = base_dir.clone();
+        for segment in module_segments {
+            module_path.push(segment);
+        }
+
+        let as_file = module_path.with_extension("rs");
+        if known.contains(as_file.as_path()) {
+            return EdgeTarget::Internal(as_file);
+        }
+        let as_mod = module_path.join("mod.rs");
+        if known.contains(as_mod.as_path()) {
+            return EdgeTarget::Internal(as_mod);
+        }
+    }
+
+    EdgeTarget::External(full_path.to_string())
+}
+
+/// Walk up from `from`'s own directory to the nearest ancestor literally
+/// named `src`, the conventional crate root. Falls back to the file's own
+/// directory when no such ancestor exists.
+fn crate_root_dir(from: &Path) -> PathBuf {
+    let own_dir = from.parent().map(Path::to_path_buf).unwrap_or_default();
+    let mut dir = own_dir.clone();
+    loop {
+        if dir.file_name().and_then(|n| n.to_str()) == Some("src") {
+            return dir;
+        }
+        if !dir.pop() {
+            return own_dir;
+        }
+    }
+}
+
+/// Resolve a Go import path against the module root declared in `go.mod`:
+/// anything prefixed by the module path is internal, resolved by
+/// replacing that prefix with the scan root and treating the result as a
+/// package directory; everything else (stdlib or a third-party module) is
+/// external.
+fn resolve_go(import_path: &str, module_path: Option<&str>, known: &HashSet<&Path>) -> EdgeTarget {
+    let Some(module_path) = module_path else {
+        return EdgeTarget::External(import_path.to_string());
+    };
+
+    let relative = match import_path.strip_prefix(module_path) {
+        Some(rest) => rest.trim_start_matches('/'),
+        None => return EdgeTarget::External(import_path.to_string()),
+    };
+
+    let package_dir: PathBuf = relative.split('/').collect();
+    if known.iter().any(|p| p.starts_with(&package_dir)) {
+        return EdgeTarget::Internal(package_dir);
+    }
+    EdgeTarget::External(import_path.to_string())
+}
+
+/// Resolve a Ruby `require_relative` path (`config`, `lib/helpers`) against
+/// the requiring file's own directory, appending `.rb`. Ruby parsers report
+/// these without a leading `./`, so any specifier is tried as relative.
+/// Falls back to the nearest ancestor directory literally named `lib`, the
+/// conventional Ruby load-path root, for specifiers expressed from there
+/// instead of from the requiring file.
+fn resolve_ruby(from: &Path, specifier: &str, known: &HashSet<&Path>) -> EdgeTarget {
+    let Some(dir) = from.parent() else {
+        return EdgeTarget::External(specifier.to_string());
+    };
+
+    let candidate = normalize(&dir.join(specifier)).with_extension("rb");
+    if known.contains(candidate.as_path()) {
+        return EdgeTarget::Internal(candidate);
+    }
+
+    if let Some(lib_dir) = nearest_lib_dir(from) {
+        let lib_candidate = normalize(&lib_dir.join(specifier)).with_extension("rb");
+        if known.contains(lib_candidate.as_path()) {
+            return EdgeTarget::Internal(lib_candidate);
+        }
+    }
+
+    EdgeTarget::External(specifier.to_string())
+}
+
+/// Walk up from `from`'s own directory to the nearest ancestor literally
+/// named `lib`, Ruby's conventional load-path root.
+fn nearest_lib_dir(from: &Path) -> Option<PathBuf> {
+    let mut dir = from.parent()?.to_path_buf();
+    loop {
+        if dir.file_name().and_then(|n| n.to_str()) == Some("lib") {
+            return Some(dir);
+        }
+        if !dir.pop() {
+            return None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Metadata;
+
+    fn metadata_with_deps(deps: &[&str]) -> Metadata {
+        Metadata {
+            exports: Vec::new(),
+            imports: Vec::new(),
+            dependencies: deps.iter().map(|s| s.to_string()).collect(),
+            loc: 0,
+            re_exports: Vec::new(),
+            test_loc: 0,
+            symbols: Vec::new(),
+            diagnostics: crate::parser::Diagnostics::default(),
+        }
+    }
+
+    #[test]
+    fn resolves_typescript_relative_import() {
+        let files = vec![
+            ModuleFile::new("src/index.ts", metadata_with_deps(&["./config"])),
+            ModuleFile::new("src/config.ts", metadata_with_deps(&[])),
+        ];
+        let graph = build_graph(&files, &ResolverConfig::default());
+        let edge = &graph.edges[0];
+        assert_eq!(edge.target, EdgeTarget::Internal(PathBuf::from("src/config.ts")));
+    }
+
+    #[test]
+    fn resolves_typescript_index_fallback() {
+        let files = vec![
+            ModuleFile::new("src/index.ts", metadata_with_deps(&["./utils"])),
+            ModuleFile::new("src/utils/index.ts", metadata_with_deps(&[])),
+        ];
+        let graph = build_graph(&files, &ResolverConfig::default());
+        assert_eq!(
+            graph.edges[0].target,
+            EdgeTarget::Internal(PathBuf::from("src/utils/index.ts"))
+        );
+    }
+
+    #[test]
+    fn typescript_unresolved_specifier_is_external() {
+        let files = vec![ModuleFile::new(
+            "src/index.ts",
+            metadata_with_deps(&["./missing"]),
+        )];
+        let graph = build_graph(&files, &ResolverConfig::default());
+        assert_eq!(
+            graph.edges[0].target,
+            EdgeTarget::External("./missing".to_string())
+        );
+    }
+
+    #[test]
+    fn resolves_python_relative_import() {
+        let files = vec![
+            ModuleFile::new("pkg/__init__.py", metadata_with_deps(&["._api"])),
+            ModuleFile::new("pkg/_api.py", metadata_with_deps(&[])),
+        ];
+        let graph = build_graph(&files, &ResolverConfig::default());
+        assert_eq!(
+            graph.edges[0].target,
+            EdgeTarget::Internal(PathBuf::from("pkg/_api.py"))
+        );
+    }
+
+    #[test]
+    fn resolves_python_parent_relative_import() {
+        let files = vec![
+            ModuleFile::new("pkg/sub/mod.py", metadata_with_deps(&["..shared"])),
+            ModuleFile::new("pkg/shared.py", metadata_with_deps(&[])),
+        ];
+        let graph = build_graph(&files, &ResolverConfig::default());
+        assert_eq!(
+            graph.edges[0].target,
+            EdgeTarget::Internal(PathBuf::from("pkg/shared.py"))
+        );
+    }
+
+    #[test]
+    fn resolves_rust_crate_path_via_module_edges() {
+        let mut custom_fields = HashMap::new();
+        custom_fields.insert(
+            "module_edges".to_string(),
+            serde_json::json!(["crate::config::Config"]),
+        );
+        let files = vec![
+            ModuleFile::new("src/main.rs", metadata_with_deps(&["crate"]))
+                .with_custom_fields(Some(custom_fields)),
+            ModuleFile::new("src/config.rs", metadata_with_deps(&[])),
+        ];
+        let graph = build_graph(&files, &ResolverConfig::default());
+        assert_eq!(
+            graph.edges[0].target,
+            EdgeTarget::Internal(PathBuf::from("src/config.rs"))
+        );
+    }
+
+    #[test]
+    fn resolves_rust_super_path() {
+        let mut custom_fields = HashMap::new();
+        custom_fields.insert(
+            "module_edges".to_string(),
+            serde_json::json!(["super::helpers::util"]),
+        );
+        let files = vec![
+            ModuleFile::new("src/nested/child.rs", metadata_with_deps(&["super"]))
+                .with_custom_fields(Some(custom_fields)),
+            ModuleFile::new("src/helpers.rs", metadata_with_deps(&[])),
+        ];
+        let graph = build_graph(&files, &ResolverConfig::default());
+        assert_eq!(
+            graph.edges[0].target,
+            EdgeTarget::Internal(PathBuf::from("src/helpers.rs"))
+        );
+    }
+
+    #[test]
+    fn resolves_go_import_within_module_root() {
+        let config = ResolverConfig {
+            go_module_path: Some("github.com/acme/widgets".to_string()),
+        };
+        let files = vec![
+            ModuleFile::new(
+                "main.go",
+                metadata_with_deps(&["github.com/acme/widgets/internal/util"]),
+            ),
+            ModuleFile::new("internal/util/util.go", metadata_with_deps(&[])),
+        ];
+        let graph = build_graph(&files, &config);
+        assert_eq!(
+            graph.edges[0].target,
+            EdgeTarget::Internal(PathBuf::from("internal/util"))
+        );
+    }
+
+    #[test]
+    fn go_external_dependency_without_module_path_configured() {
+        let files = vec![ModuleFile::new(
+            "main.go",
+            metadata_with_deps(&["github.com/jackc/pgx/v5/pgxpool"]),
+        )];
+        let graph = build_graph(&files, &ResolverConfig::default());
+        assert_eq!(
+            graph.edges[0].target,
+            EdgeTarget::External("github.com/jackc/pgx/v5/pgxpool".to_string())
+        );
+    }
+
+    #[test]
+    fn resolves_ruby_require_relative() {
+        let files = vec![
+            ModuleFile::new("app/models/user.rb", metadata_with_deps(&["config"])),
+            ModuleFile::new("app/models/config.rb", metadata_with_deps(&[])),
+        ];
+        let graph = build_graph(&files, &ResolverConfig::default());
+        assert_eq!(
+            graph.edges[0].target,
+            EdgeTarget::Internal(PathBuf::from("app/models/config.rb"))
+        );
+    }
+
+    #[test]
+    fn resolves_ruby_via_lib_convention() {
+        let files = vec![
+            ModuleFile::new(
+                "lib/myapp/service.rb",
+                metadata_with_deps(&["myapp/helpers"]),
+            ),
+            ModuleFile::new("lib/myapp/helpers.rb", metadata_with_deps(&[])),
+        ];
+        let graph = build_graph(&files, &ResolverConfig::default());
+        assert_eq!(
+            graph.edges[0].target,
+            EdgeTarget::Internal(PathBuf::from("lib/myapp/helpers.rb"))
+        );
+    }
+
+    #[test]
+    fn ruby_unresolved_require_is_external() {
+        let files = vec![ModuleFile::new(
+            "app/models/user.rb",
+            metadata_with_deps(&["missing_helper"]),
+        )];
+        let graph = build_graph(&files, &ResolverConfig::default());
+        assert_eq!(
+            graph.edges[0].target,
+            EdgeTarget::External("missing_helper".to_string())
+        );
+    }
+
+    #[test]
+    fn detects_a_cycle() {
+        let files = vec![
+            ModuleFile::new("src/a.ts", metadata_with_deps(&["./b"])),
+            ModuleFile::new("src/b.ts", metadata_with_deps(&["./a"])),
+        ];
+        let graph = build_graph(&files, &ResolverConfig::default());
+        let cycles = graph.cycles();
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].len(), 2);
+    }
+
+    #[test]
+    fn internal_and_external_edges_partition_correctly() {
+        let files = vec![
+            ModuleFile::new(
+                "src/index.ts",
+                metadata_with_deps(&["./config", "./missing"]),
+            ),
+            ModuleFile::new("src/config.ts", metadata_with_deps(&[])),
+        ];
+        let graph = build_graph(&files, &ResolverConfig::default());
+        assert_eq!(graph.internal_edges().count(), 1);
+        assert_eq!(graph.external_edges().count(), 1);
+    }
+
+    #[test]
+    fn dependents_of_and_dependencies_of_are_inverses() {
+        let files = vec![
+            ModuleFile::new("src/index.ts", metadata_with_deps(&["./config"])),
+            ModuleFile::new("src/config.ts", metadata_with_deps(&[])),
+        ];
+        let graph = build_graph(&files, &ResolverConfig::default());
+
+        assert_eq!(
+            graph.dependencies_of(Path::new("src/index.ts")),
+            vec![Path::new("src/config.ts")]
+        );
+        assert_eq!(
+            graph.dependents_of(Path::new("src/config.ts")),
+            vec![Path::new("src/index.ts")]
+        );
+        assert!(graph.dependents_of(Path::new("src/index.ts")).is_empty());
+    }
+
+    #[test]
+    fn topological_order_respects_dependencies() {
+        let files = vec![
+            ModuleFile::new("src/index.ts", metadata_with_deps(&["./config"])),
+            ModuleFile::new("src/config.ts", metadata_with_deps(&[])),
+        ];
+        let graph = build_graph(&files, &ResolverConfig::default());
+
+        let order = graph.topological_order().expect("graph is a DAG");
+        let index_pos = order.iter().position(|p| p == Path::new("src/index.ts")).unwrap();
+        let config_pos = order.iter().position(|p| p == Path::new("src/config.ts")).unwrap();
+        assert!(config_pos < index_pos);
+    }
+
+    #[test]
+    fn topological_order_reports_cycles_instead_of_an_order() {
+        let files = vec![
+            ModuleFile::new("src/a.ts", metadata_with_deps(&["./b"])),
+            ModuleFile::new("src/b.ts", metadata_with_deps(&["./a"])),
+        ];
+        let graph = build_graph(&files, &ResolverConfig::default());
+        let cycles = graph.topological_order().unwrap_err();
+        assert_eq!(cycles.len(), 1);
+    }
+
+    #[test]
+    fn build_walks_a_directory_and_resolves_internal_edges() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join("index.ts"),
+            "import { helper } from './helper';\n",
+        )
+        .unwrap();
+        std::fs::write(tmp.path().join("helper.ts"), "export function helper() {}\n").unwrap();
+
+        let graph = ModuleGraph::build(tmp.path()).unwrap();
+        assert!(graph.nodes.contains(&PathBuf::from("index.ts")));
+        assert!(graph.nodes.contains(&PathBuf::from("helper.ts")));
+        assert_eq!(
+            graph.dependencies_of(Path::new("index.ts")),
+            vec![Path::new("helper.ts")]
+        );
+    }
+
+    #[test]
+    fn build_skips_vendored_directories() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(tmp.path().join("node_modules")).unwrap();
+        std::fs::write(
+            tmp.path().join("node_modules").join("lib.ts"),
+            "export const x = 1;\n",
+        )
+        .unwrap();
+        std::fs::write(tmp.path().join("index.ts"), "export const y = 2;\n").unwrap();
+
+        let graph = ModuleGraph::build(tmp.path()).unwrap();
+        assert!(graph.nodes.contains(&PathBuf::from("index.ts")));
+        assert!(!graph
+            .nodes
+            .iter()
+            .any(|n| n.to_string_lossy().contains("node_modules")));
+    }
+
+    #[test]
+    fn read_go_module_path_reads_the_module_directive() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join("go.mod"),
+            "module github.com/acme/widgets\n\ngo 1.21\n",
+        )
+        .unwrap();
+        assert_eq!(
+            read_go_module_path(tmp.path()),
+            Some("github.com/acme/widgets".to_string())
+        );
+    }
+
+    #[test]
+    fn read_go_module_path_is_none_without_a_go_mod() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        assert_eq!(read_go_module_path(tmp.path()), None);
+    }
+}
@@ -0,0 +1,132 @@
+//! Table-driven parser conformance harness.
+//!
+//! Each case lives in `tests/fixtures/conformance/<lang>/<case>/` as an
+//! `input.<ext>` file plus (for the default mode) an `expected.json` holding
+//! the serialized `ParseResult.metadata` the parser should produce. A case
+//! opts into `error-tolerant` mode with a `mode.txt` file containing that
+//! string — instead of diffing metadata, the harness just asserts `parse`
+//! returns `Ok` on the (intentionally malformed) input.
+//!
+//! Run with `FMM_BLESS=1 cargo test --test fixture_conformance` to rewrite
+//! every `expected.json` from the parser's actual output, so adding a case
+//! is "drop an `input.<ext>`, bless it, read the diff" instead of hand-writing
+//! the expected metadata.
+
+use std::fs;
+use std::path::Path;
+
+use fmm::parser::builtin::cpp::CppParser;
+use fmm::parser::builtin::csharp::CSharpParser;
+use fmm::parser::builtin::go::GoParser;
+use fmm::parser::builtin::java::JavaParser;
+use fmm::parser::builtin::python::PythonParser;
+use fmm::parser::builtin::ruby::RubyParser;
+use fmm::parser::builtin::rust::RustParser;
+use fmm::parser::builtin::typescript::TypeScriptParser;
+use fmm::parser::Parser;
+
+/// Directory name under `tests/fixtures/conformance/` paired with the
+/// extension its `input.<ext>` file uses and a constructor for its parser.
+const LANGUAGES: &[(&str, &str, fn() -> Box<dyn Parser>)] = &[
+    ("rust", "rs", || Box::new(RustParser::new().unwrap())),
+    ("python", "py", || Box::new(PythonParser::new().unwrap())),
+    ("typescript", "ts", || Box::new(TypeScriptParser::new().unwrap())),
+    ("go", "go", || Box::new(GoParser::new().unwrap())),
+    ("java", "java", || Box::new(JavaParser::new().unwrap())),
+    ("cpp", "cpp", || Box::new(CppParser::new().unwrap())),
+    ("csharp", "cs", || Box::new(CSharpParser::new().unwrap())),
+    ("ruby", "rb", || Box::new(RubyParser::new().unwrap())),
+];
+
+#[test]
+fn fixture_conformance() {
+    let bless = std::env::var("FMM_BLESS").is_ok();
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/conformance");
+    let mut failures = Vec::new();
+    let mut ran = 0;
+
+    for (lang, extension, make_parser) in LANGUAGES {
+        let lang_dir = root.join(lang);
+        if !lang_dir.is_dir() {
+            continue;
+        }
+
+        for entry in fs::read_dir(&lang_dir).unwrap() {
+            let case_dir = entry.unwrap().path();
+            if !case_dir.is_dir() {
+                continue;
+            }
+            ran += 1;
+            if let Err(e) = run_case(&case_dir, extension, *make_parser, bless) {
+                failures.push(format!("{}: {}", case_dir.display(), e));
+            }
+        }
+    }
+
+    assert!(ran > 0, "no conformance fixtures found under {}", root.display());
+    assert!(
+        failures.is_empty(),
+        "{} fixture case(s) failed:\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}
+
+fn run_case(
+    case_dir: &Path,
+    extension: &str,
+    make_parser: fn() -> Box<dyn Parser>,
+    bless: bool,
+) -> Result<(), String> {
+    let input_path = case_dir.join(format!("input.{extension}"));
+    let source = fs::read_to_string(&input_path)
+        .map_err(|e| format!("failed to read {}: {e}", input_path.display()))?;
+
+    let mut parser = make_parser();
+    let mode = case_mode(case_dir);
+
+    if mode == Mode::ErrorTolerant {
+        return match parser.parse(&source) {
+            Ok(_) => Ok(()),
+            Err(e) => Err(format!("expected Ok on malformed input, got Err: {e}")),
+        };
+    }
+
+    let result = parser
+        .parse(&source)
+        .map_err(|e| format!("parse failed: {e}"))?;
+    let actual = serde_json::to_string_pretty(&result.metadata)
+        .map_err(|e| format!("failed to serialize metadata: {e}"))?;
+
+    let expected_path = case_dir.join("expected.json");
+    if bless {
+        fs::write(&expected_path, format!("{actual}\n"))
+            .map_err(|e| format!("failed to bless {}: {e}", expected_path.display()))?;
+        return Ok(());
+    }
+
+    let expected = fs::read_to_string(&expected_path)
+        .map_err(|e| format!("failed to read {}: {e}", expected_path.display()))?;
+    if expected.trim() != actual.trim() {
+        return Err(format!(
+            "metadata mismatch (run with FMM_BLESS=1 to update)\n--- expected\n{}\n--- actual\n{}",
+            expected.trim(),
+            actual.trim()
+        ));
+    }
+
+    Ok(())
+}
+
+#[derive(PartialEq)]
+enum Mode {
+    RunPass,
+    ErrorTolerant,
+}
+
+fn case_mode(case_dir: &Path) -> Mode {
+    match fs::read_to_string(case_dir.join("mode.txt")) {
+        Ok(contents) if contents.trim() == "error-tolerant" => Mode::ErrorTolerant,
+        _ => Mode::RunPass,
+    }
+}
@@ -0,0 +1,3 @@
+pub fn café() -> i32 {
+    0
+}